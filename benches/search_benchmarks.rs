@@ -1,5 +1,7 @@
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion};
-use probe_code::search::{perform_probe, SearchOptions};
+use probe_code::search::{
+    perform_probe, BinaryDetection, DedupMode, MatchScope, RankMode, ResultSort, SearchOptions,
+};
 use std::fs;
 use tempfile::TempDir;
 
@@ -355,15 +357,42 @@ fn benchmark_search_patterns(c: &mut Criterion) {
                     max_results: Some(100),
                     max_bytes: None,
                     max_tokens: None,
+                    tokenizer: None,
                     allow_tests: true,
                     no_merge: false,
                     merge_threshold: None,
+                    merge_respect_boundaries: true,
                     dry_run: false,
                     session: None,
                     timeout: 30,
                     question: None,
                     no_gitignore: false,
                     lsp: false,
+                    sort: ResultSort::Score,
+                    regex: false,
+                    match_scope: MatchScope::All,
+                    invert: false,
+                    kinds: None,
+                    recency_boost: false,
+                    recency_half_life_days: 14.0,
+                    context_symbols: false,
+                    context_symbols_depth: 5,
+                    explain: false,
+                    use_index: false,
+                    rank_mode: RankMode::Block,
+                    within: None,
+                    changed_since: None,
+                    tracked_only: false,
+                    rerank_top_k: 50,
+                    binary_detection: BinaryDetection::NullByte,
+                    force_text_extensions: &[],
+                    search_archives: false,
+                    encoding: None,
+                    min_score: None,
+                    follow_symlinks: false,
+                    ranking_profile: None,
+                    dedup: DedupMode::None,
+                    extra_roots: &[],
                 };
 
                 black_box(perform_probe(&options).unwrap())
@@ -399,15 +428,42 @@ fn benchmark_result_limits(c: &mut Criterion) {
                     max_results: Some(limit),
                     max_bytes: None,
                     max_tokens: None,
+                    tokenizer: None,
                     allow_tests: true,
                     no_merge: false,
                     merge_threshold: None,
+                    merge_respect_boundaries: true,
                     dry_run: false,
                     session: None,
                     timeout: 30,
                     question: None,
                     no_gitignore: false,
                     lsp: false,
+                    sort: ResultSort::Score,
+                    regex: false,
+                    match_scope: MatchScope::All,
+                    invert: false,
+                    kinds: None,
+                    recency_boost: false,
+                    recency_half_life_days: 14.0,
+                    context_symbols: false,
+                    context_symbols_depth: 5,
+                    explain: false,
+                    use_index: false,
+                    rank_mode: RankMode::Block,
+                    within: None,
+                    changed_since: None,
+                    tracked_only: false,
+                    rerank_top_k: 50,
+                    binary_detection: BinaryDetection::NullByte,
+                    force_text_extensions: &[],
+                    search_archives: false,
+                    encoding: None,
+                    min_score: None,
+                    follow_symlinks: false,
+                    ranking_profile: None,
+                    dedup: DedupMode::None,
+                    extra_roots: &[],
                 };
 
                 black_box(perform_probe(&options).unwrap())
@@ -447,15 +503,42 @@ fn benchmark_search_options(c: &mut Criterion) {
                         max_results: Some(50),
                         max_bytes: None,
                         max_tokens: None,
+                        tokenizer: None,
                         allow_tests: true,
                         no_merge: false,
                         merge_threshold: None,
+                        merge_respect_boundaries: true,
                         dry_run: false,
                         session: None,
                         timeout: 30,
                         question: None,
                         no_gitignore: false,
                         lsp: false,
+                        sort: ResultSort::Score,
+                        regex: false,
+                        match_scope: MatchScope::All,
+                        invert: false,
+                        kinds: None,
+                        recency_boost: false,
+                        recency_half_life_days: 14.0,
+                        context_symbols: false,
+                        context_symbols_depth: 5,
+                        explain: false,
+                        use_index: false,
+                        rank_mode: RankMode::Block,
+                        within: None,
+                        changed_since: None,
+                        tracked_only: false,
+                        rerank_top_k: 50,
+                        binary_detection: BinaryDetection::NullByte,
+                        force_text_extensions: &[],
+                        search_archives: false,
+                        encoding: None,
+                        min_score: None,
+                        follow_symlinks: false,
+                        ranking_profile: None,
+                        dedup: DedupMode::None,
+                        extra_roots: &[],
                     };
 
                     black_box(perform_probe(&options).unwrap())
@@ -484,15 +567,42 @@ fn benchmark_search_options(c: &mut Criterion) {
                     max_results: Some(50),
                     max_bytes: None,
                     max_tokens: None,
+                    tokenizer: None,
                     allow_tests: true,
                     no_merge: false,
                     merge_threshold: None,
+                    merge_respect_boundaries: true,
                     dry_run: false,
                     session: None,
                     timeout: 30,
                     question: None,
                     no_gitignore: false,
                     lsp: false,
+                    sort: ResultSort::Score,
+                    regex: false,
+                    match_scope: MatchScope::All,
+                    invert: false,
+                    kinds: None,
+                    recency_boost: false,
+                    recency_half_life_days: 14.0,
+                    context_symbols: false,
+                    context_symbols_depth: 5,
+                    explain: false,
+                    use_index: false,
+                    rank_mode: RankMode::Block,
+                    within: None,
+                    changed_since: None,
+                    tracked_only: false,
+                    rerank_top_k: 50,
+                    binary_detection: BinaryDetection::NullByte,
+                    force_text_extensions: &[],
+                    search_archives: false,
+                    encoding: None,
+                    min_score: None,
+                    follow_symlinks: false,
+                    ranking_profile: None,
+                    dedup: DedupMode::None,
+                    extra_roots: &[],
                 };
 
                 black_box(perform_probe(&options).unwrap())
@@ -519,15 +629,42 @@ fn benchmark_search_options(c: &mut Criterion) {
                     max_results: Some(50),
                     max_bytes: None,
                     max_tokens: None,
+                    tokenizer: None,
                     allow_tests: true,
                     no_merge: false,
                     merge_threshold: None,
+                    merge_respect_boundaries: true,
                     dry_run: false,
                     session: None,
                     timeout: 30,
                     question: None,
                     no_gitignore: false,
                     lsp: false,
+                    sort: ResultSort::Score,
+                    regex: false,
+                    match_scope: MatchScope::All,
+                    invert: false,
+                    kinds: None,
+                    recency_boost: false,
+                    recency_half_life_days: 14.0,
+                    context_symbols: false,
+                    context_symbols_depth: 5,
+                    explain: false,
+                    use_index: false,
+                    rank_mode: RankMode::Block,
+                    within: None,
+                    changed_since: None,
+                    tracked_only: false,
+                    rerank_top_k: 50,
+                    binary_detection: BinaryDetection::NullByte,
+                    force_text_extensions: &[],
+                    search_archives: false,
+                    encoding: None,
+                    min_score: None,
+                    follow_symlinks: false,
+                    ranking_profile: None,
+                    dedup: DedupMode::None,
+                    extra_roots: &[],
                 };
 
                 black_box(perform_probe(&options).unwrap())
@@ -575,15 +712,42 @@ fn benchmark_query_complexity(c: &mut Criterion) {
                         max_results: Some(100),
                         max_bytes: None,
                         max_tokens: None,
+                        tokenizer: None,
                         allow_tests: true,
                         no_merge: false,
                         merge_threshold: None,
+                        merge_respect_boundaries: true,
                         dry_run: false,
                         session: None,
                         timeout: 30,
                         question: None,
                         no_gitignore: false,
                         lsp: false,
+                        sort: ResultSort::Score,
+                        regex: false,
+                        match_scope: MatchScope::All,
+                        invert: false,
+                        kinds: None,
+                        recency_boost: false,
+                        recency_half_life_days: 14.0,
+                        context_symbols: false,
+                        context_symbols_depth: 5,
+                        explain: false,
+                        use_index: false,
+                        rank_mode: RankMode::Block,
+                        within: None,
+                        changed_since: None,
+                        tracked_only: false,
+                        rerank_top_k: 50,
+                        binary_detection: BinaryDetection::NullByte,
+                        force_text_extensions: &[],
+                        search_archives: false,
+                        encoding: None,
+                        min_score: None,
+                        follow_symlinks: false,
+                        ranking_profile: None,
+                        dedup: DedupMode::None,
+                        extra_roots: &[],
                     };
 
                     black_box(perform_probe(&options).unwrap())
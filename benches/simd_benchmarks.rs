@@ -1,7 +1,7 @@
 use ahash::AHashMap as HashMap;
 use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
 use probe_code::ranking::{
-    rank_documents, rank_documents_simd, rank_documents_simd_simple, RankingParams,
+    rank_documents, rank_documents_simd, rank_documents_simd_simple, RankingParams, RankingProfile,
 };
 use probe_code::simd_ranking::SparseVector;
 use rand::prelude::*;
@@ -154,6 +154,7 @@ fn bench_ranking_synthetic(c: &mut Criterion) {
                 documents: &doc_refs,
                 query,
                 pre_tokenized: None,
+                profile: &RankingProfile::default(),
             };
 
             group.throughput(Throughput::Elements(num_docs as u64));
@@ -204,6 +205,7 @@ fn bench_ranking_realistic(c: &mut Criterion) {
                 documents: &doc_refs,
                 query,
                 pre_tokenized: None,
+                profile: &RankingProfile::default(),
             };
 
             group.throughput(Throughput::Elements(num_docs as u64));
@@ -245,6 +247,7 @@ fn bench_memory_efficiency(c: &mut Criterion) {
         documents: &doc_refs,
         query,
         pre_tokenized: None,
+        profile: &RankingProfile::default(),
     };
 
     group.bench_function("traditional_memory", |b| {
@@ -292,6 +295,7 @@ fn bench_query_complexity(c: &mut Criterion) {
             documents: &doc_refs,
             query,
             pre_tokenized: None,
+            profile: &RankingProfile::default(),
         };
 
         group.bench_with_input(
@@ -335,6 +339,7 @@ fn bench_scalability(c: &mut Criterion) {
             documents: &doc_refs,
             query,
             pre_tokenized: None,
+            profile: &RankingProfile::default(),
         };
 
         group.throughput(Throughput::Elements(num_docs as u64));
@@ -13,9 +13,11 @@ use crate::pid_lock::PidLock;
 #[cfg(unix)]
 use crate::process_group::ProcessGroup;
 use crate::protocol::{
-    parse_call_hierarchy_from_lsp, CallHierarchyItem, CallHierarchyResult, DaemonRequest,
-    DaemonResponse, DaemonStatus, DocumentSymbol, HoverContent, IndexingQueueInfo, LanguageInfo,
-    Location, MessageCodec, PoolStatus, Position, Range, SymbolInformation,
+    into_stream_frames, parse_call_hierarchy_from_lsp, CallHierarchyCall, CallHierarchyItem,
+    CallHierarchyNode, CallHierarchyResult, DaemonRequest, DaemonResponse, DaemonStatus,
+    DocumentHighlight, DocumentHighlightKind, DocumentSymbol, FileEdits, HoverContent,
+    HoverPosition, IndexingQueueInfo, LanguageInfo, Location, MessageCodec, PoolStatus, Position,
+    Range, RelatedGroup, RelatedItem, RelatedSource, RelationKind, SymbolInformation, TextEdit,
 };
 use crate::server_manager::SingleServerManager;
 use crate::socket_path::{get_default_socket_path, remove_socket_file};
@@ -68,6 +70,11 @@ const WRITE_TIMEOUT: Duration = Duration::from_secs(5);
 const IDLE_TIMEOUT: Duration = Duration::from_secs(30);
 const REQ_TIMEOUT: Duration = Duration::from_secs(25);
 const CONNECTION_TIMEOUT: Duration = Duration::from_secs(300); // 5 minutes
+
+// Deepest call hierarchy tree served in one request
+const MAX_CALL_HIERARCHY_TREE_DEPTH: u32 = 10;
+// Shortest interval between pushed indexing progress events
+const MIN_PROGRESS_INTERVAL_MS: u64 = 100;
 use futures::FutureExt;
 use tracing::{debug, error, info, warn};
 use tracing_subscriber::prelude::*;
@@ -282,6 +289,18 @@ pub struct LspDaemon {
     control_tx: Option<mpsc::Sender<ControlMsg>>,
 }
 
+tokio::task_local! {
+    /// Set when a lookup inside a `Related` or `CallHierarchy` request is answered from
+    /// the database
+    static SERVED_FROM_CACHE: std::cell::Cell<bool>;
+}
+
+/// Record a database hit for the `Related` or `CallHierarchy` lookup running on this task,
+/// if any
+fn note_cache_hit() {
+    let _ = SERVED_FROM_CACHE.try_with(|hit| hit.set(true));
+}
+
 // Bounded concurrency for background DB stores (default concurrency is 4)
 static ASYNC_STORE_SEM: OnceLock<Arc<Semaphore>> = OnceLock::new();
 
@@ -1427,6 +1446,59 @@ impl LspDaemon {
                 }
             };
 
+            // Progress subscriptions hold the connection and push events until they end
+            if let DaemonRequest::SubscribeIndexingProgress {
+                request_id,
+                interval_ms,
+            } = request
+            {
+                let cancelled = Arc::new(AtomicBool::new(false));
+                self.cancel_flags.insert(request_id, cancelled.clone());
+                let result = self
+                    .stream_indexing_progress(&mut writer, request_id, interval_ms, &cancelled)
+                    .await;
+                self.cancel_flags.remove(&request_id);
+                if let Err(e) = result {
+                    info!(
+                        "[{}] Progress subscription {} ended: {}",
+                        client_id, request_id, e
+                    );
+                    break;
+                }
+                continue;
+            }
+
+            // Deadline requests replace the default timeout with the client's own
+            let (request, deadline) = match request {
+                DaemonRequest::Deadline {
+                    request_id,
+                    timeout_ms,
+                    request,
+                } => (*request, Some((request_id, timeout_ms))),
+                request => (request, None),
+            };
+            // Stream requests run the wrapped request and deliver its result in chunks
+            let (request, stream) = match request {
+                DaemonRequest::Stream {
+                    request_id,
+                    chunk_size,
+                    request,
+                } => {
+                    let flag = Arc::new(AtomicBool::new(false));
+                    self.cancel_flags.insert(request_id, flag.clone());
+                    (*request, Some((request_id, chunk_size, flag)))
+                }
+                request => (request, None),
+            };
+
+            // Language of the server answering the request, to report timeouts and restarts
+            let request_language = request
+                .target_file()
+                .and_then(|file_path| self.detector.detect(file_path).ok());
+            let deadline_language = deadline.and(request_language);
+            let restarts_before =
+                request_language.map(|language| self.server_manager.restart_count(language));
+
             // Light request tracing (variant only)
             let req_name: &str = match &request {
                 DaemonRequest::IndexingStatus { .. } => "IndexingStatus",
@@ -1481,7 +1553,7 @@ impl LspDaemon {
                         Some(Duration::from_secs(timeout_secs.saturating_add(10)))
                     }
                 }
-                DaemonRequest::IndexExport { .. } => {
+                DaemonRequest::IndexExport { .. } | DaemonRequest::GraphExport { .. } => {
                     // Export can be large; allow extended time
                     Some(Duration::from_secs(600))
                 }
@@ -1491,8 +1563,10 @@ impl LspDaemon {
             // Increase or disable the outer timeout for heavy LSP operations like call hierarchy,
             // since the inner handler already uses a dedicated (longer) timeout.
             // Guard against panics inside request handling to avoid crashing the daemon
-            let response = if let Some(t) = match &request {
-                DaemonRequest::CallHierarchy { .. } => {
+            let default_timeout = match &request {
+                req @ (DaemonRequest::CallHierarchy { .. }
+                | DaemonRequest::CallHierarchyTree { .. }
+                | DaemonRequest::Related { .. }) => {
                     // Use a larger cap (or disable via env) for call hierarchy
                     if std::env::var("PROBE_LSP_NO_OUTER_TIMEOUT")
                         .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
@@ -1500,15 +1574,24 @@ impl LspDaemon {
                     {
                         None
                     } else {
-                        let secs = std::env::var("PROBE_LSP_CALL_OUTER_TIMEOUT_SECS")
-                            .ok()
-                            .and_then(|s| s.parse::<u64>().ok())
-                            .unwrap_or(90);
-                        Some(Duration::from_secs(secs))
+                        req.min_deadline()
                     }
                 }
-                DaemonRequest::IndexExport { .. } => Some(Duration::from_secs(600)),
+                DaemonRequest::IndexExport { .. } | DaemonRequest::GraphExport { .. } => {
+                    Some(Duration::from_secs(600))
+                }
                 _ => Some(REQ_TIMEOUT),
+            };
+            // A client deadline replaces the default, but call hierarchy keeps its cap
+            let deadline = deadline.map(|(request_id, timeout_ms)| {
+                let floor_ms = request
+                    .min_deadline()
+                    .map_or(0, |floor| floor.as_millis() as u64);
+                (request_id, timeout_ms.max(floor_ms))
+            });
+            let response = if let Some(t) = match deadline {
+                Some((_, timeout_ms)) => Some(Duration::from_millis(timeout_ms)),
+                None => default_timeout,
             } {
                 match timeout(t, async {
                     // catch_unwind to prevent process abort on handler panics
@@ -1536,17 +1619,36 @@ impl LspDaemon {
                 .await
                 {
                     Ok(resp) => resp,
-                    Err(_) => {
-                        warn!(
-                            "[{}] Request processing timed out after {}s",
-                            client_id,
-                            t.as_secs()
-                        );
-                        DaemonResponse::Error {
-                            request_id: Uuid::new_v4(),
-                            error: format!("Request timed out after {}s", t.as_secs()),
+                    Err(_) => match deadline {
+                        Some((request_id, timeout_ms)) => {
+                            warn!(
+                                "[{}] Request missed its {}ms deadline ({:?} server)",
+                                client_id, timeout_ms, deadline_language
+                            );
+                            if let Some(language) = deadline_language {
+                                let server_manager = self.server_manager.clone();
+                                tokio::spawn(async move {
+                                    server_manager.record_missed_deadline(language).await;
+                                });
+                            }
+                            DaemonResponse::Timeout {
+                                request_id,
+                                timeout_ms,
+                                language: deadline_language,
+                            }
                         }
-                    }
+                        None => {
+                            warn!(
+                                "[{}] Request processing timed out after {}s",
+                                client_id,
+                                t.as_secs()
+                            );
+                            DaemonResponse::Error {
+                                request_id: Uuid::new_v4(),
+                                error: format!("Request timed out after {}s", t.as_secs()),
+                            }
+                        }
+                    },
                 }
             } else {
                 // No timeout: run to completion
@@ -1571,6 +1673,20 @@ impl LspDaemon {
                     }
                 }
             };
+            // A failure that got its server restarted is reported as such, so the client
+            // can retry against the replacement
+            let response = match (response, request_language, restarts_before) {
+                (DaemonResponse::Error { request_id, error }, Some(language), Some(before))
+                    if self.server_manager.restart_count(language) > before =>
+                {
+                    DaemonResponse::ServerRestarted {
+                        request_id,
+                        language,
+                        error,
+                    }
+                }
+                (response, _, _) => response,
+            };
             let request_duration = request_start.elapsed();
 
             // Track request duration (keep only last 100)
@@ -1583,23 +1699,40 @@ impl LspDaemon {
             }
 
             // Track errors
-            if let DaemonResponse::Error { .. } = &response {
+            if let DaemonResponse::Error { .. }
+            | DaemonResponse::Timeout { .. }
+            | DaemonResponse::ServerRestarted { .. } = &response
+            {
                 *self.error_count.write().await += 1;
             }
 
-            // Send response with timeout
-            if let Err(e) = self.send_response(&mut writer, &response).await {
-                error!("[{}] Failed to send response: {}", client_id, e);
-                break; // Close connection on write errors
-            }
             // Light response tracing (variant)
             let resp_name: &str = match &response {
                 DaemonResponse::IndexingStatusResponse { .. } => "IndexingStatusResponse",
                 DaemonResponse::Logs { .. } => "Logs",
                 DaemonResponse::Connected { .. } => "Connected",
                 DaemonResponse::Error { .. } => "Error",
+                DaemonResponse::Timeout { .. } => "Timeout",
+                DaemonResponse::ServerRestarted { .. } => "ServerRestarted",
                 _ => "Other",
             };
+            let shutdown_requested = matches!(response, DaemonResponse::Shutdown { .. });
+
+            // Send response with timeout
+            let send_result = match stream {
+                Some((stream_id, chunk_size, cancelled)) => {
+                    let result = self
+                        .send_streamed_response(&mut writer, response, chunk_size, &cancelled)
+                        .await;
+                    self.cancel_flags.remove(&stream_id);
+                    result
+                }
+                None => self.send_response(&mut writer, &response).await,
+            };
+            if let Err(e) = send_result {
+                error!("[{}] Failed to send response: {}", client_id, e);
+                break; // Close connection on write errors
+            }
             tracing::debug!("[ipc] sent {}", resp_name);
             if ipc_trace || resp_name == "IndexingStatusResponse" {
                 info!(
@@ -1610,7 +1743,7 @@ impl LspDaemon {
             }
 
             // Check if shutdown was requested
-            if let DaemonResponse::Shutdown { .. } = response {
+            if shutdown_requested {
                 *self.shutdown.write().await = true;
                 break;
             }
@@ -1648,6 +1781,151 @@ impl LspDaemon {
         MessageCodec::write_framed(writer, &json_data, WRITE_TIMEOUT).await
     }
 
+    /// Send a response as `StreamChunk` frames followed by a `StreamEnd`. Once `cancelled`
+    /// is set, the remaining chunks are dropped and the end frame is marked cancelled.
+    async fn send_streamed_response(
+        &self,
+        writer: &mut crate::ipc::OwnedWriteHalf,
+        response: DaemonResponse,
+        chunk_size: usize,
+        cancelled: &AtomicBool,
+    ) -> Result<()> {
+        let mut chunks_sent = 0u32;
+        let mut items_sent = 0usize;
+        for frame in into_stream_frames(response, chunk_size) {
+            if let DaemonResponse::StreamChunk {
+                request_id, items, ..
+            } = &frame
+            {
+                if cancelled.load(Ordering::Relaxed) {
+                    info!(
+                        "Stream {} cancelled after {} chunks",
+                        request_id, chunks_sent
+                    );
+                    let end = DaemonResponse::StreamEnd {
+                        request_id: *request_id,
+                        chunks: chunks_sent,
+                        total_items: items_sent,
+                        cancelled: true,
+                        warnings: None,
+                    };
+                    return self.send_response(writer, &end).await;
+                }
+                chunks_sent += 1;
+                items_sent += items.len();
+            }
+            self.send_response(writer, &frame).await?;
+        }
+        Ok(())
+    }
+
+    /// Push an `IndexingProgressUpdate` every `interval_ms` until indexing completes or stops,
+    /// then an `IndexingProgressEnd`. A failed write means the subscriber went away.
+    async fn stream_indexing_progress(
+        &self,
+        writer: &mut crate::ipc::OwnedWriteHalf,
+        request_id: Uuid,
+        interval_ms: u64,
+        cancelled: &AtomicBool,
+    ) -> Result<()> {
+        use crate::indexing::ManagerStatus;
+        use crate::protocol::ProgressStreamEnd;
+
+        let mut ticker = tokio::time::interval(Duration::from_millis(
+            interval_ms.max(MIN_PROGRESS_INTERVAL_MS),
+        ));
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+
+        let mut events = 0u32;
+        let reason = loop {
+            ticker.tick().await;
+            if cancelled.load(Ordering::Relaxed) {
+                break ProgressStreamEnd::Cancelled;
+            }
+
+            let manager = { self.indexing_manager.lock().await.clone() };
+            let Some(manager) = manager else {
+                break if events == 0 {
+                    ProgressStreamEnd::NotRunning
+                } else {
+                    ProgressStreamEnd::Stopped
+                };
+            };
+
+            let status = manager.get_status().await;
+            let tracker = manager.get_progress_tracker();
+            let queue_empty = manager.get_queue_snapshot().await.total_items == 0;
+            let update = DaemonResponse::IndexingProgressUpdate {
+                request_id,
+                sequence: events,
+                event: Self::progress_event(&status, &tracker),
+            };
+            self.send_response(writer, &update).await?;
+            events += 1;
+
+            match status {
+                ManagerStatus::ShuttingDown | ManagerStatus::Shutdown | ManagerStatus::Error(_) => {
+                    break ProgressStreamEnd::Stopped;
+                }
+                _ if queue_empty && tracker.is_complete() => break ProgressStreamEnd::Completed,
+                _ => {}
+            }
+        };
+
+        debug!(
+            "Progress subscription {} ended after {} events: {:?}",
+            request_id, events, reason
+        );
+        let end = DaemonResponse::IndexingProgressEnd {
+            request_id,
+            events,
+            reason,
+        };
+        self.send_response(writer, &end).await
+    }
+
+    /// Build a progress event with rates, ETA and the per-language breakdown
+    fn progress_event(
+        status: &crate::indexing::ManagerStatus,
+        tracker: &crate::indexing::IndexingProgress,
+    ) -> crate::protocol::IndexingProgressEvent {
+        let metrics = tracker.get_metrics();
+        crate::protocol::IndexingProgressEvent {
+            manager_status: format!("{status:?}"),
+            progress: crate::protocol::IndexingProgressInfo {
+                total_files: metrics.total_files,
+                processed_files: metrics.processed_files,
+                failed_files: metrics.failed_files,
+                active_files: metrics.active_files,
+                skipped_files: metrics.skipped_files,
+                test_files_skipped: metrics.test_files_skipped,
+                deferred_files: metrics.deferred_files,
+                processed_bytes: metrics.processed_bytes,
+                symbols_extracted: metrics.symbols_extracted,
+                progress_ratio: metrics.progress_ratio,
+                files_per_second: metrics.files_per_second,
+                bytes_per_second: metrics.bytes_per_second,
+            },
+            active_workers: metrics.active_workers,
+            elapsed_seconds: metrics.elapsed_time.as_secs(),
+            eta_seconds: tracker.estimate_time_remaining().map(|eta| eta.as_secs()),
+            languages: tracker
+                .get_language_breakdown()
+                .into_iter()
+                .map(|(language, counts)| {
+                    (
+                        language,
+                        crate::protocol::LanguageProgressInfo {
+                            processed_files: counts.processed_files,
+                            failed_files: counts.failed_files,
+                            symbols_extracted: counts.symbols_extracted,
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
     // Clean up connections that have been idle for too long
     fn cleanup_stale_connections(&self) -> usize {
         // Make MAX_IDLE_TIME configurable via environment variable
@@ -1704,6 +1982,7 @@ impl LspDaemon {
         // Reduced logging noise - only log interesting requests
         match &request {
             DaemonRequest::CallHierarchy { .. }
+            | DaemonRequest::CallHierarchyTree { .. }
             | DaemonRequest::References { .. }
             | DaemonRequest::Definition { .. } => {
                 debug!(
@@ -1737,6 +2016,53 @@ impl LspDaemon {
                     error: format!("Edge audit failed: {}", e),
                 },
             },
+            DaemonRequest::CachePrune {
+                request_id,
+                workspace_path,
+                dry_run,
+            } => {
+                let workspace = match workspace_path {
+                    Some(p) => p,
+                    None => {
+                        std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."))
+                    }
+                };
+                let cache = match self
+                    .workspace_cache_router
+                    .cache_for_workspace(&workspace)
+                    .await
+                {
+                    Ok(cache) => cache,
+                    Err(e) => {
+                        return DaemonResponse::Error {
+                            request_id,
+                            error: format!("Failed to get cache for workspace: {}", e),
+                        }
+                    }
+                };
+                match cache
+                    .backend()
+                    .prune_stale_entries(&workspace, dry_run)
+                    .await
+                {
+                    Ok(result) => {
+                        info!(
+                            "Cache prune for {}: {} missing / {} changed files, {} symbols and {} edges {}",
+                            workspace.display(),
+                            result.missing_files,
+                            result.changed_files,
+                            result.symbols_removed,
+                            result.edges_removed,
+                            if dry_run { "would be removed" } else { "removed" }
+                        );
+                        DaemonResponse::CachePruned { request_id, result }
+                    }
+                    Err(e) => DaemonResponse::Error {
+                        request_id,
+                        error: format!("Cache prune failed: {}", e),
+                    },
+                }
+            }
             DaemonRequest::WorkspaceDbPath {
                 request_id,
                 workspace_path,
@@ -1959,14 +2285,25 @@ impl LspDaemon {
                     };
                 }
 
-                match self
-                    .handle_call_hierarchy(&file_path, line, column, workspace_hint)
-                    .await
-                {
+                let (outcome, cached) = SERVED_FROM_CACHE
+                    .scope(std::cell::Cell::new(false), async {
+                        let outcome = self
+                            .handle_call_hierarchy(&file_path, line, column, workspace_hint)
+                            .await;
+                        (outcome, SERVED_FROM_CACHE.with(|hit| hit.get()))
+                    })
+                    .await;
+                if cached {
+                    // Let an enclosing `Related` lookup see the hit as well
+                    note_cache_hit();
+                }
+
+                match outcome {
                     Ok(result) => DaemonResponse::CallHierarchy {
                         request_id,
                         result,
                         warnings: None,
+                        cached,
                     },
                     Err(e) => DaemonResponse::Error {
                         request_id,
@@ -1975,64 +2312,52 @@ impl LspDaemon {
                 }
             }
 
-            DaemonRequest::Status { request_id } => {
-                let server_stats = self.server_manager.get_stats().await;
-                let all_readiness = self.server_manager.get_all_readiness_status().await;
-
-                let pool_status: Vec<PoolStatus> = server_stats
-                    .into_iter()
-                    .map(|s| {
-                        // Consider a server "ready" if it's initialized (simplified without health monitoring)
-                        let is_ready = s.initialized;
-
-                        // Find readiness information for this language
-                        let readiness_info = all_readiness
-                            .iter()
-                            .find(|r| r.language == s.language)
-                            .cloned();
+            DaemonRequest::CallHierarchyTree {
+                request_id,
+                file_path,
+                line,
+                column,
+                max_depth,
+                workspace_hint,
+            } => {
+                info!(
+                    "Received DaemonRequest::CallHierarchyTree for {:?} at {}:{} with depth {} (request_id: {})",
+                    file_path, line, column, max_depth, request_id
+                );
 
-                        PoolStatus {
-                            language: s.language,
-                            ready_servers: if is_ready { 1 } else { 0 },
-                            busy_servers: 0, // No busy concept in single server model
-                            total_servers: 1,
-                            workspaces: s
-                                .workspaces
-                                .iter()
-                                .map(|w| safe_canonicalize(w).to_string_lossy().to_string())
-                                .collect(),
-                            uptime_secs: s.uptime.as_secs(),
-                            status: format!("{:?}", s.status),
-                            health_status: if is_ready {
-                                "healthy".to_string()
-                            } else {
-                                "initializing".to_string()
-                            },
-                            consecutive_failures: 0, // No failure tracking without health monitor
-                            circuit_breaker_open: false, // No circuit breaker
-                            readiness_info,
-                        }
-                    })
-                    .collect();
+                // Check if file should be excluded from LSP processing
+                if should_exclude_from_lsp(&file_path) {
+                    warn!(
+                        "Ignoring CallHierarchyTree request for excluded file: {:?} (build artifact/generated code)",
+                        file_path
+                    );
+                    return DaemonResponse::Error {
+                        request_id,
+                        error: "File is excluded from LSP processing (build artifact or generated code)".to_string(),
+                    };
+                }
 
-                DaemonResponse::Status {
-                    request_id,
-                    status: DaemonStatus {
-                        uptime_secs: self.start_time.elapsed().as_secs(),
-                        pools: pool_status,
-                        total_requests: *self.request_count.read().await,
-                        active_connections: self.connections.len(),
-                        lsp_inflight_current: self.server_manager.total_inflight() as u64,
-                        version: env!("CARGO_PKG_VERSION").to_string(),
-                        git_hash: env!("GIT_HASH").to_string(),
-                        build_date: env!("BUILD_DATE").to_string(),
-                        universal_cache_stats: None, // Universal cache layer removed
-                        // Add database health information (Priority 4)
-                        database_health: Some(self.get_database_health_summary().await),
+                match self
+                    .handle_call_hierarchy_tree(&file_path, line, column, max_depth, workspace_hint)
+                    .await
+                {
+                    Ok((root, warnings)) => DaemonResponse::CallHierarchyTree {
+                        request_id,
+                        root,
+                        warnings: (!warnings.is_empty()).then_some(warnings),
+                    },
+                    Err(e) => DaemonResponse::Error {
+                        request_id,
+                        error: e.to_string(),
                     },
                 }
             }
 
+            DaemonRequest::Status { request_id } => DaemonResponse::Status {
+                request_id,
+                status: self.daemon_status().await,
+            },
+
             DaemonRequest::Version { request_id } => {
                 // Lightweight: no DB, no server stats — safe during early boot
                 DaemonResponse::VersionInfo {
@@ -2142,6 +2467,51 @@ impl LspDaemon {
                 }
             }
 
+            DaemonRequest::ListServers { request_id } => DaemonResponse::ServerList {
+                request_id,
+                servers: self.server_manager.list_servers().await,
+            },
+
+            DaemonRequest::Metrics { request_id } => {
+                let status = self.daemon_status().await;
+                let cache = Self::cache_statistics();
+                let indexing_manager = self.indexing_manager.lock().await.clone();
+                let queue = match indexing_manager {
+                    Some(manager) => Some(manager.get_queue_metrics().await),
+                    None => None,
+                };
+                let resident_memory_bytes = self
+                    .process_monitor
+                    .get_process_stats(std::process::id())
+                    .await
+                    .ok()
+                    .map(|stats| stats.memory_mb * 1024 * 1024);
+
+                DaemonResponse::Metrics {
+                    request_id,
+                    text: crate::metrics::render_prometheus(&crate::metrics::MetricsSnapshot {
+                        status: &status,
+                        cache: &cache,
+                        queue: queue.as_ref(),
+                        resident_memory_bytes,
+                    }),
+                }
+            }
+
+            DaemonRequest::KillServer { request_id, id } => {
+                match self.server_manager.kill_server(&id).await {
+                    Ok(pid) => DaemonResponse::ServerKilled {
+                        request_id,
+                        id,
+                        pid,
+                    },
+                    Err(e) => DaemonResponse::Error {
+                        request_id,
+                        error: e.to_string(),
+                    },
+                }
+            }
+
             DaemonRequest::DbLockSnapshot { request_id } => {
                 // Try to get a cache adapter for current working directory
                 let current_dir = std::env::current_dir().unwrap_or_else(|_| std::env::temp_dir());
@@ -2204,35 +2574,9 @@ impl LspDaemon {
                 // Universal cache layer removed - return empty stats
                 info!("Cache stats request (universal cache removed)");
 
-                let legacy_stats = crate::protocol::CacheStatistics {
-                    hit_rate: 0.0,
-                    miss_rate: 1.0,
-                    total_entries: 0,
-                    total_size_bytes: 0,
-                    disk_size_bytes: 0,
-                    entries_per_file: std::collections::HashMap::new(),
-                    entries_per_language: std::collections::HashMap::new(),
-                    age_distribution: crate::protocol::AgeDistribution {
-                        entries_last_hour: 0,
-                        entries_last_day: 0,
-                        entries_last_week: 0,
-                        entries_last_month: 0,
-                        entries_older: 0,
-                    },
-                    most_accessed: Vec::new(),
-                    memory_usage: crate::protocol::MemoryUsage {
-                        in_memory_cache_bytes: 0,
-                        persistent_cache_bytes: 0,
-                        metadata_bytes: 0,
-                        index_bytes: 0,
-                    },
-                    per_workspace_stats: None,
-                    per_operation_totals: None,
-                };
-
                 DaemonResponse::CacheStats {
                     request_id,
-                    stats: legacy_stats,
+                    stats: Self::cache_statistics(),
                 }
             }
 
@@ -2364,6 +2708,21 @@ impl LspDaemon {
                 }
             }
 
+            DaemonRequest::PrioritizeIndexing {
+                request_id,
+                file_path,
+            } => match self.handle_prioritize_indexing(&file_path).await {
+                Ok(changed) => DaemonResponse::IndexingPrioritized {
+                    request_id,
+                    file_path,
+                    changed,
+                },
+                Err(e) => DaemonResponse::Error {
+                    request_id,
+                    error: e.to_string(),
+                },
+            },
+
             DaemonRequest::IndexingStatus { request_id } => {
                 let t_req = std::time::Instant::now();
                 info!("[status] IndexingStatus request received");
@@ -2474,6 +2833,8 @@ impl LspDaemon {
                                 failed_files: progress.failed_files,
                                 active_files: progress.active_files,
                                 skipped_files: progress.skipped_files,
+                                test_files_skipped: progress.test_files_skipped,
+                                deferred_files: progress.deferred_files,
                                 processed_bytes: progress.processed_bytes,
                                 symbols_extracted: progress.symbols_extracted,
                                 progress_ratio: 0.0,
@@ -2497,6 +2858,7 @@ impl LspDaemon {
                                     .as_secs(),
                             ),
                             snapshot_age_secs: Some(0),
+                            analysis_run_id: m.get_analysis_run_id().await,
                         }
                     } else {
                         crate::protocol::IndexingStatusInfo::idle()
@@ -2959,6 +3321,7 @@ impl LspDaemon {
                                 Ok(Some(locations)) => {
                                     info!("Database HIT for {} references at {}:{}:{}",
                                          symbol_name, absolute_file_path.display(), line, column);
+                                    note_cache_hit();
                                     return Ok(locations);
                                 }
                                 Ok(None) => {
@@ -3134,32 +3497,194 @@ impl LspDaemon {
                 }
             }
 
-            DaemonRequest::DocumentSymbols {
+            DaemonRequest::DocumentHighlight {
                 request_id,
                 file_path,
+                line,
+                column,
                 workspace_hint,
             } => {
                 info!(
-                    "Received DaemonRequest::DocumentSymbols for {:?} (request_id: {})",
-                    file_path, request_id
+                    "Received DaemonRequest::DocumentHighlight for {:?} at {}:{} (request_id: {})",
+                    file_path, line, column, request_id
                 );
-                match self
-                    .handle_document_symbols(&file_path, workspace_hint)
-                    .await
-                {
-                    Ok(symbols) => DaemonResponse::DocumentSymbols {
-                        request_id,
-                        symbols,
-                        warnings: None,
-                    },
-                    Err(e) => DaemonResponse::Error {
+
+                // Check if file should be excluded from LSP processing
+                if should_exclude_from_lsp(&file_path) {
+                    warn!(
+                        "Ignoring DocumentHighlight request for excluded file: {:?} (build artifact/generated code)",
+                        file_path
+                    );
+                    return DaemonResponse::Error {
                         request_id,
-                        error: e.to_string(),
-                    },
+                        error: "File is excluded from LSP processing (build artifact or generated code)".to_string(),
+                    };
                 }
-            }
 
-            DaemonRequest::WorkspaceSymbols {
+                let absolute_file_path = safe_canonicalize(&file_path);
+
+                let result = async {
+                    let language = self.detector.detect(&absolute_file_path)?;
+                    if language == Language::Unknown {
+                        return Err(anyhow!(
+                            "Unknown language for file: {:?}",
+                            absolute_file_path
+                        ));
+                    }
+
+                    let _workspace_root = {
+                        let mut resolver = self.workspace_resolver.lock().await;
+                        resolver.resolve_workspace(&absolute_file_path, workspace_hint)?
+                    };
+
+                    let lsp_workspace_root =
+                        workspace_utils::resolve_lsp_workspace_root(language, &absolute_file_path)?;
+
+                    let server_instance = self
+                        .server_manager
+                        .ensure_workspace_registered(language, lsp_workspace_root)
+                        .await?;
+
+                    let server = server_instance.lock().await;
+                    let response_json = server
+                        .server
+                        .document_highlight(&absolute_file_path, line, column)
+                        .await?;
+
+                    Self::parse_document_highlight_response(&response_json)
+                }
+                .await;
+
+                match result {
+                    Ok(highlights) => DaemonResponse::DocumentHighlight {
+                        request_id,
+                        highlights,
+                        warnings: None,
+                    },
+                    Err(e) => DaemonResponse::Error {
+                        request_id,
+                        error: e.to_string(),
+                    },
+                }
+            }
+
+            DaemonRequest::Rename {
+                request_id,
+                file_path,
+                line,
+                column,
+                new_name,
+                workspace_hint,
+            } => {
+                info!(
+                    "Received DaemonRequest::Rename for {:?} at {}:{} to '{}' (request_id: {})",
+                    file_path, line, column, new_name, request_id
+                );
+
+                // Check if file should be excluded from LSP processing
+                if should_exclude_from_lsp(&file_path) {
+                    warn!(
+                        "Ignoring Rename request for excluded file: {:?} (build artifact/generated code)",
+                        file_path
+                    );
+                    return DaemonResponse::Error {
+                        request_id,
+                        error: "File is excluded from LSP processing (build artifact or generated code)".to_string(),
+                    };
+                }
+
+                let absolute_file_path = safe_canonicalize(&file_path);
+
+                let result = async {
+                    let language = self.detector.detect(&absolute_file_path)?;
+                    if language == Language::Unknown {
+                        return Err(anyhow!(
+                            "Unknown language for file: {:?}",
+                            absolute_file_path
+                        ));
+                    }
+
+                    let _workspace_root = {
+                        let mut resolver = self.workspace_resolver.lock().await;
+                        resolver.resolve_workspace(&absolute_file_path, workspace_hint)?
+                    };
+
+                    let lsp_workspace_root =
+                        workspace_utils::resolve_lsp_workspace_root(language, &absolute_file_path)?;
+
+                    let server_instance = self
+                        .server_manager
+                        .ensure_workspace_registered(language, lsp_workspace_root)
+                        .await?;
+
+                    let server = server_instance.lock().await;
+                    let response_json = server
+                        .server
+                        .rename(&absolute_file_path, line, column, &new_name)
+                        .await?;
+
+                    Self::parse_workspace_edit(&response_json)
+                }
+                .await;
+
+                match result {
+                    Ok((files, warnings)) => DaemonResponse::Rename {
+                        request_id,
+                        files,
+                        warnings: (!warnings.is_empty()).then_some(warnings),
+                    },
+                    Err(e) => DaemonResponse::Error {
+                        request_id,
+                        error: e.to_string(),
+                    },
+                }
+            }
+
+            DaemonRequest::HoverBatch {
+                request_id,
+                positions,
+                workspace_hint,
+            } => {
+                info!(
+                    "Received DaemonRequest::HoverBatch for {} positions (request_id: {})",
+                    positions.len(),
+                    request_id
+                );
+
+                let (results, warnings) = self.handle_hover_batch(&positions, workspace_hint).await;
+                DaemonResponse::HoverBatch {
+                    request_id,
+                    results,
+                    warnings: (!warnings.is_empty()).then_some(warnings),
+                }
+            }
+
+            DaemonRequest::DocumentSymbols {
+                request_id,
+                file_path,
+                workspace_hint,
+            } => {
+                info!(
+                    "Received DaemonRequest::DocumentSymbols for {:?} (request_id: {})",
+                    file_path, request_id
+                );
+                match self
+                    .handle_document_symbols(&file_path, workspace_hint)
+                    .await
+                {
+                    Ok(symbols) => DaemonResponse::DocumentSymbols {
+                        request_id,
+                        symbols,
+                        warnings: None,
+                    },
+                    Err(e) => DaemonResponse::Error {
+                        request_id,
+                        error: e.to_string(),
+                    },
+                }
+            }
+
+            DaemonRequest::WorkspaceSymbols {
                 request_id,
                 query,
                 workspace_hint,
@@ -3235,6 +3760,36 @@ impl LspDaemon {
                 }
             }
 
+            DaemonRequest::Related {
+                request_id,
+                file_path,
+                line,
+                column,
+                workspace_hint,
+            } => {
+                info!(
+                    "Received DaemonRequest::Related for {:?} at {}:{} (request_id: {})",
+                    file_path, line, column, request_id
+                );
+
+                if should_exclude_from_lsp(&file_path) {
+                    return DaemonResponse::Error {
+                        request_id,
+                        error: "File is excluded from LSP processing (build artifact or generated code)".to_string(),
+                    };
+                }
+
+                let (symbol, groups, warnings) = self
+                    .handle_related(&file_path, line, column, workspace_hint)
+                    .await;
+                DaemonResponse::Related {
+                    request_id,
+                    symbol,
+                    groups,
+                    warnings: (!warnings.is_empty()).then_some(warnings),
+                }
+            }
+
             // Symbol-specific cache clearing
             DaemonRequest::ClearSymbolCache {
                 request_id,
@@ -3280,23 +3835,60 @@ impl LspDaemon {
             // Handle cache key listing
             DaemonRequest::CacheListKeys {
                 request_id,
-                workspace_path: _,
-                operation_filter: _,
-                file_pattern_filter: _,
+                workspace_path,
+                operation_filter,
+                file_pattern_filter,
                 limit,
                 offset,
                 sort_by: _,
                 sort_order: _,
                 detailed: _,
             } => {
-                // Universal cache layer removed - return empty keys list
-                DaemonResponse::CacheListKeys {
-                    request_id,
-                    keys: Vec::new(),
-                    total_count: 0,
-                    offset,
-                    limit,
-                    has_more: false,
+                // Keys are the symbols cached in the workspace database, listed by file
+                let workspace = match workspace_path {
+                    Some(p) => p,
+                    None => {
+                        std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."))
+                    }
+                };
+                let cache = match self
+                    .workspace_cache_router
+                    .cache_for_workspace(&workspace)
+                    .await
+                {
+                    Ok(cache) => cache,
+                    Err(e) => {
+                        return DaemonResponse::Error {
+                            request_id,
+                            error: format!("Failed to get cache for workspace: {}", e),
+                        }
+                    }
+                };
+                match cache.backend().list_cache_keys().await {
+                    Ok(mut keys) => {
+                        keys.retain(|key| {
+                            operation_filter
+                                .as_ref()
+                                .is_none_or(|operation| &key.operation == operation)
+                                && file_pattern_filter
+                                    .as_ref()
+                                    .is_none_or(|pattern| key.file_path.contains(pattern.as_str()))
+                        });
+                        let total_count = keys.len();
+                        let keys: Vec<_> = keys.into_iter().skip(offset).take(limit).collect();
+                        DaemonResponse::CacheListKeys {
+                            request_id,
+                            has_more: offset + keys.len() < total_count,
+                            keys,
+                            total_count,
+                            offset,
+                            limit,
+                        }
+                    }
+                    Err(e) => DaemonResponse::Error {
+                        request_id,
+                        error: format!("Failed to list cache keys: {}", e),
+                    },
                 }
             }
 
@@ -3310,6 +3902,104 @@ impl LspDaemon {
                 self.handle_index_export(request_id, workspace_path, output_path, checkpoint)
                     .await
             }
+            DaemonRequest::GraphExport {
+                request_id,
+                workspace_path,
+                output_path,
+                format,
+                root,
+                direction,
+                max_depth,
+                since_run,
+                include_external,
+            } => {
+                let workspace = match workspace_path {
+                    Some(p) => p,
+                    None => {
+                        std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."))
+                    }
+                };
+                match self
+                    .handle_graph_export(
+                        &workspace,
+                        &output_path,
+                        &format,
+                        root,
+                        &direction,
+                        max_depth,
+                        since_run,
+                        include_external,
+                    )
+                    .await
+                {
+                    Ok(graph) => DaemonResponse::GraphExported {
+                        request_id,
+                        workspace_path: workspace,
+                        output_path,
+                        root_symbol: graph.metadata.root_symbol.unwrap_or_default(),
+                        nodes_count: graph.metadata.nodes_count,
+                        edges_count: graph.metadata.edges_count,
+                    },
+                    Err(e) => DaemonResponse::Error {
+                        request_id,
+                        error: format!("Graph export failed: {}", e),
+                    },
+                }
+            }
+            DaemonRequest::FindSymbols {
+                request_id,
+                workspace_path,
+                kind,
+                visibility,
+                language,
+                file_glob,
+                limit,
+            } => {
+                let workspace = match workspace_path {
+                    Some(p) => p,
+                    None => {
+                        std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."))
+                    }
+                };
+                match self
+                    .handle_find_symbols(&workspace, kind, visibility, language, file_glob, limit)
+                    .await
+                {
+                    Ok(symbols) => DaemonResponse::SymbolsFound {
+                        request_id,
+                        workspace_path: workspace,
+                        symbols,
+                    },
+                    Err(e) => DaemonResponse::Error {
+                        request_id,
+                        error: format!("Symbol query failed: {}", e),
+                    },
+                }
+            }
+            DaemonRequest::SearchSymbols {
+                request_id,
+                workspace_path,
+                query,
+                limit,
+            } => {
+                let workspace = match workspace_path {
+                    Some(p) => p,
+                    None => {
+                        std::env::current_dir().unwrap_or_else(|_| std::path::PathBuf::from("."))
+                    }
+                };
+                match self.handle_search_symbols(&workspace, &query, limit).await {
+                    Ok(symbols) => DaemonResponse::SymbolsFound {
+                        request_id,
+                        workspace_path: workspace,
+                        symbols,
+                    },
+                    Err(e) => DaemonResponse::Error {
+                        request_id,
+                        error: format!("Symbol search failed: {}", e),
+                    },
+                }
+            }
             DaemonRequest::WalSync {
                 request_id,
                 timeout_secs,
@@ -3377,6 +4067,18 @@ impl LspDaemon {
                     }
                 }
             }
+            DaemonRequest::Stream { request_id, .. } => DaemonResponse::Error {
+                request_id,
+                error: "Stream requests cannot be nested".to_string(),
+            },
+            DaemonRequest::Deadline { request_id, .. } => DaemonResponse::Error {
+                request_id,
+                error: "Deadline requests must wrap the outermost request".to_string(),
+            },
+            DaemonRequest::SubscribeIndexingProgress { request_id, .. } => DaemonResponse::Error {
+                request_id,
+                error: "Progress subscriptions cannot be wrapped in another request".to_string(),
+            },
         }
     }
 
@@ -3440,18 +4142,177 @@ impl LspDaemon {
                 }
             }
         };
-        info!(
-            "Exported database from {} to {} ({} bytes)",
-            db_path.display(),
-            output_path.display(),
-            export_bytes
-        );
-        DaemonResponse::IndexExported {
-            request_id,
-            workspace_path: workspace,
-            output_path,
-            database_size_bytes: export_bytes,
-        }
+        info!(
+            "Exported database from {} to {} ({} bytes)",
+            db_path.display(),
+            output_path.display(),
+            export_bytes
+        );
+        DaemonResponse::IndexExported {
+            request_id,
+            workspace_path: workspace,
+            output_path,
+            database_size_bytes: export_bytes,
+        }
+    }
+
+    /// Export the subgraph reachable from `root`, or what changed since the analysis run
+    /// `since_run`, and write it to `output_path`. With `include_external`, edge targets
+    /// outside the exported symbols become nodes of their own
+    #[allow(clippy::too_many_arguments)]
+    async fn handle_graph_export(
+        &self,
+        workspace: &Path,
+        output_path: &Path,
+        format: &str,
+        root: Option<String>,
+        direction: &str,
+        max_depth: u32,
+        since_run: Option<i64>,
+        include_external: bool,
+    ) -> Result<crate::graph_exporter::ExportGraph> {
+        use crate::graph_exporter::{
+            GraphExportFormat, GraphExportOptions, GraphExporter, SubgraphOptions,
+        };
+        use std::io::{BufWriter, Write};
+
+        let format: GraphExportFormat = format.parse().map_err(|e: String| anyhow!(e))?;
+        let options = match (root, since_run) {
+            (Some(root), None) => GraphExportOptions {
+                max_depth: Some(max_depth),
+                include_external,
+                subgraph: Some(SubgraphOptions {
+                    root,
+                    direction: SubgraphOptions::parse_direction(direction)?,
+                }),
+                ..Default::default()
+            },
+            (None, Some(run_id)) => GraphExportOptions {
+                baseline_run_id: Some(run_id),
+                include_external,
+                ..Default::default()
+            },
+            _ => {
+                return Err(anyhow!(
+                    "A graph export needs either a root symbol or a baseline analysis run"
+                ))
+            }
+        };
+
+        let cache = self
+            .workspace_cache_router
+            .cache_for_workspace(workspace)
+            .await?;
+        let graph = match cache.backend() {
+            BackendType::SQLite(db) => {
+                GraphExporter::export_graph(&**db, workspace.to_path_buf(), options).await?
+            }
+        };
+
+        // GraphML is written to the file as it is generated instead of rendered first
+        if matches!(format, GraphExportFormat::GraphMl) {
+            let file = std::fs::File::create(output_path)
+                .with_context(|| format!("Failed to create {}", output_path.display()))?;
+            let mut writer = BufWriter::new(file);
+            GraphExporter::write_graphml(&graph, &mut writer)
+                .with_context(|| format!("Failed to write {}", output_path.display()))?;
+            writer
+                .flush()
+                .with_context(|| format!("Failed to write {}", output_path.display()))?;
+        } else {
+            std::fs::write(output_path, GraphExporter::render(&graph, format)?)
+                .with_context(|| format!("Failed to write {}", output_path.display()))?;
+        }
+        info!(
+            "Exported graph (root {:?}, since run {:?}) with {} nodes and {} edges to {}",
+            graph.metadata.root_symbol,
+            graph.metadata.baseline_run_id,
+            graph.metadata.nodes_count,
+            graph.metadata.edges_count,
+            output_path.display()
+        );
+        Ok(graph)
+    }
+
+    /// Query the workspace's indexed symbols. Kind and visibility names are validated here,
+    /// as their `From<&str>` conversions map unknown names to a fallback instead of failing.
+    async fn handle_find_symbols(
+        &self,
+        workspace: &Path,
+        kind: Option<String>,
+        visibility: Option<String>,
+        language: Option<String>,
+        file_glob: Option<String>,
+        limit: Option<usize>,
+    ) -> Result<Vec<crate::database::SymbolState>> {
+        use crate::database::{DatabaseBackend, SymbolQuery};
+        use crate::symbol::{SymbolKind, Visibility};
+
+        let kind = match kind {
+            Some(name) => {
+                let parsed = SymbolKind::from(name.as_str());
+                if parsed == SymbolKind::Anonymous
+                    && !matches!(name.to_lowercase().as_str(), "anonymous" | "anon")
+                {
+                    return Err(anyhow!("Unknown symbol kind '{}'", name));
+                }
+                Some(parsed)
+            }
+            None => None,
+        };
+        let visibility = match visibility {
+            Some(name) => {
+                let parsed = Visibility::from(name.as_str());
+                if parsed == Visibility::Private
+                    && !matches!(name.to_lowercase().as_str(), "private" | "priv")
+                {
+                    return Err(anyhow!("Unknown visibility '{}'", name));
+                }
+                Some(parsed)
+            }
+            None => None,
+        };
+        let query = SymbolQuery {
+            kind,
+            visibility,
+            language,
+            file_glob,
+            limit,
+        };
+
+        let cache = self
+            .workspace_cache_router
+            .cache_for_workspace(workspace)
+            .await?;
+        let symbols = match cache.backend() {
+            BackendType::SQLite(db) => db.find_symbols(&query).await?,
+        };
+        debug!(
+            "find_symbols {:?} returned {} symbols for {}",
+            query,
+            symbols.len(),
+            workspace.display()
+        );
+        Ok(symbols)
+    }
+
+    /// Full-text search over the workspace's indexed symbols
+    async fn handle_search_symbols(
+        &self,
+        workspace: &Path,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<crate::database::SymbolState>> {
+        use crate::database::DatabaseBackend;
+
+        let cache = self
+            .workspace_cache_router
+            .cache_for_workspace(workspace)
+            .await?;
+        let symbols = match cache.backend() {
+            BackendType::SQLite(db) => db.search_symbols(query, limit).await?,
+        };
+        Ok(symbols)
     }
 
     /// Handle WAL sync (blocking checkpoint)
@@ -3577,6 +4438,170 @@ impl LspDaemon {
         .map_err(|_| anyhow!("Call hierarchy operation timed out after 120 seconds"))?
     }
 
+    /// Build a call hierarchy tree by following callers and callees up to `max_depth` levels.
+    ///
+    /// Each symbol is expanded through `handle_call_hierarchy`, so edges already in the
+    /// database are reused and only unknown symbols go to the language server. A symbol that
+    /// is already on the path from the root is marked as a cycle instead of being expanded
+    /// again. Symbols that cannot be expanded are kept as leaves and reported as warnings.
+    async fn handle_call_hierarchy_tree(
+        &self,
+        file_path: &Path,
+        line: u32,
+        column: u32,
+        max_depth: u32,
+        workspace_hint: Option<PathBuf>,
+    ) -> Result<(CallHierarchyNode, Vec<String>)> {
+        let mut warnings = Vec::new();
+        let max_depth = if max_depth > MAX_CALL_HIERARCHY_TREE_DEPTH {
+            warnings.push(format!(
+                "Depth {} limited to {}",
+                max_depth, MAX_CALL_HIERARCHY_TREE_DEPTH
+            ));
+            MAX_CALL_HIERARCHY_TREE_DEPTH
+        } else {
+            max_depth.max(1)
+        };
+
+        let root = self
+            .handle_call_hierarchy(file_path, line, column, workspace_hint.clone())
+            .await?;
+        let root_key = call_hierarchy_item_key(&root.item);
+
+        // Expansions are shared between branches that reach the same symbol
+        let mut expanded: std::collections::HashMap<String, Option<CallHierarchyResult>> =
+            std::collections::HashMap::new();
+        expanded.insert(root_key.clone(), Some(root.clone()));
+
+        let mut node = CallHierarchyNode {
+            item: root.item.clone(),
+            from_ranges: Vec::new(),
+            cycle: false,
+            incoming: Vec::new(),
+            outgoing: Vec::new(),
+        };
+        for incoming in [true, false] {
+            let calls = if incoming {
+                &root.incoming
+            } else {
+                &root.outgoing
+            };
+            let mut children = Vec::with_capacity(calls.len());
+            for call in calls {
+                children.push(
+                    self.expand_call_hierarchy_node(
+                        call,
+                        incoming,
+                        1,
+                        max_depth,
+                        &mut vec![root_key.clone()],
+                        &mut expanded,
+                        &workspace_hint,
+                        &mut warnings,
+                    )
+                    .await,
+                );
+            }
+            if incoming {
+                node.incoming = children;
+            } else {
+                node.outgoing = children;
+            }
+        }
+
+        Ok((node, warnings))
+    }
+
+    /// Expand one call of a call hierarchy tree at `depth` (the root's calls are depth 1),
+    /// following the same direction until `max_depth`
+    #[allow(clippy::too_many_arguments)]
+    fn expand_call_hierarchy_node<'a>(
+        &'a self,
+        call: &'a CallHierarchyCall,
+        incoming: bool,
+        depth: u32,
+        max_depth: u32,
+        path: &'a mut Vec<String>,
+        expanded: &'a mut std::collections::HashMap<String, Option<CallHierarchyResult>>,
+        workspace_hint: &'a Option<PathBuf>,
+        warnings: &'a mut Vec<String>,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = CallHierarchyNode> + Send + 'a>> {
+        Box::pin(async move {
+            let key = call_hierarchy_item_key(&call.from);
+            let mut node = CallHierarchyNode {
+                item: call.from.clone(),
+                from_ranges: call.from_ranges.clone(),
+                cycle: path.contains(&key),
+                incoming: Vec::new(),
+                outgoing: Vec::new(),
+            };
+            if node.cycle || depth >= max_depth {
+                return node;
+            }
+
+            if !expanded.contains_key(&key) {
+                let file_path = PathBuf::from(call.from.uri.replace("file://", ""));
+                let position = &call.from.selection_range.start;
+                let result = match self
+                    .handle_call_hierarchy(
+                        &file_path,
+                        position.line,
+                        position.character,
+                        workspace_hint.clone(),
+                    )
+                    .await
+                {
+                    Ok(result) => Some(result),
+                    Err(e) => {
+                        warnings.push(format!(
+                            "Could not expand {} at {}:{}: {}",
+                            call.from.name,
+                            file_path.display(),
+                            position.line + 1,
+                            e
+                        ));
+                        None
+                    }
+                };
+                expanded.insert(key.clone(), result);
+            }
+            let Some(result) = expanded.get(&key).cloned().flatten() else {
+                return node;
+            };
+
+            let calls = if incoming {
+                &result.incoming
+            } else {
+                &result.outgoing
+            };
+            path.push(key);
+            let mut children = Vec::with_capacity(calls.len());
+            for child in calls {
+                children.push(
+                    self.expand_call_hierarchy_node(
+                        child,
+                        incoming,
+                        depth + 1,
+                        max_depth,
+                        path,
+                        expanded,
+                        workspace_hint,
+                        warnings,
+                    )
+                    .await,
+                );
+            }
+            path.pop();
+
+            if incoming {
+                node.incoming = children;
+            } else {
+                node.outgoing = children;
+            }
+            node
+        })
+    }
+
     async fn handle_call_hierarchy_inner(
         &self,
         file_path: &Path,
@@ -3674,6 +4699,7 @@ impl LspDaemon {
                                 line,
                                 column
                             );
+                            note_cache_hit();
                             return Ok(result);
                         }
                         Ok(None) => {
@@ -4240,6 +5266,357 @@ impl LspDaemon {
         }
     }
 
+    /// Boxed entry into the request handler, for requests that are served by running other
+    /// requests (the handler future cannot contain itself)
+    fn handle_request_boxed(
+        &self,
+        request: DaemonRequest,
+    ) -> std::pin::Pin<Box<dyn std::future::Future<Output = DaemonResponse> + Send + '_>> {
+        Box::pin(self.handle_request_internal(request))
+    }
+
+    /// Run one lookup of a `Related` request, returning its response and whether it was
+    /// answered from the database
+    async fn related_lookup(&self, request: DaemonRequest) -> (DaemonResponse, RelatedSource) {
+        SERVED_FROM_CACHE
+            .scope(std::cell::Cell::new(false), async {
+                let response = self.handle_request_boxed(request).await;
+                let source = if SERVED_FROM_CACHE.with(|hit| hit.get()) {
+                    RelatedSource::Cache
+                } else {
+                    RelatedSource::Live
+                };
+                (response, source)
+            })
+            .await
+    }
+
+    /// References, callers, callees, implementations and type definition of the symbol at a
+    /// position. The lookups run concurrently; one that fails yields a group with an error
+    /// instead of failing the others.
+    async fn handle_related(
+        &self,
+        file_path: &Path,
+        line: u32,
+        column: u32,
+        workspace_hint: Option<PathBuf>,
+    ) -> (Option<CallHierarchyItem>, Vec<RelatedGroup>, Vec<String>) {
+        let file_path = file_path.to_path_buf();
+        let (references, calls, implementations, type_definition) = tokio::join!(
+            self.related_lookup(DaemonRequest::References {
+                request_id: Uuid::new_v4(),
+                file_path: file_path.clone(),
+                line,
+                column,
+                include_declaration: false,
+                workspace_hint: workspace_hint.clone(),
+            }),
+            self.related_lookup(DaemonRequest::CallHierarchy {
+                request_id: Uuid::new_v4(),
+                file_path: file_path.clone(),
+                line,
+                column,
+                workspace_hint: workspace_hint.clone(),
+            }),
+            self.related_lookup(DaemonRequest::Implementations {
+                request_id: Uuid::new_v4(),
+                file_path: file_path.clone(),
+                line,
+                column,
+                workspace_hint: workspace_hint.clone(),
+            }),
+            self.related_lookup(DaemonRequest::TypeDefinition {
+                request_id: Uuid::new_v4(),
+                file_path,
+                line,
+                column,
+                workspace_hint,
+            }),
+        );
+
+        let mut symbol = None;
+        let mut groups = Vec::new();
+        let mut warnings = Vec::new();
+        let location_items = |locations: Vec<Location>| {
+            locations
+                .into_iter()
+                .map(|location| RelatedItem {
+                    name: None,
+                    location,
+                })
+                .collect::<Vec<_>>()
+        };
+        let call_items = |calls: Vec<CallHierarchyCall>| {
+            calls
+                .into_iter()
+                .map(|call| RelatedItem {
+                    name: Some(call.from.name),
+                    location: Location {
+                        uri: call.from.uri,
+                        range: call.from.selection_range,
+                    },
+                })
+                .collect::<Vec<_>>()
+        };
+
+        for (kinds, (response, source)) in [
+            (&[RelationKind::References][..], references),
+            (
+                &[RelationKind::IncomingCalls, RelationKind::OutgoingCalls][..],
+                calls,
+            ),
+            (&[RelationKind::Implementations][..], implementations),
+            (&[RelationKind::TypeDefinition][..], type_definition),
+        ] {
+            match response {
+                DaemonResponse::References {
+                    locations,
+                    warnings: lookup_warnings,
+                    ..
+                }
+                | DaemonResponse::Implementations {
+                    locations,
+                    warnings: lookup_warnings,
+                    ..
+                }
+                | DaemonResponse::TypeDefinition {
+                    locations,
+                    warnings: lookup_warnings,
+                    ..
+                } => {
+                    warnings.extend(lookup_warnings.unwrap_or_default());
+                    groups.push(RelatedGroup::new(
+                        kinds[0],
+                        source,
+                        location_items(locations),
+                    ));
+                }
+                DaemonResponse::CallHierarchy {
+                    result,
+                    warnings: lookup_warnings,
+                    ..
+                } => {
+                    warnings.extend(lookup_warnings.unwrap_or_default());
+                    groups.push(RelatedGroup::new(
+                        RelationKind::IncomingCalls,
+                        source,
+                        call_items(result.incoming),
+                    ));
+                    groups.push(RelatedGroup::new(
+                        RelationKind::OutgoingCalls,
+                        source,
+                        call_items(result.outgoing),
+                    ));
+                    symbol = Some(result.item);
+                }
+                other => {
+                    let error = match other {
+                        DaemonResponse::Error { error, .. } => error,
+                        other => format!("Unexpected response: {:?}", other),
+                    };
+                    groups.extend(
+                        kinds
+                            .iter()
+                            .map(|kind| RelatedGroup::failed(*kind, error.clone())),
+                    );
+                }
+            }
+        }
+
+        (symbol, groups, warnings)
+    }
+
+    /// Hover at each position, pipelining the requests that go to the same language server.
+    /// Positions that fail get `None` and a warning instead of failing the whole batch.
+    async fn handle_hover_batch(
+        &self,
+        positions: &[HoverPosition],
+        workspace_hint: Option<PathBuf>,
+    ) -> (Vec<Option<HoverContent>>, Vec<String>) {
+        let mut results = vec![None; positions.len()];
+        let mut warnings = Vec::new();
+        let warning = |position: &HoverPosition, error: &dyn std::fmt::Display| {
+            format!(
+                "{}:{}:{}: {}",
+                position.file_path.display(),
+                position.line,
+                position.column,
+                error
+            )
+        };
+
+        // Positions grouped by the language server that answers them, in request order
+        let mut groups: Vec<((Language, PathBuf), Vec<(usize, PathBuf)>)> = Vec::new();
+        for (index, position) in positions.iter().enumerate() {
+            if should_exclude_from_lsp(&position.file_path) {
+                warnings.push(warning(
+                    position,
+                    &"File is excluded from LSP processing (build artifact or generated code)",
+                ));
+                continue;
+            }
+
+            let absolute_file_path = safe_canonicalize(&position.file_path);
+            let server_key = async {
+                let language = self.detector.detect(&absolute_file_path)?;
+                if language == Language::Unknown {
+                    return Err(anyhow!(
+                        "Unknown language for file: {:?}",
+                        absolute_file_path
+                    ));
+                }
+
+                let _workspace_root = {
+                    let mut resolver = self.workspace_resolver.lock().await;
+                    resolver.resolve_workspace(&absolute_file_path, workspace_hint.clone())?
+                };
+
+                let lsp_workspace_root =
+                    workspace_utils::resolve_lsp_workspace_root(language, &absolute_file_path)?;
+                Ok((language, lsp_workspace_root))
+            }
+            .await;
+
+            match server_key {
+                Ok(key) => match groups.iter_mut().find(|(group_key, _)| *group_key == key) {
+                    Some((_, members)) => members.push((index, absolute_file_path)),
+                    None => groups.push((key, vec![(index, absolute_file_path)])),
+                },
+                Err(e) => warnings.push(warning(position, &e)),
+            }
+        }
+
+        for ((language, lsp_workspace_root), members) in groups {
+            let server_instance = match self
+                .server_manager
+                .ensure_workspace_registered(language, lsp_workspace_root)
+                .await
+            {
+                Ok(server_instance) => server_instance,
+                Err(e) => {
+                    for (index, _) in &members {
+                        warnings.push(warning(&positions[*index], &e));
+                    }
+                    continue;
+                }
+            };
+
+            let requests: Vec<(&Path, u32, u32)> = members
+                .iter()
+                .map(|(index, path)| {
+                    (
+                        path.as_path(),
+                        positions[*index].line,
+                        positions[*index].column,
+                    )
+                })
+                .collect();
+            let server = server_instance.lock().await;
+            let responses = server.server.hover_batch(&requests).await;
+
+            for ((index, _), response) in members.iter().zip(responses) {
+                match response.and_then(|json| Self::parse_hover_response(&json)) {
+                    Ok(content) => results[*index] = content,
+                    Err(e) => warnings.push(warning(&positions[*index], &e)),
+                }
+            }
+        }
+
+        (results, warnings)
+    }
+
+    /// Parse an LSP `WorkspaceEdit` (JSON) into text edits per file, accepting both the
+    /// `changes` and the `documentChanges` form. File create/rename/delete operations are
+    /// skipped with a warning. `null` means the server has nothing to edit.
+    fn parse_workspace_edit(response: &serde_json::Value) -> Result<(Vec<FileEdits>, Vec<String>)> {
+        let mut files: Vec<FileEdits> = Vec::new();
+        let mut warnings = Vec::new();
+        if response.is_null() {
+            return Ok((files, warnings));
+        }
+
+        let mut add_edits = |uri: &str, edits: &serde_json::Value| -> Result<()> {
+            let file_path = url::Url::parse(uri)
+                .ok()
+                .and_then(|url| url.to_file_path().ok())
+                .ok_or_else(|| anyhow!("Unsupported document URI in rename edits: {}", uri))?;
+            let edits = edits
+                .as_array()
+                .ok_or_else(|| anyhow!("Invalid edits for {}", uri))?
+                .iter()
+                .map(|edit| {
+                    let range = serde_json::from_value::<Range>(edit["range"].clone())
+                        .context("Invalid range in rename edit")?;
+                    let new_text = edit["newText"]
+                        .as_str()
+                        .ok_or_else(|| anyhow!("Missing newText in rename edit"))?
+                        .to_string();
+                    Ok(TextEdit { range, new_text })
+                })
+                .collect::<Result<Vec<_>>>()?;
+
+            match files.iter_mut().find(|file| file.file_path == file_path) {
+                Some(file) => file.edits.extend(edits),
+                None => files.push(FileEdits { file_path, edits }),
+            }
+            Ok(())
+        };
+
+        if let Some(document_changes) = response["documentChanges"].as_array() {
+            for change in document_changes {
+                match change["kind"].as_str() {
+                    Some(kind) => {
+                        let uri = change["uri"]
+                            .as_str()
+                            .or_else(|| change["oldUri"].as_str())
+                            .unwrap_or("?");
+                        warnings.push(format!(
+                            "Skipped {kind} operation on {uri}: file operations are not supported"
+                        ));
+                    }
+                    None => {
+                        let uri = change["textDocument"]["uri"]
+                            .as_str()
+                            .ok_or_else(|| anyhow!("Missing document URI in rename edits"))?;
+                        add_edits(uri, &change["edits"])?;
+                    }
+                }
+            }
+        } else if let Some(changes) = response["changes"].as_object() {
+            for (uri, edits) in changes {
+                add_edits(uri, edits)?;
+            }
+        }
+
+        files.sort_by(|a, b| a.file_path.cmp(&b.file_path));
+        Ok((files, warnings))
+    }
+
+    /// Parse LSP document highlight response (JSON) into highlights; `null` means none
+    fn parse_document_highlight_response(
+        response: &serde_json::Value,
+    ) -> Result<Vec<DocumentHighlight>> {
+        let Some(items) = response.as_array() else {
+            if response.is_null() {
+                return Ok(Vec::new());
+            }
+            return Err(anyhow!(
+                "Unexpected document highlight response: {}",
+                response
+            ));
+        };
+
+        items
+            .iter()
+            .map(|item| {
+                let range = serde_json::from_value::<Range>(item["range"].clone())
+                    .context("Invalid range in document highlight")?;
+                let kind = DocumentHighlightKind::from_lsp(item["kind"].as_u64());
+                Ok(DocumentHighlight { range, kind })
+            })
+            .collect()
+    }
+
     /// Parse LSP hover response (JSON) into Option<HoverContent>
     fn parse_hover_response(response: &serde_json::Value) -> Result<Option<HoverContent>> {
         if response.is_null() {
@@ -4618,6 +5995,7 @@ impl LspDaemon {
                             line,
                             column
                         );
+                        note_cache_hit();
                         return Ok(locations);
                     }
                     Ok(None) => {
@@ -5843,6 +7221,8 @@ impl LspDaemon {
                 .unwrap_or(10 * 1024 * 1024),
             enabled_languages: config.languages,
             incremental_mode: config.incremental.unwrap_or(true),
+            include_tests: config.include_tests.unwrap_or(false),
+            max_duration: config.max_duration_secs.map(Duration::from_secs),
             discovery_batch_size: 100,
             status_update_interval_secs: 5,
             specific_files: config.specific_files,
@@ -5883,6 +7263,12 @@ impl LspDaemon {
             self.workspace_cache_router.clone(),
         ));
 
+        // Per-language toggles live in the daemon config, not in the per-run request
+        let disabled_languages = self.indexing_config.read().await.disabled_language_set();
+        indexing_manager
+            .set_disabled_languages(disabled_languages)
+            .await;
+
         let session_id = uuid::Uuid::new_v4().to_string();
 
         // Store the indexing manager
@@ -5944,6 +7330,16 @@ impl LspDaemon {
         Ok(session_id)
     }
 
+    async fn handle_prioritize_indexing(&self, file_path: &Path) -> Result<bool> {
+        let manager = self
+            .indexing_manager
+            .lock()
+            .await
+            .clone()
+            .ok_or_else(|| anyhow!("Indexing is not running"))?;
+        manager.prioritize_file(file_path).await
+    }
+
     async fn handle_stop_indexing(&self, force: bool) -> Result<bool> {
         use tokio::time::{timeout, Duration};
 
@@ -6275,6 +7671,8 @@ impl LspDaemon {
                     failed_files: progress.failed_files,
                     active_files: progress.active_files,
                     skipped_files: progress.skipped_files,
+                    test_files_skipped: progress.test_files_skipped,
+                    deferred_files: progress.deferred_files,
                     processed_bytes: progress.processed_bytes,
                     symbols_extracted: progress.symbols_extracted,
                     progress_ratio: if progress.total_files > 0 {
@@ -6313,6 +7711,7 @@ impl LspDaemon {
                 empty_cache: None,
                 snapshot_updated_at: None,
                 snapshot_age_secs: None,
+                analysis_run_id: manager.get_analysis_run_id().await,
             };
 
             // Attach empty-cache snapshot (best-effort)
@@ -6342,6 +7741,8 @@ impl LspDaemon {
                     failed_files: 0,
                     active_files: 0,
                     skipped_files: 0,
+                    test_files_skipped: 0,
+                    deferred_files: 0,
                     processed_bytes: 0,
                     symbols_extracted: 0,
                     progress_ratio: 0.0,
@@ -6368,6 +7769,7 @@ impl LspDaemon {
                 empty_cache: None,
                 snapshot_updated_at: None,
                 snapshot_age_secs: None,
+                analysis_run_id: None,
             };
 
             Ok(status_info)
@@ -6409,6 +7811,8 @@ impl LspDaemon {
                     failed_files: progress.failed_files,
                     active_files: progress.active_files,
                     skipped_files: progress.skipped_files,
+                    test_files_skipped: progress.test_files_skipped,
+                    deferred_files: progress.deferred_files,
                     processed_bytes: progress.processed_bytes,
                     symbols_extracted: progress.symbols_extracted,
                     progress_ratio: if progress.total_files > 0 {
@@ -6447,6 +7851,7 @@ impl LspDaemon {
                 empty_cache: Some(manager.get_empty_cache_info().await),
                 snapshot_updated_at: None,
                 snapshot_age_secs: None,
+                analysis_run_id: manager.get_analysis_run_id().await,
             };
             Ok(out)
         } else {
@@ -6887,7 +8292,37 @@ impl LspDaemon {
         config: crate::protocol::IndexingConfig,
     ) -> Result<()> {
         // Convert protocol config to internal config using the proper conversion function
-        let internal_config = crate::indexing::IndexingConfig::from_protocol_config(&config);
+        let mut internal_config = crate::indexing::IndexingConfig::from_protocol_config(&config);
+
+        let toggles_changed = {
+            let current = self.indexing_config.read().await;
+
+            // The protocol only carries the per-language enabled flag; keep the other
+            // per-language settings that were loaded from files
+            for (language, language_config) in &current.language_configs {
+                let mut language_config = language_config.clone();
+                language_config.enabled = internal_config
+                    .language_configs
+                    .get(language)
+                    .and_then(|c| c.enabled);
+                internal_config
+                    .language_configs
+                    .insert(*language, language_config);
+            }
+
+            current.disabled_language_set() != internal_config.disabled_language_set()
+        };
+
+        if toggles_changed {
+            internal_config.save_language_toggles()?;
+
+            // Running indexing stops dispatching files of disabled languages right away
+            if let Some(manager) = self.indexing_manager.lock().await.as_ref() {
+                manager
+                    .set_disabled_languages(internal_config.disabled_language_set())
+                    .await;
+            }
+        }
 
         // Update stored config
         *self.indexing_config.write().await = internal_config;
@@ -7075,6 +8510,91 @@ impl LspDaemon {
             .await;
     }
 
+    /// Status of the daemon and its language-server pools
+    async fn daemon_status(&self) -> DaemonStatus {
+        let server_stats = self.server_manager.get_stats().await;
+        let all_readiness = self.server_manager.get_all_readiness_status().await;
+
+        let pool_status: Vec<PoolStatus> = server_stats
+            .into_iter()
+            .map(|s| {
+                // Consider a server "ready" if it's initialized (simplified without health monitoring)
+                let is_ready = s.initialized;
+
+                // Find readiness information for this language
+                let readiness_info = all_readiness
+                    .iter()
+                    .find(|r| r.language == s.language)
+                    .cloned();
+
+                PoolStatus {
+                    language: s.language,
+                    ready_servers: if is_ready { 1 } else { 0 },
+                    busy_servers: 0, // No busy concept in single server model
+                    total_servers: 1,
+                    workspaces: s
+                        .workspaces
+                        .iter()
+                        .map(|w| safe_canonicalize(w).to_string_lossy().to_string())
+                        .collect(),
+                    uptime_secs: s.uptime.as_secs(),
+                    status: format!("{:?}", s.status),
+                    health_status: if is_ready {
+                        "healthy".to_string()
+                    } else {
+                        "initializing".to_string()
+                    },
+                    consecutive_failures: 0, // No failure tracking without health monitor
+                    circuit_breaker_open: false, // No circuit breaker
+                    readiness_info,
+                }
+            })
+            .collect();
+
+        DaemonStatus {
+            uptime_secs: self.start_time.elapsed().as_secs(),
+            pools: pool_status,
+            total_requests: *self.request_count.read().await,
+            active_connections: self.connections.len(),
+            lsp_inflight_current: self.server_manager.total_inflight() as u64,
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            git_hash: env!("GIT_HASH").to_string(),
+            build_date: env!("BUILD_DATE").to_string(),
+            universal_cache_stats: None, // Universal cache layer removed
+            // Add database health information (Priority 4)
+            database_health: Some(self.get_database_health_summary().await),
+        }
+    }
+
+    /// Cache statistics; empty since the universal cache layer was removed
+    fn cache_statistics() -> crate::protocol::CacheStatistics {
+        crate::protocol::CacheStatistics {
+            hit_rate: 0.0,
+            miss_rate: 1.0,
+            total_entries: 0,
+            total_size_bytes: 0,
+            disk_size_bytes: 0,
+            entries_per_file: std::collections::HashMap::new(),
+            entries_per_language: std::collections::HashMap::new(),
+            age_distribution: crate::protocol::AgeDistribution {
+                entries_last_hour: 0,
+                entries_last_day: 0,
+                entries_last_week: 0,
+                entries_last_month: 0,
+                entries_older: 0,
+            },
+            most_accessed: Vec::new(),
+            memory_usage: crate::protocol::MemoryUsage {
+                in_memory_cache_bytes: 0,
+                persistent_cache_bytes: 0,
+                metadata_bytes: 0,
+                index_bytes: 0,
+            },
+            per_workspace_stats: None,
+            per_operation_totals: None,
+        }
+    }
+
     /// Get database health summary string for status responses
     async fn get_database_health_summary(&self) -> String {
         let health = self.database_health_status.lock().await;
@@ -8193,6 +9713,97 @@ mod queue_conversion_tests {
     }
 }
 
+#[cfg(all(test, unix))]
+mod stream_tests {
+    use super::LspDaemon;
+    use crate::ipc::{IpcListener, IpcStream};
+    use crate::protocol::{DaemonResponse, Location, MessageCodec, Position, Range};
+    use std::sync::atomic::AtomicBool;
+    use tokio::time::Duration;
+    use uuid::Uuid;
+
+    fn location(line: u32) -> Location {
+        Location {
+            uri: "file:///src/lib.rs".to_string(),
+            range: Range {
+                start: Position { line, character: 0 },
+                end: Position { line, character: 8 },
+            },
+        }
+    }
+
+    /// Stream `response` from a daemon to a client connected over a real socket and
+    /// return what the client reassembled
+    async fn stream_to_client(response: DaemonResponse, cancelled: bool) -> DaemonResponse {
+        let dir = tempfile::tempdir().unwrap();
+        let socket_path = dir.path().join("daemon.sock").to_string_lossy().to_string();
+        let daemon = LspDaemon::new_with_config_async(socket_path.clone(), None)
+            .await
+            .unwrap();
+        let listener = IpcListener::bind(&socket_path).await.unwrap();
+
+        let client = tokio::spawn(async move {
+            let mut stream = IpcStream::connect(&socket_path).await.unwrap();
+            MessageCodec::read_response_stream(&mut stream, Duration::from_secs(5)).await
+        });
+        let (_reader, mut writer) = listener.accept().await.unwrap().into_split();
+        daemon
+            .send_streamed_response(&mut writer, response, 1_000, &AtomicBool::new(cancelled))
+            .await
+            .unwrap();
+
+        client.await.unwrap().unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_streamed_references_are_reassembled_by_the_client() {
+        let request_id = Uuid::new_v4();
+        let response = DaemonResponse::References {
+            request_id,
+            locations: (0..10_000).map(location).collect(),
+            warnings: None,
+        };
+
+        match stream_to_client(response, false).await {
+            DaemonResponse::References {
+                request_id: id,
+                locations,
+                ..
+            } => {
+                assert_eq!(id, request_id);
+                assert_eq!(locations.len(), 10_000);
+                for (i, location) in locations.iter().enumerate() {
+                    assert_eq!(location.range.start.line, i as u32);
+                }
+            }
+            other => panic!("Expected References, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_stream_reaches_the_client_as_an_error() {
+        let response = DaemonResponse::References {
+            request_id: Uuid::new_v4(),
+            locations: (0..10).map(location).collect(),
+            warnings: None,
+        };
+
+        match stream_to_client(response, true).await {
+            DaemonResponse::Error { error, .. } => assert!(error.contains("cancelled"), "{error}"),
+            other => panic!("Expected Error, got {other:?}"),
+        }
+    }
+}
+
+/// Identity of a call hierarchy item for cycle detection: the same symbol reached again
+/// has the same document, name and selection start
+fn call_hierarchy_item_key(item: &CallHierarchyItem) -> String {
+    format!(
+        "{}#{}@{}:{}",
+        item.uri, item.name, item.selection_range.start.line, item.selection_range.start.character
+    )
+}
+
 /// Check if a file path should be excluded from LSP processing
 ///
 /// This filters out build artifacts, generated code, and temporary files that
@@ -41,15 +41,18 @@
 use anyhow::Result;
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
 
 // Import protocol types for database query methods
 use crate::protocol::{CallHierarchyResult, Location};
+use crate::symbol::{SymbolKind, Visibility};
 
 pub mod converters;
 pub mod enrichment_tracking;
 pub mod sqlite_backend;
+pub mod symbol_search;
 pub use converters::ProtocolConverter;
 pub use enrichment_tracking::{EnrichmentStatus, EnrichmentTracker, EnrichmentTracking};
 pub use sqlite_backend::SQLiteBackend;
@@ -396,6 +399,89 @@ pub struct GraphPath {
     pub relation_chain: Vec<EdgeRelation>,
 }
 
+/// One symbol or edge as it was when an analysis run completed
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SnapshotEntry {
+    /// Symbol kind, or edge relation
+    pub kind: String,
+    /// Digest of the full symbol or edge, to tell whether it changed since
+    pub digest: String,
+}
+
+/// The graph left behind by a completed analysis run, the baseline for delta exports.
+/// Symbols are keyed by UID and edges by [`GraphSnapshot::edge_key`].
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct GraphSnapshot {
+    pub symbols: HashMap<String, SnapshotEntry>,
+    pub edges: HashMap<String, SnapshotEntry>,
+}
+
+impl GraphSnapshot {
+    const EDGE_KEY_SEPARATOR: char = '\u{1f}';
+
+    pub fn from_graph(symbols: &[SymbolState], edges: &[Edge]) -> Self {
+        let symbols = symbols
+            .iter()
+            .map(|symbol| {
+                let entry = SnapshotEntry {
+                    kind: symbol.kind.clone(),
+                    digest: Self::digest(symbol),
+                };
+                (symbol.symbol_uid.clone(), entry)
+            })
+            .collect();
+        let edges = edges
+            .iter()
+            // "none" targets mark analyzed symbols without relationships, not real edges
+            .filter(|edge| edge.target_symbol_uid != "none")
+            .map(|edge| {
+                let entry = SnapshotEntry {
+                    kind: edge.relation.to_string().to_string(),
+                    digest: Self::digest(edge),
+                };
+                (Self::edge_key(edge), entry)
+            })
+            .collect();
+        Self { symbols, edges }
+    }
+
+    /// Identity of an edge: its endpoints, relation and call site
+    pub fn edge_key(edge: &Edge) -> String {
+        let sep = Self::EDGE_KEY_SEPARATOR;
+        format!(
+            "{}{sep}{}{sep}{}{sep}{}{sep}{}",
+            edge.source_symbol_uid,
+            edge.target_symbol_uid,
+            edge.relation.to_string(),
+            edge.start_line.map(|l| l.to_string()).unwrap_or_default(),
+            edge.start_char.map(|c| c.to_string()).unwrap_or_default(),
+        )
+    }
+
+    /// Source and target UIDs of an edge key
+    pub fn edge_endpoints(key: &str) -> Option<(&str, &str)> {
+        let mut parts = key.split(Self::EDGE_KEY_SEPARATOR);
+        Some((parts.next()?, parts.next()?))
+    }
+
+    fn digest<T: Serialize>(value: &T) -> String {
+        let bytes = serde_json::to_vec(value).unwrap_or_default();
+        blake3::hash(&bytes).to_hex().to_string()
+    }
+}
+
+/// Filters for [`DatabaseBackend::find_symbols`]. Unset filters match every symbol.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SymbolQuery {
+    pub kind: Option<SymbolKind>,
+    pub visibility: Option<Visibility>,
+    pub language: Option<String>,
+    /// Glob matched against the stored (workspace-relative) file path, e.g. `src/**/*.rs`
+    pub file_glob: Option<String>,
+    /// Maximum number of symbols to return
+    pub limit: Option<usize>,
+}
+
 /// Analysis progress information
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct AnalysisProgress {
@@ -567,6 +653,16 @@ pub trait DatabaseBackend: Send + Sync {
         fqn: &str,
     ) -> Result<Option<SymbolState>, DatabaseError>;
 
+    /// Find symbols matching every filter of `query`, ordered by file and position
+    async fn find_symbols(&self, query: &SymbolQuery) -> Result<Vec<SymbolState>, DatabaseError>;
+
+    /// Full-text search over symbol names, FQNs and documentation, best match first
+    async fn search_symbols(
+        &self,
+        text: &str,
+        limit: usize,
+    ) -> Result<Vec<SymbolState>, DatabaseError>;
+
     // ===================
     // Relationship Storage & Querying
     // ===================
@@ -589,12 +685,14 @@ pub trait DatabaseBackend: Send + Sync {
         direction: CallDirection,
     ) -> Result<Vec<Edge>, DatabaseError>;
 
-    /// Traverse graph starting from symbol with maximum depth and relation filters
+    /// Traverse graph starting from symbol with maximum depth and relation filters.
+    /// `Outgoing` follows edges from source to target, `Incoming` from target to source.
     async fn traverse_graph(
         &self,
         start_symbol: &str,
         max_depth: u32,
         relations: &[EdgeRelation],
+        direction: CallDirection,
     ) -> Result<Vec<GraphPath>, DatabaseError>;
 
     // ===================
@@ -610,6 +708,17 @@ pub trait DatabaseBackend: Send + Sync {
         config: &str,
     ) -> Result<i64, DatabaseError>;
 
+    /// Mark an analysis run completed and record the graph it left behind, so later
+    /// graph exports can be limited to what changed since
+    async fn complete_analysis_run(&self, run_id: i64) -> Result<(), DatabaseError>;
+
+    /// Graph recorded when the run completed, or `None` if the run is unknown or has not
+    /// completed
+    async fn get_analysis_run_snapshot(
+        &self,
+        run_id: i64,
+    ) -> Result<Option<GraphSnapshot>, DatabaseError>;
+
     /// Get analysis progress for workspace
     async fn get_analysis_progress(
         &self,
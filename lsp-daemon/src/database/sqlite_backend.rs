@@ -9,7 +9,7 @@ use async_trait::async_trait;
 use dashmap::DashMap;
 use once_cell::sync::Lazy;
 use serde::Serialize;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -76,10 +76,12 @@ macro_rules! debug_execute {
 use turso::Connection;
 use turso_core as coredb;
 
+use crate::database::symbol_search::{self, SearchField, TermPosting};
 use crate::database::{
     AnalysisProgress, CallDirection, DatabaseBackend, DatabaseConfig, DatabaseError, DatabaseStats,
     DatabaseTree, DbCheckpointMode, Edge, EdgeInterpretation, EdgeRelation, GraphPath,
-    PendingEnrichmentCounts, SymbolEnrichmentPlan, SymbolState, Workspace,
+    GraphSnapshot, PendingEnrichmentCounts, SnapshotEntry, SymbolEnrichmentPlan, SymbolQuery,
+    SymbolState, Workspace,
 };
 use crate::protocol::{CallHierarchyResult, Location};
 use crate::symbol::{is_absolute_like, normalize_uid_with_hint};
@@ -999,6 +1001,11 @@ impl ConnectionPool {
         let mut index_sqls: Vec<&str> = vec![
             // symbol lookups by file and language
             "CREATE INDEX IF NOT EXISTS idx_symbol_state_file_lang ON symbol_state(file_path, language)",
+            // symbol queries by kind and visibility
+            "CREATE INDEX IF NOT EXISTS idx_symbol_state_kind_visibility ON symbol_state(kind, visibility)",
+            // full-text symbol search
+            "CREATE INDEX IF NOT EXISTS idx_symbol_search_term ON symbol_search_term(term)",
+            "CREATE INDEX IF NOT EXISTS idx_symbol_search_uid ON symbol_search_term(symbol_uid)",
             // edge lookups for references/impls/calls
             "CREATE INDEX IF NOT EXISTS idx_edge_source_relation ON edge(source_symbol_uid, relation)",
             "CREATE INDEX IF NOT EXISTS idx_edge_target_relation ON edge(target_symbol_uid, relation)",
@@ -1303,6 +1310,24 @@ impl ConnectionPool {
                 message: format!("Failed to create file_analysis table: {e}"),
             })?;
 
+        // 9b. Graph recorded when an analysis run completes (baseline for delta exports)
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS analysis_run_graph (
+                run_id TEXT NOT NULL,
+                item_type TEXT NOT NULL,
+                item_key TEXT NOT NULL,
+                kind TEXT NOT NULL,
+                digest TEXT NOT NULL
+            )
+            "#,
+            (),
+        )
+        .await
+        .map_err(|e| DatabaseError::Configuration {
+            message: format!("Failed to create analysis_run_graph table: {e}"),
+        })?;
+
         Ok(())
     }
 
@@ -1455,6 +1480,23 @@ impl ConnectionPool {
                 message: format!("Failed to create symbol_change table: {e}"),
             })?;
 
+        // 15. Full-text search terms of symbol names, FQNs and documentation
+        conn.execute(
+            r#"
+            CREATE TABLE IF NOT EXISTS symbol_search_term (
+                term TEXT NOT NULL,
+                symbol_uid TEXT NOT NULL,
+                field TEXT NOT NULL,
+                frequency INTEGER NOT NULL
+            )
+            "#,
+            (),
+        )
+        .await
+        .map_err(|e| DatabaseError::Configuration {
+            message: format!("Failed to create symbol_search_term table: {e}"),
+        })?;
+
         Ok(())
     }
 
@@ -5439,6 +5481,13 @@ impl DatabaseBackend for SQLiteBackend {
         // kv_store and tree_* tables were removed from the schema. Keep clear() tolerant.
         // Best-effort: clear core tables used by the current backend.
         let _ = safe_execute(&conn, "DELETE FROM symbol_state", (), "clear.symbol_state").await;
+        let _ = safe_execute(
+            &conn,
+            "DELETE FROM symbol_search_term",
+            (),
+            "clear.symbol_search_term",
+        )
+        .await;
         let _ = safe_execute(&conn, "DELETE FROM edge", (), "clear.edge").await;
 
         ConnectionPool::return_connection_arc(&self.pool, conn);
@@ -6096,6 +6145,190 @@ impl DatabaseBackend for SQLiteBackend {
         Ok(result)
     }
 
+    async fn find_symbols(&self, query: &SymbolQuery) -> Result<Vec<SymbolState>, DatabaseError> {
+        let pattern = match &query.file_glob {
+            Some(glob) => {
+                Some(
+                    glob::Pattern::new(glob).map_err(|e| DatabaseError::OperationFailed {
+                        message: format!("Invalid file glob '{}': {}", glob, e),
+                    })?,
+                )
+            }
+            None => None,
+        };
+
+        let mut conditions = Vec::new();
+        let mut params = Vec::new();
+        if let Some(kind) = &query.kind {
+            conditions.push("kind = ?");
+            params.push(turso::Value::Text(kind.to_string()));
+        }
+        if let Some(visibility) = &query.visibility {
+            conditions.push("visibility = ?");
+            params.push(turso::Value::Text(visibility.to_string()));
+        }
+        if let Some(language) = &query.language {
+            conditions.push("language = ?");
+            params.push(turso::Value::Text(language.to_lowercase()));
+        }
+        let where_clause = if conditions.is_empty() {
+            String::new()
+        } else {
+            format!("WHERE {}", conditions.join(" AND "))
+        };
+        let sql = format!(
+            "SELECT symbol_uid, file_path, language, name, fqn, kind, signature, visibility, \
+                    def_start_line, def_start_char, def_end_line, def_end_char, is_definition, documentation, metadata \
+               FROM symbol_state {} \
+              ORDER BY file_path, def_start_line, def_start_char",
+            where_clause
+        );
+
+        let conn = ConnectionPool::checkout_arc(&self.pool).await?;
+        let mut rows = safe_query(&conn, &sql, params, "find_symbols").await?;
+
+        // The glob is matched here rather than in SQL, as SQLite's GLOB has no `**`
+        let mut symbols = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::OperationFailed {
+                message: format!("Failed to iterate symbols: {}", e),
+            })?
+        {
+            if query.limit.is_some_and(|limit| symbols.len() >= limit) {
+                break;
+            }
+            let Some(symbol) = Self::symbol_state_from_row(&row) else {
+                continue;
+            };
+            if pattern
+                .as_ref()
+                .is_some_and(|pattern| !pattern.matches(&symbol.file_path))
+            {
+                continue;
+            }
+            symbols.push(symbol);
+        }
+
+        ConnectionPool::return_connection_arc(&self.pool, conn);
+        Ok(symbols)
+    }
+
+    async fn search_symbols(
+        &self,
+        text: &str,
+        limit: usize,
+    ) -> Result<Vec<SymbolState>, DatabaseError> {
+        let mut terms = symbol_search::tokenize(text);
+        terms.sort();
+        terms.dedup();
+        if terms.is_empty() || limit == 0 {
+            return Ok(Vec::new());
+        }
+
+        let conn = ConnectionPool::checkout_arc(&self.pool).await?;
+        let mut rows = safe_query(
+            &conn,
+            "SELECT COUNT(DISTINCT symbol_uid) FROM symbol_search_term",
+            (),
+            "search_symbols.count",
+        )
+        .await?;
+        let total_symbols = match rows.next().await {
+            Ok(Some(row)) => match row.get_value(0) {
+                Ok(turso::Value::Integer(n)) => n as usize,
+                _ => 0,
+            },
+            _ => 0,
+        };
+
+        let placeholders = terms.iter().map(|_| "?").collect::<Vec<_>>().join(", ");
+        let params: Vec<turso::Value> = terms.into_iter().map(turso::Value::Text).collect();
+        let mut rows = safe_query(
+            &conn,
+            &format!(
+                "SELECT term, symbol_uid, field, frequency FROM symbol_search_term WHERE term IN ({})",
+                placeholders
+            ),
+            params,
+            "search_symbols.terms",
+        )
+        .await?;
+        let mut postings = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::OperationFailed {
+                message: format!("Failed to iterate search terms: {}", e),
+            })?
+        {
+            let (
+                Ok(turso::Value::Text(term)),
+                Ok(turso::Value::Text(symbol_uid)),
+                Ok(turso::Value::Text(field)),
+                Ok(turso::Value::Integer(frequency)),
+            ) = (
+                row.get_value(0),
+                row.get_value(1),
+                row.get_value(2),
+                row.get_value(3),
+            )
+            else {
+                continue;
+            };
+            let Some(field) = SearchField::parse(&field) else {
+                continue;
+            };
+            postings.push(TermPosting {
+                term,
+                symbol_uid,
+                field,
+                frequency: frequency as u32,
+            });
+        }
+
+        let ranked: Vec<String> = symbol_search::rank(&postings, total_symbols)
+            .into_iter()
+            .take(limit)
+            .map(|(uid, _)| uid)
+            .collect();
+        if ranked.is_empty() {
+            ConnectionPool::return_connection_arc(&self.pool, conn);
+            return Ok(Vec::new());
+        }
+
+        let placeholders = ranked.iter().map(|_| "?").collect::<Vec<_>>().join(", ");
+        let params: Vec<turso::Value> = ranked.iter().cloned().map(turso::Value::Text).collect();
+        let mut rows = safe_query(
+            &conn,
+            &format!(
+                "SELECT symbol_uid, file_path, language, name, fqn, kind, signature, visibility, \
+                        def_start_line, def_start_char, def_end_line, def_end_char, is_definition, documentation, metadata \
+                   FROM symbol_state WHERE symbol_uid IN ({})",
+                placeholders
+            ),
+            params,
+            "search_symbols.symbols",
+        )
+        .await?;
+        let mut by_uid = HashMap::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::OperationFailed {
+                message: format!("Failed to iterate symbols: {}", e),
+            })?
+        {
+            if let Some(symbol) = Self::symbol_state_from_row(&row) {
+                by_uid.insert(symbol.symbol_uid.clone(), symbol);
+            }
+        }
+
+        ConnectionPool::return_connection_arc(&self.pool, conn);
+        Ok(ranked.iter().filter_map(|uid| by_uid.remove(uid)).collect())
+    }
+
     // ===================
     // Relationship Storage & Querying
     // ===================
@@ -6354,10 +6587,8 @@ impl DatabaseBackend for SQLiteBackend {
         start_symbol: &str,
         max_depth: u32,
         relations: &[EdgeRelation],
+        direction: CallDirection,
     ) -> Result<Vec<GraphPath>, DatabaseError> {
-        // This is a simplified implementation of graph traversal
-        // In a production system, this would use a more sophisticated graph algorithm
-
         let conn = ConnectionPool::checkout_arc(&self.pool).await?;
 
         // Convert relations to string for SQL query
@@ -6371,70 +6602,93 @@ impl DatabaseBackend for SQLiteBackend {
             return Ok(Vec::new());
         }
 
-        // For simplicity, we'll do a breadth-first traversal up to max_depth
-        let mut paths = Vec::new();
-        let mut current_depth = 0;
-        let mut current_symbols = vec![start_symbol.to_string()];
-
-        while current_depth < max_depth && !current_symbols.is_empty() {
-            let mut next_symbols = Vec::new();
-
-            for symbol in &current_symbols {
-                // Build placeholders for the IN clause
-                let placeholders = relation_strs
-                    .iter()
-                    .map(|_| "?")
-                    .collect::<Vec<_>>()
-                    .join(",");
-                let sql = format!(
-                    r#"
-                    SELECT target_symbol_uid, relation
-                    FROM edge
-                    WHERE source_symbol_uid = ? AND relation IN ({})
-                    "#,
-                    placeholders
-                );
+        // Build placeholders for the IN clause
+        let placeholders = relation_strs
+            .iter()
+            .map(|_| "?")
+            .collect::<Vec<_>>()
+            .join(",");
+        let outgoing_sql = format!(
+            "SELECT target_symbol_uid, relation FROM edge WHERE source_symbol_uid = ? AND relation IN ({})",
+            placeholders
+        );
+        let incoming_sql = format!(
+            "SELECT source_symbol_uid, relation FROM edge WHERE target_symbol_uid = ? AND relation IN ({})",
+            placeholders
+        );
+        let queries: Vec<&str> = match direction {
+            CallDirection::Outgoing => vec![&outgoing_sql],
+            CallDirection::Incoming => vec![&incoming_sql],
+            CallDirection::Both => vec![&outgoing_sql, &incoming_sql],
+        };
 
-                let mut params = vec![turso::Value::Text(symbol.clone())];
-                for rel_str in &relation_strs {
-                    params.push(turso::Value::Text(rel_str.clone()));
-                }
+        // Breadth-first, so every symbol is reported once with a shortest path to it
+        let mut paths: Vec<GraphPath> = Vec::new();
+        let mut visited: HashSet<String> = HashSet::new();
+        visited.insert(start_symbol.to_string());
+        let start = GraphPath {
+            symbol_uid: start_symbol.to_string(),
+            depth: 0,
+            path: vec![start_symbol.to_string()],
+            relation_chain: Vec::new(),
+        };
+        let mut current = vec![start];
 
-                let mut rows = safe_query(&conn, &sql, params, "traverse_graph layer").await?;
+        for depth in 1..=max_depth {
+            let mut next = Vec::new();
 
-                while let Some(row) =
-                    rows.next()
-                        .await
-                        .map_err(|e| DatabaseError::OperationFailed {
-                            message: format!("Failed to iterate traversal results: {}", e),
-                        })?
-                {
-                    let target_symbol = match row.get_value(0) {
-                        Ok(turso::Value::Text(uid)) => uid,
-                        _ => continue,
-                    };
+            for from in &current {
+                for sql in &queries {
+                    let mut params = vec![turso::Value::Text(from.symbol_uid.clone())];
+                    for rel_str in &relation_strs {
+                        params.push(turso::Value::Text(rel_str.clone()));
+                    }
 
-                    let edge_type_str = match row.get_value(1) {
-                        Ok(turso::Value::Text(edge_type)) => edge_type,
-                        _ => continue,
-                    };
+                    let mut rows = safe_query(&conn, sql, params, "traverse_graph layer").await?;
 
-                    if let Ok(relation) = crate::database::EdgeRelation::from_string(&edge_type_str)
+                    while let Some(row) =
+                        rows.next()
+                            .await
+                            .map_err(|e| DatabaseError::OperationFailed {
+                                message: format!("Failed to iterate traversal results: {}", e),
+                            })?
                     {
-                        let path = GraphPath {
-                            symbol_uid: target_symbol.clone(),
-                            depth: current_depth + 1,
-                            path: vec![start_symbol.to_string(), target_symbol.clone()],
-                            relation_chain: vec![relation],
+                        let symbol_uid = match row.get_value(0) {
+                            Ok(turso::Value::Text(uid)) => uid,
+                            _ => continue,
                         };
-                        paths.push(path);
-                        next_symbols.push(target_symbol);
+                        // "none" marks an analyzed symbol without relationships
+                        if Self::is_none_uid(&symbol_uid) || !visited.insert(symbol_uid.clone()) {
+                            continue;
+                        }
+
+                        let relation = match row.get_value(1) {
+                            Ok(turso::Value::Text(rel)) => match EdgeRelation::from_string(&rel) {
+                                Ok(relation) => relation,
+                                Err(_) => continue,
+                            },
+                            _ => continue,
+                        };
+
+                        let mut path = from.path.clone();
+                        path.push(symbol_uid.clone());
+                        let mut relation_chain = from.relation_chain.clone();
+                        relation_chain.push(relation);
+                        next.push(GraphPath {
+                            symbol_uid,
+                            depth,
+                            path,
+                            relation_chain,
+                        });
                     }
                 }
             }
 
-            current_symbols = next_symbols;
-            current_depth += 1;
+            if next.is_empty() {
+                break;
+            }
+            paths.extend(next.iter().cloned());
+            current = next;
         }
 
         ConnectionPool::return_connection_arc(&self.pool, conn);
@@ -6452,22 +6706,21 @@ impl DatabaseBackend for SQLiteBackend {
         _language: &str,
         config: &str,
     ) -> Result<i64, DatabaseError> {
-        let conn = ConnectionPool::checkout_arc(&self.pool).await?;
-
-        let run_id = uuid::Uuid::new_v4().to_string();
+        // Stored as text so the returned id can be used to look the run up again
         let run_id_int = self.generate_unique_id().await?;
+        let conn = ConnectionPool::checkout_arc(&self.pool).await?;
 
-        safe_execute(
+        let inserted = safe_execute(
             &conn,
             r#"
             INSERT INTO analysis_run (
                 run_id, workspace_id, analyzer_type, analyzer_version,
                 configuration, started_at, status
             )
-            VALUES (?, '1', ?, ?, ?, ?, 'running')
+            VALUES (?, '1', ?, ?, ?, CURRENT_TIMESTAMP, 'running')
             "#,
             [
-                turso::Value::Text(run_id),
+                turso::Value::Text(run_id_int.to_string()),
                 turso::Value::Text(analyzer_name.to_string()),
                 turso::Value::Text(analyzer_version.to_string()),
                 turso::Value::Text(config.to_string()),
@@ -6477,10 +6730,82 @@ impl DatabaseBackend for SQLiteBackend {
         .await
         .map_err(|e| DatabaseError::OperationFailed {
             message: format!("Failed to create analysis run: {}", e),
-        })?;
+        });
+
+        ConnectionPool::return_connection_arc(&self.pool, conn);
+        inserted.map(|_| run_id_int)
+    }
+
+    async fn complete_analysis_run(&self, run_id: i64) -> Result<(), DatabaseError> {
+        // Buffered writes belong to the run being completed
+        self.flush_pending_writes().await?;
+        let snapshot =
+            GraphSnapshot::from_graph(&self.get_all_symbols().await?, &self.get_all_edges().await?);
+
+        let conn = ConnectionPool::checkout_arc(&self.pool).await?;
+        let result = Self::record_analysis_run_graph(&conn, &run_id.to_string(), &snapshot).await;
+        ConnectionPool::return_connection_arc(&self.pool, conn);
+        result
+    }
+
+    async fn get_analysis_run_snapshot(
+        &self,
+        run_id: i64,
+    ) -> Result<Option<GraphSnapshot>, DatabaseError> {
+        let conn = ConnectionPool::checkout_arc(&self.pool).await?;
+        let run_id = run_id.to_string();
+
+        let mut rows = safe_query(
+            &conn,
+            "SELECT status FROM analysis_run WHERE run_id = ?",
+            [turso::Value::Text(run_id.clone())],
+            "get_analysis_run_snapshot status",
+        )
+        .await?;
+        let completed = matches!(
+            rows.next().await,
+            Ok(Some(row)) if matches!(row.get_value(0), Ok(turso::Value::Text(status)) if status == "completed")
+        );
+        if !completed {
+            ConnectionPool::return_connection_arc(&self.pool, conn);
+            return Ok(None);
+        }
+
+        let mut rows = safe_query(
+            &conn,
+            "SELECT item_type, item_key, kind, digest FROM analysis_run_graph WHERE run_id = ?",
+            [turso::Value::Text(run_id)],
+            "get_analysis_run_snapshot items",
+        )
+        .await?;
+
+        let mut snapshot = GraphSnapshot::default();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::OperationFailed {
+                message: format!("Failed to iterate analysis run graph: {}", e),
+            })?
+        {
+            let text = |index| match row.get_value(index) {
+                Ok(turso::Value::Text(value)) => Some(value),
+                _ => None,
+            };
+            let (Some(item_type), Some(key), Some(kind), Some(digest)) =
+                (text(0), text(1), text(2), text(3))
+            else {
+                continue;
+            };
+            let entry = SnapshotEntry { kind, digest };
+            match item_type.as_str() {
+                "symbol" => snapshot.symbols.insert(key, entry),
+                "edge" => snapshot.edges.insert(key, entry),
+                _ => None,
+            };
+        }
 
         ConnectionPool::return_connection_arc(&self.pool, conn);
-        Ok(run_id_int)
+        Ok(Some(snapshot))
     }
 
     async fn get_analysis_progress(
@@ -6646,28 +6971,125 @@ impl DatabaseBackend for SQLiteBackend {
         Ok(())
     }
 
-    // Missing trait methods - temporary placeholder implementations
     async fn get_all_symbols(&self) -> Result<Vec<SymbolState>, DatabaseError> {
-        // Placeholder implementation - would return all symbols from all workspaces
-        debug!("DEBUG: get_all_symbols not yet implemented, returning empty list");
-        Ok(Vec::new())
-    }
+        let conn = ConnectionPool::checkout_arc(&self.pool).await?;
+        let mut rows = safe_query(
+            &conn,
+            "SELECT symbol_uid, file_path, language, name, fqn, kind, signature, visibility, \
+                    def_start_line, def_start_char, def_end_line, def_end_char, is_definition, documentation, metadata \
+               FROM symbol_state",
+            (),
+            "get_all_symbols",
+        )
+        .await?;
 
-    async fn get_all_edges(&self) -> Result<Vec<Edge>, DatabaseError> {
-        // Placeholder implementation - would return all edges from all workspaces
-        debug!("DEBUG: get_all_edges not yet implemented, returning empty list");
-        Ok(Vec::new())
+        let mut symbols = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::OperationFailed {
+                message: format!("Failed to iterate symbols: {}", e),
+            })?
+        {
+            if let Some(symbol) = Self::symbol_state_from_row(&row) {
+                symbols.push(symbol);
+            }
+        }
+
+        ConnectionPool::return_connection_arc(&self.pool, conn);
+        Ok(symbols)
     }
 
-    // ===================
-    // LSP Protocol Query Methods Implementation
-    // ===================
+    async fn get_all_edges(&self) -> Result<Vec<Edge>, DatabaseError> {
+        let conn = ConnectionPool::checkout_arc(&self.pool).await?;
+        let mut rows = safe_query(
+            &conn,
+            r#"
+            SELECT source_symbol_uid, target_symbol_uid, relation,
+                   start_line, start_char, confidence, language, metadata, edge_file_path
+            FROM edge
+            "#,
+            (),
+            "get_all_edges",
+        )
+        .await?;
 
-    async fn get_call_hierarchy_for_symbol(
-        &self,
-        workspace_id: i64,
-        symbol_uid: &str,
-    ) -> Result<Option<CallHierarchyResult>, DatabaseError> {
+        let mut edges = Vec::new();
+        while let Some(row) = rows
+            .next()
+            .await
+            .map_err(|e| DatabaseError::OperationFailed {
+                message: format!("Failed to iterate edges: {}", e),
+            })?
+        {
+            let relation = match row.get_value(2) {
+                Ok(turso::Value::Text(rel)) => match EdgeRelation::from_string(&rel) {
+                    Ok(relation) => relation,
+                    Err(_) => continue,
+                },
+                _ => continue,
+            };
+
+            edges.push(Edge {
+                relation,
+                source_symbol_uid: match row.get_value(0) {
+                    Ok(turso::Value::Text(uid)) => uid,
+                    _ => continue,
+                },
+                target_symbol_uid: match row.get_value(1) {
+                    Ok(turso::Value::Text(uid)) => {
+                        if Self::is_none_uid(&uid) {
+                            "none".to_string()
+                        } else {
+                            uid
+                        }
+                    }
+                    Ok(turso::Value::Null) => "none".to_string(),
+                    _ => continue,
+                },
+                file_path: match row.get_value(8) {
+                    Ok(turso::Value::Text(path)) if !path.is_empty() => Some(path),
+                    _ => None,
+                },
+                start_line: match row.get_value(3) {
+                    Ok(turso::Value::Text(line)) => line.parse::<u32>().ok(),
+                    Ok(turso::Value::Integer(line)) => Some(line as u32),
+                    _ => None,
+                },
+                start_char: match row.get_value(4) {
+                    Ok(turso::Value::Text(char)) => char.parse::<u32>().ok(),
+                    Ok(turso::Value::Integer(char)) => Some(char as u32),
+                    _ => None,
+                },
+                confidence: match row.get_value(5) {
+                    Ok(turso::Value::Real(conf)) => conf as f32,
+                    Ok(turso::Value::Integer(conf)) => conf as f32,
+                    _ => 1.0,
+                },
+                language: match row.get_value(6) {
+                    Ok(turso::Value::Text(lang)) => lang,
+                    _ => "unknown".to_string(),
+                },
+                metadata: match row.get_value(7) {
+                    Ok(turso::Value::Text(meta)) => Some(meta),
+                    _ => None,
+                },
+            });
+        }
+
+        ConnectionPool::return_connection_arc(&self.pool, conn);
+        Ok(edges)
+    }
+
+    // ===================
+    // LSP Protocol Query Methods Implementation
+    // ===================
+
+    async fn get_call_hierarchy_for_symbol(
+        &self,
+        workspace_id: i64,
+        symbol_uid: &str,
+    ) -> Result<Option<CallHierarchyResult>, DatabaseError> {
         info!(
             "[DEBUG] get_call_hierarchy_for_symbol ENTRY: workspace_id={}, symbol_uid={}",
             workspace_id, symbol_uid
@@ -8638,6 +9060,7 @@ impl SQLiteBackend {
                             ),
                         })?;
                 }
+                Self::replace_search_terms(conn, symbol).await?;
                 }
                 Ok(())
             }
@@ -8670,6 +9093,52 @@ impl SQLiteBackend {
         Ok(())
     }
 
+    /// Replace the full-text search terms of `symbol`, assuming caller manages the transaction.
+    async fn replace_search_terms(
+        conn: &Connection,
+        symbol: &SymbolState,
+    ) -> Result<(), DatabaseError> {
+        safe_execute_with_retry(
+            conn,
+            "DELETE FROM symbol_search_term WHERE symbol_uid = ?",
+            [turso::Value::Text(symbol.symbol_uid.clone())],
+            "search_terms.delete",
+            6,
+        )
+        .await?;
+
+        let postings = symbol_search::symbol_postings(symbol);
+        if postings.is_empty() {
+            return Ok(());
+        }
+        let placeholders = postings
+            .iter()
+            .map(|_| "(?, ?, ?, ?)")
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut params = Vec::with_capacity(postings.len() * 4);
+        for posting in postings {
+            params.extend([
+                turso::Value::Text(posting.term),
+                turso::Value::Text(posting.symbol_uid),
+                turso::Value::Text(posting.field.as_str().to_string()),
+                turso::Value::Integer(posting.frequency as i64),
+            ]);
+        }
+        safe_execute_with_retry(
+            conn,
+            &format!(
+                "INSERT INTO symbol_search_term (term, symbol_uid, field, frequency) VALUES {}",
+                placeholders
+            ),
+            params,
+            "search_terms.insert",
+            6,
+        )
+        .await?;
+        Ok(())
+    }
+
     /// Insert a single symbol directly using the provided connection, assuming caller manages the transaction.
     async fn insert_symbol_direct_within_tx(
         &self,
@@ -8737,6 +9206,22 @@ impl SQLiteBackend {
         })
     }
 
+    /// Wait until symbols and edges buffered by the single-writer task are in the database
+    pub async fn flush_pending_writes(&self) -> Result<(), DatabaseError> {
+        let (tx, rx) = oneshot::channel();
+        self.writer_tx
+            .send(WriteMsg::Flush(tx))
+            .await
+            .map_err(|_| DatabaseError::OperationFailed {
+                message: "Writer task not available (Flush)".into(),
+            })?;
+        rx.await.unwrap_or_else(|_| {
+            Err(DatabaseError::OperationFailed {
+                message: "Writer ack dropped (Flush)".into(),
+            })
+        })
+    }
+
     /// Get symbol references using a provided connection (lock-free variant)
     ///
     /// This method takes an existing database connection instead of acquiring a pool lock.
@@ -9248,6 +9733,329 @@ impl SQLiteBackend {
         Ok(report)
     }
 
+    /// Mark the analysis run completed and store the graph it left behind in one
+    /// transaction, rolled back if any statement fails
+    async fn record_analysis_run_graph(
+        conn: &Connection,
+        run_id: &str,
+        snapshot: &GraphSnapshot,
+    ) -> Result<(), DatabaseError> {
+        safe_execute_with_retry(
+            conn,
+            "BEGIN TRANSACTION",
+            (),
+            "complete_analysis_run begin",
+            6,
+        )
+        .await?;
+
+        let recorded = async {
+            let updated = safe_execute(
+                conn,
+                r#"
+                UPDATE analysis_run
+                SET status = 'completed', completed_at = CURRENT_TIMESTAMP, symbols_found = ?
+                WHERE run_id = ?
+                "#,
+                [
+                    turso::Value::Integer(snapshot.symbols.len() as i64),
+                    turso::Value::Text(run_id.to_string()),
+                ],
+                "complete_analysis_run update",
+            )
+            .await?;
+            if updated == 0 {
+                return Err(DatabaseError::OperationFailed {
+                    message: format!("Analysis run {} does not exist", run_id),
+                });
+            }
+
+            safe_execute(
+                conn,
+                "DELETE FROM analysis_run_graph WHERE run_id = ?",
+                [turso::Value::Text(run_id.to_string())],
+                "complete_analysis_run clear",
+            )
+            .await?;
+            let items = snapshot
+                .symbols
+                .iter()
+                .map(|(key, entry)| ("symbol", key, entry))
+                .chain(
+                    snapshot
+                        .edges
+                        .iter()
+                        .map(|(key, entry)| ("edge", key, entry)),
+                );
+            for (item_type, key, entry) in items {
+                safe_execute(
+                    conn,
+                    "INSERT INTO analysis_run_graph (run_id, item_type, item_key, kind, digest) VALUES (?, ?, ?, ?, ?)",
+                    [
+                        turso::Value::Text(run_id.to_string()),
+                        turso::Value::Text(item_type.to_string()),
+                        turso::Value::Text(key.clone()),
+                        turso::Value::Text(entry.kind.clone()),
+                        turso::Value::Text(entry.digest.clone()),
+                    ],
+                    "complete_analysis_run insert",
+                )
+                .await?;
+            }
+            safe_execute(conn, "COMMIT", (), "complete_analysis_run commit").await?;
+            Ok(())
+        }
+        .await;
+
+        if recorded.is_err() {
+            rollback_transaction(conn, "complete_analysis_run").await;
+        }
+        recorded
+    }
+
+    /// Enumerate the cached symbol entries listed by `cache list-keys`, ordered by file.
+    /// The content hash is the file digest embedded in the UID, empty when it has none.
+    pub async fn list_cache_keys(
+        &self,
+    ) -> Result<Vec<crate::protocol::CacheKeyInfo>, DatabaseError> {
+        use crate::symbol::parse_version_aware_uid;
+
+        let conn = ConnectionPool::checkout_arc(&self.pool).await?;
+        let rows = safe_query(
+            &conn,
+            "SELECT symbol_uid, file_path, name, kind, def_start_line, def_start_char \
+               FROM symbol_state ORDER BY file_path, symbol_uid",
+            (),
+            "list_cache_keys",
+        )
+        .await;
+        let mut rows = match rows {
+            Ok(rows) => rows,
+            Err(e) => {
+                ConnectionPool::return_connection_arc(&self.pool, conn);
+                return Err(e);
+            }
+        };
+
+        let text = |row: &turso::Row, index: usize| match row.get_value(index) {
+            Ok(turso::Value::Text(value)) => value,
+            _ => String::new(),
+        };
+        let integer = |row: &turso::Row, index: usize| match row.get_value(index) {
+            Ok(turso::Value::Integer(value)) => value,
+            _ => 0,
+        };
+
+        let mut keys = Vec::new();
+        loop {
+            let row = match rows.next().await {
+                Ok(Some(row)) => row,
+                Ok(None) => break,
+                Err(e) => {
+                    ConnectionPool::return_connection_arc(&self.pool, conn);
+                    return Err(DatabaseError::OperationFailed {
+                        message: format!("Failed to iterate cache keys: {}", e),
+                    });
+                }
+            };
+            let key = text(&row, 0);
+            if key.is_empty() {
+                continue;
+            }
+            let name = text(&row, 2);
+            keys.push(crate::protocol::CacheKeyInfo {
+                content_hash: parse_version_aware_uid(&key)
+                    .map(|(_, hash, _, _)| hash)
+                    .unwrap_or_default(),
+                file_path: text(&row, 1),
+                operation: text(&row, 3),
+                position: format!("{}:{}", integer(&row, 4), integer(&row, 5)),
+                symbol_name: (!name.is_empty()).then_some(name),
+                size_bytes: 0,
+                access_count: 0,
+                last_accessed: String::new(),
+                created_at: String::new(),
+                workspace_id: String::new(),
+                is_expired: false,
+                key,
+            });
+        }
+
+        ConnectionPool::return_connection_arc(&self.pool, conn);
+        Ok(keys)
+    }
+
+    /// Remove cached symbols (and their edges) whose source file no longer exists
+    /// under `workspace_root`, or whose UID content hash no longer matches the file.
+    ///
+    /// The entries are enumerated with [`Self::list_cache_keys`]. Dependency (`/dep/...`)
+    /// and `EXTERNAL:` entries are never pruned since they do not refer to workspace files.
+    /// With `dry_run` the stale entries are only counted.
+    pub async fn prune_stale_symbols(
+        &self,
+        workspace_root: &Path,
+        dry_run: bool,
+    ) -> Result<crate::protocol::CachePruneResult, DatabaseError> {
+        use crate::symbol::blake3_hash_file_content;
+
+        let start = Instant::now();
+        let keys = self.list_cache_keys().await?;
+
+        let mut by_file: HashMap<String, Vec<crate::protocol::CacheKeyInfo>> = HashMap::new();
+        for key in keys {
+            if key.file_path.starts_with("/dep/") || key.file_path.starts_with("EXTERNAL:") {
+                continue;
+            }
+            by_file.entry(key.file_path.clone()).or_default().push(key);
+        }
+
+        let mut result = crate::protocol::CachePruneResult {
+            workspace_path: workspace_root.to_path_buf(),
+            files_checked: by_file.len() as u64,
+            dry_run,
+            ..Default::default()
+        };
+
+        let mut stale_uids: Vec<String> = Vec::new();
+        for (file_path, keys) in by_file {
+            let absolute = if Path::new(&file_path).is_absolute() {
+                PathBuf::from(&file_path)
+            } else {
+                workspace_root.join(&file_path)
+            };
+
+            let content = match std::fs::read_to_string(&absolute) {
+                Ok(content) => content,
+                Err(_) if !absolute.exists() => {
+                    result.missing_files += 1;
+                    stale_uids.extend(keys.into_iter().map(|key| key.key));
+                    continue;
+                }
+                // Unreadable (e.g. non-UTF8) files are left alone
+                Err(_) => continue,
+            };
+
+            let current_hash = match blake3_hash_file_content(&content) {
+                Ok(hash) => hash,
+                Err(_) => continue,
+            };
+            let changed: Vec<String> = keys
+                .into_iter()
+                // Keys without an embedded digest cannot be validated
+                .filter(|key| !key.content_hash.is_empty() && key.content_hash != current_hash)
+                .map(|key| key.key)
+                .collect();
+            if !changed.is_empty() {
+                result.changed_files += 1;
+                stale_uids.extend(changed);
+            }
+        }
+
+        result.symbols_removed = stale_uids.len() as u64;
+
+        if !stale_uids.is_empty() {
+            let conn = ConnectionPool::checkout_arc(&self.pool).await?;
+            let edges_removed = if dry_run {
+                Self::count_symbol_edges(&conn, &stale_uids).await
+            } else {
+                Self::delete_symbols_in_transaction(&conn, &stale_uids).await
+            };
+            ConnectionPool::return_connection_arc(&self.pool, conn);
+            result.edges_removed = edges_removed?;
+        }
+
+        result.duration_ms = start.elapsed().as_millis() as u64;
+        Ok(result)
+    }
+
+    /// Number of edges from or to the symbols `uids`
+    async fn count_symbol_edges(conn: &Connection, uids: &[String]) -> Result<u64, DatabaseError> {
+        // One query, so an edge between two of the symbols is counted once, as it is deleted once
+        let placeholders = uids.iter().map(|_| "?").collect::<Vec<_>>().join(", ");
+        let params: Vec<turso::Value> = uids
+            .iter()
+            .chain(uids)
+            .map(|uid| turso::Value::Text(uid.clone()))
+            .collect();
+        let mut rows = safe_query(
+            conn,
+            &format!(
+                "SELECT COUNT(*) FROM edge WHERE source_symbol_uid IN ({placeholders}) OR target_symbol_uid IN ({placeholders})"
+            ),
+            params,
+            "prune_stale_symbols count edges",
+        )
+        .await?;
+        let mut edges = 0;
+        if let Ok(Some(row)) = rows.next().await {
+            if let Ok(turso::Value::Integer(count)) = row.get_value(0) {
+                edges = count as u64;
+            }
+        }
+        Ok(edges)
+    }
+
+    /// Delete the symbols `uids` with their edges and search terms in one transaction,
+    /// returning the number of edges removed. Nothing is deleted if any statement fails.
+    async fn delete_symbols_in_transaction(
+        conn: &Connection,
+        uids: &[String],
+    ) -> Result<u64, DatabaseError> {
+        safe_execute_with_retry(
+            conn,
+            "BEGIN TRANSACTION",
+            (),
+            "prune_stale_symbols begin",
+            6,
+        )
+        .await?;
+
+        let mut edges = 0;
+        for uid in uids {
+            let deleted = async {
+                let edges_deleted = safe_execute(
+                    conn,
+                    "DELETE FROM edge WHERE source_symbol_uid = ? OR target_symbol_uid = ?",
+                    [
+                        turso::Value::Text(uid.clone()),
+                        turso::Value::Text(uid.clone()),
+                    ],
+                    "prune_stale_symbols delete edges",
+                )
+                .await?;
+                safe_execute(
+                    conn,
+                    "DELETE FROM symbol_state WHERE symbol_uid = ?",
+                    [turso::Value::Text(uid.clone())],
+                    "prune_stale_symbols delete symbol",
+                )
+                .await?;
+                safe_execute(
+                    conn,
+                    "DELETE FROM symbol_search_term WHERE symbol_uid = ?",
+                    [turso::Value::Text(uid.clone())],
+                    "prune_stale_symbols delete search terms",
+                )
+                .await?;
+                Ok::<_, DatabaseError>(edges_deleted)
+            }
+            .await;
+            match deleted {
+                Ok(edges_deleted) => edges += edges_deleted,
+                Err(e) => {
+                    rollback_transaction(conn, "prune_stale_symbols").await;
+                    return Err(e);
+                }
+            }
+        }
+
+        if let Err(e) = safe_execute(conn, "COMMIT", (), "prune_stale_symbols commit").await {
+            rollback_transaction(conn, "prune_stale_symbols commit").await;
+            return Err(e);
+        }
+        Ok(edges)
+    }
+
     // ===================
     // Symbol Auto-Creation Helper Methods
     // ===================
@@ -10403,6 +11211,141 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_find_symbols_by_kind_visibility_and_glob() {
+        use crate::symbol::{SymbolKind, Visibility};
+
+        let config = DatabaseConfig {
+            temporary: true,
+            ..Default::default()
+        };
+        let backend = SQLiteBackend::new(config).await.unwrap();
+
+        let symbol =
+            |uid: &str, file_path: &str, language: &str, kind: &str, vis: &str| SymbolState {
+                symbol_uid: uid.to_string(),
+                file_path: file_path.to_string(),
+                language: language.to_string(),
+                name: uid.to_string(),
+                fqn: None,
+                kind: kind.to_string(),
+                signature: None,
+                visibility: Some(vis.to_string()),
+                def_start_line: 1,
+                def_start_char: 0,
+                def_end_line: 2,
+                def_end_char: 1,
+                is_definition: true,
+                documentation: None,
+                metadata: None,
+            };
+        backend
+            .store_symbols(&[
+                symbol("Store", "src/db/store.rs", "rust", "trait", "public"),
+                symbol("Backend", "src/lib.rs", "rust", "trait", "public"),
+                symbol("Helper", "src/db/helper.rs", "rust", "trait", "private"),
+                symbol("run", "src/db/store.rs", "rust", "function", "public"),
+                symbol("Store", "web/store.ts", "typescript", "interface", "export"),
+            ])
+            .await
+            .unwrap();
+        backend.flush_pending_writes().await.unwrap();
+
+        let public_traits = SymbolQuery {
+            kind: Some(SymbolKind::Trait),
+            visibility: Some(Visibility::Public),
+            ..Default::default()
+        };
+        let found = backend.find_symbols(&public_traits).await.unwrap();
+        let names: Vec<&str> = found.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["Store", "Backend"]);
+
+        let in_db = SymbolQuery {
+            file_glob: Some("src/db/**".to_string()),
+            ..public_traits.clone()
+        };
+        let found = backend.find_symbols(&in_db).await.unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].file_path, "src/db/store.rs");
+
+        let typescript = SymbolQuery {
+            language: Some("typescript".to_string()),
+            ..Default::default()
+        };
+        let found = backend.find_symbols(&typescript).await.unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].kind, "interface");
+
+        let limited = SymbolQuery {
+            limit: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(backend.find_symbols(&limited).await.unwrap().len(), 2);
+
+        let invalid = SymbolQuery {
+            file_glob: Some("src/[".to_string()),
+            ..Default::default()
+        };
+        assert!(backend.find_symbols(&invalid).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_search_symbols_ranks_and_follows_updates() {
+        let config = DatabaseConfig {
+            temporary: true,
+            ..Default::default()
+        };
+        let backend = SQLiteBackend::new(config).await.unwrap();
+
+        let symbol = |uid: &str, name: &str, doc: &str| SymbolState {
+            symbol_uid: uid.to_string(),
+            file_path: "src/cache.rs".to_string(),
+            language: "rust".to_string(),
+            name: name.to_string(),
+            fqn: Some(format!("store::{}", name)),
+            kind: "function".to_string(),
+            signature: None,
+            visibility: Some("public".to_string()),
+            def_start_line: 1,
+            def_start_char: 0,
+            def_end_line: 2,
+            def_end_char: 1,
+            is_definition: true,
+            documentation: Some(doc.to_string()),
+            metadata: None,
+        };
+        backend
+            .store_symbols(&[
+                symbol("a", "prune_entries", "Drop stale entries from the cache."),
+                symbol(
+                    "b",
+                    "load",
+                    "Load the workspace cache and prune it if needed.",
+                ),
+                symbol("c", "save", "Write the index to disk."),
+            ])
+            .await
+            .unwrap();
+        backend.flush_pending_writes().await.unwrap();
+
+        let found = backend.search_symbols("prune cache", 10).await.unwrap();
+        let names: Vec<&str> = found.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["prune_entries", "load"]);
+        assert_eq!(backend.search_symbols("prune", 1).await.unwrap().len(), 1);
+        assert!(backend.search_symbols("!!", 10).await.unwrap().is_empty());
+
+        // Re-storing a symbol replaces its terms
+        backend
+            .store_symbols(&[symbol("c", "save", "Persist the cache snapshot.")])
+            .await
+            .unwrap();
+        backend.flush_pending_writes().await.unwrap();
+        let found = backend.search_symbols("snapshot", 10).await.unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].name, "save");
+        assert!(backend.search_symbols("disk", 10).await.unwrap().is_empty());
+    }
+
     #[tokio::test]
     async fn test_edge_storage_and_querying() {
         let config = DatabaseConfig {
@@ -10458,10 +11401,28 @@ mod tests {
 
         // Test traverse graph
         let paths = backend
-            .traverse_graph("source_symbol_1", 2, &[EdgeRelation::Calls])
+            .traverse_graph(
+                "source_symbol_1",
+                2,
+                &[EdgeRelation::Calls],
+                CallDirection::Outgoing,
+            )
             .await
             .unwrap();
         assert!(!paths.is_empty());
+
+        let callers = backend
+            .traverse_graph(
+                "target_symbol_1",
+                2,
+                &[EdgeRelation::Calls],
+                CallDirection::Incoming,
+            )
+            .await
+            .unwrap();
+        assert_eq!(callers.len(), 1);
+        assert_eq!(callers[0].depth, 1);
+        assert_eq!(callers[0].path, vec!["target_symbol_1", "source_symbol_1"]);
     }
 
     #[tokio::test]
@@ -10722,7 +11683,12 @@ mod tests {
 
         // Test graph traversal from base class
         let inheritance_paths = backend
-            .traverse_graph("base_class", 2, &[EdgeRelation::InheritsFrom])
+            .traverse_graph(
+                "base_class",
+                2,
+                &[EdgeRelation::InheritsFrom],
+                CallDirection::Outgoing,
+            )
             .await
             .unwrap();
         // This should be empty since we're looking for outgoing inheritance from base class
@@ -10940,6 +11906,94 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_prune_stale_symbols_removes_deleted_files(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        use crate::symbol::blake3_hash_file_content;
+
+        let config = DatabaseConfig {
+            temporary: true,
+            ..Default::default()
+        };
+        let backend = SQLiteBackend::new(config).await?;
+        let workspace = tempdir()?;
+
+        let live_content = "fn live() {}\n";
+        fs::create_dir_all(workspace.path().join("src"))?;
+        fs::write(workspace.path().join("src/live.rs"), live_content)?;
+        let live_hash = blake3_hash_file_content(live_content)?;
+
+        let symbol = |uid: String, file_path: &str, name: &str| SymbolState {
+            symbol_uid: uid,
+            file_path: file_path.to_string(),
+            language: "rust".to_string(),
+            name: name.to_string(),
+            fqn: None,
+            kind: "function".to_string(),
+            signature: None,
+            visibility: None,
+            def_start_line: 1,
+            def_start_char: 0,
+            def_end_line: 1,
+            def_end_char: 12,
+            is_definition: true,
+            documentation: None,
+            metadata: None,
+        };
+        let live_uid = format!("src/live.rs:{}:live:1", live_hash);
+        let gone_uid = "src/gone.rs:deadbeef:gone:1".to_string();
+        let gone_too_uid = "src/gone.rs:deadbeef:gone_too:1".to_string();
+        backend
+            .store_symbols(&[
+                symbol(live_uid.clone(), "src/live.rs", "live"),
+                symbol(gone_uid.clone(), "src/gone.rs", "gone"),
+                symbol(gone_too_uid.clone(), "src/gone.rs", "gone_too"),
+            ])
+            .await?;
+        let edge = |source: &str, target: &str| Edge {
+            relation: EdgeRelation::Calls,
+            source_symbol_uid: source.to_string(),
+            target_symbol_uid: target.to_string(),
+            file_path: Some("src/live.rs".to_string()),
+            start_line: Some(1),
+            start_char: Some(0),
+            confidence: 1.0,
+            language: "rust".to_string(),
+            metadata: None,
+        };
+        // The second edge joins two stale symbols and must be counted once
+        backend
+            .store_edges(&[edge(&live_uid, &gone_uid), edge(&gone_uid, &gone_too_uid)])
+            .await?;
+
+        let keys = backend.list_cache_keys().await?;
+        assert_eq!(keys.len(), 3);
+        assert_eq!(keys[0].key, gone_uid);
+        assert_eq!(keys[2].content_hash, live_hash);
+
+        // Dry run reports but keeps the stale entries
+        let report = backend.prune_stale_symbols(workspace.path(), true).await?;
+        assert!(report.dry_run);
+        assert_eq!(report.missing_files, 1);
+        assert_eq!(report.symbols_removed, 2);
+        assert_eq!(report.edges_removed, 2);
+        assert_eq!(backend.get_all_symbols().await?.len(), 3);
+
+        let report = backend.prune_stale_symbols(workspace.path(), false).await?;
+        assert_eq!(report.files_checked, 2);
+        assert_eq!(report.missing_files, 1);
+        assert_eq!(report.changed_files, 0);
+        assert_eq!(report.symbols_removed, 2);
+        assert_eq!(report.edges_removed, 2);
+
+        let remaining = backend.get_all_symbols().await?;
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].symbol_uid, live_uid);
+        assert!(backend.get_all_edges().await?.is_empty());
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_real_analysis_progress_tracking() -> Result<(), Box<dyn std::error::Error>> {
         let config = DatabaseConfig {
@@ -11086,7 +12140,10 @@ mod tests {
         assert!(references.is_empty());
 
         // Test traverse graph with empty relations
-        let paths = backend.traverse_graph("any_symbol", 2, &[]).await.unwrap();
+        let paths = backend
+            .traverse_graph("any_symbol", 2, &[], CallDirection::Outgoing)
+            .await
+            .unwrap();
         assert!(paths.is_empty());
     }
     async fn test_none_refs_edge_excludes_symbol_from_planner(
@@ -0,0 +1,178 @@
+//! Full-text search over symbol names, FQNs and documentation
+//!
+//! The Turso engine has no FTS5 module, so the index is kept in a plain table,
+//! `symbol_search_term`, holding one row per (term, symbol, field). The writer replaces the
+//! rows of each symbol it stores, which keeps the index current without ever rebuilding it.
+//! Matches are ranked with BM25, weighting name matches above FQN and documentation matches.
+
+use std::collections::{HashMap, HashSet};
+
+use crate::database::SymbolState;
+
+/// BM25 term frequency saturation
+const K1: f64 = 1.2;
+
+/// Part of a symbol a term was taken from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SearchField {
+    Name,
+    Fqn,
+    Documentation,
+}
+
+impl SearchField {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SearchField::Name => "name",
+            SearchField::Fqn => "fqn",
+            SearchField::Documentation => "doc",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "name" => Some(SearchField::Name),
+            "fqn" => Some(SearchField::Fqn),
+            "doc" => Some(SearchField::Documentation),
+            _ => None,
+        }
+    }
+
+    fn weight(&self) -> f64 {
+        match self {
+            SearchField::Name => 3.0,
+            SearchField::Fqn => 1.5,
+            SearchField::Documentation => 1.0,
+        }
+    }
+}
+
+/// One row of the term index
+#[derive(Debug, Clone, PartialEq)]
+pub struct TermPosting {
+    pub term: String,
+    pub symbol_uid: String,
+    pub field: SearchField,
+    pub frequency: u32,
+}
+
+/// Split text into lowercase terms. Identifiers are split at `_`, `::` and similar
+/// separators and at camelCase boundaries, so `parseHTTPRequest` yields `parse`, `http`
+/// and `request`. Single characters are dropped.
+pub fn tokenize(text: &str) -> Vec<String> {
+    let mut terms = Vec::new();
+    for word in text.split(|c: char| !c.is_alphanumeric()) {
+        let chars: Vec<char> = word.chars().collect();
+        let mut start = 0;
+        for i in 1..chars.len() {
+            let (prev, cur) = (chars[i - 1], chars[i]);
+            let next_is_lower = chars.get(i + 1).is_some_and(|c| c.is_lowercase());
+            let boundary = (prev.is_lowercase() && cur.is_uppercase())
+                || (prev.is_uppercase() && cur.is_uppercase() && next_is_lower)
+                || (prev.is_alphabetic() != cur.is_alphabetic());
+            if boundary {
+                push_term(&mut terms, &chars[start..i]);
+                start = i;
+            }
+        }
+        push_term(&mut terms, &chars[start..]);
+    }
+    terms
+}
+
+fn push_term(terms: &mut Vec<String>, chars: &[char]) {
+    if chars.len() > 1 {
+        terms.push(chars.iter().collect::<String>().to_lowercase());
+    }
+}
+
+/// Terms of a symbol's name, FQN and documentation with their frequencies
+pub fn symbol_postings(symbol: &SymbolState) -> Vec<TermPosting> {
+    let fields = [
+        (SearchField::Name, Some(symbol.name.as_str())),
+        (SearchField::Fqn, symbol.fqn.as_deref()),
+        (SearchField::Documentation, symbol.documentation.as_deref()),
+    ];
+
+    let mut postings = Vec::new();
+    for (field, text) in fields {
+        let mut counts: HashMap<String, u32> = HashMap::new();
+        for term in tokenize(text.unwrap_or_default()) {
+            *counts.entry(term).or_default() += 1;
+        }
+        let mut counts: Vec<_> = counts.into_iter().collect();
+        counts.sort();
+        postings.extend(counts.into_iter().map(|(term, frequency)| TermPosting {
+            term,
+            symbol_uid: symbol.symbol_uid.clone(),
+            field,
+            frequency,
+        }));
+    }
+    postings
+}
+
+/// Rank symbols by BM25 over the postings of the query terms. `total_symbols` is the number
+/// of indexed symbols. Returns symbol UIDs with their scores, best first.
+pub fn rank(postings: &[TermPosting], total_symbols: usize) -> Vec<(String, f64)> {
+    let mut symbols_per_term: HashMap<&str, HashSet<&str>> = HashMap::new();
+    for posting in postings {
+        symbols_per_term
+            .entry(&posting.term)
+            .or_default()
+            .insert(&posting.symbol_uid);
+    }
+
+    let n = total_symbols.max(1) as f64;
+    let mut scores: HashMap<&str, f64> = HashMap::new();
+    for posting in postings {
+        let df = symbols_per_term[posting.term.as_str()].len() as f64;
+        let idf = (1.0 + (n - df + 0.5) / (df + 0.5)).ln();
+        let tf = posting.frequency as f64;
+        *scores.entry(&posting.symbol_uid).or_default() +=
+            posting.field.weight() * idf * tf * (K1 + 1.0) / (tf + K1);
+    }
+
+    let mut ranked: Vec<(String, f64)> = scores
+        .into_iter()
+        .map(|(uid, score)| (uid.to_string(), score))
+        .collect();
+    ranked.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    ranked
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tokenize_splits_identifiers() {
+        assert_eq!(
+            tokenize("parseHTTPRequest get_all_symbols crate::db::Store v2 a"),
+            vec!["parse", "http", "request", "get", "all", "symbols", "crate", "db", "store"]
+        );
+        assert_eq!(
+            tokenize("Returns the UTF-8 path."),
+            vec!["returns", "the", "utf", "path"]
+        );
+    }
+
+    #[test]
+    fn test_rank_prefers_name_matches_and_rare_terms() {
+        let posting = |term: &str, uid: &str, field, frequency| TermPosting {
+            term: term.to_string(),
+            symbol_uid: uid.to_string(),
+            field,
+            frequency,
+        };
+        let postings = vec![
+            posting("cache", "named", SearchField::Name, 1),
+            posting("cache", "documented", SearchField::Documentation, 1),
+            posting("cache", "both", SearchField::Documentation, 1),
+            posting("prune", "both", SearchField::Documentation, 1),
+        ];
+        let ranked = rank(&postings, 10);
+        let order: Vec<&str> = ranked.iter().map(|(uid, _)| uid.as_str()).collect();
+        assert_eq!(order, vec!["named", "both", "documented"]);
+    }
+}
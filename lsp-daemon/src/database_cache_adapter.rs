@@ -103,6 +103,32 @@ impl BackendType {
         }
     }
 
+    /// Enumerate the cached entries, ordered by file
+    pub async fn list_cache_keys(
+        &self,
+    ) -> Result<Vec<crate::protocol::CacheKeyInfo>, anyhow::Error> {
+        match self {
+            BackendType::SQLite(db) => db
+                .list_cache_keys()
+                .await
+                .map_err(|e| anyhow::anyhow!("Database error: {}", e)),
+        }
+    }
+
+    /// Remove cached entries for workspace files that were deleted or changed
+    pub async fn prune_stale_entries(
+        &self,
+        workspace_root: &std::path::Path,
+        dry_run: bool,
+    ) -> Result<crate::protocol::CachePruneResult, anyhow::Error> {
+        match self {
+            BackendType::SQLite(db) => db
+                .prune_stale_symbols(workspace_root, dry_run)
+                .await
+                .map_err(|e| anyhow::anyhow!("Database error: {}", e)),
+        }
+    }
+
     /// Export underlying database into a standalone file (VACUUM INTO when available).
     pub async fn export_to(&self, out: &std::path::Path) -> Result<usize, anyhow::Error> {
         match self {
@@ -9,21 +9,27 @@
 //! - Multi-workspace monitoring with configurable patterns
 //! - Efficient modification time tracking
 //! - Common directory skipping (.git, node_modules, target, etc.)
+//! - Optional `.gitignore` support; ignored directories are never descended into
 //! - Configurable poll intervals and batch sizes
+//! - Debouncing that coalesces bursts of events for the same file into one
 //! - Graceful shutdown and error handling
 
 use anyhow::{anyhow, Result};
-use ignore::WalkBuilder;
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::{Match, WalkBuilder};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
-use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::sync::mpsc;
 use tokio::time::{interval, sleep};
 use tracing::{debug, error, info, trace, warn};
 
+/// Longest a burst of changes is held back, in debounce intervals from its first event
+const MAX_DEBOUNCE_INTERVALS: u32 = 10;
+
 /// Configuration for the file watcher
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FileWatcherConfig {
@@ -39,19 +45,33 @@ pub struct FileWatcherConfig {
     /// File patterns to include (empty = include all)
     pub include_patterns: Vec<String>,
 
+    /// Skip files and directories ignored by `.gitignore` files in the workspace
+    #[serde(default = "default_respect_gitignore")]
+    pub respect_gitignore: bool,
+
+    /// Additional ignore patterns in `.gitignore` syntax, applied even when
+    /// `respect_gitignore` is off
+    #[serde(default)]
+    pub custom_ignores: Vec<String>,
+
     /// Maximum file size to monitor (bytes)
     pub max_file_size_bytes: u64,
 
     /// Batch size for processing file events
     pub event_batch_size: usize,
 
-    /// Debounce interval to avoid rapid-fire events (milliseconds)
+    /// Quiet period to wait for before sending events (milliseconds). Events for the same
+    /// file within the window are coalesced into one.
     pub debounce_interval_ms: u64,
 
     /// Enable detailed logging for debugging
     pub debug_logging: bool,
 }
 
+fn default_respect_gitignore() -> bool {
+    true
+}
+
 impl Default for FileWatcherConfig {
     fn default() -> Self {
         Self {
@@ -88,7 +108,9 @@ impl Default for FileWatcherConfig {
                 "package-lock.json".to_string(),
                 "yarn.lock".to_string(),
             ],
-            include_patterns: vec![],              // Empty = include all
+            include_patterns: vec![], // Empty = include all
+            respect_gitignore: true,
+            custom_ignores: vec![],
             max_file_size_bytes: 10 * 1024 * 1024, // 10MB max
             event_batch_size: 100,
             debounce_interval_ms: 500, // 500ms debounce
@@ -97,6 +119,19 @@ impl Default for FileWatcherConfig {
     }
 }
 
+impl FileWatcherConfig {
+    /// Quiet period to wait for before sending events
+    pub fn debounce(&self) -> Duration {
+        Duration::from_millis(self.debounce_interval_ms)
+    }
+
+    /// Longest time events are held back while files keep changing, so a steady stream of
+    /// writes cannot delay them forever
+    pub fn max_debounce_wait(&self) -> Duration {
+        self.debounce() * MAX_DEBOUNCE_INTERVALS
+    }
+}
+
 /// Type of file system event detected
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum FileEventType {
@@ -145,11 +180,141 @@ impl FileEvent {
     }
 }
 
+/// Collapses the events of a debounce window to at most one event per file
+#[derive(Debug, Default)]
+struct EventCoalescer {
+    /// Pending events in the order their files first changed
+    events: Vec<FileEvent>,
+    /// Index into `events` by file path
+    index: HashMap<PathBuf, usize>,
+    /// Number of events merged away, counting both events of a dropped create/delete pair
+    coalesced: u64,
+}
+
+impl EventCoalescer {
+    fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    /// Add an event, merging it with a pending event for the same file. A file created and
+    /// then modified is reported as modified, a file modified and then deleted as deleted,
+    /// and a file deleted and recreated as modified. A file created and deleted within the
+    /// window is dropped altogether.
+    fn push(&mut self, event: FileEvent) {
+        let Some(&i) = self.index.get(&event.file_path) else {
+            self.index
+                .insert(event.file_path.clone(), self.events.len());
+            self.events.push(event);
+            return;
+        };
+
+        self.coalesced += 1;
+        let event_type = match (&self.events[i].event_type, &event.event_type) {
+            (FileEventType::Created, FileEventType::Deleted) => None,
+            (_, FileEventType::Deleted) => Some(FileEventType::Deleted),
+            _ => Some(FileEventType::Modified),
+        };
+        match event_type {
+            Some(event_type) => {
+                self.events[i] = FileEvent {
+                    event_type,
+                    ..event
+                }
+            }
+            None => {
+                self.coalesced += 1;
+                self.index.remove(&event.file_path);
+                self.events.remove(i);
+                for index in self.index.values_mut() {
+                    if *index > i {
+                        *index -= 1;
+                    }
+                }
+            }
+        }
+    }
+
+    fn drain(&mut self) -> Vec<FileEvent> {
+        self.index.clear();
+        std::mem::take(&mut self.events)
+    }
+}
+
+/// `.gitignore` files and `custom_ignores` of a workspace. A directory's `.gitignore` is
+/// read the first time a path inside it is checked.
+struct IgnoreRules {
+    workspace_root: PathBuf,
+    respect_gitignore: bool,
+    custom: Gitignore,
+    gitignores: HashMap<PathBuf, Gitignore>,
+    /// Paths found ignored during the current scan
+    ignored: Vec<PathBuf>,
+}
+
+impl IgnoreRules {
+    fn new(workspace_root: &Path, config: &FileWatcherConfig) -> Self {
+        let mut builder = GitignoreBuilder::new(workspace_root);
+        for pattern in &config.custom_ignores {
+            if let Err(e) = builder.add_line(None, pattern) {
+                warn!("Invalid ignore pattern {:?}: {}", pattern, e);
+            }
+        }
+        let custom = builder.build().unwrap_or_else(|e| {
+            warn!("Failed to build custom ignore patterns: {}", e);
+            Gitignore::empty()
+        });
+
+        Self {
+            workspace_root: workspace_root.to_path_buf(),
+            respect_gitignore: config.respect_gitignore,
+            custom,
+            gitignores: HashMap::new(),
+            ignored: Vec::new(),
+        }
+    }
+
+    /// Whether `path` is ignored. Its parent directories are not checked, as the walk never
+    /// descends into an ignored directory.
+    fn is_ignored(&mut self, path: &Path, is_dir: bool) -> bool {
+        if self.custom.matched(path, is_dir).is_ignore() {
+            return true;
+        }
+        if !self.respect_gitignore {
+            return false;
+        }
+
+        // The closest `.gitignore` with a matching rule decides
+        for dir in path.ancestors().skip(1) {
+            if !dir.starts_with(&self.workspace_root) {
+                break;
+            }
+            let gitignore = self
+                .gitignores
+                .entry(dir.to_path_buf())
+                .or_insert_with(|| Gitignore::new(dir.join(".gitignore")).0);
+            match gitignore.matched(path, is_dir) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            }
+        }
+        false
+    }
+}
+
 /// Tracks the state of files being monitored
 #[derive(Debug)]
 struct FileTracker {
     /// Map from file path to (modification_time, file_size)
     files: HashMap<PathBuf, (u64, u64)>,
+    /// Ignored files and directories found by the last scan
+    ignored: HashSet<PathBuf>,
+    /// Ignored paths that appeared, counted across scans
+    events_ignored: Arc<AtomicU64>,
     /// Workspace root this tracker monitors
     workspace_root: PathBuf,
     /// Configuration
@@ -160,6 +325,8 @@ impl FileTracker {
     fn new(workspace_root: PathBuf, config: FileWatcherConfig) -> Self {
         Self {
             files: HashMap::new(),
+            ignored: HashSet::new(),
+            events_ignored: Arc::new(AtomicU64::new(0)),
             workspace_root,
             config,
         }
@@ -190,11 +357,32 @@ impl FileTracker {
         // CRITICAL: Disable parent directory discovery to prevent climbing into junction cycles
         builder.parents(false);
 
-        // For file watching, we typically want to respect gitignore
-        builder.git_ignore(true);
+        // Ignore rules are applied by `IgnoreRules` below, so ignored paths can be counted
+        builder.git_ignore(false);
         builder.git_global(false); // Skip global gitignore for performance
         builder.git_exclude(false); // Skip .git/info/exclude for performance
 
+        // Ignored directories are pruned here, before their contents are listed or stat'ed
+        let rules = Arc::new(Mutex::new(IgnoreRules::new(
+            &self.workspace_root,
+            &self.config,
+        )));
+        let filter_rules = Arc::clone(&rules);
+        builder.filter_entry(move |entry| {
+            if entry.depth() == 0 {
+                return true;
+            }
+            let Ok(mut rules) = filter_rules.lock() else {
+                return true;
+            };
+            let is_dir = entry.file_type().is_some_and(|ft| ft.is_dir());
+            if rules.is_ignored(entry.path(), is_dir) {
+                rules.ignored.push(entry.path().to_path_buf());
+                return false;
+            }
+            true
+        });
+
         // Use single thread for file watcher to avoid overwhelming the system
         builder.threads(1);
 
@@ -338,6 +526,17 @@ impl FileTracker {
         // Update our tracking
         self.files = new_files;
 
+        // Count ignored paths that appeared since the last scan. An ignored directory counts
+        // once, however many files it holds.
+        let ignored: HashSet<PathBuf> = match rules.lock() {
+            Ok(mut rules) => std::mem::take(&mut rules.ignored).into_iter().collect(),
+            Err(_) => HashSet::new(),
+        };
+        let newly_ignored = ignored.difference(&self.ignored).count() as u64;
+        self.events_ignored
+            .fetch_add(newly_ignored, Ordering::Relaxed);
+        self.ignored = ignored;
+
         if self.config.debug_logging && !events.is_empty() {
             debug!(
                 "Detected {} changes in workspace {:?}",
@@ -444,6 +643,10 @@ pub struct FileWatcher {
     shutdown: Arc<AtomicBool>,
     /// Background task handle
     watch_task: Option<tokio::task::JoinHandle<()>>,
+    /// Events merged into another event for the same file before being sent
+    events_coalesced: Arc<AtomicU64>,
+    /// Changes to ignored paths that were not reported
+    events_ignored: Arc<AtomicU64>,
 }
 
 impl FileWatcher {
@@ -458,6 +661,8 @@ impl FileWatcher {
             event_receiver: Some(event_receiver),
             shutdown: Arc::new(AtomicBool::new(false)),
             watch_task: None,
+            events_coalesced: Arc::new(AtomicU64::new(0)),
+            events_ignored: Arc::new(AtomicU64::new(0)),
         }
     }
 
@@ -486,7 +691,8 @@ impl FileWatcher {
 
         info!("Adding workspace for file watching: {:?}", canonical_root);
 
-        let tracker = FileTracker::new(canonical_root.clone(), self.config.clone());
+        let mut tracker = FileTracker::new(canonical_root.clone(), self.config.clone());
+        tracker.events_ignored = Arc::clone(&self.events_ignored);
         self.trackers.insert(canonical_root, tracker);
 
         Ok(())
@@ -530,9 +736,10 @@ impl FileWatcher {
         let event_sender = self.event_sender.clone();
         let trackers = std::mem::take(&mut self.trackers);
         let config = self.config.clone();
+        let events_coalesced = Arc::clone(&self.events_coalesced);
 
         let task = tokio::spawn(async move {
-            Self::watch_loop(config, trackers, event_sender, shutdown).await;
+            Self::watch_loop(config, trackers, event_sender, shutdown, events_coalesced).await;
         });
 
         self.watch_task = Some(task);
@@ -577,6 +784,8 @@ impl FileWatcher {
             total_files_tracked: total_files,
             is_running: self.watch_task.is_some() && !self.shutdown.load(Ordering::Relaxed),
             poll_interval_secs: self.config.poll_interval_secs,
+            events_coalesced: self.events_coalesced.load(Ordering::Relaxed),
+            events_ignored: self.events_ignored.load(Ordering::Relaxed),
         }
     }
 
@@ -586,9 +795,10 @@ impl FileWatcher {
         mut trackers: HashMap<PathBuf, FileTracker>,
         event_sender: mpsc::UnboundedSender<Vec<FileEvent>>,
         shutdown: Arc<AtomicBool>,
+        events_coalesced: Arc<AtomicU64>,
     ) {
         let mut interval_timer = interval(Duration::from_secs(config.poll_interval_secs));
-        let mut event_buffer = Vec::new();
+        let mut pending = EventCoalescer::default();
 
         debug!("File watcher loop started");
 
@@ -599,70 +809,80 @@ impl FileWatcher {
                 trace!("File watcher tick - scanning {} workspaces", trackers.len());
             }
 
-            // Scan all workspaces for changes
-            for (workspace_root, tracker) in &mut trackers {
-                match tracker.scan_for_changes().await {
-                    Ok(mut events) => {
-                        if !events.is_empty() {
-                            event_buffer.append(&mut events);
-                        }
-                    }
-                    Err(e) => {
-                        error!(
-                            "Error scanning workspace {:?} for changes: {}",
-                            workspace_root, e
-                        );
-                    }
-                }
+            // Keep rescanning while files are still changing, so a burst of writes (a branch
+            // switch, a bulk save) is sent once it settles, with one event per file. Files that
+            // never settle are sent anyway once the first event has waited the maximum
+            let mut changed = Self::scan_workspaces(&mut trackers, &mut pending, &shutdown).await;
+            let first_event = Instant::now();
+            while changed
+                && !config.debounce().is_zero()
+                && first_event.elapsed() < config.max_debounce_wait()
+                && pending.len() < config.event_batch_size
+                && !shutdown.load(Ordering::Relaxed)
+            {
+                sleep(config.debounce()).await;
+                changed = Self::scan_workspaces(&mut trackers, &mut pending, &shutdown).await;
+            }
 
-                // Yield control to prevent blocking
-                tokio::task::yield_now().await;
+            if pending.coalesced > 0 {
+                events_coalesced.fetch_add(pending.coalesced, Ordering::Relaxed);
+                pending.coalesced = 0;
+            }
 
-                // Check shutdown signal frequently
-                if shutdown.load(Ordering::Relaxed) {
+            if !pending.is_empty() {
+                let batch = pending.drain();
+                if config.debug_logging {
+                    debug!("Sending batch of {} file events", batch.len());
+                }
+
+                if event_sender.send(batch).is_err() {
+                    error!("Failed to send file events - receiver dropped");
                     break;
                 }
             }
+        }
 
-            // Send accumulated events if we have any
-            if !event_buffer.is_empty() {
-                // Apply debouncing by batching events
-                if event_buffer.len() >= config.event_batch_size {
-                    let batch = std::mem::take(&mut event_buffer);
-
-                    if config.debug_logging {
-                        debug!("Sending batch of {} file events", batch.len());
-                    }
+        // Send any remaining events before shutting down
+        if !pending.is_empty() {
+            let _ = event_sender.send(pending.drain());
+        }
 
-                    if event_sender.send(batch).is_err() {
-                        error!("Failed to send file events - receiver dropped");
-                        break;
-                    }
-                } else if config.debounce_interval_ms > 0 {
-                    // Wait for debounce interval before sending smaller batches
-                    sleep(Duration::from_millis(config.debounce_interval_ms)).await;
-
-                    let batch = std::mem::take(&mut event_buffer);
-                    if !batch.is_empty() {
-                        if config.debug_logging {
-                            debug!("Sending debounced batch of {} file events", batch.len());
-                        }
+        debug!("File watcher loop terminated");
+    }
 
-                        if event_sender.send(batch).is_err() {
-                            error!("Failed to send debounced file events - receiver dropped");
-                            break;
-                        }
+    /// Scan all workspaces once, adding their events to `pending`. Returns whether
+    /// anything changed.
+    async fn scan_workspaces(
+        trackers: &mut HashMap<PathBuf, FileTracker>,
+        pending: &mut EventCoalescer,
+        shutdown: &AtomicBool,
+    ) -> bool {
+        let mut changed = false;
+        for (workspace_root, tracker) in trackers.iter_mut() {
+            match tracker.scan_for_changes().await {
+                Ok(events) => {
+                    changed |= !events.is_empty();
+                    for event in events {
+                        pending.push(event);
                     }
                 }
+                Err(e) => {
+                    error!(
+                        "Error scanning workspace {:?} for changes: {}",
+                        workspace_root, e
+                    );
+                }
             }
-        }
 
-        // Send any remaining events before shutting down
-        if !event_buffer.is_empty() {
-            let _ = event_sender.send(event_buffer);
-        }
+            // Yield control to prevent blocking
+            tokio::task::yield_now().await;
 
-        debug!("File watcher loop terminated");
+            // Check shutdown signal frequently
+            if shutdown.load(Ordering::Relaxed) {
+                break;
+            }
+        }
+        changed
     }
 }
 
@@ -673,6 +893,12 @@ pub struct FileWatcherStats {
     pub total_files_tracked: usize,
     pub is_running: bool,
     pub poll_interval_secs: u64,
+    /// Events merged into another event for the same file within a debounce window
+    #[serde(default)]
+    pub events_coalesced: u64,
+    /// New files and directories skipped because they are ignored
+    #[serde(default)]
+    pub events_ignored: u64,
 }
 
 impl Drop for FileWatcher {
@@ -792,6 +1018,45 @@ mod tests {
         assert!(events[0].file_path.ends_with("src.rs"));
     }
 
+    #[tokio::test]
+    async fn test_gitignored_paths_are_skipped_and_counted() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        fs::write(root.join(".gitignore"), "generated/\n*.bak\n").unwrap();
+        fs::write(root.join("lib.rs"), "fn lib() {}").unwrap();
+        fs::write(root.join("lib.rs.bak"), "fn old() {}").unwrap();
+        fs::write(root.join("notes.md"), "notes").unwrap();
+
+        let config = FileWatcherConfig {
+            custom_ignores: vec!["*.md".to_string()],
+            ..FileWatcherConfig::default()
+        };
+        let mut tracker = FileTracker::new(root.to_path_buf(), config);
+
+        let events = tracker.scan_for_changes().await.unwrap();
+        let paths: Vec<_> = events.iter().map(|e| e.file_path.clone()).collect();
+        assert_eq!(paths, vec![root.join("lib.rs")]);
+        assert_eq!(tracker.events_ignored.load(Ordering::Relaxed), 2);
+
+        // A new ignored directory counts once, and nothing inside it is reported
+        fs::create_dir_all(root.join("generated").join("nested")).unwrap();
+        fs::write(root.join("generated").join("a.rs"), "").unwrap();
+        fs::write(root.join("generated").join("nested").join("b.rs"), "").unwrap();
+        let events = tracker.scan_for_changes().await.unwrap();
+        assert!(events.is_empty());
+        assert_eq!(tracker.events_ignored.load(Ordering::Relaxed), 3);
+
+        // Without gitignore support only the custom patterns apply
+        let config = FileWatcherConfig {
+            respect_gitignore: false,
+            custom_ignores: vec!["*.md".to_string()],
+            ..FileWatcherConfig::default()
+        };
+        let mut tracker = FileTracker::new(root.to_path_buf(), config);
+        let events = tracker.scan_for_changes().await.unwrap();
+        assert_eq!(events.len(), 4);
+    }
+
     #[tokio::test]
     async fn test_watcher_lifecycle() {
         let temp_dir = TempDir::new().unwrap();
@@ -829,6 +1094,51 @@ mod tests {
         watcher.stop().await.unwrap();
     }
 
+    #[tokio::test]
+    async fn test_watcher_sends_events_for_files_that_never_settle() {
+        let temp_dir = TempDir::new().unwrap();
+        let test_file = temp_dir.path().join("busy.txt");
+
+        let config = FileWatcherConfig {
+            poll_interval_secs: 1,
+            debounce_interval_ms: 100,
+            ..FileWatcherConfig::default()
+        };
+        assert_eq!(config.max_debounce_wait(), Duration::from_secs(1));
+
+        let mut watcher = FileWatcher::new(config);
+        watcher.add_workspace(temp_dir.path()).unwrap();
+        let mut receiver = watcher.take_receiver().unwrap();
+        watcher.start().unwrap();
+
+        // Rewrite the file faster than the debounce interval, for longer than the maximum wait
+        let writing = Arc::new(AtomicBool::new(true));
+        let writer = {
+            let writing = Arc::clone(&writing);
+            let test_file = test_file.clone();
+            std::thread::spawn(move || {
+                let mut count = 0u64;
+                while writing.load(Ordering::Relaxed) {
+                    count += 1;
+                    fs::write(&test_file, count.to_string()).unwrap();
+                    std::thread::sleep(Duration::from_millis(20));
+                }
+            })
+        };
+
+        let events = timeout(Duration::from_secs(5), receiver.recv()).await;
+        writing.store(false, Ordering::Relaxed);
+        writer.join().unwrap();
+
+        let events = events
+            .expect("Events should be sent while the file keeps changing")
+            .expect("Channel closed");
+        assert_eq!(events.len(), 1);
+        assert!(events[0].file_path.ends_with("busy.txt"));
+
+        watcher.stop().await.unwrap();
+    }
+
     #[test]
     fn test_file_watcher_stats() {
         let config = FileWatcherConfig::default();
@@ -839,5 +1149,40 @@ mod tests {
         assert_eq!(stats.total_files_tracked, 0);
         assert!(!stats.is_running);
         assert_eq!(stats.poll_interval_secs, 2);
+        assert_eq!(stats.events_coalesced, 0);
+        assert_eq!(stats.events_ignored, 0);
+    }
+
+    #[test]
+    fn test_coalescer_merges_events_per_file() {
+        let root = PathBuf::from("/workspace");
+        let event =
+            |name: &str, event_type| FileEvent::new(root.join(name), event_type, root.clone());
+
+        let mut pending = EventCoalescer::default();
+        pending.push(event("a.rs", FileEventType::Created));
+        pending.push(event("b.rs", FileEventType::Modified));
+        pending.push(event("a.rs", FileEventType::Modified));
+        pending.push(event("c.rs", FileEventType::Created));
+        pending.push(event("b.rs", FileEventType::Deleted));
+        pending.push(event("c.rs", FileEventType::Deleted));
+        pending.push(event("d.rs", FileEventType::Deleted));
+        pending.push(event("d.rs", FileEventType::Created));
+
+        assert_eq!(pending.coalesced, 5);
+        let events: Vec<_> = pending
+            .drain()
+            .into_iter()
+            .map(|e| (e.file_path, e.event_type))
+            .collect();
+        assert_eq!(
+            events,
+            vec![
+                (root.join("a.rs"), FileEventType::Modified),
+                (root.join("b.rs"), FileEventType::Deleted),
+                (root.join("d.rs"), FileEventType::Modified),
+            ]
+        );
+        assert!(pending.is_empty());
     }
 }
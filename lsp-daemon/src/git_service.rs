@@ -1,3 +1,4 @@
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 // HashSet import removed as it's not used anymore after API changes
 use thiserror::Error;
@@ -9,6 +10,16 @@ pub struct GitService {
     repo_workdir: Option<PathBuf>,
 }
 
+/// The commit a line was last changed in, as found by [`GitService::blame_lines`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlameLine {
+    /// Full hex id of the commit
+    pub commit: String,
+    pub author: String,
+    /// Author time in seconds since the Unix epoch
+    pub time: i64,
+}
+
 #[derive(Debug, Error)]
 pub enum GitServiceError {
     #[error("not a git repository")]
@@ -63,6 +74,186 @@ impl GitService {
         Ok(Self { repo, repo_workdir })
     }
 
+    /// Root of the checked-out worktree (None for bare repos).
+    pub fn workdir(&self) -> Option<&Path> {
+        self.repo_workdir.as_deref()
+    }
+
+    /// Return the commit time (seconds since the Unix epoch) of the most recent commit
+    /// touching each of `paths`, walking at most `max_commits` commits back from HEAD.
+    /// Paths are relative to the repository root and use forward slashes; paths without a
+    /// commit in that window are absent from the map. Unborn repos return an empty map.
+    pub fn last_commit_times(
+        &self,
+        paths: &[String],
+        max_commits: usize,
+    ) -> Result<HashMap<String, i64>, GitServiceError> {
+        let mut times = HashMap::new();
+        let head_id = match self.repo.head_id() {
+            Ok(id) => id.detach(),
+            Err(_) => return Ok(times),
+        };
+
+        let mut pending: HashSet<&str> = paths.iter().map(String::as_str).collect();
+        let walk = self
+            .repo
+            .rev_walk([head_id])
+            .all()
+            .map_err(|e| anyhow::anyhow!("Failed to walk commit history: {}", e))?;
+
+        for info in walk.take(max_commits) {
+            if pending.is_empty() {
+                break;
+            }
+
+            let info = info.map_err(|e| anyhow::anyhow!("Failed to walk commit history: {}", e))?;
+            let commit = info.object().map_err(GitServiceError::GitCommit)?;
+            let seconds = commit.time()?.seconds;
+            let tree = commit.tree()?;
+            let parent_tree = match info.parent_ids().next() {
+                Some(parent_id) => Some(
+                    parent_id
+                        .object()
+                        .map_err(GitServiceError::GitCommit)?
+                        .into_commit()
+                        .tree()?,
+                ),
+                None => None,
+            };
+
+            // A path changed in this commit if its blob differs from the first parent's
+            pending.retain(|path| {
+                let entry_id = |tree: &gix::Tree<'_>| {
+                    tree.lookup_entry_by_path(path)
+                        .ok()
+                        .flatten()
+                        .map(|entry| entry.object_id())
+                };
+                let current = entry_id(&tree);
+                if current.is_some() && current != parent_tree.as_ref().and_then(entry_id) {
+                    times.insert(path.to_string(), seconds);
+                    false
+                } else {
+                    true
+                }
+            });
+        }
+
+        Ok(times)
+    }
+
+    /// Attribute each line of `content`, the current text of the file at `path`, to the last
+    /// commit that changed it, following first parents back from HEAD for at most
+    /// `max_commits` commits. `path` is relative to the repository root and uses forward
+    /// slashes. Lines that are not committed yet, or older than the commit window, are
+    /// `None`. Renames are not followed, so lines are attributed to the commit adding the
+    /// file under its current name.
+    pub fn blame_lines(
+        &self,
+        path: &str,
+        content: &str,
+        max_commits: usize,
+    ) -> Result<Vec<Option<BlameLine>>, GitServiceError> {
+        let mut blame = vec![None; content.lines().count()];
+        let mut commit = match self.repo.head_commit() {
+            Ok(commit) => commit,
+            Err(_) => return Ok(blame),
+        };
+        let Some(mut blob) = Self::blob_at(&commit, path)? else {
+            return Ok(blame);
+        };
+
+        // (line in `blob`, line in `content`) for lines not attributed yet
+        let matching = matching_lines(&blob.1, content);
+        let mut pending: Vec<(usize, usize)> = (0..blame.len())
+            .filter_map(|line| matching.get(&line).map(|&old| (old, line)))
+            .collect();
+
+        for _ in 0..max_commits {
+            if pending.is_empty() {
+                break;
+            }
+
+            let parent = match commit.parent_ids().next() {
+                Some(parent_id) => Some(
+                    parent_id
+                        .object()
+                        .map_err(GitServiceError::GitCommit)?
+                        .into_commit(),
+                ),
+                None => None,
+            };
+            let parent_blob = match &parent {
+                Some(parent) => Self::blob_at(parent, path)?,
+                None => None,
+            };
+
+            // Lines of this commit's version that its parent does not have came from it
+            let introduced: Vec<usize> = match &parent_blob {
+                Some(parent_blob) if parent_blob.0 == blob.0 => Vec::new(),
+                Some(parent_blob) => {
+                    let matching = matching_lines(&parent_blob.1, &blob.1);
+                    let mut introduced = Vec::new();
+                    pending.retain_mut(|(line, original)| match matching.get(&*line) {
+                        Some(&old) => {
+                            *line = old;
+                            true
+                        }
+                        None => {
+                            introduced.push(*original);
+                            false
+                        }
+                    });
+                    introduced
+                }
+                None => pending.drain(..).map(|(_, original)| original).collect(),
+            };
+
+            if !introduced.is_empty() {
+                let author = commit
+                    .author()
+                    .map_err(|e| anyhow::anyhow!("Failed to read commit author: {}", e))?;
+                let line = BlameLine {
+                    commit: commit.id.to_string(),
+                    author: author.name.to_string(),
+                    time: author.time.seconds,
+                };
+                for original in introduced {
+                    blame[original] = Some(line.clone());
+                }
+            }
+
+            match (parent, parent_blob) {
+                (Some(parent), Some(parent_blob)) => {
+                    commit = parent;
+                    blob = parent_blob;
+                }
+                _ => break,
+            }
+        }
+
+        Ok(blame)
+    }
+
+    /// Id and (lossily decoded) text of the blob at `path` in `commit`, if the path is a file
+    fn blob_at(
+        commit: &gix::Commit<'_>,
+        path: &str,
+    ) -> Result<Option<(gix::ObjectId, String)>, GitServiceError> {
+        let tree = commit.tree()?;
+        let Some(entry) = tree.lookup_entry_by_path(path).ok().flatten() else {
+            return Ok(None);
+        };
+        if !entry.mode().is_blob() {
+            return Ok(None);
+        }
+        let object = entry.object().map_err(GitServiceError::GitCommit)?;
+        Ok(Some((
+            entry.object_id(),
+            String::from_utf8_lossy(&object.data).into_owned(),
+        )))
+    }
+
     /// Return the current HEAD commit SHA as hex. Handles detached HEAD and unborn branches.
     pub fn head_commit(&self) -> Result<Option<String>, GitServiceError> {
         match self.repo.head() {
@@ -80,6 +271,28 @@ impl GitService {
         }
     }
 
+    /// Return the files tracked in the index, staged additions included, relative to the
+    /// repository root and using forward slashes. For bare repos, returns an empty list.
+    pub fn tracked_files(&self) -> Result<Vec<String>, GitServiceError> {
+        if self.repo_workdir.is_none() {
+            return Ok(Vec::new());
+        }
+
+        let index = self
+            .repo
+            .index_or_empty()
+            .map_err(|e| anyhow::anyhow!("Failed to read git index: {}", e))?;
+        let mut files: Vec<String> = index
+            .entries()
+            .iter()
+            .map(|entry| entry.path(&index).to_string())
+            .collect();
+
+        // Entries are sorted by path; a conflicted file has one entry per stage
+        files.dedup();
+        Ok(files)
+    }
+
     /// Return list of files modified relative to HEAD/index. Includes untracked, renames, typechanges.
     /// Paths are normalized to be relative to `workspace_root` and use forward slashes.
     /// For bare repos, returns an empty list.
@@ -515,3 +728,22 @@ impl GitService {
         Ok(())
     }
 }
+
+/// Map each line of `new` that is unchanged from `old` to its line index in `old`
+fn matching_lines(old: &str, new: &str) -> HashMap<usize, usize> {
+    let diff = similar::TextDiff::from_lines(old, new);
+    let mut matching = HashMap::new();
+    for op in diff.ops() {
+        if let similar::DiffOp::Equal {
+            old_index,
+            new_index,
+            len,
+        } = *op
+        {
+            for offset in 0..len {
+                matching.insert(new_index + offset, old_index + offset);
+            }
+        }
+    }
+    matching
+}
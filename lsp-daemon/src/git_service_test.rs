@@ -175,6 +175,20 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_blame_lines_without_commits_leaves_lines_unattributed() {
+        let (temp_dir, _repo) = init_test_repo();
+        let content = "fn main() {\n    println!(\"hi\");\n}\n";
+        fs::write(temp_dir.path().join("main.rs"), content).unwrap();
+
+        let service =
+            crate::git_service::GitService::discover_repo(temp_dir.path(), temp_dir.path())
+                .unwrap();
+
+        let blame = service.blame_lines("main.rs", content, 100).unwrap();
+        assert_eq!(blame, vec![None, None, None]);
+    }
+
     #[test]
     fn test_git_service_non_git_directory_error_handling() {
         let temp_dir = TempDir::new().unwrap();
@@ -4,15 +4,44 @@
 //! - JSON: Structured data with nodes and edges
 //! - GraphML: XML-based graph format for visualization tools
 //! - DOT: Graphviz format for graph rendering
+//! - Mermaid: `flowchart` diagrams that render directly in Markdown
 //!
 //! The exported graphs include symbols as nodes and relationships (calls, references, etc.) as edges.
+//!
+//! An export can be limited to the subgraph reachable from one symbol, e.g. everything that
+//! transitively calls a function. It can also be a delta against a completed analysis run: only symbols and edges
+//! added or modified since are included, marked with a `change` metadata entry, and symbols
+//! and edges removed since come back as tombstones marked `change: deleted`.
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
-
-use crate::database::{DatabaseBackend, Edge, SymbolState};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::database::{
+    CallDirection, DatabaseBackend, Edge, EdgeRelation, GraphSnapshot, SymbolState,
+};
+
+/// Traversal depth of a subgraph export when no `max_depth` is given
+pub const DEFAULT_SUBGRAPH_DEPTH: u32 = 3;
+
+/// GraphML attribute keys as (name, domain, type)
+const GRAPHML_KEYS: &[(&str, &str, &str)] = &[
+    ("label", "node", "string"),
+    ("kind", "node", "string"),
+    ("language", "node", "string"),
+    ("visibility", "node", "string"),
+    ("file_path", "node", "string"),
+    ("line", "node", "int"),
+    ("column", "node", "int"),
+    ("fqn", "node", "string"),
+    ("signature", "node", "string"),
+    ("documentation", "node", "string"),
+    ("relation", "edge", "string"),
+    ("confidence", "edge", "double"),
+    ("change", "all", "string"),
+];
 
 /// Graph export options
 #[derive(Debug, Clone)]
@@ -25,6 +54,37 @@ pub struct GraphExportOptions {
     pub edge_types_filter: Option<Vec<String>>,
     /// Include only connected symbols (symbols with at least one edge)
     pub connected_only: bool,
+    /// Materialize edge targets outside the exported symbol set (library or
+    /// unresolved references) as synthetic nodes marked `external: true`
+    pub include_external: bool,
+    /// Export only what changed since this completed analysis run (None = full export)
+    pub baseline_run_id: Option<i64>,
+    /// Export only what is reachable from a root symbol within `max_depth` (None = everything)
+    pub subgraph: Option<SubgraphOptions>,
+}
+
+/// Root and direction of a subgraph export
+#[derive(Debug, Clone)]
+pub struct SubgraphOptions {
+    /// Symbol UID, or `file#name` such as `src/foo.rs#handler`
+    pub root: String,
+    /// `Incoming` follows callers, `Outgoing` callees
+    pub direction: CallDirection,
+}
+
+impl SubgraphOptions {
+    /// Parse a direction given as `callers`, `callees` or `both`
+    pub fn parse_direction(direction: &str) -> Result<CallDirection> {
+        match direction.to_lowercase().as_str() {
+            "callers" => Ok(CallDirection::Incoming),
+            "callees" => Ok(CallDirection::Outgoing),
+            "both" => Ok(CallDirection::Both),
+            _ => Err(anyhow!(
+                "Unknown direction: {} (expected callers, callees or both)",
+                direction
+            )),
+        }
+    }
 }
 
 impl Default for GraphExportOptions {
@@ -34,10 +94,50 @@ impl Default for GraphExportOptions {
             symbol_types_filter: None,
             edge_types_filter: None,
             connected_only: false,
+            include_external: false,
+            baseline_run_id: None,
+            subgraph: None,
+        }
+    }
+}
+
+/// Output format for an exported graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GraphExportFormat {
+    #[default]
+    Json,
+    GraphMl,
+    Dot,
+    Mermaid,
+}
+
+impl std::str::FromStr for GraphExportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "json" => Ok(GraphExportFormat::Json),
+            "graphml" => Ok(GraphExportFormat::GraphMl),
+            "dot" => Ok(GraphExportFormat::Dot),
+            "mermaid" => Ok(GraphExportFormat::Mermaid),
+            _ => Err(format!(
+                "Unknown graph export format: {} (expected json, graphml, dot or mermaid)",
+                s
+            )),
         }
     }
 }
 
+/// Mermaid output options
+#[derive(Debug, Clone, Default)]
+pub struct MermaidOptions {
+    /// Group the nodes of each file into a subgraph
+    pub cluster_by_file: bool,
+    /// Keep only the most connected nodes (None = all). Mermaid becomes unreadable and slow
+    /// to render long before the other formats do.
+    pub max_nodes: Option<usize>,
+}
+
 /// Represents a graph node (symbol) for export
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GraphNode {
@@ -84,6 +184,12 @@ pub struct GraphMetadata {
     pub filtered_edge_types: Option<Vec<String>>,
     pub max_depth: Option<u32>,
     pub connected_only: bool,
+    /// Set when the graph is a delta against this analysis run
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub baseline_run_id: Option<i64>,
+    /// UID of the root symbol when the graph is a subgraph
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub root_symbol: Option<String>,
 }
 
 /// Graph exporter that handles different output formats
@@ -96,9 +202,55 @@ impl GraphExporter {
         workspace_path: PathBuf,
         options: GraphExportOptions,
     ) -> Result<ExportGraph> {
+        // Tombstones cannot be placed relative to a subgraph, so the two do not combine
+        if options.subgraph.is_some() && options.baseline_run_id.is_some() {
+            return Err(anyhow!(
+                "A subgraph export cannot also be a delta against an analysis run"
+            ));
+        }
+
         // Step 1: Get all symbols and edges from the database
-        let symbols = Self::get_filtered_symbols(backend, &options).await?;
-        let edges = Self::get_filtered_edges(backend, &options).await?;
+        let (mut symbols, stored_uids) = Self::get_filtered_symbols(backend, &options).await?;
+        let mut edges = Self::get_filtered_edges(backend, &options).await?;
+
+        // Step 1b: Keep only what is reachable from the root symbol
+        let root_symbol = match &options.subgraph {
+            Some(subgraph) => {
+                let root = Self::resolve_root(&symbols, &subgraph.root)?;
+                let relations = Self::subgraph_relations(&options);
+                let paths = backend
+                    .traverse_graph(
+                        &root,
+                        options.max_depth.unwrap_or(DEFAULT_SUBGRAPH_DEPTH),
+                        &relations,
+                        subgraph.direction.clone(),
+                    )
+                    .await
+                    .map_err(|e| anyhow!("Failed to traverse graph from {}: {}", root, e))?;
+                let mut reachable: HashSet<String> =
+                    paths.into_iter().map(|path| path.symbol_uid).collect();
+                reachable.insert(root.clone());
+
+                symbols.retain(|symbol| reachable.contains(&symbol.symbol_uid));
+                edges.retain(|edge| {
+                    relations.contains(&edge.relation)
+                        && reachable.contains(&edge.source_symbol_uid)
+                        && reachable.contains(&edge.target_symbol_uid)
+                });
+                Some(root)
+            }
+            None => None,
+        };
+        let baseline = match options.baseline_run_id {
+            Some(run_id) => Some(Self::get_baseline(backend, run_id).await?),
+            None => None,
+        };
+        // Symbols dropped by `connected_only` still exist and are no tombstones
+        let existing_uids: HashSet<String> = if baseline.is_some() {
+            symbols.iter().map(|s| s.symbol_uid.clone()).collect()
+        } else {
+            HashSet::new()
+        };
 
         // Step 2: Filter connected symbols if requested
         let (final_symbols, final_edges) = if options.connected_only {
@@ -108,8 +260,41 @@ impl GraphExporter {
         };
 
         // Step 3: Convert to graph representation
-        let nodes = Self::symbols_to_nodes(&final_symbols);
-        let graph_edges = Self::edges_to_graph_edges(&final_edges);
+        let mut nodes = Self::symbols_to_nodes(&final_symbols);
+        let mut graph_edges = Self::edges_to_graph_edges(&final_edges);
+
+        // Step 3b: Mark what changed since the baseline and add tombstones for what is gone
+        if let Some(snapshot) = &baseline {
+            Self::mark_changes(
+                snapshot,
+                &options,
+                &existing_uids,
+                &final_symbols,
+                &mut nodes,
+                &final_edges,
+                &mut graph_edges,
+            );
+        }
+
+        // Step 3c: Represent external/unresolved call targets as their own nodes
+        if options.include_external {
+            let external_nodes = Self::external_target_nodes(&nodes, &graph_edges, &stored_uids);
+            nodes.extend(external_nodes);
+        }
+
+        // Step 3d: Drop everything unchanged, keeping external nodes of changed edges
+        if baseline.is_some() {
+            graph_edges.retain(|edge| edge.metadata.contains_key("change"));
+            let endpoints: HashSet<&str> = graph_edges
+                .iter()
+                .flat_map(|edge| [edge.source.as_str(), edge.target.as_str()])
+                .collect();
+            nodes.retain(|node| {
+                node.metadata.contains_key("change")
+                    || (node.metadata.contains_key("external")
+                        && endpoints.contains(node.id.as_str()))
+            });
+        }
 
         // Step 4: Create metadata
         let metadata = GraphMetadata {
@@ -121,6 +306,8 @@ impl GraphExporter {
             filtered_edge_types: options.edge_types_filter,
             max_depth: options.max_depth,
             connected_only: options.connected_only,
+            baseline_run_id: options.baseline_run_id,
+            root_symbol,
         };
 
         Ok(ExportGraph {
@@ -130,6 +317,16 @@ impl GraphExporter {
         })
     }
 
+    /// Serialize graph in the given format, using default options for Mermaid
+    pub fn render(graph: &ExportGraph, format: GraphExportFormat) -> Result<String> {
+        match format {
+            GraphExportFormat::Json => Self::to_json(graph),
+            GraphExportFormat::GraphMl => Self::to_graphml(graph),
+            GraphExportFormat::Dot => Self::to_dot(graph),
+            GraphExportFormat::Mermaid => Self::to_mermaid(graph, &MermaidOptions::default()),
+        }
+    }
+
     /// Serialize graph to JSON format
     pub fn to_json(graph: &ExportGraph) -> Result<String> {
         serde_json::to_string_pretty(graph)
@@ -138,111 +335,150 @@ impl GraphExporter {
 
     /// Serialize graph to GraphML format
     pub fn to_graphml(graph: &ExportGraph) -> Result<String> {
-        let mut output = String::new();
-
-        // GraphML header
-        output.push_str(
-            r#"<?xml version="1.0" encoding="UTF-8"?>
-<graphml xmlns="http://graphml.graphdrawing.org/xmlns" 
-         xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance" 
-         xsi:schemaLocation="http://graphml.graphdrawing.org/xmlns 
-         http://graphml.graphdrawing.org/xmlns/1.0/graphml.xsd">
-"#,
-        );
-
-        // Define attribute keys
-        output.push_str(
-            r#"  <key id="label" for="node" attr.name="label" attr.type="string"/>
-  <key id="kind" for="node" attr.name="kind" attr.type="string"/>
-  <key id="file_path" for="node" attr.name="file_path" attr.type="string"/>
-  <key id="line" for="node" attr.name="line" attr.type="int"/>
-  <key id="column" for="node" attr.name="column" attr.type="int"/>
-  <key id="signature" for="node" attr.name="signature" attr.type="string"/>
-  <key id="visibility" for="node" attr.name="visibility" attr.type="string"/>
-  <key id="documentation" for="node" attr.name="documentation" attr.type="string"/>
-  <key id="relation" for="edge" attr.name="relation" attr.type="string"/>
-  <key id="confidence" for="edge" attr.name="confidence" attr.type="double"/>
-"#,
-        );
-
-        // Graph opening
-        output.push_str("  <graph id=\"codebase_graph\" edgedefault=\"directed\">\n");
+        let mut output = Vec::new();
+        Self::write_graphml(graph, &mut output)?;
+        Ok(String::from_utf8(output)?)
+    }
 
-        // Add nodes
+    /// Write graph as GraphML to `writer`, one element at a time, so large graphs are not
+    /// held in memory a second time as XML
+    pub fn write_graphml<W: Write>(graph: &ExportGraph, mut writer: W) -> Result<()> {
+        Self::write_graphml_header(&mut writer, graph.metadata.baseline_run_id)?;
         for node in &graph.nodes {
-            output.push_str(&format!(
-                "    <node id=\"{}\">\n",
-                Self::escape_xml(&node.id)
-            ));
-            output.push_str(&format!(
-                "      <data key=\"label\">{}</data>\n",
-                Self::escape_xml(&node.label)
-            ));
-            output.push_str(&format!(
-                "      <data key=\"kind\">{}</data>\n",
-                Self::escape_xml(&node.kind)
-            ));
+            Self::write_graphml_node(&mut writer, node)?;
+        }
+        for (i, edge) in graph.edges.iter().enumerate() {
+            Self::write_graphml_edge(&mut writer, i, edge)?;
+        }
+        Self::write_graphml_footer(&mut writer)
+    }
 
-            if let Some(file_path) = &node.file_path {
-                output.push_str(&format!(
-                    "      <data key=\"file_path\">{}</data>\n",
-                    Self::escape_xml(file_path)
-                ));
-            }
+    /// Export straight from the database as GraphML. Unlike [`Self::export_graph`] followed
+    /// by [`Self::write_graphml`], nodes and edges are converted as they are written, so
+    /// no second copy of the graph is built. Subgraphs and deltas against a baseline run are
+    /// small and are built in memory first.
+    pub async fn export_graphml<T: DatabaseBackend, W: Write>(
+        backend: &T,
+        options: GraphExportOptions,
+        mut writer: W,
+    ) -> Result<()> {
+        if options.subgraph.is_some() || options.baseline_run_id.is_some() {
+            let graph = Self::export_graph(backend, PathBuf::new(), options).await?;
+            return Self::write_graphml(&graph, writer);
+        }
 
-            output.push_str(&format!("      <data key=\"line\">{}</data>\n", node.line));
-            output.push_str(&format!(
-                "      <data key=\"column\">{}</data>\n",
-                node.column
-            ));
+        let (symbols, stored_uids) = Self::get_filtered_symbols(backend, &options).await?;
+        let edges = Self::get_filtered_edges(backend, &options).await?;
+        let (symbols, edges) = if options.connected_only {
+            Self::filter_connected_only(symbols, edges)
+        } else {
+            (symbols, edges)
+        };
 
-            if let Some(signature) = &node.signature {
-                output.push_str(&format!(
-                    "      <data key=\"signature\">{}</data>\n",
-                    Self::escape_xml(signature)
-                ));
+        Self::write_graphml_header(&mut writer, None)?;
+        let mut known: HashSet<&str> = HashSet::new();
+        for symbol in &symbols {
+            known.insert(symbol.symbol_uid.as_str());
+            Self::write_graphml_node(&mut writer, &Self::symbol_to_node(symbol))?;
+        }
+        if options.include_external {
+            for edge in &edges {
+                let target = edge.target_symbol_uid.as_str();
+                if Self::is_external_target(target, &stored_uids) && known.insert(target) {
+                    Self::write_graphml_node(&mut writer, &Self::external_node(target))?;
+                }
             }
+        }
+        for (i, edge) in edges.iter().enumerate() {
+            Self::write_graphml_edge(&mut writer, i, &Self::edge_to_graph_edge(edge))?;
+        }
+        Self::write_graphml_footer(&mut writer)
+    }
 
-            if let Some(visibility) = &node.visibility {
-                output.push_str(&format!(
-                    "      <data key=\"visibility\">{}</data>\n",
-                    Self::escape_xml(visibility)
-                ));
-            }
+    /// XML prolog and typed attribute declarations, so tools such as Gephi and yEd import
+    /// numbers as numbers
+    fn write_graphml_header<W: Write>(writer: &mut W, baseline_run_id: Option<i64>) -> Result<()> {
+        writer.write_all(
+            br#"<?xml version="1.0" encoding="UTF-8"?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns"
+         xmlns:xsi="http://www.w3.org/2001/XMLSchema-instance"
+         xsi:schemaLocation="http://graphml.graphdrawing.org/xmlns
+         http://graphml.graphdrawing.org/xmlns/1.0/graphml.xsd">
+"#,
+        )?;
+        if let Some(run_id) = baseline_run_id {
+            writeln!(writer, "  <!-- {} -->", Self::delta_note(run_id))?;
+        }
+        for (id, domain, attr_type) in GRAPHML_KEYS {
+            writeln!(
+                writer,
+                "  <key id=\"{id}\" for=\"{domain}\" attr.name=\"{id}\" attr.type=\"{attr_type}\"/>"
+            )?;
+        }
+        writer.write_all(b"  <graph id=\"codebase_graph\" edgedefault=\"directed\">\n")?;
+        Ok(())
+    }
 
-            if let Some(documentation) = &node.documentation {
-                output.push_str(&format!(
-                    "      <data key=\"documentation\">{}</data>\n",
-                    Self::escape_xml(documentation)
-                ));
+    fn write_graphml_node<W: Write>(writer: &mut W, node: &GraphNode) -> Result<()> {
+        writeln!(writer, "    <node id=\"{}\">", Self::escape_xml(&node.id))?;
+        let line = node.line.to_string();
+        let column = node.column.to_string();
+        let data = [
+            ("label", Some(&node.label)),
+            ("kind", Some(&node.kind)),
+            ("language", node.metadata.get("language")),
+            ("visibility", node.visibility.as_ref()),
+            ("file_path", node.file_path.as_ref()),
+            ("line", Some(&line)),
+            ("column", Some(&column)),
+            ("fqn", node.metadata.get("fqn")),
+            ("signature", node.signature.as_ref()),
+            ("documentation", node.documentation.as_ref()),
+            ("change", node.metadata.get("change")),
+        ];
+        for (key, value) in data {
+            if let Some(value) = value {
+                writeln!(
+                    writer,
+                    "      <data key=\"{}\">{}</data>",
+                    key,
+                    Self::escape_xml(value)
+                )?;
             }
-
-            output.push_str("    </node>\n");
         }
+        writer.write_all(b"    </node>\n")?;
+        Ok(())
+    }
 
-        // Add edges
-        for (i, edge) in graph.edges.iter().enumerate() {
-            output.push_str(&format!(
-                "    <edge id=\"e{}\" source=\"{}\" target=\"{}\">\n",
-                i,
-                Self::escape_xml(&edge.source),
-                Self::escape_xml(&edge.target)
-            ));
-            output.push_str(&format!(
-                "      <data key=\"relation\">{}</data>\n",
-                Self::escape_xml(&edge.relation)
-            ));
-            output.push_str(&format!(
-                "      <data key=\"confidence\">{}</data>\n",
-                edge.confidence
-            ));
-            output.push_str("    </edge>\n");
+    fn write_graphml_edge<W: Write>(writer: &mut W, index: usize, edge: &GraphEdge) -> Result<()> {
+        writeln!(
+            writer,
+            "    <edge id=\"e{}\" source=\"{}\" target=\"{}\">",
+            index,
+            Self::escape_xml(&edge.source),
+            Self::escape_xml(&edge.target)
+        )?;
+        writeln!(
+            writer,
+            "      <data key=\"relation\">{}</data>",
+            Self::escape_xml(&edge.relation)
+        )?;
+        writeln!(
+            writer,
+            "      <data key=\"confidence\">{}</data>",
+            edge.confidence
+        )?;
+        if let Some(change) = edge.metadata.get("change") {
+            writeln!(writer, "      <data key=\"change\">{}</data>", change)?;
         }
+        writer.write_all(b"    </edge>\n")?;
+        Ok(())
+    }
 
-        // Graph closing
-        output.push_str("  </graph>\n</graphml>\n");
-
-        Ok(output)
+    fn write_graphml_footer<W: Write>(writer: &mut W) -> Result<()> {
+        writer.write_all(b"  </graph>\n</graphml>\n")?;
+        writer.flush()?;
+        Ok(())
     }
 
     /// Serialize graph to DOT format (Graphviz)
@@ -250,6 +486,9 @@ impl GraphExporter {
         let mut output = String::new();
 
         // DOT header
+        if let Some(run_id) = graph.metadata.baseline_run_id {
+            output.push_str(&format!("// {}\n", Self::delta_note(run_id)));
+        }
         output.push_str("digraph codebase_graph {\n");
         output.push_str("  rankdir=TB;\n");
         output.push_str("  node [shape=box, style=filled];\n");
@@ -270,12 +509,20 @@ impl GraphExporter {
                 tooltip.push_str(&format!("\\n{}", sig));
             }
 
+            // Tombstones of a delta export are outlined dashed
+            let style = if Self::is_deleted(&node.metadata) {
+                ", style=\"filled,dashed\""
+            } else {
+                ""
+            };
+
             output.push_str(&format!(
-                "  {} [label=\"{}\", fillcolor=\"{}\", tooltip=\"{}\"];\n",
+                "  {} [label=\"{}\", fillcolor=\"{}\", tooltip=\"{}\"{}];\n",
                 escaped_id,
                 escaped_label,
                 color,
-                Self::escape_dot_label(&tooltip)
+                Self::escape_dot_label(&tooltip),
+                style
             ));
         }
 
@@ -285,7 +532,11 @@ impl GraphExporter {
         for edge in &graph.edges {
             let escaped_source = Self::escape_dot_id(&edge.source);
             let escaped_target = Self::escape_dot_id(&edge.target);
-            let edge_style = Self::get_edge_style(&edge.relation);
+            let edge_style = if Self::is_deleted(&edge.metadata) {
+                "color=gray, style=dashed"
+            } else {
+                Self::get_edge_style(&edge.relation)
+            };
 
             output.push_str(&format!(
                 "  {} -> {} [label=\"{}\", {}];\n",
@@ -298,24 +549,148 @@ impl GraphExporter {
         Ok(output)
     }
 
+    /// Serialize graph to a Mermaid flowchart
+    ///
+    /// Only edges between exported nodes are drawn. With `max_nodes` the nodes with the most
+    /// edges are kept and a note node tells how much was left out.
+    pub fn to_mermaid(graph: &ExportGraph, options: &MermaidOptions) -> Result<String> {
+        let node_ids: HashSet<&str> = graph.nodes.iter().map(|n| n.id.as_str()).collect();
+        let edges: Vec<&GraphEdge> = graph
+            .edges
+            .iter()
+            .filter(|e| {
+                node_ids.contains(e.source.as_str()) && node_ids.contains(e.target.as_str())
+            })
+            .collect();
+
+        // Keep the most connected nodes when over the cap, in export order
+        let mut nodes: Vec<&GraphNode> = graph.nodes.iter().collect();
+        if let Some(max_nodes) = options.max_nodes.filter(|&max| nodes.len() > max) {
+            let mut degree: HashMap<&str, usize> = HashMap::new();
+            for edge in &edges {
+                *degree.entry(edge.source.as_str()).or_default() += 1;
+                *degree.entry(edge.target.as_str()).or_default() += 1;
+            }
+            let mut ranked: Vec<(usize, &GraphNode)> = nodes.into_iter().enumerate().collect();
+            ranked.sort_by_key(|(i, n)| (std::cmp::Reverse(degree.get(n.id.as_str())), *i));
+            ranked.truncate(max_nodes);
+            ranked.sort_by_key(|(i, _)| *i);
+            nodes = ranked.into_iter().map(|(_, n)| n).collect();
+        }
+        let kept: HashSet<&str> = nodes.iter().map(|n| n.id.as_str()).collect();
+        let kept_edges: Vec<&GraphEdge> = edges
+            .into_iter()
+            .filter(|e| kept.contains(e.source.as_str()) && kept.contains(e.target.as_str()))
+            .collect();
+
+        // Sanitized ids can collide, so suffix repeats to keep them unique
+        let mut mermaid_ids: HashMap<&str, String> = HashMap::new();
+        let mut used: HashSet<String> = HashSet::new();
+        for node in &nodes {
+            let base = Self::mermaid_id(&node.id);
+            let mut id = base.clone();
+            let mut n = 2;
+            while !used.insert(id.clone()) {
+                id = format!("{}_{}", base, n);
+                n += 1;
+            }
+            mermaid_ids.insert(node.id.as_str(), id);
+        }
+
+        let mut output = String::new();
+        if let Some(run_id) = graph.metadata.baseline_run_id {
+            output.push_str(&format!("%% {}\n", Self::delta_note(run_id)));
+        }
+        output.push_str("flowchart LR\n");
+        let node_line = |node: &GraphNode, indent: &str| {
+            format!(
+                "{}{}[\"{}\"]\n",
+                indent,
+                mermaid_ids[node.id.as_str()],
+                Self::escape_mermaid_label(&node.label)
+            )
+        };
+
+        if options.cluster_by_file {
+            let mut files: Vec<&str> = Vec::new();
+            let mut by_file: HashMap<&str, Vec<&GraphNode>> = HashMap::new();
+            for node in &nodes {
+                match node.file_path.as_deref() {
+                    Some(file) => {
+                        if !by_file.contains_key(file) {
+                            files.push(file);
+                        }
+                        by_file.entry(file).or_default().push(node);
+                    }
+                    None => output.push_str(&node_line(node, "  ")),
+                }
+            }
+            for (i, file) in files.iter().enumerate() {
+                output.push_str(&format!(
+                    "  subgraph file_{}[\"{}\"]\n",
+                    i,
+                    Self::escape_mermaid_label(file)
+                ));
+                for node in &by_file[file] {
+                    output.push_str(&node_line(node, "    "));
+                }
+                output.push_str("  end\n");
+            }
+        } else {
+            for node in &nodes {
+                output.push_str(&node_line(node, "  "));
+            }
+        }
+
+        for edge in &kept_edges {
+            // Tombstones of a delta export are drawn dotted
+            let arrow = if Self::is_deleted(&edge.metadata) {
+                "-.->"
+            } else {
+                "-->"
+            };
+            output.push_str(&format!(
+                "  {} {}|{}| {}\n",
+                mermaid_ids[edge.source.as_str()],
+                arrow,
+                Self::escape_mermaid_label(&edge.relation),
+                mermaid_ids[edge.target.as_str()]
+            ));
+        }
+
+        let hidden_nodes = graph.nodes.len() - nodes.len();
+        if hidden_nodes > 0 {
+            output.push_str(&format!(
+                "  truncated_note[\"Truncated: {} of {} nodes and {} edges not shown\"]\n",
+                hidden_nodes,
+                graph.nodes.len(),
+                graph.edges.len() - kept_edges.len()
+            ));
+        }
+
+        Ok(output)
+    }
+
     // Helper methods
 
+    /// Symbols matching the type filter, with the UIDs of every stored symbol
     async fn get_filtered_symbols<T: DatabaseBackend>(
         backend: &T,
         options: &GraphExportOptions,
-    ) -> Result<Vec<SymbolState>> {
+    ) -> Result<(Vec<SymbolState>, HashSet<String>)> {
         // Get all symbols from database
         let mut symbols = backend
             .get_all_symbols()
             .await
             .map_err(|e| anyhow::anyhow!("Failed to get all symbols: {}", e))?;
+        let stored_uids = symbols.iter().map(|s| s.symbol_uid.clone()).collect();
 
         // Filter by symbol types if specified
         if let Some(symbol_types) = &options.symbol_types_filter {
             symbols.retain(|symbol| symbol_types.contains(&symbol.kind));
         }
 
-        Ok(symbols)
+        Ok((symbols, stored_uids))
     }
 
     async fn get_filtered_edges<T: DatabaseBackend>(
@@ -328,6 +703,9 @@ impl GraphExporter {
             .await
             .map_err(|e| anyhow::anyhow!("Failed to get all edges: {}", e))?;
 
+        // "none" targets mark analyzed symbols without relationships, not real edges
+        edges.retain(|edge| edge.target_symbol_uid != "none");
+
         // Filter by edge types if specified
         if let Some(edge_types) = &options.edge_types_filter {
             edges.retain(|edge| edge_types.iter().any(|et| et == edge.relation.to_string()));
@@ -336,6 +714,152 @@ impl GraphExporter {
         Ok(edges)
     }
 
+    /// Resolve a subgraph root given as a symbol UID or as `file#name`. The file may be
+    /// given relative to the workspace or as an absolute path.
+    fn resolve_root(symbols: &[SymbolState], root: &str) -> Result<String> {
+        if symbols.iter().any(|symbol| symbol.symbol_uid == root) {
+            return Ok(root.to_string());
+        }
+        let (file, name) = root
+            .rsplit_once('#')
+            .ok_or_else(|| anyhow!("No symbol with UID {} (use file#name to search)", root))?;
+
+        let file = Path::new(file);
+        let mut candidates: Vec<&SymbolState> = symbols
+            .iter()
+            .filter(|symbol| symbol.name == name)
+            .filter(|symbol| {
+                let symbol_path = Path::new(&symbol.file_path);
+                file.ends_with(symbol_path) || symbol_path.ends_with(file)
+            })
+            .collect();
+        if candidates.iter().any(|symbol| symbol.is_definition) {
+            candidates.retain(|symbol| symbol.is_definition);
+        }
+
+        match candidates.as_slice() {
+            [] => Err(anyhow!("No symbol named {} in {}", name, file.display())),
+            [symbol] => Ok(symbol.symbol_uid.clone()),
+            _ => Err(anyhow!(
+                "{} is ambiguous, pass one of these UIDs instead: {}",
+                root,
+                candidates
+                    .iter()
+                    .map(|symbol| symbol.symbol_uid.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )),
+        }
+    }
+
+    /// Relations followed by a subgraph export: the edge type filter, or calls
+    fn subgraph_relations(options: &GraphExportOptions) -> Vec<EdgeRelation> {
+        match &options.edge_types_filter {
+            Some(edge_types) => edge_types
+                .iter()
+                .filter_map(|et| EdgeRelation::from_string(et).ok())
+                .collect(),
+            None => vec![EdgeRelation::Calls],
+        }
+    }
+
+    async fn get_baseline<T: DatabaseBackend>(backend: &T, run_id: i64) -> Result<GraphSnapshot> {
+        backend
+            .get_analysis_run_snapshot(run_id)
+            .await
+            .map_err(|e| anyhow!("Failed to get graph of analysis run {}: {}", run_id, e))?
+            .ok_or_else(|| {
+                anyhow!(
+                    "Analysis run {} has no recorded graph; only completed runs can be a baseline",
+                    run_id
+                )
+            })
+    }
+
+    /// Mark nodes and edges `added` or `modified` relative to the baseline and append
+    /// tombstones for baseline symbols and edges that no longer exist. Tombstones are subject
+    /// to the same type filters as the rest of the export.
+    fn mark_changes(
+        baseline: &GraphSnapshot,
+        options: &GraphExportOptions,
+        existing_uids: &HashSet<String>,
+        symbols: &[SymbolState],
+        nodes: &mut Vec<GraphNode>,
+        edges: &[Edge],
+        graph_edges: &mut Vec<GraphEdge>,
+    ) {
+        let current = GraphSnapshot::from_graph(symbols, edges);
+
+        for (symbol, node) in symbols.iter().zip(nodes.iter_mut()) {
+            let change = match baseline.symbols.get(&symbol.symbol_uid) {
+                None => "added",
+                Some(entry) if entry.digest != current.symbols[&symbol.symbol_uid].digest => {
+                    "modified"
+                }
+                Some(_) => continue,
+            };
+            node.metadata
+                .insert("change".to_string(), change.to_string());
+        }
+
+        for (edge, graph_edge) in edges.iter().zip(graph_edges.iter_mut()) {
+            let key = GraphSnapshot::edge_key(edge);
+            let change = match baseline.edges.get(&key) {
+                None => "added",
+                Some(entry) if entry.digest != current.edges[&key].digest => "modified",
+                Some(_) => continue,
+            };
+            graph_edge
+                .metadata
+                .insert("change".to_string(), change.to_string());
+        }
+
+        let mut deleted_symbols: Vec<_> = baseline
+            .symbols
+            .iter()
+            .filter(|(uid, _)| !existing_uids.contains(*uid))
+            .filter(|(_, entry)| {
+                options
+                    .symbol_types_filter
+                    .as_ref()
+                    .map_or(true, |types| types.contains(&entry.kind))
+            })
+            .collect();
+        deleted_symbols.sort_by(|a, b| a.0.cmp(b.0));
+        nodes.extend(
+            deleted_symbols
+                .into_iter()
+                .map(|(uid, entry)| Self::tombstone_node(uid, &entry.kind)),
+        );
+
+        let mut deleted_edges: Vec<_> = baseline
+            .edges
+            .iter()
+            .filter(|(key, _)| !current.edges.contains_key(*key))
+            .filter(|(_, entry)| {
+                options
+                    .edge_types_filter
+                    .as_ref()
+                    .map_or(true, |types| types.contains(&entry.kind))
+            })
+            .collect();
+        deleted_edges.sort_by(|a, b| a.0.cmp(b.0));
+        graph_edges.extend(deleted_edges.into_iter().filter_map(|(key, entry)| {
+            let (source, target) = GraphSnapshot::edge_endpoints(key)?;
+            let mut metadata = HashMap::new();
+            metadata.insert("change".to_string(), "deleted".to_string());
+            Some(GraphEdge {
+                source: source.to_string(),
+                target: target.to_string(),
+                relation: entry.kind.clone(),
+                confidence: 0.0,
+                source_location: None,
+                target_location: None,
+                metadata,
+            })
+        }));
+    }
+
     fn filter_connected_only(
         symbols: Vec<SymbolState>,
         edges: Vec<Edge>,
@@ -358,63 +882,166 @@ impl GraphExporter {
     }
 
     fn symbols_to_nodes(symbols: &[SymbolState]) -> Vec<GraphNode> {
-        symbols
-            .iter()
-            .map(|symbol| {
-                let mut metadata = HashMap::new();
+        symbols.iter().map(Self::symbol_to_node).collect()
+    }
 
-                if let Some(fqn) = &symbol.fqn {
-                    metadata.insert("fqn".to_string(), fqn.clone());
-                }
+    fn symbol_to_node(symbol: &SymbolState) -> GraphNode {
+        let mut metadata = HashMap::new();
 
-                if symbol.is_definition {
-                    metadata.insert("is_definition".to_string(), "true".to_string());
-                }
+        if let Some(fqn) = &symbol.fqn {
+            metadata.insert("fqn".to_string(), fqn.clone());
+        }
 
-                metadata.insert("language".to_string(), symbol.language.clone());
-
-                GraphNode {
-                    id: symbol.symbol_uid.clone(),
-                    label: symbol.name.clone(),
-                    kind: symbol.kind.clone(),
-                    file_path: None, // TODO: Resolve file path from file_version_id
-                    line: symbol.def_start_line,
-                    column: symbol.def_start_char,
-                    signature: symbol.signature.clone(),
-                    visibility: symbol.visibility.clone(),
-                    documentation: symbol.documentation.clone(),
-                    metadata,
-                }
-            })
-            .collect()
+        if symbol.is_definition {
+            metadata.insert("is_definition".to_string(), "true".to_string());
+        }
+
+        metadata.insert("language".to_string(), symbol.language.clone());
+
+        GraphNode {
+            id: symbol.symbol_uid.clone(),
+            label: symbol.name.clone(),
+            kind: symbol.kind.clone(),
+            file_path: Some(symbol.file_path.clone()).filter(|path| !path.is_empty()),
+            line: symbol.def_start_line,
+            column: symbol.def_start_char,
+            signature: symbol.signature.clone(),
+            visibility: symbol.visibility.clone(),
+            documentation: symbol.documentation.clone(),
+            metadata,
+        }
     }
 
     fn edges_to_graph_edges(edges: &[Edge]) -> Vec<GraphEdge> {
-        edges
-            .iter()
-            .map(|edge| {
-                let mut metadata = HashMap::new();
-                metadata.insert("language".to_string(), edge.language.clone());
+        edges.iter().map(Self::edge_to_graph_edge).collect()
+    }
 
-                if let Some(meta) = &edge.metadata {
-                    metadata.insert("extra_metadata".to_string(), meta.clone());
-                }
+    fn edge_to_graph_edge(edge: &Edge) -> GraphEdge {
+        let mut metadata = HashMap::new();
+        metadata.insert("language".to_string(), edge.language.clone());
 
-                GraphEdge {
-                    source: edge.source_symbol_uid.clone(),
-                    target: edge.target_symbol_uid.clone(),
-                    relation: edge.relation.to_string().to_string(),
-                    confidence: edge.confidence,
-                    source_location: edge
-                        .start_line
-                        .map(|line| format!("{}:{}", line, edge.start_char.unwrap_or(0))),
-                    target_location: None, // TODO: Add target location if available
-                    metadata,
-                }
+        if let Some(meta) = &edge.metadata {
+            metadata.insert("extra_metadata".to_string(), meta.clone());
+        }
+
+        GraphEdge {
+            source: edge.source_symbol_uid.clone(),
+            target: edge.target_symbol_uid.clone(),
+            relation: edge.relation.to_string().to_string(),
+            confidence: edge.confidence,
+            source_location: edge
+                .start_line
+                .map(|line| format!("{}:{}", line, edge.start_char.unwrap_or(0))),
+            target_location: None, // TODO: Add target location if available
+            metadata,
+        }
+    }
+
+    /// Build synthetic nodes for external edge targets that are not exported symbols.
+    ///
+    /// Target UIDs use the `path:hash:name:line` format, where the path is a
+    /// `/dep/...` dependency path or an `EXTERNAL:` prefixed absolute path for
+    /// symbols outside the workspace. The path is exposed as the node's module.
+    fn external_target_nodes(
+        nodes: &[GraphNode],
+        edges: &[GraphEdge],
+        stored_uids: &HashSet<String>,
+    ) -> Vec<GraphNode> {
+        let mut known: HashSet<&str> = nodes.iter().map(|node| node.id.as_str()).collect();
+
+        edges
+            .iter()
+            .filter(|edge| {
+                Self::is_external_target(&edge.target, stored_uids)
+                    && known.insert(edge.target.as_str())
             })
+            .map(|edge| Self::external_node(&edge.target))
             .collect()
     }
 
+    /// Returns true if `target` lies outside the workspace: a dependency or `EXTERNAL:` UID,
+    /// or one with no stored symbol. Workspace symbols left out by the export filters are
+    /// not external.
+    fn is_external_target(target: &str, stored_uids: &HashSet<String>) -> bool {
+        // "none" marks an analyzed symbol with no relationships, not a real target
+        if target == "none" {
+            return false;
+        }
+        let outside_workspace = Self::split_uid(target)
+            .is_some_and(|(path, _, _)| path.starts_with("EXTERNAL:") || path.starts_with("/dep/"));
+        outside_workspace || !stored_uids.contains(target)
+    }
+
+    /// Split a `path:hash:name:line` UID into its path, name and line
+    fn split_uid(uid: &str) -> Option<(&str, &str, u32)> {
+        let mut parts = uid.rsplitn(4, ':');
+        let line = parts.next()?.parse::<u32>().ok()?;
+        let name = parts.next()?;
+        let _hash = parts.next()?;
+        let path = parts.next()?;
+        Some((path, name, line))
+    }
+
+    fn external_node(target: &str) -> GraphNode {
+        let (label, module, line) = match Self::split_uid(target) {
+            Some((module, name, line)) => (
+                name.to_string(),
+                module.trim_start_matches("EXTERNAL:").to_string(),
+                line,
+            ),
+            None => (target.to_string(), "unknown".to_string(), 0),
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("external".to_string(), "true".to_string());
+        metadata.insert("module".to_string(), module.clone());
+
+        GraphNode {
+            id: target.to_string(),
+            label,
+            kind: "external".to_string(),
+            file_path: Some(module),
+            line,
+            column: 0,
+            signature: None,
+            visibility: None,
+            documentation: None,
+            metadata,
+        }
+    }
+
+    /// Node standing in for a symbol that existed in the baseline run but no longer does
+    fn tombstone_node(uid: &str, kind: &str) -> GraphNode {
+        let (label, file_path, line) = match Self::split_uid(uid) {
+            Some((path, name, line)) => (name.to_string(), Some(path.to_string()), line),
+            None => (uid.to_string(), None, 0),
+        };
+
+        let mut metadata = HashMap::new();
+        metadata.insert("change".to_string(), "deleted".to_string());
+
+        GraphNode {
+            id: uid.to_string(),
+            label,
+            kind: kind.to_string(),
+            file_path,
+            line,
+            column: 0,
+            signature: None,
+            visibility: None,
+            documentation: None,
+            metadata,
+        }
+    }
+
+    fn is_deleted(metadata: &HashMap<String, String>) -> bool {
+        metadata.get("change").map(String::as_str) == Some("deleted")
+    }
+
+    fn delta_note(run_id: i64) -> String {
+        format!("Delta against analysis run {}", run_id)
+    }
+
     fn escape_xml(s: &str) -> String {
         s.replace("&", "&amp;")
             .replace("<", "&lt;")
@@ -433,6 +1060,23 @@ impl GraphExporter {
             .replace("\t", "\\t")
     }
 
+    /// Mermaid identifier for a node id: ASCII alphanumerics and underscores only, prefixed
+    /// so it never starts with a digit or reads as a keyword such as `end`
+    fn mermaid_id(s: &str) -> String {
+        let sanitized: String = s
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+            .collect();
+        format!("n_{}", sanitized)
+    }
+
+    /// Escape text for a quoted Mermaid label or an edge label
+    fn escape_mermaid_label(s: &str) -> String {
+        s.replace('"', "#quot;")
+            .replace('|', "#124;")
+            .replace('\n', " ")
+    }
+
     fn get_node_color(kind: &str) -> &'static str {
         match kind {
             "function" | "method" => "lightblue",
@@ -441,6 +1085,7 @@ impl GraphExporter {
             "enum" => "lightpink",
             "variable" | "field" => "lightgray",
             "module" | "namespace" => "lightcyan",
+            "external" => "lightsalmon",
             _ => "white",
         }
     }
@@ -468,6 +1113,7 @@ mod tests {
         assert_eq!(options.symbol_types_filter, None);
         assert_eq!(options.edge_types_filter, None);
         assert!(!options.connected_only);
+        assert!(!options.include_external);
     }
 
     #[test]
@@ -559,6 +1205,7 @@ mod tests {
         assert_eq!(graph.nodes.len(), 1);
         assert_eq!(graph.edges.len(), 1);
         assert_eq!(graph.nodes[0].id, "test_fn_1");
+        assert_eq!(graph.nodes[0].file_path.as_deref(), Some("test/test_fn.rs"));
         assert_eq!(graph.edges[0].source, "test_fn_1");
 
         println!(
@@ -570,6 +1217,603 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_graph_export_includes_external_nodes() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::database::{
+            DatabaseBackend, DatabaseConfig, Edge, EdgeRelation, SQLiteBackend, SymbolState,
+        };
+        use std::sync::Arc;
+
+        let config = DatabaseConfig {
+            temporary: true,
+            ..Default::default()
+        };
+        let db = Arc::new(SQLiteBackend::new(config).await?);
+
+        let caller_uid = "src/main.rs:1a2b3c4d:main:3".to_string();
+        let external_uid = "/dep/rust/serde_json/src/ser.rs:9f8e7d6c:to_string:2150".to_string();
+
+        db.store_symbols(&[SymbolState {
+            symbol_uid: caller_uid.clone(),
+            file_path: "src/main.rs".to_string(),
+            language: "rust".to_string(),
+            name: "main".to_string(),
+            fqn: None,
+            kind: "function".to_string(),
+            signature: Some("fn main()".to_string()),
+            visibility: None,
+            def_start_line: 3,
+            def_start_char: 0,
+            def_end_line: 6,
+            def_end_char: 1,
+            is_definition: true,
+            documentation: None,
+            metadata: None,
+        }])
+        .await?;
+        db.store_edges(&[Edge {
+            relation: EdgeRelation::Calls,
+            source_symbol_uid: caller_uid.clone(),
+            target_symbol_uid: external_uid.clone(),
+            file_path: Some("src/main.rs".to_string()),
+            start_line: Some(4),
+            start_char: Some(4),
+            confidence: 1.0,
+            language: "rust".to_string(),
+            metadata: None,
+        }])
+        .await?;
+
+        // Without the option the external target is not a node
+        let graph = GraphExporter::export_graph(
+            &*db,
+            PathBuf::from("/test"),
+            GraphExportOptions::default(),
+        )
+        .await?;
+        assert!(graph.nodes.iter().all(|n| n.id != external_uid));
+
+        let options = GraphExportOptions {
+            include_external: true,
+            ..Default::default()
+        };
+        let graph = GraphExporter::export_graph(&*db, PathBuf::from("/test"), options).await?;
+
+        let external = graph
+            .nodes
+            .iter()
+            .find(|n| n.id == external_uid)
+            .expect("external call target should be materialized as a node");
+        assert_eq!(external.label, "to_string");
+        assert_eq!(external.kind, "external");
+        assert_eq!(
+            external.metadata.get("external").map(String::as_str),
+            Some("true")
+        );
+        assert_eq!(
+            external.metadata.get("module").map(String::as_str),
+            Some("/dep/rust/serde_json/src/ser.rs")
+        );
+        assert_eq!(graph.metadata.nodes_count, graph.nodes.len());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_graphml_declares_typed_keys_and_streams_from_database(
+    ) -> Result<(), Box<dyn std::error::Error>> {
+        use crate::database::{
+            DatabaseBackend, DatabaseConfig, Edge, EdgeRelation, SQLiteBackend, SymbolState,
+        };
+
+        let config = DatabaseConfig {
+            temporary: true,
+            ..Default::default()
+        };
+        let db = SQLiteBackend::new(config).await?;
+        db.store_symbols(&[SymbolState {
+            symbol_uid: "src/lib.rs:abcd:parse:4".to_string(),
+            file_path: "src/lib.rs".to_string(),
+            language: "rust".to_string(),
+            name: "parse<T>".to_string(),
+            fqn: None,
+            kind: "function".to_string(),
+            signature: None,
+            visibility: Some("pub".to_string()),
+            def_start_line: 4,
+            def_start_char: 0,
+            def_end_line: 9,
+            def_end_char: 1,
+            is_definition: true,
+            documentation: None,
+            metadata: None,
+        }])
+        .await?;
+        db.store_edges(&[Edge {
+            relation: EdgeRelation::Calls,
+            source_symbol_uid: "src/lib.rs:abcd:parse:4".to_string(),
+            target_symbol_uid: "/dep/rust/std/src/io.rs:ef01:read:12".to_string(),
+            file_path: Some("src/lib.rs".to_string()),
+            start_line: Some(5),
+            start_char: Some(4),
+            confidence: 0.75,
+            language: "rust".to_string(),
+            metadata: None,
+        }])
+        .await?;
+        db.flush_pending_writes().await?;
+
+        let options = GraphExportOptions {
+            include_external: true,
+            ..Default::default()
+        };
+        let mut streamed = Vec::new();
+        GraphExporter::export_graphml(&db, options.clone(), &mut streamed).await?;
+        let streamed = String::from_utf8(streamed)?;
+
+        let graph = GraphExporter::export_graph(&db, PathBuf::from("/test"), options).await?;
+        assert_eq!(streamed, GraphExporter::to_graphml(&graph)?);
+
+        // Keys are declared with types before the graph
+        let graph_start = streamed.find("<graph ").unwrap();
+        for key in [
+            r#"<key id="language" for="node" attr.name="language" attr.type="string"/>"#,
+            r#"<key id="line" for="node" attr.name="line" attr.type="int"/>"#,
+            r#"<key id="confidence" for="edge" attr.name="confidence" attr.type="double"/>"#,
+        ] {
+            assert!(streamed.find(key).unwrap() < graph_start, "{key}");
+        }
+        assert!(streamed.contains(r#"<data key="label">parse&lt;T&gt;</data>"#));
+        assert!(streamed.contains(r#"<data key="language">rust</data>"#));
+        assert!(streamed.contains(r#"<data key="visibility">pub</data>"#));
+        assert!(streamed.contains(r#"<data key="kind">external</data>"#));
+        assert!(streamed.contains(r#"<data key="relation">calls</data>"#));
+        assert!(streamed.contains(r#"<data key="confidence">0.75</data>"#));
+        assert!(streamed.ends_with("</graphml>\n"));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_delta_export_against_completed_run() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::database::{DatabaseConfig, EdgeRelation, SQLiteBackend};
+
+        let symbol = |uid: &str, name: &str, signature: &str| SymbolState {
+            symbol_uid: uid.to_string(),
+            file_path: "src/lib.rs".to_string(),
+            language: "rust".to_string(),
+            name: name.to_string(),
+            fqn: None,
+            kind: "function".to_string(),
+            signature: Some(signature.to_string()),
+            visibility: None,
+            def_start_line: 1,
+            def_start_char: 0,
+            def_end_line: 2,
+            def_end_char: 1,
+            is_definition: true,
+            documentation: None,
+            metadata: None,
+        };
+        let calls = |source: &str, target: &str| Edge {
+            relation: EdgeRelation::Calls,
+            source_symbol_uid: source.to_string(),
+            target_symbol_uid: target.to_string(),
+            file_path: Some("src/lib.rs".to_string()),
+            start_line: Some(1),
+            start_char: Some(4),
+            confidence: 1.0,
+            language: "rust".to_string(),
+            metadata: None,
+        };
+        let (main, parse, emit) = (
+            "src/lib.rs:ab12:main:1",
+            "src/lib.rs:ab12:parse:5",
+            "src/lib.rs:ab12:emit:9",
+        );
+
+        let config = DatabaseConfig {
+            temporary: true,
+            ..Default::default()
+        };
+        let db = SQLiteBackend::new(config).await?;
+        db.store_symbols(&[
+            symbol(main, "main", "fn main()"),
+            symbol(parse, "parse", "fn parse()"),
+        ])
+        .await?;
+        db.store_edges(&[calls(main, parse)]).await?;
+
+        let run_id = db.create_analysis_run("test", "1.0", "rust", "{}").await?;
+        let options = GraphExportOptions {
+            baseline_run_id: Some(run_id),
+            ..Default::default()
+        };
+        // Only completed runs have a graph to compare against
+        assert!(
+            GraphExporter::export_graph(&db, PathBuf::from("/test"), options.clone())
+                .await
+                .is_err()
+        );
+        // Completing an unknown run fails and is rolled back, leaving the database usable
+        assert!(db.complete_analysis_run(run_id + 1).await.is_err());
+        db.complete_analysis_run(run_id).await?;
+
+        db.store_symbols(&[
+            symbol(parse, "parse", "fn parse(input: &str)"),
+            symbol(emit, "emit", "fn emit()"),
+        ])
+        .await?;
+        db.store_edges(&[calls(parse, emit)]).await?;
+        db.flush_pending_writes().await?;
+
+        let graph = GraphExporter::export_graph(&db, PathBuf::from("/test"), options).await?;
+        let change = |id: &str| {
+            graph
+                .nodes
+                .iter()
+                .find(|node| node.id == id)
+                .map(|node| node.metadata["change"].as_str())
+        };
+        assert_eq!(graph.nodes.len(), 2);
+        assert_eq!(change(parse), Some("modified"));
+        assert_eq!(change(emit), Some("added"));
+        assert_eq!(change(main), None);
+        assert_eq!(graph.edges.len(), 1);
+        assert_eq!(graph.edges[0].source, parse);
+        assert_eq!(graph.edges[0].metadata["change"], "added");
+
+        assert_eq!(graph.metadata.baseline_run_id, Some(run_id));
+        let note = format!("Delta against analysis run {}", run_id);
+        assert!(GraphExporter::to_dot(&graph)?.starts_with(&format!("// {}\n", note)));
+        assert!(GraphExporter::to_graphml(&graph)?.contains(&format!("<!-- {} -->", note)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_subgraph_export_follows_direction() -> Result<(), Box<dyn std::error::Error>> {
+        use crate::database::{create_none_edge, DatabaseConfig, SQLiteBackend};
+
+        let uid = |name: &str| format!("src/lib.rs:ab12:{}:1", name);
+        let symbol = |name: &str| SymbolState {
+            symbol_uid: uid(name),
+            file_path: "src/lib.rs".to_string(),
+            language: "rust".to_string(),
+            name: name.to_string(),
+            fqn: None,
+            kind: "function".to_string(),
+            signature: None,
+            visibility: None,
+            def_start_line: 1,
+            def_start_char: 0,
+            def_end_line: 2,
+            def_end_char: 1,
+            is_definition: true,
+            documentation: None,
+            metadata: None,
+        };
+        let calls = |source: &str, target: &str| Edge {
+            relation: EdgeRelation::Calls,
+            source_symbol_uid: uid(source),
+            target_symbol_uid: uid(target),
+            file_path: Some("src/lib.rs".to_string()),
+            start_line: Some(1),
+            start_char: Some(4),
+            confidence: 1.0,
+            language: "rust".to_string(),
+            metadata: None,
+        };
+
+        let config = DatabaseConfig {
+            temporary: true,
+            ..Default::default()
+        };
+        let db = SQLiteBackend::new(config).await?;
+        db.store_symbols(&["main", "serve", "handler", "parse", "log"].map(symbol))
+            .await?;
+        let mut edges = vec![
+            calls("main", "serve"),
+            calls("serve", "handler"),
+            calls("handler", "parse"),
+            calls("serve", "log"),
+        ];
+        edges.push(create_none_edge(&uid("parse"), EdgeRelation::Calls));
+        db.store_edges(&edges).await?;
+        db.flush_pending_writes().await?;
+
+        let export = |root: &str, direction: CallDirection, max_depth: u32| {
+            let options = GraphExportOptions {
+                max_depth: Some(max_depth),
+                subgraph: Some(SubgraphOptions {
+                    root: root.to_string(),
+                    direction,
+                }),
+                ..Default::default()
+            };
+            GraphExporter::export_graph(&db, PathBuf::from("/test"), options)
+        };
+        let labels = |graph: &ExportGraph| {
+            let mut labels: Vec<String> = graph.nodes.iter().map(|n| n.label.clone()).collect();
+            labels.sort();
+            labels
+        };
+
+        let callers = export("/ws/src/lib.rs#handler", CallDirection::Incoming, 4).await?;
+        assert_eq!(callers.metadata.root_symbol, Some(uid("handler")));
+        assert_eq!(labels(&callers), ["handler", "main", "serve"]);
+        assert_eq!(callers.edges.len(), 2);
+
+        let callees = export(&uid("serve"), CallDirection::Outgoing, 1).await?;
+        assert_eq!(labels(&callees), ["handler", "log", "serve"]);
+
+        // The analyzed-empty marker of parse is no edge
+        let both = export("src/lib.rs#handler", CallDirection::Both, 1).await?;
+        assert_eq!(labels(&both), ["handler", "parse", "serve"]);
+        assert!(both.edges.iter().all(|edge| edge.target != "none"));
+        assert!(!GraphExporter::to_dot(&both)?.contains("\"none\""));
+
+        assert!(export("src/lib.rs#missing", CallDirection::Both, 1)
+            .await
+            .is_err());
+        assert!(SubgraphOptions::parse_direction("upwards").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_mark_changes_adds_filtered_tombstones() {
+        let symbol = |uid: &str, kind: &str| SymbolState {
+            symbol_uid: uid.to_string(),
+            file_path: "src/lib.rs".to_string(),
+            language: "rust".to_string(),
+            name: uid.to_string(),
+            fqn: None,
+            kind: kind.to_string(),
+            signature: None,
+            visibility: None,
+            def_start_line: 1,
+            def_start_char: 0,
+            def_end_line: 1,
+            def_end_char: 1,
+            is_definition: true,
+            documentation: None,
+            metadata: None,
+        };
+        let edge = Edge {
+            relation: crate::database::EdgeRelation::Calls,
+            source_symbol_uid: "src/lib.rs:ab12:old:3".to_string(),
+            target_symbol_uid: "src/lib.rs:ab12:kept:7".to_string(),
+            file_path: None,
+            start_line: Some(4),
+            start_char: Some(2),
+            confidence: 1.0,
+            language: "rust".to_string(),
+            metadata: None,
+        };
+        let kept = symbol("src/lib.rs:ab12:kept:7", "function");
+        let baseline = GraphSnapshot::from_graph(
+            &[
+                kept.clone(),
+                symbol("src/lib.rs:ab12:old:3", "function"),
+                symbol("src/lib.rs:ab12:Old:5", "struct"),
+            ],
+            &[edge],
+        );
+
+        let options = GraphExportOptions {
+            symbol_types_filter: Some(vec!["function".to_string()]),
+            ..Default::default()
+        };
+        let symbols = vec![kept.clone()];
+        let existing_uids = HashSet::from([kept.symbol_uid.clone()]);
+        let mut nodes = GraphExporter::symbols_to_nodes(&symbols);
+        let mut edges = Vec::new();
+        GraphExporter::mark_changes(
+            &baseline,
+            &options,
+            &existing_uids,
+            &symbols,
+            &mut nodes,
+            &[],
+            &mut edges,
+        );
+
+        // The deleted struct is outside the filter
+        assert_eq!(nodes.len(), 2);
+        assert!(!nodes[0].metadata.contains_key("change"));
+        assert_eq!(nodes[1].id, "src/lib.rs:ab12:old:3");
+        assert_eq!(nodes[1].label, "old");
+        assert_eq!(nodes[1].line, 3);
+        assert_eq!(nodes[1].metadata["change"], "deleted");
+        assert_eq!(edges.len(), 1);
+        assert_eq!(edges[0].source, "src/lib.rs:ab12:old:3");
+        assert_eq!(edges[0].target, "src/lib.rs:ab12:kept:7");
+        assert_eq!(edges[0].relation, "calls");
+        assert_eq!(edges[0].metadata["change"], "deleted");
+
+        let graph = ExportGraph {
+            nodes,
+            edges,
+            metadata: GraphMetadata {
+                workspace_path: PathBuf::from("/test"),
+                export_timestamp: "2024-01-01T00:00:00Z".to_string(),
+                nodes_count: 2,
+                edges_count: 1,
+                filtered_symbol_types: None,
+                filtered_edge_types: None,
+                max_depth: None,
+                connected_only: false,
+                baseline_run_id: Some(7),
+                root_symbol: None,
+            },
+        };
+        let mermaid = GraphExporter::to_mermaid(&graph, &MermaidOptions::default()).unwrap();
+        assert!(mermaid.starts_with("%% Delta against analysis run 7\nflowchart LR\n"));
+        assert!(mermaid.contains(" -.->|calls| "));
+    }
+
+    #[test]
+    fn test_external_target_nodes_skip_known_stored_and_none_targets() {
+        let edge = |target: &str| GraphEdge {
+            source: "a".to_string(),
+            target: target.to_string(),
+            relation: "calls".to_string(),
+            confidence: 1.0,
+            source_location: None,
+            target_location: None,
+            metadata: HashMap::new(),
+        };
+        let known = GraphNode {
+            id: "a".to_string(),
+            label: "a".to_string(),
+            kind: "function".to_string(),
+            file_path: None,
+            line: 1,
+            column: 0,
+            signature: None,
+            visibility: None,
+            documentation: None,
+            metadata: HashMap::new(),
+        };
+        let edges = vec![
+            edge("a"),
+            edge("none"),
+            edge("src/lib.rs:cafe:filtered:3"),
+            edge("EXTERNAL:/usr/lib/libc.h:deadbeef:printf:12"),
+            edge("EXTERNAL:/usr/lib/libc.h:deadbeef:printf:12"),
+        ];
+        // Stored but filtered out of the export, so not external
+        let stored_uids =
+            HashSet::from(["a".to_string(), "src/lib.rs:cafe:filtered:3".to_string()]);
+
+        let nodes = GraphExporter::external_target_nodes(&[known], &edges, &stored_uids);
+        assert_eq!(nodes.len(), 1);
+        assert_eq!(nodes[0].label, "printf");
+        assert_eq!(nodes[0].line, 12);
+        assert_eq!(
+            nodes[0].metadata.get("module").map(String::as_str),
+            Some("/usr/lib/libc.h")
+        );
+    }
+
+    fn mermaid_graph() -> ExportGraph {
+        let node = |id: &str, label: &str, file: Option<&str>| GraphNode {
+            id: id.to_string(),
+            label: label.to_string(),
+            kind: "function".to_string(),
+            file_path: file.map(str::to_string),
+            line: 1,
+            column: 0,
+            signature: None,
+            visibility: None,
+            documentation: None,
+            metadata: HashMap::new(),
+        };
+        let edge = |source: &str, target: &str, relation: &str| GraphEdge {
+            source: source.to_string(),
+            target: target.to_string(),
+            relation: relation.to_string(),
+            confidence: 1.0,
+            source_location: None,
+            target_location: None,
+            metadata: HashMap::new(),
+        };
+        ExportGraph {
+            nodes: vec![
+                node("src/main.rs:1a2b:main:3", "main", Some("src/main.rs")),
+                node("src/lib.rs:9f8e:parse:10", "parse", Some("src/lib.rs")),
+                node(
+                    "src/lib.rs:9f8e:emit:20",
+                    "emit \"all\"",
+                    Some("src/lib.rs"),
+                ),
+                node("end", "end", None),
+            ],
+            edges: vec![
+                edge(
+                    "src/main.rs:1a2b:main:3",
+                    "src/lib.rs:9f8e:parse:10",
+                    "calls",
+                ),
+                edge(
+                    "src/main.rs:1a2b:main:3",
+                    "src/lib.rs:9f8e:emit:20",
+                    "calls",
+                ),
+                edge(
+                    "src/lib.rs:9f8e:parse:10",
+                    "src/lib.rs:9f8e:emit:20",
+                    "references",
+                ),
+                edge("src/main.rs:1a2b:main:3", "none", "calls"),
+            ],
+            metadata: GraphMetadata {
+                workspace_path: PathBuf::from("/test/workspace"),
+                export_timestamp: "2024-01-01T00:00:00Z".to_string(),
+                nodes_count: 4,
+                edges_count: 4,
+                filtered_symbol_types: None,
+                filtered_edge_types: None,
+                max_depth: None,
+                connected_only: false,
+                baseline_run_id: None,
+                root_symbol: None,
+            },
+        }
+    }
+
+    #[test]
+    fn test_to_mermaid_sanitizes_ids_and_labels_edges() {
+        assert_eq!(
+            "mermaid".parse::<GraphExportFormat>(),
+            Ok(GraphExportFormat::Mermaid)
+        );
+
+        let graph = mermaid_graph();
+        let output = GraphExporter::render(&graph, GraphExportFormat::Mermaid).unwrap();
+        assert!(output.starts_with("flowchart LR\n"));
+        assert!(output.contains("  n_src_main_rs_1a2b_main_3[\"main\"]\n"));
+        assert!(output.contains("[\"emit #quot;all#quot;\"]"));
+        // Keywords are prefixed like any other id
+        assert!(output.contains("  n_end[\"end\"]\n"));
+        assert!(
+            output.contains("  n_src_main_rs_1a2b_main_3 -->|calls| n_src_lib_rs_9f8e_parse_10\n")
+        );
+        assert!(output.contains("-->|references|"));
+        // Edges to targets that are not nodes are left out
+        assert_eq!(output.matches("-->").count(), 3);
+        assert!(!output.contains("subgraph"));
+        assert!(!output.contains("Truncated"));
+    }
+
+    #[test]
+    fn test_to_mermaid_clusters_by_file_and_truncates() {
+        let graph = mermaid_graph();
+        let options = MermaidOptions {
+            cluster_by_file: true,
+            max_nodes: None,
+        };
+        let output = GraphExporter::to_mermaid(&graph, &options).unwrap();
+        assert!(
+            output.contains("  subgraph file_0[\"src/main.rs\"]\n    n_src_main_rs_1a2b_main_3")
+        );
+        assert!(output.contains("  subgraph file_1[\"src/lib.rs\"]\n"));
+        assert_eq!(output.matches("  end\n").count(), 2);
+
+        // The isolated node is dropped first; ties keep the export order
+        let options = MermaidOptions {
+            cluster_by_file: false,
+            max_nodes: Some(2),
+        };
+        let output = GraphExporter::to_mermaid(&graph, &options).unwrap();
+        assert!(output.contains("n_src_main_rs_1a2b_main_3[\"main\"]"));
+        assert!(output.contains("n_src_lib_rs_9f8e_parse_10[\"parse\"]"));
+        assert!(!output.contains("n_end["));
+        assert_eq!(output.matches("-->").count(), 1);
+        assert!(output.contains("Truncated: 2 of 4 nodes and 3 edges not shown"));
+    }
+
     #[tokio::test]
     async fn test_to_json_serialization() {
         let graph = ExportGraph {
@@ -595,6 +1839,8 @@ mod tests {
                 filtered_edge_types: None,
                 max_depth: None,
                 connected_only: false,
+                baseline_run_id: None,
+                root_symbol: None,
             },
         };
 
@@ -333,6 +333,22 @@ impl LanguageExtractor for RustLanguageExtractor {
             }
         }
 
+        // Outer attributes are siblings preceding the item in the tree-sitter-rust grammar
+        let mut sibling = node.prev_sibling();
+        while let Some(prev) = sibling {
+            match prev.kind() {
+                "attribute_item" => {
+                    let attr_text = prev.utf8_text(content).unwrap_or("");
+                    if attr_text.contains("test]") || attr_text.contains("test(") {
+                        return true;
+                    }
+                }
+                "line_comment" | "block_comment" => {}
+                _ => break,
+            }
+            sibling = prev.prev_sibling();
+        }
+
         // Check function name starting with "test_"
         if let Some(name) = self.extract_symbol_name(node, content) {
             return name.starts_with("test_");
@@ -829,7 +845,14 @@ impl AstSymbolExtractor {
             },
             crate::language_detector::Language::Go => match node_kind {
                 "function_declaration" | "method_declaration" => (SymbolKind::Function, true),
-                "type_declaration" => (SymbolKind::Type, true),
+                // `type_declaration` has no name of its own (and may group several
+                // specs), so extract each `type_spec` and classify it by its body
+                "type_spec" => match node.child_by_field_name("type").map(|t| t.kind()) {
+                    Some("interface_type") => (SymbolKind::Interface, true),
+                    Some("struct_type") => (SymbolKind::Struct, true),
+                    _ => (SymbolKind::Type, true),
+                },
+                "type_alias" => (SymbolKind::Type, true),
                 _ => (SymbolKind::Function, false),
             },
             crate::language_detector::Language::Java => match node_kind {
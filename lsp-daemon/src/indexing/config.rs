@@ -1,6 +1,6 @@
 use anyhow::{anyhow, Context, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 use tracing::{debug, info, warn};
@@ -41,6 +41,14 @@ pub struct IndexingConfig {
     /// Whether to use incremental indexing based on file modification time
     pub incremental_mode: bool,
 
+    /// Index test files and test functions (excluded from the symbol graph by default)
+    #[serde(default)]
+    pub include_tests: bool,
+
+    /// Stop indexing after this many seconds, deferring remaining files to the next run
+    #[serde(default)]
+    pub max_duration_secs: Option<u64>,
+
     /// Batch size for file discovery operations
     pub discovery_batch_size: usize,
 
@@ -477,6 +485,8 @@ impl Default for IndexingConfig {
             global_include_patterns: vec![],
             max_file_size_bytes: 10 * 1024 * 1024, // 10MB - matches main config max_file_size_mb default
             incremental_mode: true,                // Re-enabled with timestamp fix
+            include_tests: false,
+            max_duration_secs: None, // Unlimited
             discovery_batch_size: 1000,
             status_update_interval_secs: 5,
             file_processing_timeout_ms: 30000, // 30 seconds
@@ -723,6 +733,8 @@ impl IndexingConfig {
                 .collect();
         }
 
+        config.apply_language_overrides(&main_indexing.language_configs);
+
         config.validate()?;
         Ok(config)
     }
@@ -796,11 +808,23 @@ impl IndexingConfig {
                 .context("Invalid value for PROBE_INDEX_STATUS_INTERVAL")?;
         }
 
+        if let Ok(value) = std::env::var("PROBE_INDEX_MAX_DURATION_SECS") {
+            config.max_duration_secs = Some(
+                value
+                    .parse()
+                    .context("Invalid value for PROBE_INDEX_MAX_DURATION_SECS")?,
+            );
+        }
+
         // Boolean flags
         if let Ok(value) = std::env::var("PROBE_INDEX_INCREMENTAL") {
             config.incremental_mode = parse_bool_env(&value, "PROBE_INDEX_INCREMENTAL")?;
         }
 
+        if let Ok(value) = std::env::var("PROBE_INDEX_INCLUDE_TESTS") {
+            config.include_tests = parse_bool_env(&value, "PROBE_INDEX_INCLUDE_TESTS")?;
+        }
+
         if let Ok(value) = std::env::var("PROBE_INDEX_PARALLEL") {
             config.parallel_file_processing = parse_bool_env(&value, "PROBE_INDEX_PARALLEL")?;
         }
@@ -938,6 +962,8 @@ impl IndexingConfig {
         merge_field!(max_queue_size);
         merge_field!(max_file_size_bytes);
         merge_field!(incremental_mode);
+        merge_field!(include_tests);
+        merge_field!(max_duration_secs);
         merge_field!(discovery_batch_size);
         merge_field!(status_update_interval_secs);
         merge_field!(file_processing_timeout_ms);
@@ -989,6 +1015,10 @@ impl IndexingConfig {
             return Err(anyhow!("default_depth must be greater than 0"));
         }
 
+        if self.max_duration_secs == Some(0) {
+            return Err(anyhow!("max_duration_secs must be greater than 0"));
+        }
+
         if self.file_processing_timeout_ms < 1000 {
             warn!(
                 "file_processing_timeout_ms is very low ({}ms), may cause timeouts",
@@ -1035,6 +1065,7 @@ impl IndexingConfig {
                 .unwrap_or(self.file_processing_timeout_ms),
             file_extensions: language_config
                 .map(|c| c.file_extensions.clone())
+                .filter(|extensions| !extensions.is_empty())
                 .unwrap_or_else(|| default_extensions_for_language(language)),
             exclude_patterns: {
                 let mut patterns = self.global_exclude_patterns.clone();
@@ -1097,6 +1128,8 @@ impl IndexingConfig {
             specific_files: vec![], // Empty by default, populated when indexing specific files
             max_file_size_mb: Some(self.max_file_size_bytes / 1024 / 1024),
             incremental: Some(self.incremental_mode),
+            include_tests: Some(self.include_tests),
+            max_duration_secs: self.max_duration_secs,
             languages: self
                 .priority_languages
                 .iter()
@@ -1127,6 +1160,31 @@ impl IndexingConfig {
                 .map(op_to_string)
                 .collect(),
             lsp_indexing_enabled: Some(self.lsp_caching.enabled),
+            language_configs: {
+                let mut overrides: HashMap<String, crate::protocol::LanguageIndexingConfig> = self
+                    .disabled_languages
+                    .iter()
+                    .map(|l| {
+                        (
+                            l.as_str().to_string(),
+                            crate::protocol::LanguageIndexingConfig {
+                                enabled: Some(false),
+                            },
+                        )
+                    })
+                    .collect();
+                for (language, language_config) in &self.language_configs {
+                    if language_config.enabled.is_some() {
+                        overrides.insert(
+                            language.as_str().to_string(),
+                            crate::protocol::LanguageIndexingConfig {
+                                enabled: language_config.enabled,
+                            },
+                        );
+                    }
+                }
+                overrides
+            },
         }
     }
 
@@ -1171,6 +1229,14 @@ impl IndexingConfig {
             config.incremental_mode = incremental;
         }
 
+        if let Some(include_tests) = protocol.include_tests {
+            config.include_tests = include_tests;
+        }
+
+        if protocol.max_duration_secs.is_some() {
+            config.max_duration_secs = protocol.max_duration_secs;
+        }
+
         if !protocol.languages.is_empty() {
             config.priority_languages = protocol
                 .languages
@@ -1234,8 +1300,49 @@ impl IndexingConfig {
             config.lsp_caching.enabled = lsp_indexing_enabled;
         }
 
+        config.apply_language_overrides(&protocol.language_configs);
+
         config
     }
+
+    /// Apply per-language overrides keyed by language name; unknown names are ignored
+    fn apply_language_overrides(
+        &mut self,
+        overrides: &HashMap<String, crate::protocol::LanguageIndexingConfig>,
+    ) {
+        for (name, language_override) in overrides {
+            match Language::from_str(name) {
+                Some(language) if language != Language::Unknown => {
+                    self.language_configs.entry(language).or_default().enabled =
+                        language_override.enabled;
+                }
+                _ => warn!("Ignoring indexing override for unknown language '{}'", name),
+            }
+        }
+    }
+
+    /// Languages that must not be indexed, from both `disabled_languages` and per-language flags
+    pub fn disabled_language_set(&self) -> HashSet<Language> {
+        let mut disabled: HashSet<Language> = self.disabled_languages.iter().copied().collect();
+        for (language, language_config) in &self.language_configs {
+            match language_config.enabled {
+                Some(false) => {
+                    disabled.insert(*language);
+                }
+                Some(true) => {
+                    disabled.remove(language);
+                }
+                None => {}
+            }
+        }
+        disabled
+    }
+
+    /// Persist the per-language enabled flags to the main settings file so they survive
+    /// a daemon restart. Returns the file that was written.
+    pub fn save_language_toggles(&self) -> Result<PathBuf> {
+        save_main_config_language_toggles(&self.to_protocol_config().language_configs)
+    }
 }
 
 /// Effective configuration for a specific language after merging global and language-specific settings
@@ -1592,14 +1699,7 @@ fn load_main_config() -> Result<crate::protocol::IndexingConfig> {
     // In the future, this could be enhanced to use IPC or shared configuration
 
     // Try to load probe configuration from standard locations
-    let config_paths = [
-        dirs::config_dir().map(|d| d.join("probe").join("settings.json")),
-        dirs::home_dir().map(|d| d.join(".probe").join("settings.json")),
-        Some(PathBuf::from(".probe/settings.json")),
-        Some(PathBuf::from("settings.json")),
-    ];
-
-    for config_path in config_paths.into_iter().flatten() {
+    for config_path in main_config_paths() {
         if config_path.exists() {
             if let Ok(contents) = std::fs::read_to_string(&config_path) {
                 if let Ok(config) = serde_json::from_str::<serde_json::Value>(&contents) {
@@ -1622,6 +1722,96 @@ fn load_main_config() -> Result<crate::protocol::IndexingConfig> {
     Ok(crate::protocol::IndexingConfig::default())
 }
 
+/// Standard locations of the main application settings, in lookup order
+fn main_config_paths() -> Vec<PathBuf> {
+    [
+        dirs::config_dir().map(|d| d.join("probe").join("settings.json")),
+        dirs::home_dir().map(|d| d.join(".probe").join("settings.json")),
+        Some(PathBuf::from(".probe/settings.json")),
+        Some(PathBuf::from("settings.json")),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+/// Write per-language enabled flags into `indexing.language_configs` of the settings file
+/// that `load_main_config` reads, leaving every other setting untouched
+fn save_main_config_language_toggles(
+    toggles: &HashMap<String, crate::protocol::LanguageIndexingConfig>,
+) -> Result<PathBuf> {
+    let paths = main_config_paths();
+    let existing = paths.iter().find_map(|path| {
+        let contents = std::fs::read_to_string(path).ok()?;
+        let value = serde_json::from_str::<serde_json::Value>(&contents).ok()?;
+        value
+            .get("indexing")
+            .is_some()
+            .then(|| (path.clone(), value))
+    });
+    let (config_path, mut settings) = match existing {
+        Some(found) => found,
+        None => {
+            let path = dirs::home_dir()
+                .map(|d| d.join(".probe").join("settings.json"))
+                .ok_or_else(|| anyhow!("Could not determine home directory"))?;
+            let settings = match std::fs::read_to_string(&path) {
+                Ok(contents) => serde_json::from_str(&contents)
+                    .with_context(|| format!("Failed to parse {path:?}"))?,
+                Err(_) => serde_json::json!({}),
+            };
+            (path, settings)
+        }
+    };
+
+    let root = settings
+        .as_object_mut()
+        .ok_or_else(|| anyhow!("Settings file {:?} is not a JSON object", config_path))?;
+    let indexing = root
+        .entry("indexing")
+        .or_insert_with(|| serde_json::json!({}))
+        .as_object_mut()
+        .ok_or_else(|| anyhow!("'indexing' in {:?} is not a JSON object", config_path))?;
+    let language_configs = indexing
+        .entry("language_configs")
+        .or_insert_with(|| serde_json::json!({}))
+        .as_object_mut()
+        .ok_or_else(|| {
+            anyhow!(
+                "'indexing.language_configs' in {:?} is not a JSON object",
+                config_path
+            )
+        })?;
+
+    // Drop flags that were cleared, keep any other per-language settings
+    for (name, language_config) in language_configs.iter_mut() {
+        if !toggles.contains_key(name) {
+            if let Some(entry) = language_config.as_object_mut() {
+                entry.remove("enabled");
+            }
+        }
+    }
+    for (name, toggle) in toggles {
+        if let Some(enabled) = toggle.enabled {
+            let entry = language_configs
+                .entry(name.clone())
+                .or_insert_with(|| serde_json::json!({}));
+            if let Some(entry) = entry.as_object_mut() {
+                entry.insert("enabled".to_string(), serde_json::Value::Bool(enabled));
+            }
+        }
+    }
+
+    if let Some(parent) = config_path.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("Failed to create config directory {parent:?}"))?;
+    }
+    std::fs::write(&config_path, serde_json::to_string_pretty(&settings)?)
+        .with_context(|| format!("Failed to write {config_path:?}"))?;
+    info!("Saved language indexing toggles to {:?}", config_path);
+    Ok(config_path)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1983,4 +2173,37 @@ mod tests {
         assert_eq!(rust_effective.priority, 100); // Priority language
         assert_eq!(go_effective.priority, 50); // Default priority
     }
+
+    #[test]
+    fn test_language_toggle_round_trips_through_protocol() {
+        let mut protocol = IndexingConfig::default().to_protocol_config();
+        protocol.language_configs.insert(
+            "cpp".to_string(),
+            crate::protocol::LanguageIndexingConfig {
+                enabled: Some(false),
+            },
+        );
+
+        let mut config = IndexingConfig::from_protocol_config(&protocol);
+        assert!(!config.for_language(Language::Cpp).enabled);
+        assert!(config.for_language(Language::Rust).enabled);
+        assert_eq!(
+            config.disabled_language_set(),
+            HashSet::from([Language::Cpp])
+        );
+        assert_eq!(
+            config.to_protocol_config().language_configs["cpp"].enabled,
+            Some(false)
+        );
+
+        // A per-language flag overrides the global disabled list
+        config.disabled_languages = vec![Language::Go];
+        config
+            .language_configs
+            .entry(Language::Go)
+            .or_default()
+            .enabled = Some(true);
+        assert!(!config.disabled_language_set().contains(&Language::Go));
+        assert!(config.for_language(Language::Go).enabled);
+    }
 }
@@ -2191,7 +2191,9 @@ impl LspEnrichmentWorkerPool {
             // Kind-based gating for Implementations to avoid server errors (e.g., gopls on free functions)
             let kind_lc_impl = queue_item.kind.to_ascii_lowercase();
             let impl_candidate = match queue_item.language {
-                Language::Go => matches!(kind_lc_impl.as_str(), "interface" | "method" | "type"),
+                // gopls answers for concrete types with the interfaces they satisfy, which
+                // would invert the edge; query interfaces so implementers become sources
+                Language::Go => matches!(kind_lc_impl.as_str(), "interface" | "method"),
                 Language::Rust => matches!(
                     kind_lc_impl.as_str(),
                     "trait" | "struct" | "enum" | "impl" | "method" | "type" | "typealias"
@@ -9,6 +9,7 @@
 
 use crate::cache_types::DefinitionInfo;
 use crate::database::{DatabaseBackend, PendingEnrichmentCounts, SymbolEnrichmentPlan};
+use crate::file_watcher::{FileEvent, FileEventType};
 use crate::indexing::empty_result_cache::EmptyResultCache;
 use crate::indexing::{
     lsp_enrichment_queue::{
@@ -37,7 +38,7 @@ use ignore::WalkBuilder;
 use serde::{Deserialize, Serialize};
 use std::collections::hash_map::DefaultHasher;
 use std::collections::hash_map::Entry;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
@@ -133,6 +134,12 @@ pub struct ManagerConfig {
     /// Whether to use file modification time for incremental indexing
     pub incremental_mode: bool,
 
+    /// Whether to index test files and test symbols
+    pub include_tests: bool,
+
+    /// Time budget for indexing (None = unlimited); queued files are deferred once it elapses
+    pub max_duration: Option<Duration>,
+
     /// Batch size for file discovery
     pub discovery_batch_size: usize,
 
@@ -162,6 +169,8 @@ impl Default for ManagerConfig {
             max_file_size_bytes: 10 * 1024 * 1024, // 10MB max per file
             enabled_languages: vec![],             // Empty = all languages
             incremental_mode: true,
+            include_tests: false,
+            max_duration: None,
             discovery_batch_size: 100,
             status_update_interval_secs: 5,
             specific_files: vec![], // Empty = index all files
@@ -314,6 +323,12 @@ pub struct IndexingManager {
     /// Track if Phase 1 is complete
     phase1_complete: Arc<AtomicBool>,
 
+    /// Set once `max_duration` elapses; workers stop taking new files
+    deadline_reached: Arc<AtomicBool>,
+
+    /// Languages toggled off at runtime; workers skip their files
+    disabled_languages: Arc<RwLock<HashSet<Language>>>,
+
     /// Track if Phase 2 monitor is running
     phase2_monitor_running: Arc<AtomicBool>,
 
@@ -323,6 +338,9 @@ pub struct IndexingManager {
     /// Workspace root for this indexing session (used for DB routing)
     workspace_root: Arc<RwLock<Option<PathBuf>>>,
 
+    /// Analysis run recorded for this indexing session, completed once every file is done
+    analysis_run_id: Arc<RwLock<Option<i64>>>,
+
     /// Aggregated LSP indexing counters for observability
     lsp_indexing_counters: Arc<LspIndexingCounters>,
     /// In-memory TTL cache for empty LSP results to avoid thrash
@@ -599,9 +617,12 @@ impl IndexingManager {
             enrichment_worker_handles: Arc::new(RwLock::new(Vec::new())),
             phase2_signal: Arc::new(tokio::sync::Notify::new()),
             phase1_complete: Arc::new(AtomicBool::new(false)),
+            deadline_reached: Arc::new(AtomicBool::new(false)),
+            disabled_languages: Arc::new(RwLock::new(HashSet::new())),
             phase2_monitor_running: Arc::new(AtomicBool::new(false)),
             phase2_monitor_handle: Arc::new(tokio::sync::Mutex::new(None)),
             workspace_root: Arc::new(RwLock::new(None)),
+            analysis_run_id: Arc::new(RwLock::new(None)),
             lsp_indexing_counters: Arc::new(LspIndexingCounters::default()),
             empty_cache: Arc::new(EmptyResultCache::from_env()),
             missing_lsp_snapshot: Arc::new(RwLock::new(std::collections::HashMap::new())),
@@ -629,6 +650,8 @@ impl IndexingManager {
                 .map(|l| format!("{l:?}"))
                 .collect(),
             incremental_mode: config.incremental_mode,
+            include_tests: config.include_tests,
+            max_duration: config.max_duration_secs.map(Duration::from_secs),
             discovery_batch_size: config.discovery_batch_size,
             status_update_interval_secs: config.status_update_interval_secs,
             specific_files: vec![], // Not available in comprehensive config, always empty
@@ -644,6 +667,7 @@ impl IndexingManager {
 
         // Store the full indexing configuration for LSP settings access
         manager.indexing_config = Some(config.clone());
+        manager.disabled_languages = Arc::new(RwLock::new(config.disabled_language_set()));
         manager
     }
 
@@ -701,6 +725,11 @@ impl IndexingManager {
         // Reset state
         self.reset_state().await;
 
+        // Record an analysis run, completed once every discovered file is done, so graph
+        // exports can later be limited to what changed since
+        let run_id = self.create_analysis_run(&root_path).await;
+        *self.analysis_run_id.write().await = run_id;
+
         // Update status
         *self.status.write().await = ManagerStatus::Discovering;
 
@@ -1013,11 +1042,75 @@ impl IndexingManager {
         self.progress.get_snapshot()
     }
 
+    /// Shared progress tracker, for rates, ETA and the per-language breakdown
+    pub fn get_progress_tracker(&self) -> Arc<IndexingProgress> {
+        Arc::clone(&self.progress)
+    }
+
     /// Get queue information
     pub async fn get_queue_snapshot(&self) -> crate::indexing::QueueSnapshot {
         self.queue.get_snapshot().await
     }
 
+    /// Get queue counters and utilization
+    pub async fn get_queue_metrics(&self) -> crate::indexing::QueueMetrics {
+        self.queue.get_metrics().await
+    }
+
+    /// Index a file before the rest of the queue: its queued item is raised to high
+    /// priority, or it is queued at high priority if it is not waiting yet. Returns whether
+    /// the queue changed.
+    pub async fn prioritize_file(&self, file_path: &Path) -> Result<bool> {
+        let language = self.language_detector.detect(file_path)?;
+        if language == Language::Unknown {
+            return Err(anyhow!("Not a source file: {:?}", file_path));
+        }
+        if self.disabled_languages.read().await.contains(&language) {
+            return Err(anyhow!(
+                "Indexing is disabled for {}: {:?}",
+                language.as_str(),
+                file_path
+            ));
+        }
+        self.queue.prioritize(file_path, Priority::High).await
+    }
+
+    /// Replace the set of languages whose files are no longer indexed. Takes effect for
+    /// files that have not been dispatched yet.
+    pub async fn set_disabled_languages(&self, languages: HashSet<Language>) {
+        *self.disabled_languages.write().await = languages;
+    }
+
+    /// Act on file watcher events: files that changed are indexed ahead of the background
+    /// queue, and deleted files are dropped from it. Returns the number of files raised.
+    pub async fn handle_file_events(&self, events: &[FileEvent]) -> Result<usize> {
+        let mut raised = 0;
+        for event in events {
+            match event.event_type {
+                FileEventType::Deleted => {
+                    self.queue
+                        .remove_matching(|item| item.file_path == event.file_path)
+                        .await;
+                }
+                FileEventType::Created | FileEventType::Modified => {
+                    let is_source = self
+                        .language_detector
+                        .detect(&event.file_path)
+                        .is_ok_and(|language| language != Language::Unknown);
+                    if is_source
+                        && self
+                            .queue
+                            .prioritize(&event.file_path, Priority::High)
+                            .await?
+                    {
+                        raised += 1;
+                    }
+                }
+            }
+        }
+        Ok(raised)
+    }
+
     /// Get worker statistics
     pub async fn get_worker_stats(&self) -> Vec<WorkerStats> {
         self.worker_stats.read().await.values().cloned().collect()
@@ -1028,6 +1121,7 @@ impl IndexingManager {
         self.progress.reset();
         self.queue.clear().await;
         self.shutdown_signal.store(false, Ordering::Relaxed);
+        self.deadline_reached.store(false, Ordering::Relaxed);
         self.worker_stats.write().await.clear();
 
         // Clear indexed files if not in incremental mode
@@ -1083,6 +1177,42 @@ impl IndexingManager {
             tasks.push(status_task);
         }
 
+        // Start deadline task when a time budget is configured
+        if let Some(max_duration) = self.config.max_duration {
+            let progress = Arc::clone(&self.progress);
+            let queue = Arc::clone(&self.queue);
+            let shutdown = Arc::clone(&self.shutdown_signal);
+            let deadline_reached = Arc::clone(&self.deadline_reached);
+
+            let deadline_task = tokio::spawn(async move {
+                sleep(max_duration).await;
+
+                if shutdown.load(Ordering::Relaxed) {
+                    return;
+                }
+
+                deadline_reached.store(true, Ordering::Relaxed);
+
+                // Files still queued are left for the next (incremental) run
+                let deferred = queue.remove_matching(|_| true).await;
+                if deferred > 0 {
+                    progress.defer_files(deferred as u64);
+                }
+
+                let snapshot = progress.get_snapshot();
+                info!(
+                    "Indexing time budget of {:?} reached: {}/{} files indexed ({:.1}% coverage), {} deferred to the next run",
+                    max_duration,
+                    snapshot.processed_files,
+                    snapshot.total_files,
+                    snapshot.coverage_percent(),
+                    snapshot.deferred_files
+                );
+            });
+
+            tasks.push(deadline_task);
+        }
+
         info!("Started {} background tasks", tasks.len());
         Ok(())
     }
@@ -1099,6 +1229,83 @@ impl IndexingManager {
         debug!("Shut down all background tasks");
     }
 
+    /// Analysis run currently recorded for this indexing session
+    pub async fn get_analysis_run_id(&self) -> Option<i64> {
+        *self.analysis_run_id.read().await
+    }
+
+    /// Record an analysis run for indexing `root_path`. Failures are only logged, as
+    /// indexing works without one.
+    async fn create_analysis_run(&self, root_path: &Path) -> Option<i64> {
+        let cache_adapter = match self
+            .workspace_cache_router
+            .cache_for_workspace(root_path)
+            .await
+        {
+            Ok(cache_adapter) => cache_adapter,
+            Err(e) => {
+                warn!("Failed to get cache adapter for analysis run: {}", e);
+                return None;
+            }
+        };
+        let db = match cache_adapter.backend() {
+            crate::database_cache_adapter::BackendType::SQLite(db) => db,
+        };
+        let config = serde_json::json!({ "incremental": self.config.incremental_mode });
+        match db
+            .create_analysis_run(
+                "indexing",
+                env!("CARGO_PKG_VERSION"),
+                "",
+                &config.to_string(),
+            )
+            .await
+        {
+            Ok(run_id) => {
+                info!("Started analysis run {}", run_id);
+                Some(run_id)
+            }
+            Err(e) => {
+                warn!("Failed to create analysis run: {}", e);
+                None
+            }
+        }
+    }
+
+    /// Wait until every discovered file is done, then mark the analysis run completed.
+    /// A run interrupted by shutdown is left incomplete, so it never becomes a baseline.
+    async fn complete_analysis_run_when_indexed(
+        workspace_cache_router: Arc<crate::workspace_database_router::WorkspaceDatabaseRouter>,
+        root_path: PathBuf,
+        run_id: i64,
+        queue: Arc<IndexingQueue>,
+        progress: Arc<IndexingProgress>,
+        shutdown: Arc<AtomicBool>,
+    ) {
+        while !(queue.is_empty()
+            && (progress.is_complete() || progress.get_snapshot().total_files == 0))
+        {
+            if shutdown.load(Ordering::Relaxed) {
+                return;
+            }
+            sleep(Duration::from_millis(500)).await;
+        }
+
+        let result = match workspace_cache_router.cache_for_workspace(&root_path).await {
+            Ok(cache_adapter) => match cache_adapter.backend() {
+                crate::database_cache_adapter::BackendType::SQLite(db) => db
+                    .complete_analysis_run(run_id)
+                    .await
+                    .map_err(|e| anyhow!("{}", e)),
+            },
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(()) => info!("Completed analysis run {}", run_id),
+            Err(e) => warn!("Failed to complete analysis run {}: {}", run_id, e),
+        }
+    }
+
     /// Start file discovery in the specified directory
     async fn start_file_discovery(&self, root_path: PathBuf) -> Result<()> {
         let queue = Arc::clone(&self.queue);
@@ -1108,23 +1315,36 @@ impl IndexingManager {
         let indexed_files = Arc::clone(&self.indexed_files);
         let shutdown = Arc::clone(&self.shutdown_signal);
         let specific_files = self.config.specific_files.clone();
+        let workspace_cache_router = Arc::clone(&self.workspace_cache_router);
+        let run_id = *self.analysis_run_id.read().await;
 
         // Spawn file discovery task
         let discovery_task = tokio::spawn(async move {
             match Self::discover_files_recursive(
-                root_path,
-                queue,
-                progress,
+                root_path.clone(),
+                Arc::clone(&queue),
+                Arc::clone(&progress),
                 config,
                 language_detector,
                 indexed_files,
-                shutdown,
+                Arc::clone(&shutdown),
                 specific_files,
             )
             .await
             {
                 Ok(discovered) => {
                     info!("File discovery completed - {} files discovered", discovered);
+                    if let Some(run_id) = run_id {
+                        Self::complete_analysis_run_when_indexed(
+                            workspace_cache_router,
+                            root_path,
+                            run_id,
+                            queue,
+                            progress,
+                            shutdown,
+                        )
+                        .await;
+                    }
                 }
                 Err(e) => {
                     error!("File discovery failed: {}", e);
@@ -1359,14 +1579,15 @@ impl IndexingManager {
                             continue;
                         }
 
-                        // Check if it's a test file and tests are excluded by the strategy
+                        // Check if it's a test file and tests are excluded by the config or strategy
                         if strategy.is_test_file(&file_path)
-                            && !strategy.file_strategy.include_tests
+                            && (!config.include_tests || !strategy.file_strategy.include_tests)
                         {
                             debug!(
                                 "Skipping test file: {:?} (language: {:?})",
                                 file_path, language
                             );
+                            progress.skip_test_file();
                             continue;
                         }
 
@@ -1494,6 +1715,14 @@ impl IndexingManager {
             progress.add_total_files(batch_size as u64);
         }
 
+        let test_files_skipped = progress.get_snapshot().test_files_skipped;
+        if test_files_skipped > 0 {
+            info!(
+                "Excluded {} test files from indexing (include_tests is disabled)",
+                test_files_skipped
+            );
+        }
+
         Ok(discovered_count)
     }
 
@@ -1620,12 +1849,14 @@ impl IndexingManager {
         let language_detector = Arc::clone(&self.language_detector);
         let semaphore = Arc::clone(&self.worker_semaphore);
         let shutdown = Arc::clone(&self.shutdown_signal);
+        let deadline_reached = Arc::clone(&self.deadline_reached);
+        let disabled_languages = Arc::clone(&self.disabled_languages);
         let server_manager = Arc::clone(&self.server_manager);
         let definition_cache = Arc::clone(&self.definition_cache);
         let workspace_cache_router = Arc::clone(&self.workspace_cache_router);
         let indexed_files = Arc::clone(&self.indexed_files);
         let analysis_engine = self.analysis_engine.clone();
-        let _config = self.config.clone();
+        let include_tests = self.config.include_tests;
         let indexing_config = self.indexing_config.clone();
         let phase2_signal = Arc::clone(&self.phase2_signal);
         let indexing_counters = self.lsp_indexing_counters.clone();
@@ -1651,6 +1882,16 @@ impl IndexingManager {
                     }
                 };
 
+                // Past the time budget, defer anything discovery is still enqueueing
+                if deadline_reached.load(Ordering::Relaxed) {
+                    let deferred = queue.remove_matching(|_| true).await;
+                    if deferred > 0 {
+                        progress.defer_files(deferred as u64);
+                    }
+                    sleep(Duration::from_millis(50)).await;
+                    continue;
+                }
+
                 // Get next item from queue
                 let item = match queue.dequeue().await {
                     Some(item) => item,
@@ -1661,6 +1902,24 @@ impl IndexingManager {
                     }
                 };
 
+                // Files of languages toggled off are not handed to a pipeline; symbols
+                // indexed before the toggle stay in the database
+                let language = item
+                    .language_hint
+                    .as_deref()
+                    .and_then(Language::from_str)
+                    .or_else(|| language_detector.detect(&item.file_path).ok());
+                if let Some(language) = language {
+                    if disabled_languages.read().await.contains(&language) {
+                        progress.skip_file(&format!(
+                            "{:?} ({} indexing disabled)",
+                            item.file_path,
+                            language.as_str()
+                        ));
+                        continue;
+                    }
+                }
+
                 // Update worker stats
                 {
                     let mut stats = worker_stats.write().await;
@@ -1691,6 +1950,7 @@ impl IndexingManager {
                     &indexed_files,
                     &analysis_engine,
                     &indexing_config,
+                    include_tests,
                     &database_adapter,
                     &phase2_signal,
                 )
@@ -1703,18 +1963,25 @@ impl IndexingManager {
                         worker_stat.current_file = None;
                         worker_stat.is_active = false;
 
-                        match result {
+                        match &result {
                             Ok((bytes, symbols)) => {
                                 worker_stat.files_processed += 1;
                                 worker_stat.bytes_processed += bytes;
                                 worker_stat.symbols_extracted += symbols;
-                                progress.complete_file(bytes, symbols);
+                                progress.complete_file(*bytes, *symbols);
                             }
                             Err(e) => {
                                 worker_stat.errors_encountered += 1;
                                 progress.fail_file(&format!("Worker {worker_id}: {e}"));
                             }
                         }
+                        if let Some(language) = language {
+                            progress.record_language_file(
+                                language.as_str(),
+                                result.is_ok(),
+                                result.as_ref().map_or(0, |(_, symbols)| *symbols),
+                            );
+                        }
                     }
                 }
 
@@ -1749,6 +2016,7 @@ impl IndexingManager {
             >,
         >,
         indexing_config: &Option<IndexingConfig>,
+        include_tests: bool,
         database_adapter: &LspDatabaseAdapter,
         phase2_signal: &Arc<tokio::sync::Notify>,
     ) -> Result<(u64, u64)> {
@@ -1776,11 +2044,13 @@ impl IndexingManager {
         let symbols_result = {
             let mut pipelines_write = pipelines.write().await;
             let pipeline = pipelines_write.entry(language).or_insert_with(|| {
-                IndexingPipeline::new(language).unwrap_or_else(|_| {
+                let mut pipeline = IndexingPipeline::new(language).unwrap_or_else(|_| {
                     // Fallback to minimal pipeline if creation fails
                     IndexingPipeline::new(Language::Unknown)
                         .expect("Failed to create fallback pipeline")
-                })
+                });
+                pipeline.set_include_tests(include_tests);
+                pipeline
             });
 
             pipeline.process_file(file_path, database_adapter).await
@@ -5021,6 +5291,78 @@ mod tests {
         manager.stop_indexing().await.unwrap();
     }
 
+    #[tokio::test]
+    async fn test_max_duration_defers_remaining_files() {
+        let config = ManagerConfig {
+            max_workers: 1,
+            incremental_mode: false,
+            max_duration: Some(Duration::from_millis(20)),
+            ..ManagerConfig::default()
+        };
+
+        let language_detector = Arc::new(LanguageDetector::new());
+        let registry = Arc::new(LspRegistry::new().expect("Failed to create LspRegistry"));
+        let server_manager = Arc::new(SingleServerManager::new(registry));
+        let lsp_cache_config = LspCacheConfig::default();
+        let definition_cache = Arc::new(
+            LspCache::<DefinitionInfo>::new(LspOperation::Definition, lsp_cache_config)
+                .await
+                .expect("Failed to create LspCache"),
+        );
+        let workspace_cache_router = create_test_workspace_cache_router(server_manager.clone());
+        let manager = IndexingManager::new(
+            config,
+            language_detector,
+            server_manager,
+            definition_cache,
+            workspace_cache_router,
+        );
+
+        let temp_dir = tempdir().unwrap();
+        for i in 0..300 {
+            fs::write(
+                temp_dir.path().join(format!("module_{i}.rs")),
+                format!("pub struct Item{i};\n\npub fn build_{i}() -> Item{i} {{ Item{i} }}\n"),
+            )
+            .unwrap();
+        }
+
+        manager
+            .start_indexing(temp_dir.path().to_path_buf())
+            .await
+            .unwrap();
+
+        // Deferred files count towards completion, so this run settles quickly
+        let start_time = Instant::now();
+        while start_time.elapsed() < Duration::from_secs(10) {
+            if manager.get_progress().await.is_complete() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(50)).await;
+        }
+        // Let discovery flush any in-flight batch into the deferred count
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let progress = manager.get_progress().await;
+        assert_eq!(progress.total_files, 300);
+        assert!(progress.deferred_files > 0, "deadline should defer files");
+        assert!(progress.processed_files < progress.total_files);
+        assert_eq!(
+            progress.processed_files
+                + progress.failed_files
+                + progress.skipped_files
+                + progress.deferred_files,
+            progress.total_files
+        );
+        assert_eq!(
+            progress.coverage_percent(),
+            progress.processed_files as f64 / 300.0 * 100.0
+        );
+        assert_eq!(manager.get_queue_snapshot().await.total_items, 0);
+
+        manager.stop_indexing().await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_incremental_mode_detection() {
         let temp_dir = tempdir().unwrap();
@@ -5224,6 +5566,50 @@ mod tests {
         // The exact count depends on language detection and filtering implementation
     }
 
+    #[tokio::test]
+    async fn test_discovery_excludes_test_files_by_default() {
+        let temp_dir = tempdir().unwrap();
+        let root = temp_dir.path();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::create_dir_all(root.join("tests")).unwrap();
+
+        fs::write(root.join("src/lib.rs"), "pub fn add() {}").unwrap();
+        fs::write(root.join("src/parser_test.rs"), "fn parses() {}").unwrap();
+        fs::write(root.join("tests/integration.rs"), "fn runs() {}").unwrap();
+
+        let discover = |include_tests: bool| {
+            let config = ManagerConfig {
+                include_tests,
+                incremental_mode: false,
+                ..ManagerConfig::default()
+            };
+            let queue = Arc::new(IndexingQueue::new(config.max_queue_size));
+            let progress = Arc::new(IndexingProgress::new());
+            let root = root.to_path_buf();
+            async move {
+                let discovered = IndexingManager::discover_files_recursive(
+                    root,
+                    queue,
+                    progress.clone(),
+                    config,
+                    Arc::new(LanguageDetector::new()),
+                    Arc::new(RwLock::new(HashMap::new())),
+                    Arc::new(AtomicBool::new(false)),
+                    vec![],
+                )
+                .await
+                .unwrap();
+                (discovered, progress.get_snapshot().test_files_skipped)
+            }
+        };
+
+        // Test files are skipped and reported by default
+        assert_eq!(discover(false).await, (1, 2));
+
+        // Opting in indexes them like any other file
+        assert_eq!(discover(true).await, (3, 0));
+    }
+
     #[tokio::test]
     async fn test_manager_from_indexing_config() {
         let mut indexing_config = IndexingConfig::default();
@@ -5254,6 +5640,47 @@ mod tests {
         assert_eq!(manager.config.max_queue_size, 500);
     }
 
+    #[tokio::test]
+    async fn test_disabled_language_is_not_prioritized() {
+        let temp_dir = tempdir().unwrap();
+        let cpp_file = temp_dir.path().join("main.cpp");
+        let rust_file = temp_dir.path().join("main.rs");
+        fs::write(&cpp_file, "int main() { return 0; }").unwrap();
+        fs::write(&rust_file, "fn main() {}").unwrap();
+
+        let mut indexing_config = IndexingConfig::default();
+        indexing_config
+            .language_configs
+            .entry(Language::Cpp)
+            .or_default()
+            .enabled = Some(false);
+
+        let language_detector = Arc::new(LanguageDetector::new());
+        let registry = Arc::new(LspRegistry::new().expect("Failed to create LspRegistry"));
+        let server_manager = Arc::new(SingleServerManager::new(registry));
+        let lsp_cache_config = LspCacheConfig::default();
+        let definition_cache = Arc::new(
+            LspCache::<DefinitionInfo>::new(LspOperation::Definition, lsp_cache_config)
+                .await
+                .expect("Failed to create LspCache"),
+        );
+        let workspace_cache_router = create_test_workspace_cache_router(server_manager.clone());
+        let manager = IndexingManager::from_indexing_config(
+            &indexing_config,
+            language_detector,
+            server_manager,
+            definition_cache,
+            workspace_cache_router,
+        );
+
+        assert!(manager.prioritize_file(&cpp_file).await.is_err());
+        assert!(manager.prioritize_file(&rust_file).await.unwrap());
+
+        // Re-enabling at runtime makes the language eligible again
+        manager.set_disabled_languages(HashSet::new()).await;
+        assert!(manager.prioritize_file(&cpp_file).await.unwrap());
+    }
+
     #[tokio::test]
     async fn test_concurrent_start_stop_operations() {
         let config = ManagerConfig {
@@ -63,7 +63,7 @@ pub use manager::{IndexingManager, ManagerConfig, ManagerStatus, WorkerStats};
 pub use pipelines::{
     get_fqn_from_ast, IndexingPipeline, LanguagePipeline, PipelineConfig, PipelineResult,
 };
-pub use progress::{IndexingProgress, ProgressMetrics, ProgressSnapshot};
+pub use progress::{IndexingProgress, LanguageProgress, ProgressMetrics, ProgressSnapshot};
 pub use queue::{IndexingQueue, Priority, QueueItem, QueueMetrics, QueueSnapshot};
 pub use symbol_conversion::{
     ConversionContext, FieldValidator, MetadataBuilder, SymbolUIDGenerator, ToSymbolState,
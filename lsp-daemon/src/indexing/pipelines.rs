@@ -526,6 +526,11 @@ impl LanguagePipeline {
         }
     }
 
+    /// Set whether test files and test symbols are indexed
+    pub fn set_include_tests(&mut self, include_tests: bool) {
+        self.strategy.file_strategy.include_tests = include_tests;
+    }
+
     /// Create a pipeline with custom configuration
     pub fn with_config(config: PipelineConfig) -> Self {
         let strategy = LanguageStrategyFactory::create_strategy(config.language);
@@ -989,13 +994,18 @@ impl LanguagePipeline {
             .ast_extractor
             .extract_symbols_from_file(file_path, content, self.config.language)
         {
-            Ok(extracted_symbols) => {
+            Ok(mut extracted_symbols) => {
                 debug!(
                     "AST extraction successful for {:?}: {} symbols found",
                     file_path,
                     extracted_symbols.len()
                 );
 
+                // Keep test functions out of the symbol graph unless tests are included
+                if !self.strategy.file_strategy.include_tests {
+                    extracted_symbols.retain(|symbol| !symbol.tags.iter().any(|t| t == "test"));
+                }
+
                 // Group symbols by type
                 for extracted in &extracted_symbols {
                     let symbol_info = self.convert_extracted_symbol_to_symbol_info(extracted);
@@ -1290,6 +1300,11 @@ impl IndexingPipeline {
         })
     }
 
+    /// Set whether test files and test symbols are indexed
+    pub fn set_include_tests(&mut self, include_tests: bool) {
+        self.processor.set_include_tests(include_tests);
+    }
+
     /// Process a file using this pipeline
     pub async fn process_file(
         &mut self,
@@ -1408,6 +1423,62 @@ fn test_person_creation() {
         assert!(types.iter().any(|t| t.name == "Person"));
     }
 
+    #[tokio::test]
+    async fn test_pipeline_excludes_test_symbols() {
+        let rust_code = r#"
+pub fn parse(input: &str) -> usize {
+    input.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_empty_input() {
+        assert_eq!(parse(""), 0);
+    }
+
+    #[tokio::test]
+    async fn parses_async() {}
+
+    fn test_fixture() -> &'static str {
+        "abc"
+    }
+}
+"#;
+
+        let temp_file = NamedTempFile::with_suffix(".rs").unwrap();
+        std::fs::write(temp_file.path(), rust_code).unwrap();
+        let database_adapter = LspDatabaseAdapter::new();
+
+        let mut pipeline = IndexingPipeline::new(Language::Rust).unwrap();
+        pipeline.set_include_tests(false);
+        let result = pipeline
+            .process_file(temp_file.path(), &database_adapter)
+            .await
+            .unwrap();
+        let names: Vec<&str> = result
+            .extracted_symbols
+            .iter()
+            .map(|s| s.name.as_str())
+            .collect();
+        assert!(names.contains(&"parse"), "symbols: {names:?}");
+        assert!(!names.contains(&"parses_empty_input"), "symbols: {names:?}");
+        assert!(!names.contains(&"parses_async"), "symbols: {names:?}");
+        assert!(!names.contains(&"test_fixture"), "symbols: {names:?}");
+
+        pipeline.set_include_tests(true);
+        let result = pipeline
+            .process_file(temp_file.path(), &database_adapter)
+            .await
+            .unwrap();
+        assert!(result
+            .extracted_symbols
+            .iter()
+            .any(|s| s.name == "parses_empty_input"));
+    }
+
     #[tokio::test]
     async fn test_python_pipeline() {
         let python_code = r#"
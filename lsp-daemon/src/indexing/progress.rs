@@ -4,7 +4,9 @@
 //! multiple indexing workers to update progress concurrently while providing
 //! real-time visibility into indexing status.
 
+use dashmap::DashMap;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
@@ -28,6 +30,12 @@ pub struct IndexingProgress {
     /// Files skipped (already indexed, filtered out, etc.)
     skipped_files: Arc<AtomicU64>,
 
+    /// Test files excluded during discovery (not counted towards total files)
+    test_files_skipped: Arc<AtomicU64>,
+
+    /// Queued files left unindexed because the time budget ran out
+    deferred_files: Arc<AtomicU64>,
+
     /// Total bytes processed
     processed_bytes: Arc<AtomicU64>,
 
@@ -37,6 +45,9 @@ pub struct IndexingProgress {
     /// Number of worker threads currently active
     active_workers: Arc<AtomicUsize>,
 
+    /// Per-language file and symbol counts, keyed by language name
+    languages: Arc<DashMap<String, LanguageProgress>>,
+
     /// Start time of indexing operation
     start_time: Instant,
 
@@ -54,9 +65,12 @@ impl IndexingProgress {
             failed_files: Arc::new(AtomicU64::new(0)),
             active_files: Arc::new(AtomicU64::new(0)),
             skipped_files: Arc::new(AtomicU64::new(0)),
+            test_files_skipped: Arc::new(AtomicU64::new(0)),
+            deferred_files: Arc::new(AtomicU64::new(0)),
             processed_bytes: Arc::new(AtomicU64::new(0)),
             symbols_extracted: Arc::new(AtomicU64::new(0)),
             active_workers: Arc::new(AtomicUsize::new(0)),
+            languages: Arc::new(DashMap::new()),
             start_time: now,
             last_update: Arc::new(AtomicU64::new(now.elapsed().as_millis() as u64)),
         }
@@ -69,9 +83,12 @@ impl IndexingProgress {
         self.failed_files.store(0, Ordering::Relaxed);
         self.active_files.store(0, Ordering::Relaxed);
         self.skipped_files.store(0, Ordering::Relaxed);
+        self.test_files_skipped.store(0, Ordering::Relaxed);
+        self.deferred_files.store(0, Ordering::Relaxed);
         self.processed_bytes.store(0, Ordering::Relaxed);
         self.symbols_extracted.store(0, Ordering::Relaxed);
         self.active_workers.store(0, Ordering::Relaxed);
+        self.languages.clear();
         self.update_timestamp();
     }
 
@@ -123,6 +140,40 @@ impl IndexingProgress {
         debug!("Skipped file: {}", reason);
     }
 
+    /// Record a test file excluded during discovery
+    pub fn skip_test_file(&self) -> u64 {
+        let count = self.test_files_skipped.fetch_add(1, Ordering::Relaxed) + 1;
+        self.update_timestamp();
+        count
+    }
+
+    /// Record queued files that were not indexed before the deadline
+    pub fn defer_files(&self, count: u64) -> u64 {
+        let total = self.deferred_files.fetch_add(count, Ordering::Relaxed) + count;
+        self.update_timestamp();
+        debug!("Deferred {} files to a later run", count);
+        total
+    }
+
+    /// Attribute a finished file to its language for the per-language breakdown
+    pub fn record_language_file(&self, language: &str, succeeded: bool, symbols_found: u64) {
+        let mut entry = self.languages.entry(language.to_string()).or_default();
+        if succeeded {
+            entry.processed_files += 1;
+            entry.symbols_extracted += symbols_found;
+        } else {
+            entry.failed_files += 1;
+        }
+    }
+
+    /// Per-language counts, ordered by language name
+    pub fn get_language_breakdown(&self) -> BTreeMap<String, LanguageProgress> {
+        self.languages
+            .iter()
+            .map(|entry| (entry.key().clone(), entry.value().clone()))
+            .collect()
+    }
+
     /// Increment active worker count
     pub fn add_worker(&self) -> usize {
         let count = self.active_workers.fetch_add(1, Ordering::Relaxed) + 1;
@@ -149,8 +200,9 @@ impl IndexingProgress {
         let failed = self.failed_files.load(Ordering::Relaxed);
         let active = self.active_files.load(Ordering::Relaxed);
         let skipped = self.skipped_files.load(Ordering::Relaxed);
+        let deferred = self.deferred_files.load(Ordering::Relaxed);
 
-        let completed = processed + failed + skipped;
+        let completed = processed + failed + skipped + deferred;
         let progress_ratio = if total > 0 {
             completed as f64 / total as f64
         } else {
@@ -177,6 +229,8 @@ impl IndexingProgress {
             failed_files: failed,
             active_files: active,
             skipped_files: skipped,
+            test_files_skipped: self.test_files_skipped.load(Ordering::Relaxed),
+            deferred_files: deferred,
             progress_ratio,
             files_per_second,
             processed_bytes: bytes_processed,
@@ -195,6 +249,8 @@ impl IndexingProgress {
             failed_files: self.failed_files.load(Ordering::Relaxed),
             active_files: self.active_files.load(Ordering::Relaxed),
             skipped_files: self.skipped_files.load(Ordering::Relaxed),
+            test_files_skipped: self.test_files_skipped.load(Ordering::Relaxed),
+            deferred_files: self.deferred_files.load(Ordering::Relaxed),
             processed_bytes: self.processed_bytes.load(Ordering::Relaxed),
             symbols_extracted: self.symbols_extracted.load(Ordering::Relaxed),
             active_workers: self.active_workers.load(Ordering::Relaxed),
@@ -208,11 +264,17 @@ impl IndexingProgress {
         let active = self.active_files.load(Ordering::Relaxed);
         let completed = self.processed_files.load(Ordering::Relaxed)
             + self.failed_files.load(Ordering::Relaxed)
-            + self.skipped_files.load(Ordering::Relaxed);
+            + self.skipped_files.load(Ordering::Relaxed)
+            + self.deferred_files.load(Ordering::Relaxed);
 
         total > 0 && active == 0 && completed >= total
     }
 
+    /// Percentage of discovered files that were successfully indexed
+    pub fn coverage_percent(&self) -> f64 {
+        self.get_snapshot().coverage_percent()
+    }
+
     /// Check if any workers are active
     pub fn has_active_workers(&self) -> bool {
         self.active_workers.load(Ordering::Relaxed) > 0
@@ -225,7 +287,10 @@ impl IndexingProgress {
 
         if metrics.files_per_second > 0.0 && metrics.total_files > 0 {
             let remaining_files = metrics.total_files.saturating_sub(
-                metrics.processed_files + metrics.failed_files + metrics.skipped_files,
+                metrics.processed_files
+                    + metrics.failed_files
+                    + metrics.skipped_files
+                    + metrics.deferred_files,
             );
 
             if remaining_files > 0 {
@@ -258,6 +323,10 @@ pub struct ProgressMetrics {
     pub failed_files: u64,
     pub active_files: u64,
     pub skipped_files: u64,
+    #[serde(default)]
+    pub test_files_skipped: u64,
+    #[serde(default)]
+    pub deferred_files: u64,
     pub progress_ratio: f64,
     pub files_per_second: f64,
     pub processed_bytes: u64,
@@ -267,6 +336,14 @@ pub struct ProgressMetrics {
     pub elapsed_time: Duration,
 }
 
+/// Files and symbols indexed for a single language
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LanguageProgress {
+    pub processed_files: u64,
+    pub failed_files: u64,
+    pub symbols_extracted: u64,
+}
+
 /// Lightweight progress snapshot for serialization/IPC
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ProgressSnapshot {
@@ -275,6 +352,10 @@ pub struct ProgressSnapshot {
     pub failed_files: u64,
     pub active_files: u64,
     pub skipped_files: u64,
+    #[serde(default)]
+    pub test_files_skipped: u64,
+    #[serde(default)]
+    pub deferred_files: u64,
     pub processed_bytes: u64,
     pub symbols_extracted: u64,
     pub active_workers: usize,
@@ -284,9 +365,19 @@ pub struct ProgressSnapshot {
 impl ProgressSnapshot {
     /// Check if indexing is complete
     pub fn is_complete(&self) -> bool {
-        let completed = self.processed_files + self.failed_files + self.skipped_files;
+        let completed =
+            self.processed_files + self.failed_files + self.skipped_files + self.deferred_files;
         self.total_files > 0 && self.active_files == 0 && completed >= self.total_files
     }
+
+    /// Percentage of discovered files that were successfully indexed
+    pub fn coverage_percent(&self) -> f64 {
+        if self.total_files > 0 {
+            self.processed_files as f64 / self.total_files as f64 * 100.0
+        } else {
+            0.0
+        }
+    }
 }
 
 #[cfg(test)]
@@ -358,6 +449,52 @@ mod tests {
         assert!(progress.is_complete());
     }
 
+    #[test]
+    fn test_deferred_files_count_towards_completion() {
+        let progress = IndexingProgress::new();
+        progress.set_total_files(4);
+
+        progress.start_file();
+        progress.complete_file(100, 10);
+        assert!(!progress.is_complete());
+
+        // Time budget ran out with three files still queued
+        progress.defer_files(3);
+        assert!(progress.is_complete());
+
+        let snapshot = progress.get_snapshot();
+        assert_eq!(snapshot.deferred_files, 3);
+        assert_eq!(snapshot.coverage_percent(), 25.0);
+        assert_eq!(progress.get_metrics().progress_ratio, 1.0);
+
+        progress.reset();
+        assert_eq!(progress.get_snapshot().deferred_files, 0);
+        assert_eq!(progress.coverage_percent(), 0.0);
+    }
+
+    #[test]
+    fn test_language_breakdown() {
+        let progress = IndexingProgress::new();
+        progress.record_language_file("rust", true, 12);
+        progress.record_language_file("rust", true, 3);
+        progress.record_language_file("python", false, 0);
+
+        let breakdown = progress.get_language_breakdown();
+        assert_eq!(breakdown.keys().collect::<Vec<_>>(), vec!["python", "rust"]);
+        assert_eq!(
+            breakdown["rust"],
+            LanguageProgress {
+                processed_files: 2,
+                failed_files: 0,
+                symbols_extracted: 15,
+            }
+        );
+        assert_eq!(breakdown["python"].failed_files, 1);
+
+        progress.reset();
+        assert!(progress.get_language_breakdown().is_empty());
+    }
+
     #[test]
     fn test_metrics_calculation() {
         let progress = IndexingProgress::new();
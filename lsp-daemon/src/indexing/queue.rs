@@ -7,7 +7,7 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::VecDeque;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
@@ -443,6 +443,54 @@ impl IndexingQueue {
         }
     }
 
+    /// Raise a queued file to `priority`. The queued item is moved to the back of the
+    /// `priority` level rather than duplicated; a file that is not queued yet is enqueued.
+    /// Returns whether the queue changed, which it does not for a file already queued at
+    /// `priority` or higher.
+    pub async fn prioritize(&self, file_path: &Path, priority: Priority) -> Result<bool> {
+        let mut found = None;
+        for queue in [
+            &self.critical_priority,
+            &self.high_priority,
+            &self.medium_priority,
+            &self.low_priority,
+        ] {
+            let mut queue_guard = queue.write().await;
+            if let Some(pos) = queue_guard
+                .iter()
+                .position(|item| item.file_path == file_path)
+            {
+                if queue_guard[pos].priority.as_u8() >= priority.as_u8() {
+                    return Ok(false);
+                }
+                found = queue_guard.remove(pos);
+                break;
+            }
+        }
+
+        let Some(mut item) = found else {
+            return self
+                .enqueue(QueueItem::new(file_path.to_path_buf(), priority))
+                .await;
+        };
+
+        debug!(
+            "Raised {:?} from {} to {} priority",
+            item.file_path,
+            item.priority.as_str(),
+            priority.as_str()
+        );
+        item.priority = priority;
+        let queue = match priority {
+            Priority::Critical => &self.critical_priority,
+            Priority::High => &self.high_priority,
+            Priority::Medium => &self.medium_priority,
+            Priority::Low => &self.low_priority,
+        };
+        queue.write().await.push_back(item);
+        Ok(true)
+    }
+
     /// Enqueue multiple items in batch for efficiency
     pub async fn enqueue_batch(&self, items: Vec<QueueItem>) -> Result<usize> {
         let mut enqueued_count = 0;
@@ -732,6 +780,64 @@ mod tests {
         assert!(first.file_path.to_string_lossy().contains("keep2"));
     }
 
+    #[tokio::test]
+    async fn test_prioritize_moves_item_without_duplicating() {
+        let queue = IndexingQueue::unlimited();
+        queue
+            .enqueue(QueueItem::high_priority(PathBuf::from("/high.rs")))
+            .await
+            .unwrap();
+        queue
+            .enqueue(QueueItem::low_priority(PathBuf::from("/edited.rs")).with_estimated_size(10))
+            .await
+            .unwrap();
+        queue
+            .enqueue(QueueItem::low_priority(PathBuf::from("/other.rs")))
+            .await
+            .unwrap();
+
+        assert!(queue
+            .prioritize(Path::new("/edited.rs"), Priority::High)
+            .await
+            .unwrap());
+        assert_eq!(queue.len(), 3);
+        assert_eq!(queue.len_for_priority(Priority::High).await, 2);
+        assert_eq!(queue.len_for_priority(Priority::Low).await, 1);
+
+        // Already high: nothing changes
+        assert!(!queue
+            .prioritize(Path::new("/edited.rs"), Priority::Medium)
+            .await
+            .unwrap());
+
+        // Not queued: enqueued at the requested priority
+        assert!(queue
+            .prioritize(Path::new("/new.rs"), Priority::High)
+            .await
+            .unwrap());
+        assert_eq!(queue.len(), 4);
+
+        let order: Vec<PathBuf> = [
+            queue.dequeue().await,
+            queue.dequeue().await,
+            queue.dequeue().await,
+            queue.dequeue().await,
+        ]
+        .into_iter()
+        .map(|item| item.unwrap().file_path)
+        .collect();
+        assert_eq!(
+            order,
+            vec![
+                PathBuf::from("/high.rs"),
+                PathBuf::from("/edited.rs"),
+                PathBuf::from("/new.rs"),
+                PathBuf::from("/other.rs"),
+            ]
+        );
+        assert_eq!(queue.get_metrics().await.estimated_total_bytes, 0);
+    }
+
     #[tokio::test]
     async fn test_peek() {
         let queue = IndexingQueue::unlimited();
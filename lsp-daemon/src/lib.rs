@@ -11,6 +11,7 @@ mod git_service_test;
 pub mod ipc;
 pub mod language_detector;
 pub mod logging;
+pub mod metrics;
 pub mod path_resolver;
 pub mod path_safety;
 pub mod pid_lock;
@@ -101,7 +102,7 @@ pub use daemon::{start_daemon_background, LspDaemon};
 pub use lsp_registry::LspRegistry;
 pub use watchdog::{ProcessHealth, ProcessMonitor, ProcessStats, Watchdog};
 
-pub use git_service::GitService;
+pub use git_service::{BlameLine, GitService};
 pub use path_resolver::PathResolver;
 pub use workspace_utils::{
     find_workspace_root, find_workspace_root_with_fallback, is_workspace_root,
@@ -961,7 +961,7 @@ impl LspDatabaseAdapter {
             "class_declaration" | // TypeScript/JavaScript, Java
             "interface_declaration" => true, // TypeScript/JavaScript, Java
             // Go symbols
-            "func_declaration" | "type_declaration" => true,
+            "func_declaration" | "type_declaration" | "type_spec" => true,
             // Java symbols (constructor is unique to Java)
             "constructor_declaration" => true,
             // C/C++ symbols (function_declarator is unique to C/C++)
@@ -1288,6 +1288,7 @@ impl LspDatabaseAdapter {
             "impl_item" => SymbolKind::Impl,
             "mod_item" | "namespace" | "module" | "module_def" => SymbolKind::Module,
             "type_declaration"
+            | "type_spec"
             | "type_alias_declaration"
             | "alias"
             | "annotation_def"
@@ -5649,4 +5650,101 @@ trait T { fn m(&self); }
         }
         std::fs::remove_file(interface_file).ok();
     }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_go_interface_implementations_stored_as_implements_edges() {
+        use crate::database::{DatabaseBackend, DatabaseConfig, SQLiteBackend};
+        use crate::indexing::ast_extractor::AstSymbolExtractor;
+        use crate::language_detector::Language;
+
+        let go_code = r#"package shapes
+
+type Shape interface {
+	Area() float64
+}
+
+type Circle struct {
+	Radius float64
+}
+
+func (c Circle) Area() float64 {
+	return 3.14 * c.Radius * c.Radius
+}
+"#;
+        let temp_dir = tempfile::tempdir().unwrap();
+        let go_file = temp_dir.path().join("shapes.go");
+        std::fs::write(&go_file, go_code).unwrap();
+
+        // The interface and the satisfying struct must be indexed so the enrichment
+        // worker has an interface symbol to request implementations for
+        let mut extractor = AstSymbolExtractor::new();
+        let symbols = extractor
+            .extract_symbols_from_file(&go_file, go_code, Language::Go)
+            .unwrap();
+        let shape = symbols.iter().find(|s| s.name == "Shape").expect("Shape");
+        assert_eq!(shape.kind, SymbolKind::Interface);
+        let circle = symbols.iter().find(|s| s.name == "Circle").expect("Circle");
+        assert_eq!(circle.kind, SymbolKind::Struct);
+
+        // Simulated gopls response for Implementations on `Shape`
+        let locations = vec![crate::protocol::Location {
+            uri: format!("file://{}", go_file.display()),
+            range: crate::protocol::Range {
+                start: crate::protocol::Position {
+                    line: 6,
+                    character: 5,
+                },
+                end: crate::protocol::Position {
+                    line: 6,
+                    character: 11,
+                },
+            },
+        }];
+
+        let adapter = LspDatabaseAdapter::new();
+        let edges = adapter
+            .convert_implementations_to_database(
+                &locations,
+                &go_file,
+                (2, 5), // Position of "Shape"
+                "go",
+                1,
+                temp_dir.path(),
+            )
+            .expect("impls convert");
+        assert_eq!(edges.len(), 1);
+
+        // Direction: the satisfying struct implements the interface
+        let shape_uid = adapter
+            .resolve_symbol_at_location(&go_file, 2, 5, "go", Some(temp_dir.path()))
+            .await
+            .unwrap();
+        let circle_uid = adapter
+            .resolve_symbol_at_location(&go_file, 6, 5, "go", Some(temp_dir.path()))
+            .await
+            .unwrap();
+        assert_ne!(shape_uid, circle_uid);
+        let edge = &edges[0];
+        assert_eq!(edge.relation, crate::database::EdgeRelation::Implements);
+        assert_eq!(edge.source_symbol_uid, circle_uid);
+        assert_eq!(edge.target_symbol_uid, shape_uid);
+        assert_eq!(edge.language, "go");
+
+        let db_config = DatabaseConfig {
+            path: None,
+            temporary: true,
+            compression: false,
+            cache_capacity: 1024 * 1024,
+            compression_factor: 0,
+            flush_every_ms: Some(1000),
+        };
+        let database = SQLiteBackend::new(db_config).await.unwrap();
+        database.store_edges(&edges).await.expect("store edges");
+
+        let implementations = database
+            .get_implementations_for_symbol(1, &shape_uid)
+            .await
+            .unwrap();
+        assert_eq!(implementations.len(), 1, "Implements edge should be stored");
+    }
 }
@@ -17,6 +17,26 @@ use tokio::time::{timeout, Duration, Instant};
 use tracing::{debug, error, info, warn};
 use url::Url;
 
+/// Failure of the language server itself rather than of a single request: its process
+/// exited, its pipes broke, or it stopped answering. Errors returned for a request carry
+/// this in their chain when the server, not the request, is at fault.
+#[derive(Debug, thiserror::Error)]
+pub enum ServerFailure {
+    #[error("LSP server connection failed: {0}")]
+    Transport(#[from] std::io::Error),
+    #[error("LSP server closed connection")]
+    Closed,
+    #[error("Timeout waiting for response to request {id} after {timeout:?}")]
+    Timeout { id: i64, timeout: Duration },
+}
+
+impl ServerFailure {
+    /// Returns true if `err` was caused by a failure of the language server itself
+    pub fn caused(err: &anyhow::Error) -> bool {
+        err.chain().any(|cause| cause.is::<ServerFailure>())
+    }
+}
+
 pub struct LspServer {
     child: Arc<Mutex<Option<Child>>>,
     stdin: Arc<Mutex<ChildStdin>>,
@@ -361,6 +381,12 @@ impl LspServer {
                     "hover": {
                         "dynamicRegistration": false
                     },
+                    "documentHighlight": {
+                        "dynamicRegistration": false
+                    },
+                    "rename": {
+                        "dynamicRegistration": false
+                    },
                     "completion": {
                         "dynamicRegistration": false,
                         "completionItem": {
@@ -484,6 +510,12 @@ impl LspServer {
                     "hover": {
                         "dynamicRegistration": false
                     },
+                    "documentHighlight": {
+                        "dynamicRegistration": false
+                    },
+                    "rename": {
+                        "dynamicRegistration": false
+                    },
                     "completion": {
                         "dynamicRegistration": false,
                         "completionItem": {
@@ -627,6 +659,12 @@ impl LspServer {
                     "hover": {
                         "dynamicRegistration": false
                     },
+                    "documentHighlight": {
+                        "dynamicRegistration": false
+                    },
+                    "rename": {
+                        "dynamicRegistration": false
+                    },
                     "completion": {
                         "dynamicRegistration": false,
                         "completionItem": {
@@ -998,8 +1036,11 @@ impl LspServer {
 
         // Simplified approach - just acquire the lock and write directly
         let mut stdin = self.stdin.lock().await;
-        stdin.write_all(message.as_bytes()).await?;
-        stdin.flush().await?;
+        stdin
+            .write_all(message.as_bytes())
+            .await
+            .map_err(ServerFailure::Transport)?;
+        stdin.flush().await.map_err(ServerFailure::Transport)?;
 
         Ok(())
     }
@@ -1044,10 +1085,13 @@ impl LspServer {
 
         loop {
             let mut header_line = String::new();
-            let bytes_read = stdout.read_line(&mut header_line).await?;
+            let bytes_read = stdout
+                .read_line(&mut header_line)
+                .await
+                .map_err(ServerFailure::Transport)?;
 
             if bytes_read == 0 {
-                return Err(anyhow!("LSP server closed connection"));
+                return Err(ServerFailure::Closed.into());
             }
 
             // Trim the line to handle different line endings (\r\n vs \n)
@@ -1086,7 +1130,10 @@ impl LspServer {
         }
 
         let mut body = vec![0; len];
-        stdout.read_exact(&mut body).await?;
+        stdout
+            .read_exact(&mut body)
+            .await
+            .map_err(ServerFailure::Transport)?;
 
         let msg: Value = serde_json::from_slice(&body)?;
 
@@ -1291,11 +1338,11 @@ impl LspServer {
             "TIMEOUT: No response received for request ID {} after {:?} (saw {} total messages)",
             id, timeout_duration, message_count
         );
-        Err(anyhow!(
-            "Timeout waiting for response to request {} after {:?}",
+        Err(ServerFailure::Timeout {
             id,
-            timeout_duration
-        ))
+            timeout: timeout_duration,
+        }
+        .into())
     }
 
     pub async fn open_document(&self, file_path: &Path, content: &str) -> Result<()> {
@@ -1898,6 +1945,29 @@ impl LspServer {
 
     /// Get hover information
     pub async fn hover(&self, file_path: &Path, line: u32, column: u32) -> Result<Value> {
+        let request_id = self.send_hover_request(file_path, line, column).await?;
+        self.hover_response(request_id).await
+    }
+
+    /// Hover at several positions, sending every request before waiting for any response so
+    /// the server works through them back to back. Results are in the order of `positions`.
+    pub async fn hover_batch(&self, positions: &[(&Path, u32, u32)]) -> Vec<Result<Value>> {
+        let mut sent = Vec::with_capacity(positions.len());
+        for (file_path, line, column) in positions {
+            sent.push(self.send_hover_request(file_path, *line, *column).await);
+        }
+
+        let mut results = Vec::with_capacity(sent.len());
+        for request_id in sent {
+            results.push(match request_id {
+                Ok(request_id) => self.hover_response(request_id).await,
+                Err(e) => Err(e),
+            });
+        }
+        results
+    }
+
+    async fn send_hover_request(&self, file_path: &Path, line: u32, column: u32) -> Result<i64> {
         let canon = self.canonicalize_with_root(file_path);
         let uri = Url::from_file_path(&canon)
             .map_err(|_| anyhow!("Invalid file path: {:?}", file_path))?;
@@ -1915,6 +1985,10 @@ impl LspServer {
 
         self.send_request("textDocument/hover", params, request_id)
             .await?;
+        Ok(request_id)
+    }
+
+    async fn hover_response(&self, request_id: i64) -> Result<Value> {
         let response = self
             .wait_for_response(request_id, Duration::from_secs(30))
             .await?;
@@ -1926,6 +2000,78 @@ impl LspServer {
         Ok(response["result"].clone())
     }
 
+    /// Get the occurrences of the symbol at a position within its file
+    pub async fn document_highlight(
+        &self,
+        file_path: &Path,
+        line: u32,
+        column: u32,
+    ) -> Result<Value> {
+        let canon = self.canonicalize_with_root(file_path);
+        let uri = Url::from_file_path(&canon)
+            .map_err(|_| anyhow!("Invalid file path: {:?}", file_path))?;
+
+        let request_id = self.next_request_id().await;
+        let params = json!({
+            "textDocument": {
+                "uri": uri.to_string()
+            },
+            "position": {
+                "line": line,
+                "character": column
+            }
+        });
+
+        self.send_request("textDocument/documentHighlight", params, request_id)
+            .await?;
+        let response = self
+            .wait_for_response(request_id, Duration::from_secs(30))
+            .await?;
+
+        if let Some(error) = response.get("error") {
+            return Err(anyhow!("Document highlight request failed: {:?}", error));
+        }
+
+        Ok(response["result"].clone())
+    }
+
+    /// Get the `WorkspaceEdit` that renames the symbol at a position to `new_name`
+    pub async fn rename(
+        &self,
+        file_path: &Path,
+        line: u32,
+        column: u32,
+        new_name: &str,
+    ) -> Result<Value> {
+        let canon = self.canonicalize_with_root(file_path);
+        let uri = Url::from_file_path(&canon)
+            .map_err(|_| anyhow!("Invalid file path: {:?}", file_path))?;
+
+        let request_id = self.next_request_id().await;
+        let params = json!({
+            "textDocument": {
+                "uri": uri.to_string()
+            },
+            "position": {
+                "line": line,
+                "character": column
+            },
+            "newName": new_name
+        });
+
+        self.send_request("textDocument/rename", params, request_id)
+            .await?;
+        let response = self
+            .wait_for_response(request_id, Duration::from_secs(30))
+            .await?;
+
+        if let Some(error) = response.get("error") {
+            return Err(anyhow!("Rename request failed: {:?}", error));
+        }
+
+        Ok(response["result"].clone())
+    }
+
     /// Get document symbols
     pub async fn document_symbols(&self, file_path: &Path) -> Result<Value> {
         let canon = self.canonicalize_with_root(file_path);
@@ -1976,7 +2122,10 @@ impl LspServer {
         let response = self
             .wait_for_response(request_id, Duration::from_secs(60))
             .await
-            .map_err(|e| anyhow!("Call hierarchy prepare timed out: {}", e))?;
+            .map_err(|e| {
+                let message = format!("Call hierarchy prepare timed out: {}", e);
+                e.context(message)
+            })?;
 
         if let Some(error) = response.get("error") {
             // Check if the error is "Method not found" (-32601)
@@ -2062,7 +2211,8 @@ impl LspServer {
             Err(e) => {
                 // Propagate the error - don't cache incomplete results
                 warn!("Outgoing calls request timed out or failed: {}", e);
-                return Err(anyhow!("Failed to get outgoing calls: {}", e));
+                let message = format!("Failed to get outgoing calls: {}", e);
+                return Err(e.context(message));
             }
         };
 
@@ -0,0 +1,441 @@
+//! Prometheus exposition of daemon metrics.
+//!
+//! [`render_prometheus`] turns the daemon's status, cache statistics and indexing queue
+//! metrics into the Prometheus text exposition format (version 0.0.4), for `probe lsp
+//! metrics` and scrapers. Names follow the Prometheus conventions: a `probe_lsp_` prefix,
+//! base units (`_seconds`, `_bytes`, `_ratio`) and `_total` for counters.
+
+use crate::indexing::QueueMetrics;
+use crate::protocol::{CacheStatistics, DaemonStatus, PoolStatus};
+use std::fmt::Write;
+
+/// Content type of the exposition format, for serving it over HTTP
+pub const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+/// Everything the metrics are built from
+pub struct MetricsSnapshot<'a> {
+    pub status: &'a DaemonStatus,
+    pub cache: &'a CacheStatistics,
+    /// Indexing queue, when indexing has been started
+    pub queue: Option<&'a QueueMetrics>,
+    /// Resident memory of the daemon process, when the platform reports it
+    pub resident_memory_bytes: Option<u64>,
+}
+
+#[derive(Clone, Copy)]
+enum MetricKind {
+    Counter,
+    Gauge,
+}
+
+/// One labelled value of a metric
+type Sample<'a> = (Vec<(&'a str, &'a str)>, f64);
+
+/// Render `snapshot` in the Prometheus text exposition format
+pub fn render_prometheus(snapshot: &MetricsSnapshot) -> String {
+    let mut out = Exposition::default();
+    let status = snapshot.status;
+
+    out.metric(
+        "probe_lsp_daemon_info",
+        MetricKind::Gauge,
+        "Daemon build information",
+        vec![(
+            vec![
+                ("version", status.version.as_str()),
+                ("git_hash", status.git_hash.as_str()),
+                ("build_date", status.build_date.as_str()),
+            ],
+            1.0,
+        )],
+    );
+    out.single(
+        "probe_lsp_daemon_uptime_seconds",
+        MetricKind::Gauge,
+        "Seconds since the daemon started",
+        status.uptime_secs as f64,
+    );
+    out.single(
+        "probe_lsp_daemon_requests_total",
+        MetricKind::Counter,
+        "Requests handled since the daemon started",
+        status.total_requests as f64,
+    );
+    out.single(
+        "probe_lsp_daemon_active_connections",
+        MetricKind::Gauge,
+        "Open client connections",
+        status.active_connections as f64,
+    );
+    out.single(
+        "probe_lsp_daemon_inflight_requests",
+        MetricKind::Gauge,
+        "Requests currently waiting on a language server",
+        status.lsp_inflight_current as f64,
+    );
+    if let Some(bytes) = snapshot.resident_memory_bytes {
+        out.single(
+            "probe_lsp_daemon_resident_memory_bytes",
+            MetricKind::Gauge,
+            "Resident memory of the daemon process",
+            bytes as f64,
+        );
+    }
+
+    // Language-server pools, one series per language
+    let by_language = |value: fn(&PoolStatus) -> f64| per_language(&status.pools, value);
+    out.metric(
+        "probe_lsp_pool_servers",
+        MetricKind::Gauge,
+        "Language servers in the pool",
+        by_language(|pool| pool.total_servers as f64),
+    );
+    out.metric(
+        "probe_lsp_pool_ready_servers",
+        MetricKind::Gauge,
+        "Language servers ready to take requests",
+        by_language(|pool| pool.ready_servers as f64),
+    );
+    out.metric(
+        "probe_lsp_pool_busy_servers",
+        MetricKind::Gauge,
+        "Language servers busy with a request",
+        by_language(|pool| pool.busy_servers as f64),
+    );
+    out.metric(
+        "probe_lsp_pool_workspaces",
+        MetricKind::Gauge,
+        "Workspaces served by the pool",
+        by_language(|pool| pool.workspaces.len() as f64),
+    );
+    out.metric(
+        "probe_lsp_pool_uptime_seconds",
+        MetricKind::Gauge,
+        "Seconds since the pool's language server started",
+        by_language(|pool| pool.uptime_secs as f64),
+    );
+    out.metric(
+        "probe_lsp_pool_consecutive_failures",
+        MetricKind::Gauge,
+        "Consecutive failed requests to the pool's language server",
+        by_language(|pool| pool.consecutive_failures as f64),
+    );
+    out.metric(
+        "probe_lsp_pool_circuit_breaker_open",
+        MetricKind::Gauge,
+        "Whether the pool's circuit breaker is open (1) or closed (0)",
+        by_language(|pool| f64::from(u8::from(pool.circuit_breaker_open))),
+    );
+
+    // Cache
+    let cache = snapshot.cache;
+    out.single(
+        "probe_lsp_cache_entries",
+        MetricKind::Gauge,
+        "Cached entries",
+        cache.total_entries as f64,
+    );
+    out.single(
+        "probe_lsp_cache_size_bytes",
+        MetricKind::Gauge,
+        "Size of the cached entries",
+        cache.total_size_bytes as f64,
+    );
+    out.single(
+        "probe_lsp_cache_disk_size_bytes",
+        MetricKind::Gauge,
+        "Size of the cache on disk",
+        cache.disk_size_bytes as f64,
+    );
+    out.single(
+        "probe_lsp_cache_hit_ratio",
+        MetricKind::Gauge,
+        "Fraction of cache lookups that were hits",
+        cache.hit_rate,
+    );
+    let mut languages: Vec<(&String, &u64)> = cache.entries_per_language.iter().collect();
+    languages.sort();
+    out.metric(
+        "probe_lsp_cache_language_entries",
+        MetricKind::Gauge,
+        "Cached entries per language",
+        languages
+            .into_iter()
+            .map(|(language, &entries)| (vec![("language", language.as_str())], entries as f64))
+            .collect(),
+    );
+    let memory = &cache.memory_usage;
+    out.metric(
+        "probe_lsp_cache_memory_bytes",
+        MetricKind::Gauge,
+        "Memory used by the cache, by component",
+        vec![
+            (
+                vec![("component", "in_memory_cache")],
+                memory.in_memory_cache_bytes as f64,
+            ),
+            (
+                vec![("component", "persistent_cache")],
+                memory.persistent_cache_bytes as f64,
+            ),
+            (
+                vec![("component", "metadata")],
+                memory.metadata_bytes as f64,
+            ),
+            (vec![("component", "index")], memory.index_bytes as f64),
+        ],
+    );
+
+    // Indexing queue
+    if let Some(queue) = snapshot.queue {
+        out.metric(
+            "probe_lsp_indexing_queue_items",
+            MetricKind::Gauge,
+            "Files waiting in the indexing queue, by priority",
+            vec![
+                (
+                    vec![("priority", "critical")],
+                    queue.critical_priority_items as f64,
+                ),
+                (vec![("priority", "high")], queue.high_priority_items as f64),
+                (
+                    vec![("priority", "medium")],
+                    queue.medium_priority_items as f64,
+                ),
+                (vec![("priority", "low")], queue.low_priority_items as f64),
+            ],
+        );
+        out.single(
+            "probe_lsp_indexing_queue_capacity",
+            MetricKind::Gauge,
+            "Maximum number of files in the indexing queue (0 for unbounded)",
+            queue.max_size as f64,
+        );
+        out.single(
+            "probe_lsp_indexing_queue_utilization_ratio",
+            MetricKind::Gauge,
+            "Fraction of the indexing queue capacity in use",
+            queue.utilization_ratio,
+        );
+        out.single(
+            "probe_lsp_indexing_queue_estimated_bytes",
+            MetricKind::Gauge,
+            "Estimated size of the files in the indexing queue",
+            queue.estimated_total_bytes as f64,
+        );
+        out.single(
+            "probe_lsp_indexing_queue_paused",
+            MetricKind::Gauge,
+            "Whether the indexing queue is paused (1) or running (0)",
+            f64::from(u8::from(queue.is_paused)),
+        );
+        out.single(
+            "probe_lsp_indexing_queue_enqueued_total",
+            MetricKind::Counter,
+            "Files added to the indexing queue",
+            queue.total_enqueued as f64,
+        );
+        out.single(
+            "probe_lsp_indexing_queue_dequeued_total",
+            MetricKind::Counter,
+            "Files taken from the indexing queue",
+            queue.total_dequeued as f64,
+        );
+    }
+
+    out.text
+}
+
+/// One sample per pool, labelled with the pool's language
+fn per_language(pools: &[PoolStatus], value: fn(&PoolStatus) -> f64) -> Vec<Sample<'_>> {
+    pools
+        .iter()
+        .map(|pool| (vec![("language", pool.language.as_str())], value(pool)))
+        .collect()
+}
+
+/// Accumulates metric families in the text exposition format
+#[derive(Default)]
+struct Exposition {
+    text: String,
+}
+
+impl Exposition {
+    /// Write a metric family; families without samples are left out
+    fn metric(&mut self, name: &str, kind: MetricKind, help: &str, samples: Vec<Sample>) {
+        if samples.is_empty() {
+            return;
+        }
+        let kind = match kind {
+            MetricKind::Counter => "counter",
+            MetricKind::Gauge => "gauge",
+        };
+        let _ = writeln!(self.text, "# HELP {name} {help}");
+        let _ = writeln!(self.text, "# TYPE {name} {kind}");
+        for (labels, value) in samples {
+            self.text.push_str(name);
+            if !labels.is_empty() {
+                let labels: Vec<String> = labels
+                    .iter()
+                    .map(|(label, value)| format!("{label}=\"{}\"", escape_label_value(value)))
+                    .collect();
+                let _ = write!(self.text, "{{{}}}", labels.join(","));
+            }
+            let _ = writeln!(self.text, " {}", format_value(value));
+        }
+    }
+
+    /// Write a metric family with a single unlabelled value
+    fn single(&mut self, name: &str, kind: MetricKind, help: &str, value: f64) {
+        self.metric(name, kind, help, vec![(Vec::new(), value)]);
+    }
+}
+
+/// Escape a label value: backslashes, double quotes and line feeds
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn format_value(value: f64) -> String {
+    if value.is_nan() {
+        "NaN".to_string()
+    } else if value.is_infinite() {
+        if value > 0.0 { "+Inf" } else { "-Inf" }.to_string()
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language_detector::Language;
+    use crate::protocol::{AgeDistribution, MemoryUsage};
+    use std::collections::HashMap;
+
+    fn status() -> DaemonStatus {
+        DaemonStatus {
+            uptime_secs: 90,
+            pools: vec![PoolStatus {
+                language: Language::Rust,
+                ready_servers: 1,
+                busy_servers: 0,
+                total_servers: 1,
+                workspaces: vec!["/work/app".to_string()],
+                uptime_secs: 80,
+                status: "Ready".to_string(),
+                health_status: "healthy".to_string(),
+                consecutive_failures: 0,
+                circuit_breaker_open: false,
+                readiness_info: None,
+            }],
+            total_requests: 42,
+            active_connections: 2,
+            lsp_inflight_current: 1,
+            version: "1.2.3".to_string(),
+            git_hash: "abc\"def".to_string(),
+            build_date: "2026-01-01".to_string(),
+            universal_cache_stats: None,
+            database_health: None,
+        }
+    }
+
+    fn cache() -> CacheStatistics {
+        CacheStatistics {
+            total_size_bytes: 2048,
+            disk_size_bytes: 4096,
+            total_entries: 10,
+            entries_per_file: HashMap::new(),
+            entries_per_language: HashMap::from([("rust".to_string(), 10)]),
+            hit_rate: 0.75,
+            miss_rate: 0.25,
+            age_distribution: AgeDistribution {
+                entries_last_hour: 0,
+                entries_last_day: 0,
+                entries_last_week: 0,
+                entries_last_month: 0,
+                entries_older: 0,
+            },
+            most_accessed: Vec::new(),
+            memory_usage: MemoryUsage {
+                in_memory_cache_bytes: 512,
+                persistent_cache_bytes: 1024,
+                metadata_bytes: 64,
+                index_bytes: 128,
+            },
+            per_workspace_stats: None,
+            per_operation_totals: None,
+        }
+    }
+
+    #[test]
+    fn test_render_prometheus_families() {
+        let status = status();
+        let cache = cache();
+        let text = render_prometheus(&MetricsSnapshot {
+            status: &status,
+            cache: &cache,
+            queue: None,
+            resident_memory_bytes: Some(1_048_576),
+        });
+
+        assert!(text.contains(
+            "# HELP probe_lsp_daemon_requests_total Requests handled since the daemon started\n# TYPE probe_lsp_daemon_requests_total counter\nprobe_lsp_daemon_requests_total 42\n"
+        ));
+        assert!(text.contains(
+            "probe_lsp_daemon_info{version=\"1.2.3\",git_hash=\"abc\\\"def\",build_date=\"2026-01-01\"} 1\n"
+        ));
+        assert!(text.contains("probe_lsp_daemon_resident_memory_bytes 1048576\n"));
+        assert!(text.contains("probe_lsp_pool_ready_servers{language=\"rust\"} 1\n"));
+        assert!(text.contains("probe_lsp_cache_hit_ratio 0.75\n"));
+        assert!(
+            text.contains("probe_lsp_cache_memory_bytes{component=\"persistent_cache\"} 1024\n")
+        );
+        // Indexing has not started, so there are no queue metrics
+        assert!(!text.contains("probe_lsp_indexing_queue"));
+
+        // Every sample belongs to a family declared just before it
+        let mut declared = "";
+        for line in text.lines() {
+            if let Some(rest) = line.strip_prefix("# TYPE ") {
+                declared = rest.split(' ').next().unwrap();
+            } else if !line.starts_with('#') {
+                assert!(line.starts_with(declared), "{line} outside {declared}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_render_prometheus_queue() {
+        let status = status();
+        let cache = cache();
+        let queue = QueueMetrics {
+            total_items: 3,
+            critical_priority_items: 0,
+            high_priority_items: 1,
+            medium_priority_items: 2,
+            low_priority_items: 0,
+            total_enqueued: 10,
+            total_dequeued: 7,
+            estimated_total_bytes: 300,
+            is_paused: true,
+            max_size: 100,
+            utilization_ratio: 0.03,
+            age_seconds: 5,
+        };
+        let text = render_prometheus(&MetricsSnapshot {
+            status: &status,
+            cache: &cache,
+            queue: Some(&queue),
+            resident_memory_bytes: None,
+        });
+
+        assert!(text.contains("probe_lsp_indexing_queue_items{priority=\"medium\"} 2\n"));
+        assert!(text.contains("probe_lsp_indexing_queue_paused 1\n"));
+        assert!(text.contains("probe_lsp_indexing_queue_dequeued_total 7\n"));
+        assert!(!text.contains("probe_lsp_daemon_resident_memory_bytes"));
+    }
+}
@@ -2,7 +2,7 @@ use crate::language_detector::Language;
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::time::{timeout, Duration};
 use uuid::Uuid;
@@ -45,6 +45,16 @@ pub enum DaemonRequest {
         #[serde(skip_serializing_if = "Option::is_none")]
         workspace_hint: Option<PathBuf>,
     },
+    // Call hierarchy followed recursively up to `max_depth` levels in each direction
+    CallHierarchyTree {
+        request_id: Uuid,
+        file_path: PathBuf,
+        line: u32,
+        column: u32,
+        max_depth: u32,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        workspace_hint: Option<PathBuf>,
+    },
     Definition {
         request_id: Uuid,
         file_path: PathBuf,
@@ -70,6 +80,33 @@ pub enum DaemonRequest {
         #[serde(skip_serializing_if = "Option::is_none")]
         workspace_hint: Option<PathBuf>,
     },
+    DocumentHighlight {
+        request_id: Uuid,
+        file_path: PathBuf,
+        line: u32,
+        column: u32,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        workspace_hint: Option<PathBuf>,
+    },
+    /// Ask the language server how to rename the symbol at a position. The edits are
+    /// returned, never applied by the daemon.
+    Rename {
+        request_id: Uuid,
+        file_path: PathBuf,
+        line: u32,
+        column: u32,
+        new_name: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        workspace_hint: Option<PathBuf>,
+    },
+    /// Hover at several positions in one round-trip. Requests for the same language server
+    /// are pipelined, and results come back in the order of `positions`.
+    HoverBatch {
+        request_id: Uuid,
+        positions: Vec<HoverPosition>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        workspace_hint: Option<PathBuf>,
+    },
     Completion {
         request_id: Uuid,
         file_path: PathBuf,
@@ -106,6 +143,16 @@ pub enum DaemonRequest {
         #[serde(skip_serializing_if = "Option::is_none")]
         workspace_hint: Option<PathBuf>,
     },
+    /// References, callers, callees, implementations and type definition of the symbol at a
+    /// position in one round-trip. The daemon runs the lookups concurrently.
+    Related {
+        request_id: Uuid,
+        file_path: PathBuf,
+        line: u32,
+        column: u32,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        workspace_hint: Option<PathBuf>,
+    },
     // System requests
     Status {
         request_id: Uuid,
@@ -140,6 +187,19 @@ pub enum DaemonRequest {
     DbLockSnapshot {
         request_id: Uuid,
     },
+    /// List running language-server processes (diagnostics)
+    ListServers {
+        request_id: Uuid,
+    },
+    /// Kill a single language-server process without restarting the daemon
+    KillServer {
+        request_id: Uuid,
+        id: String,
+    },
+    /// Daemon, pool, cache and indexing queue metrics in the Prometheus text format
+    Metrics {
+        request_id: Uuid,
+    },
     // Indexing management requests
     StartIndexing {
         request_id: Uuid,
@@ -150,6 +210,18 @@ pub enum DaemonRequest {
         request_id: Uuid,
         force: bool,
     },
+    /// Index a file ahead of the rest of the indexing queue
+    PrioritizeIndexing {
+        request_id: Uuid,
+        file_path: PathBuf,
+    },
+    /// Push an `IndexingProgressUpdate` every `interval_ms` over this connection until
+    /// indexing completes, then a terminal `IndexingProgressEnd`. Can be cancelled with
+    /// `Cancel { cancel_request_id: request_id }` or by closing the connection.
+    SubscribeIndexingProgress {
+        request_id: Uuid,
+        interval_ms: u64,
+    },
     IndexingStatus {
         request_id: Uuid,
     },
@@ -263,6 +335,15 @@ pub enum DaemonRequest {
         detailed: bool,
     },
 
+    /// Remove cached symbols and edges for files that were deleted or changed
+    CachePrune {
+        request_id: Uuid,
+        workspace_path: Option<PathBuf>,
+        /// Report what would be removed without deleting anything
+        #[serde(default)]
+        dry_run: bool,
+    },
+
     /// Get workspace database file path (used by CLI for offline operations)
     WorkspaceDbPath {
         request_id: Uuid,
@@ -276,6 +357,52 @@ pub enum DaemonRequest {
         output_path: PathBuf,
         checkpoint: bool,
     },
+    /// Export the symbol graph reachable from a root symbol, or everything that changed
+    /// since a completed analysis run, to a file
+    GraphExport {
+        request_id: Uuid,
+        workspace_path: Option<PathBuf>,
+        output_path: PathBuf,
+        /// json, graphml, dot or mermaid
+        format: String,
+        /// Symbol UID or `file#name`
+        #[serde(default)]
+        root: Option<String>,
+        /// callers, callees or both
+        direction: String,
+        max_depth: u32,
+        /// Analysis run to export the changes since, instead of a subgraph
+        #[serde(default)]
+        since_run: Option<i64>,
+        /// Add nodes for edge targets outside the exported symbols, marked `external`
+        #[serde(default)]
+        include_external: bool,
+    },
+    /// Query indexed symbols; unset filters match every symbol
+    FindSymbols {
+        request_id: Uuid,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        workspace_path: Option<PathBuf>,
+        #[serde(default)]
+        kind: Option<String>,
+        #[serde(default)]
+        visibility: Option<String>,
+        #[serde(default)]
+        language: Option<String>,
+        /// Glob over workspace-relative file paths
+        #[serde(default)]
+        file_glob: Option<String>,
+        #[serde(default)]
+        limit: Option<usize>,
+    },
+    /// Full-text search over indexed symbol names, FQNs and documentation
+    SearchSymbols {
+        request_id: Uuid,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        workspace_path: Option<PathBuf>,
+        query: String,
+        limit: usize,
+    },
     /// Force WAL checkpoint and wait for exclusive access if needed
     WalSync {
         request_id: Uuid,
@@ -306,6 +433,65 @@ pub enum DaemonRequest {
         #[serde(default)]
         samples: usize,
     },
+    /// Run `request` and deliver its result list as `StreamChunk` frames of at most
+    /// `chunk_size` items followed by a terminal `StreamEnd`. Can be cancelled between
+    /// chunks with `Cancel { cancel_request_id: request_id }`.
+    Stream {
+        request_id: Uuid,
+        chunk_size: usize,
+        request: Box<DaemonRequest>,
+    },
+    /// Run `request` with a deadline of `timeout_ms`, replacing the daemon's default timeout
+    /// (call hierarchy requests keep at least their `min_deadline`). A request that misses it
+    /// is answered with `Timeout`; the language server that failed to respond is restarted
+    /// once it keeps missing deadlines.
+    Deadline {
+        request_id: Uuid,
+        timeout_ms: u64,
+        request: Box<DaemonRequest>,
+    },
+}
+
+impl DaemonRequest {
+    /// File whose language server answers this request, for requests about a single document
+    pub fn target_file(&self) -> Option<&Path> {
+        match self {
+            DaemonRequest::CallHierarchy { file_path, .. }
+            | DaemonRequest::CallHierarchyTree { file_path, .. }
+            | DaemonRequest::Definition { file_path, .. }
+            | DaemonRequest::References { file_path, .. }
+            | DaemonRequest::Hover { file_path, .. }
+            | DaemonRequest::DocumentHighlight { file_path, .. }
+            | DaemonRequest::Rename { file_path, .. }
+            | DaemonRequest::Completion { file_path, .. }
+            | DaemonRequest::DocumentSymbols { file_path, .. }
+            | DaemonRequest::Implementations { file_path, .. }
+            | DaemonRequest::TypeDefinition { file_path, .. }
+            | DaemonRequest::Related { file_path, .. } => Some(file_path),
+            DaemonRequest::HoverBatch { positions, .. } => positions
+                .first()
+                .map(|position| position.file_path.as_path()),
+            DaemonRequest::Stream { request, .. } => request.target_file(),
+            _ => None,
+        }
+    }
+
+    /// Shortest time the daemon gives this request, even under a shorter deadline. Call
+    /// hierarchy can be slow, and a tree may need fresh lookups for every level, so these
+    /// requests get `PROBE_LSP_CALL_OUTER_TIMEOUT_SECS` (90s by default) per level.
+    pub fn min_deadline(&self) -> Option<Duration> {
+        let levels = match self {
+            DaemonRequest::CallHierarchyTree { max_depth, .. } => (*max_depth).max(1) as u64,
+            DaemonRequest::CallHierarchy { .. } | DaemonRequest::Related { .. } => 1,
+            DaemonRequest::Stream { request, .. } => return request.min_deadline(),
+            _ => return None,
+        };
+        let secs = std::env::var("PROBE_LSP_CALL_OUTER_TIMEOUT_SECS")
+            .ok()
+            .and_then(|s| s.parse::<u64>().ok())
+            .unwrap_or(90);
+        Some(Duration::from_secs(secs.saturating_mul(levels)))
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -338,6 +524,15 @@ pub enum DaemonResponse {
         result: CallHierarchyResult,
         #[serde(skip_serializing_if = "Option::is_none")]
         warnings: Option<Vec<String>>,
+        /// Whether the result was answered from the database instead of the language server
+        #[serde(default)]
+        cached: bool,
+    },
+    CallHierarchyTree {
+        request_id: Uuid,
+        root: CallHierarchyNode,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        warnings: Option<Vec<String>>,
     },
     Definition {
         request_id: Uuid,
@@ -357,6 +552,28 @@ pub enum DaemonResponse {
         #[serde(skip_serializing_if = "Option::is_none")]
         warnings: Option<Vec<String>>,
     },
+    DocumentHighlight {
+        request_id: Uuid,
+        highlights: Vec<DocumentHighlight>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        warnings: Option<Vec<String>>,
+    },
+    /// The text edits of the rename's `WorkspaceEdit`, per file. File create/rename/delete
+    /// operations are not supported and are reported in `warnings`
+    Rename {
+        request_id: Uuid,
+        files: Vec<FileEdits>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        warnings: Option<Vec<String>>,
+    },
+    /// One entry per requested position; positions that failed are `None` and explained in
+    /// `warnings`
+    HoverBatch {
+        request_id: Uuid,
+        results: Vec<Option<HoverContent>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        warnings: Option<Vec<String>>,
+    },
     Completion {
         request_id: Uuid,
         items: Vec<CompletionItem>,
@@ -387,6 +604,16 @@ pub enum DaemonResponse {
         #[serde(skip_serializing_if = "Option::is_none")]
         warnings: Option<Vec<String>>,
     },
+    /// One group per relationship kind, in a fixed order. `symbol` is the call hierarchy
+    /// item of the requested symbol when the server reported one
+    Related {
+        request_id: Uuid,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        symbol: Option<CallHierarchyItem>,
+        groups: Vec<RelatedGroup>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        warnings: Option<Vec<String>>,
+    },
     // System responses
     Status {
         request_id: Uuid,
@@ -445,6 +672,22 @@ pub enum DaemonResponse {
         #[serde(skip_serializing_if = "Option::is_none")]
         active_ms: Option<u128>,
     },
+    /// Running language-server processes
+    ServerList {
+        request_id: Uuid,
+        servers: Vec<LspServerProcessInfo>,
+    },
+    /// Prometheus text exposition of the daemon's metrics
+    Metrics {
+        request_id: Uuid,
+        text: String,
+    },
+    ServerKilled {
+        request_id: Uuid,
+        id: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pid: Option<u32>,
+    },
     // Indexing management responses
     IndexingStarted {
         request_id: Uuid,
@@ -455,6 +698,24 @@ pub enum DaemonResponse {
         request_id: Uuid,
         was_running: bool,
     },
+    IndexingPrioritized {
+        request_id: Uuid,
+        file_path: PathBuf,
+        /// False if the file was already queued at high priority
+        changed: bool,
+    },
+    /// One event of a `SubscribeIndexingProgress` stream
+    IndexingProgressUpdate {
+        request_id: Uuid,
+        sequence: u32,
+        event: IndexingProgressEvent,
+    },
+    /// Terminal frame of a `SubscribeIndexingProgress` stream
+    IndexingProgressEnd {
+        request_id: Uuid,
+        events: u32,
+        reason: ProgressStreamEnd,
+    },
     IndexingStatusResponse {
         request_id: Uuid,
         status: IndexingStatusInfo,
@@ -545,6 +806,11 @@ pub enum DaemonResponse {
         has_more: bool,
     },
 
+    CachePruned {
+        request_id: Uuid,
+        result: CachePruneResult,
+    },
+
     WorkspaceDbPath {
         request_id: Uuid,
         workspace_path: PathBuf,
@@ -558,6 +824,19 @@ pub enum DaemonResponse {
         output_path: PathBuf,
         database_size_bytes: usize,
     },
+    GraphExported {
+        request_id: Uuid,
+        workspace_path: PathBuf,
+        output_path: PathBuf,
+        root_symbol: String,
+        nodes_count: usize,
+        edges_count: usize,
+    },
+    SymbolsFound {
+        request_id: Uuid,
+        workspace_path: PathBuf,
+        symbols: Vec<crate::database::SymbolState>,
+    },
     /// Response for WAL sync request
     WalSynced {
         request_id: Uuid,
@@ -571,6 +850,38 @@ pub enum DaemonResponse {
         counts: EdgeAuditInfo,
         samples: Vec<String>,
     },
+    /// One chunk of a streamed result list; `request_id` is the wrapped request's ID
+    StreamChunk {
+        request_id: Uuid,
+        sequence: u32,
+        items: StreamItems,
+    },
+    /// Terminal frame of a streamed response
+    StreamEnd {
+        request_id: Uuid,
+        chunks: u32,
+        total_items: usize,
+        #[serde(default)]
+        cancelled: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        warnings: Option<Vec<String>>,
+    },
+
+    /// A `Deadline` request did not complete in time
+    Timeout {
+        request_id: Uuid,
+        timeout_ms: u64,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        language: Option<Language>,
+    },
+
+    /// A request failed and its failure got the language server restarted automatically;
+    /// the replacement is already serving, so the request can be retried
+    ServerRestarted {
+        request_id: Uuid,
+        language: Language,
+        error: String,
+    },
 
     Error {
         request_id: Uuid,
@@ -578,6 +889,268 @@ pub enum DaemonResponse {
     },
 }
 
+/// Result items carried by a `StreamChunk`, tagged with the response they belong to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum StreamItems {
+    References(Vec<Location>),
+    Implementations(Vec<Location>),
+    WorkspaceSymbols(Vec<SymbolInformation>),
+}
+
+impl StreamItems {
+    pub fn len(&self) -> usize {
+        match self {
+            StreamItems::References(items) | StreamItems::Implementations(items) => items.len(),
+            StreamItems::WorkspaceSymbols(items) => items.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Append the items of a later chunk, which must be of the same kind
+    fn extend(&mut self, other: StreamItems) -> Result<()> {
+        match (self, other) {
+            (StreamItems::References(items), StreamItems::References(more))
+            | (StreamItems::Implementations(items), StreamItems::Implementations(more)) => {
+                items.extend(more)
+            }
+            (StreamItems::WorkspaceSymbols(items), StreamItems::WorkspaceSymbols(more)) => {
+                items.extend(more)
+            }
+            _ => return Err(anyhow::anyhow!("Stream chunk kind changed mid-stream")),
+        }
+        Ok(())
+    }
+
+    fn into_response(self, request_id: Uuid, warnings: Option<Vec<String>>) -> DaemonResponse {
+        match self {
+            StreamItems::References(locations) => DaemonResponse::References {
+                request_id,
+                locations,
+                warnings,
+            },
+            StreamItems::Implementations(locations) => DaemonResponse::Implementations {
+                request_id,
+                locations,
+                warnings,
+            },
+            StreamItems::WorkspaceSymbols(symbols) => DaemonResponse::WorkspaceSymbols {
+                request_id,
+                symbols,
+                warnings,
+            },
+        }
+    }
+}
+
+/// Result items of a streamed response that have not been sent yet
+#[derive(Debug)]
+enum PendingItems {
+    References(std::vec::IntoIter<Location>),
+    Implementations(std::vec::IntoIter<Location>),
+    WorkspaceSymbols(std::vec::IntoIter<SymbolInformation>),
+}
+
+impl PendingItems {
+    fn len(&self) -> usize {
+        match self {
+            PendingItems::References(items) | PendingItems::Implementations(items) => items.len(),
+            PendingItems::WorkspaceSymbols(items) => items.len(),
+        }
+    }
+
+    /// Take the next `chunk_size` items
+    fn next_chunk(&mut self, chunk_size: usize) -> StreamItems {
+        match self {
+            PendingItems::References(items) => {
+                StreamItems::References(items.by_ref().take(chunk_size).collect())
+            }
+            PendingItems::Implementations(items) => {
+                StreamItems::Implementations(items.by_ref().take(chunk_size).collect())
+            }
+            PendingItems::WorkspaceSymbols(items) => {
+                StreamItems::WorkspaceSymbols(items.by_ref().take(chunk_size).collect())
+            }
+        }
+    }
+}
+
+/// Frames of a streamed response, built one at a time as they are sent; see
+/// `into_stream_frames`
+#[derive(Debug)]
+pub struct StreamFrames {
+    request_id: Uuid,
+    chunk_size: usize,
+    /// Items left to send, or `None` once the end frame was produced
+    pending: Option<PendingItems>,
+    /// A response without a streamable result list, sent as its only frame
+    single: Option<DaemonResponse>,
+    sequence: u32,
+    total_items: usize,
+    warnings: Option<Vec<String>>,
+}
+
+impl Iterator for StreamFrames {
+    type Item = DaemonResponse;
+
+    fn next(&mut self) -> Option<DaemonResponse> {
+        if let Some(response) = self.single.take() {
+            return Some(response);
+        }
+        let pending = self.pending.as_mut()?;
+        // Always send one chunk so the client learns the response kind
+        if self.sequence == 0 || pending.len() > 0 {
+            let items = pending.next_chunk(self.chunk_size);
+            let sequence = self.sequence;
+            self.sequence += 1;
+            return Some(DaemonResponse::StreamChunk {
+                request_id: self.request_id,
+                sequence,
+                items,
+            });
+        }
+        self.pending = None;
+        Some(DaemonResponse::StreamEnd {
+            request_id: self.request_id,
+            chunks: self.sequence,
+            total_items: self.total_items,
+            cancelled: false,
+            warnings: self.warnings.take(),
+        })
+    }
+}
+
+/// Split a response into `StreamChunk` frames of at most `chunk_size` items and a terminal
+/// `StreamEnd`. Each chunk is only built when the previous frame has been taken, so a large
+/// result is never copied into frames all at once. Responses without a streamable result
+/// list (including errors) are yielded as a single frame unchanged.
+pub fn into_stream_frames(response: DaemonResponse, chunk_size: usize) -> StreamFrames {
+    let (request_id, pending, warnings) = match response {
+        DaemonResponse::References {
+            request_id,
+            locations,
+            warnings,
+        } => (
+            request_id,
+            PendingItems::References(locations.into_iter()),
+            warnings,
+        ),
+        DaemonResponse::Implementations {
+            request_id,
+            locations,
+            warnings,
+        } => (
+            request_id,
+            PendingItems::Implementations(locations.into_iter()),
+            warnings,
+        ),
+        DaemonResponse::WorkspaceSymbols {
+            request_id,
+            symbols,
+            warnings,
+        } => (
+            request_id,
+            PendingItems::WorkspaceSymbols(symbols.into_iter()),
+            warnings,
+        ),
+        other => {
+            return StreamFrames {
+                request_id: Uuid::nil(),
+                chunk_size,
+                pending: None,
+                single: Some(other),
+                sequence: 0,
+                total_items: 0,
+                warnings: None,
+            }
+        }
+    };
+
+    StreamFrames {
+        request_id,
+        chunk_size: chunk_size.max(1),
+        total_items: pending.len(),
+        pending: Some(pending),
+        single: None,
+        sequence: 0,
+        warnings,
+    }
+}
+
+/// Reassembles the frames of a streamed response into the original response
+#[derive(Debug, Default)]
+pub struct StreamAssembler {
+    items: Option<StreamItems>,
+    next_sequence: u32,
+}
+
+impl StreamAssembler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of items received so far
+    pub fn received_items(&self) -> usize {
+        self.items.as_ref().map_or(0, StreamItems::len)
+    }
+
+    /// Feed the next frame. Returns the assembled response once the stream has ended; a
+    /// frame that is not part of a stream (e.g. an `Error`) completes it as-is. A cancelled
+    /// stream completes with an `Error` response.
+    pub fn push(&mut self, frame: DaemonResponse) -> Result<Option<DaemonResponse>> {
+        match frame {
+            DaemonResponse::StreamChunk {
+                sequence, items, ..
+            } => {
+                if sequence != self.next_sequence {
+                    return Err(anyhow::anyhow!(
+                        "Out-of-order stream chunk: expected {}, got {}",
+                        self.next_sequence,
+                        sequence
+                    ));
+                }
+                self.next_sequence += 1;
+                match self.items.as_mut() {
+                    Some(received) => received.extend(items)?,
+                    None => self.items = Some(items),
+                }
+                Ok(None)
+            }
+            DaemonResponse::StreamEnd {
+                request_id,
+                chunks,
+                total_items,
+                cancelled,
+                warnings,
+            } => {
+                if cancelled {
+                    return Ok(Some(DaemonResponse::Error {
+                        request_id,
+                        error: format!("Stream cancelled after {} items", self.received_items()),
+                    }));
+                }
+                if chunks != self.next_sequence || total_items != self.received_items() {
+                    return Err(anyhow::anyhow!(
+                        "Incomplete stream: expected {} chunks/{} items, received {}/{}",
+                        chunks,
+                        total_items,
+                        self.next_sequence,
+                        self.received_items()
+                    ));
+                }
+                let items = self
+                    .items
+                    .take()
+                    .ok_or_else(|| anyhow::anyhow!("Stream ended without any chunks"))?;
+                Ok(Some(items.into_response(request_id, warnings)))
+            }
+            other => Ok(Some(other)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ConnectionInfo {
     pub client_id: String,
@@ -609,24 +1182,180 @@ pub struct CallHierarchyCall {
     pub from_ranges: Vec<Range>,
 }
 
+/// A symbol in a call hierarchy tree. The root has callers and callees; below the root a
+/// node only continues in the direction it was reached from. A node whose symbol already
+/// appears on the path from the root is marked as a cycle and not expanded.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallHierarchyNode {
+    pub item: CallHierarchyItem,
+    /// Call sites between this node and its parent (empty for the root)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub from_ranges: Vec<Range>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub cycle: bool,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub incoming: Vec<CallHierarchyNode>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub outgoing: Vec<CallHierarchyNode>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Location {
     pub uri: String,
     pub range: Range,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Range {
     pub start: Position,
     pub end: Position,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Position {
     pub line: u32,
     pub character: u32,
 }
 
+/// An occurrence of a symbol within its file, as returned by `textDocument/documentHighlight`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DocumentHighlight {
+    pub range: Range,
+    pub kind: DocumentHighlightKind,
+}
+
+/// Whether a highlighted occurrence reads or writes the symbol. Servers report `Text` when
+/// they cannot tell, and it is the LSP default when no kind is given.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DocumentHighlightKind {
+    Text,
+    Read,
+    Write,
+}
+
+impl DocumentHighlightKind {
+    /// Convert the numeric kind used by LSP (1 = text, 2 = read, 3 = write)
+    pub fn from_lsp(kind: Option<u64>) -> Self {
+        match kind {
+            Some(2) => DocumentHighlightKind::Read,
+            Some(3) => DocumentHighlightKind::Write,
+            _ => DocumentHighlightKind::Text,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DocumentHighlightKind::Text => "text",
+            DocumentHighlightKind::Read => "read",
+            DocumentHighlightKind::Write => "write",
+        }
+    }
+}
+
+/// Replacement of the text in `range` with `new_text`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TextEdit {
+    pub range: Range,
+    pub new_text: String,
+}
+
+/// The text edits a `WorkspaceEdit` makes to one file, in the order the server sent them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FileEdits {
+    pub file_path: PathBuf,
+    pub edits: Vec<TextEdit>,
+}
+
+/// How the symbols in a `Related` group relate to the requested symbol
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RelationKind {
+    References,
+    IncomingCalls,
+    OutgoingCalls,
+    Implementations,
+    TypeDefinition,
+}
+
+impl RelationKind {
+    /// Heading shown for the group in terminal output
+    pub fn title(self) -> &'static str {
+        match self {
+            RelationKind::References => "References",
+            RelationKind::IncomingCalls => "Incoming Calls",
+            RelationKind::OutgoingCalls => "Outgoing Calls",
+            RelationKind::Implementations => "Implementations",
+            RelationKind::TypeDefinition => "Type Definition",
+        }
+    }
+}
+
+/// Whether a `Related` group was answered from the daemon's database or by the language
+/// server
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RelatedSource {
+    Cache,
+    Live,
+}
+
+/// A location related to the requested symbol; calls also carry the caller or callee name
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelatedItem {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub location: Location,
+}
+
+/// The related symbols of one kind. A lookup that failed has no items and an `error`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RelatedGroup {
+    pub kind: RelationKind,
+    pub source: RelatedSource,
+    pub items: Vec<RelatedItem>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl RelatedGroup {
+    /// Group of `items` in file and position order, each location listed once
+    pub fn new(kind: RelationKind, source: RelatedSource, mut items: Vec<RelatedItem>) -> Self {
+        let key = |item: &RelatedItem| {
+            let start = &item.location.range.start;
+            (item.location.uri.clone(), start.line, start.character)
+        };
+        items.sort_by_key(key);
+        items.dedup_by(|a, b| {
+            a.location.uri == b.location.uri && a.location.range == b.location.range
+        });
+        Self {
+            kind,
+            source,
+            items,
+            error: None,
+        }
+    }
+
+    /// Group for a lookup that failed with `error`
+    pub fn failed(kind: RelationKind, error: String) -> Self {
+        Self {
+            kind,
+            source: RelatedSource::Live,
+            items: Vec::new(),
+            error: Some(error),
+        }
+    }
+}
+
+/// A position to hover at in a `HoverBatch` request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HoverPosition {
+    pub file_path: PathBuf,
+    pub line: u32,
+    pub column: u32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HoverContent {
     pub contents: String,
@@ -874,6 +1603,10 @@ pub struct IndexingConfig {
     #[serde(default)]
     pub incremental: Option<bool>,
     #[serde(default)]
+    pub include_tests: Option<bool>,
+    #[serde(default)]
+    pub max_duration_secs: Option<u64>,
+    #[serde(default)]
     pub languages: Vec<String>,
     #[serde(default)]
     pub recursive: bool,
@@ -902,6 +1635,17 @@ pub struct IndexingConfig {
     pub lsp_priority_operations: Vec<String>,
     #[serde(default)]
     pub lsp_disabled_operations: Vec<String>,
+
+    /// Per-language overrides keyed by language name (e.g. "cpp"), same shape as settings.json
+    #[serde(default, skip_serializing_if = "std::collections::HashMap::is_empty")]
+    pub language_configs: std::collections::HashMap<String, LanguageIndexingConfig>,
+}
+
+/// Per-language indexing overrides that can be changed while the daemon runs
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LanguageIndexingConfig {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
 }
 
 impl Default for IndexingConfig {
@@ -921,6 +1665,8 @@ impl Default for IndexingConfig {
             specific_files: vec![],
             max_file_size_mb: Some(10),
             incremental: Some(true),
+            include_tests: None,
+            max_duration_secs: None,
             languages: vec![],
             recursive: true,
 
@@ -936,6 +1682,7 @@ impl Default for IndexingConfig {
             lsp_operation_timeout_ms: None,
             lsp_priority_operations: vec![],
             lsp_disabled_operations: vec![],
+            language_configs: std::collections::HashMap::new(),
         }
     }
 }
@@ -964,6 +1711,10 @@ pub struct IndexingStatusInfo {
     pub snapshot_updated_at: Option<u64>, // unix seconds when snapshot was produced
     #[serde(default)]
     pub snapshot_age_secs: Option<u64>, // age computed at response time
+    /// Analysis run recorded by the current indexing session, the baseline for
+    /// `index-export --since-run` once indexing completes
+    #[serde(default)]
+    pub analysis_run_id: Option<i64>,
 }
 
 impl IndexingStatusInfo {
@@ -978,6 +1729,8 @@ impl IndexingStatusInfo {
                 failed_files: 0,
                 active_files: 0,
                 skipped_files: 0,
+                test_files_skipped: 0,
+                deferred_files: 0,
                 processed_bytes: 0,
                 symbols_extracted: 0,
                 progress_ratio: 0.0,
@@ -1004,6 +1757,7 @@ impl IndexingStatusInfo {
             empty_cache: None,
             snapshot_updated_at: None,
             snapshot_age_secs: None,
+            analysis_run_id: None,
         }
     }
 }
@@ -1015,6 +1769,12 @@ pub struct IndexingProgressInfo {
     pub failed_files: u64,
     pub active_files: u64,
     pub skipped_files: u64,
+    /// Test files excluded from indexing because `include_tests` is disabled
+    #[serde(default)]
+    pub test_files_skipped: u64,
+    /// Files left unindexed because `max_duration_secs` was reached
+    #[serde(default)]
+    pub deferred_files: u64,
     pub processed_bytes: u64,
     pub symbols_extracted: u64,
     pub progress_ratio: f64,
@@ -1022,6 +1782,42 @@ pub struct IndexingProgressInfo {
     pub bytes_per_second: f64,
 }
 
+/// Progress pushed to `SubscribeIndexingProgress` subscribers
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndexingProgressEvent {
+    pub manager_status: String,
+    pub progress: IndexingProgressInfo,
+    pub active_workers: usize,
+    pub elapsed_seconds: u64,
+    /// Estimated seconds until all discovered files are done, once a rate is known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub eta_seconds: Option<u64>,
+    /// Per-language counts keyed by language name
+    #[serde(default)]
+    pub languages: std::collections::BTreeMap<String, LanguageProgressInfo>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LanguageProgressInfo {
+    pub processed_files: u64,
+    pub failed_files: u64,
+    pub symbols_extracted: u64,
+}
+
+/// Why a progress subscription ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProgressStreamEnd {
+    /// Every discovered file was processed
+    Completed,
+    /// Indexing was stopped or failed before completing
+    Stopped,
+    /// No indexing was running when the subscription started
+    NotRunning,
+    /// The subscriber cancelled the stream
+    Cancelled,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct IndexingQueueInfo {
     pub total_items: usize,
@@ -1449,6 +2245,22 @@ pub struct LspServerHealthInfo {
     pub response_time_ms: u64,
 }
 
+/// A language-server process managed by the daemon
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LspServerProcessInfo {
+    /// Identifier accepted by `KillServer` (the daemon runs one server per language)
+    pub id: String,
+    pub language: Language,
+    pub pid: Option<u32>,
+    pub workspaces: Vec<PathBuf>,
+    pub uptime_secs: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memory_mb: Option<u64>,
+    /// Automatic restarts after repeated errors since the daemon started
+    #[serde(default)]
+    pub restarts: u32,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PoolStatus {
     pub language: Language,
@@ -1666,6 +2478,22 @@ pub struct SymbolCacheClearResult {
     pub duration_ms: u64,
 }
 
+// Cache prune result
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CachePruneResult {
+    pub workspace_path: PathBuf,
+    /// Distinct workspace files referenced by cached symbols
+    pub files_checked: u64,
+    /// Referenced files that no longer exist on disk
+    pub missing_files: u64,
+    /// Referenced files whose content digest no longer matches the cached symbols
+    pub changed_files: u64,
+    pub symbols_removed: u64,
+    pub edges_removed: u64,
+    pub dry_run: bool,
+    pub duration_ms: u64,
+}
+
 // Generic cache operation results
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClearResult {
@@ -1883,6 +2711,25 @@ impl MessageCodec {
         Ok(message_buf)
     }
 
+    /// Read the frames of a streamed response until it completes and return the assembled
+    /// response. `read_timeout` applies to each frame, not the whole stream.
+    pub async fn read_response_stream<R>(
+        reader: &mut R,
+        read_timeout: Duration,
+    ) -> Result<DaemonResponse>
+    where
+        R: AsyncReadExt + Unpin,
+    {
+        let mut assembler = StreamAssembler::new();
+        loop {
+            let frame = Self::read_framed(reader, read_timeout).await?;
+            let frame: DaemonResponse = serde_json::from_slice(&frame)?;
+            if let Some(response) = assembler.push(frame)? {
+                return Ok(response);
+            }
+        }
+    }
+
     /// Async method to write a framed message with timeout
     pub async fn write_framed<W>(writer: &mut W, data: &[u8], write_timeout: Duration) -> Result<()>
     where
@@ -2337,6 +3184,190 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_document_highlight_kind_from_lsp() {
+        assert_eq!(
+            DocumentHighlightKind::from_lsp(Some(3)),
+            DocumentHighlightKind::Write
+        );
+        assert_eq!(
+            DocumentHighlightKind::from_lsp(Some(2)),
+            DocumentHighlightKind::Read
+        );
+        // Kind is optional in LSP and defaults to text
+        assert_eq!(
+            DocumentHighlightKind::from_lsp(None),
+            DocumentHighlightKind::Text
+        );
+        assert_eq!(
+            serde_json::to_string(&DocumentHighlightKind::Write).unwrap(),
+            "\"write\""
+        );
+    }
+
+    #[test]
+    fn test_call_hierarchy_tree_round_trip() {
+        let item = |name: &str| CallHierarchyItem {
+            name: name.to_string(),
+            kind: "function".to_string(),
+            uri: format!("file:///src/{name}.rs"),
+            range: Range {
+                start: Position {
+                    line: 1,
+                    character: 0,
+                },
+                end: Position {
+                    line: 3,
+                    character: 1,
+                },
+            },
+            selection_range: Range {
+                start: Position {
+                    line: 1,
+                    character: 3,
+                },
+                end: Position {
+                    line: 1,
+                    character: 7,
+                },
+            },
+        };
+        let leaf = |name: &str, cycle: bool| CallHierarchyNode {
+            item: item(name),
+            from_ranges: Vec::new(),
+            cycle,
+            incoming: Vec::new(),
+            outgoing: Vec::new(),
+        };
+        let response = DaemonResponse::CallHierarchyTree {
+            request_id: Uuid::new_v4(),
+            root: CallHierarchyNode {
+                outgoing: vec![CallHierarchyNode {
+                    outgoing: vec![leaf("main", true)],
+                    ..leaf("helper", false)
+                }],
+                ..leaf("main", false)
+            },
+            warnings: None,
+        };
+
+        let encoded = MessageCodec::encode_response(&response).expect("encode");
+        match MessageCodec::decode_response(&encoded).expect("decode") {
+            DaemonResponse::CallHierarchyTree { root, .. } => {
+                assert!(!root.cycle);
+                assert!(root.incoming.is_empty());
+                let helper = &root.outgoing[0];
+                assert_eq!(helper.item.name, "helper");
+                assert!(helper.outgoing[0].cycle);
+            }
+            _ => panic!("expected CallHierarchyTree"),
+        }
+    }
+
+    #[test]
+    fn test_hover_batch_round_trip_keeps_position_order() {
+        let request = DaemonRequest::HoverBatch {
+            request_id: Uuid::new_v4(),
+            positions: vec![
+                HoverPosition {
+                    file_path: PathBuf::from("src/lib.rs"),
+                    line: 10,
+                    column: 4,
+                },
+                HoverPosition {
+                    file_path: PathBuf::from("src/main.rs"),
+                    line: 2,
+                    column: 8,
+                },
+            ],
+            workspace_hint: None,
+        };
+        let encoded = MessageCodec::encode(&request).expect("encode");
+        match MessageCodec::decode_request(&encoded).expect("decode") {
+            DaemonRequest::HoverBatch { positions, .. } => {
+                let lines: Vec<u32> = positions.iter().map(|p| p.line).collect();
+                assert_eq!(lines, vec![10, 2]);
+                assert_eq!(positions[1].file_path, PathBuf::from("src/main.rs"));
+            }
+            _ => panic!("expected HoverBatch"),
+        }
+
+        let response = DaemonResponse::HoverBatch {
+            request_id: Uuid::new_v4(),
+            results: vec![
+                None,
+                Some(HoverContent {
+                    contents: "fn main()".to_string(),
+                    range: None,
+                }),
+            ],
+            warnings: Some(vec!["src/lib.rs:10:4: no server".to_string()]),
+        };
+        let encoded = MessageCodec::encode_response(&response).expect("encode");
+        match MessageCodec::decode_response(&encoded).expect("decode") {
+            DaemonResponse::HoverBatch { results, .. } => {
+                assert!(results[0].is_none());
+                assert_eq!(results[1].as_ref().unwrap().contents, "fn main()");
+            }
+            _ => panic!("expected HoverBatch"),
+        }
+    }
+
+    #[test]
+    fn test_related_group_deduplicates_locations() {
+        let item = |uri: &str, line: u32| RelatedItem {
+            name: None,
+            location: Location {
+                uri: uri.to_string(),
+                range: Range {
+                    start: Position { line, character: 4 },
+                    end: Position {
+                        line,
+                        character: 10,
+                    },
+                },
+            },
+        };
+        let group = RelatedGroup::new(
+            RelationKind::References,
+            RelatedSource::Cache,
+            vec![
+                item("file:///src/main.rs", 8),
+                item("file:///src/lib.rs", 3),
+                item("file:///src/main.rs", 8),
+            ],
+        );
+        let lines: Vec<_> = group
+            .items
+            .iter()
+            .map(|item| (item.location.uri.as_str(), item.location.range.start.line))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![("file:///src/lib.rs", 3), ("file:///src/main.rs", 8)]
+        );
+
+        let response = DaemonResponse::Related {
+            request_id: Uuid::new_v4(),
+            symbol: None,
+            groups: vec![
+                group,
+                RelatedGroup::failed(RelationKind::TypeDefinition, "unsupported".to_string()),
+            ],
+            warnings: None,
+        };
+        let encoded = MessageCodec::encode_response(&response).expect("encode");
+        match MessageCodec::decode_response(&encoded).expect("decode") {
+            DaemonResponse::Related { groups, .. } => {
+                assert_eq!(groups[0].source, RelatedSource::Cache);
+                assert_eq!(groups[0].items.len(), 2);
+                assert_eq!(groups[1].kind, RelationKind::TypeDefinition);
+                assert_eq!(groups[1].error.as_deref(), Some("unsupported"));
+            }
+            _ => panic!("expected Related"),
+        }
+    }
+
     #[test]
     fn test_get_logs_request_with_sequence() {
         // Test GetLogs request with sequence parameter
@@ -2455,6 +3486,183 @@ mod tests {
         assert!(result.incoming.is_empty());
         assert!(result.outgoing.is_empty());
     }
+
+    fn test_location(i: u32) -> Location {
+        Location {
+            uri: format!("file:///src/module_{}.rs", i % 97),
+            range: Range {
+                start: Position {
+                    line: i,
+                    character: 4,
+                },
+                end: Position {
+                    line: i,
+                    character: 12,
+                },
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_large_references_response_is_streamed_in_chunks() {
+        let request_id = Uuid::new_v4();
+        let response = DaemonResponse::References {
+            request_id,
+            locations: (0..10_000).map(test_location).collect(),
+            warnings: Some(vec!["partial index".to_string()]),
+        };
+
+        let (mut client, mut daemon) = tokio::io::duplex(64 * 1024);
+        let writer = tokio::spawn(async move {
+            for frame in into_stream_frames(response, 1_000) {
+                let data = serde_json::to_vec(&frame).unwrap();
+                MessageCodec::write_framed(&mut daemon, &data, Duration::from_secs(5))
+                    .await
+                    .unwrap();
+            }
+        });
+
+        // Each frame is delivered and decoded separately, in order, until the done frame
+        let mut assembler = StreamAssembler::new();
+        let mut sequences = Vec::new();
+        let assembled = loop {
+            let data = MessageCodec::read_framed(&mut client, Duration::from_secs(5))
+                .await
+                .unwrap();
+            let frame: DaemonResponse = serde_json::from_slice(&data).unwrap();
+            match &frame {
+                DaemonResponse::StreamChunk {
+                    request_id: id,
+                    sequence,
+                    items,
+                } => {
+                    assert_eq!(*id, request_id);
+                    assert_eq!(items.len(), 1_000);
+                    sequences.push(*sequence);
+                }
+                DaemonResponse::StreamEnd {
+                    chunks,
+                    total_items,
+                    ..
+                } => {
+                    assert_eq!(*chunks, 10);
+                    assert_eq!(*total_items, 10_000);
+                }
+                other => panic!("Unexpected frame: {other:?}"),
+            }
+            if let Some(response) = assembler.push(frame).unwrap() {
+                break response;
+            }
+        };
+        writer.await.unwrap();
+
+        assert_eq!(sequences, (0..10).collect::<Vec<u32>>());
+        match assembled {
+            DaemonResponse::References {
+                request_id: id,
+                locations,
+                warnings,
+            } => {
+                assert_eq!(id, request_id);
+                assert_eq!(locations.len(), 10_000);
+                for (i, location) in locations.iter().enumerate() {
+                    assert_eq!(location.range.start.line, i as u32);
+                }
+                assert_eq!(warnings, Some(vec!["partial index".to_string()]));
+            }
+            other => panic!("Expected References, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_response_stream_handles_empty_and_non_stream_responses() {
+        let request_id = Uuid::new_v4();
+        let (mut client, mut daemon) = tokio::io::duplex(64 * 1024);
+        let frames: Vec<_> = into_stream_frames(
+            DaemonResponse::WorkspaceSymbols {
+                request_id,
+                symbols: vec![],
+                warnings: None,
+            },
+            100,
+        )
+        .collect();
+        // An empty result still sends one chunk so its kind is known
+        assert_eq!(frames.len(), 2);
+        let error = DaemonResponse::Error {
+            request_id,
+            error: "boom".to_string(),
+        };
+        assert_eq!(into_stream_frames(error.clone(), 100).count(), 1);
+
+        for frame in frames.into_iter().chain(std::iter::once(error)) {
+            let data = serde_json::to_vec(&frame).unwrap();
+            MessageCodec::write_framed(&mut daemon, &data, Duration::from_secs(5))
+                .await
+                .unwrap();
+        }
+
+        let timeout = Duration::from_secs(5);
+        match MessageCodec::read_response_stream(&mut client, timeout)
+            .await
+            .unwrap()
+        {
+            DaemonResponse::WorkspaceSymbols { symbols, .. } => assert!(symbols.is_empty()),
+            other => panic!("Expected WorkspaceSymbols, got {other:?}"),
+        }
+        match MessageCodec::read_response_stream(&mut client, timeout)
+            .await
+            .unwrap()
+        {
+            DaemonResponse::Error { error, .. } => assert_eq!(error, "boom"),
+            other => panic!("Expected Error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_stream_assembler_rejects_gaps_and_reports_cancellation() {
+        let request_id = Uuid::new_v4();
+        let mut frames: Vec<_> = into_stream_frames(
+            DaemonResponse::Implementations {
+                request_id,
+                locations: (0..5).map(test_location).collect(),
+                warnings: None,
+            },
+            2,
+        )
+        .collect();
+        assert_eq!(frames.len(), 4);
+
+        let mut assembler = StreamAssembler::new();
+        assembler.push(frames.remove(0)).unwrap();
+        assert!(assembler.push(frames.remove(1)).is_err());
+
+        let mut assembler = StreamAssembler::new();
+        assembler
+            .push(
+                into_stream_frames(
+                    DaemonResponse::References {
+                        request_id,
+                        locations: vec![test_location(0)],
+                        warnings: None,
+                    },
+                    1,
+                )
+                .next()
+                .unwrap(),
+            )
+            .unwrap();
+        let cancelled = assembler
+            .push(DaemonResponse::StreamEnd {
+                request_id,
+                chunks: 1,
+                total_items: 1,
+                cancelled: true,
+                warnings: None,
+            })
+            .unwrap();
+        assert!(matches!(cancelled, Some(DaemonResponse::Error { .. })));
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1,6 +1,6 @@
 use crate::language_detector::Language;
 use crate::lsp_registry::LspServerConfig;
-use crate::lsp_server::LspServer;
+use crate::lsp_server::{LspServer, ServerFailure};
 use crate::protocol::{ServerReadinessInfo, WorkspaceInfo};
 use crate::workspace_utils;
 use crate::workspace_utils::discover_language_roots;
@@ -54,6 +54,10 @@ impl ServerHealth {
         }
     }
 
+    fn mark_unhealthy(&self) {
+        self.is_healthy.store(false, Ordering::Relaxed);
+    }
+
     fn is_healthy(&self) -> bool {
         self.is_healthy.load(Ordering::Relaxed)
     }
@@ -758,10 +762,18 @@ impl SingleServerManager {
     where
         F: std::future::Future<Output = Result<T>>,
     {
-        // Check circuit breaker - fail fast if server is unhealthy
+        // Check circuit breaker - fail fast if server is unhealthy. Each rejected request
+        // counts as another failure of the server, so it is restarted once it stays down.
         if !self.is_server_healthy(language) {
             let health = self.get_language_health(language);
             let failures = health.get_consecutive_failures();
+            if self.restart_after_errors(language).await {
+                return Err(anyhow!(
+                    "Server for {:?} was unhealthy ({} consecutive failures) and has been restarted",
+                    language,
+                    failures
+                ));
+            }
             return Err(anyhow!(
                 "Server for {:?} is unhealthy ({} consecutive failures). Failing fast.",
                 language,
@@ -880,7 +892,11 @@ impl SingleServerManager {
                 Ok(result)
             }
             Err(err) => {
-                health.record_failure(self.concurrency_config.max_consecutive_failures);
+                // Requests the server merely rejected say nothing about its state
+                let server_failed = ServerFailure::caused(&err);
+                if server_failed {
+                    health.record_failure(self.concurrency_config.max_consecutive_failures);
+                }
                 let failures = health.get_consecutive_failures();
                 warn!(
                     "LSP operation failed for {:?} ({} consecutive failures): {}",
@@ -914,6 +930,9 @@ impl SingleServerManager {
                     });
                 }
 
+                if server_failed {
+                    self.restart_after_errors(language).await;
+                }
                 Err(err)
             }
         };
@@ -1154,18 +1173,55 @@ impl SingleServerManager {
         Ok(())
     }
 
+    /// Count a request that missed its deadline against the language's server. One miss
+    /// may just be a slow request, so the server is only restarted once misses and other
+    /// errors reach the rate configured on the process monitor. Returns whether the server
+    /// was restarted.
+    pub async fn record_missed_deadline(&self, language: Language) -> bool {
+        warn!("{:?} server did not respond before a deadline", language);
+        self.restart_after_errors(language).await
+    }
+
+    /// Count a failure of the language's server (a broken connection, crash or timeout)
+    /// and restart the server, with its workspaces and a clean health record, once its
+    /// failures reach the rate configured on the process monitor. Returns whether the
+    /// server was restarted.
+    async fn restart_after_errors(&self, language: Language) -> bool {
+        if !self.process_monitor.record_error(language.as_str()) {
+            return false;
+        }
+        warn!(
+            "{:?} server keeps failing; restarting it (restart #{})",
+            language,
+            self.process_monitor.restart_count(language.as_str())
+        );
+        if let Err(e) = self.restart_server(language).await {
+            warn!("Failed to restart failing {:?} server: {}", language, e);
+        }
+        self.language_health.remove(&language);
+        true
+    }
+
+    /// Number of automatic restarts of the language's server after repeated failures
+    pub fn restart_count(&self, language: Language) -> u32 {
+        self.process_monitor.restart_count(language.as_str())
+    }
+
     /// Restart a server (simple restart without health checking)
     pub async fn restart_server(&self, language: Language) -> Result<()> {
         warn!("Restarting server for {:?}", language);
 
         // Remove the server from our map
         let mut bootstrap_ws: Option<PathBuf> = None;
+        let mut workspaces: Vec<PathBuf> = Vec::new();
         if let Some((_, server_instance)) = self.servers.remove(&language) {
             // Try to shutdown gracefully and capture bootstrap workspace
             match tokio::time::timeout(Duration::from_secs(2), server_instance.lock()).await {
                 Ok(server) => {
                     // Remember the workspace we bootstrapped with so we can respawn immediately.
                     bootstrap_ws = server.bootstrap_workspace.clone();
+                    workspaces = server.registered_workspaces.iter().cloned().collect();
+                    workspaces.sort();
                     if let Err(e) = server.server.shutdown().await {
                         warn!(
                             "Error shutting down {:?} server during restart: {}",
@@ -1189,17 +1245,30 @@ impl SingleServerManager {
             );
         }
 
-        // If we know a bootstrap workspace, spawn a fresh instance *now*.
-        if let Some(ws) = bootstrap_ws {
+        // If we know a bootstrap workspace, spawn a fresh instance *now* and give it back
+        // every workspace the old instance served.
+        if let Some(ws) = bootstrap_ws.or_else(|| workspaces.first().cloned()) {
             info!(
                 "Spawning fresh {:?} server using bootstrap workspace {:?}",
                 language, ws
             );
-            if let Err(e) = self.ensure_workspace_registered(language, ws).await {
+            if let Err(e) = self.ensure_workspace_registered(language, ws.clone()).await {
                 warn!(
                     "Failed to spawn fresh {:?} server after restart: {}",
                     language, e
                 );
+            } else {
+                for workspace in workspaces.into_iter().filter(|w| *w != ws) {
+                    if let Err(e) = self
+                        .ensure_workspace_registered_core(language, workspace.clone())
+                        .await
+                    {
+                        warn!(
+                            "Failed to re-register {:?} with restarted {:?} server: {}",
+                            workspace, language, e
+                        );
+                    }
+                }
             }
         } else {
             info!(
@@ -2054,6 +2123,103 @@ impl SingleServerManager {
         self.servers.len()
     }
 
+    /// List running server processes with their pid, workspaces, uptime, memory usage and
+    /// automatic restart count
+    pub async fn list_servers(&self) -> Vec<crate::protocol::LspServerProcessInfo> {
+        // Clone handles first so no DashMap guard is held across .await
+        let instances: Vec<(Language, Arc<Mutex<ServerInstance>>)> = self
+            .servers
+            .iter()
+            .map(|entry| (*entry.key(), entry.value().clone()))
+            .collect();
+
+        let mut servers = Vec::with_capacity(instances.len());
+        for (language, server_instance) in instances {
+            // Use non-blocking try_lock so a server busy initializing doesn't hang the listing
+            let (pid, workspaces, uptime_secs) = match server_instance.try_lock() {
+                Ok(server) => {
+                    let mut workspaces: Vec<PathBuf> =
+                        server.registered_workspaces.iter().cloned().collect();
+                    workspaces.sort();
+                    (
+                        server.server.get_pid(),
+                        workspaces,
+                        server.start_time.elapsed().as_secs(),
+                    )
+                }
+                Err(_) => {
+                    debug!("Server {:?} is busy, listing without details", language);
+                    (None, Vec::new(), 0)
+                }
+            };
+
+            let memory_mb = match pid {
+                Some(pid) => self
+                    .process_monitor
+                    .get_instance_stats(language.as_str(), pid)
+                    .await
+                    .ok()
+                    .map(|stats| stats.memory_mb),
+                None => None,
+            };
+
+            servers.push(crate::protocol::LspServerProcessInfo {
+                id: language.as_str().to_string(),
+                language,
+                pid,
+                workspaces,
+                uptime_secs,
+                memory_mb,
+                restarts: self.process_monitor.restart_count(language.as_str()),
+            });
+        }
+
+        servers.sort_by(|a, b| a.id.cmp(&b.id));
+        servers
+    }
+
+    /// Shut down the server with the given id and drop it from the pool.
+    ///
+    /// Returns the pid of the killed process, or an error when no server with that id
+    /// is running. The next request for the language spawns a fresh server.
+    pub async fn kill_server(&self, id: &str) -> Result<Option<u32>> {
+        let language = self
+            .servers
+            .iter()
+            .map(|entry| *entry.key())
+            .find(|language| language.as_str().eq_ignore_ascii_case(id))
+            .ok_or_else(|| anyhow!("No running LSP server with id '{}'", id))?;
+
+        let Some((_, server_instance)) = self.servers.remove(&language) else {
+            return Err(anyhow!("No running LSP server with id '{}'", id));
+        };
+
+        warn!("Killing {:?} server on request", language);
+        let mut killed_pid = None;
+        match tokio::time::timeout(Duration::from_secs(2), server_instance.lock()).await {
+            Ok(server) => {
+                killed_pid = server.server.get_pid();
+                if let Err(e) = server.server.shutdown().await {
+                    warn!("Error shutting down {:?} server: {}", language, e);
+                } else {
+                    info!("Killed {:?} server", language);
+                }
+            }
+            Err(_) => {
+                warn!(
+                    "Timeout acquiring lock for {:?} server during kill; instance dropped from pool",
+                    language
+                );
+            }
+        }
+
+        if let Some(pid) = killed_pid {
+            self.child_processes.lock().await.retain(|&p| p != pid);
+        }
+
+        Ok(killed_pid)
+    }
+
     pub async fn get_all_workspaces(&self) -> Vec<WorkspaceInfo> {
         let mut workspaces = Vec::new();
 
@@ -2736,6 +2902,182 @@ mod tests {
         );
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_list_and_kill_server_by_id() {
+        let registry = Arc::new(crate::lsp_registry::LspRegistry::new().expect("registry"));
+        let manager = SingleServerManager::new(registry);
+
+        // `cat` stands in for a language server: it runs until it is killed
+        let config = LspServerConfig {
+            language: Language::Rust,
+            command: "cat".to_string(),
+            ..Default::default()
+        };
+        let server = LspServer::spawn(&config).expect("spawn stand-in server");
+        let mut instance = ServerInstance::new(server);
+        instance.add_workspace(PathBuf::from("/test/workspace"));
+        manager
+            .servers
+            .insert(Language::Rust, Arc::new(Mutex::new(instance)));
+
+        let servers = manager.list_servers().await;
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].id, "rust");
+        assert_eq!(servers[0].language, Language::Rust);
+        assert_eq!(
+            servers[0].workspaces,
+            vec![PathBuf::from("/test/workspace")]
+        );
+        let pid = servers[0]
+            .pid
+            .expect("running server should report its pid");
+
+        let killed = manager.kill_server("rust").await.expect("kill by id");
+        assert_eq!(killed, Some(pid));
+        assert!(manager.list_servers().await.is_empty());
+
+        assert!(
+            manager.kill_server("rust").await.is_err(),
+            "Killing an unknown id should fail"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_server_missing_deadlines_repeatedly_is_replaced() {
+        let registry = Arc::new(crate::lsp_registry::LspRegistry::new().expect("registry"));
+        let manager = Arc::new(SingleServerManager::new(registry));
+
+        // `sleep` stands in for a hung language server: it never answers a request
+        let config = LspServerConfig {
+            language: Language::Rust,
+            command: "sleep".to_string(),
+            args: vec!["60".to_string()],
+            ..Default::default()
+        };
+        let server = LspServer::spawn(&config).expect("spawn stand-in server");
+        manager.servers.insert(
+            Language::Rust,
+            Arc::new(Mutex::new(ServerInstance::new(server))),
+        );
+
+        let hung = manager.servers.get(&Language::Rust).unwrap().clone();
+        let request = async move {
+            let instance = hung.lock().await;
+            instance
+                .server
+                .hover(Path::new("/test/workspace/lib.rs"), 0, 0)
+                .await
+        };
+        assert!(
+            tokio::time::timeout(Duration::from_millis(200), request)
+                .await
+                .is_err(),
+            "The stand-in server should miss the deadline"
+        );
+
+        let threshold = crate::watchdog::ErrorRestartConfig::default().error_threshold;
+        for _ in 1..threshold {
+            assert!(!manager.record_missed_deadline(Language::Rust).await);
+        }
+        assert_eq!(
+            manager.list_servers().await.len(),
+            1,
+            "A few missed deadlines should not restart the server"
+        );
+
+        assert!(manager.record_missed_deadline(Language::Rust).await);
+        assert!(
+            manager.list_servers().await.is_empty(),
+            "The hung instance should be shut down"
+        );
+        assert_eq!(manager.process_monitor().restart_count("rust"), 1);
+    }
+
+    #[tokio::test]
+    async fn test_only_server_failures_count_toward_restarts() {
+        let registry = Arc::new(crate::lsp_registry::LspRegistry::new().expect("registry"));
+        let manager = SingleServerManager::new(registry);
+        let threshold = crate::watchdog::ErrorRestartConfig::default().error_threshold;
+
+        for _ in 0..threshold * 2 {
+            let result: Result<()> = manager
+                .execute_with_semaphore(Language::Rust, async {
+                    Err(anyhow!("Definition request failed: invalid position"))
+                })
+                .await;
+            assert!(result.is_err());
+        }
+        assert!(
+            manager.is_server_healthy(Language::Rust),
+            "Rejected requests should not mark the server unhealthy"
+        );
+        assert_eq!(manager.restart_count(Language::Rust), 0);
+
+        for _ in 0..threshold {
+            let result: Result<()> = manager
+                .execute_with_semaphore(Language::Go, async {
+                    Err(anyhow::Error::from(ServerFailure::Closed).context("Definition failed"))
+                })
+                .await;
+            assert!(result.is_err());
+        }
+        assert_eq!(manager.restart_count(Language::Go), 1);
+        assert!(manager.is_server_healthy(Language::Go));
+    }
+
+    #[tokio::test]
+    async fn test_unhealthy_server_is_restarted_while_failing_fast() {
+        let registry = Arc::new(crate::lsp_registry::LspRegistry::new().expect("registry"));
+        let mut manager = SingleServerManager::new(registry);
+        // A threshold above the consecutive failures that mark a server unhealthy
+        let max_failures = manager.concurrency_config.max_consecutive_failures;
+        manager.process_monitor = Arc::new(ProcessMonitor::new().with_error_restart(
+            crate::watchdog::ErrorRestartConfig {
+                error_threshold: max_failures + 3,
+                error_window: Duration::from_secs(60),
+                restart_cooldown: Duration::from_secs(60),
+            },
+        ));
+
+        // Server failures mixed with rejections until the server is marked unhealthy
+        let mut requests = 0;
+        while manager.is_server_healthy(Language::Rust) {
+            let error = if requests % 2 == 0 {
+                anyhow::Error::from(ServerFailure::Closed)
+            } else {
+                anyhow!("Hover request failed: no symbol")
+            };
+            let _: Result<()> = manager
+                .execute_with_semaphore(Language::Rust, async { Err(error) })
+                .await;
+            requests += 1;
+        }
+        assert_eq!(manager.restart_count(Language::Rust), 0);
+
+        // Requests failing fast keep counting toward the restart
+        for _ in 0..2 {
+            let result: Result<()> = manager
+                .execute_with_semaphore(Language::Rust, async { Ok(()) })
+                .await;
+            assert!(result.is_err());
+        }
+        assert_eq!(manager.restart_count(Language::Rust), 0);
+        let result: Result<()> = manager
+            .execute_with_semaphore(Language::Rust, async { Ok(()) })
+            .await;
+        assert!(result.is_err());
+        assert_eq!(manager.restart_count(Language::Rust), 1);
+
+        // The restarted server starts with a clean health record
+        assert!(manager.is_server_healthy(Language::Rust));
+        let result: Result<()> = manager
+            .execute_with_semaphore(Language::Rust, async { Ok(()) })
+            .await;
+        assert!(result.is_ok());
+    }
+
     // Additional tests can be added here for more complex error handling scenarios
     // when proper mocking infrastructure is in place
 }
@@ -1,8 +1,9 @@
 use anyhow::Result;
+use std::collections::{HashMap, VecDeque};
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::sync::Mutex;
-use tokio::time::{interval, Duration};
+use tokio::time::{interval, Duration, Instant};
 use tracing::{debug, error, info, warn};
 
 /// Type alias for recovery callback to avoid complex type warning
@@ -132,6 +133,41 @@ impl Watchdog {
     }
 }
 
+/// When a server instance that keeps returning errors is restarted automatically
+#[derive(Debug, Clone)]
+pub struct ErrorRestartConfig {
+    /// Errors within `error_window` that trigger a restart (0 disables automatic restarts)
+    pub error_threshold: u32,
+    /// Window in which errors are counted
+    pub error_window: Duration,
+    /// Minimum time between two automatic restarts of the same instance
+    pub restart_cooldown: Duration,
+}
+
+impl Default for ErrorRestartConfig {
+    fn default() -> Self {
+        let env_u64 = |name: &str, default: u64| {
+            std::env::var(name)
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(default)
+        };
+        Self {
+            error_threshold: env_u64("PROBE_LSP_RESTART_ERROR_THRESHOLD", 5) as u32,
+            error_window: Duration::from_secs(env_u64("PROBE_LSP_RESTART_ERROR_WINDOW_SECS", 60)),
+            restart_cooldown: Duration::from_secs(env_u64("PROBE_LSP_RESTART_COOLDOWN_SECS", 60)),
+        }
+    }
+}
+
+/// Recent errors and automatic restarts of one server instance
+#[derive(Debug, Default)]
+struct InstanceErrors {
+    errors: VecDeque<Instant>,
+    last_restart: Option<Instant>,
+    restarts: u32,
+}
+
 /// Monitor LSP server process resource usage  
 #[derive(Debug)]
 pub struct ProcessMonitor {
@@ -141,6 +177,10 @@ pub struct ProcessMonitor {
     max_memory_mb: u64,
     /// Timeout for getting process stats
     stats_timeout: Duration,
+    /// Error rate at which server instances are restarted
+    error_restart: ErrorRestartConfig,
+    /// Error tracking per server instance id
+    instances: std::sync::Mutex<HashMap<String, InstanceErrors>>,
 }
 
 impl Default for ProcessMonitor {
@@ -151,11 +191,7 @@ impl Default for ProcessMonitor {
 
 impl ProcessMonitor {
     pub fn new() -> Self {
-        Self {
-            max_cpu_percent: 80.0,
-            max_memory_mb: 1024, // 1GB default
-            stats_timeout: Duration::from_secs(5),
-        }
+        Self::with_limits(80.0, 1024) // 1GB default
     }
 
     pub fn with_limits(max_cpu_percent: f32, max_memory_mb: u64) -> Self {
@@ -163,9 +199,75 @@ impl ProcessMonitor {
             max_cpu_percent,
             max_memory_mb,
             stats_timeout: Duration::from_secs(5),
+            error_restart: ErrorRestartConfig::default(),
+            instances: std::sync::Mutex::new(HashMap::new()),
         }
     }
 
+    /// Use `config` instead of the environment to decide when instances are restarted
+    pub fn with_error_restart(mut self, config: ErrorRestartConfig) -> Self {
+        self.error_restart = config;
+        self
+    }
+
+    /// Record a failed request to a server instance. Returns true when the instance has
+    /// reached the error threshold within the window and is out of its restart cooldown;
+    /// the restart is counted and its error window cleared, so concurrent failures claim
+    /// at most one restart and the caller that gets true should restart the instance.
+    pub fn record_error(&self, instance: &str) -> bool {
+        let config = &self.error_restart;
+        if config.error_threshold == 0 {
+            return false;
+        }
+
+        let now = Instant::now();
+        let mut instances = self.instances.lock().unwrap_or_else(|e| e.into_inner());
+        let entry = instances.entry(instance.to_string()).or_default();
+        while let Some(&oldest) = entry.errors.front() {
+            if now.duration_since(oldest) <= config.error_window {
+                break;
+            }
+            entry.errors.pop_front();
+        }
+        entry.errors.push_back(now);
+
+        if entry.errors.len() < config.error_threshold as usize {
+            return false;
+        }
+        if let Some(last_restart) = entry.last_restart {
+            if now.duration_since(last_restart) < config.restart_cooldown {
+                debug!(
+                    "{} reached {} errors but was restarted {:?} ago; waiting for cooldown",
+                    instance,
+                    entry.errors.len(),
+                    now.duration_since(last_restart)
+                );
+                return false;
+            }
+        }
+
+        entry.errors.clear();
+        entry.last_restart = Some(now);
+        entry.restarts += 1;
+        true
+    }
+
+    /// Number of automatic restarts of a server instance
+    pub fn restart_count(&self, instance: &str) -> u32 {
+        self.instances
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .get(instance)
+            .map_or(0, |entry| entry.restarts)
+    }
+
+    /// Get statistics for the process of a server instance, including its restart count
+    pub async fn get_instance_stats(&self, instance: &str, pid: u32) -> Result<ProcessStats> {
+        let mut stats = self.get_process_stats(pid).await?;
+        stats.restart_count = self.restart_count(instance);
+        Ok(stats)
+    }
+
     /// Check if a process is within resource limits
     /// Returns true if healthy, false if exceeding limits
     pub async fn check_process_health(&self, pid: u32) -> Result<ProcessHealth> {
@@ -253,7 +355,7 @@ impl ProcessMonitor {
     }
 
     /// Get process statistics
-    async fn get_process_stats(&self, pid: u32) -> Result<ProcessStats> {
+    pub async fn get_process_stats(&self, pid: u32) -> Result<ProcessStats> {
         // Use procfs on Linux/Unix or similar approach
         #[cfg(target_os = "linux")]
         {
@@ -276,6 +378,7 @@ impl ProcessMonitor {
                 cpu_percent: 0.0,
                 memory_mb: 0,
                 running: true,
+                restart_count: 0,
             })
         }
     }
@@ -317,6 +420,7 @@ impl ProcessMonitor {
             cpu_percent,
             memory_mb,
             running: true,
+            restart_count: 0,
         })
     }
 
@@ -358,6 +462,7 @@ impl ProcessMonitor {
             cpu_percent,
             memory_mb,
             running: true,
+            restart_count: 0,
         })
     }
 
@@ -371,6 +476,7 @@ impl ProcessMonitor {
             cpu_percent: 0.0,
             memory_mb: 0,
             running: true,
+            restart_count: 0,
         })
     }
 }
@@ -381,6 +487,8 @@ pub struct ProcessStats {
     pub cpu_percent: f32,
     pub memory_mb: u64,
     pub running: bool,
+    /// Automatic restarts of the server instance (set by `get_instance_stats`)
+    pub restart_count: u32,
 }
 
 #[derive(Debug, Clone)]
@@ -446,6 +554,7 @@ mod tests {
             cpu_percent: 25.5,
             memory_mb: 256,
             running: true,
+            restart_count: 0,
         };
 
         assert_eq!(stats.pid, 1234);
@@ -461,6 +570,7 @@ mod tests {
             cpu_percent: 90.0, // High CPU
             memory_mb: 256,
             running: true,
+            restart_count: 0,
         };
 
         let health = ProcessHealth {
@@ -476,6 +586,44 @@ mod tests {
         assert!(!health.exceeds_memory_limit);
     }
 
+    #[test]
+    fn test_record_error_restarts_once_per_cooldown() {
+        let monitor = ProcessMonitor::new().with_error_restart(ErrorRestartConfig {
+            error_threshold: 3,
+            error_window: Duration::from_secs(60),
+            restart_cooldown: Duration::from_secs(60),
+        });
+
+        assert!(!monitor.record_error("rust"));
+        assert!(!monitor.record_error("rust"));
+        assert!(monitor.record_error("rust"));
+        assert_eq!(monitor.restart_count("rust"), 1);
+
+        // Flapping within the cooldown does not restart again
+        for _ in 0..5 {
+            assert!(!monitor.record_error("rust"));
+        }
+        assert_eq!(monitor.restart_count("rust"), 1);
+
+        // Instances are tracked separately
+        assert!(!monitor.record_error("go"));
+        assert_eq!(monitor.restart_count("go"), 0);
+    }
+
+    #[test]
+    fn test_record_error_disabled_by_zero_threshold() {
+        let monitor = ProcessMonitor::new().with_error_restart(ErrorRestartConfig {
+            error_threshold: 0,
+            error_window: Duration::from_secs(60),
+            restart_cooldown: Duration::ZERO,
+        });
+
+        for _ in 0..10 {
+            assert!(!monitor.record_error("rust"));
+        }
+        assert_eq!(monitor.restart_count("rust"), 0);
+    }
+
     #[tokio::test]
     async fn test_watchdog_recovery_callback() {
         let watchdog = Watchdog::new(60);
@@ -19,6 +19,7 @@ const QUERY_FLAG_MAP = {
 	ignore: '--ignore',
 	allowTests: '--allow-tests',
 	withContext: '--with-context',
+	sexp: '--sexp',
 	maxResults: '--max-results',
 	format: '--format'
 };
@@ -34,6 +35,7 @@ const QUERY_FLAG_MAP = {
  * @param {string[]} [options.ignore] - Patterns to ignore
  * @param {boolean} [options.allowTests] - Include test files
  * @param {boolean} [options.withContext] - Include owning source-block context in JSON output
+ * @param {boolean} [options.sexp] - Include the tree-sitter S-expression of each matched node
  * @param {number} [options.maxResults] - Maximum number of results
  * @param {string} [options.format] - Output format ('markdown', 'plain', 'json', 'color')
  * @param {Object} [options.binaryOptions] - Options for getting the binary
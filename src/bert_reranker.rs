@@ -14,8 +14,14 @@ use candle_transformers::models::bert::{BertModel, Config, DTYPE};
 #[cfg(feature = "bert-reranker")]
 use hf_hub::{api::tokio::Api, Repo, RepoType};
 #[cfg(feature = "bert-reranker")]
+use lru::LruCache;
+#[cfg(feature = "bert-reranker")]
 use serde_json;
 #[cfg(feature = "bert-reranker")]
+use std::collections::HashMap;
+#[cfg(feature = "bert-reranker")]
+use std::num::NonZeroUsize;
+#[cfg(feature = "bert-reranker")]
 use std::path::Path;
 #[cfg(feature = "bert-reranker")]
 #[allow(unused_imports)]
@@ -24,9 +30,53 @@ use std::sync::atomic::{AtomicBool, AtomicUsize};
 #[allow(unused_imports)]
 use std::sync::Arc;
 #[cfg(feature = "bert-reranker")]
-use tokenizers::Tokenizer;
+use std::sync::{Mutex, OnceLock};
+#[cfg(feature = "bert-reranker")]
+use tokenizers::{Encoding, Tokenizer};
 
 use crate::models::SearchResult;
+#[cfg(feature = "bert-reranker")]
+use crate::search::cache::SessionScores;
+
+/// Number of top BM25 candidates rescored by a BERT reranker unless `--rerank-top-k` is given
+pub const DEFAULT_RERANK_TOP_K: usize = 50;
+
+/// Cross-encoder model used for a BERT reranker name, or `None` if `reranker` is not BERT-based.
+/// `bert` is an alias for the smallest model.
+pub fn model_for_reranker(reranker: &str) -> Option<&'static str> {
+    match reranker {
+        "bert" | "ms-marco-tinybert" => Some("cross-encoder/ms-marco-TinyBERT-L-2-v2"),
+        "ms-marco-minilm-l6" => Some("cross-encoder/ms-marco-MiniLM-L-6-v2"),
+        "ms-marco-minilm-l12" => Some("cross-encoder/ms-marco-MiniLM-L-12-v2"),
+        _ => None,
+    }
+}
+
+/// Models loaded so far in this process, keyed by model name. Loading reads (and on first use
+/// downloads) the weights, so each model is loaded once and shared by later searches.
+#[cfg(feature = "bert-reranker")]
+static LOADED_RERANKERS: OnceLock<Mutex<HashMap<String, Arc<ParallelBertReranker>>>> =
+    OnceLock::new();
+
+/// Get the reranker for `model_name`, loading it on first use
+#[cfg(feature = "bert-reranker")]
+async fn loaded_reranker(
+    model_name: &str,
+    num_threads: usize,
+) -> Result<Arc<ParallelBertReranker>> {
+    let loaded = LOADED_RERANKERS.get_or_init(Default::default);
+    if let Some(reranker) = loaded.lock().unwrap().get(model_name) {
+        return Ok(reranker.clone());
+    }
+
+    let reranker = Arc::new(ParallelBertReranker::new(model_name, Some(num_threads)).await?);
+    Ok(loaded
+        .lock()
+        .unwrap()
+        .entry(model_name.to_string())
+        .or_insert(reranker)
+        .clone())
+}
 
 #[cfg(feature = "bert-reranker")]
 pub struct BertReranker {
@@ -243,11 +293,16 @@ impl BertReranker {
     }
 
     fn score_pair(&self, query: &str, document: &str) -> Result<f32> {
-        // Truncate document if too long (keep query + document under max_length)
-        let max_doc_length = self
-            .max_length
-            .saturating_sub(query.len() / 4)
-            .saturating_sub(10); // rough estimate
+        let document = self.encode_document(document)?;
+        self.score_encoded(query, &document)
+    }
+
+    /// Tokenize the document side of a query-document pair. The result does not depend on
+    /// the query, so it can be reused when the same block is scored against other queries.
+    fn encode_document(&self, document: &str) -> Result<Encoding> {
+        // Truncate document if too long (rough estimate, independent of the query so the
+        // encoding can be cached; the joined pair is cut to max_length tokens anyway)
+        let max_doc_length = self.max_length.saturating_sub(10);
         let doc_truncated = if document.len() > max_doc_length {
             // Find a valid UTF-8 character boundary at or before max_doc_length
             let mut truncate_at = max_doc_length;
@@ -259,11 +314,22 @@ impl BertReranker {
             document
         };
 
-        // Prepare input for cross-encoder using encode_pair
-        // This ensures proper token type IDs are generated automatically
+        self.tokenizer
+            .encode(doc_truncated, false)
+            .map_err(|e| anyhow::anyhow!("Tokenization failed: {}", e))
+    }
+
+    /// Score a query against an already tokenized document
+    fn score_encoded(&self, query: &str, document: &Encoding) -> Result<f32> {
+        let query_encoding = self
+            .tokenizer
+            .encode(query, false)
+            .map_err(|e| anyhow::anyhow!("Tokenization failed: {}", e))?;
+
+        // Join the pair with the model's special tokens; this also generates the token type IDs
         let mut encoding = self
             .tokenizer
-            .encode((query, doc_truncated), true)
+            .post_process(query_encoding, Some(document.clone()), true)
             .map_err(|e| anyhow::anyhow!("Tokenization failed: {}", e))?;
 
         // Truncate if too long
@@ -358,6 +424,7 @@ pub async fn rerank_with_bert(
     queries: &[String],
     model_name: &str,
     question: Option<&str>,
+    session: Option<&str>,
 ) -> Result<(), anyhow::Error> {
     if results.is_empty() {
         return Ok(());
@@ -370,7 +437,7 @@ pub async fn rerank_with_bert(
         .unwrap_or(4)
         .min(8); // Cap at 8 threads to avoid overwhelming the system
 
-    let parallel_reranker = ParallelBertReranker::new(model_name, Some(num_threads)).await?;
+    let parallel_reranker = loaded_reranker(model_name, num_threads).await?;
 
     // Use the question if provided, otherwise join the queries
     let combined_query = if let Some(q) = question {
@@ -395,7 +462,8 @@ pub async fn rerank_with_bert(
     let documents_refs: Vec<&str> = documents.iter().map(|s| s.as_str()).collect();
 
     // Get BERT scores using parallel processing
-    let ranked_indices = parallel_reranker.rerank_parallel(&combined_query, &documents_refs)?;
+    let ranked_indices =
+        parallel_reranker.rerank_parallel(&combined_query, &documents_refs, session)?;
 
     // Update results with BERT scores and rankings
     for (rank_index, (original_index, bert_score)) in ranked_indices.iter().enumerate() {
@@ -458,16 +526,85 @@ pub async fn rerank_with_bert(
     _queries: &[String],
     _model_name: &str,
     _question: Option<&str>,
+    _session: Option<&str>,
 ) -> Result<(), anyhow::Error> {
     Err(anyhow::anyhow!(
         "BERT reranker is not available. Build with --features bert-reranker to enable."
     ))
 }
 
+/// Number of blocks cached per session unless `PROBE_BERT_CACHE_SIZE` is set
+#[cfg(feature = "bert-reranker")]
+const DEFAULT_BLOCK_CACHE_SIZE: usize = 1024;
+
+/// Encodings and scores of the blocks reranked in the current search session, keyed by a
+/// hash of the block text. Paginated and narrowing queries in a session rescore mostly the
+/// same blocks. Scores are stored on disk with the session, so pairs scored by an earlier
+/// `probe` invocation are not rescored; encodings only live as long as this process, where
+/// blocks seen before only have the query side tokenized again.
+#[cfg(feature = "bert-reranker")]
+struct SessionBlockCache {
+    session_id: Option<String>,
+    blocks: LruCache<u64, Encoding>,
+    scores: SessionScores,
+    scores_changed: bool,
+    hits: usize,
+    lookups: usize,
+}
+
+#[cfg(feature = "bert-reranker")]
+impl SessionBlockCache {
+    fn new() -> Self {
+        let cache_size = std::env::var("PROBE_BERT_CACHE_SIZE")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .and_then(NonZeroUsize::new)
+            .unwrap_or(NonZeroUsize::new(DEFAULT_BLOCK_CACHE_SIZE).unwrap());
+        Self {
+            session_id: None,
+            blocks: LruCache::new(cache_size),
+            scores: SessionScores::default(),
+            scores_changed: false,
+            hits: 0,
+            lookups: 0,
+        }
+    }
+
+    /// Make `session_id` the current session, dropping everything cached for a previous one
+    /// and loading the scores `model` gave earlier in this one
+    fn enter_session(&mut self, session_id: &str, model: &str) {
+        if self.session_id.as_deref() != Some(session_id) {
+            self.session_id = Some(session_id.to_string());
+            self.blocks.clear();
+            self.scores = SessionScores::load(session_id, model);
+            self.scores_changed = false;
+            self.hits = 0;
+            self.lookups = 0;
+        }
+    }
+
+    /// Store the scores added since the session was entered or last saved
+    fn save_scores(&mut self, model: &str) -> Result<()> {
+        if let Some(session_id) = self.session_id.as_deref().filter(|_| self.scores_changed) {
+            self.scores.save(session_id, model)?;
+            self.scores_changed = false;
+        }
+        Ok(())
+    }
+}
+
+/// Cache key for a query or block text
+#[cfg(feature = "bert-reranker")]
+fn text_key(text: &str) -> u64 {
+    ahash::RandomState::with_seeds(1, 2, 3, 4).hash_one(text)
+}
+
 #[cfg(feature = "bert-reranker")]
 pub struct ParallelBertReranker {
     engines: Vec<std::sync::Arc<parking_lot::Mutex<BertInferenceEngine>>>,
     num_threads: usize,
+    model_name: String,
+    session_cache: parking_lot::Mutex<SessionBlockCache>,
 }
 
 #[cfg(feature = "bert-reranker")]
@@ -485,6 +622,45 @@ impl BertInferenceEngine {
     pub fn score_pair(&self, query: &str, document: &str) -> Result<f32> {
         self.reranker.score_pair(query, document)
     }
+
+    /// Score a query-document pair, reusing the document's encoding and any earlier score for
+    /// the same pair from the session cache
+    fn score_in_session(
+        &self,
+        query: &str,
+        document: &str,
+        session_cache: &parking_lot::Mutex<SessionBlockCache>,
+    ) -> Result<f32> {
+        let block_key = text_key(document);
+        let pair_key = format!("{:x}:{block_key:x}", text_key(query));
+
+        let cached_encoding = {
+            let mut guard = session_cache.lock();
+            let cache = &mut *guard;
+            cache.lookups += 1;
+            if let Some(score) = cache.scores.scores.get(&pair_key) {
+                cache.hits += 1;
+                return Ok(*score);
+            }
+            let encoding = cache.blocks.get(&block_key).cloned();
+            if encoding.is_some() {
+                cache.hits += 1;
+            }
+            encoding
+        };
+
+        let encoding = match cached_encoding {
+            Some(encoding) => encoding,
+            None => self.reranker.encode_document(document)?,
+        };
+        let score = self.reranker.score_encoded(query, &encoding)?;
+
+        let mut cache = session_cache.lock();
+        cache.blocks.put(block_key, encoding);
+        cache.scores.scores.insert(pair_key, score);
+        cache.scores_changed = true;
+        Ok(score)
+    }
 }
 
 #[cfg(feature = "bert-reranker")]
@@ -519,10 +695,20 @@ impl ParallelBertReranker {
         Ok(Self {
             engines,
             num_threads,
+            model_name: model_name.to_string(),
+            session_cache: parking_lot::Mutex::new(SessionBlockCache::new()),
         })
     }
 
-    pub fn rerank_parallel(&self, query: &str, documents: &[&str]) -> Result<Vec<(usize, f32)>> {
+    /// Score `documents` against `query` in parallel. With a `session`, block encodings are
+    /// cached for later calls in this process, and scores are stored with the session for later
+    /// calls in any process.
+    pub fn rerank_parallel(
+        &self,
+        query: &str,
+        documents: &[&str],
+        session: Option<&str>,
+    ) -> Result<Vec<(usize, f32)>> {
         use rayon::prelude::*;
         use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
         use std::sync::Arc;
@@ -602,6 +788,13 @@ impl ParallelBertReranker {
             eprintln!("DEBUG: About to start parallel processing of {total_docs} documents");
         }
 
+        let session_cache = session.map(|session_id| {
+            self.session_cache
+                .lock()
+                .enter_session(session_id, &self.model_name);
+            &self.session_cache
+        });
+
         // Create chunks for parallel processing - each chunk is processed by a dedicated engine
         let chunk_size = total_docs.div_ceil(self.engines.len());
         let chunks: Vec<_> = documents
@@ -647,9 +840,11 @@ impl ParallelBertReranker {
                 let engine_guard = engine.lock();
 
                 for (doc_idx, document) in chunk {
-                    let score = engine_guard
-                        .score_pair(&query, document)
-                        .with_context(|| format!("Failed to score document {doc_idx}"))?;
+                    let score = match session_cache {
+                        Some(cache) => engine_guard.score_in_session(&query, document, cache),
+                        None => engine_guard.score_pair(&query, document),
+                    }
+                    .with_context(|| format!("Failed to score document {doc_idx}"))?;
                     chunk_results.push((doc_idx, score));
 
                     // Update progress counter
@@ -703,6 +898,32 @@ impl ParallelBertReranker {
             );
         }
 
+        // Store the new scores for later searches in the session, in this process or another
+        if let Some(cache) = session_cache {
+            if let Err(e) = cache.lock().save_scores(&self.model_name) {
+                if debug_mode {
+                    println!("DEBUG: Failed to store BERT scores for the session: {e}");
+                }
+            }
+        }
+
+        if let Some(cache) = session_cache.filter(|_| debug_mode) {
+            let cache = cache.lock();
+            let hit_rate = if cache.lookups > 0 {
+                cache.hits as f64 / cache.lookups as f64 * 100.0
+            } else {
+                0.0
+            };
+            println!(
+                "DEBUG: BERT block cache: {}/{} lookups hit ({:.1}%), {} blocks cached and {} scores stored for this session",
+                cache.hits,
+                cache.lookups,
+                hit_rate,
+                cache.blocks.len(),
+                cache.scores.scores.len()
+            );
+        }
+
         // Sort by score descending
         all_scores.sort_by(|a, b| b.1.partial_cmp(&a.1).unwrap_or(std::cmp::Ordering::Equal));
 
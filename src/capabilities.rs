@@ -0,0 +1,125 @@
+//! Machine-readable manifest of supported languages and what probe can do with them.
+//!
+//! Language support is spread across the tree-sitter parsers used for search and
+//! extraction (`language`), the ast-grep backed `query` command, the daemon's symbol
+//! analyzers (`lsp_daemon::analyzer`) and the LSP server registry
+//! (`lsp_daemon::lsp_registry`). This module consolidates them into a single report.
+
+use lsp_daemon::analyzer::{AnalyzerManager, LanguageAnalyzerFactory};
+use lsp_daemon::symbol::SymbolUIDGenerator;
+use lsp_daemon::{Language, LspRegistry};
+use serde::Serialize;
+use std::sync::Arc;
+
+use crate::language::factory::{builtin_languages, get_language_impl};
+
+/// Capabilities of a single language
+#[derive(Debug, Clone, Serialize)]
+pub struct LanguageCapabilities {
+    /// Canonical language name (as accepted by `--language`)
+    pub language: String,
+    /// File extensions mapped to this language
+    pub extensions: Vec<String>,
+    /// Code-aware search (`probe search`)
+    pub search: bool,
+    /// Code block extraction (`probe extract`)
+    pub extract: bool,
+    /// Structural AST queries (`probe query`)
+    pub ast_query: bool,
+    /// Symbol analyzer used for indexing: "specialized", "tree-sitter" or none
+    pub analyzer: Option<String>,
+    /// Command of the configured LSP server, if any
+    pub lsp_server: Option<String>,
+    /// Whether the LSP server command was found on PATH
+    pub lsp_available: bool,
+}
+
+/// Report the capabilities of every supported language, in a stable order
+pub fn language_capabilities() -> Vec<LanguageCapabilities> {
+    let analyzer_languages =
+        AnalyzerManager::with_default_analyzers(Arc::new(SymbolUIDGenerator::new()))
+            .supported_languages();
+    let registry = LspRegistry::new().ok();
+
+    builtin_languages()
+        .map(|(language, extensions)| {
+            let parsed = extensions
+                .iter()
+                .all(|ext| get_language_impl(ext).is_some());
+
+            let analyzer = if LanguageAnalyzerFactory::has_specialized_analyzer(language) {
+                Some("specialized".to_string())
+            } else if analyzer_languages.iter().any(|l| l == language) {
+                Some("tree-sitter".to_string())
+            } else {
+                None
+            };
+
+            let lsp_language = Language::from_str(language);
+            let lsp_server = registry
+                .as_ref()
+                .zip(lsp_language)
+                .and_then(|(registry, lang)| registry.get(lang))
+                .map(|config| config.command.clone());
+            let lsp_available = registry
+                .as_ref()
+                .zip(lsp_language)
+                .is_some_and(|(registry, lang)| registry.is_lsp_available(lang));
+
+            LanguageCapabilities {
+                language: language.to_string(),
+                extensions: extensions.iter().map(|ext| ext.to_string()).collect(),
+                search: parsed,
+                extract: parsed,
+                ast_query: crate::query::is_supported_language(language),
+                analyzer,
+                lsp_server,
+                lsp_available,
+            }
+        })
+        .collect()
+}
+
+/// Handle the `capabilities` command
+pub fn handle_capabilities(json: bool) -> anyhow::Result<()> {
+    use colored::*;
+
+    let capabilities = language_capabilities();
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&capabilities)?);
+        return Ok(());
+    }
+
+    let mark = |supported: bool| if supported { "yes".green() } else { "no".red() };
+
+    println!(
+        "{:<12} {:<22} {:<7} {:<8} {:<10} {:<12} {}",
+        "Language".bold(),
+        "Extensions".bold(),
+        "Search".bold(),
+        "Extract".bold(),
+        "AST query".bold(),
+        "Analyzer".bold(),
+        "LSP server".bold()
+    );
+    for caps in &capabilities {
+        let lsp = match &caps.lsp_server {
+            Some(command) if caps.lsp_available => command.green().to_string(),
+            Some(command) => format!("{command} (not found)").yellow().to_string(),
+            None => "-".dimmed().to_string(),
+        };
+        println!(
+            "{:<12} {:<22} {:<7} {:<8} {:<10} {:<12} {}",
+            caps.language,
+            caps.extensions.join(", "),
+            mark(caps.search),
+            mark(caps.extract),
+            mark(caps.ast_query),
+            caps.analyzer.as_deref().unwrap_or("-"),
+            lsp
+        );
+    }
+
+    Ok(())
+}
@@ -1,5 +1,6 @@
 use clap::{Parser as ClapParser, Subcommand};
 use probe_code::lsp_integration::LspSubcommands;
+use probe_code::search::MergeThreshold;
 use std::path::PathBuf;
 
 #[derive(ClapParser, Debug)]
@@ -32,8 +33,8 @@ pub struct Args {
     #[arg(short = 'n', long = "exclude-filenames")]
     pub exclude_filenames: bool,
 
-    /// Ranking algorithm for search results. BERT models (ms-marco-*) require --features bert-reranker
-    #[arg(short = 'r', long = "reranker", default_value = "bm25", value_parser = ["bm25", "hybrid", "hybrid2", "tfidf", "ms-marco-tinybert", "ms-marco-minilm-l6", "ms-marco-minilm-l12"])]
+    /// Ranking algorithm for search results. BERT models ('bert', ms-marco-*) require --features bert-reranker
+    #[arg(short = 'r', long = "reranker", default_value = "bm25", value_parser = ["bm25", "hybrid", "hybrid2", "tfidf", "bert", "ms-marco-tinybert", "ms-marco-minilm-l6", "ms-marco-minilm-l12"])]
     pub reranker: String,
 
     /// Use frequency-based search with stemming and stopword removal (enabled by default)
@@ -68,24 +69,34 @@ pub struct Args {
     #[arg(long = "no-merge", default_value = "false")]
     pub no_merge: bool,
 
-    /// Maximum number of lines between code blocks to consider them adjacent for merging (default: 5)
-    #[arg(long = "merge-threshold")]
-    pub merge_threshold: Option<usize>,
+    /// Maximum number of lines between code blocks to consider them adjacent for merging
+    /// (default: 5), or 'auto' to pick it per file from how densely its lines match
+    #[arg(long = "merge-threshold", value_name = "LINES|auto")]
+    pub merge_threshold: Option<MergeThreshold>,
+
+    /// Merge nearby blocks even when they belong to different top-level declarations
+    #[arg(long = "merge-across-symbols")]
+    pub merge_across_symbols: bool,
 
     /// Output only file names and line numbers without full content
     #[arg(long = "dry-run")]
     pub dry_run: bool,
 
     /// Output format (default: outline)
-    /// Use 'json' or 'xml' for machine-readable output
-    #[arg(short = 'o', long = "format", default_value = "outline", value_parser = ["terminal", "markdown", "plain", "json", "xml", "color", "outline", "outline-xml"])]
+    /// Use 'json' or 'xml' for machine-readable output, or 'jsonl' for one result per line
+    #[arg(short = 'o', long = "format", default_value = "outline", value_parser = ["terminal", "markdown", "plain", "json", "jsonl", "xml", "color", "outline", "outline-xml"])]
     pub format: String,
 
     /// Session ID for caching search results
     #[arg(long = "session")]
     pub session: Option<String>,
 
-    /// Timeout in seconds for search operation (default: 30)
+    /// Remove session caches not used for this many seconds before searching
+    #[arg(long = "session-ttl", value_name = "SECONDS")]
+    pub session_ttl: Option<u64>,
+
+    /// Timeout in seconds for search operation (default: 30); on timeout the results found
+    /// so far are returned
     #[arg(long = "timeout", default_value = "30")]
     pub timeout: u64,
 
@@ -123,7 +134,8 @@ pub enum Commands {
         #[arg(value_name = "PATTERN")]
         pattern: String,
 
-        /// Files or directories to search (defaults to current directory)
+        /// Files or directories to search (defaults to current directory). Several roots are
+        /// searched together, with their results ranked and limited as one set
         #[arg(value_name = "PATH", default_value = ".")]
         paths: Vec<PathBuf>,
 
@@ -135,12 +147,17 @@ pub enum Commands {
         #[arg(short, long)]
         ignore: Vec<String>,
 
+        /// Read more ignore patterns from this file, one gitignore-style glob per line; may be
+        /// repeated. Combined with --ignore and the search.ignore config setting
+        #[arg(long = "ignore-file", value_name = "PATH")]
+        ignore_file: Vec<PathBuf>,
+
         /// Exclude files whose names match query words (filename matching is enabled by default)
         #[arg(short = 'n', long = "exclude-filenames")]
         exclude_filenames: bool,
 
-        /// Ranking algorithm for search results. BERT models (ms-marco-*) require --features bert-reranker
-        #[arg(short = 'r', long = "reranker", default_value = "bm25", value_parser = ["bm25", "hybrid", "hybrid2", "tfidf", "ms-marco-tinybert", "ms-marco-minilm-l6", "ms-marco-minilm-l12"])]
+        /// Ranking algorithm for search results. BERT models ('bert', ms-marco-*) require --features bert-reranker
+        #[arg(short = 'r', long = "reranker", default_value = "bm25", value_parser = ["bm25", "hybrid", "hybrid2", "tfidf", "bert", "ms-marco-tinybert", "ms-marco-minilm-l6", "ms-marco-minilm-l12"])]
         reranker: String,
 
         /// Use frequency-based search with stemming and stopword removal (enabled by default)
@@ -165,6 +182,7 @@ pub enum Commands {
             "c", "h",
             "cpp", "cc", "cxx", "hpp", "hxx",
             "java",
+            "kotlin", "kt", "kts",
             "ruby", "rb",
             "php",
             "swift",
@@ -188,6 +206,11 @@ pub enum Commands {
         #[arg(long = "max-tokens")]
         max_tokens: Option<usize>,
 
+        /// Count --max-tokens exactly with a model's BPE encoding instead of estimating:
+        /// 'p50k' (Codex), 'cl100k' (GPT-3.5/GPT-4) or 'o200k' (GPT-4o)
+        #[arg(long = "tokenizer", value_parser = ["p50k", "cl100k", "o200k"])]
+        tokenizer: Option<String>,
+
         /// Allow test files and test code blocks in search results
         #[arg(long = "allow-tests")]
         allow_tests: bool,
@@ -200,24 +223,36 @@ pub enum Commands {
         #[arg(long = "no-merge", default_value = "false")]
         no_merge: bool,
 
-        /// Maximum number of lines between code blocks to consider them adjacent for merging (default: 5)
-        #[arg(long = "merge-threshold")]
-        merge_threshold: Option<usize>,
+        /// Maximum number of lines between code blocks to consider them adjacent for merging
+        /// (default: 5), or 'auto' to pick it per file from how densely its lines match
+        #[arg(long = "merge-threshold", value_name = "LINES|auto")]
+        merge_threshold: Option<MergeThreshold>,
+
+        /// Merge nearby blocks even when they belong to different top-level declarations
+        #[arg(long = "merge-across-symbols")]
+        merge_across_symbols: bool,
 
         /// Output only file names and line numbers without full content
         #[arg(long = "dry-run")]
         dry_run: bool,
 
         /// Output format (default: outline)
-        /// Use 'json' or 'xml' for machine-readable output with structured data
-        #[arg(short = 'o', long = "format", default_value = "outline", value_parser = ["terminal", "markdown", "plain", "json", "xml", "color", "outline", "outline-xml"])]
+        /// Use 'json' or 'xml' for machine-readable output with structured data,
+        /// 'jsonl' for one compact JSON result per line, or 'plain' for bare
+        /// 'path:line' lines suitable for scripts
+        #[arg(short = 'o', long = "format", default_value = "outline", value_parser = ["terminal", "markdown", "plain", "json", "jsonl", "xml", "color", "outline", "outline-xml"])]
         format: String,
 
         /// Session ID for caching search results
         #[arg(long = "session")]
         session: Option<String>,
 
-        /// Timeout in seconds for search operation (default: 30)
+        /// Remove session caches not used for this many seconds before searching
+        #[arg(long = "session-ttl", value_name = "SECONDS")]
+        session_ttl: Option<u64>,
+
+        /// Timeout in seconds for search operation (default: 30); on timeout the results found
+        /// so far are returned
         #[arg(long = "timeout", default_value = "30")]
         timeout: u64,
 
@@ -228,6 +263,131 @@ pub enum Commands {
         /// Enable LSP integration for enhanced symbol information
         #[arg(long = "lsp")]
         lsp: bool,
+
+        /// Order of the final results. Limits are always applied by relevance first,
+        /// so 'path' and 'line' only reorder the selected top results
+        #[arg(long = "sort", default_value = "score", value_parser = ["score", "path", "line"])]
+        sort: String,
+
+        /// Treat the pattern as a raw regular expression instead of a search query
+        #[arg(long = "regex")]
+        regex: bool,
+
+        /// Limit regex matches to part of the source; 'strings-and-comments' only
+        /// reports matches inside string literals and comments (requires --regex)
+        #[arg(long = "match-scope", default_value = "all", value_parser = ["all", "strings-and-comments"], requires = "regex")]
+        match_scope: String,
+
+        /// Return code blocks (functions, classes, ...) that do NOT match the query,
+        /// e.g. handlers missing error logging
+        #[arg(long = "invert")]
+        invert: bool,
+
+        /// With --invert, only return blocks of these kinds (comma-separated), e.g.
+        /// 'function' for functions that never log an error
+        #[arg(long = "kinds", value_delimiter = ',', value_parser = ["function", "class", "struct", "enum", "interface", "trait", "impl", "type", "const", "variable", "module", "macro"], requires = "invert")]
+        kinds: Vec<String>,
+
+        /// Rank results from recently committed files higher (no effect outside git repositories)
+        #[arg(long = "recency-boost")]
+        recency_boost: bool,
+
+        /// Days after which the recency boost is halved (used with --recency-boost)
+        #[arg(
+            long = "recency-half-life",
+            default_value = "14",
+            requires = "recency_boost"
+        )]
+        recency_half_life_days: f64,
+
+        /// Include the signatures of enclosing scopes (impl, class, module, ...) with each result
+        #[arg(long = "context-symbols")]
+        context_symbols: bool,
+
+        /// Maximum number of enclosing signatures per result (used with --context-symbols)
+        #[arg(
+            long = "context-symbols-depth",
+            default_value = "5",
+            requires = "context_symbols"
+        )]
+        context_symbols_depth: usize,
+
+        /// Show how each result was scored: BM25, reranker adjustment and per-term tf/idf
+        #[arg(long = "explain")]
+        explain: bool,
+
+        /// Cache tokenized code blocks on disk and reuse them for files unchanged since the last run
+        #[arg(long = "use-index")]
+        use_index: bool,
+
+        /// Rank code blocks ('block') or whole files ('file'). In file mode each file is
+        /// returned once with the summed score of its blocks, and --max-results counts files
+        #[arg(long = "rank-mode", default_value = "block", value_parser = ["block", "file"])]
+        rank_mode: String,
+
+        /// Only search inside these kinds of syntax nodes (comma-separated), e.g.
+        /// 'comment' to audit comments or 'string' to find hardcoded URLs
+        #[arg(long = "within", value_delimiter = ',', value_parser = ["comment", "string", "identifier"])]
+        within: Vec<String>,
+
+        /// Only search files changed since this git ref (e.g. 'main'), including uncommitted
+        /// and untracked files. Fails if the path is not in a git repository
+        #[arg(long = "since", value_name = "GIT_REF")]
+        since: Option<String>,
+
+        /// Only search files tracked by git, leaving out untracked files even when they are
+        /// not ignored. Fails if the path is not in a git repository
+        #[arg(long = "tracked-only")]
+        tracked_only: bool,
+
+        /// Number of top BM25 candidates rescored by a BERT reranker; lower is faster
+        #[arg(long = "rerank-top-k", default_value = "50")]
+        rerank_top_k: usize,
+
+        /// How binary files are recognised and skipped: 'null-byte' (NUL bytes or invalid
+        /// UTF-8), 'heuristic' (mostly control characters) or 'never'
+        #[arg(long = "binary-detection", default_value = "null-byte", value_parser = ["null-byte", "heuristic", "never"])]
+        binary_detection: String,
+
+        /// Always search files with this extension as text, e.g. 'proto' or 'js' (repeatable)
+        #[arg(long = "force-text-extension", value_name = "EXT")]
+        force_text_extension: Vec<String>,
+
+        /// Also search the text entries of .zip and .jar archives (e.g. source jars), reported
+        /// as 'archive.jar!path/in/archive'
+        #[arg(long = "search-archives")]
+        search_archives: bool,
+
+        /// Decode files with this encoding instead of UTF-8, e.g. 'shift_jis' or 'latin1', or
+        /// 'auto' to detect it per file. Files that cannot be decoded are reported as skipped
+        #[arg(long = "encoding", value_name = "ENCODING")]
+        encoding: Option<String>,
+
+        /// Only return results whose final reranker score is at least this value (BM25-based
+        /// for bm25 and hybrid rankers); the number dropped is reported with the limits
+        #[arg(long = "min-score", value_name = "SCORE")]
+        min_score: Option<f32>,
+
+        /// Also follow symbolic links to files (symlinked directories are always walked).
+        /// A file reached through several links is searched once
+        #[arg(long = "follow-symlinks")]
+        follow_symlinks: bool,
+
+        /// Rank with this profile from the 'search.ranking_profiles' config instead of the
+        /// configured 'search.ranking_profile' ('default' unless set)
+        #[arg(long = "ranking-profile", value_name = "NAME")]
+        ranking_profile: Option<String>,
+
+        /// Collapse duplicate blocks after ranking: 'exact' for byte-identical code, 'near' for
+        /// mostly identical code such as copied boilerplate. The best-ranked block of each
+        /// group is kept with the number of duplicates collapsed into it
+        #[arg(long = "dedup", default_value = "none", value_parser = ["none", "exact", "near"])]
+        dedup: String,
+
+        /// With --format plain, print 'path:line:snippet' lines with the first line of each
+        /// result instead of 'path:line'
+        #[arg(long = "snippet")]
+        snippet: bool,
     },
 
     /// Extract code blocks from files
@@ -256,8 +416,9 @@ pub enum Commands {
 
         /// Output format (default: color)
         /// Use 'json' or 'xml' for machine-readable output with structured data
+        /// Use 'outline' for just the declaration signatures of the extracted code
         /// Use 'outline-diff' for semantically enhanced git diff output
-        #[arg(short = 'o', long = "format", default_value = "color", value_parser = ["markdown", "plain", "json", "xml", "color", "outline-xml", "outline-diff"])]
+        #[arg(short = 'o', long = "format", default_value = "color", value_parser = ["markdown", "plain", "json", "xml", "color", "outline", "outline-xml", "outline-diff"])]
         format: String,
 
         /// Read input from clipboard instead of files
@@ -275,6 +436,11 @@ pub enum Commands {
         #[arg(long = "dry-run")]
         dry_run: bool,
 
+        /// Print only the resolved range of each block as `file:start-end`, one per line and
+        /// without any other output, to check what would be extracted
+        #[arg(long = "plain-paths", conflicts_with_all = ["dry_run", "line_numbers", "blame"])]
+        plain_paths: bool,
+
         /// Parse input as git diff format
         #[arg(long = "diff")]
         diff: bool,
@@ -302,6 +468,59 @@ pub enum Commands {
         /// Include standard library references in LSP results (when using --lsp flag)
         #[arg(long = "include-stdlib")]
         include_stdlib: bool,
+
+        /// Remove comments from the extracted code to save tokens (line numbers are preserved)
+        #[arg(long = "strip-comments")]
+        strip_comments: bool,
+
+        /// Replace the contents of string literals with a placeholder before sharing code
+        #[arg(long = "redact-strings")]
+        redact_strings: bool,
+
+        /// Replace function bodies with `{ ... }`, keeping doc comments, attributes and
+        /// signatures, for a compact API summary
+        #[arg(long = "signatures-only", conflicts_with_all = ["line_numbers", "blame", "diff_blocks"])]
+        signatures_only: bool,
+
+        /// Extract only these kinds of declarations (comma-separated) from files given without
+        /// a line or symbol, each as its own block, e.g. 'function' or 'type'. Files without a
+        /// grammar are extracted whole, with a warning
+        #[arg(long = "only-kinds", value_delimiter = ',', value_parser = ["function", "class", "struct", "enum", "interface", "trait", "impl", "type", "const", "variable", "module", "macro"])]
+        only_kinds: Vec<String>,
+
+        /// With --only-kinds, skip declarations that are not public (`pub` in Rust, exported
+        /// in Go, JavaScript and TypeScript, `public` in Java and C#, no leading `_` in Python)
+        #[arg(long = "public-only", requires = "only_kinds")]
+        public_only: bool,
+
+        /// Prefix extracted code with a gutter of file line numbers (e.g. `42 | let x = 1;`)
+        #[arg(long = "line-numbers")]
+        line_numbers: bool,
+
+        /// Annotate each extracted line with the short hash and author of the commit that
+        /// last changed it (JSON output adds a parallel `blame` array)
+        #[arg(long = "blame")]
+        blame: bool,
+
+        /// With diff input, extract the enclosing function or block of each changed line
+        /// separately and mark the changed lines with `+` (JSON adds `changed_lines`)
+        #[arg(long = "diff-blocks")]
+        diff_blocks: bool,
+
+        /// With --to-clipboard, copy at most this many bytes. Longer output is cut with a
+        /// note and written in full to a temporary file
+        #[arg(long = "clipboard-max-bytes", value_name = "BYTES")]
+        clipboard_max_bytes: Option<usize>,
+
+        /// Decode files with this encoding instead of UTF-8, e.g. 'shift_jis' or 'latin1', or
+        /// 'auto' to detect it per file
+        #[arg(long = "encoding", value_name = "ENCODING")]
+        encoding: Option<String>,
+
+        /// Reuse the blocks extracted earlier in this run for a file and request that come up
+        /// again, as long as the file is unchanged. The cache lives only as long as the process
+        #[arg(long = "cache")]
+        cache: bool,
     },
 
     /// List symbols (functions, structs, classes, constants, etc.) in files
@@ -331,6 +550,27 @@ pub enum Commands {
         text_extensions: Vec<String>,
     },
 
+    /// Show supported languages and their capabilities
+    ///
+    /// Reports, per language, the file extensions probe recognises, whether search,
+    /// extraction and AST queries are supported, which symbol analyzer backs indexing,
+    /// and whether the configured LSP server is installed.
+    Capabilities {
+        /// Output the manifest as JSON
+        #[arg(long = "json")]
+        json: bool,
+    },
+
+    /// Print the JSON Schema of a command's JSON output
+    ///
+    /// Describes the document printed by `--format json` of the search, extract or query
+    /// command, so integrators can validate against it instead of reverse-engineering it.
+    Schema {
+        /// Command whose JSON output to describe
+        #[arg(value_name = "COMMAND", value_parser = ["search", "extract", "query"])]
+        command: String,
+    },
+
     /// Search code using AST patterns for precise structural matching
     ///
     /// This command uses ast-grep to search for structural patterns in code.
@@ -343,7 +583,13 @@ pub enum Commands {
         #[arg(value_name = "PATTERN")]
         pattern: String,
 
-        /// Files or directories to search (defaults to current directory)
+        /// Also match this pattern (repeatable). Files are parsed once for all patterns and
+        /// a node matched by several patterns is reported once
+        #[arg(long = "or", value_name = "PATTERN")]
+        or_patterns: Vec<String>,
+
+        /// Files or directories to search (defaults to current directory). Several roots are
+        /// searched together, with their results ranked and limited as one set
         #[arg(value_name = "PATH", default_value = ".")]
         path: PathBuf,
 
@@ -357,6 +603,7 @@ pub enum Commands {
             "c", "h",
             "cpp", "cc", "cxx", "hpp", "hxx",
             "java",
+            "kotlin", "kt", "kts",
             "ruby", "rb",
             "php",
             "swift",
@@ -380,6 +627,11 @@ pub enum Commands {
         #[arg(long = "no-gitignore")]
         no_gitignore: bool,
 
+        /// Also follow symbolic links to files (symlinked directories are always walked).
+        /// A file reached through several links is queried once
+        #[arg(long = "follow-symlinks")]
+        follow_symlinks: bool,
+
         /// Maximum number of results to return
         #[arg(long = "max-results")]
         max_results: Option<usize>,
@@ -396,9 +648,106 @@ pub enum Commands {
         #[arg(long = "text-extension", value_name = "EXT")]
         text_extensions: Vec<String>,
 
+        /// Only keep matches whose parent node has this kind (repeatable). Use NAME=KIND to
+        /// anchor capture $NAME instead, e.g. --parent-kind expression_statement
+        #[arg(long = "parent-kind", value_name = "KIND|CAPTURE=KIND")]
+        parent_kinds: Vec<String>,
+
+        /// Show the tree-sitter S-expression of each matched node (useful for debugging patterns)
+        #[arg(long = "sexp")]
+        sexp: bool,
+
+        /// Group matches under one heading per file ('file') or per enclosing definition
+        /// ('symbol'). Symbol grouping collapses matches nested inside another match
+        #[arg(long = "group-by", default_value = "none", value_parser = ["none", "file", "symbol"])]
+        group_by: String,
+
+        /// Only print per-file and total match counts as CSV (same as --format count).
+        /// Combine with --max-results to stop counting early
+        #[arg(long = "count-only")]
+        count_only: bool,
+
         /// Output format (default: color)
-        /// Use 'json' or 'xml' for machine-readable output with structured data
-        #[arg(short = 'o', long = "format", default_value = "color", value_parser = ["markdown", "plain", "json", "xml", "color", "outline-xml"])]
+        /// Use 'json' or 'xml' for machine-readable output with structured data, 'sexp' to
+        /// print the indented S-expression of each match (PROBE_SEXP_MAX_DEPTH limits nesting),
+        /// or 'count' for per-file match counts
+        #[arg(short = 'o', long = "format", default_value = "color", value_parser = ["markdown", "plain", "json", "xml", "color", "outline-xml", "sexp", "count"])]
+        format: String,
+    },
+
+    /// Search files line by line with a regular expression, like grep
+    ///
+    /// A fast, grep-compatible line search that honours .gitignore. Unlike `search`, results
+    /// are matching lines rather than ranked code blocks. Use --show-symbol to label each
+    /// match with the function or class it is in.
+    Grep {
+        /// Regular expression to search for
+        #[arg(value_name = "PATTERN")]
+        pattern: String,
+
+        /// Files or directories to search
+        #[arg(value_name = "PATH", default_value = ".")]
+        paths: Vec<PathBuf>,
+
+        /// Match case-insensitively
+        #[arg(short = 'i', long = "ignore-case")]
+        ignore_case: bool,
+
+        /// Prefix each line with its line number
+        #[arg(short = 'n', long = "line-number")]
+        line_number: bool,
+
+        /// Only print the number of matching lines of each file
+        #[arg(short = 'c', long = "count")]
+        count: bool,
+
+        /// Only print the names of files with a match
+        #[arg(short = 'l', long = "files-with-matches")]
+        files_with_matches: bool,
+
+        /// Only print the names of files without a match
+        #[arg(short = 'L', long = "files-without-match")]
+        files_without_match: bool,
+
+        /// Select the lines that do not match
+        #[arg(short = 'v', long = "invert-match")]
+        invert_match: bool,
+
+        /// Lines of context to print before each match
+        #[arg(short = 'B', long = "before-context", value_name = "NUM")]
+        before_context: Option<usize>,
+
+        /// Lines of context to print after each match
+        #[arg(short = 'A', long = "after-context", value_name = "NUM")]
+        after_context: Option<usize>,
+
+        /// Lines of context to print before and after each match
+        #[arg(short = 'C', long = "context", value_name = "NUM")]
+        context: Option<usize>,
+
+        /// Custom patterns to ignore (in addition to .gitignore and common patterns)
+        #[arg(long = "ignore")]
+        ignore: Vec<String>,
+
+        /// Search files ignored by .gitignore too
+        #[arg(long = "no-gitignore")]
+        no_gitignore: bool,
+
+        /// When to color the output
+        #[arg(long = "color", default_value = "auto", value_parser = ["auto", "always", "never"])]
+        color: String,
+
+        /// Stop reading a file after this many matching lines
+        #[arg(short = 'm', long = "max-count", value_name = "NUM")]
+        max_count: Option<usize>,
+
+        /// Label each match with the innermost function, class or other symbol containing it
+        /// (languages with a tree-sitter grammar only)
+        #[arg(long = "show-symbol")]
+        show_symbol: bool,
+
+        /// Output format: grep-style lines ('text') or one JSON document ('json')
+        #[arg(short = 'o', long = "format", default_value = "text", value_parser = ["text", "json"])]
         format: String,
     },
 
@@ -25,6 +25,8 @@ pub struct ProbeConfig {
     pub performance: Option<PerformanceConfig>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub indexing: Option<IndexingConfig>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub languages: Option<LanguagesConfig>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -59,6 +61,24 @@ pub struct SearchConfig {
     pub allow_tests: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub no_gitignore: Option<bool>,
+    /// Extra words the tokenizer filters out, on top of the built-in stop words
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_words_add: Option<Vec<String>>,
+    /// Built-in stop words the tokenizer keeps; `"*"` keeps all of them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stop_words_remove: Option<Vec<String>>,
+    /// Language of the Snowball stemmer, e.g. "english" or "german"
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub stemmer_language: Option<String>,
+    /// Glob patterns of files and directories to skip, on top of `--ignore` patterns
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ignore: Option<Vec<String>>,
+    /// Name of the ranking profile used when `--ranking-profile` is not given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ranking_profile: Option<String>,
+    /// Named ranking profiles, each a set of relevance weights
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ranking_profiles: Option<HashMap<String, crate::ranking::RankingProfile>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -304,6 +324,17 @@ pub struct PerformanceConfig {
     pub optimize_blocks: Option<bool>,
 }
 
+/// Overrides for language auto-detection
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct LanguagesConfig {
+    /// Language per file extension, e.g. `"mjs": "javascript"` or `"snap": "tsx"`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extensions: Option<HashMap<String, String>>,
+    /// Language per regex matched against a `#!` first line, for files without a known extension
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shebangs: Option<HashMap<String, String>>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct IndexingConfig {
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -432,6 +463,7 @@ pub struct ResolvedConfig {
     pub lsp: ResolvedLspConfig,
     pub performance: ResolvedPerformanceConfig,
     pub indexing: ResolvedIndexingConfig,
+    pub languages: ResolvedLanguagesConfig,
 }
 
 #[derive(Debug, Clone)]
@@ -453,6 +485,12 @@ pub struct ResolvedSearchConfig {
     pub merge_threshold: usize,
     pub allow_tests: bool,
     pub no_gitignore: bool,
+    pub stop_words_add: Vec<String>,
+    pub stop_words_remove: Vec<String>,
+    pub stemmer_language: String,
+    pub ignore: Vec<String>,
+    pub ranking_profile: String,
+    pub ranking_profiles: HashMap<String, crate::ranking::RankingProfile>,
 }
 
 #[derive(Debug, Clone)]
@@ -508,6 +546,12 @@ pub struct ResolvedPerformanceConfig {
     pub optimize_blocks: bool,
 }
 
+#[derive(Debug, Clone, Default)]
+pub struct ResolvedLanguagesConfig {
+    pub extensions: HashMap<String, String>,
+    pub shebangs: HashMap<String, String>,
+}
+
 #[derive(Debug, Clone)]
 pub struct ResolvedIndexingConfig {
     pub enabled: bool,
@@ -760,6 +804,28 @@ impl ProbeConfig {
             if other_search.no_gitignore.is_some() {
                 base_search.no_gitignore = other_search.no_gitignore;
             }
+            if other_search.stop_words_add.is_some() {
+                base_search.stop_words_add = other_search.stop_words_add;
+            }
+            if other_search.stop_words_remove.is_some() {
+                base_search.stop_words_remove = other_search.stop_words_remove;
+            }
+            if other_search.stemmer_language.is_some() {
+                base_search.stemmer_language = other_search.stemmer_language;
+            }
+            if other_search.ignore.is_some() {
+                base_search.ignore = other_search.ignore;
+            }
+            if other_search.ranking_profile.is_some() {
+                base_search.ranking_profile = other_search.ranking_profile;
+            }
+            // Profiles are merged by name, a more specific config replaces a whole profile
+            if let Some(other_profiles) = other_search.ranking_profiles {
+                base_search
+                    .ranking_profiles
+                    .get_or_insert_with(HashMap::new)
+                    .extend(other_profiles);
+            }
         }
 
         // Merge extract
@@ -997,6 +1063,23 @@ impl ProbeConfig {
             }
         }
 
+        // Merge languages, entry by entry
+        if let Some(other_languages) = other.languages {
+            let base_languages = base.languages.get_or_insert(LanguagesConfig::default());
+            if let Some(extensions) = other_languages.extensions {
+                base_languages
+                    .extensions
+                    .get_or_insert_with(HashMap::new)
+                    .extend(extensions);
+            }
+            if let Some(shebangs) = other_languages.shebangs {
+                base_languages
+                    .shebangs
+                    .get_or_insert_with(HashMap::new)
+                    .extend(shebangs);
+            }
+        }
+
         base
     }
 
@@ -1061,6 +1144,21 @@ impl ProbeConfig {
         if let Ok(val) = env::var("PROBE_NO_GITIGNORE") {
             search.no_gitignore = Some(val == "1" || val.to_lowercase() == "true");
         }
+        if let Ok(val) = env::var("PROBE_STOP_WORDS_ADD") {
+            search.stop_words_add = Some(val.split(',').map(|s| s.trim().to_string()).collect());
+        }
+        if let Ok(val) = env::var("PROBE_STOP_WORDS_REMOVE") {
+            search.stop_words_remove = Some(val.split(',').map(|s| s.trim().to_string()).collect());
+        }
+        if let Ok(val) = env::var("PROBE_STEMMER_LANGUAGE") {
+            search.stemmer_language = Some(val);
+        }
+        if let Ok(val) = env::var("PROBE_SEARCH_IGNORE") {
+            search.ignore = Some(val.split(',').map(|s| s.trim().to_string()).collect());
+        }
+        if let Ok(val) = env::var("PROBE_RANKING_PROFILE") {
+            search.ranking_profile = Some(val);
+        }
 
         // Extract
         let extract = self.extract.get_or_insert(ExtractConfig::default());
@@ -1279,6 +1377,7 @@ impl ProbeConfig {
         let lsp = self.lsp.unwrap_or_default();
         let performance = self.performance.unwrap_or_default();
         let indexing = self.indexing.unwrap_or_default();
+        let languages = self.languages.unwrap_or_default();
 
         ResolvedConfig {
             defaults: ResolvedDefaultsConfig {
@@ -1297,6 +1396,16 @@ impl ProbeConfig {
                 merge_threshold: search.merge_threshold.unwrap_or(5),
                 allow_tests: search.allow_tests.unwrap_or(false),
                 no_gitignore: search.no_gitignore.unwrap_or(false),
+                stop_words_add: search.stop_words_add.unwrap_or_default(),
+                stop_words_remove: search.stop_words_remove.unwrap_or_default(),
+                stemmer_language: search
+                    .stemmer_language
+                    .unwrap_or_else(|| "english".to_string()),
+                ignore: search.ignore.unwrap_or_default(),
+                ranking_profile: search
+                    .ranking_profile
+                    .unwrap_or_else(|| crate::ranking::DEFAULT_RANKING_PROFILE.to_string()),
+                ranking_profiles: search.ranking_profiles.unwrap_or_default(),
             },
             extract: ResolvedExtractConfig {
                 context_lines: extract.context_lines.unwrap_or(0),
@@ -1428,6 +1537,10 @@ impl ProbeConfig {
                 },
                 language_configs: indexing.language_configs.unwrap_or_default(),
             },
+            languages: ResolvedLanguagesConfig {
+                extensions: languages.extensions.unwrap_or_default(),
+                shebangs: languages.shebangs.unwrap_or_default(),
+            },
         }
     }
 
@@ -1460,6 +1573,7 @@ impl ProbeConfig {
                     "hybrid",
                     "hybrid2",
                     "tfidf",
+                    "bert",
                     "ms-marco-tinybert",
                     "ms-marco-minilm-l6",
                     "ms-marco-minilm-l12",
@@ -1469,6 +1583,18 @@ impl ProbeConfig {
                     anyhow::bail!("Invalid reranker: {}", reranker);
                 }
             }
+
+            if let Some(ref language) = search.stemmer_language {
+                if crate::ranking::stemmer_algorithm(language).is_none() {
+                    anyhow::bail!("Invalid stemmer language: {}", language);
+                }
+            }
+
+            for (name, profile) in search.ranking_profiles.iter().flatten() {
+                profile
+                    .validate()
+                    .with_context(|| format!("Invalid ranking profile: {name}"))?;
+            }
         }
 
         Ok(())
@@ -1495,6 +1621,12 @@ impl ResolvedConfig {
                 merge_threshold: Some(self.search.merge_threshold),
                 allow_tests: Some(self.search.allow_tests),
                 no_gitignore: Some(self.search.no_gitignore),
+                stop_words_add: Some(self.search.stop_words_add.clone()),
+                stop_words_remove: Some(self.search.stop_words_remove.clone()),
+                stemmer_language: Some(self.search.stemmer_language.clone()),
+                ignore: Some(self.search.ignore.clone()),
+                ranking_profile: Some(self.search.ranking_profile.clone()),
+                ranking_profiles: Some(self.search.ranking_profiles.clone()),
             }),
             extract: Some(ExtractConfig {
                 context_lines: Some(self.extract.context_lines),
@@ -1606,6 +1738,10 @@ impl ResolvedConfig {
                 }),
                 language_configs: Some(self.indexing.language_configs.clone()),
             }),
+            languages: Some(LanguagesConfig {
+                extensions: Some(self.languages.extensions.clone()),
+                shebangs: Some(self.languages.shebangs.clone()),
+            }),
         }
     }
 
@@ -1852,6 +1988,15 @@ pub mod config_ops {
         // Try to infer type from key name and common patterns
         let key_lower = key.to_lowercase();
 
+        // List fields take comma-separated values
+        if key_lower.ends_with(".ignore") || key_lower.contains("stop_words") {
+            return Ok(json!(value
+                .split(',')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .collect::<Vec<_>>()));
+        }
+
         // Check for boolean fields
         if key_lower.ends_with("enabled")
             || key_lower.ends_with("enable")
@@ -2028,6 +2173,68 @@ mod tests {
         assert_eq!(defaults.timeout, Some(60)); // Added from override
     }
 
+    #[test]
+    fn test_search_ignore_from_more_specific_config_replaces_list() {
+        let global = ProbeConfig {
+            search: Some(SearchConfig {
+                ignore: Some(vec!["vendor/**".to_string()]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let project = ProbeConfig {
+            search: Some(SearchConfig {
+                ignore: Some(vec!["gen/**".to_string()]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let unset = ProbeConfig {
+            search: Some(SearchConfig::default()),
+            ..Default::default()
+        };
+
+        let merged = ProbeConfig::merge_configs(global.clone(), project);
+        assert_eq!(merged.resolve_with_defaults().search.ignore, vec!["gen/**"]);
+        let merged = ProbeConfig::merge_configs(global, unset);
+        assert_eq!(
+            merged.resolve_with_defaults().search.ignore,
+            vec!["vendor/**"]
+        );
+    }
+
+    #[test]
+    fn test_ranking_profiles_merge_by_name_and_fill_defaults() {
+        let global: ProbeConfig = serde_json::from_str(
+            r#"{"search": {"ranking_profiles": {
+                "docs": {"filename_boost": 2.0},
+                "strict": {"exact_match_boost": 3.0}
+            }}}"#,
+        )
+        .unwrap();
+        let project: ProbeConfig = serde_json::from_str(
+            r#"{"search": {"ranking_profile": "docs", "ranking_profiles": {
+                "docs": {"k1": 1.2, "b": 0.75}
+            }}}"#,
+        )
+        .unwrap();
+        assert!(project.validate().is_ok());
+
+        let search = ProbeConfig::merge_configs(global, project)
+            .resolve_with_defaults()
+            .search;
+        assert_eq!(search.ranking_profile, "docs");
+        let docs = &search.ranking_profiles["docs"];
+        assert_eq!((docs.filename_boost, docs.k1, docs.b), (1.0, 1.2, 0.75));
+        assert_eq!(search.ranking_profiles["strict"].exact_match_boost, 3.0);
+
+        let invalid: ProbeConfig =
+            serde_json::from_str(r#"{"search": {"ranking_profiles": {"wide": {"b": 2.0}}}}"#)
+                .unwrap();
+        let err = invalid.validate().unwrap_err();
+        assert!(format!("{err:#}").contains("Invalid ranking profile: wide"));
+    }
+
     #[test]
     fn test_json_serialization() {
         let config = ProbeConfig {
@@ -2261,6 +2468,25 @@ mod tests {
         assert_eq!(indexing.watch_files, Some(true)); // Added from override
     }
 
+    #[test]
+    fn test_language_overrides_merge_by_entry() {
+        let base: ProbeConfig = serde_json::from_str(
+            r#"{"languages": {"extensions": {"mjs": "javascript", "snap": "ts"}}}"#,
+        )
+        .unwrap();
+        let override_config: ProbeConfig = serde_json::from_str(
+            r#"{"languages": {"extensions": {"snap": "tsx"}, "shebangs": {"\\bdeno\\b": "typescript"}}}"#,
+        )
+        .unwrap();
+
+        let languages = ProbeConfig::merge_configs(base, override_config)
+            .resolve_with_defaults()
+            .languages;
+        assert_eq!(languages.extensions["mjs"], "javascript"); // Kept from base
+        assert_eq!(languages.extensions["snap"], "tsx"); // Overridden
+        assert_eq!(languages.shebangs[r"\bdeno\b"], "typescript"); // Added from override
+    }
+
     #[test]
     fn test_resolved_indexing_defaults() {
         // Test the actual resolved values for indexing configuration
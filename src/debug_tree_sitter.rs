@@ -145,6 +145,7 @@ fn get_language_name(extension: &str) -> &str {
         "py" => "Python",
         "go" => "Go",
         "java" => "Java",
+        "kt" | "kts" => "Kotlin",
         "c" | "h" => "C",
         "cpp" | "cc" | "cxx" | "hpp" | "hxx" => "C++",
         "rb" => "Ruby",
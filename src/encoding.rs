@@ -0,0 +1,181 @@
+//! Decoding of source files that are not UTF-8, e.g. legacy Shift_JIS or Latin-1 files.
+//!
+//! Files are transcoded to UTF-8 as a whole before tokenization or extraction. Line breaks
+//! survive transcoding unchanged, so line numbers refer to the original file.
+
+use anyhow::Result;
+use encoding_rs::Encoding;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// How the bytes of source files are decoded to text
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceEncoding {
+    /// UTF-8 when the file is valid UTF-8, otherwise the encoding guessed from its content
+    Auto,
+    /// A fixed encoding, given by any WHATWG label such as `shift_jis` or `latin1`
+    Fixed(&'static Encoding),
+}
+
+impl SourceEncoding {
+    /// Parse an optional `--encoding` value; `None` keeps the default UTF-8 decoding
+    pub fn parse_option(encoding: Option<&str>) -> Result<Option<Self>> {
+        encoding
+            .map(|label| label.parse().map_err(anyhow::Error::msg))
+            .transpose()
+    }
+
+    /// Name shown in messages: `auto` or the canonical name of the encoding
+    pub fn name(self) -> &'static str {
+        match self {
+            SourceEncoding::Auto => "auto",
+            SourceEncoding::Fixed(encoding) => encoding.name(),
+        }
+    }
+
+    /// Decode `bytes`, or `None` if they are malformed in this encoding. A byte order mark
+    /// takes precedence over the encoding.
+    pub fn decode(self, bytes: &[u8]) -> Option<String> {
+        if let Some((encoding, bom_length)) = Encoding::for_bom(bytes) {
+            return decode_strict(encoding, &bytes[bom_length..]);
+        }
+        match self {
+            SourceEncoding::Auto => {
+                if let Ok(text) = std::str::from_utf8(bytes) {
+                    return Some(text.to_string());
+                }
+                let mut detector = chardetng::EncodingDetector::new();
+                detector.feed(bytes, true);
+                decode_strict(detector.guess(None, true), bytes)
+            }
+            SourceEncoding::Fixed(encoding) => decode_strict(encoding, bytes),
+        }
+    }
+}
+
+impl FromStr for SourceEncoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("auto") {
+            return Ok(SourceEncoding::Auto);
+        }
+        Encoding::for_label(s.trim().as_bytes())
+            .map(SourceEncoding::Fixed)
+            .ok_or_else(|| {
+                format!("Unknown encoding: {s}. Use 'auto' or a label such as utf-8, shift_jis or latin1")
+            })
+    }
+}
+
+fn decode_strict(encoding: &'static Encoding, bytes: &[u8]) -> Option<String> {
+    encoding
+        .decode_without_bom_handling_and_without_replacement(bytes)
+        .map(|text| text.into_owned())
+}
+
+/// Error for a file skipped because it is malformed in the requested encoding.
+#[derive(Debug)]
+pub struct DecodingError {
+    pub path: PathBuf,
+    pub encoding: &'static str,
+}
+
+impl fmt::Display for DecodingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "File could not be decoded as {}: {}",
+            self.encoding,
+            self.path.display()
+        )
+    }
+}
+
+impl std::error::Error for DecodingError {}
+
+/// Decode the content of the file at `path`. Without an encoding the bytes are read as
+/// UTF-8 with invalid sequences replaced; with one, malformed content fails with
+/// [`DecodingError`].
+pub fn decode_file_bytes(
+    path: &Path,
+    bytes: Vec<u8>,
+    encoding: Option<SourceEncoding>,
+) -> Result<String> {
+    match encoding {
+        None => Ok(match String::from_utf8(bytes) {
+            Ok(content) => content,
+            Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+        }),
+        Some(encoding) => encoding.decode(&bytes).ok_or_else(|| {
+            DecodingError {
+                path: path.to_path_buf(),
+                encoding: encoding.name(),
+            }
+            .into()
+        }),
+    }
+}
+
+/// Read and decode a file, or an `archive!entry` path, for search or extraction
+pub fn read_text_file(path: &Path, encoding: Option<SourceEncoding>) -> Result<String> {
+    decode_file_bytes(path, crate::search::archive::read_file(path)?, encoding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_encoding_labels() {
+        assert_eq!("auto".parse(), Ok(SourceEncoding::Auto));
+        assert_eq!(
+            "latin1".parse(),
+            Ok(SourceEncoding::Fixed(encoding_rs::WINDOWS_1252))
+        );
+        assert_eq!(
+            "Shift_JIS".parse(),
+            Ok(SourceEncoding::Fixed(encoding_rs::SHIFT_JIS))
+        );
+        assert!("klingon".parse::<SourceEncoding>().is_err());
+        assert_eq!(SourceEncoding::parse_option(None).unwrap(), None);
+    }
+
+    #[test]
+    fn test_decode_preserves_lines() {
+        let (bytes, _, _) = encoding_rs::SHIFT_JIS.encode("// 日本語\nfn main() {}\n");
+        let text = SourceEncoding::Fixed(encoding_rs::SHIFT_JIS)
+            .decode(&bytes)
+            .unwrap();
+        assert_eq!(
+            text.lines().collect::<Vec<_>>(),
+            ["// 日本語", "fn main() {}"]
+        );
+
+        // Latin-1 never fails, and auto detection falls back to it for this input
+        let latin1 = b"// caf\xe9\nfn main() {}\n";
+        assert_eq!(
+            SourceEncoding::Auto.decode(latin1).unwrap(),
+            "// café\nfn main() {}\n"
+        );
+    }
+
+    #[test]
+    fn test_malformed_content_is_a_decoding_error() {
+        let path = Path::new("legacy.rs");
+        let err = decode_file_bytes(
+            path,
+            b"\x82".to_vec(),
+            Some(SourceEncoding::Fixed(encoding_rs::SHIFT_JIS)),
+        )
+        .unwrap_err();
+        assert!(err.downcast_ref::<DecodingError>().is_some());
+
+        // Without an encoding, invalid UTF-8 is replaced as before
+        assert_eq!(
+            decode_file_bytes(path, b"caf\xe9".to_vec(), None).unwrap(),
+            "caf\u{fffd}"
+        );
+    }
+}
@@ -0,0 +1,72 @@
+//! Git blame annotations for extracted code.
+//!
+//! Each extracted line is attributed to the last commit that changed it using
+//! [`GitService::blame_lines`]. Files outside a git repository, untracked files and
+//! uncommitted lines simply have no annotation.
+
+use lsp_daemon::{BlameLine, GitService};
+use probe_code::models::SearchResult;
+use std::collections::HashMap;
+use std::path::{Component, Path};
+
+/// Number of commits followed back from HEAD when attributing lines
+const MAX_BLAME_COMMITS: usize = 5000;
+
+/// Blame each line of every result's code, in result order. Each entry holds one
+/// annotation per line of the result's `code`, or `None` where no commit is known.
+pub fn blame_results(results: &[SearchResult]) -> Vec<Vec<Option<BlameLine>>> {
+    let mut file_blame: HashMap<&str, Vec<Option<BlameLine>>> = HashMap::new();
+    for result in results {
+        if !file_blame.contains_key(result.file.as_str()) {
+            file_blame.insert(&result.file, blame_file(Path::new(&result.file)));
+        }
+    }
+
+    results
+        .iter()
+        .map(|result| {
+            let lines = &file_blame[result.file.as_str()];
+            let start = result.lines.0.max(1) - 1;
+            (start..start + result.code.lines().count())
+                .map(|line| lines.get(line).cloned().flatten())
+                .collect()
+        })
+        .collect()
+}
+
+/// Blame every line of the file's current content; empty if that is not possible
+fn blame_file(path: &Path) -> Vec<Option<BlameLine>> {
+    let Ok(path) = path.canonicalize() else {
+        return Vec::new();
+    };
+    let Some(parent) = path.parent() else {
+        return Vec::new();
+    };
+    let Ok(git) = GitService::discover_repo(parent, parent) else {
+        return Vec::new();
+    };
+    let Some(repo_path) = git
+        .workdir()
+        .and_then(|dir| dir.canonicalize().ok())
+        .and_then(|dir| path.strip_prefix(dir).ok().map(to_git_path))
+    else {
+        return Vec::new();
+    };
+    let Ok(content) = std::fs::read_to_string(&path) else {
+        return Vec::new();
+    };
+
+    git.blame_lines(&repo_path, &content, MAX_BLAME_COMMITS)
+        .unwrap_or_default()
+}
+
+/// Git paths are relative to the repository root and use forward slashes
+fn to_git_path(path: &Path) -> String {
+    path.components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(part.to_string_lossy()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
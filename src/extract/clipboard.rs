@@ -0,0 +1,68 @@
+//! Size limiting for extraction output copied to the clipboard.
+//!
+//! Some platforms silently truncate large clipboard payloads, losing the tail of a long
+//! extraction. With a byte limit the clipboard gets the head of the output followed by a
+//! note with the full size, and the complete output is written to a temporary file whose
+//! path is included in the note.
+
+use std::path::{Path, PathBuf};
+
+/// Text to copy for `output` when it is longer than `max_bytes`, or `None` if it fits.
+///
+/// The output is cut at the last character boundary within `max_bytes`, and a note giving
+/// the copied and full sizes, plus `full_output` when given, is appended after the cut.
+pub fn truncate_for_clipboard(
+    output: &str,
+    max_bytes: usize,
+    full_output: Option<&Path>,
+) -> Option<String> {
+    if output.len() <= max_bytes {
+        return None;
+    }
+
+    let mut cut = max_bytes;
+    while !output.is_char_boundary(cut) {
+        cut -= 1;
+    }
+
+    let mut truncated = output[..cut].to_string();
+    truncated.push_str(&format!(
+        "\n\n[Truncated by probe: copied {cut} of {} bytes",
+        output.len()
+    ));
+    match full_output {
+        Some(path) => truncated.push_str(&format!(". Full output: {}]", path.display())),
+        None => truncated.push(']'),
+    }
+    Some(truncated)
+}
+
+/// Write the complete output to a temporary file so nothing is lost when the clipboard copy
+/// is truncated. Returns `None` if the file cannot be written.
+pub fn write_full_output(output: &str) -> Option<PathBuf> {
+    let path = std::env::temp_dir().join(format!("probe-extract-{}.txt", std::process::id()));
+    std::fs::write(&path, output).ok()?;
+    Some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_within_limit_is_copied_whole() {
+        assert_eq!(truncate_for_clipboard("fn main() {}", 12, None), None);
+    }
+
+    #[test]
+    fn test_long_output_is_cut_at_a_character_boundary() {
+        // 'é' takes two bytes, so a limit of 5 falls inside the third one
+        let truncated = truncate_for_clipboard("éééé", 5, None).unwrap();
+        assert_eq!(truncated, "éé\n\n[Truncated by probe: copied 4 of 8 bytes]");
+
+        let truncated =
+            truncate_for_clipboard("abcdef", 3, Some(Path::new("/tmp/full.txt"))).unwrap();
+        assert!(truncated.starts_with("abc\n\n"));
+        assert!(truncated.ends_with("copied 3 of 6 bytes. Full output: /tmp/full.txt]"));
+    }
+}
@@ -0,0 +1,125 @@
+//! Removal of comment nodes from extracted code.
+//!
+//! Comments are located with tree-sitter and blanked out in place, so every line keeps
+//! its original line number: a line that only contained a comment becomes empty, and
+//! trailing comments are trimmed from lines that also contain code.
+
+use anyhow::{Context, Result};
+use probe_code::encoding::{self, SourceEncoding};
+use probe_code::language::factory::get_language_impl;
+use probe_code::language::{get_pooled_parser, return_pooled_parser};
+use probe_code::models::SearchResult;
+use std::collections::HashSet;
+use std::path::Path;
+
+/// Collect the byte ranges of all top-level comment nodes in a tree
+fn collect_comment_ranges(node: tree_sitter::Node, ranges: &mut Vec<(usize, usize)>) {
+    if node.kind().contains("comment") {
+        ranges.push((node.start_byte(), node.end_byte()));
+        return;
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_comment_ranges(child, ranges);
+    }
+}
+
+/// Strip comments from `content`, parsed with the grammar for `extension`.
+///
+/// The number of lines is preserved. Content in a language without a tree-sitter
+/// grammar is returned unchanged.
+pub fn strip_comments(content: &str, extension: &str) -> Result<String> {
+    if get_language_impl(extension).is_none() {
+        return Ok(content.to_string());
+    }
+
+    let mut parser = get_pooled_parser(extension)?;
+    let tree = parser.parse(content, None);
+    return_pooled_parser(extension, parser);
+    let tree = tree.context("Failed to parse content for comment stripping")?;
+
+    let mut ranges = Vec::new();
+    collect_comment_ranges(tree.root_node(), &mut ranges);
+    if ranges.is_empty() {
+        return Ok(content.to_string());
+    }
+
+    // Blank out comment bytes, keeping newlines so multi-line comments leave empty lines
+    let mut stripped = String::with_capacity(content.len());
+    let mut touched_lines = HashSet::new();
+    let mut last = 0;
+    for (start, end) in ranges {
+        stripped.push_str(&content[last..start]);
+        let line = content[..start].matches('\n').count();
+        for (offset, _) in content[start..end].match_indices('\n').enumerate() {
+            stripped.push('\n');
+            touched_lines.insert(line + offset + 1);
+        }
+        touched_lines.insert(line);
+        last = end;
+    }
+    stripped.push_str(&content[last..]);
+
+    // Trim whitespace left behind on lines that contained a comment
+    let had_trailing_newline = stripped.ends_with('\n');
+    let mut output = stripped
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            if touched_lines.contains(&i) {
+                line.trim_end()
+            } else {
+                line
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    if had_trailing_newline {
+        output.push('\n');
+    }
+    Ok(output)
+}
+
+/// Rewrite the code of each extracted result with a line-preserving source transform
+/// such as [`strip_comments`].
+///
+/// The transform is applied to the whole file so nodes are recognised with full context;
+/// if a result's code does not line up with the file (e.g. it was synthesised), the
+/// snippet is transformed on its own instead.
+pub fn transform_results<F>(
+    path: &Path,
+    encoding: Option<SourceEncoding>,
+    results: &mut [SearchResult],
+    transform: F,
+) -> Result<()>
+where
+    F: Fn(&str, &str) -> Result<String>,
+{
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    let content = match encoding {
+        Some(encoding) => encoding::read_text_file(path, Some(encoding))?,
+        None => std::fs::read_to_string(path)
+            .with_context(|| format!("Failed to read file: {}", path.display()))?,
+    };
+    let transformed = transform(&content, extension)?;
+
+    let original_lines: Vec<&str> = content.lines().collect();
+    let mut transformed_lines: Vec<&str> = transformed.lines().collect();
+    // Blanking a final line that has no trailing newline leaves one line fewer behind
+    transformed_lines.resize(original_lines.len(), "");
+
+    for result in results.iter_mut() {
+        let (start, end) = result.lines;
+        let range = start.saturating_sub(1)..end.min(original_lines.len());
+        let aligned = start >= 1
+            && range.start < range.end
+            && original_lines[range.clone()].join("\n") == result.code;
+
+        result.code = if aligned {
+            transformed_lines[range].join("\n")
+        } else {
+            transform(&result.code, extension)?
+        };
+    }
+    Ok(())
+}
@@ -0,0 +1,143 @@
+//! In-process cache of extraction results for unchanged inputs.
+//!
+//! Repeated calls to `handle_extract` (for example from a watcher pipeline) with
+//! the same file and the same extraction request would otherwise re-read and
+//! re-parse the file every time. This cache stores the extracted blocks keyed by
+//! the request parameters and validates each entry against the MD5 hash of the
+//! file contents, so any edit to the file transparently invalidates it.
+//!
+//! It is enabled with `probe extract --cache` and lives only as long as the process.
+use anyhow::Result;
+use lazy_static::lazy_static;
+use lru::LruCache;
+use probe_code::encoding::SourceEncoding;
+use probe_code::models::SearchResult;
+use probe_code::utils::hash::md5_hex_file;
+use std::collections::HashSet;
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+const DEFAULT_CACHE_SIZE: usize = 512;
+
+lazy_static! {
+    /// Cached extraction results: request key -> (content MD5, extracted blocks)
+    static ref EXTRACTION_CACHE: Mutex<LruCache<String, (String, Vec<SearchResult>)>> = {
+        let cache_size = std::env::var("PROBE_EXTRACT_CACHE_SIZE")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(DEFAULT_CACHE_SIZE);
+        Mutex::new(LruCache::new(
+            NonZeroUsize::new(cache_size).unwrap_or(NonZeroUsize::new(DEFAULT_CACHE_SIZE).unwrap()),
+        ))
+    };
+}
+
+/// Number of times extraction actually parsed a file, used for testing
+static PARSE_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// Parameters identifying a single extraction request for one file
+pub struct ExtractionRequest<'a> {
+    pub path: &'a Path,
+    pub start_line: Option<usize>,
+    pub end_line: Option<usize>,
+    pub symbol: Option<&'a str>,
+    pub specific_lines: Option<&'a HashSet<usize>>,
+    pub allow_tests: bool,
+    pub context_lines: usize,
+    pub encoding: Option<SourceEncoding>,
+}
+
+impl ExtractionRequest<'_> {
+    /// Build the cache key for this request. The content hash is stored alongside
+    /// the cached value rather than in the key, so a changed file replaces its
+    /// previous entry instead of accumulating stale ones.
+    fn cache_key(&self) -> String {
+        let path = self
+            .path
+            .canonicalize()
+            .unwrap_or_else(|_| self.path.to_path_buf());
+        let mut lines: Vec<usize> = self
+            .specific_lines
+            .map(|set| set.iter().copied().collect())
+            .unwrap_or_default();
+        lines.sort_unstable();
+        format!(
+            "{}|{:?}|{:?}|{:?}|{:?}|{}|{}|{}",
+            path.display(),
+            self.start_line,
+            self.end_line,
+            self.symbol,
+            lines,
+            self.allow_tests,
+            self.context_lines,
+            self.encoding.map_or("utf-8", SourceEncoding::name)
+        )
+    }
+}
+
+/// Return cached extraction results for `request` if the file content is
+/// unchanged, otherwise run `extract` and cache its output.
+///
+/// Errors from `extract` are returned as-is and never cached. If the file hash
+/// cannot be computed (e.g. the file does not exist), the cache is bypassed.
+pub fn get_or_extract<F>(request: &ExtractionRequest, extract: F) -> Result<Vec<SearchResult>>
+where
+    F: FnOnce() -> Result<Vec<SearchResult>>,
+{
+    let debug_mode = std::env::var("DEBUG").unwrap_or_default() == "1";
+
+    let content_hash = match md5_hex_file(request.path) {
+        Ok(hash) => hash,
+        Err(_) => {
+            PARSE_COUNT.fetch_add(1, Ordering::Relaxed);
+            return extract();
+        }
+    };
+    let key = request.cache_key();
+
+    {
+        let mut cache = EXTRACTION_CACHE
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner());
+        if let Some((cached_hash, cached_results)) = cache.get(&key) {
+            if *cached_hash == content_hash {
+                if debug_mode {
+                    eprintln!("[DEBUG] Extraction cache hit for {:?}", request.path);
+                }
+                return Ok(cached_results.clone());
+            }
+            cache.pop(&key);
+            if debug_mode {
+                eprintln!(
+                    "[DEBUG] Extraction cache invalidated for {:?} (content changed)",
+                    request.path
+                );
+            }
+        }
+    }
+
+    PARSE_COUNT.fetch_add(1, Ordering::Relaxed);
+    let results = extract()?;
+
+    let mut cache = EXTRACTION_CACHE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    cache.put(key, (content_hash, results.clone()));
+
+    Ok(results)
+}
+
+/// Number of extractions that were not served from the cache
+pub fn parse_count() -> usize {
+    PARSE_COUNT.load(Ordering::Relaxed)
+}
+
+/// Remove all cached extraction results
+pub fn clear_extraction_cache() {
+    let mut cache = EXTRACTION_CACHE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    cache.clear();
+}
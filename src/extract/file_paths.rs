@@ -45,7 +45,8 @@ fn is_likely_file_path(path_str: &str) -> bool {
         // Check if just the suffix is a common property (regardless of prefix)
         // But allow common file extensions
         let common_extensions = [
-            "tf", "js", "ts", "rs", "go", "py", "rb", "php", "java", "cs", "cpp", "c", "h", "hpp",
+            "tf", "js", "ts", "rs", "go", "py", "rb", "php", "java", "kt", "cs", "cpp", "c", "h",
+            "hpp",
         ];
         if common_properties.contains(&suffix) && !common_extensions.contains(&suffix) {
             return false;
@@ -866,7 +867,10 @@ pub fn parse_file_with_line(input: &str, allow_tests: bool) -> Vec<FilePathInfo>
         }
 
         // Check if it's a range (contains a hyphen)
-        if let Some((start_str, end_str)) = line_spec.split_once('-') {
+        if let Some((start_str, end_str)) = line_spec
+            .split_once('-')
+            .filter(|_| !line_spec.contains(','))
+        {
             let start_num = start_str.parse::<usize>().ok();
             let end_num = end_str.parse::<usize>().ok();
 
@@ -941,10 +945,17 @@ pub fn parse_file_with_line(input: &str, allow_tests: bool) -> Vec<FilePathInfo>
                 }
             }
         } else {
-            // Try to parse as a single line number
-            let line_num = line_spec.parse::<usize>().ok();
+            // Try to parse as a single line number, or a comma-separated list of them
+            // (`file.rs:42,88` or `file.rs:42,file.rs:88`)
+            if let Some(line_nums) = parse_line_list(file_part, rest) {
+                let lines_set: HashSet<usize> = line_nums.iter().copied().collect();
+                // Several lines are extracted as separate blocks, so there is no single start
+                let start = if lines_set.len() == 1 {
+                    Some(line_nums[0])
+                } else {
+                    None
+                };
 
-            if let Some(num) = line_num {
                 // Handle glob pattern
                 if file_part.contains('*') || file_part.contains('{') {
                     // Use WalkBuilder to respect .gitignore
@@ -955,10 +966,7 @@ pub fn parse_file_with_line(input: &str, allow_tests: bool) -> Vec<FilePathInfo>
                             let should_include =
                                 !is_ignored_by_gitignore(&entry) && (allow_tests || !is_test);
                             if should_include {
-                                // Create a HashSet with just this line number
-                                let mut lines_set = HashSet::new();
-                                lines_set.insert(num);
-                                results.push((entry, Some(num), None, None, Some(lines_set)));
+                                results.push((entry, start, None, None, Some(lines_set.clone())));
                             }
                         }
                     }
@@ -970,10 +978,7 @@ pub fn parse_file_with_line(input: &str, allow_tests: bool) -> Vec<FilePathInfo>
                     if path.is_absolute() && path.exists() {
                         let is_test = is_test_file(&path);
                         if !is_ignored_by_gitignore(&path) && (allow_tests || !is_test) {
-                            // Create a HashSet with just this line number
-                            let mut lines_set = HashSet::new();
-                            lines_set.insert(num);
-                            results.push((path, Some(num), None, None, Some(lines_set)));
+                            results.push((path, start, None, None, Some(lines_set.clone())));
                         }
                     } else {
                         // Try special path resolution for relative paths or special syntax
@@ -983,15 +988,12 @@ pub fn parse_file_with_line(input: &str, allow_tests: bool) -> Vec<FilePathInfo>
                                 if !is_ignored_by_gitignore(&resolved_path)
                                     && (allow_tests || !is_test)
                                 {
-                                    // Create a HashSet with just this line number
-                                    let mut lines_set = HashSet::new();
-                                    lines_set.insert(num);
                                     results.push((
                                         resolved_path,
-                                        Some(num),
+                                        start,
                                         None,
                                         None,
-                                        Some(lines_set),
+                                        Some(lines_set.clone()),
                                     ));
                                 }
                             }
@@ -1005,15 +1007,12 @@ pub fn parse_file_with_line(input: &str, allow_tests: bool) -> Vec<FilePathInfo>
                                     let is_test = is_test_file(&path);
                                     if !is_ignored_by_gitignore(&path) && (allow_tests || !is_test)
                                     {
-                                        // Create a HashSet with just this line number
-                                        let mut lines_set = HashSet::new();
-                                        lines_set.insert(num);
                                         results.push((
                                             path,
-                                            Some(num),
+                                            start,
                                             None,
                                             None,
-                                            Some(lines_set),
+                                            Some(lines_set.clone()),
                                         ));
                                     }
                                 } else if debug_mode {
@@ -1083,6 +1082,19 @@ pub fn parse_file_with_line(input: &str, allow_tests: bool) -> Vec<FilePathInfo>
     results
 }
 
+/// Parse the line part of `file:42`, `file:42,88` or `file:42,file:88` into line numbers,
+/// in the order given. Anything after a further colon in an entry (e.g. a column) is ignored.
+fn parse_line_list(file_part: &str, rest: &str) -> Option<Vec<usize>> {
+    let repeated_prefix = format!("{file_part}:");
+    rest.split(',')
+        .map(|entry| {
+            let entry = entry.trim();
+            let entry = entry.strip_prefix(&repeated_prefix).unwrap_or(entry);
+            entry.split(':').next().unwrap_or("").parse::<usize>().ok()
+        })
+        .collect()
+}
+
 // Thread-local storage for the custom ignore patterns
 thread_local! {
     static CUSTOM_IGNORES: std::cell::RefCell<Vec<String>> = const { std::cell::RefCell::new(Vec::new()) };
@@ -1733,6 +1745,33 @@ Also: version 1.2.3, but not file.extension.that.is.too.long.to.be.real.
         assert_eq!(results.len(), 1);
     }
 
+    #[test]
+    fn test_parse_file_with_line_list() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("billing.rs");
+        fs::write(&file, "fn a() {}\nfn b() {}\nfn c() {}\n").unwrap();
+        let file_str = file.to_str().unwrap();
+
+        for input in [
+            format!("{file_str}:3,1"),
+            format!("{file_str}:3,{file_str}:1"),
+        ] {
+            let results = parse_file_with_line(&input, true);
+            assert_eq!(results.len(), 1, "{input}");
+            let (path, start, end, _, lines) = &results[0];
+            assert_eq!(path, &file);
+            assert_eq!((*start, *end), (None, None));
+            assert_eq!(lines.as_ref().unwrap(), &HashSet::from([1, 3]));
+        }
+
+        // A single line keeps its start line
+        let results = parse_file_with_line(&format!("{file_str}:2"), true);
+        assert_eq!(results[0].1, Some(2));
+        assert_eq!(results[0].4.as_ref().unwrap(), &HashSet::from([2]));
+    }
+
     #[test]
     fn test_parse_file_with_unsupported_extension() {
         // Test that unsupported extensions don't cause failures when they exist
@@ -4,13 +4,117 @@
 //! in various formats (terminal, markdown, plain, json, xml, color).
 
 use anyhow::Result;
+use lsp_daemon::BlameLine;
+use probe_code::language::overrides::effective_extension;
 use probe_code::models::SearchResult;
 use probe_code::search::search_tokens::sum_tokens_with_deduplication;
+use schemars::JsonSchema;
 use serde::Serialize;
 use std::fmt::Write as FmtWrite;
 use std::path::Path;
 
-use super::outline_diff_formatter;
+use super::{outline_diff_formatter, outline_formatter};
+
+/// JSON output of `probe extract`
+#[derive(Serialize, JsonSchema)]
+struct ExtractJsonOutput<R> {
+    /// Extracted code blocks, in input order
+    results: Vec<R>,
+    summary: JsonSummary,
+    /// Version of probe that produced the output
+    version: &'static str,
+    /// System prompt for LLM models, when `--prompt` is used
+    #[serde(skip_serializing_if = "Option::is_none")]
+    system_prompt: Option<String>,
+    /// Instructions for LLM models, when `--instructions` is used
+    #[serde(skip_serializing_if = "Option::is_none")]
+    user_instructions: Option<String>,
+    /// Input the targets were extracted from, when `--keep-input` is used
+    #[serde(skip_serializing_if = "Option::is_none")]
+    original_input: Option<String>,
+}
+
+/// Totals over the extracted blocks; dry runs only report the count
+#[derive(Serialize, JsonSchema)]
+struct JsonSummary {
+    count: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total_bytes: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    total_tokens: Option<usize>,
+}
+
+/// An extracted block without its code, reported on dry runs
+#[derive(Serialize, JsonSchema)]
+struct JsonDryRunResult<'a> {
+    file: &'a str,
+    /// First and last line of the block
+    #[serde(serialize_with = "serialize_lines_as_array")]
+    #[schemars(with = "[usize; 2]")]
+    lines: (usize, usize),
+    node_type: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lsp_info: Option<&'a serde_json::Value>,
+}
+
+/// An extracted code block
+#[derive(Serialize, JsonSchema)]
+struct JsonResult<'a> {
+    file: &'a str,
+    /// First and last line of the block
+    #[serde(serialize_with = "serialize_lines_as_array")]
+    #[schemars(with = "[usize; 2]")]
+    lines: (usize, usize),
+    node_type: &'a str,
+    code: &'a str,
+    /// File line number of each line in `code`, when line numbers are requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line_numbers: Option<Vec<usize>>,
+    /// Last commit of each line in `code` (null if unknown), when blame is
+    /// requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    blame: Option<Vec<Option<JsonBlame<'a>>>>,
+    /// File line numbers of the changed lines in `code`, when changed lines
+    /// are marked
+    #[serde(skip_serializing_if = "Option::is_none")]
+    changed_lines: Option<Vec<usize>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    symbol_signature: Option<&'a String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    original_input: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    lsp_info: Option<&'a serde_json::Value>,
+}
+
+/// Commit that last changed a line
+#[derive(Serialize, JsonSchema)]
+struct JsonBlame<'a> {
+    /// Full commit hash
+    commit: &'a str,
+    author: &'a str,
+    /// Commit time in seconds since the Unix epoch
+    time: i64,
+}
+
+// Helper function to serialize lines as an array
+fn serialize_lines_as_array<S>(
+    lines: &(usize, usize),
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error>
+where
+    S: serde::Serializer,
+{
+    use serde::ser::SerializeSeq;
+    let mut seq = serializer.serialize_seq(Some(2))?;
+    seq.serialize_element(&lines.0)?;
+    seq.serialize_element(&lines.1)?;
+    seq.end()
+}
+
+/// JSON Schema of the `--format json` output of `probe extract`
+pub fn extract_json_schema() -> schemars::Schema {
+    probe_code::utils::json_schema::output_schema::<ExtractJsonOutput<JsonResult<'static>>>()
+}
 
 /// A single internal function that handles both dry-run and non-dry-run formatting.
 ///
@@ -23,6 +127,10 @@ use super::outline_diff_formatter;
 /// * `user_instructions` - Optional user instructions for LLM models
 /// * `is_dry_run` - Whether this is a dry-run request (only file names/line numbers)
 /// * `symbols` - Whether to show symbol signatures instead of full code
+/// * `line_numbers` - Whether to prefix code with a file line-number gutter
+/// * `blame` - Per-result, per-line commit annotations to prefix code with
+/// * `changed_lines` - Whether to mark each result's `matched_lines` as changed lines
+#[allow(clippy::too_many_arguments)]
 fn format_extraction_internal(
     results: &[SearchResult],
     format: &str,
@@ -31,6 +139,9 @@ fn format_extraction_internal(
     user_instructions: Option<&str>,
     is_dry_run: bool,
     symbols: bool,
+    line_numbers: bool,
+    blame: Option<&[Vec<Option<BlameLine>>]>,
+    changed_lines: bool,
 ) -> Result<String> {
     let mut output = String::new();
 
@@ -39,38 +150,23 @@ fn format_extraction_internal(
         return outline_diff_formatter::format_outline_diff(results, original_input);
     }
 
+    // Outline shows declaration signatures only, so dry-run and symbols make no difference
+    if format == "outline" {
+        return outline_formatter::format_outline(results);
+    }
+
     match format {
         // ---------------------------------------
         // JSON output
         // ---------------------------------------
         "json" => {
+            // Prompts and the original input go at the root of the output
+            let system_prompt = system_prompt.map(str::to_string);
+            let user_instructions = user_instructions.map(str::to_string);
+            let original_input = original_input.map(str::to_string);
+
             if is_dry_run {
                 // DRY-RUN JSON structure
-                #[derive(Serialize)]
-                struct JsonDryRunResult<'a> {
-                    file: &'a str,
-                    #[serde(serialize_with = "serialize_lines_as_array")]
-                    lines: (usize, usize),
-                    node_type: &'a str,
-                    #[serde(skip_serializing_if = "Option::is_none")]
-                    lsp_info: Option<&'a serde_json::Value>,
-                }
-
-                // Helper function to serialize lines as an array
-                fn serialize_lines_as_array<S>(
-                    lines: &(usize, usize),
-                    serializer: S,
-                ) -> std::result::Result<S::Ok, S::Error>
-                where
-                    S: serde::Serializer,
-                {
-                    use serde::ser::SerializeSeq;
-                    let mut seq = serializer.serialize_seq(Some(2))?;
-                    seq.serialize_element(&lines.0)?;
-                    seq.serialize_element(&lines.1)?;
-                    seq.end()
-                }
-
                 let json_results: Vec<JsonDryRunResult> = results
                     .iter()
                     .map(|r| JsonDryRunResult {
@@ -82,68 +178,55 @@ fn format_extraction_internal(
                     .collect();
 
                 // Create a wrapper object with results and summary
-                let mut wrapper = serde_json::json!({
-                    "results": json_results,
-                    "summary": {
-                        "count": results.len(),
+                let wrapper = ExtractJsonOutput {
+                    results: json_results,
+                    summary: JsonSummary {
+                        count: results.len(),
+                        total_bytes: None,
+                        total_tokens: None,
                     },
-                    "version": probe_code::version::get_version()
-                });
-
-                // Add system prompt, user instructions, and original_input if provided
-                if let Some(prompt) = system_prompt {
-                    wrapper["system_prompt"] = serde_json::Value::String(prompt.to_string());
-                }
-
-                if let Some(instructions) = user_instructions {
-                    wrapper["user_instructions"] =
-                        serde_json::Value::String(instructions.to_string());
-                }
-
-                if let Some(input) = original_input {
-                    wrapper["original_input"] = serde_json::Value::String(input.to_string());
-                }
+                    version: probe_code::version::get_version(),
+                    system_prompt,
+                    user_instructions,
+                    original_input,
+                };
 
+                // Going through a JSON value keeps the object keys sorted
+                let wrapper = serde_json::to_value(&wrapper)?;
                 write!(output, "{}", serde_json::to_string_pretty(&wrapper)?)?;
             } else {
                 // NON-DRY-RUN JSON structure
-                #[derive(Serialize)]
-                struct JsonResult<'a> {
-                    file: &'a str,
-                    #[serde(serialize_with = "serialize_lines_as_array")]
-                    lines: (usize, usize),
-                    node_type: &'a str,
-                    code: &'a str,
-                    #[serde(skip_serializing_if = "Option::is_none")]
-                    symbol_signature: Option<&'a String>,
-                    #[serde(skip_serializing_if = "Option::is_none")]
-                    original_input: Option<&'a str>,
-                    #[serde(skip_serializing_if = "Option::is_none")]
-                    lsp_info: Option<&'a serde_json::Value>,
-                }
-
-                // Helper function to serialize lines as an array
-                fn serialize_lines_as_array<S>(
-                    lines: &(usize, usize),
-                    serializer: S,
-                ) -> std::result::Result<S::Ok, S::Error>
-                where
-                    S: serde::Serializer,
-                {
-                    use serde::ser::SerializeSeq;
-                    let mut seq = serializer.serialize_seq(Some(2))?;
-                    seq.serialize_element(&lines.0)?;
-                    seq.serialize_element(&lines.1)?;
-                    seq.end()
-                }
-
                 let json_results: Vec<JsonResult> = results
                     .iter()
-                    .map(|r| JsonResult {
+                    .enumerate()
+                    .map(|(index, r)| JsonResult {
                         file: &r.file,
                         lines: r.lines,
                         node_type: &r.node_type,
                         code: &r.code,
+                        line_numbers: line_numbers.then(|| {
+                            let start = r.lines.0.max(1);
+                            (start..start + r.code.lines().count()).collect()
+                        }),
+                        blame: blame.map(|blame| {
+                            blame[index]
+                                .iter()
+                                .map(|line| {
+                                    line.as_ref().map(|line| JsonBlame {
+                                        commit: &line.commit,
+                                        author: &line.author,
+                                        time: line.time,
+                                    })
+                                })
+                                .collect()
+                        }),
+                        changed_lines: changed_lines.then(|| {
+                            r.matched_lines
+                                .iter()
+                                .flatten()
+                                .map(|&line| r.lines.0.max(1) + line - 1)
+                                .collect()
+                        }),
                         symbol_signature: r.symbol_signature.as_ref(),
                         // We no longer put original_input per result. If you truly need it,
                         // you can uncomment the line below, but it's typically at the root.
@@ -168,36 +251,27 @@ fn format_extraction_internal(
                 };
 
                 // Create a wrapper object with results and summary
-                let mut wrapper = serde_json::json!({
-                    "results": json_results,
-                    "summary": {
-                        "count": results.len(),
-                        "total_bytes": if symbols {
-                            results.iter().map(|r| {
-                                r.symbol_signature.as_ref().map(|s| s.len()).unwrap_or(0)
-                            }).sum::<usize>()
+                let wrapper = ExtractJsonOutput {
+                    results: json_results,
+                    summary: JsonSummary {
+                        count: results.len(),
+                        total_bytes: Some(if symbols {
+                            results
+                                .iter()
+                                .map(|r| r.symbol_signature.as_ref().map(|s| s.len()).unwrap_or(0))
+                                .sum::<usize>()
                         } else {
                             results.iter().map(|r| r.code.len()).sum::<usize>()
-                        },
-                        "total_tokens": total_tokens,
+                        }),
+                        total_tokens: Some(total_tokens),
                     },
-                    "version": probe_code::version::get_version()
-                });
-
-                // Add system prompt, user instructions, and original_input if provided
-                if let Some(input) = original_input {
-                    wrapper["original_input"] = serde_json::Value::String(input.to_string());
-                }
-
-                if let Some(prompt) = system_prompt {
-                    wrapper["system_prompt"] = serde_json::Value::String(prompt.to_string());
-                }
-
-                if let Some(instructions) = user_instructions {
-                    wrapper["user_instructions"] =
-                        serde_json::Value::String(instructions.to_string());
-                }
+                    version: probe_code::version::get_version(),
+                    system_prompt,
+                    user_instructions,
+                    original_input,
+                };
 
+                let wrapper = serde_json::to_value(&wrapper)?;
                 write!(output, "{}", serde_json::to_string_pretty(&wrapper)?)?;
             }
         }
@@ -397,27 +471,53 @@ fn format_extraction_internal(
                 }
 
                 // For each result, we either skip the code if is_dry_run, or include it otherwise.
-                for result in results {
-                    // Common: show file (with format-specific prefix)
+                let mut previous: Option<&SearchResult> = None;
+                for (index, result) in results.iter().enumerate() {
+                    // Mark the gap between non-adjacent blocks of the same file
+                    if let Some(prev) = previous.filter(|prev| prev.file == result.file) {
+                        let (gap_start, gap_end) =
+                            (prev.lines.1 + 1, result.lines.0.saturating_sub(1));
+                        if gap_start <= gap_end {
+                            let gap = if gap_start == gap_end {
+                                format!("... line {gap_start} omitted ...")
+                            } else {
+                                format!("... lines {gap_start}-{gap_end} omitted ...")
+                            };
+                            if format == "markdown" {
+                                writeln!(output, "*{gap}*")?;
+                            } else {
+                                writeln!(output, "{}", gap.dimmed())?;
+                            }
+                            writeln!(output)?;
+                        }
+                    }
+                    previous = Some(result);
+
+                    // Common: show file (with format-specific prefix). Markdown puts the line
+                    // range in the heading, in the `path:start-end` form extract accepts
                     if format == "markdown" {
-                        writeln!(output, "## File: {}", result.file.yellow())?;
+                        if result.node_type == "file" {
+                            writeln!(output, "## File: {}", result.file)?;
+                        } else {
+                            writeln!(
+                                output,
+                                "## File: {}:{}-{}",
+                                result.file, result.lines.0, result.lines.1
+                            )?;
+                        }
                     } else {
                         writeln!(output, "File: {}", result.file.yellow())?;
                     }
 
                     // Show lines if not a full file
-                    if result.node_type != "file" {
-                        if format == "markdown" {
-                            writeln!(output, "### Lines: {}-{}", result.lines.0, result.lines.1)?;
-                        } else {
-                            writeln!(output, "Lines: {}-{}", result.lines.0, result.lines.1)?;
-                        }
+                    if result.node_type != "file" && format != "markdown" {
+                        writeln!(output, "Lines: {}-{}", result.lines.0, result.lines.1)?;
                     }
 
                     // Show node type if not file/context
                     if result.node_type != "file" && result.node_type != "context" {
                         if format == "markdown" {
-                            writeln!(output, "### Type: {}", result.node_type.cyan())?;
+                            writeln!(output, "### Type: {}", result.node_type)?;
                         } else {
                             writeln!(output, "Type: {}", result.node_type.cyan())?;
                         }
@@ -580,26 +680,43 @@ fn format_extraction_internal(
                             }
                         } else {
                             // Show full code (existing behavior)
-                            // Attempt a basic "highlight" approach by checking file extension
-                            let extension = Path::new(&result.file)
-                                .extension()
-                                .and_then(|ext| ext.to_str())
-                                .unwrap_or("");
+                            // Attempt a basic "highlight" approach with the language the file is
+                            // parsed as, after extension and shebang overrides
+                            let extension =
+                                effective_extension(Path::new(&result.file), &result.code);
                             let language = get_language_from_extension(extension);
+                            let code = if let Some(blame) = blame {
+                                with_blame_gutter(
+                                    &result.code,
+                                    result.lines.0,
+                                    &blame[index],
+                                    line_numbers,
+                                )
+                            } else if line_numbers {
+                                with_line_number_gutter(&result.code, result.lines.0)
+                            } else {
+                                result.code.clone()
+                            };
+                            let code = if changed_lines {
+                                with_changed_line_markers(
+                                    &code,
+                                    result.matched_lines.as_deref().unwrap_or_default(),
+                                )
+                            } else {
+                                code
+                            };
 
                             match format {
                                 "markdown" => {
-                                    if !language.is_empty() {
-                                        writeln!(output, "```{language}")?;
-                                    } else {
-                                        writeln!(output, "```")?;
-                                    }
-                                    writeln!(output, "{}", result.code)?;
-                                    writeln!(output, "```")?;
+                                    let fence = code_fence(&code);
+                                    writeln!(output, "{fence}{language}")?;
+                                    writeln!(output, "{code}")?;
+                                    writeln!(output, "{fence}")?;
+                                    writeln!(output)?;
                                 }
                                 "plain" => {
                                     writeln!(output)?;
-                                    writeln!(output, "{}", result.code)?;
+                                    writeln!(output, "{code}")?;
                                     writeln!(output)?;
                                     writeln!(output, "----------------------------------------")?;
                                     writeln!(output)?;
@@ -610,7 +727,7 @@ fn format_extraction_internal(
                                     } else {
                                         writeln!(output, "```")?;
                                     }
-                                    writeln!(output, "{}", result.code)?;
+                                    writeln!(output, "{code}")?;
                                     writeln!(output, "```")?;
                                 }
                                 // "terminal" or anything else not covered
@@ -620,7 +737,7 @@ fn format_extraction_internal(
                                     } else {
                                         writeln!(output, "```")?;
                                     }
-                                    writeln!(output, "{}", result.code)?;
+                                    writeln!(output, "{code}")?;
                                     writeln!(output, "```")?;
                                 }
                             }
@@ -959,6 +1076,9 @@ pub fn format_extraction_dry_run(
         user_instructions,
         true, // is_dry_run
         symbols,
+        false, // line_numbers
+        None,  // blame
+        false, // changed_lines
     )
 }
 
@@ -987,9 +1107,224 @@ pub fn format_extraction_results(
         user_instructions,
         false, // is_dry_run
         symbols,
+        false, // line_numbers
+        None,  // blame
+        false, // changed_lines
     )
 }
 
+/// Format the extraction results like [`format_extraction_results`], prefixing each code
+/// line with its file line number (e.g. `42 | let x = 1;`)
+///
+/// JSON output keeps `code` untouched and adds a parallel `line_numbers` array instead.
+///
+/// # Arguments
+///
+/// * `results` - The search results to format
+/// * `format` - The output format (terminal, markdown, plain, json, or color)
+/// * `system_prompt` - Optional system prompt for LLM models
+/// * `user_instructions` - Optional user instructions for LLM models
+/// * `symbols` - Whether to show symbol signatures instead of full code
+pub fn format_extraction_results_numbered(
+    results: &[SearchResult],
+    format: &str,
+    original_input: Option<&str>,
+    system_prompt: Option<&str>,
+    user_instructions: Option<&str>,
+    symbols: bool,
+) -> Result<String> {
+    format_extraction_internal(
+        results,
+        format,
+        original_input,
+        system_prompt,
+        user_instructions,
+        false, // is_dry_run
+        symbols,
+        true,  // line_numbers
+        None,  // blame
+        false, // changed_lines
+    )
+}
+
+/// Format the extraction results like [`format_extraction_results`], prefixing each code
+/// line with the short hash and author of the commit that last changed it, and its file line
+/// number when `line_numbers` is set (e.g. `1a2b3c4 alice 42 | let x = 1;`)
+///
+/// `blame` holds one annotation per code line for each result, as produced by
+/// [`super::blame::blame_results`]. JSON output keeps `code` untouched and adds a parallel
+/// `blame` array of `{commit, author, time}` objects, with `null` for lines without a commit.
+///
+/// # Arguments
+///
+/// * `results` - The search results to format
+/// * `format` - The output format (terminal, markdown, plain, json, or color)
+/// * `system_prompt` - Optional system prompt for LLM models
+/// * `user_instructions` - Optional user instructions for LLM models
+/// * `symbols` - Whether to show symbol signatures instead of full code
+/// * `line_numbers` - Whether to add file line numbers after the blame annotation
+/// * `blame` - Commit annotations of each result's code lines
+#[allow(clippy::too_many_arguments)]
+pub fn format_extraction_results_blamed(
+    results: &[SearchResult],
+    format: &str,
+    original_input: Option<&str>,
+    system_prompt: Option<&str>,
+    user_instructions: Option<&str>,
+    symbols: bool,
+    line_numbers: bool,
+    blame: &[Vec<Option<BlameLine>>],
+) -> Result<String> {
+    format_extraction_internal(
+        results,
+        format,
+        original_input,
+        system_prompt,
+        user_instructions,
+        false, // is_dry_run
+        symbols,
+        line_numbers,
+        Some(blame),
+        false, // changed_lines
+    )
+}
+
+/// Format the extraction results like [`format_extraction_results`], marking the changed
+/// lines of each block (its `matched_lines`, e.g. the lines a diff touched) with `+` and
+/// indenting the others to match. Line numbers and blame annotations follow the marker.
+///
+/// JSON output keeps `code` untouched and adds a `changed_lines` array of file line numbers.
+///
+/// # Arguments
+///
+/// * `results` - The search results to format
+/// * `format` - The output format (terminal, markdown, plain, json, or color)
+/// * `system_prompt` - Optional system prompt for LLM models
+/// * `user_instructions` - Optional user instructions for LLM models
+/// * `symbols` - Whether to show symbol signatures instead of full code
+/// * `line_numbers` - Whether to add file line numbers after the marker
+/// * `blame` - Optional commit annotations of each result's code lines
+#[allow(clippy::too_many_arguments)]
+pub fn format_extraction_results_changed(
+    results: &[SearchResult],
+    format: &str,
+    original_input: Option<&str>,
+    system_prompt: Option<&str>,
+    user_instructions: Option<&str>,
+    symbols: bool,
+    line_numbers: bool,
+    blame: Option<&[Vec<Option<BlameLine>>]>,
+) -> Result<String> {
+    format_extraction_internal(
+        results,
+        format,
+        original_input,
+        system_prompt,
+        user_instructions,
+        false, // is_dry_run
+        symbols,
+        line_numbers,
+        blame,
+        true, // changed_lines
+    )
+}
+
+/// Format the resolved range of each result as a bare `file:start-end` line, in result
+/// order and without any decoration, so callers can compare expected and actual ranges
+pub fn format_extraction_plain_paths(results: &[SearchResult]) -> String {
+    results
+        .iter()
+        .map(|result| format!("{}:{}-{}", result.file, result.lines.0, result.lines.1))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Prefix the lines of `code` listed in `changed` (1-based) with `+ ` and all others with
+/// two spaces, like the added lines of a diff
+fn with_changed_line_markers(code: &str, changed: &[usize]) -> String {
+    code.lines()
+        .enumerate()
+        .map(|(offset, line)| {
+            let marker = if changed.contains(&(offset + 1)) {
+                '+'
+            } else {
+                ' '
+            };
+            if line.is_empty() {
+                marker.to_string()
+            } else {
+                format!("{marker} {line}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Prefix every line of `code` with the short hash and author of its last commit, padded to
+/// the widest author in the block, followed by the file line number if `line_numbers` is set.
+/// Lines without a commit get a blank annotation.
+fn with_blame_gutter(
+    code: &str,
+    start_line: usize,
+    blame: &[Option<BlameLine>],
+    line_numbers: bool,
+) -> String {
+    let start_line = start_line.max(1);
+    let last_line = start_line + code.lines().count().saturating_sub(1);
+    let number_width = last_line.to_string().len();
+    let author_width = blame
+        .iter()
+        .flatten()
+        .map(|line| line.author.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    code.lines()
+        .enumerate()
+        .map(|(offset, line)| {
+            let (commit, author) = match blame.get(offset).and_then(Option::as_ref) {
+                Some(blame) => (
+                    blame.commit.get(..7).unwrap_or(&blame.commit),
+                    blame.author.as_str(),
+                ),
+                None => ("", ""),
+            };
+            let mut gutter = format!("{commit:<7} {author:<author_width$}");
+            if line_numbers {
+                let number = start_line + offset;
+                gutter.push_str(&format!(" {number:>number_width$}"));
+            }
+            if line.is_empty() {
+                format!("{gutter} |")
+            } else {
+                format!("{gutter} | {line}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Prefix every line of `code` with a right-aligned gutter holding its file line number,
+/// starting at `start_line`. The gutter is as wide as the largest number in the block.
+fn with_line_number_gutter(code: &str, start_line: usize) -> String {
+    let start_line = start_line.max(1);
+    let last_line = start_line + code.lines().count().saturating_sub(1);
+    let width = last_line.to_string().len();
+
+    code.lines()
+        .enumerate()
+        .map(|(offset, line)| {
+            let number = start_line + offset;
+            if line.is_empty() {
+                format!("{number:>width$} |")
+            } else {
+                format!("{number:>width$} | {line}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
 /// Format and print the extraction results in the specified format
 ///
 /// # Arguments
@@ -1029,24 +1364,33 @@ fn escape_xml(s: &str) -> String {
         .replace("'", "&apos;")
 }
 
+/// Fence for a Markdown code block: three backticks, or one more than the longest run of
+/// backticks in `code` so that the code cannot close the block early
+fn code_fence(code: &str) -> String {
+    let longest_run = code.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+    "`".repeat(longest_run.max(2) + 1)
+}
+
 /// Get the language name from a file extension for syntax highlighting
 pub fn get_language_from_extension(extension: &str) -> &'static str {
     match extension {
         "rs" => "rust",
         "py" => "python",
-        "js" => "javascript",
+        "js" | "mjs" | "cjs" => "javascript",
+        "jsx" => "jsx",
         "ts" => "typescript",
+        "tsx" => "tsx",
         "go" => "go",
         "c" | "h" => "c",
-        "cpp" | "cc" | "cxx" | "hpp" => "cpp",
+        "cpp" | "cc" | "cxx" | "hpp" | "hxx" => "cpp",
         "java" => "java",
         "rb" => "ruby",
         "php" => "php",
         "sh" => "bash",
-        "md" => "markdown",
+        "md" | "markdown" => "markdown",
         "json" => "json",
         "yaml" | "yml" => "yaml",
-        "html" => "html",
+        "html" | "htm" => "html",
         "css" => "css",
         "sql" => "sql",
         "kt" | "kts" => "kotlin",
@@ -1109,6 +1453,10 @@ mod tests {
             tokenized_content: None,
             lsp_info: Some(lsp_info),
             parent_context: None,
+            score_breakdown: None,
+            term_matches: None,
+            duplicates: None,
+            root: None,
         };
         let out =
             format_extraction_results(&[result], "terminal", None, None, None, false).unwrap();
@@ -1118,4 +1466,180 @@ mod tests {
             out
         );
     }
+
+    fn code_result(lines: (usize, usize), code: &str) -> SearchResult {
+        SearchResult {
+            file: "src/lib.rs".to_string(),
+            lines,
+            node_type: "function".to_string(),
+            code: code.to_string(),
+            symbol_signature: None,
+            matched_by_filename: None,
+            rank: None,
+            score: None,
+            tfidf_score: None,
+            bm25_score: None,
+            tfidf_rank: None,
+            bm25_rank: None,
+            new_score: None,
+            hybrid2_rank: None,
+            combined_score_rank: None,
+            file_unique_terms: None,
+            file_total_matches: None,
+            file_match_rank: None,
+            block_unique_terms: None,
+            block_total_matches: None,
+            parent_file_id: None,
+            block_id: None,
+            matched_keywords: None,
+            matched_lines: None,
+            tokenized_content: None,
+            lsp_info: None,
+            parent_context: None,
+            score_breakdown: None,
+            term_matches: None,
+            duplicates: None,
+            root: None,
+        }
+    }
+
+    #[test]
+    fn test_line_number_gutter_is_right_aligned() {
+        let code = "fn greet() {\n    let s = \"héllo wörld 👋\";\n\n    println!(\"{s}\");\n}";
+        let gutter = with_line_number_gutter(code, 98);
+        let lines: Vec<&str> = gutter.lines().collect();
+
+        assert_eq!(
+            lines,
+            vec![
+                " 98 | fn greet() {",
+                " 99 |     let s = \"héllo wörld 👋\";",
+                "100 |",
+                "101 |     println!(\"{s}\");",
+                "102 | }",
+            ]
+        );
+        // Multi-byte content never shifts the separator
+        assert!(lines.iter().all(|l| l.find('|') == Some(4)));
+    }
+
+    #[test]
+    fn test_numbered_text_output_uses_file_line_numbers() {
+        let result = code_result((7, 9), "fn a() {\n    b();\n}");
+        let out = format_extraction_results_numbered(&[result], "plain", None, None, None, false)
+            .unwrap();
+
+        assert!(out.contains("7 | fn a() {\n8 |     b();\n9 | }"), "{out}");
+    }
+
+    #[test]
+    fn test_plain_paths_lists_one_range_per_line() {
+        let first = code_result((3, 5), "fn a() {}");
+        let mut second = code_result((10, 42), "fn b() {}");
+        second.file = "web/app.tsx".to_string();
+        let out = format_extraction_plain_paths(&[first, second]);
+
+        assert_eq!(out, "src/lib.rs:3-5\nweb/app.tsx:10-42");
+        assert_eq!(format_extraction_plain_paths(&[]), "");
+    }
+
+    #[test]
+    fn test_markdown_output_fences_each_block() {
+        let rust = code_result((3, 5), "fn a() {\n    b();\n}");
+        let mut tsx = code_result((10, 12), "const doc = `\n```\n`;");
+        tsx.file = "web/app.tsx".to_string();
+        let out =
+            format_extraction_results(&[rust, tsx], "markdown", None, None, None, false).unwrap();
+
+        assert!(
+            out.contains("## File: src/lib.rs:3-5\n### Type: function\n```rust\nfn a() {\n    b();\n}\n```\n"),
+            "{out}"
+        );
+        // A fence longer than any backtick run in the code keeps the block intact
+        assert!(
+            out.contains("## File: web/app.tsx:10-12\n### Type: function\n````tsx\nconst doc = `\n```\n`;\n````\n"),
+            "{out}"
+        );
+    }
+
+    #[test]
+    fn test_numbered_json_output_adds_parallel_line_numbers() {
+        let result = code_result((41, 43), "let x = 1;\nlet y = 2;\nlet z = x + y;");
+        let out =
+            format_extraction_results_numbered(&[result.clone()], "json", None, None, None, false)
+                .unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&out).unwrap();
+
+        assert_eq!(parsed["results"][0]["code"], json!(result.code));
+        assert_eq!(parsed["results"][0]["line_numbers"], json!([41, 42, 43]));
+
+        // Plain extraction output is unchanged
+        let out = format_extraction_results(&[result], "json", None, None, None, false).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&out).unwrap();
+        assert!(parsed["results"][0].get("line_numbers").is_none());
+    }
+
+    fn blame_line(commit: &str, author: &str) -> Option<BlameLine> {
+        Some(BlameLine {
+            commit: commit.to_string(),
+            author: author.to_string(),
+            time: 1_700_000_000,
+        })
+    }
+
+    #[test]
+    fn test_blamed_text_output_annotates_each_line() {
+        let result = code_result((7, 9), "fn a() {\n    b();\n}");
+        let blame = vec![vec![
+            blame_line("1a2b3c4d5e6f", "alice"),
+            blame_line("9f8e7d6c5b4a", "bob"),
+            None,
+        ]];
+        let out = format_extraction_results_blamed(
+            &[result],
+            "plain",
+            None,
+            None,
+            None,
+            false,
+            true,
+            &blame,
+        )
+        .unwrap();
+
+        assert!(
+            out.contains(
+                "1a2b3c4 alice 7 | fn a() {\n9f8e7d6 bob   8 |     b();\n              9 | }"
+            ),
+            "{out}"
+        );
+    }
+
+    #[test]
+    fn test_blamed_json_output_adds_parallel_blame() {
+        let result = code_result((3, 4), "let x = 1;\nlet y = 2;");
+        let blame = vec![vec![blame_line("1a2b3c4d5e6f", "alice"), None]];
+        let out = format_extraction_results_blamed(
+            &[result.clone()],
+            "json",
+            None,
+            None,
+            None,
+            false,
+            false,
+            &blame,
+        )
+        .unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&out).unwrap();
+
+        assert_eq!(parsed["results"][0]["code"], json!(result.code));
+        assert_eq!(
+            parsed["results"][0]["blame"],
+            json!([
+                {"commit": "1a2b3c4d5e6f", "author": "alice", "time": 1_700_000_000},
+                null
+            ])
+        );
+        assert!(parsed["results"][0].get("line_numbers").is_none());
+    }
 }
@@ -0,0 +1,414 @@
+//! Extracting only the declarations of some kinds from a file, used with `only_kinds`.
+//!
+//! Instead of the whole file, each declaration of a requested kind is extracted on its own,
+//! together with the doc comments and attributes directly above it. Declarations are
+//! classified with the same labels as the `symbols` command, and the search stops at a match,
+//! so a class is extracted with its methods rather than next to them. Declarations local to a
+//! function body are never extracted on their own.
+//!
+//! With `public_only`, declarations outside the public API of their module are skipped, by the
+//! conventions of each language: `pub` in Rust, capitalized names in Go, names without a leading
+//! underscore in Python, exports in JavaScript and TypeScript and `public` in Java and C#.
+//! Other languages have every declaration treated as public.
+//!
+//! Files with syntax errors are not filtered: no declarations are extracted from them and the
+//! lines the parser could not make sense of are reported back, so the caller can extract the
+//! whole file instead.
+
+use anyhow::{Context, Result};
+use probe_code::encoding::{self, SourceEncoding};
+use probe_code::language::factory::get_language_impl;
+use probe_code::language::language_trait::LanguageImpl;
+use probe_code::language::overrides::{effective_extension, resolve_extension};
+use probe_code::language::{get_pooled_parser, return_pooled_parser};
+use probe_code::models::SearchResult;
+use std::path::Path;
+use std::str::FromStr;
+use tree_sitter::Node;
+
+use super::symbols::{extract_symbol_name, normalize_kind, semantic_symbol_node};
+
+/// Category of declaration that extraction can be restricted to with `only_kinds`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SymbolKind {
+    /// Functions and methods
+    Function,
+    Class,
+    Struct,
+    Enum,
+    Interface,
+    Trait,
+    /// Rust `impl` blocks
+    Impl,
+    /// Every type definition: classes, structs, enums, interfaces, traits and type aliases
+    Type,
+    /// Constants and statics
+    Const,
+    Variable,
+    Module,
+    Macro,
+}
+
+impl SymbolKind {
+    /// Returns true if a tree-sitter node kind declares a symbol of this kind
+    pub fn matches(self, node_kind: &str) -> bool {
+        let label = normalize_kind(node_kind);
+        let label = label.as_str();
+        match self {
+            SymbolKind::Function => matches!(label, "function" | "method"),
+            SymbolKind::Class => label == "class",
+            SymbolKind::Struct => label == "struct",
+            SymbolKind::Enum => label == "enum",
+            SymbolKind::Interface => label == "interface",
+            SymbolKind::Trait => label == "trait",
+            SymbolKind::Impl => label == "impl",
+            SymbolKind::Type => matches!(
+                label,
+                "class" | "struct" | "enum" | "interface" | "trait" | "type"
+            ),
+            SymbolKind::Const => matches!(label, "const" | "static"),
+            SymbolKind::Variable => label == "variable",
+            SymbolKind::Module => label == "module",
+            SymbolKind::Macro => label == "macro",
+        }
+    }
+}
+
+impl FromStr for SymbolKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "function" | "functions" | "fn" => Ok(SymbolKind::Function),
+            "class" | "classes" => Ok(SymbolKind::Class),
+            "struct" | "structs" => Ok(SymbolKind::Struct),
+            "enum" | "enums" => Ok(SymbolKind::Enum),
+            "interface" | "interfaces" => Ok(SymbolKind::Interface),
+            "trait" | "traits" => Ok(SymbolKind::Trait),
+            "impl" | "impls" => Ok(SymbolKind::Impl),
+            "type" | "types" => Ok(SymbolKind::Type),
+            "const" | "consts" | "constant" | "constants" => Ok(SymbolKind::Const),
+            "variable" | "variables" => Ok(SymbolKind::Variable),
+            "module" | "modules" => Ok(SymbolKind::Module),
+            "macro" | "macros" => Ok(SymbolKind::Macro),
+            _ => Err(format!(
+                "Unknown symbol kind: {s}. Expected one of: function, class, struct, enum, \
+                 interface, trait, impl, type, const, variable, module, macro"
+            )),
+        }
+    }
+}
+
+/// Declarations of some kinds extracted from one file
+#[derive(Debug)]
+pub struct Declarations {
+    /// Each declaration as its own result, in file order; empty if the file has syntax errors
+    pub results: Vec<SearchResult>,
+    /// 1-based line ranges the parser could not make sense of
+    pub error_lines: Vec<(usize, usize)>,
+}
+
+/// What to extract from a file
+struct Filter<'f> {
+    kinds: &'f [SymbolKind],
+    public_only: bool,
+    allow_tests: bool,
+    extension: &'f str,
+}
+
+/// The declaration a symbol node stands for, looking through export and decorator wrappers
+fn declaration_node<'a>(node: &Node<'a>, lang: &dyn LanguageImpl, source: &[u8]) -> Node<'a> {
+    let node = semantic_symbol_node(node, lang, source).unwrap_or(*node);
+    if node.kind() == "decorated_definition" {
+        if let Some(definition) = node.child_by_field_name("definition") {
+            return definition;
+        }
+    }
+    node
+}
+
+/// The node kind a symbol node declares, looking through export and decorator wrappers
+pub(crate) fn declared_kind(node: &Node, lang: &dyn LanguageImpl, source: &[u8]) -> &'static str {
+    declaration_node(node, lang, source).kind()
+}
+
+/// The closest declaration containing `node`, skipping the wrappers around `node` itself
+fn enclosing_declaration<'a>(node: &Node<'a>, lang: &dyn LanguageImpl) -> Option<Node<'a>> {
+    let mut current = node.parent();
+    while let Some(ancestor) = current {
+        let wrapper = matches!(
+            ancestor.kind(),
+            "export_statement" | "declare_statement" | "decorated_definition"
+        );
+        if !wrapper && lang.is_symbol_node(&ancestor) {
+            return Some(ancestor);
+        }
+        current = ancestor.parent();
+    }
+    None
+}
+
+/// Returns true if one of the modifier children of `node` is one of `words`
+fn has_modifier(node: &Node, source: &[u8], words: &[&str]) -> bool {
+    let mut cursor = node.walk();
+    let found = node
+        .children(&mut cursor)
+        .filter(|child| child.kind().contains("modifier"))
+        .any(|modifier| {
+            modifier
+                .utf8_text(source)
+                .is_ok_and(|text| text.split_whitespace().any(|word| words.contains(&word)))
+        });
+    found
+}
+
+/// Rust items are public with a plain `pub`. Impl blocks, and the items of traits and trait
+/// impls, have no visibility of their own and follow their trait or type.
+fn rust_is_public(node: &Node, enclosing: Option<&Node>, source: &[u8]) -> bool {
+    let mut cursor = node.walk();
+    let visibility = node
+        .children(&mut cursor)
+        .find(|child| child.kind() == "visibility_modifier");
+    if let Some(visibility) = visibility {
+        return visibility.utf8_text(source).is_ok_and(|text| text == "pub");
+    }
+    match node.kind() {
+        "impl_item" => true,
+        "macro_definition" => node
+            .prev_sibling()
+            .and_then(|previous| previous.utf8_text(source).ok())
+            .is_some_and(|text| text.contains("macro_export")),
+        _ => enclosing.is_some_and(|enclosing| {
+            enclosing.kind() == "trait_item"
+                || (enclosing.kind() == "impl_item"
+                    && enclosing.child_by_field_name("trait").is_some())
+        }),
+    }
+}
+
+/// Go exports capitalized names; a grouped `const` or `var` block is public if any of its
+/// names is
+fn go_is_public(node: &Node, source: &[u8]) -> bool {
+    let exported = |name: &str| name.chars().next().is_some_and(char::is_uppercase);
+    match node.kind() {
+        "type_declaration" | "const_declaration" | "var_declaration" => {
+            let mut cursor = node.walk();
+            let found = node.named_children(&mut cursor).any(|spec| {
+                spec.child_by_field_name("name")
+                    .and_then(|name| name.utf8_text(source).ok())
+                    .is_some_and(exported)
+            });
+            found
+        }
+        _ => exported(&extract_symbol_name(node, source)),
+    }
+}
+
+/// Whether `node` belongs to the public API of its module. A member is only public if the
+/// declaration containing it is too.
+fn is_public(node: &Node, source: &[u8], lang: &dyn LanguageImpl, extension: &str) -> bool {
+    let enclosing = enclosing_declaration(node, lang);
+    let declaration = declaration_node(node, lang, source);
+    let public = match extension {
+        "rs" => rust_is_public(&declaration, enclosing.as_ref(), source),
+        "go" => go_is_public(&declaration, source),
+        "py" => {
+            let name = extract_symbol_name(&declaration, source);
+            !name.starts_with('_') || (name.starts_with("__") && name.ends_with("__"))
+        }
+        "js" | "jsx" | "ts" | "tsx" => match enclosing {
+            // Class and interface members are public unless marked private
+            Some(_) => {
+                !has_modifier(&declaration, source, &["private", "protected"])
+                    && !extract_symbol_name(&declaration, source).starts_with('#')
+            }
+            None => {
+                node.kind() == "export_statement"
+                    || node
+                        .parent()
+                        .is_some_and(|parent| parent.kind() == "export_statement")
+            }
+        },
+        // Interface members are implicitly public
+        "java" | "cs" => {
+            has_modifier(&declaration, source, &["public"])
+                || (enclosing.is_some_and(|enclosing| enclosing.kind().contains("interface"))
+                    && !has_modifier(&declaration, source, &["private"]))
+        }
+        _ => true,
+    };
+    public && enclosing.is_none_or(|enclosing| is_public(&enclosing, source, lang, extension))
+}
+
+/// Collect the outermost declarations matching `filter` below `node`, in source order.
+/// Syntax errors are not searched, and declarations containing one are skipped.
+fn collect_declarations<'a>(
+    node: Node<'a>,
+    source: &[u8],
+    lang: &dyn LanguageImpl,
+    filter: &Filter,
+    found: &mut Vec<Node<'a>>,
+) {
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if child.is_error() || (!filter.allow_tests && lang.is_test_node(&child, source)) {
+            continue;
+        }
+        if lang.is_symbol_node(&child) {
+            let kind = declared_kind(&child, lang, source);
+            if filter.kinds.iter().any(|wanted| wanted.matches(kind)) {
+                let public =
+                    !filter.public_only || is_public(&child, source, lang, filter.extension);
+                if public && !child.has_error() {
+                    found.push(child);
+                }
+                continue;
+            }
+            if SymbolKind::Function.matches(kind) {
+                continue;
+            }
+        }
+        collect_declarations(child, source, lang, filter, found);
+    }
+}
+
+/// Collect the 1-based line ranges of the syntax errors below `node`
+fn collect_error_lines(node: Node, lines: &mut Vec<(usize, usize)>) {
+    if node.is_error() || node.is_missing() {
+        let range = (node.start_position().row + 1, end_row(&node) + 1);
+        if lines.last() != Some(&range) {
+            lines.push(range);
+        }
+        return;
+    }
+    if !node.has_error() {
+        return;
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_error_lines(child, lines);
+    }
+}
+
+/// Last row a node covers; nodes that end with their newline end at column 0 of the next row
+fn end_row(node: &Node) -> usize {
+    let end = node.end_position();
+    if end.column == 0 && end.row > node.start_position().row {
+        end.row - 1
+    } else {
+        end.row
+    }
+}
+
+/// First row of `node` including the comments and attributes directly above it
+fn leading_row(node: &Node) -> usize {
+    let mut start = *node;
+    while let Some(previous) = start.prev_sibling() {
+        let kind = previous.kind();
+        let attached =
+            kind.contains("comment") || kind.contains("attribute") || kind == "decorator";
+        if !attached || end_row(&previous) + 1 < start.start_position().row {
+            break;
+        }
+        start = previous;
+    }
+    start.start_position().row
+}
+
+/// Extract each declaration of `kinds` in the file at `path` as its own result, in file order,
+/// skipping those outside the public API with `public_only`.
+///
+/// Returns `None` if the file has no tree-sitter grammar, and no results but the error lines if
+/// it has syntax errors, so the caller can fall back to extracting the whole file.
+pub fn extract_declarations(
+    path: &Path,
+    kinds: &[SymbolKind],
+    public_only: bool,
+    allow_tests: bool,
+    encoding: Option<SourceEncoding>,
+) -> Result<Option<Declarations>> {
+    let content = encoding::read_text_file(path, encoding)
+        .with_context(|| format!("Failed to read file: {path:?}"))?;
+    let extension = effective_extension(path, &content);
+    let Some(lang) = get_language_impl(extension) else {
+        return Ok(None);
+    };
+
+    let mut parser = get_pooled_parser(extension)?;
+    let tree = parser.parse(&content, None);
+    return_pooled_parser(extension, parser);
+    let Some(tree) = tree else {
+        return Ok(None);
+    };
+
+    let mut error_lines = Vec::new();
+    collect_error_lines(tree.root_node(), &mut error_lines);
+    if !error_lines.is_empty() {
+        return Ok(Some(Declarations {
+            results: Vec::new(),
+            error_lines,
+        }));
+    }
+
+    let source = content.as_bytes();
+    let filter = Filter {
+        kinds,
+        public_only,
+        allow_tests,
+        extension: resolve_extension(extension),
+    };
+    let mut declarations = Vec::new();
+    collect_declarations(
+        tree.root_node(),
+        source,
+        lang.as_ref(),
+        &filter,
+        &mut declarations,
+    );
+
+    let lines: Vec<&str> = content.lines().collect();
+    let results = declarations
+        .iter()
+        .map(|node| {
+            let start = leading_row(node);
+            let end = end_row(node).min(lines.len().saturating_sub(1)).max(start);
+            SearchResult {
+                file: path.to_string_lossy().to_string(),
+                lines: (start + 1, end + 1),
+                node_type: declared_kind(node, lang.as_ref(), source).to_string(),
+                code: lines[start..=end].join("\n"),
+                symbol_signature: None,
+                matched_by_filename: None,
+                rank: None,
+                score: None,
+                tfidf_score: None,
+                bm25_score: None,
+                tfidf_rank: None,
+                bm25_rank: None,
+                new_score: None,
+                hybrid2_rank: None,
+                combined_score_rank: None,
+                file_unique_terms: None,
+                file_total_matches: None,
+                file_match_rank: None,
+                block_unique_terms: None,
+                block_total_matches: None,
+                parent_file_id: None,
+                block_id: None,
+                matched_keywords: None,
+                matched_lines: None,
+                tokenized_content: None,
+                lsp_info: None,
+                parent_context: None,
+                score_breakdown: None,
+                term_matches: None,
+                duplicates: None,
+                root: None,
+            }
+        })
+        .collect();
+    Ok(Some(Declarations {
+        results,
+        error_lines,
+    }))
+}
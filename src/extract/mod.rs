@@ -4,11 +4,19 @@
 //! and optional line numbers. When a line number is specified, it uses tree-sitter to find
 //! the closest suitable parent node (function, struct, class, etc.) for that line.
 
+pub mod blame;
+pub mod clipboard;
+pub mod comment_stripper;
+pub mod extraction_cache;
 mod file_paths;
 mod formatter;
+pub mod kind_filter;
 mod outline_diff_formatter;
+mod outline_formatter;
 mod processor;
 mod prompts;
+pub mod signature_extractor;
+pub mod string_redactor;
 pub mod symbol_finder;
 pub mod symbols;
 
@@ -20,14 +28,19 @@ pub use file_paths::{
 };
 #[allow(unused_imports)]
 pub use formatter::{
-    format_and_print_extraction_results, format_extraction_dry_run, format_extraction_results,
+    extract_json_schema, format_and_print_extraction_results, format_extraction_dry_run,
+    format_extraction_plain_paths, format_extraction_results, format_extraction_results_blamed,
+    format_extraction_results_changed, format_extraction_results_numbered,
 };
 #[allow(unused_imports)]
+pub use kind_filter::SymbolKind;
+#[allow(unused_imports)]
 pub use processor::process_file_for_extraction as process_file_for_extraction_multi;
 #[allow(unused_imports)]
 pub use prompts::PromptTemplate;
 
 use anyhow::{anyhow, Result};
+use probe_code::encoding::SourceEncoding;
 use probe_code::extract::file_paths::{set_custom_ignores, FilePathInfo};
 use probe_code::models::SearchResult;
 use std::collections::HashSet;
@@ -111,8 +124,13 @@ pub struct ExtractOptions {
     pub input_file: Option<String>,
     /// Whether to write to clipboard
     pub to_clipboard: bool,
+    /// Maximum number of bytes to write to the clipboard; longer output is truncated with a
+    /// note and written in full to a temporary file
+    pub clipboard_max_bytes: Option<usize>,
     /// Whether to perform a dry run
     pub dry_run: bool,
+    /// Whether to print only the resolved `file:start-end` range of each block
+    pub plain_paths: bool,
     /// Whether to parse input as git diff format
     pub diff: bool,
     /// Whether to allow test files and test code blocks
@@ -128,6 +146,31 @@ pub struct ExtractOptions {
     /// Whether to include LSP-based enrichment data (compatibility field).
     #[allow(dead_code)]
     pub lsp: bool,
+    /// Whether to reuse cached results for files whose content hash is unchanged
+    pub cache: bool,
+    /// Whether to remove comments from extracted code (line numbers are preserved)
+    pub strip_comments: bool,
+    /// Whether to replace the contents of string literals with a redaction placeholder
+    pub redact_strings: bool,
+    /// Whether to replace function bodies with `{ ... }`, keeping doc comments, attributes
+    /// and signatures
+    pub signatures_only: bool,
+    /// Only extract declarations of these kinds from files extracted whole, one block each.
+    /// Files without a grammar are extracted whole, with a warning
+    pub only_kinds: Option<Vec<SymbolKind>>,
+    /// With `only_kinds`, skip declarations outside the public API of their module
+    pub public_only: bool,
+    /// Whether to prefix extracted code with a gutter of file line numbers
+    pub line_numbers: bool,
+    /// Whether to annotate each extracted line with the commit and author that last changed
+    /// it (lines outside a git repository are left unannotated)
+    pub blame: bool,
+    /// Whether to extract the enclosing block of each changed line of a diff separately and
+    /// mark the changed lines within it
+    pub diff_blocks: bool,
+    /// Encoding of the extracted files: an encoding label such as `shift_jis` or `latin1`,
+    /// or `auto` to detect it per file. `None` reads files as UTF-8.
+    pub encoding: Option<String>,
 }
 
 /// Handle the extract command
@@ -135,8 +178,13 @@ pub fn handle_extract(options: ExtractOptions) -> Result<()> {
     use arboard::Clipboard;
     use colored::*;
 
+    let encoding = SourceEncoding::parse_option(options.encoding.as_deref())?;
+
+    // Status messages are only printed for text-based formats, and never around plain paths
+    let show_status = options.format != "json" && options.format != "xml" && !options.plain_paths;
+
     // Print version at the start for text-based formats
-    if options.format != "json" && options.format != "xml" {
+    if show_status {
         println!("Probe version: {}", crate::version::get_version());
     }
 
@@ -177,6 +225,36 @@ pub fn handle_extract(options: ExtractOptions) -> Result<()> {
             "[DEBUG] Instructions: {instructions:?}",
             instructions = options.instructions
         );
+        eprintln!("[DEBUG] Cache: {cache}", cache = options.cache);
+        eprintln!(
+            "[DEBUG] Strip comments: {strip}",
+            strip = options.strip_comments
+        );
+        eprintln!(
+            "[DEBUG] Redact strings: {redact}",
+            redact = options.redact_strings
+        );
+        eprintln!(
+            "[DEBUG] Signatures only: {signatures_only}",
+            signatures_only = options.signatures_only
+        );
+        eprintln!(
+            "[DEBUG] Only kinds: {only_kinds:?}",
+            only_kinds = options.only_kinds
+        );
+        eprintln!(
+            "[DEBUG] Public only: {public_only}",
+            public_only = options.public_only
+        );
+        eprintln!(
+            "[DEBUG] Line numbers: {line_numbers}",
+            line_numbers = options.line_numbers
+        );
+        eprintln!("[DEBUG] Blame: {blame}", blame = options.blame);
+        eprintln!(
+            "[DEBUG] Diff blocks: {diff_blocks}",
+            diff_blocks = options.diff_blocks
+        );
     }
 
     // Set custom ignore patterns
@@ -189,7 +267,7 @@ pub fn handle_extract(options: ExtractOptions) -> Result<()> {
 
     if options.from_clipboard {
         // Read from clipboard
-        if options.format != "json" && options.format != "xml" {
+        if show_status {
             println!("{}", "Reading from clipboard...".bold().blue());
         }
         let mut clipboard = Clipboard::new()?;
@@ -251,14 +329,14 @@ pub fn handle_extract(options: ExtractOptions) -> Result<()> {
         }
 
         if file_paths.is_empty() {
-            if options.format != "json" && options.format != "xml" {
+            if show_status {
                 println!("{}", "No file paths found in clipboard.".yellow().bold());
             }
             return Ok(());
         }
     } else if let Some(input_file_path) = &options.input_file {
         // Read from input file
-        if options.format != "json" && options.format != "xml" {
+        if show_status {
             println!(
                 "{}",
                 format!("Reading from file: {input_file_path}...")
@@ -335,7 +413,7 @@ pub fn handle_extract(options: ExtractOptions) -> Result<()> {
         }
 
         if file_paths.is_empty() {
-            if options.format != "json" && options.format != "xml" {
+            if show_status {
                 println!(
                     "{}",
                     format!("No file paths found in input file: {input_file_path}")
@@ -351,7 +429,7 @@ pub fn handle_extract(options: ExtractOptions) -> Result<()> {
 
         if is_stdin_available {
             // Read from stdin
-            if options.format != "json" && options.format != "xml" {
+            if show_status {
                 println!("{}", "Reading from stdin...".bold().blue());
             }
             let mut buffer = String::new();
@@ -396,7 +474,7 @@ pub fn handle_extract(options: ExtractOptions) -> Result<()> {
             }
         } else {
             // No arguments and no stdin, show help
-            if options.format != "json" && options.format != "xml" {
+            if show_status {
                 println!(
                     "{}",
                     "No files specified and no stdin input detected."
@@ -426,7 +504,7 @@ pub fn handle_extract(options: ExtractOptions) -> Result<()> {
         }
 
         if file_paths.is_empty() {
-            if options.format != "json" && options.format != "xml" {
+            if show_status {
                 println!("{}", "No file paths found in stdin.".yellow().bold());
             }
             return Ok(());
@@ -477,8 +555,19 @@ pub fn handle_extract(options: ExtractOptions) -> Result<()> {
         }
     }
 
+    // Extract the enclosing block of each changed line on its own, instead of one range
+    // spanning the first to the last change in a file
+    if options.diff_blocks {
+        for (_, start_line, end_line, _, lines) in &mut file_paths {
+            if lines.is_some() {
+                *start_line = None;
+                *end_line = None;
+            }
+        }
+    }
+
     // Only print file information for non-JSON/XML formats
-    if options.format != "json" && options.format != "xml" {
+    if show_status {
         println!("{text}", text = "Files to extract:".bold().green());
 
         for (path, start_line, end_line, symbol, lines) in &file_paths {
@@ -575,6 +664,13 @@ pub fn handle_extract(options: ExtractOptions) -> Result<()> {
         context_lines: usize,
         debug_mode: bool,
         format: String,
+        cache: bool,
+        strip_comments: bool,
+        redact_strings: bool,
+        signatures_only: bool,
+        only_kinds: Option<Vec<SymbolKind>>,
+        public_only: bool,
+        encoding: Option<SourceEncoding>,
 
         #[allow(dead_code)]
         original_input: Option<String>,
@@ -598,6 +694,13 @@ pub fn handle_extract(options: ExtractOptions) -> Result<()> {
                 context_lines: options.context_lines,
                 debug_mode,
                 format: options.format.clone(),
+                cache: options.cache,
+                strip_comments: options.strip_comments,
+                redact_strings: options.redact_strings,
+                signatures_only: options.signatures_only,
+                only_kinds: options.only_kinds.clone(),
+                public_only: options.public_only,
+                encoding,
                 original_input: original_input.clone(),
                 system_prompt: system_prompt.clone(),
                 user_instructions: options.instructions.clone(),
@@ -647,16 +750,129 @@ pub fn handle_extract(options: ExtractOptions) -> Result<()> {
             eprintln!("[DEBUG] Test file detected: {:?}", params.path);
         }
 
-        match processor::process_file_for_extraction(
-            &params.path,
-            params.start_line,
-            params.end_line,
-            params.symbol.as_deref(),
-            params.allow_tests,
-            params.context_lines,
-            params.specific_lines.as_ref(),
-            false, // symbols functionality removed
-        ) {
+        let extract = || {
+            processor::process_file_for_extraction_with_encoding(
+                &params.path,
+                params.start_line,
+                params.end_line,
+                params.symbol.as_deref(),
+                params.allow_tests,
+                params.context_lines,
+                params.specific_lines.as_ref(),
+                false, // symbols functionality removed
+                params.encoding,
+            )
+        };
+
+        // Files extracted whole are reduced to the declarations of the requested kinds
+        let whole_file = params.start_line.is_none()
+            && params.end_line.is_none()
+            && params.symbol.is_none()
+            && params.specific_lines.is_none();
+        let declarations = match &params.only_kinds {
+            Some(kinds) if whole_file => kind_filter::extract_declarations(
+                &params.path,
+                kinds,
+                params.public_only,
+                params.allow_tests,
+                params.encoding,
+            )
+            .transpose(),
+            _ => None,
+        };
+        match &declarations {
+            None if params.only_kinds.is_some() && whole_file => eprintln!(
+                "{}",
+                format!(
+                    "Warning: could not parse {}, extracting the whole file",
+                    params.path.display()
+                )
+                .yellow()
+            ),
+            Some(Ok(found)) if !found.error_lines.is_empty() => {
+                let lines: Vec<String> = found
+                    .error_lines
+                    .iter()
+                    .map(|&(start, end)| {
+                        if start == end {
+                            start.to_string()
+                        } else {
+                            format!("{start}-{end}")
+                        }
+                    })
+                    .collect();
+                eprintln!(
+                    "{}",
+                    format!(
+                        "Warning: syntax errors in {} at lines {}, extracting the whole file",
+                        params.path.display(),
+                        lines.join(", ")
+                    )
+                    .yellow()
+                );
+            }
+            _ => {}
+        }
+        let declarations = declarations
+            .filter(|found| !matches!(found, Ok(found) if !found.error_lines.is_empty()));
+
+        let extraction_result = match declarations {
+            Some(declarations) => declarations.map(|found| found.results),
+            None if params.cache => extraction_cache::get_or_extract(
+                &extraction_cache::ExtractionRequest {
+                    path: &params.path,
+                    start_line: params.start_line,
+                    end_line: params.end_line,
+                    symbol: params.symbol.as_deref(),
+                    specific_lines: params.specific_lines.as_ref(),
+                    allow_tests: params.allow_tests,
+                    context_lines: params.context_lines,
+                    encoding: params.encoding,
+                },
+                extract,
+            ),
+            None => extract(),
+        };
+
+        // Signatures are found in the file as extracted; the line-preserving transforms below
+        // then fall back to the shortened snippets
+        let extraction_result = if params.signatures_only {
+            extraction_result.and_then(|mut result_vec| {
+                signature_extractor::signatures_only_results(
+                    &params.path,
+                    params.encoding,
+                    &mut result_vec,
+                )?;
+                Ok(result_vec)
+            })
+        } else {
+            extraction_result
+        };
+
+        let extraction_result = if params.strip_comments || params.redact_strings {
+            extraction_result.and_then(|mut result_vec| {
+                comment_stripper::transform_results(
+                    &params.path,
+                    params.encoding,
+                    &mut result_vec,
+                    |content, extension| {
+                        let mut content = content.to_string();
+                        if params.strip_comments {
+                            content = comment_stripper::strip_comments(&content, extension)?;
+                        }
+                        if params.redact_strings {
+                            content = string_redactor::redact_strings(&content, extension)?;
+                        }
+                        Ok(content)
+                    },
+                )?;
+                Ok(result_vec)
+            })
+        } else {
+            extraction_result
+        };
+
+        match extraction_result {
             Ok(result_vec) => {
                 if params.debug_mode {
                     eprintln!(
@@ -812,6 +1028,9 @@ pub fn handle_extract(options: ExtractOptions) -> Result<()> {
 
     results = new_results;
 
+    // Emit the remaining blocks of each file in source order
+    results.sort_by(|a, b| a.file.cmp(&b.file).then(a.lines.0.cmp(&b.lines.0)));
+
     if debug_mode {
         eprintln!(
             "[DEBUG] After deduplication: {len} results",
@@ -873,7 +1092,9 @@ pub fn handle_extract(options: ExtractOptions) -> Result<()> {
         };
 
         // Format the results
-        let result = if options.dry_run {
+        let result = if options.plain_paths {
+            Ok(formatter::format_extraction_plain_paths(&results))
+        } else if options.dry_run {
             formatter::format_extraction_dry_run(
                 &results,
                 &options.format,
@@ -882,6 +1103,38 @@ pub fn handle_extract(options: ExtractOptions) -> Result<()> {
                 options.instructions.as_deref(),
                 false, // symbols functionality removed
             )
+        } else if options.diff_blocks {
+            let blame = options.blame.then(|| blame::blame_results(&results));
+            formatter::format_extraction_results_changed(
+                &results,
+                &options.format,
+                original_input.as_deref(),
+                system_prompt.as_deref(),
+                options.instructions.as_deref(),
+                false, // symbols functionality removed
+                options.line_numbers,
+                blame.as_deref(),
+            )
+        } else if options.blame {
+            formatter::format_extraction_results_blamed(
+                &results,
+                &options.format,
+                original_input.as_deref(),
+                system_prompt.as_deref(),
+                options.instructions.as_deref(),
+                false, // symbols functionality removed
+                options.line_numbers,
+                &blame::blame_results(&results),
+            )
+        } else if options.line_numbers {
+            formatter::format_extraction_results_numbered(
+                &results,
+                &options.format,
+                original_input.as_deref(),
+                system_prompt.as_deref(),
+                options.instructions.as_deref(),
+                false, // symbols functionality removed
+            )
         } else {
             formatter::format_extraction_results(
                 &results,
@@ -903,16 +1156,35 @@ pub fn handle_extract(options: ExtractOptions) -> Result<()> {
     match res {
         Ok(formatted_output) => {
             if options.to_clipboard {
-                // Write to clipboard
+                // Write to clipboard, truncated to the size limit if there is one
+                let truncated = match options.clipboard_max_bytes {
+                    Some(max_bytes) if formatted_output.len() > max_bytes => {
+                        let full_output = clipboard::write_full_output(&formatted_output);
+                        let mut warning = format!(
+                            "Warning: output is {} bytes, only the first {max_bytes} bytes were copied to the clipboard.",
+                            formatted_output.len()
+                        );
+                        if let Some(path) = &full_output {
+                            warning
+                                .push_str(&format!(" Full output written to {}", path.display()));
+                        }
+                        eprintln!("{}", warning.yellow());
+                        clipboard::truncate_for_clipboard(
+                            &formatted_output,
+                            max_bytes,
+                            full_output.as_deref(),
+                        )
+                    }
+                    _ => None,
+                };
+                let clipboard_text = truncated.as_deref().unwrap_or(&formatted_output);
+
                 let mut clipboard = Clipboard::new()?;
-                clipboard.set_text(&formatted_output)?;
+                clipboard.set_text(clipboard_text)?;
                 println!("{}", "Results copied to clipboard.".green().bold());
 
                 if debug_mode {
-                    println!(
-                        "[DEBUG] Wrote {} bytes to clipboard",
-                        formatted_output.len()
-                    );
+                    println!("[DEBUG] Wrote {} bytes to clipboard", clipboard_text.len());
                 }
             } else {
                 // Print to stdout
@@ -921,7 +1193,7 @@ pub fn handle_extract(options: ExtractOptions) -> Result<()> {
         }
         Err(e) => {
             // Only print error messages for non-JSON/XML formats
-            if options.format != "json" && options.format != "xml" {
+            if show_status {
                 eprintln!("{}", format!("Error formatting results: {e}").red());
             }
             if debug_mode {
@@ -931,7 +1203,7 @@ pub fn handle_extract(options: ExtractOptions) -> Result<()> {
     }
 
     // Print summary of errors if any (only for non-JSON/XML formats)
-    if !errors.is_empty() && options.format != "json" && options.format != "xml" {
+    if !errors.is_empty() && show_status {
         println!();
         println!(
             "{} {} {}",
@@ -0,0 +1,214 @@
+//! Outline format: a collapsed overview of extracted code.
+//!
+//! Instead of full bodies, each extracted range is shown as the signature lines of the
+//! declarations it contains, indented by nesting depth. Declarations come from the symbol
+//! tree in [`super::symbols`]; files without a tree-sitter grammar fall back to their comment
+//! and heading lines.
+
+use anyhow::Result;
+use probe_code::models::SearchResult;
+use std::collections::HashMap;
+use std::fmt::Write as FmtWrite;
+use std::path::Path;
+
+use super::symbols::{extract_symbols_with_options, SymbolNode, SymbolOptions};
+
+/// A single line of the outline
+struct OutlineEntry {
+    line: usize,
+    depth: usize,
+    text: String,
+}
+
+/// Format extraction results as outlines of their declarations
+pub fn format_outline(results: &[SearchResult]) -> Result<String> {
+    let mut output = String::new();
+    if results.is_empty() {
+        writeln!(output, "No results found.")?;
+        return Ok(output);
+    }
+
+    // Symbols are parsed once per file, even when several ranges of it were extracted
+    let mut file_symbols: HashMap<&str, Option<Vec<SymbolNode>>> = HashMap::new();
+    let options = SymbolOptions {
+        allow_tests: true,
+        strict: true,
+        text_extensions: Vec::new(),
+    };
+
+    for result in results {
+        writeln!(output, "File: {}", result.file)?;
+        if result.node_type != "file" {
+            writeln!(output, "Lines: {}-{}", result.lines.0, result.lines.1)?;
+        }
+
+        let symbols = file_symbols.entry(result.file.as_str()).or_insert_with(|| {
+            extract_symbols_with_options(Path::new(&result.file), &options)
+                .ok()
+                .map(|file| file.symbols)
+        });
+
+        let mut entries = Vec::new();
+        match symbols {
+            Some(symbols) => collect_entries(symbols, result.lines, 0, &mut entries),
+            None => collect_comment_entries(&result.code, result.lines.0, &mut entries),
+        }
+
+        if entries.is_empty() {
+            writeln!(output, "(no declarations)")?;
+        } else {
+            let width = entries
+                .iter()
+                .map(|entry| entry.line.to_string().len())
+                .max()
+                .unwrap_or(1);
+            for entry in &entries {
+                writeln!(
+                    output,
+                    "{:>width$} | {}{}",
+                    entry.line,
+                    "  ".repeat(entry.depth),
+                    entry.text
+                )?;
+            }
+        }
+        writeln!(output)?;
+    }
+
+    Ok(output)
+}
+
+/// Collect the symbols starting inside `lines`, in source order. Nesting only counts
+/// parents that are themselves part of the outline.
+fn collect_entries(
+    symbols: &[SymbolNode],
+    lines: (usize, usize),
+    depth: usize,
+    entries: &mut Vec<OutlineEntry>,
+) {
+    for symbol in symbols {
+        let included = symbol.line >= lines.0 && symbol.line <= lines.1;
+        if included {
+            entries.push(OutlineEntry {
+                line: symbol.line,
+                depth,
+                // Multi-line signatures are collapsed onto one line
+                text: symbol
+                    .signature
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" "),
+            });
+        }
+        let child_depth = if included { depth + 1 } else { depth };
+        collect_entries(&symbol.children, lines, child_depth, entries);
+    }
+}
+
+/// Fallback for unsupported languages: keep comment and heading lines
+fn collect_comment_entries(code: &str, start_line: usize, entries: &mut Vec<OutlineEntry>) {
+    for (offset, line) in code.lines().enumerate() {
+        let trimmed = line.trim();
+        if ["//", "/*", "#", "--", ";", "<!--"]
+            .iter()
+            .any(|prefix| trimmed.starts_with(prefix))
+        {
+            entries.push(OutlineEntry {
+                line: start_line + offset,
+                depth: 0,
+                text: trimmed.to_string(),
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn result_for(file: &Path, lines: (usize, usize), node_type: &str) -> SearchResult {
+        let content = fs::read_to_string(file).unwrap();
+        let code = content
+            .lines()
+            .skip(lines.0 - 1)
+            .take(lines.1 - lines.0 + 1)
+            .collect::<Vec<_>>()
+            .join("\n");
+        SearchResult {
+            file: file.to_string_lossy().to_string(),
+            lines,
+            node_type: node_type.to_string(),
+            code,
+            symbol_signature: None,
+            matched_by_filename: None,
+            rank: None,
+            score: None,
+            tfidf_score: None,
+            bm25_score: None,
+            tfidf_rank: None,
+            bm25_rank: None,
+            new_score: None,
+            hybrid2_rank: None,
+            combined_score_rank: None,
+            file_unique_terms: None,
+            file_total_matches: None,
+            file_match_rank: None,
+            block_unique_terms: None,
+            block_total_matches: None,
+            parent_file_id: None,
+            block_id: None,
+            matched_keywords: None,
+            matched_lines: None,
+            tokenized_content: None,
+            lsp_info: None,
+            parent_context: None,
+            score_breakdown: None,
+            term_matches: None,
+            duplicates: None,
+            root: None,
+        }
+    }
+
+    #[test]
+    fn test_outline_shows_nested_signatures() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("invoice.rs");
+        fs::write(
+            &file,
+            "pub struct Invoice {\n    total: u64,\n}\n\nimpl Invoice {\n    pub fn settle(&self) {\n        charge(self.total);\n    }\n}\n",
+        )
+        .unwrap();
+
+        let output = format_outline(&[result_for(&file, (1, 9), "file")]).unwrap();
+
+        assert!(output.contains("1 | pub struct Invoice"), "{output}");
+        assert!(output.contains("5 | impl Invoice"), "{output}");
+        assert!(output.contains("6 |   pub fn settle(&self)"), "{output}");
+        assert!(!output.contains("charge(self.total)"), "{output}");
+
+        // A partial range only outlines the declarations that start inside it
+        let output = format_outline(&[result_for(&file, (6, 8), "function_item")]).unwrap();
+        assert!(output.contains("Lines: 6-8"), "{output}");
+        assert!(output.contains("6 | pub fn settle(&self)"), "{output}");
+        assert!(!output.contains("impl Invoice"), "{output}");
+    }
+
+    #[test]
+    fn test_outline_falls_back_to_comments_and_headings() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("deploy.conf");
+        fs::write(
+            &file,
+            "# Deployment\nregion = eu\n# Limits\nmax_workers = 4\n",
+        )
+        .unwrap();
+
+        let output = format_outline(&[result_for(&file, (1, 4), "file")]).unwrap();
+
+        assert!(output.contains("1 | # Deployment"), "{output}");
+        assert!(output.contains("3 | # Limits"), "{output}");
+        assert!(!output.contains("region = eu"), "{output}");
+    }
+}
@@ -3,12 +3,13 @@
 //! This module provides functions for processing files and extracting code blocks
 //! based on file paths and optional line numbers.
 use anyhow::{Context, Result};
+use probe_code::encoding::{self, SourceEncoding};
 use probe_code::extract::symbol_finder::find_all_symbols_in_file;
 use probe_code::language::factory::get_language_impl;
+use probe_code::language::overrides::effective_extension;
 use probe_code::language::parser::parse_file_for_code_blocks;
 use probe_code::models::SearchResult;
 use std::collections::HashSet;
-use std::fs;
 use std::path::Path;
 
 /// Process a single file and extract code blocks
@@ -17,12 +18,15 @@ use std::path::Path;
 /// merge them into a bounding block, and return it. If no blocks are found, fallback
 /// to the literal lines. If only a single line is specified, do the same but for that line.
 /// If a symbol is specified, we delegate to `find_symbol_in_file`.
-/// If specific lines are provided, we find AST blocks for each line and merge them.
+/// If only specific lines are provided (e.g. `file.rs:42,88,120`), each line's enclosing AST
+/// block is extracted once and overlapping blocks are merged, yielding one result per
+/// disjoint block in file order.
 /// If no lines or symbol are specified, return the entire file.
 ///
 /// This function returns a Vec of SearchResults. For symbol lookups, multiple results
 /// may be returned when the name is ambiguous (e.g., "process" matches both a function
-/// and class methods). For all other modes, a single-element Vec is returned.
+/// and class methods) and specific lines yield one result per block. For all other modes, a
+/// single-element Vec is returned.
 #[allow(clippy::too_many_arguments)]
 pub fn process_file_for_extraction(
     path: &Path,
@@ -33,6 +37,33 @@ pub fn process_file_for_extraction(
     context_lines: usize,
     specific_lines: Option<&HashSet<usize>>,
     symbols: bool,
+) -> Result<Vec<SearchResult>> {
+    process_file_for_extraction_with_encoding(
+        path,
+        start_line,
+        end_line,
+        symbol,
+        allow_tests,
+        context_lines,
+        specific_lines,
+        symbols,
+        None,
+    )
+}
+
+/// Like [`process_file_for_extraction`], but decodes the file with `encoding` instead of
+/// UTF-8. Line numbers refer to the original file either way.
+#[allow(clippy::too_many_arguments)]
+pub fn process_file_for_extraction_with_encoding(
+    path: &Path,
+    start_line: Option<usize>,
+    end_line: Option<usize>,
+    symbol: Option<&str>,
+    allow_tests: bool,
+    context_lines: usize,
+    specific_lines: Option<&HashSet<usize>>,
+    symbols: bool,
+    encoding: Option<SourceEncoding>,
 ) -> Result<Vec<SearchResult>> {
     // Check if debug mode is enabled
     let debug_mode = std::env::var("DEBUG").unwrap_or_default() == "1";
@@ -57,7 +88,10 @@ pub fn process_file_for_extraction(
     }
 
     // Read the file content
-    let content = fs::read_to_string(path).context(format!("Failed to read file: {path:?}"))?;
+    let content = match encoding {
+        Some(encoding) => encoding::read_text_file(path, Some(encoding))?,
+        None => std::fs::read_to_string(path).context(format!("Failed to read file: {path:?}"))?,
+    };
     let lines: Vec<&str> = content.lines().collect();
 
     if debug_mode {
@@ -123,7 +157,7 @@ pub fn process_file_for_extraction(
 
         let code_blocks_result = parse_file_for_code_blocks(
             &content,
-            file_extension(path),
+            file_extension(path, &content),
             &needed_lines,
             allow_tests,
             None,
@@ -216,6 +250,10 @@ pub fn process_file_for_extraction(
                     tokenized_content: Some(tokenized_content),
                     lsp_info: None,
                     parent_context: None,
+                    score_breakdown: None,
+                    term_matches: None,
+                    duplicates: None,
+                    root: None,
                 }])
             }
             _ => {
@@ -266,6 +304,10 @@ pub fn process_file_for_extraction(
                     tokenized_content: Some(tokenized_content),
                     lsp_info: None,
                     parent_context: None,
+                    score_breakdown: None,
+                    term_matches: None,
+                    duplicates: None,
+                    root: None,
                 }])
             }
         }
@@ -300,7 +342,7 @@ pub fn process_file_for_extraction(
 
         match parse_file_for_code_blocks(
             &content,
-            file_extension(path),
+            file_extension(path, &content),
             &needed_lines,
             allow_tests,
             None,
@@ -373,6 +415,10 @@ pub fn process_file_for_extraction(
                     tokenized_content: Some(tokenized_content),
                     lsp_info: None,
                     parent_context: None,
+                    score_breakdown: None,
+                    term_matches: None,
+                    duplicates: None,
+                    root: None,
                 }])
             }
             _ => {
@@ -435,6 +481,10 @@ pub fn process_file_for_extraction(
                     tokenized_content: Some(tokenized_content),
                     lsp_info: None,
                     parent_context: None,
+                    score_breakdown: None,
+                    term_matches: None,
+                    duplicates: None,
+                    root: None,
                 }])
             }
         }
@@ -491,6 +541,10 @@ pub fn process_file_for_extraction(
                 tokenized_content: Some(tokenized_content),
                 lsp_info: None,
                 parent_context: None,
+                score_breakdown: None,
+                term_matches: None,
+                duplicates: None,
+                root: None,
             }]);
         }
 
@@ -517,167 +571,27 @@ pub fn process_file_for_extraction(
             );
         }
 
-        // Use the clamped set for the rest of the function
-        let lines_set = &clamped_lines;
+        // Each target line is extracted on its own; overlapping ranges are then merged
+        let mut targets: Vec<usize> = clamped_lines.into_iter().collect();
+        targets.sort_unstable();
 
-        // Parse AST for all specified lines
-        let code_blocks_result = parse_file_for_code_blocks(
+        let results = extract_line_targets(
+            path,
             &content,
-            file_extension(path),
-            lines_set,
+            &lines,
+            &targets,
             allow_tests,
-            None,
+            context_lines,
+            symbols,
         );
-
-        match code_blocks_result {
-            Ok(blocks) if !blocks.is_empty() => {
-                // Merge them into a bounding block
-                let min_start = blocks.iter().map(|b| b.start_row).min().unwrap_or(0);
-                let max_end = blocks.iter().map(|b| b.end_row).max().unwrap_or(0);
-
-                // Ensure max_end is within bounds of the file
-                let max_end = std::cmp::min(max_end, lines.len() - 1);
-
-                // Ensure min_start is not greater than max_end
-                let min_start = std::cmp::min(min_start, max_end);
-
-                // lines in the file are 0-indexed internally, so we add 1 for final display
-                let merged_start = min_start + 1;
-                let merged_end = max_end + 1;
-
-                if debug_mode {
-                    eprintln!(
-                        "[DEBUG] Found {} AST blocks for specific lines, merging into lines {}-{}",
-                        blocks.len(),
-                        merged_start,
-                        merged_end
-                    );
-                }
-
-                let merged_content = lines[min_start..=max_end].join("\n");
-
-                // Tokenize the content
-                let filename = path
-                    .file_name()
-                    .map(|f| f.to_string_lossy().to_string())
-                    .unwrap_or_default();
-                let tokenized_content =
-                    crate::ranking::preprocess_text_with_filename(&merged_content, &filename);
-
-                Ok(vec![SearchResult {
-                    file: path.to_string_lossy().to_string(),
-                    lines: (merged_start, merged_end),
-                    node_type: "merged_ast_specific_lines".to_string(),
-                    code: merged_content,
-                    symbol_signature: extract_symbol_signature_for_extract(
-                        path,
-                        &content,
-                        merged_start,
-                        merged_end,
-                        symbols,
-                    ),
-                    matched_by_filename: None,
-                    rank: None,
-                    score: None,
-                    tfidf_score: None,
-                    bm25_score: None,
-                    tfidf_rank: None,
-                    bm25_rank: None,
-                    new_score: None,
-                    hybrid2_rank: None,
-                    combined_score_rank: None,
-                    file_unique_terms: None,
-                    file_total_matches: None,
-                    file_match_rank: None,
-                    block_unique_terms: None,
-                    block_total_matches: None,
-                    parent_file_id: None,
-                    block_id: None,
-                    matched_keywords: None,
-                    matched_lines: None,
-                    tokenized_content: Some(tokenized_content),
-                    lsp_info: None,
-                    parent_context: None,
-                }])
-            }
-            _ => {
-                // Fallback to literal extraction of the specific lines
-                if debug_mode {
-                    eprintln!(
-                        "[DEBUG] No AST blocks found for specific lines, falling back to literal lines"
-                    );
-                }
-
-                // Get the min and max line numbers
-                let min_line = *lines_set.iter().min().unwrap_or(&1);
-                let max_line = *lines_set.iter().max().unwrap_or(&lines.len());
-
-                // Add some context around the lines
-                let start = if min_line <= context_lines {
-                    1
-                } else {
-                    min_line - context_lines
-                };
-                let end = std::cmp::min(max_line + context_lines, lines.len());
-
-                let start_idx = start - 1;
-                let end_idx = end;
-                let range_content = lines[start_idx..end_idx].join("\n");
-
-                // Tokenize the content
-                let filename = path
-                    .file_name()
-                    .map(|f| f.to_string_lossy().to_string())
-                    .unwrap_or_default();
-                let tokenized_content =
-                    crate::ranking::preprocess_text_with_filename(&range_content, &filename);
-
-                // Convert specific lines to Vec for matched_lines, adjusting to be relative to the extracted range
-                let matched_lines_vec = if !lines_set.is_empty() {
-                    let mut relative_lines: Vec<usize> = lines_set
-                        .iter()
-                        .filter(|&&line| line >= start && line <= end)
-                        .map(|&line| line - start + 1)
-                        .collect();
-                    relative_lines.sort();
-                    Some(relative_lines)
-                } else {
-                    None
-                };
-
-                Ok(vec![SearchResult {
-                    file: path.to_string_lossy().to_string(),
-                    lines: (start, end),
-                    node_type: "specific_lines".to_string(),
-                    code: range_content,
-                    symbol_signature: extract_symbol_signature_for_extract(
-                        path, &content, start, end, symbols,
-                    ),
-                    matched_by_filename: None,
-                    rank: None,
-                    score: None,
-                    tfidf_score: None,
-                    bm25_score: None,
-                    tfidf_rank: None,
-                    bm25_rank: None,
-                    new_score: None,
-                    hybrid2_rank: None,
-                    combined_score_rank: None,
-                    file_unique_terms: None,
-                    file_total_matches: None,
-                    file_match_rank: None,
-                    block_unique_terms: None,
-                    block_total_matches: None,
-                    parent_file_id: None,
-                    block_id: None,
-                    matched_keywords: None,
-                    matched_lines: matched_lines_vec,
-                    tokenized_content: Some(tokenized_content),
-                    lsp_info: None,
-                    parent_context: None,
-                }])
-            }
+        if debug_mode {
+            eprintln!(
+                "[DEBUG] Extracted {} target lines into {} blocks",
+                targets.len(),
+                results.len()
+            );
         }
+        Ok(results)
     } else {
         // No line specified, return the entire file
         if debug_mode {
@@ -725,10 +639,136 @@ pub fn process_file_for_extraction(
             tokenized_content: Some(tokenized_content),
             lsp_info: None,
             parent_context: None,
+            score_breakdown: None,
+            term_matches: None,
+            duplicates: None,
+            root: None,
         }])
     }
 }
 
+/// Extract the enclosing AST block of each target line (just the line when no block contains
+/// it), pad each range with `context_lines`, and merge ranges that overlap or touch. Targets
+/// already covered by an earlier block are not extracted again. Returns one result per
+/// merged range, in file order.
+fn extract_line_targets(
+    path: &Path,
+    content: &str,
+    lines: &[&str],
+    targets: &[usize],
+    allow_tests: bool,
+    context_lines: usize,
+    symbols: bool,
+) -> Vec<SearchResult> {
+    if lines.is_empty() {
+        return Vec::new();
+    }
+
+    // Parse once for all targets; every block found is kept, and targets outside any block
+    // fall back to the line itself. Overlaps are resolved by the merge below.
+    let needed_lines: HashSet<usize> = targets.iter().copied().collect();
+    let blocks = parse_file_for_code_blocks(
+        content,
+        file_extension(path, content),
+        &needed_lines,
+        allow_tests,
+        None,
+    )
+    .unwrap_or_default();
+
+    // 1-based inclusive ranges, with whether they came from an AST block
+    let mut ranges: Vec<(usize, usize, bool)> = blocks
+        .iter()
+        .map(|block| (block.start_row + 1, block.end_row + 1, true))
+        .collect();
+    ranges.extend(
+        targets
+            .iter()
+            .filter(|&&line| {
+                !blocks
+                    .iter()
+                    .any(|block| block.start_row < line && line <= block.end_row + 1)
+            })
+            .map(|&line| (line, line, false)),
+    );
+    for range in &mut ranges {
+        let end = (range.1 + context_lines).min(lines.len());
+        let start = range.0.saturating_sub(context_lines).max(1);
+        *range = (start.min(end), end, range.2);
+    }
+
+    ranges.sort_unstable();
+    let mut merged: Vec<(usize, usize, bool)> = Vec::with_capacity(ranges.len());
+    for (start, end, from_ast) in ranges {
+        match merged.last_mut() {
+            Some(last) if start <= last.1 + 1 => {
+                last.1 = last.1.max(end);
+                last.2 |= from_ast;
+            }
+            _ => merged.push((start, end, from_ast)),
+        }
+    }
+
+    let filename = path
+        .file_name()
+        .map(|f| f.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    merged
+        .into_iter()
+        .map(|(start, end, from_ast)| {
+            let code = lines[start - 1..end].join("\n");
+            let tokenized_content = crate::ranking::preprocess_text_with_filename(&code, &filename);
+            let matched_lines: Vec<usize> = targets
+                .iter()
+                .filter(|&&line| line >= start && line <= end)
+                .map(|&line| line - start + 1)
+                .collect();
+
+            SearchResult {
+                file: path.to_string_lossy().to_string(),
+                lines: (start, end),
+                node_type: if from_ast {
+                    "merged_ast_specific_lines"
+                } else {
+                    "specific_lines"
+                }
+                .to_string(),
+                code,
+                symbol_signature: extract_symbol_signature_for_extract(
+                    path, content, start, end, symbols,
+                ),
+                matched_by_filename: None,
+                rank: None,
+                score: None,
+                tfidf_score: None,
+                bm25_score: None,
+                tfidf_rank: None,
+                bm25_rank: None,
+                new_score: None,
+                hybrid2_rank: None,
+                combined_score_rank: None,
+                file_unique_terms: None,
+                file_total_matches: None,
+                file_match_rank: None,
+                block_unique_terms: None,
+                block_total_matches: None,
+                parent_file_id: None,
+                block_id: None,
+                matched_keywords: None,
+                matched_lines: Some(matched_lines),
+                tokenized_content: Some(tokenized_content),
+                lsp_info: None,
+                parent_context: None,
+                score_breakdown: None,
+                term_matches: None,
+                duplicates: None,
+                root: None,
+            }
+        })
+        .collect()
+}
+
 /// Helper function to extract symbol signature for a specific line range
 /// Returns Some(String) if symbols is true and extraction succeeds, None otherwise
 fn extract_symbol_signature_for_extract(
@@ -745,7 +785,7 @@ fn extract_symbol_signature_for_extract(
     let debug_mode = std::env::var("DEBUG").unwrap_or_default() == "1";
 
     // Get file extension
-    let extension = file_extension(path);
+    let extension = file_extension(path, content);
 
     // Get language implementation
     let language_impl = get_language_impl(extension)?;
@@ -934,6 +974,10 @@ pub fn extract_all_symbols_from_file(path: &Path, allow_tests: bool) -> Result<V
             tokenized_content: None,
             lsp_info: None,
             parent_context: None,
+            score_breakdown: None,
+            term_matches: None,
+            duplicates: None,
+            root: None,
         })
         .collect();
 
@@ -950,7 +994,7 @@ pub fn extract_all_symbols_from_file(path: &Path, allow_tests: bool) -> Result<V
     Ok(results)
 }
 
-/// Helper to get file extension as a &str
-fn file_extension(path: &Path) -> &str {
-    path.extension().and_then(|ext| ext.to_str()).unwrap_or("")
+/// Helper to get the extension to parse a file as, after the user's language overrides
+fn file_extension<'a>(path: &'a Path, content: &str) -> &'a str {
+    effective_extension(path, content)
 }
@@ -0,0 +1,197 @@
+//! Signature-only extraction: function bodies are replaced with `{ ... }`.
+//!
+//! Bodies are located with tree-sitter. Doc comments, attributes and signatures are kept as
+//! written, declarations without a body (trait or abstract methods) are left as they are,
+//! and code that does not parse cleanly (e.g. inside macros) is kept in full. Unlike the
+//! other transforms this one shortens the code, so lines no longer match the file.
+
+use anyhow::{Context, Result};
+use probe_code::encoding::{self, SourceEncoding};
+use probe_code::language::factory::get_language_impl;
+use probe_code::language::{get_pooled_parser, return_pooled_parser};
+use probe_code::models::SearchResult;
+use std::ops::Range;
+use std::path::Path;
+
+/// Returns true for declarations that may own a body: functions, methods, constructors
+fn is_function_kind(kind: &str) -> bool {
+    kind.contains("function") || kind.contains("method") || kind.contains("constructor")
+}
+
+/// Returns true for statement blocks, as opposed to the expression body of a lambda
+fn is_body_kind(kind: &str) -> bool {
+    kind == "block"
+        || kind.ends_with("_block")
+        || kind.ends_with("body")
+        || kind == "compound_statement"
+        || kind == "body_statement"
+}
+
+fn overlaps(node: &tree_sitter::Node, range: &Range<usize>) -> bool {
+    node.start_byte() < range.end && node.end_byte() > range.start
+}
+
+fn contains(node: &tree_sitter::Node, range: &Range<usize>) -> bool {
+    node.start_byte() >= range.start && node.end_byte() <= range.end
+}
+
+/// Whether any part of the tree within `range` failed to parse
+fn has_parse_error(node: tree_sitter::Node, range: &Range<usize>) -> bool {
+    if !overlaps(&node, range) {
+        return false;
+    }
+    if node.is_error() || node.is_missing() {
+        return true;
+    }
+    if contains(&node, range) {
+        return node.has_error();
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        if has_parse_error(child, range) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Collect the outermost bodies of declarations that lie entirely within `range`
+fn collect_bodies<'a>(
+    node: tree_sitter::Node<'a>,
+    range: &Range<usize>,
+    bodies: &mut Vec<tree_sitter::Node<'a>>,
+) {
+    if !overlaps(&node, range) {
+        return;
+    }
+    if is_function_kind(node.kind()) && contains(&node, range) {
+        if let Some(body) = node
+            .child_by_field_name("body")
+            .filter(|body| is_body_kind(body.kind()))
+        {
+            bodies.push(body);
+            return;
+        }
+    }
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_bodies(child, range, bodies);
+    }
+}
+
+/// The byte range to replace for `body` and its replacement. Braced bodies become
+/// `{ ... }`; indented bodies (Python) become `...`, after the docstring if there is one.
+fn elision(content: &str, body: tree_sitter::Node) -> (Range<usize>, String) {
+    let text = &content[body.byte_range()];
+    if text.starts_with('{') {
+        return (body.byte_range(), "{ ... }".to_string());
+    }
+
+    let docstring = body.named_child(0).filter(|statement| {
+        statement.kind() == "expression_statement"
+            && statement
+                .named_child(0)
+                .is_some_and(|expression| expression.kind() == "string")
+    });
+    match docstring {
+        Some(docstring) => {
+            let line_start = content[..docstring.start_byte()]
+                .rfind('\n')
+                .map_or(0, |index| index + 1);
+            let indent = &content[line_start..docstring.start_byte()];
+            (
+                docstring.end_byte()..body.end_byte(),
+                format!("\n{indent}..."),
+            )
+        }
+        None => (body.byte_range(), "...".to_string()),
+    }
+}
+
+/// Elide the bodies in `content[range]`, or `None` if that part does not parse cleanly
+fn elide_range(content: &str, tree: &tree_sitter::Tree, range: Range<usize>) -> Option<String> {
+    if has_parse_error(tree.root_node(), &range) {
+        return None;
+    }
+
+    let mut bodies = Vec::new();
+    collect_bodies(tree.root_node(), &range, &mut bodies);
+
+    let mut output = String::with_capacity(range.len());
+    let mut last = range.start;
+    for body in bodies {
+        let (elided, replacement) = elision(content, body);
+        output.push_str(&content[last..elided.start]);
+        output.push_str(&replacement);
+        last = elided.end;
+    }
+    output.push_str(&content[last..range.end]);
+    Some(output)
+}
+
+fn parse(content: &str, extension: &str) -> Result<tree_sitter::Tree> {
+    let mut parser = get_pooled_parser(extension)?;
+    let tree = parser.parse(content, None);
+    return_pooled_parser(extension, parser);
+    tree.context("Failed to parse content for signature extraction")
+}
+
+/// Replace the function bodies in `content`, parsed with the grammar for `extension`.
+///
+/// Content in a language without a tree-sitter grammar, or that does not parse cleanly,
+/// is returned unchanged.
+pub fn elide_bodies(content: &str, extension: &str) -> Result<String> {
+    if get_language_impl(extension).is_none() {
+        return Ok(content.to_string());
+    }
+    let tree = parse(content, extension)?;
+    Ok(elide_range(content, &tree, 0..content.len()).unwrap_or_else(|| content.to_string()))
+}
+
+/// Reduce each extracted result to its signatures.
+///
+/// The file is parsed as a whole so methods are recognised inside their class; if a
+/// result's code does not line up with the file, the snippet is parsed on its own instead.
+pub fn signatures_only_results(
+    path: &Path,
+    encoding: Option<SourceEncoding>,
+    results: &mut [SearchResult],
+) -> Result<()> {
+    let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    if get_language_impl(extension).is_none() {
+        return Ok(());
+    }
+    let content = encoding::read_text_file(path, encoding)?;
+    let tree = parse(&content, extension)?;
+
+    // Byte offset of the start of each line, plus the end of the content
+    let mut line_starts: Vec<usize> = std::iter::once(0)
+        .chain(content.match_indices('\n').map(|(index, _)| index + 1))
+        .collect();
+    if line_starts.last() != Some(&content.len()) {
+        line_starts.push(content.len());
+    }
+    let line_count = line_starts.len() - 1;
+
+    for result in results.iter_mut() {
+        let (start, end) = result.lines;
+        let range = (start >= 1 && start <= end && end <= line_count).then(|| {
+            let range_end = line_starts[end];
+            let range_end = if content[..range_end].ends_with('\n') {
+                range_end - 1
+            } else {
+                range_end
+            };
+            line_starts[start - 1]..range_end
+        });
+
+        let elided = match range.filter(|range| content[range.clone()] == result.code) {
+            Some(range) => elide_range(&content, &tree, range),
+            None => Some(elide_bodies(&result.code, extension)?),
+        };
+        if let Some(elided) = elided {
+            result.code = elided;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,140 @@
+//! Redaction of string literal contents in extracted code.
+//!
+//! String literals are located with tree-sitter and their interior is replaced with a
+//! `<redacted:N>` placeholder, where `N` is the number of characters removed. Quotes,
+//! prefixes (`r#"`, `b"`, `f"`, `@"`, ...) and any interpolated code are kept, and
+//! newlines inside multi-line strings are preserved so line numbers do not shift.
+
+use anyhow::{Context, Result};
+use probe_code::language::factory::get_language_impl;
+use probe_code::language::{get_pooled_parser, return_pooled_parser};
+
+/// Node kinds that represent a complete string literal across the supported grammars
+const STRING_LITERAL_KINDS: &[&str] = &[
+    "string",                         // Python, JavaScript, TypeScript, Ruby, ...
+    "string_literal",                 // Rust, C, C++, Java, C#
+    "raw_string_literal",             // Rust, Go, C++
+    "interpreted_string_literal",     // Go
+    "template_string",                // JavaScript, TypeScript
+    "verbatim_string_literal",        // C#
+    "interpolated_string_expression", // C#
+    "encapsed_string",                // PHP
+    "line_string_literal",            // Swift
+    "multi_line_string_literal",      // Swift
+];
+
+/// Returns true if the node kind is interpolated code inside a string (`${x}`, `{x}`, `#{x}`)
+fn is_interpolation_kind(kind: &str) -> bool {
+    kind.contains("interpolation") || kind.contains("substitution")
+}
+
+/// Returns true if the node kind holds the literal text of a string
+fn is_string_content_kind(kind: &str) -> bool {
+    kind.contains("content") || kind.contains("fragment")
+}
+
+/// Split a string literal into its opening and closing delimiter lengths (in bytes).
+///
+/// Handles prefixes such as `r`, `b`, `f`, `rb`, `@`, `$`, raw-string hashes (`r#"..."#`)
+/// and triple-quoted strings.
+fn delimiter_lengths(text: &str) -> Option<(usize, usize)> {
+    let prefix_len = text
+        .find(|c: char| c == '"' || c == '\'' || c == '`')
+        .filter(|&idx| {
+            text[..idx]
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '#' | '@' | '$'))
+        })?;
+    let hashes = text[..prefix_len].chars().filter(|&c| c == '#').count();
+    let quote = text[prefix_len..].chars().next()?;
+
+    let quote_run = text[prefix_len..]
+        .chars()
+        .take_while(|&c| c == quote)
+        .count();
+    let quotes = if quote_run >= 3 { 3 } else { 1 };
+
+    let closing = format!("{}{}", quote.to_string().repeat(quotes), "#".repeat(hashes));
+    let open_len = prefix_len + quotes;
+    let close_len = closing.len();
+    if open_len + close_len > text.len() || !text.ends_with(&closing) {
+        return None;
+    }
+    Some((open_len, close_len))
+}
+
+/// Build the placeholder for a redacted interior, keeping its newlines
+fn placeholder(interior: &str) -> String {
+    let newlines = interior.matches('\n').count();
+    format!(
+        "<redacted:{}>{}",
+        interior.chars().count(),
+        "\n".repeat(newlines)
+    )
+}
+
+/// Collect the byte ranges to redact for every string literal below `node`
+fn collect_redactions(node: tree_sitter::Node, source: &str, ranges: &mut Vec<(usize, usize)>) {
+    if STRING_LITERAL_KINDS.contains(&node.kind()) {
+        let mut cursor = node.walk();
+        let children: Vec<_> = node.named_children(&mut cursor).collect();
+
+        if children.iter().any(|c| is_interpolation_kind(c.kind())) {
+            // Only redact the literal parts; interpolated expressions are code
+            for child in children {
+                if is_string_content_kind(child.kind()) && child.start_byte() < child.end_byte() {
+                    ranges.push((child.start_byte(), child.end_byte()));
+                }
+            }
+            return;
+        }
+
+        let (start, end) = (node.start_byte(), node.end_byte());
+        if let Some((open, close)) = delimiter_lengths(&source[start..end]) {
+            if start + open < end - close {
+                ranges.push((start + open, end - close));
+            }
+        }
+        return;
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_redactions(child, source, ranges);
+    }
+}
+
+/// Redact the contents of all string literals in `content`, parsed with the grammar for
+/// `extension`.
+///
+/// Content in a language without a tree-sitter grammar is returned unchanged.
+pub fn redact_strings(content: &str, extension: &str) -> Result<String> {
+    if get_language_impl(extension).is_none() {
+        return Ok(content.to_string());
+    }
+
+    let mut parser = get_pooled_parser(extension)?;
+    let tree = parser.parse(content, None);
+    return_pooled_parser(extension, parser);
+    let tree = tree.context("Failed to parse content for string redaction")?;
+
+    let mut ranges = Vec::new();
+    collect_redactions(tree.root_node(), content, &mut ranges);
+    if ranges.is_empty() {
+        return Ok(content.to_string());
+    }
+    ranges.sort_unstable();
+
+    let mut redacted = String::with_capacity(content.len());
+    let mut last = 0;
+    for (start, end) in ranges {
+        if start < last {
+            continue;
+        }
+        redacted.push_str(&content[last..start]);
+        redacted.push_str(&placeholder(&content[start..end]));
+        last = end;
+    }
+    redacted.push_str(&content[last..]);
+    Ok(redacted)
+}
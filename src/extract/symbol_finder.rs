@@ -25,8 +25,10 @@ fn get_qualified_name<'a>(
                 if child.kind() == "identifier"
                     || child.kind() == "type_identifier"
                     || child.kind() == "field_identifier"
+                    || child.kind() == "simple_identifier"
                     || child.kind() == "constant"
                     || child.kind() == "name"
+                    || is_wrapped_name(&parent, &child)
                 {
                     if let Ok(name) = child.utf8_text(content) {
                         // Skip if the parent's name is the same as the symbol
@@ -43,6 +45,22 @@ fn get_qualified_name<'a>(
     symbol_name.to_string()
 }
 
+/// Whether `child` is the declared type of a named declaration, such as the return type of a
+/// C# or Java method or the type of a C# property, which can be a bare identifier too
+fn is_declared_type(node: &tree_sitter::Node, child: &tree_sitter::Node) -> bool {
+    node.child_by_field_name("name").is_some()
+        && ["type", "returns"]
+            .iter()
+            .any(|field| node.child_by_field_name(field).as_ref() == Some(child))
+}
+
+/// Whether `child` is a `name` field that wraps the identifier in another node, as Swift
+/// does for the extended type of an extension (`user_type`) and for properties (`pattern`)
+fn is_wrapped_name(node: &tree_sitter::Node, child: &tree_sitter::Node) -> bool {
+    matches!(child.kind(), "user_type" | "pattern")
+        && node.child_by_field_name("name").as_ref() == Some(child)
+}
+
 /// Recursively collect ALL AST nodes matching the given symbol name.
 /// Unlike `find_symbol_node` which early-returns on the first match,
 /// this function pushes every match into the `matches` vector.
@@ -81,10 +99,15 @@ fn find_all_symbol_nodes<'a>(
         // Try to extract the name of this node
         let mut cursor = node.walk();
         for child in node.children(&mut cursor) {
+            if is_declared_type(&node, &child) {
+                continue;
+            }
+
             if child.kind() == "identifier"
                 || child.kind() == "field_identifier"
                 || child.kind() == "type_identifier"
                 || child.kind() == "property_identifier"
+                || child.kind() == "simple_identifier"
                 || child.kind() == "constant"
                 || child.kind() == "name"
                 || child.kind() == "variable"
@@ -92,6 +115,7 @@ fn find_all_symbol_nodes<'a>(
                 || child.kind() == "module_id"
                 || child.kind() == "field_name"
                 || child.kind() == "prefix_id"
+                || is_wrapped_name(&node, &child)
             // PHP uses "name" for identifiers
             {
                 if let Ok(name) = child.utf8_text(content) {
@@ -302,6 +326,10 @@ fn text_search_fallback(
             tokenized_content: Some(tokenized_content),
             lsp_info: None,
             parent_context: None,
+            score_breakdown: None,
+            term_matches: None,
+            duplicates: None,
+            root: None,
         }]);
     }
 
@@ -357,8 +385,8 @@ pub fn find_all_symbols_in_file(
         );
     }
 
-    // Get the file extension to determine the language
-    let extension = path.extension().and_then(|ext| ext.to_str()).unwrap_or("");
+    // Get the file extension to determine the language, after the user's language overrides
+    let extension = crate::language::overrides::effective_extension(path, content);
 
     // Get the language implementation for this extension
     let language_impl = match crate::language::factory::get_language_impl(extension) {
@@ -406,6 +434,10 @@ pub fn find_all_symbols_in_file(
                 tokenized_content: Some(tokenized_content),
                 lsp_info: None,
                 parent_context: None,
+                score_breakdown: None,
+                term_matches: None,
+                duplicates: None,
+                root: None,
             }]);
         }
     };
@@ -586,6 +618,10 @@ pub fn find_all_symbols_in_file(
                 tokenized_content: Some(tokenized_content),
                 lsp_info: None,
                 parent_context: None,
+                score_breakdown: None,
+                term_matches: None,
+                duplicates: None,
+                root: None,
             }
         })
         .collect();
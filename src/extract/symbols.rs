@@ -247,7 +247,7 @@ fn collect_symbols(
 
 /// Return the actual declaration represented by wrapper nodes such as
 /// TypeScript/JavaScript `export_statement`.
-fn semantic_symbol_node<'a>(
+pub(crate) fn semantic_symbol_node<'a>(
     node: &Node<'a>,
     lang: &dyn crate::language::language_trait::LanguageImpl,
     source: &[u8],
@@ -289,6 +289,7 @@ fn collect_children_symbols(
             child.kind(),
             "declaration_list"
                 | "class_body"
+                | "enum_class_body"
                 | "block"
                 | "field_declaration_list"
                 | "enum_body"
@@ -315,7 +316,7 @@ fn collect_children_symbols(
 }
 
 /// Extract a symbol name from an AST node.
-fn extract_symbol_name(node: &Node, source: &[u8]) -> String {
+pub(crate) fn extract_symbol_name(node: &Node, source: &[u8]) -> String {
     if node.kind() == "function_definition" {
         if let Some(name) = extract_c_like_function_name(node, source) {
             return name;
@@ -373,6 +374,7 @@ fn extract_symbol_name(node: &Node, source: &[u8]) -> String {
             "identifier"
                 | "type_identifier"
                 | "property_identifier"
+                | "simple_identifier"
                 | "constant"
                 | "name"
                 | "variable"
@@ -393,6 +395,19 @@ fn extract_symbol_name(node: &Node, source: &[u8]) -> String {
                 }
             }
         }
+        // For variable_declaration inside a Kotlin property_declaration
+        if child.kind() == "variable_declaration" {
+            if let Some(name_node) = find_child_by_kind(&child, "simple_identifier") {
+                if let Ok(text) = name_node.utf8_text(source) {
+                    return text.to_string();
+                }
+            }
+        }
+    }
+
+    // Kotlin companion objects are usually unnamed
+    if node.kind() == "companion_object" {
+        return "companion object".to_string();
     }
 
     // Fallback: use node kind
@@ -677,7 +692,7 @@ fn find_matching_brace(source: &[u8], open_brace: usize) -> Option<usize> {
 }
 
 /// Normalize tree-sitter node kinds to user-friendly labels.
-fn normalize_kind(kind: &str) -> String {
+pub(crate) fn normalize_kind(kind: &str) -> String {
     match kind {
         "function_item"
         | "function_declaration"
@@ -1,9 +1,112 @@
+use crate::encoding::{decode_file_bytes, DecodingError, SourceEncoding};
+use crate::models::SkipReason;
 use anyhow::{Context, Result};
+use std::fmt;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 /// Maximum file size that search-like text paths will read into memory.
 pub const MAX_SEARCHABLE_TEXT_FILE_SIZE_BYTES: u64 = 1024 * 1024;
 
+/// Number of leading bytes the binary heuristic looks at.
+const BINARY_HEURISTIC_SAMPLE_BYTES: usize = 8 * 1024;
+
+/// How files are recognised as binary, and skipped, when read for text search.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BinaryDetection {
+    /// Any NUL byte or invalid UTF-8 makes a file binary
+    #[default]
+    NullByte,
+    /// A file is binary when more than 30% of its first 8 KiB are control bytes; invalid
+    /// UTF-8 in text files is replaced
+    Heuristic,
+    /// No file is binary; invalid UTF-8 is replaced
+    Never,
+}
+
+impl BinaryDetection {
+    /// Whether `bytes` are the content of a binary file
+    pub fn is_binary(self, bytes: &[u8]) -> bool {
+        match self {
+            BinaryDetection::NullByte => bytes.contains(&0) || std::str::from_utf8(bytes).is_err(),
+            BinaryDetection::Heuristic => {
+                let sample = &bytes[..bytes.len().min(BINARY_HEURISTIC_SAMPLE_BYTES)];
+                let control_bytes = sample
+                    .iter()
+                    .filter(|&&byte| byte < 0x20 && !matches!(byte, b'\t' | b'\n' | b'\r' | 0x0c))
+                    .count();
+                control_bytes * 10 > sample.len() * 3
+            }
+            BinaryDetection::Never => false,
+        }
+    }
+}
+
+impl FromStr for BinaryDetection {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "null-byte" => Ok(BinaryDetection::NullByte),
+            "heuristic" => Ok(BinaryDetection::Heuristic),
+            "never" => Ok(BinaryDetection::Never),
+            _ => Err(format!(
+                "Unknown binary detection: {s}. Expected one of: null-byte, heuristic, never"
+            )),
+        }
+    }
+}
+
+/// Error for a file skipped because it looks binary.
+#[derive(Debug)]
+pub struct BinaryFileError {
+    pub path: PathBuf,
+}
+
+impl fmt::Display for BinaryFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "File appears to contain binary data: {}",
+            self.path.display()
+        )
+    }
+}
+
+impl std::error::Error for BinaryFileError {}
+
+/// Why a file was not searched, when an error returned by the read functions means it was
+/// skipped as binary or undecodable rather than being unreadable.
+pub fn skip_reason(error: &anyhow::Error) -> Option<SkipReason> {
+    if error.downcast_ref::<BinaryFileError>().is_some() {
+        Some(SkipReason::Binary)
+    } else if error.downcast_ref::<DecodingError>().is_some() {
+        Some(SkipReason::Decoding)
+    } else {
+        None
+    }
+}
+
+/// Binary detection to use for `path`: files with one of `force_text_extensions` (with or
+/// without the leading dot) are always text.
+pub fn binary_detection_for(
+    path: &Path,
+    detection: BinaryDetection,
+    force_text_extensions: &[String],
+) -> BinaryDetection {
+    let forced_text = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            crate::extract::symbols::matches_text_extension(ext, force_text_extensions)
+        });
+    if forced_text {
+        BinaryDetection::Never
+    } else {
+        detection
+    }
+}
+
 const HARD_DENY_FILENAMES: &[&str] = &[".ds_store", "thumbs.db"];
 
 const HARD_DENY_COMPOUND_SUFFIXES: &[&str] =
@@ -105,18 +208,49 @@ pub fn validate_searchable_text_file(path: &Path) -> Result<PathBuf> {
 }
 
 pub fn read_searchable_text_file(path: &Path) -> Result<String> {
-    let resolved_path = validate_searchable_text_file(path)?;
-    let content = std::fs::read_to_string(&resolved_path)
-        .with_context(|| format!("Failed to read file: {}", resolved_path.display()))?;
+    read_searchable_text_file_with(path, BinaryDetection::NullByte)
+}
 
-    if content.as_bytes().contains(&0) {
-        anyhow::bail!(
-            "File appears to contain binary data: {}",
-            resolved_path.display()
-        );
-    }
+/// Read a file for text search, failing with [`BinaryFileError`] if `detection` finds it is
+/// binary. Virtual `archive!entry` paths are read from inside the archive.
+pub fn read_searchable_text_file_with(path: &Path, detection: BinaryDetection) -> Result<String> {
+    read_searchable_text_file_decoded(path, detection, None)
+}
 
-    Ok(content)
+/// Like [`read_searchable_text_file_with`], decoding the file with `encoding` instead of
+/// UTF-8. Files malformed in that encoding fail with [`DecodingError`].
+pub fn read_searchable_text_file_decoded(
+    path: &Path,
+    detection: BinaryDetection,
+    encoding: Option<SourceEncoding>,
+) -> Result<String> {
+    let (resolved_path, bytes) = match crate::search::archive::split_entry_path(path) {
+        Some((archive, entry)) => (
+            path.to_path_buf(),
+            crate::search::archive::read_entry(&archive, &entry)?,
+        ),
+        None => {
+            let resolved_path = validate_searchable_text_file(path)?;
+            let bytes = std::fs::read(&resolved_path)
+                .with_context(|| format!("Failed to read file: {}", resolved_path.display()))?;
+            (resolved_path, bytes)
+        }
+    };
+
+    // Other encodings can be invalid UTF-8 or contain NUL bytes (UTF-16), so their binary
+    // detection looks at the decoded text
+    let content = match encoding {
+        None if detection.is_binary(&bytes) => None,
+        None => Some(decode_file_bytes(&resolved_path, bytes, None)?),
+        Some(_) => Some(decode_file_bytes(&resolved_path, bytes, encoding)?)
+            .filter(|content| !detection.is_binary(content.as_bytes())),
+    };
+    content.ok_or_else(|| {
+        BinaryFileError {
+            path: resolved_path,
+        }
+        .into()
+    })
 }
 
 #[cfg(test)]
@@ -143,6 +277,57 @@ mod tests {
         assert!(err.to_string().contains("binary data"));
     }
 
+    #[test]
+    fn read_rejects_invalid_utf8_as_binary() {
+        let mut file = NamedTempFile::new().unwrap();
+        file.write_all(b"caf\xe9 au lait").unwrap();
+
+        let err = read_searchable_text_file(file.path()).unwrap_err();
+        assert_eq!(skip_reason(&err), Some(SkipReason::Binary));
+
+        let latin1 = Some(SourceEncoding::Fixed(encoding_rs::WINDOWS_1252));
+        let content =
+            read_searchable_text_file_decoded(file.path(), BinaryDetection::NullByte, latin1)
+                .unwrap();
+        assert_eq!(content, "café au lait");
+
+        let shift_jis = Some(SourceEncoding::Fixed(encoding_rs::SHIFT_JIS));
+        let err =
+            read_searchable_text_file_decoded(file.path(), BinaryDetection::NullByte, shift_jis)
+                .unwrap_err();
+        assert_eq!(skip_reason(&err), Some(SkipReason::Decoding));
+
+        let content = read_searchable_text_file_with(file.path(), BinaryDetection::Never).unwrap();
+        assert_eq!(content, "caf\u{fffd} au lait");
+    }
+
+    #[test]
+    fn heuristic_tolerates_sparse_nul_bytes() {
+        let mut text = b"let minified = 1;".repeat(20);
+        text.push(0);
+        assert!(BinaryDetection::NullByte.is_binary(&text));
+        assert!(!BinaryDetection::Heuristic.is_binary(&text));
+        assert!(BinaryDetection::Heuristic.is_binary(&[0, 1, 2, 3, b'a', 0, 0, 0]));
+        assert!(!BinaryDetection::Never.is_binary(&[0, 1, 2, 3]));
+    }
+
+    #[test]
+    fn text_extensions_disable_binary_detection() {
+        let extensions = vec![".proto".to_string()];
+        assert_eq!(
+            binary_detection_for(
+                Path::new("api.PROTO"),
+                BinaryDetection::NullByte,
+                &extensions
+            ),
+            BinaryDetection::Never
+        );
+        assert_eq!(
+            binary_detection_for(Path::new("api.rs"), BinaryDetection::Heuristic, &extensions),
+            BinaryDetection::Heuristic
+        );
+    }
+
     #[test]
     fn read_rejects_oversized_files() {
         let mut file = NamedTempFile::new().unwrap();
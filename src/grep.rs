@@ -10,6 +10,9 @@ use std::io::{self, BufRead, IsTerminal, Write};
 use std::path::{Path, PathBuf};
 use std::sync::Mutex;
 
+use probe_code::extract::symbols::{extract_symbols_with_options, SymbolNode, SymbolOptions};
+use serde::Serialize;
+
 pub struct GrepParams {
     pub pattern: String,
     pub paths: Vec<PathBuf>,
@@ -26,6 +29,10 @@ pub struct GrepParams {
     pub no_gitignore: bool,
     pub color: String,
     pub max_count: Option<usize>,
+    /// Prefix matches with the enclosing function/symbol name (tree-sitter languages only)
+    pub show_symbol: bool,
+    /// Output format: "text" (grep-style lines) or "json" (one document, like `search`)
+    pub format: String,
 }
 
 /// Configuration for grep operations
@@ -37,6 +44,7 @@ struct GrepConfig {
     show_line_numbers: bool,
     invert_match: bool,
     max_count: Option<usize>,
+    show_symbol: bool,
 }
 
 impl GrepConfig {
@@ -63,6 +71,7 @@ impl GrepConfig {
             show_line_numbers: params.line_number,
             invert_match: params.invert_match,
             max_count: params.max_count,
+            show_symbol: params.show_symbol,
         })
     }
 }
@@ -74,6 +83,67 @@ struct MatchedLine {
     content: String,
 }
 
+/// A matching line together with its own context lines (for JSON output)
+#[derive(Debug, Clone)]
+struct ContextMatch {
+    line: MatchedLine,
+    /// 1-based character column of the first regex match (none for inverted matches)
+    column: Option<usize>,
+    before: Vec<String>,
+    after: Vec<String>,
+}
+
+/// One entry of the JSON output; its shape depends on the output mode
+#[derive(Debug, Clone, Serialize)]
+#[serde(untagged)]
+enum JsonEntry {
+    Match {
+        file: String,
+        line: usize,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        column: Option<usize>,
+        text: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        symbol: Option<String>,
+        before: Vec<String>,
+        after: Vec<String>,
+    },
+    Count {
+        file: String,
+        count: usize,
+    },
+    File {
+        file: String,
+    },
+}
+
+/// Load the symbol tree of a file, or nothing if the language is not supported
+fn load_symbols(file_path: &Path) -> Vec<SymbolNode> {
+    let options = SymbolOptions {
+        allow_tests: true,
+        strict: true,
+        ..SymbolOptions::default()
+    };
+    extract_symbols_with_options(file_path, &options)
+        .map(|file_symbols| file_symbols.symbols)
+        .unwrap_or_default()
+}
+
+/// Label of the innermost symbol enclosing `line_number`, e.g. `fn process_data`
+fn enclosing_symbol_label(symbols: &[SymbolNode], line_number: usize) -> Option<String> {
+    let symbol = symbols
+        .iter()
+        .find(|s| !s.name.is_empty() && s.line <= line_number && line_number <= s.end_line)?;
+
+    enclosing_symbol_label(&symbol.children, line_number).or_else(|| {
+        let kind = match symbol.kind.as_str() {
+            "function" | "method" => "fn",
+            other => other,
+        };
+        Some(format!("{kind} {}", symbol.name))
+    })
+}
+
 /// Result of processing a single file (for simple modes)
 struct FileMatchResult {
     has_match: bool,
@@ -126,6 +196,62 @@ impl<'a> FileProcessor<'a> {
         })
     }
 
+    /// Collect every match with its full before/after context. Unlike the streaming output,
+    /// context lines shared by nearby matches are repeated for each of them.
+    fn collect_matches(&self, file_path: &Path) -> Result<Vec<ContextMatch>> {
+        let file = fs::File::open(file_path)
+            .with_context(|| format!("Failed to open file: {}", file_path.display()))?;
+
+        // Lines that fail to decode are kept as gaps so line numbers stay accurate
+        let lines: Vec<Option<String>> = io::BufReader::new(file)
+            .lines()
+            .map(|line| line.ok())
+            .collect();
+        let context = |range: std::ops::Range<usize>| -> Vec<String> {
+            lines[range].iter().flatten().cloned().collect()
+        };
+
+        let mut matches = Vec::new();
+        for (index, content) in lines.iter().enumerate() {
+            let Some(content) = content else {
+                continue;
+            };
+            if self.config.regex.is_match(content) == self.config.invert_match {
+                continue;
+            }
+            if self
+                .config
+                .max_count
+                .is_some_and(|max| matches.len() >= max)
+            {
+                break;
+            }
+
+            let column = if self.config.invert_match {
+                None
+            } else {
+                self.config
+                    .regex
+                    .find(content)
+                    .map(|m| content[..m.start()].chars().count() + 1)
+            };
+            let before_start = index.saturating_sub(self.config.before_context);
+            let after_end = (index + 1 + self.config.after_context).min(lines.len());
+
+            matches.push(ContextMatch {
+                line: MatchedLine {
+                    line_number: index + 1,
+                    content: content.clone(),
+                },
+                column,
+                before: context(before_start..index),
+                after: context(index + 1..after_end),
+            });
+        }
+
+        Ok(matches)
+    }
+
     /// Process file with streaming output (for full context mode)
     fn process_with_output<F>(&self, file_path: &Path, mut output_fn: F) -> Result<FileMatchResult>
     where
@@ -238,6 +364,9 @@ pub fn handle_grep(params: GrepParams) -> Result<()> {
 
     // Mutex for synchronized output to prevent interleaved results
     let stdout = Mutex::new(io::stdout());
+    // JSON output is a single document, so entries are gathered and printed at the end
+    let json_output = params.format == "json";
+    let json_entries: Mutex<Vec<(PathBuf, Vec<JsonEntry>)>> = Mutex::new(Vec::new());
 
     for path in params.paths.iter() {
         let walker = build_walker_parallel(path, &params.ignore, params.no_gitignore);
@@ -245,6 +374,7 @@ pub fn handle_grep(params: GrepParams) -> Result<()> {
         let config = config.clone();
         let params = params.clone();
         let stdout_ref = &stdout;
+        let json_entries_ref = &json_entries;
 
         walker.run(|| {
             let config = config.clone();
@@ -265,14 +395,57 @@ pub fn handle_grep(params: GrepParams) -> Result<()> {
                 let file_processor = FileProcessor::new(&config);
 
                 match output_mode {
+                    OutputMode::FullWithContext if json_output => {
+                        let matches = match file_processor.collect_matches(file_path) {
+                            Ok(m) => m,
+                            Err(_) => return ignore::WalkState::Continue,
+                        };
+                        if matches.is_empty() {
+                            return ignore::WalkState::Continue;
+                        }
+
+                        let symbols = config.show_symbol.then(|| load_symbols(file_path));
+                        let entries = matches
+                            .into_iter()
+                            .map(|m| JsonEntry::Match {
+                                file: file_path.display().to_string(),
+                                line: m.line.line_number,
+                                column: m.column,
+                                symbol: symbols.as_ref().and_then(|symbols| {
+                                    enclosing_symbol_label(symbols, m.line.line_number)
+                                }),
+                                text: m.line.content,
+                                before: m.before,
+                                after: m.after,
+                            })
+                            .collect();
+                        if let Ok(mut collected) = json_entries_ref.lock() {
+                            collected.push((file_path.to_path_buf(), entries));
+                        }
+                    }
                     OutputMode::FullWithContext => {
                         // For streaming mode, collect output in a buffer first
                         let mut buffer = Vec::new();
+                        // Symbols are only parsed once the file is known to have a match
+                        let mut symbols: Option<Vec<SymbolNode>> = None;
 
                         let result =
                             file_processor.process_with_output(file_path, |line, is_match| {
+                                let label = if config.show_symbol && is_match {
+                                    let symbols =
+                                        symbols.get_or_insert_with(|| load_symbols(file_path));
+                                    enclosing_symbol_label(symbols, line.line_number)
+                                } else {
+                                    None
+                                };
                                 // Format line into buffer
-                                let formatted = format_line(&config, file_path, line, is_match);
+                                let formatted = format_line(
+                                    &config,
+                                    file_path,
+                                    line,
+                                    is_match,
+                                    label.as_deref(),
+                                );
                                 buffer.push(formatted);
                             });
 
@@ -307,6 +480,21 @@ pub fn handle_grep(params: GrepParams) -> Result<()> {
                             return ignore::WalkState::Continue;
                         }
 
+                        if json_output {
+                            let file = file_path.display().to_string();
+                            let entry = match output_mode {
+                                OutputMode::Count => JsonEntry::Count {
+                                    file,
+                                    count: result.match_count,
+                                },
+                                _ => JsonEntry::File { file },
+                            };
+                            if let Ok(mut collected) = json_entries_ref.lock() {
+                                collected.push((file_path.to_path_buf(), vec![entry]));
+                            }
+                            return ignore::WalkState::Continue;
+                        }
+
                         // Format and write output atomically
                         if let Ok(mut out) = stdout_ref.lock() {
                             match output_mode {
@@ -336,22 +524,61 @@ pub fn handle_grep(params: GrepParams) -> Result<()> {
         });
     }
 
+    if json_output {
+        // Files are walked in parallel, so order them by path for stable output
+        let mut collected = json_entries.into_inner().unwrap_or_default();
+        collected.sort_by(|a, b| a.0.cmp(&b.0));
+        let entries: Vec<JsonEntry> = collected
+            .into_iter()
+            .flat_map(|(_, entries)| entries)
+            .collect();
+        let json = serde_json::to_string_pretty(&json_output_document(&entries))?;
+        println!("{json}");
+    }
+
     Ok(())
 }
 
-/// Format a single line for output
+/// Build the JSON document for grep, shaped like the `search` JSON output
+fn json_output_document(entries: &[JsonEntry]) -> serde_json::Value {
+    let files = entries
+        .iter()
+        .map(|entry| match entry {
+            JsonEntry::Match { file, .. }
+            | JsonEntry::Count { file, .. }
+            | JsonEntry::File { file } => file.as_str(),
+        })
+        .collect::<std::collections::HashSet<_>>()
+        .len();
+
+    serde_json::json!({
+        "results": entries,
+        "summary": {
+            "count": entries.len(),
+            "files": files,
+        },
+        "version": probe_code::version::get_version()
+    })
+}
+
+/// Format a single line for output, optionally labelled with its enclosing symbol
 fn format_line(
     config: &GrepConfig,
     file_path: &Path,
     line: &MatchedLine,
     is_match: bool,
+    symbol: Option<&str>,
 ) -> String {
     let file_str = file_path.display().to_string();
 
     if config.use_color {
-        format_colored_line(config, &file_str, line, is_match)
+        let label = symbol
+            .map(|s| format!(" {} ", format!("[{s}]").cyan()))
+            .unwrap_or_default();
+        format_colored_line(config, &file_str, line, is_match, &label)
     } else {
-        format_plain_line(config, &file_str, line, is_match)
+        let label = symbol.map(|s| format!(" [{s}] ")).unwrap_or_default();
+        format_plain_line(config, &file_str, line, is_match, &label)
     }
 }
 
@@ -361,31 +588,34 @@ fn format_colored_line(
     file_str: &str,
     line: &MatchedLine,
     is_match: bool,
+    label: &str,
 ) -> String {
     if is_match {
         let highlighted = highlight_matches(config, &line.content);
 
         if config.show_line_numbers {
             format!(
-                "{}:{}:{}",
+                "{}:{}:{}{}",
                 file_str.green(),
                 line.line_number.to_string().green(),
+                label,
                 highlighted
             )
         } else {
-            format!("{}:{}", file_str.green(), highlighted)
+            format!("{}:{}{}", file_str.green(), label, highlighted)
         }
     } else {
         // Context line
         if config.show_line_numbers {
             format!(
-                "{}-{}-{}",
+                "{}-{}-{}{}",
                 file_str.green(),
                 line.line_number.to_string().cyan(),
+                label,
                 line.content
             )
         } else {
-            format!("{}-{}", file_str.green(), line.content)
+            format!("{}-{}{}", file_str.green(), label, line.content)
         }
     }
 }
@@ -396,16 +626,17 @@ fn format_plain_line(
     file_str: &str,
     line: &MatchedLine,
     is_match: bool,
+    label: &str,
 ) -> String {
     if config.show_line_numbers {
         let separator = if is_match { ":" } else { "-" };
         format!(
-            "{}{}{}{}{}",
-            file_str, separator, line.line_number, separator, line.content
+            "{}{}{}{}{}{}",
+            file_str, separator, line.line_number, separator, label, line.content
         )
     } else {
         let separator = if is_match { ":" } else { "-" };
-        format!("{}{}{}", file_str, separator, line.content)
+        format!("{}{}{}{}", file_str, separator, label, line.content)
     }
 }
 
@@ -497,6 +728,8 @@ mod tests {
             no_gitignore: false,
             color: "never".to_string(),
             max_count: Some(10),
+            show_symbol: false,
+            format: "text".to_string(),
         };
 
         let config = GrepConfig::from_params(&params).unwrap();
@@ -525,6 +758,8 @@ mod tests {
             no_gitignore: false,
             color: "auto".to_string(),
             max_count: None,
+            show_symbol: false,
+            format: "text".to_string(),
         };
 
         matches!(
@@ -555,6 +790,8 @@ mod tests {
             no_gitignore: false,
             color: "auto".to_string(),
             max_count: None,
+            show_symbol: false,
+            format: "text".to_string(),
         };
 
         let result = FileMatchResult {
@@ -584,6 +821,7 @@ mod tests {
             show_line_numbers: true,
             invert_match: false,
             max_count: None,
+            show_symbol: false,
         };
 
         let processor = FileProcessor::new(&config);
@@ -614,4 +852,115 @@ mod tests {
         // Should output: line 2 (before), line 3 (before), line 4 (match), line 5 (after)
         assert_eq!(output_lines, vec![2, 3, 4, 5]);
     }
+
+    #[test]
+    fn test_collect_matches_with_context_for_json() {
+        let config = GrepConfig {
+            regex: regex::Regex::new("needle").unwrap(),
+            before_context: 1,
+            after_context: 1,
+            use_color: false,
+            show_line_numbers: true,
+            invert_match: false,
+            max_count: None,
+            show_symbol: false,
+        };
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("haystack.txt");
+        std::fs::write(&file_path, "first\n  needle one\nneedle two\nlast\n").unwrap();
+
+        let matches = FileProcessor::new(&config)
+            .collect_matches(&file_path)
+            .unwrap();
+        assert_eq!(matches.len(), 2);
+
+        // Context shared by adjacent matches is repeated for each of them
+        assert_eq!(matches[0].line.line_number, 2);
+        assert_eq!(matches[0].column, Some(3));
+        assert_eq!(matches[0].before, vec!["first"]);
+        assert_eq!(matches[0].after, vec!["needle two"]);
+        assert_eq!(matches[1].before, vec!["  needle one"]);
+        assert_eq!(matches[1].after, vec!["last"]);
+    }
+
+    #[test]
+    fn test_json_output_document_shapes() {
+        let document = json_output_document(&[
+            JsonEntry::Match {
+                file: "src/lib.rs".to_string(),
+                line: 2,
+                column: Some(9),
+                text: "    let needle = 1;".to_string(),
+                symbol: None,
+                before: vec!["fn process_data() {".to_string()],
+                after: vec![],
+            },
+            JsonEntry::Count {
+                file: "src/lib.rs".to_string(),
+                count: 3,
+            },
+            JsonEntry::File {
+                file: "src/main.rs".to_string(),
+            },
+        ]);
+
+        let results = document["results"].as_array().unwrap();
+        assert_eq!(results[0]["line"], 2);
+        assert_eq!(results[0]["column"], 9);
+        assert_eq!(results[0]["before"][0], "fn process_data() {");
+        assert!(results[0].get("symbol").is_none());
+        assert_eq!(
+            results[1],
+            serde_json::json!({"file": "src/lib.rs", "count": 3})
+        );
+        assert_eq!(results[2], serde_json::json!({"file": "src/main.rs"}));
+        assert_eq!(document["summary"]["count"], 3);
+        assert_eq!(document["summary"]["files"], 2);
+    }
+
+    #[test]
+    fn test_show_symbol_labels_match_with_enclosing_function() {
+        let config = GrepConfig {
+            regex: regex::Regex::new("needle").unwrap(),
+            before_context: 0,
+            after_context: 0,
+            use_color: false,
+            show_line_numbers: true,
+            invert_match: false,
+            max_count: None,
+            show_symbol: true,
+        };
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        let file_path = temp_dir.path().join("lib.rs");
+        std::fs::write(
+            &file_path,
+            "fn process_data() {\n    let needle = 1;\n}\n\nconst OUTSIDE: &str = \"none\";\n",
+        )
+        .unwrap();
+
+        let symbols = load_symbols(&file_path);
+        assert_eq!(
+            enclosing_symbol_label(&symbols, 2).as_deref(),
+            Some("fn process_data")
+        );
+        assert_eq!(enclosing_symbol_label(&symbols, 4), None);
+
+        let line = MatchedLine {
+            line_number: 2,
+            content: "    let needle = 1;".to_string(),
+        };
+        let label = enclosing_symbol_label(&symbols, line.line_number);
+        let formatted = format_line(&config, &file_path, &line, true, label.as_deref());
+        assert!(
+            formatted.ends_with(":2: [fn process_data]     let needle = 1;"),
+            "Unexpected output: {formatted}"
+        );
+
+        // Unsupported languages fall back silently to no label
+        let text_path = temp_dir.path().join("notes.txt");
+        std::fs::write(&text_path, "needle\n").unwrap();
+        assert_eq!(enclosing_symbol_label(&load_symbols(&text_path), 1), None);
+    }
 }
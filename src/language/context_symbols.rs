@@ -0,0 +1,230 @@
+//! Enclosing-symbol context for code blocks.
+//!
+//! A matched block deep inside an `impl`, class or module is hard to read on its own, so
+//! this walks up the tree-sitter AST from each block and records the one-line signatures of
+//! its named parents (e.g. `mod bar {`, `impl Foo {`) in [`CodeBlock::parent_context`].
+
+use crate::language::factory::get_language_impl;
+use crate::language::language_trait::LanguageImpl;
+use crate::models::{CodeBlock, ParentContext};
+use tree_sitter::{Node, Tree};
+
+/// Default maximum number of enclosing signatures attached to a block
+pub const DEFAULT_CONTEXT_SYMBOLS_DEPTH: usize = 5;
+
+/// Attach the signatures of the named nodes enclosing each block, outermost first.
+///
+/// Only parents the language treats as containers are considered, and anonymous ones
+/// (closures, bare blocks, object literals) are skipped. At most `max_depth` signatures are
+/// kept per block, preferring the nearest parents. Blocks without enclosing symbols are left
+/// untouched.
+pub fn attach_context_symbols(
+    blocks: &mut [CodeBlock],
+    tree: &Tree,
+    content: &str,
+    extension: &str,
+    max_depth: usize,
+) {
+    if max_depth == 0 {
+        return;
+    }
+    let Some(language_impl) = get_language_impl(extension) else {
+        return;
+    };
+    let source_lines: Vec<&str> = content.lines().collect();
+    let root = tree.root_node();
+
+    for block in blocks.iter_mut() {
+        let Some(node) = root.descendant_for_byte_range(block.start_byte, block.end_byte) else {
+            continue;
+        };
+
+        let mut contexts = Vec::new();
+        let mut current = Some(node);
+        while let Some(ancestor) = current {
+            if contexts.len() >= max_depth {
+                break;
+            }
+            // The block itself (or a node starting on its first line) adds nothing new
+            if ancestor.start_position().row < block.start_row
+                && language_impl.is_acceptable_parent(&ancestor)
+                && is_named_scope(&ancestor)
+            {
+                let row = ancestor.start_position().row;
+                contexts.push(ParentContext {
+                    node_type: ancestor.kind().to_string(),
+                    start_line: row + 1,
+                    end_line: ancestor.end_position().row + 1,
+                    context_line: source_lines
+                        .get(row)
+                        .map(|line| line.trim_end().to_string())
+                        .unwrap_or_default(),
+                    preceding_comments: Vec::new(),
+                });
+            }
+            current = ancestor.parent();
+        }
+
+        if !contexts.is_empty() {
+            contexts.reverse();
+            block.parent_context = Some(contexts);
+        }
+    }
+}
+
+/// Whether a container node introduces a named scope. Checks the usual name fields, plus the
+/// wrapped definition for wrappers such as Python decorators, JS exports and Go type specs.
+fn is_named_scope(node: &Node) -> bool {
+    let has_name = |node: &Node| {
+        ["name", "type", "declarator"]
+            .iter()
+            .any(|field| node.child_by_field_name(field).is_some())
+    };
+    if has_name(node) {
+        return true;
+    }
+    if let Some(inner) = node
+        .child_by_field_name("definition")
+        .or_else(|| node.child_by_field_name("declaration"))
+    {
+        return has_name(&inner);
+    }
+    let mut cursor = node.walk();
+    let found = node
+        .named_children(&mut cursor)
+        .any(|child| child.kind().ends_with("_spec") && has_name(&child));
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::language::get_or_parse_tree_pooled;
+
+    /// Build a block covering the first occurrence of `snippet` in `content`
+    fn block_for(content: &str, snippet: &str) -> CodeBlock {
+        let start_byte = content.find(snippet).expect("snippet not found");
+        let end_byte = start_byte + snippet.len();
+        CodeBlock {
+            start_row: content[..start_byte].matches('\n').count(),
+            end_row: content[..end_byte].matches('\n').count(),
+            start_byte,
+            end_byte,
+            node_type: "test".to_string(),
+            parent_node_type: None,
+            parent_start_row: None,
+            parent_end_row: None,
+            parent_context: None,
+        }
+    }
+
+    fn context_lines(block: &CodeBlock) -> Vec<&str> {
+        block
+            .parent_context
+            .as_ref()
+            .map(|contexts| contexts.iter().map(|c| c.context_line.as_str()).collect())
+            .unwrap_or_default()
+    }
+
+    const RUST_SOURCE: &str = r#"mod billing {
+    pub struct Invoice;
+
+    impl Invoice {
+        pub fn settle(&self) {
+            let apply = || {
+                charge();
+            };
+            apply();
+        }
+    }
+}
+"#;
+
+    #[test]
+    fn test_attaches_enclosing_signatures_outermost_first() {
+        let tree = get_or_parse_tree_pooled("context_symbols_rust.rs", RUST_SOURCE, "rs").unwrap();
+        let snippet = "pub fn settle(&self) {\n            let apply = || {\n                charge();\n            };\n            apply();\n        }";
+        let mut blocks = vec![block_for(RUST_SOURCE, snippet)];
+
+        attach_context_symbols(&mut blocks, &tree, RUST_SOURCE, "rs", 5);
+
+        assert_eq!(
+            context_lines(&blocks[0]),
+            vec!["mod billing {", "    impl Invoice {"]
+        );
+        let contexts = blocks[0].parent_context.as_ref().unwrap();
+        assert_eq!(contexts[0].node_type, "mod_item");
+        assert_eq!((contexts[1].start_line, contexts[1].end_line), (4, 11));
+    }
+
+    #[test]
+    fn test_depth_cap_keeps_nearest_parents() {
+        let tree = get_or_parse_tree_pooled("context_symbols_rust.rs", RUST_SOURCE, "rs").unwrap();
+        let mut blocks = vec![block_for(RUST_SOURCE, "charge();")];
+
+        attach_context_symbols(&mut blocks, &tree, RUST_SOURCE, "rs", 2);
+        // The closure is anonymous, so the nearest named parents are the fn and the impl
+        assert_eq!(
+            context_lines(&blocks[0]),
+            vec!["    impl Invoice {", "        pub fn settle(&self) {"]
+        );
+
+        let mut blocks = vec![block_for(RUST_SOURCE, "charge();")];
+        attach_context_symbols(&mut blocks, &tree, RUST_SOURCE, "rs", 0);
+        assert!(blocks[0].parent_context.is_none());
+    }
+
+    #[test]
+    fn test_skips_anonymous_blocks() {
+        let source = r#"describe("widgets", () => {
+  class Widget {
+    render() {
+      return [
+        draw(),
+      ];
+    }
+  }
+});
+"#;
+        let tree = get_or_parse_tree_pooled("context_symbols_js.js", source, "js").unwrap();
+        let mut blocks = vec![block_for(source, "draw()")];
+
+        attach_context_symbols(&mut blocks, &tree, source, "js", 10);
+
+        // The arrow function, statement blocks, class body and array literal are skipped
+        assert_eq!(
+            context_lines(&blocks[0]),
+            vec!["  class Widget {", "    render() {"]
+        );
+    }
+
+    #[test]
+    fn test_swift_extensions_and_nested_types() {
+        let source = r#"extension Point {
+    struct Segment {
+        var length: Double {
+            return start.distance(to: end)
+        }
+    }
+}
+"#;
+        let tree =
+            get_or_parse_tree_pooled("context_symbols_swift.swift", source, "swift").unwrap();
+        let mut blocks = vec![block_for(source, "return start.distance(to: end)")];
+
+        attach_context_symbols(&mut blocks, &tree, source, "swift", 5);
+
+        // Extensions and structs both parse as class_declaration
+        assert_eq!(
+            context_lines(&blocks[0]),
+            vec![
+                "extension Point {",
+                "    struct Segment {",
+                "        var length: Double {"
+            ]
+        );
+        let contexts = blocks[0].parent_context.as_ref().unwrap();
+        assert_eq!(contexts[0].node_type, "class_declaration");
+        assert_eq!(contexts[2].node_type, "property_declaration");
+    }
+}
@@ -7,8 +7,10 @@ use probe_code::language::haskell::HaskellLanguage;
 use probe_code::language::html::HtmlLanguage;
 use probe_code::language::java::JavaLanguage;
 use probe_code::language::javascript::JavaScriptLanguage;
+use probe_code::language::kotlin::KotlinLanguage;
 use probe_code::language::language_trait::LanguageImpl;
 use probe_code::language::markdown::MarkdownLanguage;
+use probe_code::language::overrides::resolve_extension;
 use probe_code::language::php::PhpLanguage;
 use probe_code::language::python::PythonLanguage;
 use probe_code::language::ruby::RubyLanguage;
@@ -18,28 +20,135 @@ use probe_code::language::swift::SwiftLanguage;
 use probe_code::language::typescript::TypeScriptLanguage;
 use probe_code::language::yaml::YamlLanguage;
 
-/// Factory function to get the appropriate language implementation based on file extension
+/// Factory function to get the appropriate language implementation based on file extension,
+/// after the user's language overrides
 pub fn get_language_impl(extension: &str) -> Option<Box<dyn LanguageImpl>> {
-    match extension {
-        "rs" => Some(Box::new(RustLanguage::new())),
-        "js" | "jsx" => Some(Box::new(JavaScriptLanguage::new())),
-        "ts" => Some(Box::new(TypeScriptLanguage::new_typescript())),
-        "tsx" => Some(Box::new(TypeScriptLanguage::new_tsx())),
-        "py" => Some(Box::new(PythonLanguage::new())),
-        "go" => Some(Box::new(GoLanguage::new())),
-        "c" | "h" => Some(Box::new(CLanguage::new())),
-        "cpp" | "cc" | "cxx" | "hpp" | "hxx" => Some(Box::new(CppLanguage::new())),
-        "java" => Some(Box::new(JavaLanguage::new())),
-        "rb" => Some(Box::new(RubyLanguage::new())),
-        "php" => Some(Box::new(PhpLanguage::new())),
-        "swift" => Some(Box::new(SwiftLanguage::new())),
-        "cs" => Some(Box::new(CSharpLanguage::new())),
-        "sol" => Some(Box::new(SolidityLanguage::new())),
-        "cr" => Some(Box::new(CrystalLanguage::new())),
-        "hs" | "lhs" => Some(Box::new(HaskellLanguage::new())),
-        "html" | "htm" => Some(Box::new(HtmlLanguage::new())),
-        "md" | "markdown" => Some(Box::new(MarkdownLanguage::new())),
-        "yaml" | "yml" => Some(Box::new(YamlLanguage::new())),
-        _ => None,
-    }
+    builtin_language_impl(resolve_extension(extension))
+}
+
+/// A language with a built-in tree-sitter grammar
+struct BuiltinLanguage {
+    /// Canonical name, as accepted by `--language`
+    name: &'static str,
+    /// File extensions mapped to the language
+    extensions: &'static [&'static str],
+    /// Create the implementation for one of `extensions`
+    create: fn(&str) -> Box<dyn LanguageImpl>,
+}
+
+/// Built-in languages, the single source for extension lookup and the capabilities report
+const BUILTIN_LANGUAGES: &[BuiltinLanguage] = &[
+    BuiltinLanguage {
+        name: "rust",
+        extensions: &["rs"],
+        create: |_| Box::new(RustLanguage::new()),
+    },
+    BuiltinLanguage {
+        name: "javascript",
+        extensions: &["js", "jsx"],
+        create: |_| Box::new(JavaScriptLanguage::new()),
+    },
+    BuiltinLanguage {
+        name: "typescript",
+        extensions: &["ts", "tsx"],
+        create: |extension| match extension {
+            "tsx" => Box::new(TypeScriptLanguage::new_tsx()),
+            _ => Box::new(TypeScriptLanguage::new_typescript()),
+        },
+    },
+    BuiltinLanguage {
+        name: "python",
+        extensions: &["py"],
+        create: |_| Box::new(PythonLanguage::new()),
+    },
+    BuiltinLanguage {
+        name: "go",
+        extensions: &["go"],
+        create: |_| Box::new(GoLanguage::new()),
+    },
+    BuiltinLanguage {
+        name: "c",
+        extensions: &["c", "h"],
+        create: |_| Box::new(CLanguage::new()),
+    },
+    BuiltinLanguage {
+        name: "cpp",
+        extensions: &["cpp", "cc", "cxx", "hpp", "hxx"],
+        create: |_| Box::new(CppLanguage::new()),
+    },
+    BuiltinLanguage {
+        name: "java",
+        extensions: &["java"],
+        create: |_| Box::new(JavaLanguage::new()),
+    },
+    BuiltinLanguage {
+        name: "kotlin",
+        extensions: &["kt", "kts"],
+        create: |_| Box::new(KotlinLanguage::new()),
+    },
+    BuiltinLanguage {
+        name: "ruby",
+        extensions: &["rb"],
+        create: |_| Box::new(RubyLanguage::new()),
+    },
+    BuiltinLanguage {
+        name: "php",
+        extensions: &["php"],
+        create: |_| Box::new(PhpLanguage::new()),
+    },
+    BuiltinLanguage {
+        name: "swift",
+        extensions: &["swift"],
+        create: |_| Box::new(SwiftLanguage::new()),
+    },
+    BuiltinLanguage {
+        name: "csharp",
+        extensions: &["cs"],
+        create: |_| Box::new(CSharpLanguage::new()),
+    },
+    BuiltinLanguage {
+        name: "solidity",
+        extensions: &["sol"],
+        create: |_| Box::new(SolidityLanguage::new()),
+    },
+    BuiltinLanguage {
+        name: "crystal",
+        extensions: &["cr"],
+        create: |_| Box::new(CrystalLanguage::new()),
+    },
+    BuiltinLanguage {
+        name: "haskell",
+        extensions: &["hs", "lhs"],
+        create: |_| Box::new(HaskellLanguage::new()),
+    },
+    BuiltinLanguage {
+        name: "html",
+        extensions: &["html", "htm"],
+        create: |_| Box::new(HtmlLanguage::new()),
+    },
+    BuiltinLanguage {
+        name: "markdown",
+        extensions: &["md", "markdown"],
+        create: |_| Box::new(MarkdownLanguage::new()),
+    },
+    BuiltinLanguage {
+        name: "yaml",
+        extensions: &["yaml", "yml"],
+        create: |_| Box::new(YamlLanguage::new()),
+    },
+];
+
+/// Language implementation for the built-in extensions
+fn builtin_language_impl(extension: &str) -> Option<Box<dyn LanguageImpl>> {
+    BUILTIN_LANGUAGES
+        .iter()
+        .find(|language| language.extensions.contains(&extension))
+        .map(|language| (language.create)(extension))
+}
+
+/// Each language with a built-in grammar and the file extensions mapped to it, in a stable order
+pub fn builtin_languages() -> impl Iterator<Item = (&'static str, &'static [&'static str])> {
+    BUILTIN_LANGUAGES
+        .iter()
+        .map(|language| (language.name, language.extensions))
 }
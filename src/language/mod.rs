@@ -4,9 +4,11 @@
 // Import submodules
 pub mod block_handling;
 pub mod common;
+pub mod context_symbols;
 pub mod crystal;
 pub mod factory;
 pub mod language_trait;
+pub mod overrides;
 pub mod parser;
 pub mod parser_pool;
 pub mod test_detection;
@@ -21,6 +23,7 @@ pub mod haskell;
 pub mod html;
 pub mod java;
 pub mod javascript;
+pub mod kotlin;
 pub mod markdown;
 pub mod php;
 pub mod python;
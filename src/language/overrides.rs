@@ -0,0 +1,176 @@
+//! User overrides for language detection, configured in the `languages` config section.
+//!
+//! Detection is extension-based. Overrides map extra extensions (e.g. `mjs`, or `snap` for
+//! `.tsx.snap` fixtures) and shebang lines to one of the supported languages, and are
+//! consulted before the built-in mapping in [`super::factory`]. Every override resolves to
+//! the canonical extension of its language, which the rest of the crate already understands.
+//! Overrides naming an unsupported language are reported once and make the matching files
+//! plain text.
+
+use probe_code::config::ResolvedLanguagesConfig;
+use regex::Regex;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::OnceLock;
+
+/// Language overrides resolved to canonical extensions
+#[derive(Debug, Default)]
+pub struct LanguageOverrides {
+    /// Canonical extension per overridden extension; `None` treats the files as plain text
+    extensions: HashMap<String, Option<&'static str>>,
+    /// Patterns matched against a `#!` first line, with the canonical extension of their language
+    shebangs: Vec<(Regex, Option<&'static str>)>,
+}
+
+impl LanguageOverrides {
+    /// Resolve the overrides of a config, warning about entries that cannot be used
+    pub fn new(config: &ResolvedLanguagesConfig) -> Self {
+        let resolve = |source: &str, language: &str| {
+            let extension = canonical_extension(language);
+            if extension.is_none() {
+                eprintln!(
+                    "Warning: Unsupported language '{language}' for {source} in language overrides, treating those files as plain text"
+                );
+            }
+            extension
+        };
+
+        let extensions = config
+            .extensions
+            .iter()
+            .map(|(extension, language)| {
+                let extension = extension.trim_start_matches('.').to_lowercase();
+                let target = resolve(&format!("extension '{extension}'"), language);
+                (extension, target)
+            })
+            .collect();
+
+        // Patterns are tried in a stable order, as config maps have none
+        let mut patterns: Vec<_> = config.shebangs.iter().collect();
+        patterns.sort();
+        let shebangs = patterns
+            .into_iter()
+            .filter_map(|(pattern, language)| match Regex::new(pattern) {
+                Ok(regex) => Some((regex, resolve(&format!("shebang '{pattern}'"), language))),
+                Err(e) => {
+                    eprintln!(
+                        "Warning: Invalid shebang pattern '{pattern}' in language overrides: {e}"
+                    );
+                    None
+                }
+            })
+            .collect();
+
+        Self {
+            extensions,
+            shebangs,
+        }
+    }
+
+    /// Extension to detect the language of files with `extension` by
+    pub fn resolve_extension<'a>(&self, extension: &'a str) -> &'a str {
+        match self.extensions.get(&extension.to_lowercase()) {
+            Some(Some(target)) => target,
+            // Plain text: no language implementation has an empty extension
+            Some(None) => "",
+            None => extension,
+        }
+    }
+
+    /// Canonical extension of the first shebang pattern matching the first line of `content`
+    pub fn shebang_extension(&self, content: &str) -> Option<&'static str> {
+        let first_line = content
+            .lines()
+            .next()
+            .filter(|line| line.starts_with("#!"))?;
+        self.shebangs
+            .iter()
+            .find(|(regex, _)| regex.is_match(first_line))
+            .and_then(|(_, extension)| *extension)
+    }
+}
+
+/// Canonical extension of a supported language, given by name or by one of its extensions
+pub fn canonical_extension(language: &str) -> Option<&'static str> {
+    let extension = match language.trim_start_matches('.').to_lowercase().as_str() {
+        "rust" | "rs" => "rs",
+        "javascript" | "js" | "jsx" | "mjs" | "cjs" => "js",
+        "typescript" | "ts" | "mts" | "cts" => "ts",
+        "tsx" => "tsx",
+        "python" | "py" => "py",
+        "go" | "golang" => "go",
+        "c" | "h" => "c",
+        "cpp" | "c++" | "cc" | "cxx" | "hpp" | "hxx" => "cpp",
+        "java" => "java",
+        "kotlin" | "kt" | "kts" => "kt",
+        "ruby" | "rb" => "rb",
+        "php" => "php",
+        "swift" => "swift",
+        "csharp" | "c#" | "cs" => "cs",
+        "solidity" | "sol" => "sol",
+        "crystal" | "cr" => "cr",
+        "haskell" | "hs" | "lhs" => "hs",
+        "html" | "htm" => "html",
+        "markdown" | "md" => "md",
+        "yaml" | "yml" => "yaml",
+        _ => return None,
+    };
+    Some(extension)
+}
+
+/// The process-wide overrides, loaded from the configuration on first use
+pub fn language_overrides() -> &'static LanguageOverrides {
+    static OVERRIDES: OnceLock<LanguageOverrides> = OnceLock::new();
+    OVERRIDES.get_or_init(|| LanguageOverrides::new(&probe_code::config::get_config().languages))
+}
+
+/// Extension to detect the language of files with `extension` by, after user overrides
+pub fn resolve_extension(extension: &str) -> &str {
+    language_overrides().resolve_extension(extension)
+}
+
+/// Extension to parse a file as. Extension overrides are applied first; files whose extension
+/// has no language (including files without one) fall back to the shebang overrides.
+pub fn effective_extension<'a>(path: &'a Path, content: &str) -> &'a str {
+    let extension = resolve_extension(path.extension().and_then(|ext| ext.to_str()).unwrap_or(""));
+    if super::factory::get_language_impl(extension).is_some() {
+        return extension;
+    }
+    language_overrides()
+        .shebang_extension(content)
+        .unwrap_or(extension)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_overrides_resolve_extensions_and_shebangs() {
+        let config = ResolvedLanguagesConfig {
+            extensions: HashMap::from([
+                ("snap".to_string(), "tsx".to_string()),
+                (".MJS".to_string(), "javascript".to_string()),
+                ("fixture".to_string(), "cobol".to_string()),
+            ]),
+            shebangs: HashMap::from([(r"\bdeno\b".to_string(), "typescript".to_string())]),
+        };
+        let overrides = LanguageOverrides::new(&config);
+
+        assert_eq!(overrides.resolve_extension("snap"), "tsx");
+        assert_eq!(overrides.resolve_extension("mjs"), "js");
+        assert_eq!(overrides.resolve_extension("rs"), "rs");
+        // Unsupported languages fall back to plain text
+        assert_eq!(overrides.resolve_extension("fixture"), "");
+
+        assert_eq!(
+            overrides.shebang_extension("#!/usr/bin/env -S deno run\nconsole.log(1);\n"),
+            Some("ts")
+        );
+        assert_eq!(
+            overrides.shebang_extension("#!/usr/bin/env python3\n"),
+            None
+        );
+        assert_eq!(overrides.shebang_extension("// deno\n"), None);
+    }
+}
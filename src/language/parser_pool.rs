@@ -44,7 +44,9 @@ lazy_static::lazy_static! {
             let critical_languages = ["rs", "js", "ts", "py", "go", "java"];
 
             // Tier 2: Common languages - warm with lower priority
-            let common_languages = ["cpp", "c", "jsx", "tsx", "rb", "php", "cs", "sol", "cr", "hs"];
+            let common_languages = [
+                "cpp", "c", "jsx", "tsx", "rb", "php", "cs", "kt", "sol", "cr", "hs",
+            ];
 
             // Tier 3: Specialized languages - warm last
             let specialized_languages = ["swift", "h", "cc", "cxx", "hpp", "hxx"];
@@ -86,6 +88,7 @@ fn detect_languages_in_directory(path: &Path) -> HashSet<String> {
         true,  // Include tests for complete detection
         &[],   // No custom ignores
         false, // Respect gitignore
+        false, // Do not follow symlinks
     ) {
         for file in &file_list.files {
             if let Some(extension) = file.extension() {
@@ -136,8 +139,8 @@ pub fn smart_warm_parser_pool_for_directory(path: &Path) {
     // Prioritize languages by usage frequency (most common first)
     let priority_order = [
         "rs", "js", "ts", "py", "go", "java", // Tier 1: Critical
-        "cpp", "c", "jsx", "tsx", "rb", "php", "cs", // Tier 2: Common
-        "swift", "sol", "cr", "hs", "lhs", "h", "cc", "cxx", "hpp",
+        "cpp", "c", "jsx", "tsx", "rb", "php", "cs", "kt", // Tier 2: Common
+        "kts", "swift", "sol", "cr", "hs", "lhs", "h", "cc", "cxx", "hpp",
         "hxx", // Tier 3: Specialized
     ];
 
@@ -29,14 +29,16 @@ impl LanguageImpl for SwiftLanguage {
         matches!(
             node.kind(),
             "function_declaration"
+                | "init_declaration"
+                | "deinit_declaration"
+                | "subscript_declaration"
+                // Classes, structs, enums, actors and extensions all parse as
+                // class_declaration, told apart by their declaration_kind
                 | "class_declaration"
-                | "struct_declaration"
-                | "enum_declaration"
                 | "protocol_declaration"
-                | "extension_declaration"
+                | "protocol_function_declaration"
                 | "typealias_declaration"
-                | "variable_declaration"
-                | "constant_declaration"
+                | "property_declaration"
         )
     }
 
@@ -63,7 +65,7 @@ impl LanguageImpl for SwiftLanguage {
 
             // Also check for @Test attribute
             for child in node.children(&mut cursor) {
-                if child.kind() == "attribute" {
+                if matches!(child.kind(), "attribute" | "modifiers") {
                     let attr_text = child.utf8_text(source).unwrap_or("");
                     if attr_text.contains("@Test") {
                         if debug_mode {
@@ -81,7 +83,7 @@ impl LanguageImpl for SwiftLanguage {
 
             // Check if class inherits from XCTestCase
             for child in node.children(&mut cursor) {
-                if child.kind() == "type_inheritance_clause" {
+                if child.kind() == "inheritance_specifier" {
                     let inheritance_text = child.utf8_text(source).unwrap_or("");
                     if inheritance_text.contains("XCTestCase") {
                         if debug_mode {
@@ -100,7 +102,10 @@ impl LanguageImpl for SwiftLanguage {
         let mut current = node;
 
         while let Some(parent) = current.parent() {
-            if parent.kind() == "function_declaration" {
+            if matches!(
+                parent.kind(),
+                "function_declaration" | "init_declaration" | "deinit_declaration"
+            ) {
                 return Some(parent);
             }
             current = parent;
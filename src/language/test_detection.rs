@@ -82,6 +82,14 @@ pub fn is_test_file(path: &Path) -> bool {
             return true;
         }
 
+        // Kotlin: *Test.kt, *Tests.kt
+        if file_name.ends_with("Test.kt") || file_name.ends_with("Tests.kt") {
+            if _debug_mode {
+                println!("DEBUG: Test file detected (Kotlin pattern): {file_name}");
+            }
+            return true;
+        }
+
         // Ruby: test_*.rb, *_test.rb, *_spec.rb
         if file_name.starts_with("test_") && file_name.ends_with(".rb")
             || file_name.ends_with("_test.rb")
@@ -828,6 +828,27 @@ fn test_csharp_language_implementation() {
     );
 }
 
+#[test]
+fn test_kotlin_language_implementation() {
+    use probe_code::language::factory::get_language_impl;
+
+    // Kotlin sources and scripts share one implementation
+    for extension in ["kt", "kts"] {
+        let kotlin_impl = get_language_impl(extension);
+        assert!(
+            kotlin_impl.is_some(),
+            "Should be able to get Kotlin language implementation for .{extension}"
+        );
+
+        let mut parser = tree_sitter::Parser::new();
+        parser
+            .set_language(&kotlin_impl.unwrap().get_tree_sitter_language())
+            .expect("Kotlin grammar should load");
+        let tree = parser.parse("fun main() {}\n", None).unwrap();
+        assert!(!tree.root_node().has_error());
+    }
+}
+
 // Helper function to print the AST structure
 fn print_ast_structure(node: tree_sitter::Node, depth: usize) {
     let indent = " ".repeat(depth * 2);
@@ -18,7 +18,9 @@
 //! ### Searching for code
 //!
 //! ```no_run
-//! use probe_code::search::{perform_probe, SearchOptions};
+//! use probe_code::search::{
+//!     perform_probe, BinaryDetection, DedupMode, MatchScope, RankMode, ResultSort, SearchOptions,
+//! };
 //! use std::path::Path;
 //!
 //! // Create search options
@@ -35,15 +37,42 @@
 //!     max_results: Some(10),
 //!     max_bytes: None,
 //!     max_tokens: Some(10000),
+//!     tokenizer: None,
 //!     allow_tests: false,
 //!     no_merge: false,
 //!     merge_threshold: None,
+//!     merge_respect_boundaries: true,
 //!     dry_run: false,
 //!     session: None,
 //!     timeout: 30,
 //!     question: None,
 //!     no_gitignore: false,
 //!     lsp: false,
+//!     sort: ResultSort::Score,
+//!     regex: false,
+//!     match_scope: MatchScope::All,
+//!     invert: false,
+//!     kinds: None,
+//!     recency_boost: false,
+//!     recency_half_life_days: 14.0,
+//!     context_symbols: false,
+//!     context_symbols_depth: 5,
+//!     explain: false,
+//!     use_index: false,
+//!     rank_mode: RankMode::Block,
+//!     within: None,
+//!     changed_since: None,
+//!     tracked_only: false,
+//!     rerank_top_k: 50,
+//!     binary_detection: BinaryDetection::NullByte,
+//!     force_text_extensions: &[],
+//!     search_archives: false,
+//!     encoding: None,
+//!     min_score: None,
+//!     follow_symlinks: false,
+//!     ranking_profile: None,
+//!     dedup: DedupMode::None,
+//!     extra_roots: &[],
 //! };
 //!
 //! let results = perform_probe(&options).unwrap();
@@ -63,7 +92,9 @@
 //!     from_clipboard: false,
 //!     input_file: None,
 //!     to_clipboard: false,
+//!     clipboard_max_bytes: None,
 //!     dry_run: false,
+//!     plain_paths: false,
 //!     diff: false,
 //!     allow_tests: false,
 //!     keep_input: false,
@@ -71,6 +102,16 @@
 //!     instructions: None,
 //!     no_gitignore: false,
 //!     lsp: false,
+//!     cache: false,
+//!     strip_comments: false,
+//!     redact_strings: false,
+//!     signatures_only: false,
+//!     only_kinds: None,
+//!     public_only: false,
+//!     line_numbers: false,
+//!     blame: false,
+//!     diff_blocks: false,
+//!     encoding: None,
 //! };
 //!
 //! handle_extract(options).unwrap();
@@ -79,13 +120,14 @@
 //! ### AST pattern matching
 //!
 //! ```no_run
-//! use probe_code::query::{perform_query, QueryOptions};
+//! use probe_code::query::{perform_query, GroupBy, QueryOptions};
 //! use std::path::Path;
 //!
 //! // Using the lower-level perform_query function
 //! let options = QueryOptions {
 //!     path: Path::new("."),
 //!     pattern: "fn $NAME($$$PARAMS) { $$$BODY }",
+//!     or_patterns: &[],
 //!     language: Some("rust"),
 //!     ignore: &[],
 //!     allow_tests: false,
@@ -95,6 +137,10 @@
 //!     no_gitignore: false,
 //!     strict: false,
 //!     text_extensions: &[],
+//!     parent_kinds: &[],
+//!     sexp: false,
+//!     group_by: GroupBy::None,
+//!     follow_symlinks: false,
 //! };
 //!
 //! let matches = perform_query(&options).unwrap();
@@ -105,7 +151,9 @@
 extern crate self as probe_code;
 
 pub mod bert_reranker;
+pub mod capabilities;
 pub mod config;
+pub mod encoding;
 pub mod extract;
 pub mod file_guard;
 pub mod language;
@@ -124,14 +172,19 @@ pub mod version;
 
 // Re-export commonly used types for convenience
 pub use extract::{
-    format_and_print_extraction_results, handle_extract, process_file_for_extraction,
-    ExtractOptions,
+    extract_json_schema, format_and_print_extraction_results, handle_extract,
+    process_file_for_extraction, ExtractOptions,
 };
 pub use models::{CodeBlock, LimitedSearchResults, SearchLimits, SearchResult};
 pub use path_resolver::resolve_path;
 pub use query::{
-    format_and_print_query_results, handle_query, perform_query, AstMatch, QueryOptions,
+    count_query_matches, format_and_print_query_results, group_matches, handle_query,
+    perform_query, perform_query_at_position, query_json_schema, AstMatch, AstNodeInfo,
+    CaptureSpan, EnclosingDefinition, GroupBy, MatchGroup, PositionMatch, QueryCounts,
+    QueryOptions,
+};
+pub use search::{
+    format_and_print_search_results, perform_probe, search_json_schema, SearchOptions,
 };
-pub use search::{format_and_print_search_results, perform_probe, SearchOptions};
 
 // Tests are defined in their respective modules with #[cfg(test)]
@@ -4,10 +4,11 @@ use dashmap::DashMap;
 use std::collections::{HashSet, VecDeque};
 use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicU64 as GlobalAtomicU64;
-use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
-use tokio::sync::Mutex as AsyncMutex;
+use tokio::sync::{Mutex as AsyncMutex, Semaphore};
+use tokio::task::JoinSet;
 
 /// Configuration knobs for the call graph cache.
 #[derive(Clone, Debug)]
@@ -152,6 +153,58 @@ impl CallGraphCache {
         Ok(node)
     }
 
+    /// Pre-compute the call hierarchies of `keys`, e.g. for a known set of hot symbols before
+    /// an interactive session. Keys that are not cached yet are passed to `provider`, at most
+    /// `concurrency` at a time, which returns the hierarchy and whether it came from a cache
+    /// behind this one (such as the daemon's database). Keys already cached here, computed
+    /// meanwhile by another caller or listed twice are counted as cached too.
+    pub async fn warm<F, Fut>(
+        self: &Arc<Self>,
+        keys: Vec<NodeKey>,
+        concurrency: usize,
+        provider: F,
+    ) -> WarmReport
+    where
+        F: Fn(NodeKey) -> Fut,
+        Fut: std::future::Future<Output = Result<(CallHierarchyInfo, bool)>> + Send + 'static,
+    {
+        let semaphore = Arc::new(Semaphore::new(concurrency.max(1)));
+        let mut set = JoinSet::new();
+        for key in keys {
+            let permit = semaphore
+                .clone()
+                .acquire_owned()
+                .await
+                .expect("warm semaphore is never closed");
+            let cache = Arc::clone(self);
+            let computation = provider(key.clone());
+            set.spawn(async move {
+                let _permit = permit;
+                let computed = Arc::new(AtomicBool::new(false));
+                let flag = computed.clone();
+                let result = cache
+                    .get_or_compute(key.clone(), move || async move {
+                        let (info, cached) = computation.await?;
+                        flag.store(!cached, Ordering::Relaxed);
+                        Ok(info)
+                    })
+                    .await;
+                (key, result.map(|_| computed.load(Ordering::Relaxed)))
+            });
+        }
+
+        let mut report = WarmReport::default();
+        while let Some(joined) = set.join_next().await {
+            match joined {
+                Ok((_, Ok(true))) => report.computed += 1,
+                Ok((_, Ok(false))) => report.cached += 1,
+                Ok((key, Err(e))) => report.failed.push((key, e.to_string())),
+                Err(e) => tracing::warn!("Call hierarchy warming task failed: {}", e),
+            }
+        }
+        report
+    }
+
     /// Insert a computed node (and register it for file- and id-based invalidation).
     pub fn insert_node(&self, node: Arc<CachedNode>) {
         let key = node.key.clone();
@@ -397,6 +450,17 @@ impl CallGraphCache {
     }
 }
 
+/// Outcome of [`CallGraphCache::warm`]
+#[derive(Debug, Default)]
+pub struct WarmReport {
+    /// Hierarchies computed and inserted
+    pub computed: usize,
+    /// Keys that were already cached, here or behind the provider
+    pub cached: usize,
+    /// Keys whose hierarchy could not be computed, with the error
+    pub failed: Vec<(NodeKey, String)>,
+}
+
 /// Cache statistics for monitoring
 #[derive(Debug, Clone)]
 pub struct CacheStats {
@@ -34,6 +34,12 @@ enum DaemonHealth {
     Unhealthy,
 }
 
+/// Extra time to wait for the daemon's answer to a request with a deadline
+const DEADLINE_GRACE_MS: u64 = 2000;
+
+/// Items per chunk of the result lists the daemon streams back
+const STREAM_CHUNK_SIZE: usize = 1000;
+
 /// Resolve the socket path with optional override.
 /// If PROBE_LSP_SOCKET_PATH is set, we use it; otherwise fall back to the default.
 fn effective_socket_path() -> String {
@@ -49,6 +55,8 @@ pub struct LspClient {
     config: LspConfig,
     daemon_started_by_us: bool,
     position_analyzer: PositionAnalyzer,
+    /// Deadline for requests answered by a language server, set with `set_request_deadline`
+    request_deadline_ms: Option<u64>,
 }
 
 impl Drop for LspClient {
@@ -80,6 +88,7 @@ impl LspClient {
             config,
             daemon_started_by_us: false,
             position_analyzer,
+            request_deadline_ms: None,
         };
 
         if use_daemon {
@@ -101,6 +110,7 @@ impl LspClient {
             config,
             daemon_started_by_us: false,
             position_analyzer,
+            request_deadline_ms: None,
         };
 
         if use_daemon {
@@ -308,9 +318,103 @@ impl LspClient {
         Err(last_error.unwrap_or_else(|| anyhow!("All retry attempts failed")))
     }
 
-    /// Send a request to the daemon and wait for response (public interface with retry)
+    /// Give requests answered by a language server a deadline of `timeout_ms`. The daemon
+    /// answers a request that misses it with a timeout instead of waiting for the server.
+    pub fn set_request_deadline(&mut self, timeout_ms: u64) {
+        self.request_deadline_ms = Some(timeout_ms);
+    }
+
+    /// Deadline sent with `request`, if one is set and a language server answers it. Call
+    /// hierarchy requests get at least the daemon's minimum for them.
+    fn deadline_for(&self, request: &DaemonRequest) -> Option<u64> {
+        request.target_file()?;
+        let timeout_ms = self.request_deadline_ms?;
+        let floor_ms = request
+            .min_deadline()
+            .map_or(0, |floor| floor.as_millis() as u64);
+        Some(timeout_ms.max(floor_ms))
+    }
+
+    /// How long to wait for the daemon's answer to `request`. A request with a deadline gets
+    /// a little longer, so the daemon's `Timeout` answer arrives before we give up.
+    fn response_timeout(&self, request: &DaemonRequest) -> Duration {
+        match self.deadline_for(request) {
+            Some(timeout_ms) => Duration::from_millis(timeout_ms + DEADLINE_GRACE_MS),
+            None => Duration::from_millis(self.config.timeout_ms),
+        }
+    }
+
+    /// Wrap `request` in a `Deadline` if it should carry one
+    fn with_deadline(&self, request: DaemonRequest) -> DaemonRequest {
+        match self.deadline_for(&request) {
+            Some(timeout_ms) => DaemonRequest::Deadline {
+                request_id: Uuid::new_v4(),
+                timeout_ms,
+                request: Box::new(request),
+            },
+            None => request,
+        }
+    }
+
+    /// Turn the daemon's answers about a language server that failed the request into the
+    /// errors reported to callers
+    fn check_server_response(response: DaemonResponse) -> Result<DaemonResponse> {
+        match response {
+            DaemonResponse::Timeout {
+                timeout_ms,
+                language: Some(language),
+                ..
+            } => Err(anyhow!(
+                "{:?} language server did not respond within {}ms",
+                language,
+                timeout_ms
+            )),
+            DaemonResponse::Timeout { timeout_ms, .. } => Err(anyhow!(
+                "Language server did not respond within {}ms",
+                timeout_ms
+            )),
+            DaemonResponse::ServerRestarted {
+                request_id,
+                language,
+                error,
+            } => Ok(DaemonResponse::Error {
+                request_id,
+                error: format!(
+                    "{:?} server was restarted after repeated failures: {}",
+                    language, error
+                ),
+            }),
+            response => Ok(response),
+        }
+    }
+
+    /// Whether to retry a request that got `response`: a server restarted after repeated
+    /// failures is already serving again
+    async fn should_retry_after_restart(response: &DaemonResponse) -> bool {
+        let DaemonResponse::ServerRestarted {
+            language, error, ..
+        } = response
+        else {
+            return false;
+        };
+        warn!(
+            "Retrying request after {:?} server restart: {}",
+            language, error
+        );
+        sleep(Duration::from_millis(500)).await;
+        true
+    }
+
+    /// Send a request to the daemon and wait for response (public interface with retry).
+    /// Requests answered by a language server carry the request deadline, if one is set.
     async fn send_request(&mut self, request: DaemonRequest) -> Result<DaemonResponse> {
-        self.send_request_with_retry(request).await
+        let request = self.with_deadline(request);
+
+        let mut response = self.send_request_with_retry(request.clone()).await?;
+        if Self::should_retry_after_restart(&response).await {
+            response = self.send_request_with_retry(request).await?;
+        }
+        Self::check_server_response(response)
     }
 
     // Minimal public helper for ad-hoc requests from management layer
@@ -318,6 +422,58 @@ impl LspClient {
         self.send_request(request).await
     }
 
+    /// Send a request whose result list the daemon streams back in chunks of at most
+    /// `chunk_size` items, and return the reassembled response. Unlike a single response
+    /// frame, large results are not bound by the per-message size limit.
+    pub async fn send_streamed(
+        &mut self,
+        request: DaemonRequest,
+        chunk_size: usize,
+    ) -> Result<DaemonResponse> {
+        let request = DaemonRequest::Stream {
+            request_id: Uuid::new_v4(),
+            chunk_size,
+            request: Box::new(request),
+        };
+        let read_timeout = self.response_timeout(&request);
+        let request = self.with_deadline(request);
+
+        let mut response = self.send_streamed_once(&request, read_timeout).await?;
+        if Self::should_retry_after_restart(&response).await {
+            response = self.send_streamed_once(&request, read_timeout).await?;
+        }
+        Self::check_server_response(response)
+    }
+
+    /// Send a `Stream` request once and read its frames, each within `read_timeout`
+    async fn send_streamed_once(
+        &mut self,
+        request: &DaemonRequest,
+        read_timeout: Duration,
+    ) -> Result<DaemonResponse> {
+        if self.stream.is_none() {
+            self.connect().await?;
+        }
+        let stream = self
+            .stream
+            .as_mut()
+            .ok_or_else(|| anyhow!("Not connected to daemon"))?;
+
+        let encoded = MessageCodec::encode(request)?;
+        let result = async {
+            stream.write_all(&encoded).await?;
+            stream.flush().await?;
+            MessageCodec::read_response_stream(stream, read_timeout).await
+        }
+        .await;
+
+        if let Err(e) = &result {
+            error!("Streamed request failed: {}", e);
+            self.stream = None; // Clean up broken socket
+        }
+        result
+    }
+
     // duplicate removed
 
     /// Send a request to the daemon and wait for response (internal implementation)
@@ -353,12 +509,14 @@ impl LspClient {
             return Err(anyhow!("Failed to flush request: {}", e));
         }
 
-        // Read response with timeout using proper message framing
-        let timeout_duration = Duration::from_millis(self.config.timeout_ms);
-        debug!(
-            "Waiting for response with timeout: {}ms",
-            self.config.timeout_ms
-        );
+        // Read response with timeout using proper message framing. A request with a deadline
+        // gets a little longer, so the daemon's `Timeout` answer arrives before we give up.
+        let timeout_ms = match &request {
+            DaemonRequest::Deadline { timeout_ms, .. } => timeout_ms + DEADLINE_GRACE_MS,
+            _ => self.config.timeout_ms,
+        };
+        let timeout_duration = Duration::from_millis(timeout_ms);
+        debug!("Waiting for response with timeout: {}ms", timeout_ms);
 
         // Read message length (4 bytes)
         let mut length_buf = [0u8; 4];
@@ -370,15 +528,12 @@ impl LspClient {
                 return Err(anyhow!("Failed to read message length: {}", e));
             }
             Err(_) => {
-                error!(
-                    "Timeout reading message length after {}ms",
-                    self.config.timeout_ms
-                );
+                error!("Timeout reading message length after {}ms", timeout_ms);
                 self.stream = None; // Clean up broken socket on timeout
                 let sp = effective_socket_path();
                 return Err(anyhow!(
                     "Timeout connecting to daemon after {}ms (socket: {})",
-                    self.config.timeout_ms,
+                    timeout_ms,
                     sp
                 ));
             }
@@ -408,13 +563,13 @@ impl LspClient {
             Err(_) => {
                 error!(
                     "Timeout reading message body of {} bytes after {}ms",
-                    message_len, self.config.timeout_ms
+                    message_len, timeout_ms
                 );
                 self.stream = None; // Clean up broken socket on timeout
                 let sp = effective_socket_path();
                 return Err(anyhow!(
                     "Timeout waiting for daemon response after {}ms (socket: {})",
-                    self.config.timeout_ms,
+                    timeout_ms,
                     sp
                 ));
             }
@@ -702,6 +857,18 @@ impl LspClient {
         line: u32,
         column: u32,
     ) -> Result<CallHierarchyInfo> {
+        self.get_call_hierarchy_with_source(file_path, line, column)
+            .await
+            .map(|(hierarchy, _)| hierarchy)
+    }
+
+    /// Get call hierarchy for a symbol, and whether the daemon answered it from its cache
+    pub async fn get_call_hierarchy_with_source(
+        &mut self,
+        file_path: &Path,
+        line: u32,
+        column: u32,
+    ) -> Result<(CallHierarchyInfo, bool)> {
         debug!(
             "Getting call hierarchy for {:?} at {}:{}",
             file_path, line, column
@@ -721,24 +888,25 @@ impl LspClient {
 
         debug!("Sending CallHierarchy request to daemon");
 
-        // Add timeout for call hierarchy request - this can be slow due to rust-analyzer
-        let call_timeout = Duration::from_millis(self.config.timeout_ms);
+        // Add timeout for call hierarchy request - this can be slow due to rust-analyzer.
+        // It outlasts the request's deadline so the daemon can report a hung server first.
+        let call_timeout = self.response_timeout(&request);
         let response = timeout(call_timeout, self.send_request(request))
             .await
             .map_err(|_| {
                 anyhow!(
                     "Call hierarchy request timed out after {}ms",
-                    self.config.timeout_ms
+                    call_timeout.as_millis()
                 )
             })??;
 
         debug!("Received response from daemon");
 
         match response {
-            DaemonResponse::CallHierarchy { result, .. } => {
+            DaemonResponse::CallHierarchy { result, cached, .. } => {
                 debug!("Call hierarchy response received successfully");
                 let converted = convert_call_hierarchy_result(result);
-                Ok(converted)
+                Ok((converted, cached))
             }
             DaemonResponse::Error { error, .. } => {
                 debug!("Call hierarchy failed: {}", error);
@@ -748,6 +916,48 @@ impl LspClient {
         }
     }
 
+    /// Get the call hierarchy as a tree, following callers and callees up to `max_depth`
+    /// levels. Returns the root node and warnings for symbols that could not be expanded.
+    pub async fn get_call_hierarchy_tree(
+        &mut self,
+        file_path: &Path,
+        line: u32,
+        column: u32,
+        max_depth: u32,
+    ) -> Result<(lsp_daemon::protocol::CallHierarchyNode, Vec<String>)> {
+        let request = DaemonRequest::CallHierarchyTree {
+            request_id: Uuid::new_v4(),
+            file_path: file_path.to_path_buf(),
+            line,
+            column,
+            max_depth,
+            workspace_hint: self.config.workspace_hint.as_ref().map(PathBuf::from),
+        };
+
+        // Each level may need fresh call hierarchy requests
+        let call_timeout = Duration::from_millis(
+            self.config
+                .timeout_ms
+                .saturating_mul(max_depth.max(1) as u64),
+        );
+        let response = timeout(call_timeout, self.send_request(request))
+            .await
+            .map_err(|_| {
+                anyhow!(
+                    "Call hierarchy request timed out after {}ms",
+                    call_timeout.as_millis()
+                )
+            })??;
+
+        match response {
+            DaemonResponse::CallHierarchyTree { root, warnings, .. } => {
+                Ok((root, warnings.unwrap_or_default()))
+            }
+            DaemonResponse::Error { error, .. } => Err(anyhow!("Call hierarchy failed: {}", error)),
+            _ => Err(anyhow!("Unexpected response type")),
+        }
+    }
+
     /// Get daemon status
     pub async fn get_status(&mut self) -> Result<LspDaemonStatus> {
         let request = DaemonRequest::Status {
@@ -1746,6 +1956,66 @@ impl LspClient {
         }
     }
 
+    /// Index `file_path` ahead of the rest of the queue. Returns false if it was already
+    /// queued at high priority.
+    pub async fn prioritize_indexing(&mut self, file_path: &Path) -> Result<bool> {
+        let request = DaemonRequest::PrioritizeIndexing {
+            request_id: Uuid::new_v4(),
+            file_path: file_path.to_path_buf(),
+        };
+
+        let response = self.send_request(request).await?;
+
+        match response {
+            DaemonResponse::IndexingPrioritized { changed, .. } => Ok(changed),
+            DaemonResponse::Error { error, .. } => Err(anyhow!(error)),
+            _ => Err(anyhow!("Unexpected response type")),
+        }
+    }
+
+    /// Subscribe to indexing progress: the daemon pushes an event every `interval_ms` and
+    /// `on_event` is called for each one until indexing ends. Returns why the stream ended.
+    pub async fn subscribe_indexing_progress<F>(
+        &mut self,
+        interval_ms: u64,
+        mut on_event: F,
+    ) -> Result<lsp_daemon::protocol::ProgressStreamEnd>
+    where
+        F: FnMut(&lsp_daemon::protocol::IndexingProgressEvent) -> Result<()>,
+    {
+        let stream = self
+            .stream
+            .as_mut()
+            .ok_or_else(|| anyhow!("Not connected to daemon"))?;
+
+        let request = DaemonRequest::SubscribeIndexingProgress {
+            request_id: Uuid::new_v4(),
+            interval_ms,
+        };
+        let encoded = MessageCodec::encode(&request)?;
+        // Events arrive every `interval_ms`; allow the usual timeout on top of that
+        let read_timeout = Duration::from_millis(interval_ms + self.config.timeout_ms);
+        let result = async {
+            stream.write_all(&encoded).await?;
+            stream.flush().await?;
+            loop {
+                let frame = MessageCodec::read_framed(stream, read_timeout).await?;
+                match serde_json::from_slice::<DaemonResponse>(&frame)? {
+                    DaemonResponse::IndexingProgressUpdate { event, .. } => on_event(&event)?,
+                    DaemonResponse::IndexingProgressEnd { reason, .. } => return Ok(reason),
+                    DaemonResponse::Error { error, .. } => return Err(anyhow!(error)),
+                    _ => return Err(anyhow!("Unexpected response type")),
+                }
+            }
+        }
+        .await;
+
+        if result.is_err() {
+            self.stream = None; // The daemon may still be mid-stream; drop the connection
+        }
+        result
+    }
+
     pub async fn get_indexing_status(
         &mut self,
     ) -> Result<lsp_daemon::protocol::IndexingStatusInfo> {
@@ -1800,6 +2070,63 @@ impl LspClient {
         }
     }
 
+    /// List running language-server processes
+    pub async fn list_servers(
+        &mut self,
+    ) -> Result<Vec<lsp_daemon::protocol::LspServerProcessInfo>> {
+        let request = DaemonRequest::ListServers {
+            request_id: Uuid::new_v4(),
+        };
+        match self.send_request(request).await? {
+            DaemonResponse::ServerList { servers, .. } => Ok(servers),
+            DaemonResponse::Error { error, .. } => Err(anyhow!(error)),
+            _ => Err(anyhow!("Unexpected response type")),
+        }
+    }
+
+    /// Daemon, pool, cache and indexing queue metrics in the Prometheus text format
+    pub async fn metrics(&mut self) -> Result<String> {
+        let request = DaemonRequest::Metrics {
+            request_id: Uuid::new_v4(),
+        };
+        match self.send_request(request).await? {
+            DaemonResponse::Metrics { text, .. } => Ok(text),
+            DaemonResponse::Error { error, .. } => Err(anyhow!(error)),
+            _ => Err(anyhow!("Unexpected response type")),
+        }
+    }
+
+    /// Kill a single language-server process by id, returning its pid if known
+    pub async fn kill_server(&mut self, id: &str) -> Result<Option<u32>> {
+        let request = DaemonRequest::KillServer {
+            request_id: Uuid::new_v4(),
+            id: id.to_string(),
+        };
+        match self.send_request(request).await? {
+            DaemonResponse::ServerKilled { pid, .. } => Ok(pid),
+            DaemonResponse::Error { error, .. } => Err(anyhow!(error)),
+            _ => Err(anyhow!("Unexpected response type")),
+        }
+    }
+
+    /// Remove cached entries for deleted or changed files in a workspace
+    pub async fn prune_cache(
+        &mut self,
+        workspace_path: Option<PathBuf>,
+        dry_run: bool,
+    ) -> Result<lsp_daemon::protocol::CachePruneResult> {
+        let request = DaemonRequest::CachePrune {
+            request_id: Uuid::new_v4(),
+            workspace_path,
+            dry_run,
+        };
+        match self.send_request(request).await? {
+            DaemonResponse::CachePruned { result, .. } => Ok(result),
+            DaemonResponse::Error { error, .. } => Err(anyhow!(error)),
+            _ => Err(anyhow!("Unexpected response type for cache prune")),
+        }
+    }
+
     /// Fetch workspace DB path from daemon
     pub async fn get_workspace_db_path(
         &mut self,
@@ -1960,7 +2287,7 @@ impl LspClient {
             workspace_hint: self.config.workspace_hint.as_ref().map(PathBuf::from),
         };
 
-        let response = self.send_request(request).await?;
+        let response = self.send_streamed(request, STREAM_CHUNK_SIZE).await?;
 
         match response {
             DaemonResponse::References { locations, .. } => Ok(locations),
@@ -1993,6 +2320,93 @@ impl LspClient {
         }
     }
 
+    /// Get the occurrences of the symbol at a position within its file, each marked as a
+    /// read, a write or a plain text match
+    pub async fn call_document_highlight(
+        &mut self,
+        file: &Path,
+        line: u32,
+        column: u32,
+    ) -> Result<Vec<lsp_daemon::protocol::DocumentHighlight>> {
+        let request = DaemonRequest::DocumentHighlight {
+            request_id: Uuid::new_v4(),
+            file_path: file.to_path_buf(),
+            line,
+            column,
+            workspace_hint: self.config.workspace_hint.as_ref().map(PathBuf::from),
+        };
+
+        let response = self.send_request(request).await?;
+
+        match response {
+            DaemonResponse::DocumentHighlight { highlights, .. } => Ok(highlights),
+            DaemonResponse::Error { error, .. } => Err(anyhow!(error)),
+            _ => Err(anyhow!("Unexpected response type")),
+        }
+    }
+
+    /// Get the edits that rename the symbol at a position to `new_name`, grouped per file,
+    /// along with warnings for parts of the server's edit that cannot be represented
+    pub async fn call_rename(
+        &mut self,
+        file: &Path,
+        line: u32,
+        column: u32,
+        new_name: &str,
+    ) -> Result<(Vec<lsp_daemon::protocol::FileEdits>, Vec<String>)> {
+        let request = DaemonRequest::Rename {
+            request_id: Uuid::new_v4(),
+            file_path: file.to_path_buf(),
+            line,
+            column,
+            new_name: new_name.to_string(),
+            workspace_hint: self.config.workspace_hint.as_ref().map(PathBuf::from),
+        };
+
+        let response = self.send_request(request).await?;
+
+        match response {
+            DaemonResponse::Rename {
+                files, warnings, ..
+            } => Ok((files, warnings.unwrap_or_default())),
+            DaemonResponse::Error { error, .. } => Err(anyhow!(error)),
+            _ => Err(anyhow!("Unexpected response type")),
+        }
+    }
+
+    /// Get hover information for several positions in one daemon round-trip. Requests that
+    /// go to the same language server are pipelined. Results are in the order of `positions`,
+    /// with `None` for positions without hover information or whose request failed.
+    pub async fn hover_batch(
+        &mut self,
+        positions: Vec<lsp_daemon::protocol::HoverPosition>,
+    ) -> Result<Vec<Option<lsp_daemon::protocol::HoverContent>>> {
+        if positions.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let request = DaemonRequest::HoverBatch {
+            request_id: Uuid::new_v4(),
+            positions,
+            workspace_hint: self.config.workspace_hint.as_ref().map(PathBuf::from),
+        };
+
+        let response = self.send_request(request).await?;
+
+        match response {
+            DaemonResponse::HoverBatch {
+                results, warnings, ..
+            } => {
+                for warning in warnings.unwrap_or_default() {
+                    debug!("Hover batch: {}", warning);
+                }
+                Ok(results)
+            }
+            DaemonResponse::Error { error, .. } => Err(anyhow!(error)),
+            _ => Err(anyhow!("Unexpected response type")),
+        }
+    }
+
     /// Get document symbols for a file
     pub async fn call_document_symbols(
         &mut self,
@@ -2209,7 +2623,7 @@ impl LspClient {
             workspace_hint: self.config.workspace_hint.as_ref().map(PathBuf::from),
         };
 
-        let response = self.send_request(request).await?;
+        let response = self.send_streamed(request, STREAM_CHUNK_SIZE).await?;
 
         match response {
             DaemonResponse::WorkspaceSymbols { mut symbols, .. } => {
@@ -2223,6 +2637,33 @@ impl LspClient {
         }
     }
 
+    /// Search workspace symbols and rank them client-side by fuzzy score. The server is asked
+    /// for a broad candidate set (the query's first character as well as the full query),
+    /// which is then reordered before `max_results` is applied.
+    pub async fn call_workspace_symbols_fuzzy(
+        &mut self,
+        query: &str,
+        max_results: Option<usize>,
+    ) -> Result<Vec<crate::lsp_integration::symbol_resolver::FuzzySymbol>> {
+        let mut queries = Vec::new();
+        if let Some(first) = query.chars().find(|c| c.is_alphanumeric()) {
+            queries.push(first.to_string());
+        }
+        if !queries.iter().any(|q| q == query) {
+            queries.push(query.to_string());
+        }
+
+        let mut candidates = Vec::new();
+        for candidate_query in queries {
+            candidates.extend(self.call_workspace_symbols(&candidate_query, None).await?);
+        }
+        Ok(crate::lsp_integration::symbol_resolver::rank_symbols_fuzzy(
+            query,
+            candidates,
+            max_results,
+        ))
+    }
+
     /// Get readiness status for LSP servers
     pub async fn get_readiness_status(
         &mut self,
@@ -2344,6 +2785,49 @@ impl LspClient {
         }
     }
 
+    /// Get references, callers, callees, implementations and type definition of a symbol in
+    /// one request. Returns the symbol's call hierarchy item when the server reported one.
+    pub async fn call_related(
+        &mut self,
+        file: &Path,
+        line: u32,
+        column: u32,
+    ) -> Result<(
+        Option<lsp_daemon::protocol::CallHierarchyItem>,
+        Vec<lsp_daemon::protocol::RelatedGroup>,
+        Vec<String>,
+    )> {
+        let request = DaemonRequest::Related {
+            request_id: Uuid::new_v4(),
+            file_path: file.to_path_buf(),
+            line,
+            column,
+            workspace_hint: self.config.workspace_hint.as_ref().map(PathBuf::from),
+        };
+
+        // The slowest lookup is usually call hierarchy, so allow as long as for that
+        let call_timeout = self.response_timeout(&request);
+        let response = timeout(call_timeout, self.send_request(request))
+            .await
+            .map_err(|_| {
+                anyhow!(
+                    "Related request timed out after {}ms",
+                    call_timeout.as_millis()
+                )
+            })??;
+
+        match response {
+            DaemonResponse::Related {
+                symbol,
+                groups,
+                warnings,
+                ..
+            } => Ok((symbol, groups, warnings.unwrap_or_default())),
+            DaemonResponse::Error { error, .. } => Err(anyhow!(error)),
+            _ => Err(anyhow!("Unexpected response type")),
+        }
+    }
+
     /// Extract FQN using centralized daemon logic
     fn get_fqn_from_ast(file_path: &Path, line: u32, column: u32) -> Result<String> {
         lsp_daemon::fqn::get_fqn_from_ast(file_path, line, column, None)
@@ -2374,6 +2858,35 @@ impl LspClient {
 
         self.send_request(request).await
     }
+
+    /// Send graph export request to daemon, for the subgraph of `root` or the changes
+    /// since the analysis run `since_run`, optionally with nodes for external targets
+    #[allow(clippy::too_many_arguments)]
+    pub async fn export_graph(
+        &mut self,
+        workspace_path: Option<PathBuf>,
+        output_path: PathBuf,
+        format: String,
+        root: Option<String>,
+        direction: String,
+        max_depth: u32,
+        since_run: Option<i64>,
+        include_external: bool,
+    ) -> Result<DaemonResponse> {
+        let request = DaemonRequest::GraphExport {
+            request_id: Uuid::new_v4(),
+            workspace_path,
+            output_path,
+            format,
+            root,
+            direction,
+            max_depth,
+            since_run,
+            include_external,
+        };
+
+        self.send_request(request).await
+    }
 }
 
 #[cfg(test)]
@@ -2389,6 +2902,7 @@ mod tests {
             config,
             daemon_started_by_us: false,
             position_analyzer: PositionAnalyzer::new(),
+            request_deadline_ms: None,
         };
 
         // Test supported file types
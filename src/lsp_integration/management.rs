@@ -98,6 +98,377 @@ impl LspManager {
         Ok(())
     }
 
+    /// List running language-server processes; does not start the daemon
+    async fn list_servers(fmt: &str) -> Result<()> {
+        let config = LspConfig {
+            use_daemon: true,
+            auto_start: false,
+            ..Default::default()
+        };
+        let mut client = LspClient::new(config).await?;
+        let servers = client.list_servers().await?;
+
+        match fmt {
+            "json" => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&json!({
+                        "total": servers.len(),
+                        "servers": servers,
+                    }))?
+                );
+            }
+            _ => {
+                println!(
+                    "{} {}",
+                    "LSP Servers".bold().cyan(),
+                    format!("({})", servers.len()).dimmed()
+                );
+                if servers.is_empty() {
+                    println!("  {}", "No language servers running".yellow());
+                }
+                for s in &servers {
+                    let pid = s.pid.map(|p| p.to_string()).unwrap_or("-".to_string());
+                    let memory = s
+                        .memory_mb
+                        .map(|m| format!("{m}MB"))
+                        .unwrap_or("-".to_string());
+                    println!(
+                        "  {} pid={} uptime={} memory={} restarts={}",
+                        s.id.bold(),
+                        pid,
+                        Self::format_duration(Duration::from_secs(s.uptime_secs)),
+                        memory,
+                        s.restarts
+                    );
+                    for ws in &s.workspaces {
+                        println!("    {}", ws.display().to_string().dimmed());
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Kill a single language-server process by id
+    async fn kill_server(id: &str, format: &str) -> Result<()> {
+        let config = LspConfig {
+            use_daemon: true,
+            auto_start: false,
+            ..Default::default()
+        };
+        let mut client = LspClient::new(config).await?;
+        let pid = client.kill_server(id).await?;
+
+        match format {
+            "json" => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&json!({
+                        "status": "killed",
+                        "id": id,
+                        "pid": pid,
+                    }))?
+                );
+            }
+            _ => {
+                let pid = pid.map(|p| format!(" (pid {p})")).unwrap_or_default();
+                println!("{} Killed {} server{}", "✓".green(), id.bold(), pid);
+            }
+        }
+        Ok(())
+    }
+
+    /// Print the daemon's Prometheus metrics, or serve them over HTTP at `listen`
+    async fn show_metrics(listen: Option<&str>) -> Result<()> {
+        match listen {
+            Some(addr) => Self::serve_metrics(addr).await,
+            None => {
+                print!("{}", Self::fetch_metrics().await?);
+                Ok(())
+            }
+        }
+    }
+
+    async fn fetch_metrics() -> Result<String> {
+        let config = LspConfig {
+            use_daemon: true,
+            auto_start: false,
+            ..Default::default()
+        };
+        let mut client = LspClient::new(config).await?;
+        client.metrics().await
+    }
+
+    /// Answer `GET /metrics` on `addr` with fresh metrics from the daemon, one connection at
+    /// a time; 503 while the daemon is unreachable
+    async fn serve_metrics(addr: &str) -> Result<()> {
+        let listener = tokio::net::TcpListener::bind(addr)
+            .await
+            .with_context(|| format!("Failed to listen on {addr}"))?;
+        eprintln!(
+            "Serving metrics at http://{}/metrics",
+            listener.local_addr()?
+        );
+
+        loop {
+            let (mut stream, _) = listener.accept().await?;
+            let mut buf = vec![0u8; 8192];
+            let n = match time::timeout(Duration::from_secs(5), stream.read(&mut buf)).await {
+                Ok(Ok(n)) => n,
+                _ => continue,
+            };
+            let request = String::from_utf8_lossy(&buf[..n]);
+            let mut request_line = request.lines().next().unwrap_or("").split_whitespace();
+            let method = request_line.next().unwrap_or("");
+            let path = request_line.next().unwrap_or("");
+            let path = path.split('?').next().unwrap_or("");
+
+            let (status, content_type, body) = match (method, path) {
+                ("GET", "/metrics") => match Self::fetch_metrics().await {
+                    Ok(text) => ("200 OK", lsp_daemon::metrics::PROMETHEUS_CONTENT_TYPE, text),
+                    Err(e) => {
+                        warn!("Failed to fetch daemon metrics: {}", e);
+                        (
+                            "503 Service Unavailable",
+                            "text/plain; charset=utf-8",
+                            format!("LSP daemon unavailable: {e}\n"),
+                        )
+                    }
+                },
+                ("GET", _) => (
+                    "404 Not Found",
+                    "text/plain; charset=utf-8",
+                    "Not found; metrics are at /metrics\n".to_string(),
+                ),
+                _ => (
+                    "405 Method Not Allowed",
+                    "text/plain; charset=utf-8",
+                    "Method not allowed\n".to_string(),
+                ),
+            };
+            let response = format!(
+                "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            let _ = stream.write_all(response.as_bytes()).await;
+            let _ = stream.shutdown().await;
+        }
+    }
+
+    /// Compute the call hierarchies of the `file#symbol` targets listed in `list`, so the
+    /// daemon answers later queries for them from its cache
+    async fn warm_call_hierarchies(
+        list: &Path,
+        concurrency: Option<u64>,
+        format: &str,
+    ) -> Result<()> {
+        use crate::lsp_integration::call_graph_cache::{CallGraphCache, CallGraphCacheConfig};
+        use crate::lsp_integration::symbol_resolver::{resolve_location, ResolvedLocation};
+        use crate::lsp_integration::types::NodeKey;
+        use std::collections::HashMap;
+        use std::sync::Arc;
+
+        let content = std::fs::read_to_string(list)
+            .with_context(|| format!("Failed to read target list {}", list.display()))?;
+
+        // Resolve every target up front; unresolvable ones are reported as failures
+        let mut keys = Vec::new();
+        let mut locations: HashMap<NodeKey, ResolvedLocation> = HashMap::new();
+        let mut failed: Vec<(String, String)> = Vec::new();
+        for target in content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        {
+            let Some((_, symbol)) = target.split_once('#') else {
+                failed.push((target.to_string(), "expected 'file#symbol'".to_string()));
+                continue;
+            };
+            let resolved = resolve_location(target).and_then(|location| {
+                let bytes = std::fs::read(&location.file_path).with_context(|| {
+                    format!("Failed to read file: {}", location.file_path.display())
+                })?;
+                Ok((location, format!("{:x}", md5::compute(bytes))))
+            });
+            match resolved {
+                Ok((location, content_md5)) => {
+                    let key = NodeKey::new(symbol, location.file_path.clone(), content_md5);
+                    locations.insert(key.clone(), location);
+                    keys.push(key);
+                }
+                Err(e) => failed.push((target.to_string(), e.to_string())),
+            }
+        }
+
+        Self::ensure_ready().await?;
+        let concurrency = match concurrency {
+            Some(n) => n as usize,
+            None => {
+                let mut client = LspClient::new(LspConfig::default()).await?;
+                let status = client.get_status().await?;
+                status
+                    .language_pools
+                    .values()
+                    .map(|pool| pool.total_servers)
+                    .sum::<usize>()
+                    .max(1)
+            }
+        };
+
+        let total = keys.len() + failed.len();
+        let locations = Arc::new(locations);
+        let cache = Arc::new(CallGraphCache::new(CallGraphCacheConfig::default()));
+        let report = cache
+            .warm(keys, concurrency, |key| {
+                let location = locations.get(&key).cloned();
+                async move {
+                    let location =
+                        location.ok_or_else(|| anyhow!("No location for {}", key.symbol))?;
+                    let mut client = LspClient::new(LspConfig::default()).await?;
+                    client
+                        .get_call_hierarchy_with_source(
+                            &location.file_path,
+                            location.line,
+                            location.column,
+                        )
+                        .await
+                }
+            })
+            .await;
+        failed.extend(
+            report
+                .failed
+                .into_iter()
+                .map(|(key, error)| (format!("{}#{}", key.file.display(), key.symbol), error)),
+        );
+
+        match format {
+            "json" => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&json!({
+                        "targets": total,
+                        "computed": report.computed,
+                        "cached": report.cached,
+                        "failed": failed
+                            .iter()
+                            .map(|(target, error)| json!({ "target": target, "error": error }))
+                            .collect::<Vec<_>>(),
+                    }))?
+                );
+            }
+            _ => {
+                println!(
+                    "{} {} computed, {} already cached, {} failed ({} targets, concurrency {})",
+                    "Call hierarchy warm:".bold().cyan(),
+                    report.computed.to_string().green(),
+                    report.cached,
+                    if failed.is_empty() {
+                        "0".normal()
+                    } else {
+                        failed.len().to_string().red()
+                    },
+                    total,
+                    concurrency
+                );
+                for (target, error) in &failed {
+                    println!("  {} {}: {}", "✗".red(), target, error.dimmed());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Query indexed symbols via the daemon and list them
+    async fn handle_symbols_command(
+        workspace: Option<std::path::PathBuf>,
+        kind: Option<String>,
+        visibility: Option<String>,
+        language: Option<String>,
+        file_glob: Option<String>,
+        limit: Option<usize>,
+        format: &str,
+    ) -> Result<()> {
+        use lsp_daemon::protocol::{DaemonRequest, DaemonResponse};
+
+        // The daemon resolves relative paths against its own directory, not ours
+        let workspace = match workspace {
+            Some(path) => path
+                .canonicalize()
+                .with_context(|| format!("Workspace {} does not exist", path.display()))?,
+            None => std::env::current_dir()?,
+        };
+        let mut client = LspClient::new(LspConfig::default()).await?;
+        let request = DaemonRequest::FindSymbols {
+            request_id: uuid::Uuid::new_v4(),
+            workspace_path: Some(workspace),
+            kind,
+            visibility,
+            language,
+            file_glob,
+            limit,
+        };
+        match client.send(request).await? {
+            DaemonResponse::SymbolsFound { symbols, .. } => Self::print_symbols(&symbols, format),
+            DaemonResponse::Error { error, .. } => Err(anyhow!(error)),
+            _ => Err(anyhow!("Unexpected response")),
+        }
+    }
+
+    /// Full-text search over indexed symbols via the daemon
+    async fn handle_search_symbols_command(
+        query: &str,
+        workspace: Option<std::path::PathBuf>,
+        limit: usize,
+        format: &str,
+    ) -> Result<()> {
+        use lsp_daemon::protocol::{DaemonRequest, DaemonResponse};
+
+        let workspace = match workspace {
+            Some(path) => path
+                .canonicalize()
+                .with_context(|| format!("Workspace {} does not exist", path.display()))?,
+            None => std::env::current_dir()?,
+        };
+        let mut client = LspClient::new(LspConfig::default()).await?;
+        let request = DaemonRequest::SearchSymbols {
+            request_id: uuid::Uuid::new_v4(),
+            workspace_path: Some(workspace),
+            query: query.to_string(),
+            limit,
+        };
+        match client.send(request).await? {
+            DaemonResponse::SymbolsFound { symbols, .. } => Self::print_symbols(&symbols, format),
+            DaemonResponse::Error { error, .. } => Err(anyhow!(error)),
+            _ => Err(anyhow!("Unexpected response")),
+        }
+    }
+
+    fn print_symbols(symbols: &[lsp_daemon::database::SymbolState], format: &str) -> Result<()> {
+        match format {
+            "json" => println!("{}", serde_json::to_string_pretty(symbols)?),
+            _ => {
+                if symbols.is_empty() {
+                    println!("{}", "No matching symbols".yellow());
+                    return Ok(());
+                }
+                for symbol in symbols {
+                    println!(
+                        "{} {} {}  {}:{}",
+                        format!("{:<10}", symbol.kind).cyan(),
+                        format!("{:<9}", symbol.visibility.as_deref().unwrap_or("-")).dimmed(),
+                        symbol.fqn.as_deref().unwrap_or(&symbol.name).bold(),
+                        symbol.file_path,
+                        symbol.def_start_line
+                    );
+                }
+                println!("\n{} {}", "Total:".bold(), symbols.len());
+            }
+        }
+        Ok(())
+    }
+
     /// Run an on-demand edge audit via the daemon and print a compact report
     async fn handle_edge_audit_command(
         workspace: Option<std::path::PathBuf>,
@@ -335,6 +706,7 @@ impl LspManager {
                 recursive,
                 max_workers,
                 memory_budget,
+                max_duration,
                 format,
                 progress,
                 wait,
@@ -346,6 +718,7 @@ impl LspManager {
                     *recursive,
                     *max_workers,
                     *memory_budget,
+                    *max_duration,
                     format,
                     *progress,
                     *wait,
@@ -361,10 +734,22 @@ impl LspManager {
             LspSubcommands::IndexStop { force, format } => {
                 Self::handle_index_stop_command(*force, format).await
             }
+            LspSubcommands::IndexPrioritize { file, format } => {
+                Self::handle_index_prioritize_command(file, format).await
+            }
             LspSubcommands::IndexConfig { config_command } => {
                 Self::handle_index_config_command(config_command, format).await
             }
-            LspSubcommands::Call { command } => Self::handle_call_command(command).await,
+            LspSubcommands::Call {
+                workspace_relative,
+                timeout_secs,
+                command,
+            } => Self::handle_call_command(command, *workspace_relative, *timeout_secs).await,
+            LspSubcommands::CallHierarchyWarm {
+                file,
+                concurrency,
+                format,
+            } => Self::warm_call_hierarchies(file, *concurrency, format).await,
             LspSubcommands::IndexExport {
                 workspace,
                 output,
@@ -373,7 +758,26 @@ impl LspManager {
                 timeout_secs: _,
                 yes,
                 offline,
+                root,
+                direction,
+                depth,
+                since_run,
+                include_external,
             } => {
+                if root.is_some() || since_run.is_some() {
+                    return Self::handle_graph_export(
+                        workspace.clone(),
+                        output.clone(),
+                        root.as_deref(),
+                        direction,
+                        *depth,
+                        *since_run,
+                        *include_external,
+                        *daemon,
+                        *yes,
+                    )
+                    .await;
+                }
                 Self::handle_index_export(
                     workspace.clone(),
                     output.clone(),
@@ -404,6 +808,34 @@ impl LspManager {
                 samples,
                 format,
             } => Self::handle_edge_audit_command(workspace.clone(), *samples, format).await,
+            LspSubcommands::Symbols {
+                workspace,
+                kind,
+                visibility,
+                language,
+                glob,
+                limit,
+                format,
+            } => {
+                Self::handle_symbols_command(
+                    workspace.clone(),
+                    kind.clone(),
+                    visibility.clone(),
+                    language.clone(),
+                    glob.clone(),
+                    *limit,
+                    format,
+                )
+                .await
+            }
+            LspSubcommands::SearchSymbols {
+                query,
+                workspace,
+                limit,
+                format,
+            } => {
+                Self::handle_search_symbols_command(query, workspace.clone(), *limit, format).await
+            }
             LspSubcommands::EnrichSymbol {
                 target,
                 workspace_hint,
@@ -452,6 +884,9 @@ impl LspManager {
                     }
                 }
             }
+            LspSubcommands::Servers { format: fmt } => Self::list_servers(fmt).await,
+            LspSubcommands::Metrics { listen } => Self::show_metrics(listen.as_deref()).await,
+            LspSubcommands::KillServer { id } => Self::kill_server(id, format).await,
         }
     }
 
@@ -2684,6 +3119,11 @@ impl LspManager {
                     println!("  Detailed: {detailed}");
                 }
             },
+            CacheSubcommands::Prune {
+                workspace,
+                dry_run,
+                format,
+            } => Self::handle_cache_prune(client, workspace.as_ref(), *dry_run, format).await?,
             CacheSubcommands::ListKeys {
                 workspace,
                 operation,
@@ -2992,6 +3432,7 @@ impl LspManager {
         recursive: bool,
         max_workers: Option<usize>,
         memory_budget: Option<u64>,
+        max_duration: Option<u64>,
         format: &str,
         show_progress: bool,
         wait: bool,
@@ -3050,6 +3491,7 @@ impl LspManager {
             specific_files,
             max_file_size_mb: Some(10),
             incremental: Some(true),
+            max_duration_secs: max_duration,
             languages: language_list,
             recursive,
             ..Default::default()
@@ -3481,6 +3923,44 @@ impl LspManager {
         Ok(())
     }
 
+    /// Handle index prioritize command - move a file to the front of the indexing queue
+    async fn handle_index_prioritize_command(file: &Path, format: &str) -> Result<()> {
+        let file_path = file
+            .canonicalize()
+            .with_context(|| format!("File not found: {}", file.display()))?;
+
+        let mut client = LspClient::new(LspConfig::default()).await?;
+        let changed = client.prioritize_indexing(&file_path).await?;
+
+        match format {
+            "json" => {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&json!({
+                        "file_path": file_path,
+                        "changed": changed
+                    }))?
+                );
+            }
+            _ => {
+                if changed {
+                    println!(
+                        "{} {}",
+                        "Prioritized for indexing:".green(),
+                        file_path.display()
+                    );
+                } else {
+                    println!(
+                        "{} {}",
+                        "Already queued at high priority:".yellow(),
+                        file_path.display()
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Handle index stop command
     async fn handle_index_stop_command(force: bool, format: &str) -> Result<()> {
         // Do not auto-start the daemon for a stop request; if it's not
@@ -3589,6 +4069,8 @@ impl LspManager {
                 lsp_operation_timeout_ms,
                 lsp_priority_operations,
                 lsp_disabled_operations,
+                language,
+                enabled,
                 format,
             } => {
                 let mut config = client.get_indexing_config().await?;
@@ -3655,6 +4137,14 @@ impl LspManager {
                         .filter(|s| !s.is_empty())
                         .collect();
                 }
+                if let (Some(language), Some(enabled)) = (language, enabled) {
+                    let language = Self::parse_language(language)?;
+                    config
+                        .language_configs
+                        .entry(language.as_str().to_string())
+                        .or_default()
+                        .enabled = Some(*enabled);
+                }
 
                 client.set_indexing_config(config.clone()).await?;
                 Self::display_indexing_config(&config, format).await
@@ -3698,6 +4188,10 @@ impl LspManager {
                     println!("  {}: {}", "Session ID".bold(), session_id);
                 }
 
+                if let Some(run_id) = status.analysis_run_id {
+                    println!("  {}: {}", "Analysis Run".bold(), run_id);
+                }
+
                 if let Some(started_at) = status.started_at {
                     let now = std::time::SystemTime::now()
                         .duration_since(std::time::UNIX_EPOCH)
@@ -3731,6 +4225,26 @@ impl LspManager {
                 println!("  {}: {}", "Processed".bold(), progress.processed_files);
                 println!("  {}: {}", "Failed".bold(), progress.failed_files);
                 println!("  {}: {}", "Skipped".bold(), progress.skipped_files);
+                if progress.test_files_skipped > 0 {
+                    println!(
+                        "  {}: {}",
+                        "Test files excluded".bold(),
+                        progress.test_files_skipped
+                    );
+                }
+                if progress.deferred_files > 0 {
+                    let coverage = if progress.total_files > 0 {
+                        progress.processed_files as f64 / progress.total_files as f64 * 100.0
+                    } else {
+                        0.0
+                    };
+                    println!(
+                        "  {}: {} ({:.1}% coverage, remaining files are indexed on the next run)",
+                        "Deferred (time budget)".bold(),
+                        progress.deferred_files,
+                        coverage
+                    );
+                }
                 println!("  {}: {}", "Active".bold(), progress.active_files);
                 println!(
                     "  {}: {} symbols",
@@ -4336,6 +4850,21 @@ impl LspManager {
                     println!("  {}: All files", "Include Patterns".bold());
                 }
 
+                let mut disabled_languages: Vec<&str> = config
+                    .language_configs
+                    .iter()
+                    .filter(|(_, c)| c.enabled == Some(false))
+                    .map(|(name, _)| name.as_str())
+                    .collect();
+                disabled_languages.sort_unstable();
+                if !disabled_languages.is_empty() {
+                    println!(
+                        "  {}: {}",
+                        "Disabled Languages".bold(),
+                        disabled_languages.join(", ")
+                    );
+                }
+
                 // LSP Caching Configuration section
                 println!("\n{}", "LSP Caching Configuration".bold().magenta());
 
@@ -4425,6 +4954,18 @@ impl LspManager {
         detailed: bool,
         interval: u64,
     ) -> Result<()> {
+        // JSON consumers get the daemon's pushed progress events as JSON lines
+        if format == "json" {
+            let reason = client
+                .subscribe_indexing_progress(interval.saturating_mul(1000), |event| {
+                    println!("{}", serde_json::to_string(event)?);
+                    Ok(())
+                })
+                .await?;
+            println!("{}", json!({ "status": "ended", "reason": reason }));
+            return Ok(());
+        }
+
         let mut interval = tokio::time::interval(Duration::from_secs(interval));
         interval.set_missed_tick_behavior(MissedTickBehavior::Skip);
 
@@ -4840,33 +5381,88 @@ impl LspManager {
         Ok(seconds)
     }
 
-    /// Handle workspace cache clear command
-    async fn handle_workspace_cache_clear(
+    /// Remove cached symbols of files that were deleted or changed since they were indexed
+    async fn handle_cache_prune(
         client: &mut LspClient,
         workspace_path: Option<&std::path::PathBuf>,
-        older_than: Option<&str>,
-        force: bool,
-        yes: bool,
+        dry_run: bool,
         format: &str,
     ) -> Result<()> {
-        // Parse older_than duration if provided
-        let older_than_seconds = if let Some(duration_str) = older_than {
-            Some(Self::parse_duration_string(duration_str)?)
-        } else {
-            None
+        // Resolve the workspace on the client side; the daemon's working directory
+        // is unrelated to where the command was invoked.
+        let workspace = match workspace_path {
+            Some(path) => path.clone(),
+            None => std::env::current_dir()?,
         };
+        let workspace = workspace.canonicalize().unwrap_or(workspace);
 
-        // Confirmation prompt for destructive operations
-        if !force && !yes && std::env::var("PROBE_BATCH").unwrap_or_default() != "1" {
-            use std::io::{self, Write};
+        let result = client.prune_cache(Some(workspace), dry_run).await?;
 
-            if let Some(age) = older_than {
-                if workspace_path.is_some() {
-                    print!("Are you sure you want to clear workspace cache entries older than {age}? [y/N]: ");
-                } else {
-                    print!("Are you sure you want to clear ALL workspace cache entries older than {age}? [y/N]: ");
-                }
-            } else if workspace_path.is_some() {
+        match format {
+            "json" => {
+                println!("{}", serde_json::to_string_pretty(&result)?);
+            }
+            _ => {
+                let title = if result.dry_run {
+                    "Cache Prune (dry run)"
+                } else {
+                    "Cache Prune Results"
+                };
+                println!("{}", title.bold().green());
+                println!(
+                    "  {} {}",
+                    "Workspace:".bold(),
+                    result.workspace_path.display()
+                );
+                println!("  {} {}", "Files checked:".bold(), result.files_checked);
+                println!("  {} {}", "Missing files:".bold(), result.missing_files);
+                println!("  {} {}", "Changed files:".bold(), result.changed_files);
+                let verb = if result.dry_run {
+                    "would be removed"
+                } else {
+                    "removed"
+                };
+                println!(
+                    "  {} {} {}",
+                    "Symbols:".bold(),
+                    result.symbols_removed,
+                    verb
+                );
+                println!("  {} {} {}", "Edges:".bold(), result.edges_removed, verb);
+                println!("  {} {}ms", "Duration:".bold(), result.duration_ms);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Handle workspace cache clear command
+    async fn handle_workspace_cache_clear(
+        client: &mut LspClient,
+        workspace_path: Option<&std::path::PathBuf>,
+        older_than: Option<&str>,
+        force: bool,
+        yes: bool,
+        format: &str,
+    ) -> Result<()> {
+        // Parse older_than duration if provided
+        let older_than_seconds = if let Some(duration_str) = older_than {
+            Some(Self::parse_duration_string(duration_str)?)
+        } else {
+            None
+        };
+
+        // Confirmation prompt for destructive operations
+        if !force && !yes && std::env::var("PROBE_BATCH").unwrap_or_default() != "1" {
+            use std::io::{self, Write};
+
+            if let Some(age) = older_than {
+                if workspace_path.is_some() {
+                    print!("Are you sure you want to clear workspace cache entries older than {age}? [y/N]: ");
+                } else {
+                    print!("Are you sure you want to clear ALL workspace cache entries older than {age}? [y/N]: ");
+                }
+            } else if workspace_path.is_some() {
                 print!("Are you sure you want to clear the workspace cache? [y/N]: ");
             } else {
                 print!("Are you sure you want to clear ALL workspace caches? [y/N]: ");
@@ -4958,15 +5554,26 @@ impl LspManager {
     }
 
     /// Handle LSP call commands
-    async fn handle_call_command(command: &crate::lsp_integration::LspCallCommands) -> Result<()> {
+    async fn handle_call_command(
+        command: &crate::lsp_integration::LspCallCommands,
+        workspace_relative: bool,
+        timeout_secs: Option<u64>,
+    ) -> Result<()> {
+        use crate::lsp_integration::path_resolver::PathResolver;
         use crate::lsp_integration::LspCallCommands;
 
         // Ensure daemon is ready
         Self::ensure_ready().await?;
 
         // Create client
-        let config = LspConfig::default();
+        let mut config = LspConfig::default();
+        if let Some(secs) = timeout_secs {
+            config.timeout_ms = secs.saturating_mul(1000);
+        }
         let mut client = LspClient::new(config).await?;
+        if let Some(secs) = timeout_secs {
+            client.set_request_deadline(secs.saturating_mul(1000));
+        }
 
         match command {
             LspCallCommands::Definition { location, format } => {
@@ -4974,11 +5581,13 @@ impl LspManager {
                 let results = client
                     .call_definition(&resolved.file_path, resolved.line, resolved.column)
                     .await?;
-                Self::display_locations(&results, "Definition", format).await
+                let paths = PathResolver::for_file(&resolved.file_path, workspace_relative)?;
+                Self::display_locations(&results, "Definition", &paths, format, false).await
             }
             LspCallCommands::References {
                 location,
                 include_declaration,
+                preview,
                 format,
             } => {
                 let resolved = crate::lsp_integration::symbol_resolver::resolve_location(location)?;
@@ -4990,7 +5599,8 @@ impl LspManager {
                         *include_declaration,
                     )
                     .await?;
-                Self::display_locations(&results, "References", format).await
+                let paths = PathResolver::for_file(&resolved.file_path, workspace_relative)?;
+                Self::display_locations(&results, "References", &paths, format, *preview).await
             }
             LspCallCommands::Hover { location, format } => {
                 let resolved = crate::lsp_integration::symbol_resolver::resolve_location(location)?;
@@ -4999,38 +5609,127 @@ impl LspManager {
                     .await?;
                 Self::display_hover_info(&result, format).await
             }
+            LspCallCommands::DocumentHighlight { location, format } => {
+                let resolved = crate::lsp_integration::symbol_resolver::resolve_location(location)?;
+                let highlights = client
+                    .call_document_highlight(&resolved.file_path, resolved.line, resolved.column)
+                    .await?;
+                let paths = PathResolver::for_file(&resolved.file_path, workspace_relative)?;
+                Self::display_document_highlights(&highlights, &resolved.file_path, &paths, format)
+                    .await
+            }
+            LspCallCommands::Rename {
+                location,
+                new_name,
+                apply,
+                format,
+            } => {
+                use crate::lsp_integration::rename;
+
+                let resolved = crate::lsp_integration::symbol_resolver::resolve_location(location)?;
+                let (files, warnings) = client
+                    .call_rename(
+                        &resolved.file_path,
+                        resolved.line,
+                        resolved.column,
+                        new_name,
+                    )
+                    .await?;
+                let files = rename::normalize_edits(files);
+                rename::check_files_on_disk(&files)?;
+                let conflicts = rename::find_conflicts(&files);
+
+                let applied = *apply && conflicts.is_empty();
+                if applied {
+                    rename::apply_workspace_edits(&files)?;
+                }
+                let paths = PathResolver::for_file(&resolved.file_path, workspace_relative)?;
+                Self::display_rename(&files, &conflicts, &warnings, applied, &paths, format)
+                    .await?;
+                if *apply && !applied {
+                    return Err(anyhow!(
+                        "Refusing to apply rename: found {} conflicting edit(s)",
+                        conflicts.len()
+                    ));
+                }
+                Ok(())
+            }
             LspCallCommands::DocumentSymbols { file, format } => {
                 let results = client.call_document_symbols(file).await?;
                 Self::display_document_symbols(&results, format).await
             }
+            LspCallCommands::WorkspaceSymbols {
+                query,
+                max_results,
+                fuzzy: true,
+                format,
+            } => {
+                let results = client
+                    .call_workspace_symbols_fuzzy(query, *max_results)
+                    .await?;
+                let paths = PathResolver::for_current_dir(workspace_relative)?;
+                Self::display_fuzzy_workspace_symbols(&results, &paths, format).await
+            }
             LspCallCommands::WorkspaceSymbols {
                 query,
                 max_results,
                 format,
+                ..
             } => {
                 let results = client.call_workspace_symbols(query, *max_results).await?;
-                Self::display_workspace_symbols(&results, format).await
+                let paths = PathResolver::for_current_dir(workspace_relative)?;
+                Self::display_workspace_symbols(&results, &paths, format).await
             }
-            LspCallCommands::CallHierarchy { location, format } => {
+            LspCallCommands::CallHierarchy {
+                location,
+                depth,
+                format,
+            } if *depth > 1 => {
+                let resolved = crate::lsp_integration::symbol_resolver::resolve_location(location)?;
+                let (root, warnings) = client
+                    .get_call_hierarchy_tree(
+                        &resolved.file_path,
+                        resolved.line,
+                        resolved.column,
+                        *depth,
+                    )
+                    .await?;
+                let paths = PathResolver::for_file(&resolved.file_path, workspace_relative)?;
+                Self::display_call_hierarchy_tree(&root, &warnings, &paths, format).await
+            }
+            LspCallCommands::CallHierarchy {
+                location, format, ..
+            } => {
                 let resolved = crate::lsp_integration::symbol_resolver::resolve_location(location)?;
                 let result = client
                     .get_call_hierarchy(&resolved.file_path, resolved.line, resolved.column)
                     .await?;
-                Self::display_call_hierarchy(&result, format).await
+                let paths = PathResolver::for_file(&resolved.file_path, workspace_relative)?;
+                Self::display_call_hierarchy(&result, &paths, format).await
             }
             LspCallCommands::Implementations { location, format } => {
                 let resolved = crate::lsp_integration::symbol_resolver::resolve_location(location)?;
                 let results = client
                     .call_implementations(&resolved.file_path, resolved.line, resolved.column)
                     .await?;
-                Self::display_locations(&results, "Implementations", format).await
+                let paths = PathResolver::for_file(&resolved.file_path, workspace_relative)?;
+                Self::display_locations(&results, "Implementations", &paths, format, false).await
             }
             LspCallCommands::TypeDefinition { location, format } => {
                 let resolved = crate::lsp_integration::symbol_resolver::resolve_location(location)?;
                 let results = client
                     .call_type_definition(&resolved.file_path, resolved.line, resolved.column)
                     .await?;
-                Self::display_locations(&results, "Type Definition", format).await
+                let paths = PathResolver::for_file(&resolved.file_path, workspace_relative)?;
+                Self::display_locations(&results, "Type Definition", &paths, format, false).await
+            }
+            LspCallCommands::Related { location, format } => {
+                let resolved = crate::lsp_integration::symbol_resolver::resolve_location(location)?;
+                let (symbol, groups, warnings) = client
+                    .call_related(&resolved.file_path, resolved.line, resolved.column)
+                    .await?;
+                let paths = PathResolver::for_file(&resolved.file_path, workspace_relative)?;
+                Self::display_related(symbol.as_ref(), &groups, &warnings, &paths, format).await
             }
             LspCallCommands::Fqn { location, format } => {
                 let resolved = crate::lsp_integration::symbol_resolver::resolve_location(location)?;
@@ -5046,17 +5745,39 @@ impl LspManager {
     async fn display_locations(
         locations: &[lsp_daemon::protocol::Location],
         command_name: &str,
+        paths: &crate::lsp_integration::path_resolver::PathResolver,
         format: &str,
+        preview: bool,
     ) -> Result<()> {
+        use crate::lsp_integration::preview::{location_previews, LocationPreview};
+
+        // Previews are read before paths are rewritten for display
+        let previews = if preview && format != "plain" {
+            location_previews(locations)
+        } else {
+            Vec::new()
+        };
+
         match format {
             "json" => {
-                println!("{}", serde_json::to_string_pretty(locations)?);
+                let mut locations = locations.to_vec();
+                paths.apply(locations.as_mut_slice());
+                if preview {
+                    let locations: Vec<LocationPreview> = locations
+                        .into_iter()
+                        .zip(previews)
+                        .map(|(location, preview)| LocationPreview { location, preview })
+                        .collect();
+                    println!("{}", serde_json::to_string_pretty(&locations)?);
+                } else {
+                    println!("{}", serde_json::to_string_pretty(&locations)?);
+                }
             }
             "plain" => {
                 for location in locations {
                     println!(
                         "{}:{}:{}",
-                        location.uri,
+                        paths.display(&location.uri),
                         location.range.start.line + 1,
                         location.range.start.character + 1
                     );
@@ -5084,13 +5805,17 @@ impl LspManager {
                     println!(
                         "{} {}:{}:{}",
                         format!("{}.", i + 1).dimmed(),
-                        file_path.cyan(),
+                        paths.display(file_path).cyan(),
                         (location.range.start.line + 1).to_string().yellow(),
                         (location.range.start.character + 1).to_string().yellow()
                     );
 
                     // Show a snippet of code if possible
-                    if let Ok(content) = std::fs::read_to_string(file_path) {
+                    if preview {
+                        if let Some(snippet) = &previews[i] {
+                            println!("   {}", snippet.dimmed());
+                        }
+                    } else if let Ok(content) = std::fs::read_to_string(file_path) {
                         let lines: Vec<&str> = content.lines().collect();
                         let line_idx = location.range.start.line as usize;
                         if line_idx < lines.len() {
@@ -5119,6 +5844,243 @@ impl LspManager {
         Ok(())
     }
 
+    /// Display the occurrences of a symbol within a file and whether each reads or writes it
+    async fn display_document_highlights(
+        highlights: &[lsp_daemon::protocol::DocumentHighlight],
+        file_path: &std::path::Path,
+        paths: &crate::lsp_integration::path_resolver::PathResolver,
+        format: &str,
+    ) -> Result<()> {
+        use lsp_daemon::protocol::DocumentHighlightKind;
+
+        match format {
+            "json" => {
+                println!("{}", serde_json::to_string_pretty(highlights)?);
+            }
+            "plain" => {
+                for highlight in highlights {
+                    println!(
+                        "{}:{}:{} {}",
+                        paths.display_path(file_path),
+                        highlight.range.start.line + 1,
+                        highlight.range.start.character + 1,
+                        highlight.kind.as_str()
+                    );
+                }
+            }
+            _ => {
+                // Terminal format
+                if highlights.is_empty() {
+                    println!("{}", "No highlights found".yellow());
+                    return Ok(());
+                }
+
+                println!("{}", "Document Highlights:".bold().green());
+                println!();
+
+                let lines: Vec<String> = std::fs::read_to_string(file_path)
+                    .map(|content| content.lines().map(str::to_string).collect())
+                    .unwrap_or_default();
+
+                for (i, highlight) in highlights.iter().enumerate() {
+                    let kind = match highlight.kind {
+                        DocumentHighlightKind::Write => highlight.kind.as_str().red(),
+                        DocumentHighlightKind::Read => highlight.kind.as_str().cyan(),
+                        DocumentHighlightKind::Text => highlight.kind.as_str().dimmed(),
+                    };
+                    println!(
+                        "{} {}:{}:{} {}",
+                        format!("{}.", i + 1).dimmed(),
+                        paths.display_path(file_path).cyan(),
+                        (highlight.range.start.line + 1).to_string().yellow(),
+                        (highlight.range.start.character + 1).to_string().yellow(),
+                        kind
+                    );
+                    if let Some(line) = lines.get(highlight.range.start.line as usize) {
+                        let line = line.trim();
+                        if !line.is_empty() {
+                            println!("   {}", line.dimmed());
+                        }
+                    }
+                }
+
+                let count = |kind: DocumentHighlightKind| {
+                    highlights.iter().filter(|h| h.kind == kind).count()
+                };
+                println!();
+                println!(
+                    "{}",
+                    format!(
+                        "Found {} {} ({} writes, {} reads, {} text)",
+                        highlights.len(),
+                        if highlights.len() == 1 {
+                            "occurrence"
+                        } else {
+                            "occurrences"
+                        },
+                        count(DocumentHighlightKind::Write),
+                        count(DocumentHighlightKind::Read),
+                        count(DocumentHighlightKind::Text)
+                    )
+                    .green()
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Display the edits of a rename per file, marking conflicting edits
+    async fn display_rename(
+        files: &[lsp_daemon::protocol::FileEdits],
+        conflicts: &[crate::lsp_integration::rename::EditConflict],
+        warnings: &[String],
+        applied: bool,
+        paths: &crate::lsp_integration::path_resolver::PathResolver,
+        format: &str,
+    ) -> Result<()> {
+        use crate::lsp_integration::rename::original_text;
+
+        let edit_count: usize = files.iter().map(|file| file.edits.len()).sum();
+        let is_conflicting = |file: &lsp_daemon::protocol::FileEdits,
+                              edit: &lsp_daemon::protocol::TextEdit| {
+            conflicts.iter().any(|conflict| {
+                conflict.file_path == file.file_path
+                    && (&conflict.first == edit || &conflict.second == edit)
+            })
+        };
+
+        match format {
+            "json" => {
+                let mut files = files.to_vec();
+                paths.apply(files.as_mut_slice());
+                let mut conflicts = conflicts.to_vec();
+                paths.apply(conflicts.as_mut_slice());
+                let output = json!({
+                    "files": files,
+                    "conflicts": conflicts,
+                    "warnings": warnings,
+                    "applied": applied,
+                });
+                println!("{}", serde_json::to_string_pretty(&output)?);
+            }
+            "plain" => {
+                for file in files {
+                    let text = std::fs::read_to_string(&file.file_path).unwrap_or_default();
+                    for edit in &file.edits {
+                        println!(
+                            "{}:{}:{} {} -> {}{}",
+                            paths.display_path(&file.file_path),
+                            edit.range.start.line + 1,
+                            edit.range.start.character + 1,
+                            original_text(&text, edit).unwrap_or(""),
+                            edit.new_text,
+                            if is_conflicting(file, edit) {
+                                " CONFLICT"
+                            } else {
+                                ""
+                            }
+                        );
+                    }
+                }
+                for warning in warnings {
+                    eprintln!("warning: {warning}");
+                }
+            }
+            _ => {
+                // Terminal format
+                for warning in warnings {
+                    println!("{} {}", "Warning:".yellow().bold(), warning);
+                }
+                if files.is_empty() {
+                    println!("{}", "No edits returned for rename".yellow());
+                    return Ok(());
+                }
+
+                let title = if applied {
+                    "Applied Rename:"
+                } else {
+                    "Rename Preview:"
+                };
+                println!("{}", title.bold().green());
+
+                for file in files {
+                    // The edits have not been applied yet when reading for a preview
+                    let text = if applied {
+                        String::new()
+                    } else {
+                        std::fs::read_to_string(&file.file_path).unwrap_or_default()
+                    };
+                    println!();
+                    println!(
+                        "{} {}",
+                        paths.display_path(&file.file_path).cyan(),
+                        format!(
+                            "({} {})",
+                            file.edits.len(),
+                            if file.edits.len() == 1 {
+                                "edit"
+                            } else {
+                                "edits"
+                            }
+                        )
+                        .dimmed()
+                    );
+                    for edit in &file.edits {
+                        let position = format!(
+                            "{}:{}",
+                            edit.range.start.line + 1,
+                            edit.range.start.character + 1
+                        );
+                        let change = match original_text(&text, edit) {
+                            Some(old) if !applied => {
+                                format!("{} -> {}", old.red(), edit.new_text.green())
+                            }
+                            _ => edit.new_text.green().to_string(),
+                        };
+                        if is_conflicting(file, edit) {
+                            println!(
+                                "   {} {} {}",
+                                position.yellow(),
+                                change,
+                                "CONFLICT".red().bold()
+                            );
+                        } else {
+                            println!("   {} {}", position.yellow(), change);
+                        }
+                    }
+                }
+
+                println!();
+                let summary = format!(
+                    "{} {} in {} {}",
+                    edit_count,
+                    if edit_count == 1 { "edit" } else { "edits" },
+                    files.len(),
+                    if files.len() == 1 { "file" } else { "files" }
+                );
+                if applied {
+                    println!("{}", format!("Applied {summary}").green());
+                } else if !conflicts.is_empty() {
+                    println!(
+                        "{}",
+                        format!(
+                            "{summary}, {} conflicting. Resolve the conflicts before applying.",
+                            conflicts.len()
+                        )
+                        .red()
+                    );
+                } else {
+                    println!(
+                        "{} {}",
+                        summary.green(),
+                        "(preview only, run again with --apply to write)".dimmed()
+                    );
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Display hover information
     async fn display_hover_info(
         hover: &Option<lsp_daemon::protocol::HoverContent>,
@@ -5337,19 +6299,18 @@ impl LspManager {
     /// Display workspace symbols
     async fn display_workspace_symbols(
         symbols: &[lsp_daemon::protocol::SymbolInformation],
+        paths: &crate::lsp_integration::path_resolver::PathResolver,
         format: &str,
     ) -> Result<()> {
         match format {
             "json" => {
-                println!("{}", serde_json::to_string_pretty(symbols)?);
+                let mut symbols = symbols.to_vec();
+                paths.apply(symbols.as_mut_slice());
+                println!("{}", serde_json::to_string_pretty(&symbols)?);
             }
             "plain" => {
                 for symbol in symbols {
-                    let file_path = symbol
-                        .location
-                        .uri
-                        .strip_prefix("file://")
-                        .unwrap_or(&symbol.location.uri);
+                    let file_path = paths.display(&symbol.location.uri);
                     println!(
                         "{}:{}:{} {} {}",
                         file_path,
@@ -5378,12 +6339,7 @@ impl LspManager {
                 > = HashMap::new();
 
                 for symbol in symbols {
-                    let file_path = symbol
-                        .location
-                        .uri
-                        .strip_prefix("file://")
-                        .unwrap_or(&symbol.location.uri)
-                        .to_string();
+                    let file_path = paths.display(&symbol.location.uri);
                     symbols_by_file.entry(file_path).or_default().push(symbol);
                 }
 
@@ -5439,11 +6395,14 @@ impl LspManager {
     /// Display call hierarchy information
     async fn display_call_hierarchy(
         hierarchy: &crate::lsp_integration::types::CallHierarchyInfo,
+        paths: &crate::lsp_integration::path_resolver::PathResolver,
         format: &str,
     ) -> Result<()> {
         match format {
             "json" => {
-                println!("{}", serde_json::to_string_pretty(hierarchy)?);
+                let mut hierarchy = hierarchy.clone();
+                paths.apply(&mut hierarchy);
+                println!("{}", serde_json::to_string_pretty(&hierarchy)?);
             }
             "plain" => {
                 // Plain format: just list all the calls
@@ -5473,7 +6432,7 @@ impl LspManager {
                             call.name.cyan(),
                             format!("({}:{})", call.line + 1, call.column + 1).dimmed()
                         );
-                        println!("     {}", call.file_path.dimmed());
+                        println!("     {}", paths.display(&call.file_path).dimmed());
                     }
                     println!();
                 }
@@ -5492,7 +6451,7 @@ impl LspManager {
                             call.name.cyan(),
                             format!("({}:{})", call.line + 1, call.column + 1).dimmed()
                         );
-                        println!("     {}", call.file_path.dimmed());
+                        println!("     {}", paths.display(&call.file_path).dimmed());
                     }
                     println!();
                 }
@@ -5505,6 +6464,310 @@ impl LspManager {
         Ok(())
     }
 
+    /// Display fuzzy-ranked workspace symbols in rank order with their scores
+    async fn display_fuzzy_workspace_symbols(
+        symbols: &[crate::lsp_integration::symbol_resolver::FuzzySymbol],
+        paths: &crate::lsp_integration::path_resolver::PathResolver,
+        format: &str,
+    ) -> Result<()> {
+        match format {
+            "json" => {
+                let mut symbols = symbols.to_vec();
+                paths.apply(symbols.as_mut_slice());
+                println!("{}", serde_json::to_string_pretty(&symbols)?);
+            }
+            "plain" => {
+                for ranked in symbols {
+                    let symbol = &ranked.symbol;
+                    println!(
+                        "{}:{}:{} {} {} {:.2}",
+                        paths.display(&symbol.location.uri),
+                        symbol.location.range.start.line + 1,
+                        symbol.location.range.start.character + 1,
+                        Self::format_symbol_kind(&symbol.kind),
+                        symbol.name,
+                        ranked.fuzzy_score
+                    );
+                }
+            }
+            _ => {
+                // Terminal format
+                if symbols.is_empty() {
+                    println!("{}", "No workspace symbols found".yellow());
+                    return Ok(());
+                }
+
+                println!("{}", "Workspace Symbols (fuzzy ranked):".bold().green());
+                println!();
+
+                for (i, ranked) in symbols.iter().enumerate() {
+                    let symbol = &ranked.symbol;
+                    println!(
+                        "{} {} {} {}",
+                        format!("{}.", i + 1).dimmed(),
+                        Self::format_symbol_kind(&symbol.kind).bold(),
+                        symbol.name.cyan(),
+                        format!("(score {:.2})", ranked.fuzzy_score).yellow()
+                    );
+                    println!(
+                        "   {}",
+                        format!(
+                            "{}:{}:{}",
+                            paths.display(&symbol.location.uri),
+                            symbol.location.range.start.line + 1,
+                            symbol.location.range.start.character + 1
+                        )
+                        .dimmed()
+                    );
+                }
+
+                let count = symbols.len();
+                println!();
+                println!(
+                    "{} {}",
+                    format!(
+                        "Found {} {}",
+                        count,
+                        if count == 1 { "symbol" } else { "symbols" }
+                    )
+                    .green(),
+                    "in workspace".dimmed()
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Display a call hierarchy tree with callers and callees nested by depth
+    async fn display_call_hierarchy_tree(
+        root: &lsp_daemon::protocol::CallHierarchyNode,
+        warnings: &[String],
+        paths: &crate::lsp_integration::path_resolver::PathResolver,
+        format: &str,
+    ) -> Result<()> {
+        use lsp_daemon::protocol::{CallHierarchyItem, CallHierarchyNode};
+
+        // Nothing below reads the files, so the paths can be rewritten up front
+        let mut root = root.clone();
+        paths.apply(&mut root);
+        let root = &root;
+
+        fn location(item: &CallHierarchyItem) -> String {
+            format!(
+                "{}:{}:{}",
+                item.uri.strip_prefix("file://").unwrap_or(&item.uri),
+                item.selection_range.start.line + 1,
+                item.selection_range.start.character + 1
+            )
+        }
+
+        fn children(node: &CallHierarchyNode, incoming: bool) -> &[CallHierarchyNode] {
+            if incoming {
+                &node.incoming
+            } else {
+                &node.outgoing
+            }
+        }
+
+        fn print_plain(node: &CallHierarchyNode, incoming: bool, depth: usize) {
+            println!(
+                "{}: {}{} {}{}",
+                if incoming { "INCOMING" } else { "OUTGOING" },
+                "  ".repeat(depth - 1),
+                node.item.name,
+                location(&node.item),
+                if node.cycle { " (cycle)" } else { "" }
+            );
+            for child in children(node, incoming) {
+                print_plain(child, incoming, depth + 1);
+            }
+        }
+
+        fn print_tree(node: &CallHierarchyNode, incoming: bool, prefix: &str, last: bool) {
+            let branch = if last { "└── " } else { "├── " };
+            let cycle = if node.cycle {
+                format!(" {}", "↻ cycle".magenta())
+            } else {
+                String::new()
+            };
+            println!(
+                "{}{}{} {}{}",
+                prefix.dimmed(),
+                branch.dimmed(),
+                node.item.name.cyan(),
+                format!("({})", location(&node.item)).dimmed(),
+                cycle
+            );
+            let prefix = format!("{prefix}{}", if last { "    " } else { "│   " });
+            let nodes = children(node, incoming);
+            for (i, child) in nodes.iter().enumerate() {
+                print_tree(child, incoming, &prefix, i + 1 == nodes.len());
+            }
+        }
+
+        match format {
+            "json" => {
+                println!("{}", serde_json::to_string_pretty(root)?);
+                for warning in warnings {
+                    eprintln!("warning: {warning}");
+                }
+            }
+            "plain" => {
+                for incoming in [true, false] {
+                    for child in children(root, incoming) {
+                        print_plain(child, incoming, 1);
+                    }
+                }
+                for warning in warnings {
+                    eprintln!("warning: {warning}");
+                }
+            }
+            _ => {
+                // Terminal format
+                println!(
+                    "{} {} {}",
+                    "Call Hierarchy:".bold().green(),
+                    root.item.name.cyan().bold(),
+                    format!("({})", location(&root.item)).dimmed()
+                );
+                println!();
+
+                for (incoming, title) in [(true, "Incoming Calls:"), (false, "Outgoing Calls:")] {
+                    let nodes = children(root, incoming);
+                    if nodes.is_empty() {
+                        continue;
+                    }
+                    let title = if incoming {
+                        title.bold().blue()
+                    } else {
+                        title.bold().yellow()
+                    };
+                    println!("{title}");
+                    for (i, node) in nodes.iter().enumerate() {
+                        print_tree(node, incoming, "", i + 1 == nodes.len());
+                    }
+                    println!();
+                }
+
+                if root.incoming.is_empty() && root.outgoing.is_empty() {
+                    println!("{}", "No call hierarchy information found".yellow());
+                }
+                for warning in warnings {
+                    println!("{} {}", "Warning:".yellow().bold(), warning);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Display the related symbols of a `lsp call related` request, one section per kind
+    async fn display_related(
+        symbol: Option<&lsp_daemon::protocol::CallHierarchyItem>,
+        groups: &[lsp_daemon::protocol::RelatedGroup],
+        warnings: &[String],
+        paths: &crate::lsp_integration::path_resolver::PathResolver,
+        format: &str,
+    ) -> Result<()> {
+        use lsp_daemon::protocol::{RelatedItem, RelatedSource};
+
+        // Nothing below reads the files, so the paths can be rewritten up front
+        let mut symbol = symbol.cloned();
+        if let Some(item) = symbol.as_mut() {
+            paths.apply(item);
+        }
+        let mut groups = groups.to_vec();
+        paths.apply(groups.as_mut_slice());
+
+        fn location(item: &RelatedItem) -> String {
+            let uri = &item.location.uri;
+            format!(
+                "{}:{}:{}",
+                uri.strip_prefix("file://").unwrap_or(uri),
+                item.location.range.start.line + 1,
+                item.location.range.start.character + 1
+            )
+        }
+
+        match format {
+            "json" => {
+                let output = serde_json::json!({
+                    "symbol": symbol,
+                    "groups": groups,
+                });
+                println!("{}", serde_json::to_string_pretty(&output)?);
+                for warning in warnings {
+                    eprintln!("warning: {warning}");
+                }
+            }
+            "plain" => {
+                for group in &groups {
+                    let label = group.kind.title().to_uppercase();
+                    if let Some(error) = &group.error {
+                        eprintln!("{label}: error: {error}");
+                    }
+                    for item in &group.items {
+                        match &item.name {
+                            Some(name) => println!("{label}: {} {name}", location(item)),
+                            None => println!("{label}: {}", location(item)),
+                        }
+                    }
+                }
+                for warning in warnings {
+                    eprintln!("warning: {warning}");
+                }
+            }
+            _ => {
+                // Terminal format
+                match &symbol {
+                    Some(item) => println!(
+                        "{} {}",
+                        "Related Symbols:".bold().green(),
+                        item.name.cyan().bold()
+                    ),
+                    None => println!("{}", "Related Symbols:".bold().green()),
+                }
+                println!();
+
+                for group in &groups {
+                    let source = match group.source {
+                        RelatedSource::Cache => "[cache]",
+                        RelatedSource::Live => "[live]",
+                    };
+                    println!(
+                        "{} ({}) {}",
+                        format!("{}:", group.kind.title()).bold().blue(),
+                        group.items.len(),
+                        source.dimmed()
+                    );
+                    if let Some(error) = &group.error {
+                        println!("  {} {}", "Error:".red().bold(), error);
+                    }
+                    for (i, item) in group.items.iter().enumerate() {
+                        match &item.name {
+                            Some(name) => println!(
+                                "  {}. {} {}",
+                                (i + 1).to_string().dimmed(),
+                                name.cyan(),
+                                format!("({})", location(item)).dimmed()
+                            ),
+                            None => println!(
+                                "  {}. {}",
+                                (i + 1).to_string().dimmed(),
+                                location(item).cyan()
+                            ),
+                        }
+                    }
+                    println!();
+                }
+
+                for warning in warnings {
+                    println!("{} {}", "Warning:".yellow().bold(), warning);
+                }
+            }
+        }
+        Ok(())
+    }
+
     /// Format symbol kind as a readable string with icon
     fn format_symbol_kind(kind: &lsp_daemon::protocol::SymbolKind) -> String {
         use lsp_daemon::protocol::SymbolKind;
@@ -5539,6 +6802,96 @@ impl LspManager {
         }
     }
 
+    /// Export the symbol graph reachable from `root`, or what changed since the analysis run
+    /// `since_run`, through the daemon, in the format matching the output file's extension
+    #[allow(clippy::too_many_arguments)]
+    async fn handle_graph_export(
+        workspace: Option<std::path::PathBuf>,
+        output: std::path::PathBuf,
+        root: Option<&str>,
+        direction: &str,
+        depth: u32,
+        since_run: Option<i64>,
+        include_external: bool,
+        daemon: bool,
+        yes: bool,
+    ) -> Result<()> {
+        if output.exists() && !yes {
+            return Err(anyhow!(
+                "Output file '{}' exists. Pass --yes to overwrite it.",
+                output.display()
+            ));
+        }
+
+        let format = match output.extension().and_then(|ext| ext.to_str()) {
+            Some("dot" | "gv") => "dot",
+            Some("graphml") => "graphml",
+            Some("mmd" | "mermaid") => "mermaid",
+            _ => "json",
+        };
+
+        // The daemon may run elsewhere, so send absolute paths
+        let cwd = std::env::current_dir()?;
+        let ws_root = workspace.unwrap_or_else(|| cwd.clone());
+        let output = if output.is_absolute() {
+            output
+        } else {
+            cwd.join(output)
+        };
+        let root = match root {
+            Some(root) => Some(match root.rsplit_once('#') {
+                Some((file, name)) if Path::new(file).exists() => {
+                    let file = std::fs::canonicalize(file)?;
+                    format!("{}#{}", file.display(), name)
+                }
+                _ => root.to_string(),
+            }),
+            None => None,
+        };
+
+        let mut client = LspClient::new(LspConfig {
+            use_daemon: true,
+            auto_start: daemon,
+            timeout_ms: 300_000,
+            ..Default::default()
+        })
+        .await?;
+        match client
+            .export_graph(
+                Some(ws_root),
+                output,
+                format.to_string(),
+                root,
+                direction.to_string(),
+                depth,
+                since_run,
+                include_external,
+            )
+            .await?
+        {
+            lsp_daemon::protocol::DaemonResponse::GraphExported {
+                output_path,
+                root_symbol,
+                nodes_count,
+                edges_count,
+                ..
+            } => {
+                let title = match since_run {
+                    Some(run_id) => format!("Exported changes since analysis run {}", run_id),
+                    None => format!("Exported {} graph of {}", direction, root_symbol),
+                };
+                println!("{}", title.green().bold());
+                println!("Output file: {}", output_path.to_string_lossy());
+                println!("Nodes: {}, edges: {}", nodes_count, edges_count);
+                Ok(())
+            }
+            lsp_daemon::protocol::DaemonResponse::Error { error, .. } => {
+                Err(anyhow!("Graph export failed: {}", error))
+            }
+            _ => Err(anyhow!("Unexpected response from daemon")),
+        }
+    }
+
     /// Handle graph export command
     async fn handle_index_export(
         workspace: Option<std::path::PathBuf>,
@@ -1,8 +1,11 @@
 pub mod call_graph_cache;
 pub mod client;
 pub mod management;
+pub mod path_resolver;
 pub mod position_analyzer;
+pub mod preview;
 pub mod readiness;
+pub mod rename;
 pub mod stdlib_filter;
 pub mod symbol_resolver;
 pub mod types;
@@ -161,10 +164,36 @@ pub enum LspSubcommands {
 
     /// Call LSP methods directly
     Call {
+        /// Print file paths inside the workspace relative to its root
+        #[clap(long = "workspace-relative", global = true)]
+        workspace_relative: bool,
+
+        /// Seconds to wait for the language server before giving up (default 30s).
+        /// Call hierarchy requests keep their longer minimum; a server that keeps
+        /// missing deadlines is restarted.
+        #[clap(long = "timeout", global = true)]
+        timeout_secs: Option<u64>,
+
         #[clap(subcommand)]
         command: LspCallCommands,
     },
 
+    /// Pre-compute and cache call hierarchies for a list of hot symbols
+    CallHierarchyWarm {
+        /// File with one 'file.rs#symbol_name' target per line (blank lines and lines
+        /// starting with '#' are skipped)
+        #[clap(long = "file", value_name = "LIST")]
+        file: std::path::PathBuf,
+
+        /// Targets computed at a time (default: number of running language servers)
+        #[clap(long = "concurrency", value_parser = clap::value_parser!(u64).range(1..))]
+        concurrency: Option<u64>,
+
+        /// Output format (terminal, json)
+        #[clap(short = 'o', long = "format", default_value = "terminal", value_parser = ["terminal", "json"])]
+        format: String,
+    },
+
     /// Cache management subcommands
     Cache {
         #[clap(subcommand)]
@@ -197,6 +226,10 @@ pub enum LspSubcommands {
         #[clap(long = "memory-budget")]
         memory_budget: Option<u64>,
 
+        /// Stop indexing after this many seconds; remaining files are indexed on the next run
+        #[clap(long = "max-duration")]
+        max_duration: Option<u64>,
+
         /// Output format (terminal, json)
         #[clap(short = 'o', long = "format", default_value = "terminal", value_parser = ["terminal", "json"])]
         format: String,
@@ -240,6 +273,16 @@ pub enum LspSubcommands {
         format: String,
     },
 
+    /// Index a file ahead of the rest of the running indexing session
+    IndexPrioritize {
+        /// File to index next
+        file: std::path::PathBuf,
+
+        /// Output format (terminal, json)
+        #[clap(short = 'o', long = "format", default_value = "terminal", value_parser = ["terminal", "json"])]
+        format: String,
+    },
+
     /// Configure indexing settings
     IndexConfig {
         #[clap(subcommand)]
@@ -275,6 +318,31 @@ pub enum LspSubcommands {
         /// Run export fully online via daemon without shutdown (default). Use --offline to shutdown/checkpoint/copy
         #[clap(long = "offline", action = clap::ArgAction::SetTrue)]
         offline: bool,
+
+        /// Export the symbol graph reachable from this symbol instead of the database.
+        /// Takes a symbol UID or file#name (e.g. src/foo.rs#handler); the format follows the
+        /// output extension (.dot, .graphml, .mmd, otherwise JSON)
+        #[clap(long = "root")]
+        root: Option<String>,
+
+        /// Direction to follow from --root
+        #[clap(long = "direction", default_value = "both", value_parser = ["callers", "callees", "both"], requires = "root")]
+        direction: String,
+
+        /// Maximum traversal depth from --root
+        #[clap(long = "depth", default_value = "3", value_parser = clap::value_parser!(u32).range(1..), requires = "root")]
+        depth: u32,
+
+        /// Export only the symbols and edges that changed since this completed analysis run,
+        /// with tombstones for deleted ones. Each indexing run records one; `index-status`
+        /// shows its ID
+        #[clap(long = "since-run", conflicts_with = "root")]
+        since_run: Option<i64>,
+
+        /// With --root or --since-run, add a node marked `external` for each edge target
+        /// outside the exported symbols, such as library or unresolved calls
+        #[clap(long = "include-external")]
+        include_external: bool,
     },
 
     /// Audit edges in the workspace database and print a compact report
@@ -292,6 +360,55 @@ pub enum LspSubcommands {
         format: String,
     },
 
+    /// List indexed symbols filtered by kind, visibility, language and file path
+    Symbols {
+        /// Workspace path to query (defaults to current directory)
+        #[clap(short = 'w', long = "workspace")]
+        workspace: Option<std::path::PathBuf>,
+
+        /// Symbol kind (function, method, struct, trait, interface, ...)
+        #[clap(long = "kind")]
+        kind: Option<String>,
+
+        /// Visibility (public, private, protected, internal, package, export)
+        #[clap(long = "visibility")]
+        visibility: Option<String>,
+
+        /// Language (rust, typescript, python, ...)
+        #[clap(long = "language")]
+        language: Option<String>,
+
+        /// Glob over workspace-relative file paths (e.g. "src/**/*.rs")
+        #[clap(long = "glob")]
+        glob: Option<String>,
+
+        /// Maximum number of symbols to list
+        #[clap(long = "limit")]
+        limit: Option<usize>,
+
+        /// Output format (terminal, json)
+        #[clap(short = 'o', long = "format", default_value = "terminal", value_parser = ["terminal", "json"])]
+        format: String,
+    },
+
+    /// Search indexed symbol names, FQNs and documentation, best match first
+    SearchSymbols {
+        /// Words to search for
+        query: String,
+
+        /// Workspace path to search (defaults to current directory)
+        #[clap(short = 'w', long = "workspace")]
+        workspace: Option<std::path::PathBuf>,
+
+        /// Maximum number of symbols to list
+        #[clap(long = "limit", default_value = "20")]
+        limit: usize,
+
+        /// Output format (terminal, json)
+        #[clap(short = 'o', long = "format", default_value = "terminal", value_parser = ["terminal", "json"])]
+        format: String,
+    },
+
     /// Force a WAL checkpoint and wait until it can acquire the lock
     WalSync {
         /// Maximum seconds to wait (0 = wait indefinitely)
@@ -331,6 +448,28 @@ pub enum LspSubcommands {
         #[clap(short = 'o', long = "format", default_value = "terminal", value_parser = ["terminal", "json"])]
         format: String,
     },
+
+    /// List running language-server processes (id, pid, uptime, memory, restarts, workspaces)
+    Servers {
+        /// Output format (terminal, json)
+        #[clap(short = 'o', long = "format", default_value = "terminal", value_parser = ["terminal", "json"])]
+        format: String,
+    },
+
+    /// Print daemon metrics in the Prometheus text format
+    Metrics {
+        /// Serve the metrics at http://ADDR/metrics instead of printing them once
+        /// (e.g. 127.0.0.1:9464)
+        #[clap(long = "listen", value_name = "ADDR")]
+        listen: Option<String>,
+    },
+
+    /// Kill a single language-server process without restarting the daemon
+    KillServer {
+        /// Server id as shown by `probe lsp servers` (e.g. "rust")
+        #[clap(value_parser)]
+        id: String,
+    },
 }
 
 #[derive(Subcommand, Debug, Clone)]
@@ -413,6 +552,14 @@ pub enum IndexConfigSubcommands {
         #[clap(long = "lsp-disabled-operations")]
         lsp_disabled_operations: Option<String>,
 
+        /// Language to toggle with --enabled (e.g. cpp, rust, python)
+        #[clap(long = "language", requires = "enabled")]
+        language: Option<String>,
+
+        /// Enable or disable indexing for --language; already indexed symbols stay queryable
+        #[clap(long = "enabled", requires = "language")]
+        enabled: Option<bool>,
+
         /// Output format (terminal, json)
         #[clap(short = 'o', long = "format", default_value = "terminal", value_parser = ["terminal", "json"])]
         format: String,
@@ -603,13 +750,28 @@ pub enum CacheSubcommands {
         format: String,
     },
 
+    /// Remove cache entries for files that were deleted or changed since they were indexed
+    Prune {
+        /// Workspace path to prune (defaults to current directory)
+        #[clap(short = 'w', long = "workspace")]
+        workspace: Option<std::path::PathBuf>,
+
+        /// Report what would be removed without deleting anything
+        #[clap(long = "dry-run")]
+        dry_run: bool,
+
+        /// Output format (terminal, json)
+        #[clap(short = 'o', long = "format", default_value = "terminal", value_parser = ["terminal", "json"])]
+        format: String,
+    },
+
     /// List all cache keys with pagination and filtering support
     ListKeys {
         /// Workspace path to list keys for (optional, lists all if not specified)
         #[clap(short = 'w', long = "workspace")]
         workspace: Option<std::path::PathBuf>,
 
-        /// Filter by LSP operation type (hover, references, definition, etc.)
+        /// Filter by the kind of the cached symbol (function, struct, method, etc.)
         #[clap(long)]
         operation: Option<String>,
 
@@ -806,6 +968,10 @@ pub enum LspCallCommands {
         #[clap(long = "include-declaration")]
         include_declaration: bool,
 
+        /// Show a one-line preview of the source at each reference (terminal and json formats)
+        #[clap(long = "preview")]
+        preview: bool,
+
         /// Output format (terminal, json, plain)
         #[clap(short = 'o', long = "format", default_value = "terminal", value_parser = ["terminal", "json", "plain"])]
         format: String,
@@ -821,6 +987,33 @@ pub enum LspCallCommands {
         format: String,
     },
 
+    /// Find the read and write occurrences of a symbol within its file
+    DocumentHighlight {
+        /// Location in format 'file.rs:42:10' (line:column) or 'file.rs#symbol_name'
+        location: String,
+
+        /// Output format (terminal, json, plain)
+        #[clap(short = 'o', long = "format", default_value = "terminal", value_parser = ["terminal", "json", "plain"])]
+        format: String,
+    },
+
+    /// Rename a symbol across the workspace (previews the edits unless --apply is given)
+    Rename {
+        /// Location in format 'file.rs:42:10' (line:column) or 'file.rs#symbol_name'
+        location: String,
+
+        /// New name for the symbol
+        new_name: String,
+
+        /// Write the edits to disk instead of only previewing them
+        #[clap(long = "apply")]
+        apply: bool,
+
+        /// Output format (terminal, json, plain)
+        #[clap(short = 'o', long = "format", default_value = "terminal", value_parser = ["terminal", "json", "plain"])]
+        format: String,
+    },
+
     /// List all symbols in a document
     DocumentSymbols {
         /// File path to get symbols from
@@ -840,6 +1033,10 @@ pub enum LspCallCommands {
         #[clap(long = "max-results")]
         max_results: Option<usize>,
 
+        /// Rank a broad candidate set client-side by fuzzy score instead of keeping the server's order
+        #[clap(long = "fuzzy")]
+        fuzzy: bool,
+
         /// Output format (terminal, json, plain)
         #[clap(short = 'o', long = "format", default_value = "terminal", value_parser = ["terminal", "json", "plain"])]
         format: String,
@@ -850,6 +1047,10 @@ pub enum LspCallCommands {
         /// Location in format 'file.rs:42:10' (line:column) or 'file.rs#symbol_name'
         location: String,
 
+        /// Follow callers and callees this many levels deep (1 = immediate calls only)
+        #[clap(long = "depth", default_value = "1", value_parser = clap::value_parser!(u32).range(1..))]
+        depth: u32,
+
         /// Output format (terminal, json, plain)
         #[clap(short = 'o', long = "format", default_value = "terminal", value_parser = ["terminal", "json", "plain"])]
         format: String,
@@ -875,6 +1076,16 @@ pub enum LspCallCommands {
         format: String,
     },
 
+    /// Show references, callers, callees, implementations and type definition of a symbol in one request
+    Related {
+        /// Location in format 'file.rs:42:10' (line:column) or 'file.rs#symbol_name'
+        location: String,
+
+        /// Output format (terminal, json, plain)
+        #[clap(short = 'o', long = "format", default_value = "terminal", value_parser = ["terminal", "json", "plain"])]
+        format: String,
+    },
+
     /// Get fully qualified name (FQN) for a symbol
     Fqn {
         /// Location in format 'file.rs:42:10' (line:column) or 'file.rs#symbol_name'
@@ -0,0 +1,235 @@
+//! File paths in the output of `probe lsp call` commands.
+//!
+//! Language servers report absolute paths. With `--workspace-relative`, paths inside the
+//! workspace root are printed relative to it instead, so output is the same on every
+//! machine. Paths outside of the workspace, such as the standard library, stay absolute.
+
+use anyhow::Result;
+use lsp_daemon::protocol::{
+    CallHierarchyItem, CallHierarchyNode, FileEdits, Location, RelatedGroup, RelatedItem,
+    SymbolInformation,
+};
+use std::path::{Path, PathBuf};
+
+use crate::lsp_integration::rename::EditConflict;
+use crate::lsp_integration::symbol_resolver::FuzzySymbol;
+use crate::lsp_integration::types::CallHierarchyInfo;
+
+/// Renders paths and `file://` URIs for display, optionally relative to a workspace root
+#[derive(Debug, Clone, Default)]
+pub struct PathResolver {
+    root: Option<PathBuf>,
+}
+
+impl PathResolver {
+    /// Leave paths as reported by the language server
+    pub fn absolute() -> Self {
+        Self::default()
+    }
+
+    /// Render paths inside `root` relative to it
+    pub fn relative_to(root: &Path) -> Self {
+        Self {
+            root: Some(root.canonicalize().unwrap_or_else(|_| root.to_path_buf())),
+        }
+    }
+
+    /// Resolver for the workspace containing `file`, or [`PathResolver::absolute`]
+    /// unless `workspace_relative` is set
+    pub fn for_file(file: &Path, workspace_relative: bool) -> Result<Self> {
+        if !workspace_relative {
+            return Ok(Self::absolute());
+        }
+        let root = lsp_daemon::find_workspace_root_with_fallback(file)?;
+        Ok(Self::relative_to(&root))
+    }
+
+    /// Resolver for the workspace containing the current directory
+    pub fn for_current_dir(workspace_relative: bool) -> Result<Self> {
+        if !workspace_relative {
+            return Ok(Self::absolute());
+        }
+        let cwd = std::env::current_dir()?;
+        // Workspace detection starts at the parent of the path it is given
+        let root = lsp_daemon::find_workspace_root(&cwd.join("_")).unwrap_or(cwd);
+        Ok(Self::relative_to(&root))
+    }
+
+    /// Path of a `file://` URI or plain path as it should be printed
+    pub fn display(&self, path_or_uri: &str) -> String {
+        let path = path_or_uri.strip_prefix("file://").unwrap_or(path_or_uri);
+        self.relative(path).unwrap_or_else(|| path.to_string())
+    }
+
+    /// Like [`PathResolver::display`], for paths
+    pub fn display_path(&self, path: &Path) -> String {
+        self.display(&path.to_string_lossy())
+    }
+
+    /// Apply `--workspace-relative` to the paths of a result about to be serialized
+    pub fn apply<T: RelativizePaths + ?Sized>(&self, value: &mut T) {
+        if self.root.is_some() {
+            value.relativize_paths(self);
+        }
+    }
+
+    fn relative(&self, path: &str) -> Option<String> {
+        let root = self.root.as_ref()?;
+        let relative = Path::new(path).strip_prefix(root).ok()?;
+        if relative.as_os_str().is_empty() {
+            return Some(".".to_string());
+        }
+        Some(relative.to_string_lossy().into_owned())
+    }
+
+    /// Rewrite a URI in place; URIs outside of the workspace keep their `file://` form
+    fn rewrite_uri(&self, uri: &mut String) {
+        let path = uri.strip_prefix("file://").unwrap_or(uri);
+        if let Some(relative) = self.relative(path) {
+            *uri = relative;
+        }
+    }
+
+    fn rewrite_path(&self, path: &mut PathBuf) {
+        if let Some(relative) = self.relative(&path.to_string_lossy()) {
+            *path = PathBuf::from(relative);
+        }
+    }
+}
+
+/// Results whose file paths can be rewritten relative to the workspace
+pub trait RelativizePaths {
+    fn relativize_paths(&mut self, paths: &PathResolver);
+}
+
+impl<T: RelativizePaths> RelativizePaths for [T] {
+    fn relativize_paths(&mut self, paths: &PathResolver) {
+        for item in self {
+            item.relativize_paths(paths);
+        }
+    }
+}
+
+impl RelativizePaths for Location {
+    fn relativize_paths(&mut self, paths: &PathResolver) {
+        paths.rewrite_uri(&mut self.uri);
+    }
+}
+
+impl RelativizePaths for SymbolInformation {
+    fn relativize_paths(&mut self, paths: &PathResolver) {
+        self.location.relativize_paths(paths);
+    }
+}
+
+impl RelativizePaths for FuzzySymbol {
+    fn relativize_paths(&mut self, paths: &PathResolver) {
+        self.symbol.relativize_paths(paths);
+    }
+}
+
+impl RelativizePaths for CallHierarchyInfo {
+    fn relativize_paths(&mut self, paths: &PathResolver) {
+        for call in self
+            .incoming_calls
+            .iter_mut()
+            .chain(self.outgoing_calls.iter_mut())
+        {
+            call.file_path = paths.display(&call.file_path);
+        }
+    }
+}
+
+impl RelativizePaths for CallHierarchyNode {
+    fn relativize_paths(&mut self, paths: &PathResolver) {
+        paths.rewrite_uri(&mut self.item.uri);
+        self.incoming.relativize_paths(paths);
+        self.outgoing.relativize_paths(paths);
+    }
+}
+
+impl RelativizePaths for CallHierarchyItem {
+    fn relativize_paths(&mut self, paths: &PathResolver) {
+        paths.rewrite_uri(&mut self.uri);
+    }
+}
+
+impl RelativizePaths for RelatedItem {
+    fn relativize_paths(&mut self, paths: &PathResolver) {
+        self.location.relativize_paths(paths);
+    }
+}
+
+impl RelativizePaths for RelatedGroup {
+    fn relativize_paths(&mut self, paths: &PathResolver) {
+        self.items.relativize_paths(paths);
+    }
+}
+
+impl RelativizePaths for FileEdits {
+    fn relativize_paths(&mut self, paths: &PathResolver) {
+        paths.rewrite_path(&mut self.file_path);
+    }
+}
+
+impl RelativizePaths for EditConflict {
+    fn relativize_paths(&mut self, paths: &PathResolver) {
+        paths.rewrite_path(&mut self.file_path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_daemon::protocol::{Position, Range};
+
+    fn location(uri: &str) -> Location {
+        let position = Position {
+            line: 0,
+            character: 0,
+        };
+        Location {
+            uri: uri.to_string(),
+            range: Range {
+                start: position.clone(),
+                end: position,
+            },
+        }
+    }
+
+    #[test]
+    fn test_paths_inside_workspace_become_relative() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        let paths = PathResolver::relative_to(&root);
+
+        let inside = root.join("src").join("lib.rs");
+        let uri = format!("file://{}", inside.display());
+        assert_eq!(
+            paths.display(&uri),
+            Path::new("src").join("lib.rs").display().to_string()
+        );
+        assert_eq!(paths.display_path(&root), ".");
+        assert_eq!(
+            paths.display("file:///usr/lib/rustlib/src/vec.rs"),
+            "/usr/lib/rustlib/src/vec.rs"
+        );
+
+        let mut locations = vec![location(&uri), location("file:///usr/lib/std.rs")];
+        paths.apply(locations.as_mut_slice());
+        assert_eq!(
+            locations[0].uri,
+            Path::new("src").join("lib.rs").display().to_string()
+        );
+        assert_eq!(locations[1].uri, "file:///usr/lib/std.rs");
+    }
+
+    #[test]
+    fn test_absolute_resolver_leaves_results_untouched() {
+        let paths = PathResolver::absolute();
+        let mut locations = vec![location("file:///work/src/lib.rs")];
+        paths.apply(locations.as_mut_slice());
+        assert_eq!(locations[0].uri, "file:///work/src/lib.rs");
+        assert_eq!(paths.display("file:///work/src/lib.rs"), "/work/src/lib.rs");
+    }
+}
@@ -0,0 +1,130 @@
+//! One-line previews of the source at LSP locations.
+//!
+//! Locations only carry a file and a range, so telling forty references apart means opening
+//! each file. A preview is the text of the lines a location covers, read from disk, with runs
+//! of whitespace collapsed to single spaces and cut to a fixed width.
+
+use lsp_daemon::protocol::Location;
+use serde::Serialize;
+use std::collections::HashMap;
+
+/// Longest preview, in characters, before it is truncated
+pub const MAX_PREVIEW_CHARS: usize = 120;
+
+/// A location with the preview of its source, as printed by `--preview` JSON output
+#[derive(Debug, Clone, Serialize)]
+pub struct LocationPreview {
+    #[serde(flatten)]
+    pub location: Location,
+    pub preview: Option<String>,
+}
+
+/// Collapse whitespace to single spaces and cut `text` to `max_chars` characters,
+/// marking a cut with `...`
+pub fn normalize_snippet(text: &str, max_chars: usize) -> String {
+    let normalized = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    if normalized.chars().count() <= max_chars {
+        return normalized;
+    }
+    let kept: String = normalized
+        .chars()
+        .take(max_chars.saturating_sub(3))
+        .collect();
+    format!("{}...", kept.trim_end())
+}
+
+/// The preview of each location, in order. `None` when the file cannot be read, the range
+/// lies outside it, or the lines it covers are blank. Each file is read once.
+pub fn location_previews(locations: &[Location]) -> Vec<Option<String>> {
+    let mut files: HashMap<&str, Option<String>> = HashMap::new();
+    locations
+        .iter()
+        .map(|location| {
+            let path = location
+                .uri
+                .strip_prefix("file://")
+                .unwrap_or(&location.uri);
+            let content = files
+                .entry(path)
+                .or_insert_with(|| std::fs::read_to_string(path).ok());
+            content
+                .as_deref()
+                .and_then(|content| preview_range(content, location))
+        })
+        .collect()
+}
+
+fn preview_range(content: &str, location: &Location) -> Option<String> {
+    let start = location.range.start.line as usize;
+    let end = (location.range.end.line as usize).max(start);
+    let lines: Vec<&str> = content.lines().skip(start).take(end - start + 1).collect();
+    let preview = normalize_snippet(&lines.join(" "), MAX_PREVIEW_CHARS);
+    (!preview.is_empty()).then_some(preview)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_daemon::protocol::{Position, Range};
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn location(path: &std::path::Path, start: u32, end: u32) -> Location {
+        Location {
+            uri: format!("file://{}", path.display()),
+            range: Range {
+                start: Position {
+                    line: start,
+                    character: 4,
+                },
+                end: Position {
+                    line: end,
+                    character: 8,
+                },
+            },
+        }
+    }
+
+    #[test]
+    fn test_normalize_snippet_collapses_whitespace_and_truncates() {
+        assert_eq!(
+            normalize_snippet("  let  total =\t sum(items);  ", 80),
+            "let total = sum(items);"
+        );
+        assert_eq!(
+            normalize_snippet("let total = sum(items);", 12),
+            "let total..."
+        );
+        assert_eq!(normalize_snippet("héllo wörld", 11), "héllo wörld");
+        assert_eq!(normalize_snippet("   ", 80), "");
+    }
+
+    #[test]
+    fn test_location_previews_read_the_covered_lines() {
+        let dir = TempDir::new().unwrap();
+        let file = dir.path().join("lib.rs");
+        fs::write(
+            &file,
+            "fn main() {\n    let total = compute(\n        1,\n    );\n\n}\n",
+        )
+        .unwrap();
+
+        let previews = location_previews(&[
+            location(&file, 1, 1),
+            location(&file, 1, 3),
+            location(&file, 4, 4),
+            location(&file, 40, 40),
+            location(&dir.path().join("missing.rs"), 0, 0),
+        ]);
+        assert_eq!(
+            previews,
+            vec![
+                Some("let total = compute(".to_string()),
+                Some("let total = compute( 1, );".to_string()),
+                None,
+                None,
+                None,
+            ]
+        );
+    }
+}
@@ -0,0 +1,262 @@
+//! Previewing and applying the edits of an LSP rename.
+//!
+//! The daemon returns the language server's `WorkspaceEdit` as text edits grouped per file.
+//! Nothing is written unless the edits are applied explicitly, and they are only applied when
+//! every file exists on disk and no two edits in a file touch the same text.
+
+use anyhow::{anyhow, bail, Context, Result};
+use lsp_daemon::protocol::{FileEdits, Position, TextEdit};
+use serde::Serialize;
+use std::path::PathBuf;
+
+/// Two edits in the same file that cannot both be applied
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct EditConflict {
+    pub file_path: PathBuf,
+    pub first: TextEdit,
+    pub second: TextEdit,
+}
+
+fn position_key(position: &Position) -> (u32, u32) {
+    (position.line, position.character)
+}
+
+/// Sort the edits of every file by position and drop exact duplicates, which servers emit
+/// when the same occurrence is reachable through more than one reference.
+pub fn normalize_edits(mut files: Vec<FileEdits>) -> Vec<FileEdits> {
+    for file in &mut files {
+        file.edits.sort_by_key(|edit| {
+            (
+                position_key(&edit.range.start),
+                position_key(&edit.range.end),
+            )
+        });
+        file.edits.dedup();
+    }
+    files
+}
+
+/// Find edits that overlap, or that replace the same range with different text.
+/// Expects edits sorted by [`normalize_edits`]; edits that merely touch do not conflict.
+pub fn find_conflicts(files: &[FileEdits]) -> Vec<EditConflict> {
+    let mut conflicts = Vec::new();
+    for file in files {
+        for (i, first) in file.edits.iter().enumerate() {
+            for second in &file.edits[i + 1..] {
+                if position_key(&second.range.start) > position_key(&first.range.end)
+                    || (position_key(&second.range.start) == position_key(&first.range.end)
+                        && second.range != first.range)
+                {
+                    break;
+                }
+                conflicts.push(EditConflict {
+                    file_path: file.file_path.clone(),
+                    first: first.clone(),
+                    second: second.clone(),
+                });
+            }
+        }
+    }
+    conflicts
+}
+
+/// Fail unless every edited file exists on disk. Edits for unsaved buffers cannot be
+/// previewed or applied, as the text they were computed against is not available.
+pub fn check_files_on_disk(files: &[FileEdits]) -> Result<()> {
+    let missing: Vec<String> = files
+        .iter()
+        .filter(|file| !file.file_path.is_file())
+        .map(|file| file.file_path.display().to_string())
+        .collect();
+    if !missing.is_empty() {
+        bail!(
+            "Rename edits files that are not on disk (unsaved or deleted): {}. \
+             Save them and run the rename again.",
+            missing.join(", ")
+        );
+    }
+    Ok(())
+}
+
+/// Byte offset of an LSP position, whose character is counted in UTF-16 code units.
+/// A character past the end of the line refers to the end of the line.
+fn position_to_offset(text: &str, position: &Position) -> Option<usize> {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        line_start += text[line_start..].find('\n')? + 1;
+    }
+    let line = &text[line_start..];
+    let line = &line[..line.find('\n').unwrap_or(line.len())];
+    let line = line.strip_suffix('\r').unwrap_or(line);
+
+    let mut units = 0;
+    for (offset, ch) in line.char_indices() {
+        if units >= position.character as usize {
+            return Some(line_start + offset);
+        }
+        units += ch.len_utf16();
+    }
+    Some(line_start + line.len())
+}
+
+/// Text currently covered by `edit`, or `None` if its range is outside of `text`
+pub fn original_text<'a>(text: &'a str, edit: &TextEdit) -> Option<&'a str> {
+    let start = position_to_offset(text, &edit.range.start)?;
+    let end = position_to_offset(text, &edit.range.end)?;
+    text.get(start..end)
+}
+
+/// Apply non-conflicting edits to `text`
+pub fn apply_to_text(text: &str, edits: &[TextEdit]) -> Result<String> {
+    let mut spans = edits
+        .iter()
+        .map(|edit| {
+            let start = position_to_offset(text, &edit.range.start);
+            let end = position_to_offset(text, &edit.range.end);
+            match (start, end) {
+                (Some(start), Some(end)) if start <= end => Ok((start, end, &edit.new_text)),
+                _ => Err(anyhow!(
+                    "Edit range {}:{}-{}:{} is outside of the file",
+                    edit.range.start.line + 1,
+                    edit.range.start.character + 1,
+                    edit.range.end.line + 1,
+                    edit.range.end.character + 1
+                )),
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    // Apply back to front so earlier offsets stay valid
+    spans.sort_by_key(|&(start, end, _)| std::cmp::Reverse((start, end)));
+    let mut result = text.to_string();
+    for (start, end, new_text) in spans {
+        result.replace_range(start..end, new_text);
+    }
+    Ok(result)
+}
+
+/// Write the edits to disk. Every file is checked and edited in memory first, so an error
+/// leaves all files untouched. Returns the number of edits applied.
+pub fn apply_workspace_edits(files: &[FileEdits]) -> Result<usize> {
+    check_files_on_disk(files)?;
+    let conflicts = find_conflicts(files);
+    if !conflicts.is_empty() {
+        bail!(
+            "Refusing to apply rename: found {} conflicting edit(s)",
+            conflicts.len()
+        );
+    }
+
+    let mut updated = Vec::with_capacity(files.len());
+    for file in files {
+        let text = std::fs::read_to_string(&file.file_path)
+            .with_context(|| format!("Failed to read {}", file.file_path.display()))?;
+        let new_text = apply_to_text(&text, &file.edits)
+            .with_context(|| format!("Failed to edit {}", file.file_path.display()))?;
+        updated.push((&file.file_path, new_text));
+    }
+
+    for (file_path, new_text) in updated {
+        std::fs::write(file_path, new_text)
+            .with_context(|| format!("Failed to write {}", file_path.display()))?;
+    }
+    Ok(files.iter().map(|file| file.edits.len()).sum())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_daemon::protocol::Range;
+
+    fn edit(start: (u32, u32), end: (u32, u32), new_text: &str) -> TextEdit {
+        TextEdit {
+            range: Range {
+                start: Position {
+                    line: start.0,
+                    character: start.1,
+                },
+                end: Position {
+                    line: end.0,
+                    character: end.1,
+                },
+            },
+            new_text: new_text.to_string(),
+        }
+    }
+
+    fn file(edits: Vec<TextEdit>) -> Vec<FileEdits> {
+        normalize_edits(vec![FileEdits {
+            file_path: PathBuf::from("/tmp/lib.rs"),
+            edits,
+        }])
+    }
+
+    #[test]
+    fn test_apply_counts_utf16_columns() {
+        // '𝒳' is two UTF-16 code units and four bytes
+        let text = "let 𝒳 = old;\nold();\r\nold\n";
+        let edits = vec![
+            edit((0, 9), (0, 12), "new"),
+            edit((1, 0), (1, 3), "new"),
+            edit((2, 0), (2, 3), "new"),
+        ];
+        assert_eq!(original_text(text, &edits[0]), Some("old"));
+        assert_eq!(
+            apply_to_text(text, &edits).unwrap(),
+            "let 𝒳 = new;\nnew();\r\nnew\n"
+        );
+        assert!(apply_to_text(text, &[edit((7, 0), (7, 1), "x")]).is_err());
+    }
+
+    #[test]
+    fn test_duplicate_edits_are_not_conflicts() {
+        let files = file(vec![
+            edit((3, 4), (3, 7), "new"),
+            edit((1, 0), (1, 3), "new"),
+            edit((3, 4), (3, 7), "new"),
+            edit((3, 7), (3, 9), "_x"),
+        ]);
+        assert_eq!(files[0].edits.len(), 3);
+        assert!(find_conflicts(&files).is_empty());
+    }
+
+    #[test]
+    fn test_overlapping_and_diverging_edits_conflict() {
+        let files = file(vec![
+            edit((1, 0), (1, 6), "new"),
+            edit((1, 4), (1, 8), "other"),
+            edit((2, 0), (2, 3), "new"),
+            edit((2, 0), (2, 3), "renamed"),
+        ]);
+        let conflicts = find_conflicts(&files);
+        assert_eq!(conflicts.len(), 2);
+        assert_eq!(conflicts[0].first.new_text, "new");
+        assert_eq!(conflicts[0].second.new_text, "other");
+        assert_eq!(conflicts[1].second.new_text, "renamed");
+    }
+
+    #[test]
+    fn test_missing_files_are_rejected() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let saved = temp_dir.path().join("saved.rs");
+        std::fs::write(&saved, "fn old() {}\n").unwrap();
+        let unsaved = temp_dir.path().join("unsaved.rs");
+
+        let files = vec![
+            FileEdits {
+                file_path: saved.clone(),
+                edits: vec![edit((0, 3), (0, 6), "new")],
+            },
+            FileEdits {
+                file_path: unsaved.clone(),
+                edits: vec![edit((0, 0), (0, 3), "new")],
+            },
+        ];
+        let error = apply_workspace_edits(&files).unwrap_err().to_string();
+        assert!(error.contains("unsaved.rs"));
+        assert_eq!(std::fs::read_to_string(&saved).unwrap(), "fn old() {}\n");
+
+        assert_eq!(apply_workspace_edits(&files[..1]).unwrap(), 1);
+        assert_eq!(std::fs::read_to_string(&saved).unwrap(), "fn new() {}\n");
+    }
+}
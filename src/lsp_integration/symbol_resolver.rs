@@ -6,8 +6,13 @@
 //!
 //! The resolver uses tree-sitter AST parsing to find symbol positions when using the #symbol syntax,
 //! leveraging existing code from the extract module.
+//!
+//! It also ranks workspace symbols client-side with a fuzzy score, for servers whose own
+//! fuzzy matching is weak.
 
 use anyhow::{Context, Result};
+use lsp_daemon::protocol::SymbolInformation;
+use serde::Serialize;
 use std::path::PathBuf;
 
 use crate::extract::symbol_finder::find_symbol_in_file_with_position;
@@ -303,6 +308,136 @@ fn resolve_file_path(file_part: &str) -> Result<PathBuf> {
     }
 }
 
+// Fuzzy score weights: every matched character scores FUZZY_MATCH, with bonuses for matches
+// at word and camelCase boundaries and for runs of consecutive matches
+const FUZZY_MATCH: f32 = 1.0;
+const FUZZY_BOUNDARY_BONUS: f32 = 3.0;
+const FUZZY_CONSECUTIVE_BONUS: f32 = 2.0;
+const FUZZY_PREFIX_BONUS: f32 = 4.0;
+const FUZZY_EXACT_BONUS: f32 = 10.0;
+// Per unmatched character, so shorter names win among otherwise equal matches
+const FUZZY_UNMATCHED_PENALTY: f32 = 0.05;
+
+/// A workspace symbol with the fuzzy score it was ranked by
+#[derive(Debug, Clone, Serialize)]
+pub struct FuzzySymbol {
+    #[serde(flatten)]
+    pub symbol: SymbolInformation,
+    pub fuzzy_score: f32,
+}
+
+/// Whether `chars[i]` starts a word: the first character, one after a separator, or an
+/// uppercase letter after a lowercase one (camelCase)
+fn is_word_boundary(chars: &[char], i: usize) -> bool {
+    if i == 0 {
+        return true;
+    }
+    let (prev, cur) = (chars[i - 1], chars[i]);
+    (!prev.is_alphanumeric() && cur.is_alphanumeric())
+        || (prev.is_lowercase() && cur.is_uppercase())
+}
+
+fn max_score(a: Option<f32>, b: Option<f32>) -> Option<f32> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(a.max(b)),
+        (a, b) => a.or(b),
+    }
+}
+
+/// Fuzzy score of `candidate` for `query`, or `None` unless the query is a case-insensitive
+/// subsequence of the candidate. Among all ways to match the subsequence the best scoring
+/// one is used, so `hsr` prefers the humps of `HttpServerRequest` over other `s` and `r`.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<f32> {
+    let lower = |c: char| c.to_lowercase().next().unwrap_or(c);
+    let query: Vec<char> = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .map(lower)
+        .collect();
+    let chars: Vec<char> = candidate.chars().collect();
+    if query.is_empty() {
+        return Some(0.0);
+    }
+
+    // best[j]: best score with the current query character matched at chars[j]
+    let mut best: Vec<Option<f32>> = vec![None; chars.len()];
+    for (i, &query_char) in query.iter().enumerate() {
+        let mut next = vec![None; chars.len()];
+        // Best score of the previous query character matched before chars[j - 1]
+        let mut best_before = None;
+        for j in 0..chars.len() {
+            if lower(chars[j]) == query_char {
+                let bonus = FUZZY_MATCH
+                    + if is_word_boundary(&chars, j) {
+                        FUZZY_BOUNDARY_BONUS
+                    } else {
+                        0.0
+                    };
+                next[j] = if i == 0 {
+                    Some(bonus)
+                } else {
+                    let consecutive = j
+                        .checked_sub(1)
+                        .and_then(|k| best[k])
+                        .map(|score| score + FUZZY_CONSECUTIVE_BONUS);
+                    max_score(consecutive, best_before).map(|score| score + bonus)
+                };
+            }
+            if j > 0 {
+                best_before = max_score(best_before, best[j - 1]);
+            }
+        }
+        best = next;
+    }
+
+    let mut score = best.into_iter().fold(None, max_score)?;
+    let query: String = query.into_iter().collect();
+    let name = candidate.to_lowercase();
+    if name == query {
+        score += FUZZY_EXACT_BONUS;
+    } else if name.starts_with(&query) {
+        score += FUZZY_PREFIX_BONUS;
+    }
+    score -= chars.len().saturating_sub(query.chars().count()) as f32 * FUZZY_UNMATCHED_PENALTY;
+    Some(score)
+}
+
+/// Rank workspace symbols by [`fuzzy_score`] against `query`, dropping symbols that do not
+/// match and duplicates returned for more than one candidate query, then apply `max_results`
+pub fn rank_symbols_fuzzy(
+    query: &str,
+    symbols: Vec<SymbolInformation>,
+    max_results: Option<usize>,
+) -> Vec<FuzzySymbol> {
+    let mut seen = std::collections::HashSet::new();
+    let mut ranked: Vec<FuzzySymbol> = symbols
+        .into_iter()
+        .filter(|symbol| {
+            let start = &symbol.location.range.start;
+            seen.insert((
+                symbol.name.clone(),
+                symbol.location.uri.clone(),
+                start.line,
+                start.character,
+            ))
+        })
+        .filter_map(|symbol| {
+            let fuzzy_score = fuzzy_score(query, &symbol.name)?;
+            Some(FuzzySymbol {
+                symbol,
+                fuzzy_score,
+            })
+        })
+        .collect();
+
+    // Stable, so ties keep the server's order
+    ranked.sort_by(|a, b| b.fuzzy_score.total_cmp(&a.fuzzy_score));
+    if let Some(max) = max_results {
+        ranked.truncate(max);
+    }
+    ranked
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -442,6 +577,64 @@ impl TestStruct {
         assert!(location.line < 1000); // Arbitrary reasonable upper bound
     }
 
+    #[test]
+    fn test_fuzzy_score_prefers_boundaries_and_exact_matches() {
+        assert_eq!(fuzzy_score("xyz", "HttpServer"), None);
+        assert_eq!(fuzzy_score("sh", "HttpServer"), None);
+
+        // camelCase humps beat scattered matches
+        let humps = fuzzy_score("hsr", "HttpServerRequest").unwrap();
+        let scattered = fuzzy_score("hsr", "hashes_for").unwrap();
+        assert!(humps > scattered, "{humps} <= {scattered}");
+
+        // snake_case boundaries count too, and case does not matter
+        assert!(
+            fuzzy_score("PR", "parse_request").unwrap() > fuzzy_score("pr", "spare_rows").unwrap()
+        );
+
+        let exact = fuzzy_score("parse", "parse").unwrap();
+        let prefix = fuzzy_score("parse", "parse_block").unwrap();
+        let inner = fuzzy_score("parse", "reparse").unwrap();
+        assert!(exact > prefix && prefix > inner);
+    }
+
+    #[test]
+    fn test_rank_symbols_fuzzy_orders_dedupes_and_limits() {
+        use lsp_daemon::protocol::{Location, Position, Range, SymbolKind};
+
+        let symbol = |name: &str, line: u32| SymbolInformation {
+            name: name.to_string(),
+            kind: SymbolKind::Function,
+            deprecated: None,
+            location: Location {
+                uri: "file:///src/lib.rs".to_string(),
+                range: Range {
+                    start: Position { line, character: 0 },
+                    end: Position { line, character: 4 },
+                },
+            },
+            container_name: None,
+            tags: None,
+        };
+        let symbols = vec![
+            symbol("unrelated", 1),
+            symbol("handle_stream_request", 2),
+            symbol("HttpServerRequest", 3),
+            symbol("HttpServerRequest", 3),
+            symbol("hsr", 4),
+        ];
+
+        let ranked = rank_symbols_fuzzy("hsr", symbols.clone(), None);
+        let names: Vec<&str> = ranked.iter().map(|s| s.symbol.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["hsr", "HttpServerRequest", "handle_stream_request"]
+        );
+        assert!(ranked[0].fuzzy_score > ranked[1].fuzzy_score);
+
+        assert_eq!(rank_symbols_fuzzy("hsr", symbols, Some(1)).len(), 1);
+    }
+
     #[test]
     fn test_resolved_location_creation() {
         let path = PathBuf::from("/test/file.rs");
@@ -12,9 +12,16 @@ mod query_validator;
 
 use cli::{Args, Commands};
 use probe_code::{
-    extract::{handle_extract, ExtractOptions},
+    bert_reranker::DEFAULT_RERANK_TOP_K,
+    extract::{handle_extract, ExtractOptions, SymbolKind},
+    language::context_symbols::DEFAULT_CONTEXT_SYMBOLS_DEPTH,
     lsp_integration::management::LspManager,
-    search::{format_and_print_search_results, perform_probe, SearchOptions},
+    models::{SkipReason, SkippedFile},
+    search::{
+        file_list_cache::combine_ignore_patterns, format_and_print_search_results, perform_probe,
+        recency_boost::DEFAULT_RECENCY_HALF_LIFE_DAYS, BinaryDetection, DedupMode, MatchScope,
+        MergeThreshold, NodeKind, RankMode, ResultSort, SearchOptions, TokenizerKind,
+    },
 };
 
 struct SearchParams {
@@ -31,17 +38,45 @@ struct SearchParams {
     max_results: Option<usize>,
     max_bytes: Option<usize>,
     max_tokens: Option<usize>,
+    tokenizer: Option<TokenizerKind>,
     allow_tests: bool,
     no_merge: bool,
-    merge_threshold: Option<usize>,
+    merge_threshold: Option<MergeThreshold>,
+    merge_respect_boundaries: bool,
     dry_run: bool,
     format: String,
     session: Option<String>,
+    session_ttl: Option<u64>,
     timeout: u64,
     question: Option<String>,
     no_gitignore: bool,
     verbose: bool,
     lsp: bool,
+    sort: ResultSort,
+    regex: bool,
+    match_scope: MatchScope,
+    invert: bool,
+    kinds: Option<Vec<SymbolKind>>,
+    recency_boost: bool,
+    recency_half_life_days: f64,
+    context_symbols: bool,
+    context_symbols_depth: usize,
+    explain: bool,
+    use_index: bool,
+    rank_mode: RankMode,
+    within: Option<Vec<NodeKind>>,
+    changed_since: Option<String>,
+    tracked_only: bool,
+    rerank_top_k: usize,
+    binary_detection: BinaryDetection,
+    force_text_extensions: Vec<String>,
+    search_archives: bool,
+    encoding: Option<String>,
+    min_score: Option<f32>,
+    follow_symlinks: bool,
+    ranking_profile: Option<String>,
+    dedup: DedupMode,
+    snippet: bool,
 }
 
 struct BenchmarkParams {
@@ -110,7 +145,7 @@ fn apply_config_defaults(args: &mut Args, config: &probe_code::config::ResolvedC
                     *no_gitignore = config.search.no_gitignore;
                 }
                 if merge_threshold.is_none() {
-                    *merge_threshold = Some(config.search.merge_threshold);
+                    *merge_threshold = Some(MergeThreshold::Lines(config.search.merge_threshold));
                 }
                 if format == "color" {
                     // Only override if using default
@@ -185,53 +220,55 @@ fn handle_search(params: SearchParams) -> Result<()> {
         query_validator::validate_strict_elastic_syntax(&params.pattern)?;
     }
 
+    // Structured and plain formats keep stdout free of anything but the results, so
+    // scripts can consume it directly
+    let structured_output = matches!(params.format.as_str(), "json" | "jsonl" | "xml" | "plain");
+
     // Print version at the start for text-based formats
-    if params.verbose && params.format != "json" && params.format != "xml" {
+    if params.verbose && !structured_output {
         println!("Probe version: {}", probe_code::version::get_version());
     }
 
     let use_frequency = params.frequency_search;
 
-    if params.format != "json" && params.format != "xml" {
+    if !structured_output {
         println!("{} {}", "Pattern:".bold().green(), params.pattern);
     }
-    // Normalize the search root early. Some downstream code paths are stricter about absolute paths.
-    let raw_root = params.paths.first().unwrap();
-
+    // Normalize the search roots early. Some downstream code paths are stricter about absolute paths.
     // Use safe path operations to avoid following symlinks/junctions
     use probe_code::path_safety;
 
-    // On Windows CI, avoid canonicalize() which can trigger stack overflow with junction points
-    #[cfg(target_os = "windows")]
-    let canonical_root = if path_safety::is_ci_environment() {
-        // In CI, just use the path as-is or make it absolute without canonicalize
-        if raw_root.is_absolute() {
-            raw_root.clone()
-        } else {
-            std::env::current_dir()
-                .unwrap_or_else(|_| PathBuf::from("."))
-                .join(raw_root)
-        }
-    } else if path_safety::exists_no_follow(raw_root) {
-        match raw_root.canonicalize() {
-            Ok(p) => p,
-            Err(_) => raw_root.clone(),
+    let canonicalize_root = |raw_root: &PathBuf| -> PathBuf {
+        // On Windows CI, avoid canonicalize() which can trigger stack overflow with junction points
+        #[cfg(target_os = "windows")]
+        if path_safety::is_ci_environment() {
+            // In CI, just use the path as-is or make it absolute without canonicalize
+            return if raw_root.is_absolute() {
+                raw_root.clone()
+            } else {
+                std::env::current_dir()
+                    .unwrap_or_else(|_| PathBuf::from("."))
+                    .join(raw_root)
+            };
         }
-    } else {
-        raw_root.clone()
-    };
 
-    #[cfg(not(target_os = "windows"))]
-    let canonical_root = if path_safety::exists_no_follow(raw_root) {
-        match raw_root.canonicalize() {
-            Ok(p) => p,
-            Err(_) => raw_root.clone(),
+        if path_safety::exists_no_follow(raw_root) {
+            match raw_root.canonicalize() {
+                Ok(p) => p,
+                Err(_) => raw_root.clone(),
+            }
+        } else {
+            raw_root.clone()
         }
-    } else {
-        raw_root.clone()
     };
-    if params.format != "json" && params.format != "xml" {
-        println!("{} {}", "Path:".bold().green(), canonical_root.display());
+    let canonical_roots: Vec<PathBuf> = params.paths.iter().map(canonicalize_root).collect();
+    let (canonical_root, extra_roots) = canonical_roots.split_first().unwrap();
+    if !structured_output {
+        let roots: Vec<String> = canonical_roots
+            .iter()
+            .map(|root| root.display().to_string())
+            .collect();
+        println!("{} {}", "Path:".bold().green(), roots.join(", "));
     }
 
     // Show advanced options if they differ from defaults
@@ -245,6 +282,9 @@ fn handle_search(params: SearchParams) -> Result<()> {
     if params.reranker != "hybrid" {
         advanced_options.push(format!("Reranker: {}", params.reranker));
     }
+    if params.rerank_top_k != DEFAULT_RERANK_TOP_K {
+        advanced_options.push(format!("Rerank top K: {}", params.rerank_top_k));
+    }
     if !use_frequency {
         advanced_options.push("Frequency search disabled".to_string());
     }
@@ -263,12 +303,57 @@ fn handle_search(params: SearchParams) -> Result<()> {
     if let Some(threshold) = params.merge_threshold {
         advanced_options.push(format!("Merge threshold: {threshold}"));
     }
+    if !params.merge_respect_boundaries {
+        advanced_options.push("Merging across symbol boundaries".to_string());
+    }
+    if params.rank_mode == RankMode::File {
+        advanced_options.push("Ranking whole files".to_string());
+    }
+    if let Some(kinds) = &params.within {
+        advanced_options.push(format!("Within: {kinds:?}"));
+    }
+    if let Some(git_ref) = &params.changed_since {
+        advanced_options.push(format!("Changed since: {git_ref}"));
+    }
+    if params.tracked_only {
+        advanced_options.push("Tracked files only".to_string());
+    }
+    if params.binary_detection != BinaryDetection::NullByte {
+        advanced_options.push(format!("Binary detection: {:?}", params.binary_detection));
+    }
+    if params.search_archives {
+        advanced_options.push("Searching archives".to_string());
+    }
+    if let Some(encoding) = &params.encoding {
+        advanced_options.push(format!("Encoding: {encoding}"));
+    }
+    if let Some(min_score) = params.min_score {
+        advanced_options.push(format!("Min score: {min_score}"));
+    }
+    if params.follow_symlinks {
+        advanced_options.push("Following symlinks".to_string());
+    }
+    if let Some(ranking_profile) = &params.ranking_profile {
+        advanced_options.push(format!("Ranking profile: {ranking_profile}"));
+    }
+    if params.dedup != DedupMode::None {
+        advanced_options.push(format!("Dedup: {:?}", params.dedup));
+    }
+    if !params.force_text_extensions.is_empty() {
+        advanced_options.push(format!(
+            "Forced text extensions: {}",
+            params.force_text_extensions.join(", ")
+        ));
+    }
     if params.dry_run {
         advanced_options.push("Dry run (file names and lines only)".to_string());
     }
     if let Some(session) = &params.session {
         advanced_options.push(format!("Session: {session}"));
     }
+    if let Some(ttl) = params.session_ttl {
+        advanced_options.push(format!("Session TTL: {ttl} seconds"));
+    }
 
     // Show timeout if it's not the default value of 30 seconds
     if params.timeout != 30 {
@@ -277,8 +362,7 @@ fn handle_search(params: SearchParams) -> Result<()> {
 
     if params.verbose
         && !advanced_options.is_empty()
-        && params.format != "json"
-        && params.format != "xml"
+        && !structured_output
     {
         println!(
             "{} {}",
@@ -293,8 +377,8 @@ fn handle_search(params: SearchParams) -> Result<()> {
     let query = vec![params.pattern.clone()];
 
     let search_options = SearchOptions {
-        // Pass a normalized path so directory roots are always accepted.
-        path: &canonical_root,
+        // Pass normalized paths so directory roots are always accepted.
+        path: canonical_root,
         queries: &query,
         files_only: params.files_only,
         custom_ignores: &params.ignore,
@@ -306,17 +390,53 @@ fn handle_search(params: SearchParams) -> Result<()> {
         max_results: params.max_results,
         max_bytes: params.max_bytes,
         max_tokens: params.max_tokens,
+        tokenizer: params.tokenizer,
         allow_tests: params.allow_tests,
         no_merge: params.no_merge,
         merge_threshold: params.merge_threshold,
+        merge_respect_boundaries: params.merge_respect_boundaries,
         dry_run: params.dry_run,
         session: params.session.as_deref(),
         timeout: params.timeout,
         question: params.question.as_deref(),
         no_gitignore: params.no_gitignore,
         lsp: params.lsp,
+        sort: params.sort,
+        regex: params.regex,
+        match_scope: params.match_scope,
+        invert: params.invert,
+        kinds: params.kinds.clone(),
+        recency_boost: params.recency_boost,
+        recency_half_life_days: params.recency_half_life_days,
+        context_symbols: params.context_symbols,
+        context_symbols_depth: params.context_symbols_depth,
+        explain: params.explain,
+        use_index: params.use_index,
+        rank_mode: params.rank_mode,
+        within: params.within.clone(),
+        changed_since: params.changed_since.clone(),
+        tracked_only: params.tracked_only,
+        rerank_top_k: params.rerank_top_k,
+        binary_detection: params.binary_detection,
+        force_text_extensions: &params.force_text_extensions,
+        search_archives: params.search_archives,
+        encoding: params.encoding.clone(),
+        min_score: params.min_score,
+        follow_symlinks: params.follow_symlinks,
+        ranking_profile: params.ranking_profile.clone(),
+        dedup: params.dedup,
+        extra_roots,
     };
 
+    // Drop stale sessions before this search loads or writes its own
+    if let Some(ttl) = params.session_ttl {
+        if let Err(e) =
+            probe_code::search::cache::expire_sessions(std::time::Duration::from_secs(ttl))
+        {
+            eprintln!("Error expiring session caches: {e}");
+        }
+    }
+
     let limited_results = perform_probe(&search_options)?;
 
     // Calculate search time
@@ -331,9 +451,14 @@ fn handle_search(params: SearchParams) -> Result<()> {
         probe_code::search::query::create_query_plan(&search_options.queries[0], false).ok()
     };
 
+    // Files left unprocessed when the timeout cut the search short
+    let unprocessed_on_timeout = limited_results
+        .timed_out
+        .then(|| limited_results.files_skipped_early_termination.unwrap_or(0));
+
     if limited_results.results.is_empty() {
-        // For JSON and XML formats, still call format_and_print_search_results
-        if params.format == "json" || params.format == "xml" {
+        // For structured formats, still call format_and_print_search_results
+        if structured_output {
             format_and_print_search_results(
                 &limited_results.results,
                 search_options.dry_run,
@@ -341,6 +466,8 @@ fn handle_search(params: SearchParams) -> Result<()> {
                 query_plan.as_ref(),
                 Some(&limited_results.skipped_files),
                 limited_results.limits_applied.as_ref(),
+                params.snippet,
+                unprocessed_on_timeout,
             );
         } else {
             // Check if results are empty because all were filtered by session cache
@@ -354,6 +481,7 @@ fn handle_search(params: SearchParams) -> Result<()> {
                         .bold(),
                     cached_skipped
                 );
+                print_cached_blocks(&limited_results.cached_blocks);
                 println!();
                 println!(
                     "{}",
@@ -370,6 +498,7 @@ fn handle_search(params: SearchParams) -> Result<()> {
                 println!("  - Remove file type filters to search all files");
                 println!("  - Use exact:false (default) for stemming, or exact:true for precise symbol lookup");
             }
+            print_unsearched_skips(&limited_results.skipped_files);
             if params.verbose {
                 println!();
                 println!("Search completed in {duration:.2?}");
@@ -377,7 +506,7 @@ fn handle_search(params: SearchParams) -> Result<()> {
         }
     } else {
         // For non-JSON/XML formats, print search time (only if verbose)
-        if params.verbose && params.format != "json" && params.format != "xml" {
+        if params.verbose && !structured_output {
             println!("Search completed in {duration:.2?}");
             println!();
         }
@@ -389,12 +518,28 @@ fn handle_search(params: SearchParams) -> Result<()> {
             query_plan.as_ref(),
             Some(&limited_results.skipped_files),
             limited_results.limits_applied.as_ref(),
+            params.snippet,
+            unprocessed_on_timeout,
         );
 
+        // Dry runs also report how big the results would be without limits
+        if let Some(estimate) = limited_results
+            .estimated_full_size
+            .filter(|_| !structured_output)
+        {
+            println!();
+            println!(
+                "{} {} results, {} bytes, {} tokens",
+                "Estimated size without limits:".yellow().bold(),
+                estimate.results,
+                estimate.total_bytes,
+                estimate.total_tokens
+            );
+        }
+
         // Don't print skipped files info for JSON/XML/outline-xml formats (they include it in structured output)
         if !limited_results.skipped_files.is_empty()
-            && params.format != "json"
-            && params.format != "xml"
+            && !structured_output
             && params.format != "outline-xml"
         {
             let use_stderr = false;
@@ -410,6 +555,14 @@ fn handle_search(params: SearchParams) -> Result<()> {
                 };
             }
 
+            // Binary and undecodable files are reported separately below, they were never
+            // searched
+            let limit_skipped: Vec<_> = limited_results
+                .skipped_files
+                .iter()
+                .filter(|skipped| !skipped.reason.is_unsearched())
+                .collect();
+
             if let Some(limits) = &limited_results.limits_applied {
                 output!();
                 output!("{}", "Limits applied:".yellow().bold());
@@ -422,11 +575,18 @@ fn handle_search(params: SearchParams) -> Result<()> {
                 if let Some(max_tokens) = limits.max_tokens {
                     output!("  {} {max_tokens}", "Max tokens:".yellow());
                 }
+                if let Some(min_score) = limits.min_score {
+                    output!(
+                        "  {} {min_score} ({} results below)",
+                        "Min score:".yellow(),
+                        limits.below_min_score
+                    );
+                }
 
                 output!();
 
                 // Calculate total skipped files (results skipped + files not processed)
-                let results_skipped = limited_results.skipped_files.len();
+                let results_skipped = limit_skipped.len();
                 let files_not_processed =
                     limited_results.files_skipped_early_termination.unwrap_or(0);
                 let total_skipped = results_skipped + files_not_processed;
@@ -437,6 +597,25 @@ fn handle_search(params: SearchParams) -> Result<()> {
                     total_skipped
                 );
 
+                // Say which limit excluded the skipped results, so they are not mistaken
+                // for files that were never searched
+                let mut reason_counts: Vec<(SkipReason, usize)> = Vec::new();
+                for skipped in &limit_skipped {
+                    match reason_counts
+                        .iter_mut()
+                        .find(|(reason, _)| *reason == skipped.reason)
+                    {
+                        Some((_, count)) => *count += 1,
+                        None => reason_counts.push((skipped.reason, 1)),
+                    }
+                }
+                for (reason, count) in reason_counts {
+                    output!(
+                        "  {} {count} results",
+                        format!("{}:", reason.description()).yellow()
+                    );
+                }
+
                 // Show list of skipped files with match counts
                 if results_skipped > 0 {
                     output!();
@@ -446,7 +625,7 @@ fn handle_search(params: SearchParams) -> Result<()> {
                     let mut file_matches: HashMap<String, (HashSet<String>, usize)> =
                         HashMap::new();
 
-                    for skipped in &limited_results.skipped_files {
+                    for skipped in limit_skipped.iter().map(|s| &s.result) {
                         // Convert to relative path
                         let relative_path =
                             if let Ok(abs_path) = std::fs::canonicalize(&skipped.file) {
@@ -531,11 +710,13 @@ fn handle_search(params: SearchParams) -> Result<()> {
                     }
                 }
             }
+
+            print_unsearched_skips(&limited_results.skipped_files);
         }
 
         // Display information about cached blocks (when there are still results to show)
         if let Some(cached_skipped) = limited_results.cached_blocks_skipped {
-            if cached_skipped > 0 {
+            if cached_skipped > 0 && !structured_output {
                 println!();
                 println!(
                     "{} {}",
@@ -544,12 +725,23 @@ fn handle_search(params: SearchParams) -> Result<()> {
                         .bold(),
                     cached_skipped
                 );
+                print_cached_blocks(&limited_results.cached_blocks);
             }
         }
     }
 
+    if let Some(unprocessed) = unprocessed_on_timeout.filter(|_| !structured_output) {
+        println!();
+        println!(
+            "{} {unprocessed} files were not processed, results are partial",
+            format!("Search timed out after {}s:", params.timeout)
+                .yellow()
+                .bold()
+        );
+    }
+
     // Add helpful tip at the very bottom of output (only when there are results, not for JSON/XML formats)
-    if !limited_results.results.is_empty() && params.format != "json" && params.format != "xml" {
+    if !limited_results.results.is_empty() && !structured_output {
         println!();
         println!("💡 Tip: Use `probe extract <file>:<line>` to see full function/class context for any result above");
     }
@@ -557,6 +749,35 @@ fn handle_search(params: SearchParams) -> Result<()> {
     Ok(())
 }
 
+/// List the blocks suppressed by session deduplication (populated with `--explain`)
+fn print_cached_blocks(blocks: &[probe_code::models::BlockRef]) {
+    for block in blocks {
+        println!("  {}:{}-{}", block.file, block.lines.0, block.lines.1);
+    }
+}
+
+/// List the files skipped as binary or undecodable, so they are not silently missing from
+/// the results
+fn print_unsearched_skips(skipped_files: &[SkippedFile]) {
+    for (reason, heading) in [
+        (SkipReason::Binary, "Skipped binary files:"),
+        (SkipReason::Decoding, "Skipped undecodable files:"),
+    ] {
+        let files: Vec<_> = skipped_files
+            .iter()
+            .filter(|skipped| skipped.reason == reason)
+            .collect();
+        if files.is_empty() {
+            continue;
+        }
+        println!();
+        println!("{} {}", heading.yellow().bold(), files.len());
+        for skipped in files {
+            println!("  {}", skipped.result.file);
+        }
+    }
+}
+
 fn handle_benchmark(params: BenchmarkParams) -> Result<()> {
     use std::process::Command;
 
@@ -717,7 +938,7 @@ async fn main() -> Result<()> {
                 pattern,
                 paths,
                 files_only: args.files_only,
-                ignore: args.ignore,
+                ignore: combine_ignore_patterns(&config.search.ignore, &[], &args.ignore)?,
                 exclude_filenames: args.exclude_filenames,
                 reranker: args.reranker,
                 frequency_search: args.frequency_search,
@@ -727,12 +948,15 @@ async fn main() -> Result<()> {
                 max_results: args.max_results,
                 max_bytes: args.max_bytes,
                 max_tokens: args.max_tokens,
+                tokenizer: None,
                 allow_tests: args.allow_tests,
                 no_merge: args.no_merge,
                 merge_threshold: args.merge_threshold,
+                merge_respect_boundaries: !args.merge_across_symbols,
                 dry_run: args.dry_run,
                 format: args.format,
                 session: args.session,
+                session_ttl: args.session_ttl,
                 timeout: args.timeout,
                 question: args.question,
                 no_gitignore: args.no_gitignore
@@ -741,6 +965,31 @@ async fn main() -> Result<()> {
                         .unwrap_or(false),
                 verbose: false,
                 lsp: args.lsp,
+                sort: ResultSort::Score,
+                regex: false,
+                match_scope: MatchScope::All,
+                invert: false,
+                kinds: None,
+                recency_boost: false,
+                recency_half_life_days: DEFAULT_RECENCY_HALF_LIFE_DAYS,
+                context_symbols: false,
+                context_symbols_depth: DEFAULT_CONTEXT_SYMBOLS_DEPTH,
+                explain: false,
+                use_index: false,
+                rank_mode: RankMode::Block,
+                within: None,
+                changed_since: None,
+                tracked_only: false,
+                rerank_top_k: DEFAULT_RERANK_TOP_K,
+                binary_detection: BinaryDetection::NullByte,
+                force_text_extensions: Vec::new(),
+                search_archives: false,
+                encoding: None,
+                min_score: None,
+                follow_symlinks: false,
+                ranking_profile: None,
+                dedup: DedupMode::None,
+                snippet: false,
             })?
         }
         Some(Commands::Search {
@@ -748,6 +997,7 @@ async fn main() -> Result<()> {
             paths,
             files_only,
             ignore,
+            ignore_file,
             exclude_filenames,
             reranker,
             frequency_search,
@@ -757,21 +1007,49 @@ async fn main() -> Result<()> {
             max_results,
             max_bytes,
             max_tokens,
+            tokenizer,
             allow_tests,
             no_merge,
             merge_threshold,
+            merge_across_symbols,
             dry_run,
             format,
             session,
+            session_ttl,
             timeout,
             question,
             no_gitignore,
             lsp,
+            sort,
+            regex,
+            match_scope,
+            invert,
+            kinds,
+            recency_boost,
+            recency_half_life_days,
+            context_symbols,
+            context_symbols_depth,
+            explain,
+            use_index,
+            rank_mode,
+            within,
+            since,
+            tracked_only,
+            rerank_top_k,
+            binary_detection,
+            force_text_extension,
+            search_archives,
+            encoding,
+            min_score,
+            follow_symlinks,
+            ranking_profile,
+            dedup,
+            snippet,
         }) => handle_search(SearchParams {
             pattern,
             paths,
             files_only,
-            ignore,
+            ignore: combine_ignore_patterns(&config.search.ignore, &ignore_file, &ignore)?,
             exclude_filenames,
             reranker,
             frequency_search,
@@ -781,12 +1059,15 @@ async fn main() -> Result<()> {
             max_results,
             max_bytes,
             max_tokens,
+            tokenizer: tokenizer.and_then(|t| t.parse().ok()),
             allow_tests,
             no_merge,
             merge_threshold,
+            merge_respect_boundaries: !merge_across_symbols,
             dry_run,
             format,
             session,
+            session_ttl,
             timeout,
             question,
             no_gitignore: no_gitignore
@@ -795,6 +1076,33 @@ async fn main() -> Result<()> {
                     .unwrap_or(false),
             verbose: false,
             lsp,
+            sort: sort.parse().unwrap_or_default(),
+            regex,
+            match_scope: match_scope.parse().unwrap_or_default(),
+            invert,
+            kinds: (!kinds.is_empty())
+                .then(|| kinds.iter().filter_map(|kind| kind.parse().ok()).collect()),
+            recency_boost,
+            recency_half_life_days,
+            context_symbols,
+            context_symbols_depth,
+            explain,
+            use_index,
+            rank_mode: rank_mode.parse().unwrap_or_default(),
+            within: (!within.is_empty())
+                .then(|| within.iter().filter_map(|kind| kind.parse().ok()).collect()),
+            changed_since: since,
+            tracked_only,
+            rerank_top_k,
+            binary_detection: binary_detection.parse().unwrap_or_default(),
+            force_text_extensions: force_text_extension,
+            search_archives,
+            encoding,
+            min_score,
+            follow_symlinks,
+            ranking_profile,
+            dedup: dedup.parse().unwrap_or_default(),
+            snippet,
         })?,
         Some(Commands::Extract {
             files,
@@ -805,6 +1113,7 @@ async fn main() -> Result<()> {
             input_file,
             to_clipboard,
             dry_run,
+            plain_paths,
             diff,
             allow_tests,
             keep_input,
@@ -813,6 +1122,17 @@ async fn main() -> Result<()> {
             no_gitignore,
             lsp,
             include_stdlib: _,
+            strip_comments,
+            redact_strings,
+            signatures_only,
+            only_kinds,
+            public_only,
+            line_numbers,
+            blame,
+            diff_blocks,
+            clipboard_max_bytes,
+            encoding,
+            cache,
         }) => handle_extract(ExtractOptions {
             files,
             custom_ignores: ignore,
@@ -821,7 +1141,9 @@ async fn main() -> Result<()> {
             from_clipboard,
             input_file,
             to_clipboard,
+            clipboard_max_bytes,
             dry_run,
+            plain_paths,
             diff,
             allow_tests,
             keep_input,
@@ -837,6 +1159,21 @@ async fn main() -> Result<()> {
                     .map(|v| v == "1" || v.eq_ignore_ascii_case("true"))
                     .unwrap_or(false),
             lsp,
+            cache,
+            strip_comments,
+            redact_strings,
+            signatures_only,
+            only_kinds: (!only_kinds.is_empty()).then(|| {
+                only_kinds
+                    .iter()
+                    .filter_map(|kind| kind.parse().ok())
+                    .collect()
+            }),
+            public_only,
+            line_numbers,
+            blame,
+            diff_blocks,
+            encoding,
         })?,
         Some(Commands::Symbols {
             files,
@@ -853,8 +1190,20 @@ async fn main() -> Result<()> {
                 text_extensions,
             },
         )?,
+        Some(Commands::Capabilities { json }) => {
+            probe_code::capabilities::handle_capabilities(json)?
+        }
+        Some(Commands::Schema { command }) => {
+            let schema = match command.as_str() {
+                "search" => probe_code::search_json_schema(),
+                "extract" => probe_code::extract_json_schema(),
+                _ => probe_code::query_json_schema(),
+            };
+            println!("{}", serde_json::to_string_pretty(&schema)?);
+        }
         Some(Commands::Query {
             pattern,
+            or_patterns,
             path,
             language,
             ignore,
@@ -863,10 +1212,16 @@ async fn main() -> Result<()> {
             with_context,
             strict,
             text_extensions,
+            parent_kinds,
+            sexp,
+            group_by,
+            count_only,
             format,
             no_gitignore,
+            follow_symlinks,
         }) => probe_code::query::handle_query(
             &pattern,
+            &or_patterns,
             &path,
             language.as_deref().map(|lang| {
                 // Normalize language aliases
@@ -879,6 +1234,7 @@ async fn main() -> Result<()> {
                     "cc" | "cxx" | "hpp" | "hxx" => "cpp",
                     "rb" => "ruby",
                     "cs" => "csharp",
+                    "kt" | "kts" => "kotlin",
                     "sol" => "solidity",
                     "cr" => "crystal",
                     "hs" | "lhs" => "haskell",
@@ -888,12 +1244,53 @@ async fn main() -> Result<()> {
             &ignore,
             allow_tests,
             max_results,
-            &format,
+            if count_only { "count" } else { &format },
             no_gitignore || std::env::var("PROBE_NO_GITIGNORE").unwrap_or_default() == "1",
             with_context,
             strict,
             text_extensions,
+            parent_kinds,
+            sexp,
+            group_by.parse().unwrap_or_default(),
+            follow_symlinks,
         )?,
+        Some(Commands::Grep {
+            pattern,
+            paths,
+            ignore_case,
+            line_number,
+            count,
+            files_with_matches,
+            files_without_match,
+            invert_match,
+            before_context,
+            after_context,
+            context,
+            ignore,
+            no_gitignore,
+            color,
+            max_count,
+            show_symbol,
+            format,
+        }) => grep::handle_grep(grep::GrepParams {
+            pattern,
+            paths,
+            ignore_case,
+            line_number,
+            count,
+            files_with_matches,
+            files_without_match,
+            invert_match,
+            before_context,
+            after_context,
+            context,
+            ignore,
+            no_gitignore,
+            color,
+            max_count,
+            show_symbol,
+            format,
+        })?,
         Some(Commands::Benchmark {
             bench,
             sample_size,
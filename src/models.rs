@@ -2,10 +2,18 @@
 #[derive(Debug)]
 pub struct LimitedSearchResults {
     pub results: Vec<SearchResult>,
-    pub skipped_files: Vec<SearchResult>,
+    // Results that matched but were left out, each with the limit that excluded it
+    pub skipped_files: Vec<SkippedFile>,
     pub limits_applied: Option<SearchLimits>,
     pub cached_blocks_skipped: Option<usize>,
     pub files_skipped_early_termination: Option<usize>,
+    // Size of the results if no limits were applied, estimated on dry runs
+    pub estimated_full_size: Option<SizeEstimate>,
+    // Blocks suppressed because the session already returned them (only with `explain`)
+    pub cached_blocks: Vec<BlockRef>,
+    // The timeout passed before every file was processed; `files_skipped_early_termination`
+    // counts the files left out
+    pub timed_out: bool,
 }
 
 // Structure to track which limits were applied
@@ -14,6 +22,10 @@ pub struct SearchLimits {
     pub max_results: Option<usize>,
     pub max_bytes: Option<usize>,
     pub max_tokens: Option<usize>,
+    /// Minimum final score of returned results
+    pub min_score: Option<f32>,
+    /// Number of ranked results dropped for scoring below `min_score`
+    pub below_min_score: usize,
 
     #[allow(dead_code)]
     pub total_bytes: usize,
@@ -21,6 +33,113 @@ pub struct SearchLimits {
     pub total_tokens: usize,
 }
 
+// Limit that caused a matching result to be left out of the returned results, or why a
+// file was not searched at all: it looks binary (`Binary`) or is malformed in the requested
+// encoding (`Decoding`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, schemars::JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    MaxResults,
+    MaxBytes,
+    MaxTokens,
+    Binary,
+    Decoding,
+}
+
+impl SkipReason {
+    // Identifier used in structured output
+    pub fn as_str(self) -> &'static str {
+        match self {
+            SkipReason::MaxResults => "max_results",
+            SkipReason::MaxBytes => "max_bytes",
+            SkipReason::MaxTokens => "max_tokens",
+            SkipReason::Binary => "binary",
+            SkipReason::Decoding => "decoding",
+        }
+    }
+
+    // Explanation shown in the terminal summary
+    pub fn description(self) -> &'static str {
+        match self {
+            SkipReason::MaxResults => "max results reached",
+            SkipReason::MaxBytes => "max bytes reached",
+            SkipReason::MaxTokens => "max tokens reached",
+            SkipReason::Binary => "binary file",
+            SkipReason::Decoding => "could not be decoded",
+        }
+    }
+
+    // Whether the whole file was left unsearched, rather than a result cut by a limit
+    pub fn is_unsearched(self) -> bool {
+        matches!(self, SkipReason::Binary | SkipReason::Decoding)
+    }
+}
+
+// Structure to hold a result skipped by the limits and the reason it was skipped
+#[derive(Debug, Clone)]
+pub struct SkippedFile {
+    pub result: SearchResult,
+    pub reason: SkipReason,
+}
+
+impl SkippedFile {
+    // Entry for a whole file that was not searched, e.g. because it looks binary or could
+    // not be decoded
+    pub fn unsearched(file: String, reason: SkipReason) -> Self {
+        SkippedFile {
+            result: SearchResult {
+                file,
+                lines: (1, 1),
+                node_type: "file".to_string(),
+                code: String::new(),
+                symbol_signature: None,
+                matched_by_filename: None,
+                rank: None,
+                score: None,
+                tfidf_score: None,
+                bm25_score: None,
+                tfidf_rank: None,
+                bm25_rank: None,
+                new_score: None,
+                hybrid2_rank: None,
+                combined_score_rank: None,
+                file_unique_terms: None,
+                file_total_matches: None,
+                file_match_rank: None,
+                block_unique_terms: None,
+                block_total_matches: None,
+                parent_file_id: None,
+                block_id: None,
+                matched_keywords: None,
+                matched_lines: None,
+                tokenized_content: None,
+                lsp_info: None,
+                parent_context: None,
+                score_breakdown: None,
+                term_matches: None,
+                duplicates: None,
+                root: None,
+            },
+            reason,
+        }
+    }
+}
+
+// File and line range of a code block, e.g. one suppressed by the session cache
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize)]
+pub struct BlockRef {
+    pub file: String,
+    pub lines: (usize, usize),
+}
+
+// Structure to hold the estimated size of a result set
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizeEstimate {
+    pub results: usize,
+    pub total_bytes: usize,
+    pub total_tokens: usize,
+}
+
 #[derive(Debug, PartialEq, Eq, Clone)]
 pub struct ParentContext {
     pub node_type: String,
@@ -85,6 +204,52 @@ pub struct SearchResult {
     pub lsp_info: Option<serde_json::Value>,
     // Parent context chain for enhanced outline display
     pub parent_context: Option<Vec<ParentContext>>,
+    /// How the score was computed (only populated with `--explain`)
+    pub score_breakdown: Option<ScoreBreakdown>,
+    /// Where each query term matched within `code`, in order of position
+    pub term_matches: Option<Vec<TermMatch>>,
+    /// Number of lower-ranked duplicate blocks collapsed into this one (only with `dedup`)
+    pub duplicates: Option<usize>,
+    /// Search root the result was found under (only when searching several roots)
+    pub root: Option<String>,
+}
+
+/// A single occurrence of a query term within a result's code
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, schemars::JsonSchema)]
+pub struct TermMatch {
+    /// The query term that matched
+    pub term: String,
+    /// Byte offset of the match within `code` (always on a character boundary)
+    pub start_byte: usize,
+    /// Byte offset just past the end of the match within `code`
+    pub end_byte: usize,
+    /// Line number of the match in the file
+    pub line: usize,
+    /// 1-based column of the match within its line, counted in characters
+    pub column: usize,
+}
+
+/// Contribution of a single query term to a result's BM25 score
+#[derive(Debug, Clone, PartialEq, serde::Serialize, schemars::JsonSchema)]
+pub struct TermScore {
+    pub term: String,
+    /// Occurrences of the term in the block (including its filename)
+    pub tf: usize,
+    /// Inverse document frequency of the term across the ranked blocks
+    pub idf: f64,
+}
+
+/// Breakdown of a result's relevance score, reported by `--explain`
+#[derive(Debug, Clone, PartialEq, serde::Serialize, schemars::JsonSchema)]
+pub struct ScoreBreakdown {
+    /// Reranker that produced the final score (bm25, hybrid, ms-marco-*)
+    pub reranker: String,
+    /// Raw BM25 score before any boosting
+    pub bm25: f64,
+    /// Amount the reranker added to (or removed from) the BM25 score
+    pub rerank: f64,
+    /// Per-term tf/idf inputs, sorted by term
+    pub terms: Vec<TermScore>,
 }
 
 // Structure to hold node information for merging
@@ -4,15 +4,25 @@ use crate::extract::symbols::{
 };
 use anyhow::Result;
 use ast_grep_core::language::{Language, TSLanguage};
-use ast_grep_core::AstGrep;
+use ast_grep_core::{AstGrep, Pattern};
 use ast_grep_language::SupportLang;
 use colored::*;
 use ignore::WalkBuilder;
+use lazy_static::lazy_static;
+use lru::LruCache;
 use probe_code::file_guard;
+use probe_code::language::overrides::effective_extension;
 use probe_code::path_resolver::resolve_path;
+use probe_code::search::file_list_cache;
+use probe_code::semantic_context::{MatchContext, OwnerContext, ParsedSourceContext};
 use rayon::prelude::*; // Added import
-use std::collections::HashSet;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::num::NonZeroUsize;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
 use std::time::Instant;
 use tree_sitter::Node;
 
@@ -27,25 +37,156 @@ pub struct AstMatch {
     pub column_end: usize,
     pub matched_text: String,
     pub node_type: String,
+    /// Tree-sitter S-expression of the matched node (only populated with `sexp`)
+    pub sexp: Option<String>,
+    /// Nodes bound to each named pattern metavariable, keyed without the `$` prefix. A `$NAME`
+    /// capture holds one span; a `$$$NAME` capture holds its nodes in source order. Matches
+    /// found without ast-grep (plain text, recovered functions) have no captures.
+    pub captures: HashMap<String, Vec<CaptureSpan>>,
+    /// Definition enclosing the match (only populated with `GroupBy::Symbol`)
+    pub definition: Option<EnclosingDefinition>,
+    /// Index of the pattern that produced the match: 0 for `QueryOptions::pattern`, then
+    /// `QueryOptions::or_patterns` in order
+    pub pattern_index: usize,
+}
+
+/// The innermost definition (function, method, class, ...) that contains a match. A match
+/// that is itself a definition is its own enclosing definition.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EnclosingDefinition {
+    /// Qualified name of the definition, when it has one
+    pub name: Option<String>,
+    pub node_type: String,
+    pub line_start: usize,
+    pub line_end: usize,
+}
+
+/// How query matches are grouped in the output
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GroupBy {
+    /// List every match on its own
+    #[default]
+    None,
+    /// One heading per file with its matches nested
+    File,
+    /// One heading per enclosing definition with its matches nested. Matches inside another
+    /// match of the same definition are collapsed into it
+    Symbol,
+}
+
+impl FromStr for GroupBy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(GroupBy::None),
+            "file" => Ok(GroupBy::File),
+            "symbol" => Ok(GroupBy::Symbol),
+            _ => Err(format!(
+                "Unknown grouping: {s}. Expected one of: none, file, symbol"
+            )),
+        }
+    }
+}
+
+/// Matches that share a file and, with `GroupBy::Symbol`, an enclosing definition
+pub struct MatchGroup<'a> {
+    pub file_path: &'a Path,
+    /// Enclosing definition of the matches; `None` when grouping by file or for matches
+    /// outside any definition
+    pub definition: Option<&'a EnclosingDefinition>,
+    /// Positions of the grouped matches in the slice passed to `group_matches`
+    pub indices: Vec<usize>,
+}
+
+/// Match counts of a query, produced by `count_query_matches`
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct QueryCounts {
+    /// Files with at least one match and their match counts, sorted by path
+    pub files: Vec<(PathBuf, usize)>,
+    /// Sum of the per-file counts
+    pub total: usize,
+}
+
+/// Source range and tree-sitter node kind of a node bound to a pattern metavariable
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CaptureSpan {
+    pub kind: String,
+    pub byte_start: usize,
+    pub byte_end: usize,
+    pub line_start: usize,
+    pub line_end: usize,
+    pub column_start: usize,
+    pub column_end: usize,
+}
+
+/// A syntax node with its kind and source range
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AstNodeInfo {
+    pub kind: String,
+    pub byte_start: usize,
+    pub byte_end: usize,
+    pub line_start: usize,
+    pub line_end: usize,
+    pub column_start: usize,
+    pub column_end: usize,
+}
+
+/// The node found at a position, together with its ancestors (innermost first)
+#[derive(Debug, Clone)]
+pub struct PositionMatch {
+    pub file_path: PathBuf,
+    pub node: AstNodeInfo,
+    pub ancestors: Vec<AstNodeInfo>,
 }
 
 /// Options for the ast-grep query
 pub struct QueryOptions<'a> {
     pub path: &'a Path,
     pub pattern: &'a str,
+    /// Further patterns matched in the same pass. A node matching any pattern is a match;
+    /// a byte range matched by several patterns is reported once, for the first of them
+    pub or_patterns: &'a [String],
     pub language: Option<&'a str>,
     pub ignore: &'a [String],
     pub allow_tests: bool,
     pub max_results: Option<usize>,
     pub with_context: bool,
-    #[allow(dead_code)]
     pub format: &'a str,
     pub no_gitignore: bool,
     pub strict: bool,
     pub text_extensions: &'a [String],
+    /// Anchor matches to their parent node: `KIND` keeps matches whose parent node has that
+    /// kind, `NAME=KIND` requires every node of capture `$NAME` to have such a parent. Kinds
+    /// for the same target are alternatives; different targets must all hold.
+    pub parent_kinds: &'a [String],
+    /// Attach the tree-sitter S-expression of each matched node
+    pub sexp: bool,
+    /// How matches are grouped; `GroupBy::Symbol` also attaches enclosing definitions
+    pub group_by: GroupBy,
+    /// Follow symbolic links to files when walking `path`; symlinked directories always are
+    pub follow_symlinks: bool,
+}
+
+impl<'a> QueryOptions<'a> {
+    /// All patterns to match, in `pattern_index` order
+    pub fn patterns(&self) -> impl Iterator<Item = &'a str> {
+        std::iter::once(self.pattern).chain(self.or_patterns.iter().map(String::as_str))
+    }
 }
 
-#[derive(Clone, Copy)]
+const PATTERN_CACHE_SIZE: usize = 128;
+
+lazy_static! {
+    /// Compiled patterns keyed by (language, pattern source); `None` marks an invalid pattern
+    static ref PATTERN_CACHE: Mutex<LruCache<(String, String), Option<Arc<Pattern<ProbeQueryLang>>>>> =
+        Mutex::new(LruCache::new(NonZeroUsize::new(PATTERN_CACHE_SIZE).unwrap()));
+}
+
+/// Number of times a pattern was actually compiled, used for testing
+static PATTERN_COMPILE_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Clone, Copy, Debug)]
 enum ProbeQueryLang {
     Builtin(SupportLang),
     Solidity,
@@ -53,6 +194,26 @@ enum ProbeQueryLang {
 }
 
 impl Language for ProbeQueryLang {
+    // `$` cannot appear in C# or Kotlin identifiers, so their patterns spell metavariables
+    // with the expando character ast-grep parses them with
+    fn pre_process_pattern<'q>(&self, query: &'q str) -> Cow<'q, str> {
+        match self {
+            ProbeQueryLang::Builtin(lang @ (SupportLang::CSharp | SupportLang::Kotlin)) => {
+                lang.pre_process_pattern(query)
+            }
+            _ => Cow::Borrowed(query),
+        }
+    }
+
+    fn expando_char(&self) -> char {
+        match self {
+            ProbeQueryLang::Builtin(lang @ (SupportLang::CSharp | SupportLang::Kotlin)) => {
+                lang.expando_char()
+            }
+            _ => self.meta_var_char(),
+        }
+    }
+
     fn get_ts_language(&self) -> TSLanguage {
         match self {
             ProbeQueryLang::Builtin(lang) => lang.get_ts_language(),
@@ -73,17 +234,48 @@ fn get_language(lang: &str) -> Option<ProbeQueryLang> {
         "c" => Some(ProbeQueryLang::Builtin(SupportLang::C)),
         "cpp" => Some(ProbeQueryLang::Builtin(SupportLang::Cpp)),
         "java" => Some(ProbeQueryLang::Builtin(SupportLang::Java)),
+        "kotlin" | "kt" | "kts" => Some(ProbeQueryLang::Builtin(SupportLang::Kotlin)),
         "ruby" => Some(ProbeQueryLang::Builtin(SupportLang::Ruby)),
         "php" => Some(ProbeQueryLang::Builtin(SupportLang::Php)),
         "swift" => Some(ProbeQueryLang::Builtin(SupportLang::Swift)),
         "haskell" | "hs" | "lhs" => Some(ProbeQueryLang::Builtin(SupportLang::Haskell)),
         "solidity" | "sol" => Some(ProbeQueryLang::Solidity),
         "crystal" | "cr" => Some(ProbeQueryLang::Crystal),
-        "csharp" => Some(ProbeQueryLang::Builtin(SupportLang::CSharp)),
+        "csharp" | "cs" | "c#" => Some(ProbeQueryLang::Builtin(SupportLang::CSharp)),
         _ => None,
     }
 }
 
+/// Check whether structural AST queries are supported for a language
+pub fn is_supported_language(lang: &str) -> bool {
+    get_language(lang).is_some()
+}
+
+/// Infer the query language from a file extension
+fn infer_language(file_ext: &str) -> Option<ProbeQueryLang> {
+    match file_ext {
+        "rs" => Some(ProbeQueryLang::Builtin(SupportLang::Rust)),
+        "js" | "jsx" | "mjs" => Some(ProbeQueryLang::Builtin(SupportLang::JavaScript)),
+        "ts" | "tsx" => Some(ProbeQueryLang::Builtin(SupportLang::TypeScript)),
+        "py" => Some(ProbeQueryLang::Builtin(SupportLang::Python)),
+        "go" => Some(ProbeQueryLang::Builtin(SupportLang::Go)),
+        "c" | "h" => Some(ProbeQueryLang::Builtin(SupportLang::C)),
+        "cpp" | "hpp" | "cc" | "hh" | "cxx" | "hxx" => {
+            Some(ProbeQueryLang::Builtin(SupportLang::Cpp))
+        }
+        "java" => Some(ProbeQueryLang::Builtin(SupportLang::Java)),
+        "kt" | "kts" => Some(ProbeQueryLang::Builtin(SupportLang::Kotlin)),
+        "rb" => Some(ProbeQueryLang::Builtin(SupportLang::Ruby)),
+        "php" => Some(ProbeQueryLang::Builtin(SupportLang::Php)),
+        "swift" => Some(ProbeQueryLang::Builtin(SupportLang::Swift)),
+        "hs" | "lhs" => Some(ProbeQueryLang::Builtin(SupportLang::Haskell)),
+        "sol" => Some(ProbeQueryLang::Solidity),
+        "cr" => Some(ProbeQueryLang::Crystal),
+        "cs" => Some(ProbeQueryLang::Builtin(SupportLang::CSharp)),
+        _ => None, // Unsupported extension
+    }
+}
+
 /// Get the file extension for a language
 fn get_file_extension(lang: &str) -> Vec<&str> {
     match lang.to_lowercase().as_str() {
@@ -95,13 +287,14 @@ fn get_file_extension(lang: &str) -> Vec<&str> {
         "c" => vec![".c", ".h"],
         "cpp" => vec![".cpp", ".hpp", ".cc", ".hh", ".cxx", ".hxx"],
         "java" => vec![".java"],
+        "kotlin" | "kt" | "kts" => vec![".kt", ".kts"],
         "ruby" => vec![".rb"],
         "php" => vec![".php"],
         "swift" => vec![".swift"],
         "haskell" | "hs" | "lhs" => vec![".hs", ".lhs"],
         "solidity" | "sol" => vec![".sol"],
         "crystal" | "cr" => vec![".cr"],
-        "csharp" => vec![".cs"],
+        "csharp" | "cs" | "c#" => vec![".cs"],
         _ => vec![],
     }
 }
@@ -157,8 +350,15 @@ fn query_file(file_path: &Path, options: &QueryOptions) -> Result<Vec<AstMatch>>
     // used by regular search.
     let content = file_guard::read_searchable_text_file(file_path)?;
 
+    // Plain-text matches have no syntax tree to anchor against
+    let plain_text_allowed = options.parent_kinds.is_empty();
+
     if force_plain_text {
-        return Ok(query_plain_text_file(file_path, &content, options.pattern));
+        return Ok(if plain_text_allowed {
+            query_plain_text_patterns(file_path, &content, options)
+        } else {
+            vec![]
+        });
     }
 
     // Get the language for ast-grep
@@ -169,133 +369,561 @@ fn query_file(file_path: &Path, options: &QueryOptions) -> Result<Vec<AstMatch>>
             None => return Ok(vec![]),
         }
     } else {
-        // If language is not specified, try to infer from file extension
-        let inferred_lang = match file_ext {
-            "rs" => Some(ProbeQueryLang::Builtin(SupportLang::Rust)),
-            "js" | "jsx" | "mjs" => Some(ProbeQueryLang::Builtin(SupportLang::JavaScript)),
-            "ts" | "tsx" => Some(ProbeQueryLang::Builtin(SupportLang::TypeScript)),
-            "py" => Some(ProbeQueryLang::Builtin(SupportLang::Python)),
-            "go" => Some(ProbeQueryLang::Builtin(SupportLang::Go)),
-            "c" | "h" => Some(ProbeQueryLang::Builtin(SupportLang::C)),
-            "cpp" | "hpp" | "cc" | "hh" | "cxx" | "hxx" => {
-                Some(ProbeQueryLang::Builtin(SupportLang::Cpp))
-            }
-            "java" => Some(ProbeQueryLang::Builtin(SupportLang::Java)),
-            "rb" => Some(ProbeQueryLang::Builtin(SupportLang::Ruby)),
-            "php" => Some(ProbeQueryLang::Builtin(SupportLang::Php)),
-            "swift" => Some(ProbeQueryLang::Builtin(SupportLang::Swift)),
-            "hs" | "lhs" => Some(ProbeQueryLang::Builtin(SupportLang::Haskell)),
-            "sol" => Some(ProbeQueryLang::Solidity),
-            "cr" => Some(ProbeQueryLang::Crystal),
-            "cs" => Some(ProbeQueryLang::Builtin(SupportLang::CSharp)),
-            _ => None, // Unsupported extension
-        };
-
-        match inferred_lang {
+        // If language is not specified, try to infer from file extension and language overrides
+        match infer_language(effective_extension(file_path, &content)) {
             Some(lang) => lang,
             None => {
-                return if options.strict {
+                return if options.strict || !plain_text_allowed {
                     Ok(vec![])
                 } else {
-                    Ok(query_plain_text_file(file_path, &content, options.pattern))
+                    Ok(query_plain_text_patterns(file_path, &content, options))
                 };
             }
         }
     };
 
-    // Create the document and grep instance
-    let grep = AstGrep::new(&content, lang);
+    // Counting only needs match ranges, not snippet text or captures
+    let counting = options.format == "count";
 
-    // Create the pattern and find all matches
-    let matches = match std::panic::catch_unwind(|| {
-        grep.root().find_all(options.pattern).collect::<Vec<_>>()
-    }) {
-        Ok(matches) => matches,
-        Err(_) => {
+    // Parse the file once and match every pattern against the same tree
+    let grep = AstGrep::new(&content, lang);
+    let mut ast_matches = Vec::new();
+    for (pattern_index, pattern) in options.patterns().enumerate() {
+        // Compile the pattern once per language and reuse it across files
+        let Some(compiled) = get_or_compile_pattern(pattern, lang) else {
             // Only print error if language is explicitly specified
             // This suppresses errors during auto-detection
             if options.language.is_some() {
-                eprintln!(
-                    "Error parsing pattern: '{}' is not a valid ast-grep pattern",
-                    options.pattern
-                );
+                eprintln!("Error parsing pattern: '{pattern}' is not a valid ast-grep pattern");
             }
-            return Ok(vec![]);
+            continue;
+        };
+
+        // Find all matches
+        let matches =
+            match std::panic::catch_unwind(|| grep.root().find_all(&*compiled).collect::<Vec<_>>())
+            {
+                Ok(matches) => matches,
+                Err(_) => continue,
+            };
+
+        let metavariables = if counting {
+            Vec::new()
+        } else {
+            pattern_metavariables(pattern)
+        };
+
+        // Convert matches to AstMatch structs
+        let mut pattern_matches = Vec::new();
+        for node in matches {
+            let range = node.range();
+
+            let env = node.get_env();
+            let mut captures = HashMap::new();
+            for (name, multiple) in &metavariables {
+                let nodes = if *multiple {
+                    env.get_multiple_matches(name)
+                } else {
+                    env.get_match(name).cloned().into_iter().collect()
+                };
+                if nodes.is_empty() {
+                    continue;
+                }
+                let spans = nodes
+                    .iter()
+                    .map(|captured| {
+                        let range = captured.range();
+                        let (line_start, column_start) = byte_to_line_column(&content, range.start);
+                        let (line_end, column_end) = byte_to_line_column(&content, range.end);
+                        CaptureSpan {
+                            kind: captured.kind().to_string(),
+                            byte_start: range.start,
+                            byte_end: range.end,
+                            line_start,
+                            line_end,
+                            column_start,
+                            column_end,
+                        }
+                    })
+                    .collect();
+                captures.insert(name.clone(), spans);
+            }
+
+            // Convert byte offsets to line and column numbers
+            let mut line_start = 1;
+            let mut column_start = 1;
+            let mut line_end = 1;
+            let mut column_end = 1;
+
+            let mut current_line = 1;
+            let mut current_column = 1;
+
+            for (i, c) in content.char_indices() {
+                if i == range.start {
+                    line_start = current_line;
+                    column_start = current_column;
+                }
+                if i == range.end {
+                    line_end = current_line;
+                    column_end = current_column;
+                    break;
+                }
+
+                if c == '\n' {
+                    current_line += 1;
+                    current_column = 1;
+                } else {
+                    current_column += 1;
+                }
+            }
+
+            pattern_matches.push(AstMatch {
+                file_path: file_path.to_path_buf(),
+                byte_start: range.start,
+                byte_end: range.end,
+                line_start,
+                line_end,
+                column_start,
+                column_end,
+                matched_text: if counting {
+                    String::new()
+                } else {
+                    node.text().to_string()
+                },
+                node_type: "match".to_string(),
+                sexp: None,
+                captures,
+                definition: None,
+                pattern_index: 0,
+            });
+        }
+
+        supplement_c_like_function_matches(
+            &mut pattern_matches,
+            &content,
+            file_path,
+            pattern,
+            options.language,
+            file_ext,
+        );
+        supplement_rust_function_matches(
+            &mut pattern_matches,
+            &content,
+            file_path,
+            pattern,
+            options.language,
+            file_ext,
+        );
+        supplement_python_function_matches(
+            &mut pattern_matches,
+            &content,
+            file_path,
+            pattern,
+            options.language,
+            file_ext,
+        );
+
+        union_matches(&mut ast_matches, pattern_matches, pattern_index);
+    }
+    if !options.or_patterns.is_empty() {
+        ast_matches.sort_by_key(|m| m.byte_start);
+    }
+
+    if !options.parent_kinds.is_empty() && !ast_matches.is_empty() {
+        let anchors = ParentKindAnchors::parse(options.parent_kinds);
+        retain_anchored_matches(&mut ast_matches, &content, lang.get_ts_language(), &anchors);
+    }
+
+    if (options.sexp || options.format == "sexp") && !counting {
+        let indented = options.format == "sexp";
+        attach_sexp(&mut ast_matches, &content, lang.get_ts_language(), indented);
+    }
+
+    if options.group_by == GroupBy::Symbol && !ast_matches.is_empty() {
+        attach_definitions(&mut ast_matches, file_path);
+        ast_matches = collapse_nested_matches(ast_matches);
+    }
+
+    Ok(ast_matches)
+}
+
+/// Add the matches of the pattern at `pattern_index`, skipping any whose byte range an
+/// earlier pattern already matched
+fn union_matches(
+    ast_matches: &mut Vec<AstMatch>,
+    new_matches: Vec<AstMatch>,
+    pattern_index: usize,
+) {
+    let existing: HashSet<(usize, usize)> = ast_matches
+        .iter()
+        .map(|m| (m.byte_start, m.byte_end))
+        .collect();
+    ast_matches.extend(
+        new_matches
+            .into_iter()
+            .filter(|m| !existing.contains(&(m.byte_start, m.byte_end)))
+            .map(|m| AstMatch { pattern_index, ..m }),
+    );
+}
+
+/// Plain-text matches of every pattern, for files without a usable parser
+fn query_plain_text_patterns(
+    file_path: &Path,
+    content: &str,
+    options: &QueryOptions,
+) -> Vec<AstMatch> {
+    let mut matches = Vec::new();
+    for (pattern_index, pattern) in options.patterns().enumerate() {
+        union_matches(
+            &mut matches,
+            query_plain_text_file(file_path, content, pattern),
+            pattern_index,
+        );
+    }
+    if !options.or_patterns.is_empty() {
+        matches.sort_by_key(|m| m.byte_start);
+    }
+    matches
+}
+
+/// Named metavariables of an ast-grep pattern in order of first use, with whether each is a
+/// `$$$` multi-node capture. Anonymous `$$$` and non-capturing `$_` variables are skipped.
+fn pattern_metavariables(pattern: &str) -> Vec<(String, bool)> {
+    let bytes = pattern.as_bytes();
+    let mut variables: Vec<(String, bool)> = Vec::new();
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] != b'$' {
+            i += 1;
+            continue;
+        }
+        let multiple = pattern[i..].starts_with("$$$");
+        i += if multiple { 3 } else { 1 };
+        let name_start = i;
+        while i < bytes.len()
+            && (bytes[i].is_ascii_uppercase() || bytes[i].is_ascii_digit() || bytes[i] == b'_')
+        {
+            i += 1;
+        }
+        let name = &pattern[name_start..i];
+        let capturing = name.starts_with(|c: char| c.is_ascii_uppercase());
+        if capturing && !variables.iter().any(|(existing, _)| existing == name) {
+            variables.push((name.to_string(), multiple));
+        }
+    }
+    variables
+}
+
+/// Parent node kinds required by `QueryOptions::parent_kinds`
+#[derive(Debug, Default)]
+struct ParentKindAnchors<'a> {
+    /// Allowed parent kinds of the matched node (empty: unconstrained)
+    matched: Vec<&'a str>,
+    /// Allowed parent kinds per capture name, without the `$` prefix
+    captures: HashMap<&'a str, Vec<&'a str>>,
+}
+
+impl<'a> ParentKindAnchors<'a> {
+    fn parse(entries: &'a [String]) -> Self {
+        let mut anchors = Self::default();
+        for entry in entries {
+            match entry.split_once('=') {
+                Some((name, kind)) => anchors
+                    .captures
+                    .entry(name.trim().trim_start_matches('$'))
+                    .or_default()
+                    .push(kind.trim()),
+                None => anchors.matched.push(entry.trim()),
+            }
+        }
+        anchors
+    }
+}
+
+/// Drop matches whose node, or whose anchored captures, sit under a parent of another kind
+fn retain_anchored_matches(
+    ast_matches: &mut Vec<AstMatch>,
+    content: &str,
+    language: TSLanguage,
+    anchors: &ParentKindAnchors,
+) {
+    let mut parser = tree_sitter::Parser::new();
+    if parser.set_language(&language).is_err() {
+        return;
+    }
+
+    let Some(tree) = parser.parse(content, None) else {
+        return;
+    };
+
+    let parent_is = |byte_start: usize, byte_end: usize, kinds: &[&str]| {
+        tree.root_node()
+            .descendant_for_byte_range(byte_start, byte_end)
+            .and_then(|node| node.parent())
+            .is_some_and(|parent| kinds.contains(&parent.kind()))
+    };
+
+    ast_matches.retain(|m| {
+        if !anchors.matched.is_empty() && !parent_is(m.byte_start, m.byte_end, &anchors.matched) {
+            return false;
         }
+        anchors.captures.iter().all(|(name, kinds)| {
+            m.captures.get(*name).is_some_and(|spans| {
+                spans
+                    .iter()
+                    .all(|span| parent_is(span.byte_start, span.byte_end, kinds))
+            })
+        })
+    });
+}
+
+/// Fill in the S-expression of the node spanning each match's byte range. With `indented`,
+/// each named child goes on its own line, cut off below `PROBE_SEXP_MAX_DEPTH` levels if set.
+fn attach_sexp(ast_matches: &mut [AstMatch], content: &str, language: TSLanguage, indented: bool) {
+    let mut parser = tree_sitter::Parser::new();
+    if parser.set_language(&language).is_err() {
+        return;
+    }
+
+    let Some(tree) = parser.parse(content, None) else {
+        return;
     };
 
-    // Convert matches to AstMatch structs
-    let mut ast_matches = Vec::new();
-    for node in matches {
-        let range = node.range();
-
-        // Convert byte offsets to line and column numbers
-        let mut line_start = 1;
-        let mut column_start = 1;
-        let mut line_end = 1;
-        let mut column_end = 1;
-
-        let mut current_line = 1;
-        let mut current_column = 1;
-
-        for (i, c) in content.char_indices() {
-            if i == range.start {
-                line_start = current_line;
-                column_start = current_column;
+    let max_depth = std::env::var("PROBE_SEXP_MAX_DEPTH")
+        .ok()
+        .and_then(|v| v.parse::<usize>().ok());
+    for m in ast_matches.iter_mut() {
+        m.sexp = tree
+            .root_node()
+            .descendant_for_byte_range(m.byte_start, m.byte_end)
+            .map(|node| {
+                if indented {
+                    indented_sexp(node, max_depth)
+                } else {
+                    node.to_sexp()
+                }
+            });
+    }
+}
+
+/// Render `node` like `Node::to_sexp`, but with every named child on its own line indented
+/// by its depth. Children nested deeper than `max_depth` are elided as `...`.
+fn indented_sexp(node: Node, max_depth: Option<usize>) -> String {
+    let mut out = String::new();
+    write_indented_sexp(node, None, 0, max_depth, &mut out);
+    out
+}
+
+fn write_indented_sexp(
+    node: Node,
+    field: Option<&str>,
+    depth: usize,
+    max_depth: Option<usize>,
+    out: &mut String,
+) {
+    if depth > 0 {
+        out.push('\n');
+        out.push_str(&"  ".repeat(depth));
+    }
+    if let Some(field) = field {
+        out.push_str(field);
+        out.push_str(": ");
+    }
+    out.push('(');
+    if node.is_missing() {
+        out.push_str("MISSING ");
+        if node.is_named() {
+            out.push_str(node.kind());
+        } else {
+            out.push_str(&format!("{:?}", node.kind()));
+        }
+        out.push(')');
+        return;
+    }
+    out.push_str(node.kind());
+
+    let mut cursor = node.walk();
+    if cursor.goto_first_child() {
+        loop {
+            let child = cursor.node();
+            if child.is_named() || child.is_missing() {
+                if max_depth.is_some_and(|max| depth >= max) {
+                    out.push_str(" ...");
+                    break;
+                }
+                write_indented_sexp(child, cursor.field_name(), depth + 1, max_depth, out);
             }
-            if i == range.end {
-                line_end = current_line;
-                column_end = current_column;
+            if !cursor.goto_next_sibling() {
                 break;
             }
+        }
+    }
+    out.push(')');
+}
 
-            if c == '\n' {
-                current_line += 1;
-                current_column = 1;
-            } else {
-                current_column += 1;
+/// Set the enclosing definition of each match in `file_path`
+fn attach_definitions(ast_matches: &mut [AstMatch], file_path: &Path) {
+    let Some(parsed) = ParsedSourceContext::parse(file_path) else {
+        return;
+    };
+
+    for m in ast_matches.iter_mut() {
+        m.definition = parsed
+            .query_source_context(m.byte_start, m.byte_end, &m.matched_text)
+            .and_then(|context| context.owner)
+            .map(|owner| EnclosingDefinition {
+                name: owner.qualified_symbol.or(owner.symbol),
+                node_type: owner.node_type,
+                line_start: owner.lines[0],
+                line_end: owner.lines[1],
+            });
+    }
+}
+
+/// Drop matches that lie inside another match of the same enclosing definition, keeping
+/// the outermost one
+fn collapse_nested_matches(mut ast_matches: Vec<AstMatch>) -> Vec<AstMatch> {
+    // Outer matches sort before the matches they contain
+    ast_matches.sort_by(|a, b| {
+        a.byte_start
+            .cmp(&b.byte_start)
+            .then(b.byte_end.cmp(&a.byte_end))
+    });
+
+    let mut kept: Vec<AstMatch> = Vec::with_capacity(ast_matches.len());
+    for m in ast_matches {
+        let nested = kept.iter().any(|outer| {
+            outer.definition == m.definition
+                && outer.byte_start <= m.byte_start
+                && m.byte_end <= outer.byte_end
+        });
+        if !nested {
+            kept.push(m);
+        }
+    }
+    kept
+}
+
+/// Group matches by file, or by file and enclosing definition with `GroupBy::Symbol`.
+/// Groups keep the order in which their first match appears; `GroupBy::None` puts every
+/// match in a group of its own.
+pub fn group_matches(matches: &[AstMatch], group_by: GroupBy) -> Vec<MatchGroup<'_>> {
+    let mut groups: Vec<MatchGroup> = Vec::new();
+    let mut group_index: HashMap<(&Path, Option<&EnclosingDefinition>), usize> = HashMap::new();
+
+    for (position, m) in matches.iter().enumerate() {
+        let definition = match group_by {
+            GroupBy::None => {
+                groups.push(MatchGroup {
+                    file_path: &m.file_path,
+                    definition: m.definition.as_ref(),
+                    indices: vec![position],
+                });
+                continue;
+            }
+            GroupBy::File => None,
+            GroupBy::Symbol => m.definition.as_ref(),
+        };
+
+        let index = *group_index
+            .entry((m.file_path.as_path(), definition))
+            .or_insert_with(|| {
+                groups.push(MatchGroup {
+                    file_path: &m.file_path,
+                    definition,
+                    indices: Vec::new(),
+                });
+                groups.len() - 1
+            });
+        groups[index].indices.push(position);
+    }
+
+    groups
+}
+
+/// Return the compiled pattern for `(lang, pattern)`, compiling it on first use.
+///
+/// Invalid patterns are cached too, so they are only rejected once per language.
+fn get_or_compile_pattern(
+    pattern: &str,
+    lang: ProbeQueryLang,
+) -> Option<Arc<Pattern<ProbeQueryLang>>> {
+    let key = (format!("{lang:?}"), pattern.to_string());
+    // Hold the lock while compiling so concurrent files never compile the same pattern twice
+    let mut cache = PATTERN_CACHE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(compiled) = cache.get(&key) {
+        return compiled.clone();
+    }
+
+    PATTERN_COMPILE_COUNT.fetch_add(1, Ordering::Relaxed);
+    let compiled = std::panic::catch_unwind(|| compile_pattern(pattern, lang))
+        .ok()
+        .map(Arc::new);
+    cache.put(key, compiled.clone());
+    compiled
+}
+
+/// Compile `pattern` for `lang`. C# member patterns are compiled inside a class body so they
+/// match methods and properties rather than local functions.
+fn compile_pattern(pattern: &str, lang: ProbeQueryLang) -> Pattern<ProbeQueryLang> {
+    if let ProbeQueryLang::Builtin(SupportLang::CSharp) = lang {
+        if let Some((context, kind)) = csharp_member_context(pattern, lang) {
+            if let Ok(compiled) = Pattern::contextual(&context, kind, lang) {
+                return compiled;
             }
         }
+    }
+    Pattern::new(pattern, lang)
+}
 
-        ast_matches.push(AstMatch {
-            file_path: file_path.to_path_buf(),
-            byte_start: range.start,
-            byte_end: range.end,
-            line_start,
-            line_end,
-            column_start,
-            column_end,
-            matched_text: node.text().to_string(),
-            node_type: "match".to_string(),
-        });
+/// Member declarations a C# pattern is compiled as when it parses as one in a class body
+const CSHARP_MEMBER_KINDS: &[&str] = &[
+    "method_declaration",
+    "constructor_declaration",
+    "property_declaration",
+    "indexer_declaration",
+    "operator_declaration",
+    "event_declaration",
+];
+
+/// Patterns like `public $RET $NAME($$$) { $$$ }` parse as C# members only inside a class;
+/// on their own they parse as local functions or not at all. Returns the class wrapping the
+/// pattern and the member kind it parses as, or `None` if it is not a single member.
+fn csharp_member_context(pattern: &str, lang: ProbeQueryLang) -> Option<(String, &'static str)> {
+    let context = format!("class ProbeQueryContext {{ {pattern} }}");
+    let mut parser = tree_sitter::Parser::new();
+    parser.set_language(&lang.get_ts_language()).ok()?;
+    let tree = parser.parse(lang.pre_process_pattern(&context).as_ref(), None)?;
+
+    // A body of `$$$` lacks its semicolon, so only the class body's shape is checked
+    let body = tree
+        .root_node()
+        .named_child(0)?
+        .child_by_field_name("body")?;
+    if body.named_child_count() != 1 {
+        return None;
     }
+    let member_kind = body.named_child(0)?.kind();
+    CSHARP_MEMBER_KINDS
+        .iter()
+        .copied()
+        .find(|kind| *kind == member_kind)
+        .map(|kind| (context, kind))
+}
 
-    supplement_c_like_function_matches(
-        &mut ast_matches,
-        &content,
-        file_path,
-        options.pattern,
-        options.language,
-        file_ext,
-    );
-    supplement_rust_function_matches(
-        &mut ast_matches,
-        &content,
-        file_path,
-        options.pattern,
-        options.language,
-        file_ext,
-    );
-    supplement_python_function_matches(
-        &mut ast_matches,
-        &content,
-        file_path,
-        options.pattern,
-        options.language,
-        file_ext,
-    );
+/// Number of pattern compilations that were not served from the cache
+pub fn pattern_compile_count() -> usize {
+    PATTERN_COMPILE_COUNT.load(Ordering::Relaxed)
+}
 
-    Ok(ast_matches)
+/// Remove all cached compiled patterns
+pub fn clear_pattern_cache() {
+    let mut cache = PATTERN_CACHE
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    cache.clear();
 }
 
 fn query_plain_text_file(file_path: &Path, content: &str, pattern: &str) -> Vec<AstMatch> {
@@ -317,6 +945,10 @@ fn query_plain_text_file(file_path: &Path, content: &str, pattern: &str) -> Vec<
                 column_end: line.len() + 1,
                 matched_text: line.to_string(),
                 node_type: "text".to_string(),
+                sexp: None,
+                captures: HashMap::new(),
+                definition: None,
+                pattern_index: 0,
             });
         }
         byte_offset += line.len() + 1;
@@ -365,6 +997,10 @@ fn supplement_c_like_function_matches(
             column_end,
             matched_text,
             node_type: "match".to_string(),
+            sexp: None,
+            captures: HashMap::new(),
+            definition: None,
+            pattern_index: 0,
         });
     }
 
@@ -506,6 +1142,10 @@ fn collect_function_node_matches(
                 column_end: node.end_position().column + 1,
                 matched_text: content[byte_start..byte_end].to_string(),
                 node_type: "match".to_string(),
+                sexp: None,
+                captures: HashMap::new(),
+                definition: None,
+                pattern_index: 0,
             });
         }
     }
@@ -578,6 +1218,74 @@ fn byte_to_line_column(content: &str, byte: usize) -> (usize, usize) {
 }
 
 pub fn perform_query(options: &QueryOptions) -> Result<Vec<AstMatch>> {
+    let all_matches: Vec<AstMatch> = query_files_in_parallel(options, |path| {
+        std::panic::catch_unwind(|| query_file(path, options))
+            .unwrap_or_else(|_| {
+                // Panic was caught, return empty results
+                Ok(vec![])
+            })
+            .unwrap_or_else(|_| {
+                // Error was caught, return empty results
+                vec![]
+            })
+    })
+    .into_iter()
+    .flatten()
+    .collect();
+
+    // Apply max_results limit
+    let mut all_matches = all_matches;
+    if let Some(max) = options.max_results {
+        all_matches.truncate(max);
+    }
+
+    Ok(all_matches)
+}
+
+/// Count the matches of a query per file without keeping snippet text, captures or
+/// S-expressions. With `max_results`, files are skipped once that many matches have been
+/// counted and the total is capped at the limit.
+pub fn count_query_matches(options: &QueryOptions) -> Result<QueryCounts> {
+    let options = QueryOptions {
+        format: "count",
+        sexp: false,
+        with_context: false,
+        ..*options
+    };
+    let counted = AtomicUsize::new(0);
+    let limit = options.max_results.unwrap_or(usize::MAX);
+
+    let per_file = query_files_in_parallel(&options, |path| {
+        if counted.load(Ordering::Relaxed) >= limit {
+            return (path.to_path_buf(), 0);
+        }
+        let count = std::panic::catch_unwind(|| query_file(path, &options))
+            .ok()
+            .and_then(|result| result.ok())
+            .map_or(0, |matches| matches.len());
+        counted.fetch_add(count, Ordering::Relaxed);
+        (path.to_path_buf(), count)
+    });
+
+    let mut counts = QueryCounts::default();
+    for (path, count) in per_file {
+        let count = count.min(limit - counts.total);
+        if count > 0 {
+            counts.total += count;
+            counts.files.push((path, count));
+        }
+    }
+    counts.files.sort();
+
+    Ok(counts)
+}
+
+/// Run `query` on every file selected by the query options, in parallel, returning the
+/// results in walk order
+fn query_files_in_parallel<T: Send>(
+    options: &QueryOptions,
+    query: impl Fn(&Path) -> T + Sync,
+) -> Vec<T> {
     // Suppress panic output if language is not specified
     let suppress_output = options.language.is_none();
 
@@ -636,43 +1344,218 @@ pub fn perform_query(options: &QueryOptions) -> Result<Vec<AstMatch>> {
         builder.git_exclude(false);
     }
 
-    let file_paths: Vec<PathBuf> = builder
+    let mut file_paths: Vec<PathBuf> = builder
         .build()
         .filter_map(|entry| entry.ok())
         .filter(|entry| entry.file_type().is_some_and(|ft| ft.is_file()))
         .filter(|entry| !should_ignore_file(entry.path(), options))
         .filter(|entry| !file_guard::is_hard_denied_path(entry.path()))
-        .map(|entry| entry.path().to_path_buf())
+        .filter_map(|entry| file_list_cache::walked_file_path(&entry, options.follow_symlinks))
         .collect();
+    if options.follow_symlinks {
+        file_list_cache::dedup_real_paths(&mut file_paths);
+    }
 
     // Process files in parallel
-    let all_matches: Vec<AstMatch> = file_paths
-        .par_iter()
-        .flat_map(|path| {
-            std::panic::catch_unwind(|| query_file(path, options))
-                .unwrap_or_else(|_| {
-                    // Panic was caught, return empty results
-                    Ok(vec![])
-                })
-                .unwrap_or_else(|_| {
-                    // Error was caught, return empty results
-                    vec![]
-                })
-        })
-        .collect();
+    let results: Vec<T> = file_paths.par_iter().map(|path| query(path)).collect();
 
     // Restore the original panic hook if we changed it
     if let Some(hook) = original_hook {
         std::panic::set_hook(hook);
     }
 
-    // Apply max_results limit
-    let mut all_matches = all_matches;
-    if let Some(max) = options.max_results {
-        all_matches.truncate(max);
+    results
+}
+
+/// Find the named syntax node at a 1-based line/column position and its ancestor chain.
+///
+/// Columns are byte offsets within the line, matching tree-sitter positions.
+/// Returns `Ok(None)` when the position lies outside the file.
+pub fn perform_query_at_position(
+    path: &Path,
+    line: usize,
+    column: usize,
+) -> Result<Option<PositionMatch>> {
+    let file_ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    let Some(lang) = infer_language(file_ext) else {
+        anyhow::bail!(
+            "Unsupported file type for AST position query: {}",
+            path.display()
+        );
+    };
+
+    let content = file_guard::read_searchable_text_file(path)?;
+    let Some(byte) = line_column_to_byte(&content, line, column) else {
+        return Ok(None);
+    };
+
+    let mut parser = tree_sitter::Parser::new();
+    parser.set_language(&lang.get_ts_language())?;
+    let tree = parser
+        .parse(&content, None)
+        .ok_or_else(|| anyhow::anyhow!("Failed to parse {}", path.display()))?;
+
+    let Some(node) = tree.root_node().named_descendant_for_byte_range(byte, byte) else {
+        return Ok(None);
+    };
+
+    let mut ancestors = Vec::new();
+    let mut current = node.parent();
+    while let Some(parent) = current {
+        ancestors.push(node_info(parent));
+        current = parent.parent();
     }
 
-    Ok(all_matches)
+    Ok(Some(PositionMatch {
+        file_path: path.to_path_buf(),
+        node: node_info(node),
+        ancestors,
+    }))
+}
+
+fn node_info(node: Node) -> AstNodeInfo {
+    AstNodeInfo {
+        kind: node.kind().to_string(),
+        byte_start: node.start_byte(),
+        byte_end: node.end_byte(),
+        line_start: node.start_position().row + 1,
+        line_end: node.end_position().row + 1,
+        column_start: node.start_position().column + 1,
+        column_end: node.end_position().column + 1,
+    }
+}
+
+/// Convert a 1-based line/column position to a byte offset, if it lies within the content
+fn line_column_to_byte(content: &str, line: usize, column: usize) -> Option<usize> {
+    if line == 0 || column == 0 {
+        return None;
+    }
+
+    let mut line_start = 0;
+    for (idx, text) in content.split('\n').enumerate() {
+        if idx + 1 == line {
+            let text = text.strip_suffix('\r').unwrap_or(text);
+            if column > text.len() + 1 {
+                return None;
+            }
+            return Some(line_start + column - 1);
+        }
+        line_start += text.len() + 1;
+    }
+
+    None
+}
+
+/// JSON output of `probe query`
+#[derive(serde::Serialize, schemars::JsonSchema)]
+struct QueryJsonOutput<'a> {
+    /// Matches, in file order
+    results: Vec<JsonMatch<'a>>,
+    summary: JsonSummary,
+    /// Version of probe that produced the output
+    version: &'static str,
+    /// `probe.query.context.v1` when matches carry their source context (`--with-context`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    schema_version: Option<&'static str>,
+    /// Matches grouped by file or enclosing definition (with `--group-by`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    groups: Option<Vec<JsonMatchGroup<'a>>>,
+}
+
+/// A syntax node matched by the query
+#[derive(serde::Serialize, schemars::JsonSchema)]
+struct JsonMatch<'a> {
+    file: String,
+    /// First and last line of the match
+    lines: [usize; 2],
+    node_type: &'a str,
+    /// Source text of the match
+    content: &'a str,
+    column_start: usize,
+    column_end: usize,
+    /// S-expression of the matched node (with `--sexp`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    sexp: Option<&'a str>,
+    /// Index of the pattern that produced the match, when several patterns are given
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pattern_index: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    definition: Option<JsonDefinition<'a>>,
+    /// Nodes bound to each named metavariable, keyed without the `$` prefix
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    captures: BTreeMap<&'a str, Vec<JsonCaptureSpan<'a>>>,
+    /// Language of the matched file (with `--with-context`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language: Option<String>,
+    /// Pattern that produced the match (with `--with-context`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pattern: Option<JsonPatternRef<'a>>,
+    /// Matched node (with `--with-context`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    r#match: Option<MatchContext>,
+    /// Definition owning the match (with `--with-context`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    owner: Option<OwnerContext>,
+}
+
+/// A node bound to a metavariable
+#[derive(serde::Serialize, schemars::JsonSchema)]
+struct JsonCaptureSpan<'a> {
+    kind: &'a str,
+    bytes: [usize; 2],
+    lines: [usize; 2],
+    column_start: usize,
+    column_end: usize,
+}
+
+/// The innermost definition that contains a match
+#[derive(serde::Serialize, schemars::JsonSchema)]
+struct JsonDefinition<'a> {
+    /// Qualified name of the definition, when it has one
+    name: Option<&'a str>,
+    node_type: &'a str,
+    lines: [usize; 2],
+}
+
+impl<'a> From<&'a EnclosingDefinition> for JsonDefinition<'a> {
+    fn from(definition: &'a EnclosingDefinition) -> Self {
+        Self {
+            name: definition.name.as_deref(),
+            node_type: &definition.node_type,
+            lines: [definition.line_start, definition.line_end],
+        }
+    }
+}
+
+/// Source of the pattern that produced a match
+#[derive(serde::Serialize, schemars::JsonSchema)]
+struct JsonPatternRef<'a> {
+    source: Option<&'a str>,
+    /// Reserved for named patterns; always null
+    id: Option<String>,
+}
+
+/// Totals over the matches
+#[derive(serde::Serialize, schemars::JsonSchema)]
+struct JsonSummary {
+    count: usize,
+    total_bytes: usize,
+    total_tokens: usize,
+}
+
+/// Matches sharing a file or enclosing definition
+#[derive(serde::Serialize, schemars::JsonSchema)]
+struct JsonMatchGroup<'a> {
+    file: String,
+    /// Indices of the group's matches in `results`
+    results: Vec<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    definition: Option<JsonDefinition<'a>>,
+}
+
+/// JSON Schema of the `--format json` output of `probe query`
+pub fn query_json_schema() -> schemars::Schema {
+    probe_code::utils::json_schema::output_schema::<QueryJsonOutput<'static>>()
 }
 
 /// Helper function to escape XML special characters
@@ -684,14 +1567,98 @@ fn escape_xml(s: &str) -> String {
         .replace("'", "&apos;")
 }
 
+/// Heading of a match group: the file, followed by the enclosing definition if there is one
+fn group_heading(group: &MatchGroup) -> String {
+    let file = group.file_path.display();
+    match group.definition {
+        Some(definition) => format!(
+            "{file}: {} {} (lines {}-{})",
+            definition.node_type,
+            definition.name.as_deref().unwrap_or("<anonymous>"),
+            definition.line_start,
+            definition.line_end
+        ),
+        None => file.to_string(),
+    }
+}
+
+/// The pattern that produced `m`, when there is more than one to tell apart
+fn matched_pattern<'a>(m: &AstMatch, patterns: &[&'a str]) -> Option<&'a str> {
+    if patterns.len() > 1 {
+        patterns.get(m.pattern_index).copied()
+    } else {
+        None
+    }
+}
+
+/// Print grouped matches in the text formats: one heading per group with its matches nested
+fn print_grouped_query_results(
+    matches: &[AstMatch],
+    format: &str,
+    patterns: &[&str],
+    group_by: GroupBy,
+) {
+    for group in group_matches(matches, group_by) {
+        let heading = group_heading(&group);
+        match format {
+            "plain" => println!("{heading}"),
+            "markdown" => println!("### {heading}"),
+            _ => println!("{}", heading.cyan().bold()),
+        }
+        println!();
+
+        for m in group.indices.iter().map(|&index| &matches[index]) {
+            let location = format!("{}:{}", m.line_start, m.column_start);
+            match format {
+                "markdown" => {
+                    let lang = m
+                        .file_path
+                        .extension()
+                        .and_then(|e| e.to_str())
+                        .unwrap_or("");
+                    println!("**{location}**");
+                    println!("```{lang}");
+                    println!("{}", m.matched_text.trim());
+                    println!("```");
+                    if let Some(pattern) = matched_pattern(m, patterns) {
+                        println!("Pattern: `{pattern}`");
+                    }
+                }
+                _ => {
+                    if format == "plain" {
+                        println!("  {location}");
+                    } else {
+                        println!("  {}", location.cyan());
+                    }
+                    for line in m.matched_text.trim().lines() {
+                        println!("    {line}");
+                    }
+                    if let Some(pattern) = matched_pattern(m, patterns) {
+                        if format == "plain" {
+                            println!("  Pattern: {pattern}");
+                        } else {
+                            println!("  {} {}", "Pattern:".dimmed(), pattern.dimmed());
+                        }
+                    }
+                }
+            }
+            println!();
+        }
+    }
+}
+
 /// Format and print the query results
 pub fn format_and_print_query_results(
     matches: &[AstMatch],
     format: &str,
-    pattern: &str,
+    patterns: &[&str],
     with_context: bool,
+    group_by: GroupBy,
 ) -> Result<()> {
     match format {
+        "color" | "terminal" | "plain" | "markdown" if group_by != GroupBy::None => {
+            print_grouped_query_results(matches, format, patterns, group_by);
+        }
         "color" | "terminal" => {
             for m in matches {
                 println!(
@@ -705,6 +1672,12 @@ pub fn format_and_print_query_results(
                     .cyan()
                 );
                 println!("{}", m.matched_text.trim());
+                if let Some(sexp) = &m.sexp {
+                    println!("{} {}", "S-expression:".dimmed(), sexp.dimmed());
+                }
+                if let Some(pattern) = matched_pattern(m, patterns) {
+                    println!("{} {}", "Pattern:".dimmed(), pattern.dimmed());
+                }
                 println!();
             }
         }
@@ -717,6 +1690,12 @@ pub fn format_and_print_query_results(
                     m.column_start
                 );
                 println!("{}", m.matched_text.trim());
+                if let Some(sexp) = &m.sexp {
+                    println!("S-expression: {sexp}");
+                }
+                if let Some(pattern) = matched_pattern(m, patterns) {
+                    println!("Pattern: {pattern}");
+                }
                 println!();
             }
         }
@@ -739,16 +1718,19 @@ pub fn format_and_print_query_results(
                 println!("```{lang}");
                 println!("{}", m.matched_text.trim());
                 println!("```");
+                if let Some(sexp) = &m.sexp {
+                    println!("S-expression: `{sexp}`");
+                }
+                if let Some(pattern) = matched_pattern(m, patterns) {
+                    println!("Pattern: `{pattern}`");
+                }
                 println!();
             }
         }
         "json" => {
-            use std::collections::HashMap;
-
             // BATCH TOKENIZATION WITH DEDUPLICATION OPTIMIZATION for query JSON output:
             // Process all matched text in batch to leverage content deduplication
             use probe_code::search::search_tokens::sum_tokens_with_deduplication;
-            use probe_code::semantic_context::ParsedSourceContext;
 
             let matched_texts: Vec<&str> =
                 matches.iter().map(|m| m.matched_text.as_str()).collect();
@@ -758,17 +1740,41 @@ pub fn format_and_print_query_results(
                 HashMap::new();
 
             // Create standardized results
-            let json_matches_standardized: Vec<_> = matches
+            let json_matches_standardized: Vec<JsonMatch> = matches
                 .iter()
                 .map(|m| {
-                    let mut result = serde_json::json!({
-                        "file": m.file_path.to_string_lossy(),
-                        "lines": [m.line_start, m.line_end],
-                        "node_type": m.node_type,
-                        "content": m.matched_text,
-                        "column_start": m.column_start,
-                        "column_end": m.column_end
-                    });
+                    let mut result = JsonMatch {
+                        file: m.file_path.to_string_lossy().into_owned(),
+                        lines: [m.line_start, m.line_end],
+                        node_type: &m.node_type,
+                        content: &m.matched_text,
+                        column_start: m.column_start,
+                        column_end: m.column_end,
+                        sexp: m.sexp.as_deref(),
+                        pattern_index: (patterns.len() > 1).then_some(m.pattern_index),
+                        definition: m.definition.as_ref().map(JsonDefinition::from),
+                        captures: m
+                            .captures
+                            .iter()
+                            .map(|(name, spans)| {
+                                let spans = spans
+                                    .iter()
+                                    .map(|span| JsonCaptureSpan {
+                                        kind: &span.kind,
+                                        bytes: [span.byte_start, span.byte_end],
+                                        lines: [span.line_start, span.line_end],
+                                        column_start: span.column_start,
+                                        column_end: span.column_end,
+                                    })
+                                    .collect();
+                                (name.as_str(), spans)
+                            })
+                            .collect(),
+                        language: None,
+                        pattern: None,
+                        r#match: None,
+                        owner: None,
+                    };
 
                     if with_context {
                         let parsed = parsed_files
@@ -777,15 +1783,13 @@ pub fn format_and_print_query_results(
                         if let Some(context) = parsed.as_ref().and_then(|parsed| {
                             parsed.query_source_context(m.byte_start, m.byte_end, &m.matched_text)
                         }) {
-                            result["language"] = serde_json::json!(context.language);
-                            result["pattern"] = serde_json::json!({
-                                "source": pattern,
-                                "id": serde_json::Value::Null,
+                            result.language = Some(context.language);
+                            result.pattern = Some(JsonPatternRef {
+                                source: patterns.get(m.pattern_index).copied(),
+                                id: None,
                             });
-                            result["match"] = serde_json::json!(context.r#match);
-                            if let Some(owner) = context.owner {
-                                result["owner"] = serde_json::json!(owner);
-                            }
+                            result.r#match = Some(context.r#match);
+                            result.owner = context.owner;
                         }
                     }
 
@@ -793,19 +1797,32 @@ pub fn format_and_print_query_results(
                 })
                 .collect();
 
+            // Groups refer to their matches by index into `results`
+            let groups = (group_by != GroupBy::None).then(|| group_matches(matches, group_by));
+
             // Create the wrapper object
-            let mut wrapper = serde_json::json!({
-                "results": json_matches_standardized,
-                "summary": {
-                    "count": matches.len(),
-                    "total_bytes": matches.iter().map(|m| m.matched_text.len()).sum::<usize>(),
-                    "total_tokens": total_tokens
+            let wrapper = QueryJsonOutput {
+                results: json_matches_standardized,
+                summary: JsonSummary {
+                    count: matches.len(),
+                    total_bytes: matches.iter().map(|m| m.matched_text.len()).sum::<usize>(),
+                    total_tokens,
                 },
-                "version": probe_code::version::get_version()
-            });
-            if with_context {
-                wrapper["schema_version"] = serde_json::json!("probe.query.context.v1");
-            }
+                version: probe_code::version::get_version(),
+                schema_version: with_context.then_some("probe.query.context.v1"),
+                groups: groups.as_ref().map(|groups| {
+                    groups
+                        .iter()
+                        .map(|group| JsonMatchGroup {
+                            file: group.file_path.to_string_lossy().into_owned(),
+                            results: group.indices.clone(),
+                            definition: group.definition.map(JsonDefinition::from),
+                        })
+                        .collect()
+                }),
+            };
+            // Going through a JSON value keeps the object keys sorted
+            let wrapper = serde_json::to_value(&wrapper)?;
 
             println!("{}", serde_json::to_string_pretty(&wrapper)?);
         }
@@ -824,6 +1841,39 @@ pub fn format_and_print_query_results(
                 println!("    <column_start>{}</column_start>", m.column_start);
                 println!("    <column_end>{}</column_end>", m.column_end);
                 println!("    <code><![CDATA[{}]]></code>", m.matched_text.trim());
+                if let Some(sexp) = &m.sexp {
+                    println!("    <sexp>{}</sexp>", escape_xml(sexp));
+                }
+                if patterns.len() > 1 {
+                    println!("    <pattern_index>{}</pattern_index>", m.pattern_index);
+                }
+                if let Some(definition) = &m.definition {
+                    println!(
+                        "    <definition node_type=\"{}\" name=\"{}\" lines=\"{}-{}\"/>",
+                        escape_xml(&definition.node_type),
+                        escape_xml(definition.name.as_deref().unwrap_or("")),
+                        definition.line_start,
+                        definition.line_end
+                    );
+                }
+                if !m.captures.is_empty() {
+                    let captures: BTreeMap<_, _> = m.captures.iter().collect();
+                    println!("    <captures>");
+                    for (name, spans) in captures {
+                        for span in spans {
+                            println!(
+                                "      <capture name=\"{}\" kind=\"{}\" bytes=\"{}-{}\" lines=\"{}-{}\"/>",
+                                escape_xml(name),
+                                escape_xml(&span.kind),
+                                span.byte_start,
+                                span.byte_end,
+                                span.line_start,
+                                span.line_end
+                            );
+                        }
+                    }
+                    println!("    </captures>");
+                }
                 println!("  </result>");
             }
 
@@ -852,19 +1902,57 @@ pub fn format_and_print_query_results(
 
             println!("</probe_results>");
         }
+        "sexp" => {
+            for m in matches {
+                println!(
+                    "{}",
+                    format!(
+                        "{}:{}:{}",
+                        m.file_path.display(),
+                        m.line_start,
+                        m.column_start
+                    )
+                    .cyan()
+                );
+                println!("{}", m.matched_text.trim());
+                if let Some(sexp) = &m.sexp {
+                    println!("{sexp}");
+                }
+                println!();
+            }
+        }
         _ => {
             // Default to color format
-            format_and_print_query_results(matches, "color", pattern, with_context)?;
+            format_and_print_query_results(matches, "color", patterns, with_context, group_by)?;
         }
     }
 
     Ok(())
 }
 
+/// Print match counts as `file,matches` CSV rows followed by a `total` row
+fn print_query_counts(counts: &QueryCounts) {
+    println!("file,matches");
+    for (path, count) in &counts.files {
+        println!("{},{count}", csv_field(&path.to_string_lossy()));
+    }
+    println!("total,{}", counts.total);
+}
+
+/// Quote a CSV field if it contains a separator, quote or line break
+fn csv_field(value: &str) -> Cow<'_, str> {
+    if value.contains([',', '"', '\n', '\r']) {
+        Cow::Owned(format!("\"{}\"", value.replace('"', "\"\"")))
+    } else {
+        Cow::Borrowed(value)
+    }
+}
+
 /// Handle the query command
 #[allow(clippy::too_many_arguments)]
 pub fn handle_query(
     pattern: &str,
+    or_patterns: &[String],
     path: &Path,
     language: Option<&str>,
     ignore: &[String],
@@ -875,15 +1963,25 @@ pub fn handle_query(
     with_context: bool,
     strict: bool,
     text_extensions: Vec<String>,
+    parent_kinds: Vec<String>,
+    sexp: bool,
+    group_by: GroupBy,
+    follow_symlinks: bool,
 ) -> Result<()> {
+    // The count summary is meant to be piped, so it gets no header either
+    let machine_readable = matches!(format, "json" | "xml" | "count");
+
     // Print version at the start for text-based formats
-    if format != "json" && format != "xml" {
+    if !machine_readable {
         println!("Probe version: {}", probe_code::version::get_version());
     }
 
     // Only print information for non-JSON/XML formats
-    if format != "json" && format != "xml" {
+    if !machine_readable {
         println!("{} {}", "Pattern:".bold().green(), pattern);
+        for or_pattern in or_patterns {
+            println!("{} {}", "Or pattern:".bold().green(), or_pattern);
+        }
         println!("{} {}", "Path:".bold().green(), path.display());
 
         // Print language if provided, otherwise show auto-detect
@@ -901,6 +1999,20 @@ pub fn handle_query(
         if no_gitignore {
             advanced_options.push("Ignoring .gitignore".to_string());
         }
+        if follow_symlinks {
+            advanced_options.push("Following symlinks".to_string());
+        }
+        if !parent_kinds.is_empty() {
+            advanced_options.push(format!("Parent kinds: {}", parent_kinds.join(", ")));
+        }
+        if sexp {
+            advanced_options.push("Showing S-expressions".to_string());
+        }
+        match group_by {
+            GroupBy::None => {}
+            GroupBy::File => advanced_options.push("Grouping by file".to_string()),
+            GroupBy::Symbol => advanced_options.push("Grouping by symbol".to_string()),
+        }
         if let Some(max) = max_results {
             advanced_options.push(format!("Max results: {max}"));
         }
@@ -919,6 +2031,7 @@ pub fn handle_query(
     let options = QueryOptions {
         path,
         pattern,
+        or_patterns,
         language,
         ignore,
         allow_tests,
@@ -928,9 +2041,19 @@ pub fn handle_query(
         no_gitignore,
         strict,
         text_extensions: &text_extensions,
+        parent_kinds: &parent_kinds,
+        sexp,
+        group_by,
+        follow_symlinks,
     };
 
+    if format == "count" {
+        print_query_counts(&count_query_matches(&options)?);
+        return Ok(());
+    }
+
     let matches = perform_query(&options)?;
+    let patterns: Vec<&str> = options.patterns().collect();
 
     // Calculate search time
     let duration = start_time.elapsed();
@@ -938,7 +2061,7 @@ pub fn handle_query(
     if matches.is_empty() {
         // For JSON and XML formats, still call format_and_print_query_results
         if format == "json" || format == "xml" {
-            format_and_print_query_results(&matches, format, pattern, with_context)?;
+            format_and_print_query_results(&matches, format, &patterns, with_context, group_by)?;
         } else {
             // For other formats, print the "No results found" message
             println!("{}", "No results found.".yellow().bold());
@@ -951,7 +2074,7 @@ pub fn handle_query(
             println!();
         }
 
-        format_and_print_query_results(&matches, format, pattern, with_context)?;
+        format_and_print_query_results(&matches, format, &patterns, with_context, group_by)?;
 
         // Skip summary for JSON and XML formats
         if format != "json" && format != "xml" {
@@ -1001,6 +2124,7 @@ contract Counter {
         let options = QueryOptions {
             path: temp_dir.path(),
             pattern: "function $NAME() public { $$$BODY }",
+            or_patterns: &[],
             language: Some("solidity"),
             ignore: &[],
             allow_tests: true,
@@ -1010,6 +2134,10 @@ contract Counter {
             no_gitignore: true,
             strict: false,
             text_extensions: &[],
+            parent_kinds: &[],
+            sexp: false,
+            group_by: GroupBy::None,
+            follow_symlinks: false,
         };
 
         let matches = perform_query(&options).expect("Solidity query should run");
@@ -1037,6 +2165,7 @@ end
         let options = QueryOptions {
             path: temp_dir.path(),
             pattern: "def increment : Int32",
+            or_patterns: &[],
             language: Some("crystal"),
             ignore: &[],
             allow_tests: true,
@@ -1046,6 +2175,10 @@ end
             no_gitignore: true,
             strict: false,
             text_extensions: &[],
+            parent_kinds: &[],
+            sexp: false,
+            group_by: GroupBy::None,
+            follow_symlinks: false,
         };
 
         let matches = perform_query(&options).expect("Crystal query should run");
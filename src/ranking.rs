@@ -1,9 +1,14 @@
 use crate::simd_ranking::{SimdBm25Params, SparseDocumentMatrix};
 use ahash::{AHashMap, AHashSet};
+use probe_code::models::{SearchResult, TermScore};
 use probe_code::search::elastic_query::Expr;
 use probe_code::search::tokenization;
 use rayon::prelude::*;
 use rust_stemmers::{Algorithm, Stemmer};
+use serde::{Deserialize, Serialize};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::path::Path;
 use std::sync::OnceLock;
 
 // Replace standard collections with ahash versions for better performance
@@ -31,12 +36,241 @@ pub struct RankingParams<'a> {
     pub query: &'a str,
     /// Pre-tokenized content (optional)
     pub pre_tokenized: Option<&'a [Vec<String>]>,
+    /// Ranking profile supplying the BM25 parameters
+    pub profile: &'a RankingProfile,
 }
 
-/// Returns a reference to the global stemmer instance
+/// Name of the ranking profile used when none is configured. Unless the config redefines it,
+/// it is [`RankingProfile::default`], the built-in ranking.
+pub const DEFAULT_RANKING_PROFILE: &str = "default";
+
+/// Tunable relevance weights, defined as named profiles under `search.ranking_profiles` in the
+/// config. Fields left out of a profile keep their default, which reproduces the built-in
+/// ranking: no extra boosts and BM25 with `k1 = 1.5`, `b = 0.5`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RankingProfile {
+    /// Score multiplier for blocks in files whose name contains a query term
+    pub filename_boost: f64,
+    /// Score multiplier for blocks containing a query word exactly as typed, case included
+    pub exact_match_boost: f64,
+    /// Weight of the bonus for query terms occurring close together; 0 disables it
+    pub proximity_weight: f64,
+    /// BM25 term frequency saturation
+    pub k1: f64,
+    /// BM25 document length normalization, from 0 (none) to 1 (full)
+    pub b: f64,
+}
+
+impl Default for RankingProfile {
+    fn default() -> Self {
+        Self {
+            filename_boost: 1.0,
+            exact_match_boost: 1.0,
+            proximity_weight: 0.0,
+            k1: 1.5,
+            b: 0.5,
+        }
+    }
+}
+
+impl RankingProfile {
+    /// Check that the weights are usable: boosts must be positive, the proximity weight and
+    /// `k1` non-negative, and `b` between 0 and 1
+    pub fn validate(&self) -> anyhow::Result<()> {
+        for (name, boost) in [
+            ("filename_boost", self.filename_boost),
+            ("exact_match_boost", self.exact_match_boost),
+        ] {
+            if !(boost > 0.0 && boost.is_finite()) {
+                anyhow::bail!("{name} must be a positive number, got {boost}");
+            }
+        }
+        if !(self.proximity_weight >= 0.0 && self.proximity_weight.is_finite()) {
+            anyhow::bail!(
+                "proximity_weight must not be negative, got {}",
+                self.proximity_weight
+            );
+        }
+        if !(self.k1 >= 0.0 && self.k1.is_finite()) {
+            anyhow::bail!("k1 must not be negative, got {}", self.k1);
+        }
+        if !(0.0..=1.0).contains(&self.b) {
+            anyhow::bail!("b must be between 0 and 1, got {}", self.b);
+        }
+        Ok(())
+    }
+}
+
+/// Looks up a ranking profile in the config by name, or the configured
+/// `search.ranking_profile` when `name` is `None`
+pub fn resolve_ranking_profile(name: Option<&str>) -> anyhow::Result<RankingProfile> {
+    let search = &probe_code::config::get_config().search;
+    let name = name.unwrap_or(&search.ranking_profile);
+    match search.ranking_profiles.get(name) {
+        Some(profile) => Ok(profile.clone()),
+        None if name == DEFAULT_RANKING_PROFILE => Ok(RankingProfile::default()),
+        None => {
+            let mut known: Vec<&str> = search.ranking_profiles.keys().map(String::as_str).collect();
+            if !known.contains(&DEFAULT_RANKING_PROFILE) {
+                known.push(DEFAULT_RANKING_PROFILE);
+            }
+            known.sort_unstable();
+            anyhow::bail!(
+                "Unknown ranking profile: {name} (available: {})",
+                known.join(", ")
+            )
+        }
+    }
+}
+
+/// The boosts of a [`RankingProfile`] for one query, applied on top of the BM25 score
+pub struct ProfileBoosts<'a> {
+    profile: &'a RankingProfile,
+    /// Positive query words as typed
+    words: Vec<String>,
+    /// The same words, tokenized like documents
+    terms: HashSet<String>,
+}
+
+impl<'a> ProfileBoosts<'a> {
+    pub fn new(profile: &'a RankingProfile, queries: &[String]) -> Self {
+        let words = typed_query_words(queries);
+        let terms = words.iter().flat_map(|word| tokenize(word)).collect();
+        Self {
+            profile,
+            words,
+            terms,
+        }
+    }
+
+    /// Score multiplier for a block of `code` in `file`, 1.0 with the default profile
+    pub fn factor(&self, file: &str, code: &str) -> f64 {
+        let profile = self.profile;
+        let mut factor = 1.0;
+        if profile.filename_boost != 1.0 {
+            let file_name = Path::new(file)
+                .file_name()
+                .map(|name| name.to_string_lossy())
+                .unwrap_or_default();
+            if tokenize(&file_name)
+                .iter()
+                .any(|token| self.terms.contains(token))
+            {
+                factor *= profile.filename_boost;
+            }
+        }
+        if profile.exact_match_boost != 1.0
+            && self.words.iter().any(|word| code.contains(word.as_str()))
+        {
+            factor *= profile.exact_match_boost;
+        }
+        if profile.proximity_weight > 0.0 {
+            factor *= 1.0 + profile.proximity_weight * term_proximity(&tokenize(code), &self.terms);
+        }
+        factor
+    }
+}
+
+/// The positive words of the queries as typed: `AND`/`OR`, `-term` exclusions, `+`, quotes,
+/// parentheses and `field:` prefixes are dropped
+fn typed_query_words(queries: &[String]) -> Vec<String> {
+    queries
+        .iter()
+        .flat_map(|query| query.split_whitespace())
+        .filter(|word| !word.starts_with('-') && *word != "AND" && *word != "OR")
+        .map(|word| {
+            let word = word
+                .trim_start_matches(['+', '('])
+                .trim_end_matches(')')
+                .trim_matches('"');
+            // Drop a `field:` prefix, but keep `a::b` paths
+            match word.find(':') {
+                Some(index) if !word[index..].starts_with("::") => &word[index + 1..],
+                _ => word,
+            }
+        })
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// How close together the distinct query terms of a document are: the number of distinct
+/// terms over the length of the shortest token window containing all of them. 1 when they
+/// are adjacent, 0 when fewer than two distinct terms occur.
+fn term_proximity(tokens: &[String], terms: &HashSet<String>) -> f64 {
+    let positions: Vec<(usize, &str)> = tokens
+        .iter()
+        .enumerate()
+        .filter(|(_, token)| terms.contains(*token))
+        .map(|(position, token)| (position, token.as_str()))
+        .collect();
+    let distinct = positions
+        .iter()
+        .map(|(_, term)| *term)
+        .collect::<HashSet<_>>()
+        .len();
+    if distinct < 2 {
+        return 0.0;
+    }
+
+    // Slide a window over the matches, shrinking it from the left while it still holds every term
+    let mut counts: HashMap<&str, usize> = HashMap::new();
+    let mut shortest = usize::MAX;
+    let mut start = 0;
+    for &(end_position, term) in &positions {
+        *counts.entry(term).or_insert(0) += 1;
+        while counts.len() == distinct {
+            let (start_position, start_term) = positions[start];
+            shortest = shortest.min(end_position - start_position + 1);
+            let count = counts.get_mut(start_term).expect("term in window");
+            *count -= 1;
+            if *count == 0 {
+                counts.remove(start_term);
+            }
+            start += 1;
+        }
+    }
+    distinct as f64 / shortest as f64
+}
+
+/// Snowball stemming algorithm for a language name, e.g. "english" or "german"
+pub fn stemmer_algorithm(language: &str) -> Option<Algorithm> {
+    let algorithm = match language.to_lowercase().as_str() {
+        "arabic" => Algorithm::Arabic,
+        "danish" => Algorithm::Danish,
+        "dutch" => Algorithm::Dutch,
+        "english" => Algorithm::English,
+        "finnish" => Algorithm::Finnish,
+        "french" => Algorithm::French,
+        "german" => Algorithm::German,
+        "greek" => Algorithm::Greek,
+        "hungarian" => Algorithm::Hungarian,
+        "italian" => Algorithm::Italian,
+        "norwegian" => Algorithm::Norwegian,
+        "portuguese" => Algorithm::Portuguese,
+        "romanian" => Algorithm::Romanian,
+        "russian" => Algorithm::Russian,
+        "spanish" => Algorithm::Spanish,
+        "swedish" => Algorithm::Swedish,
+        "tamil" => Algorithm::Tamil,
+        "turkish" => Algorithm::Turkish,
+        _ => return None,
+    };
+    Some(algorithm)
+}
+
+/// Returns a reference to the global stemmer instance, for the configured `stemmer_language`
 pub fn get_stemmer() -> &'static Stemmer {
     static STEMMER: OnceLock<Stemmer> = OnceLock::new();
-    STEMMER.get_or_init(|| Stemmer::create(Algorithm::English))
+    STEMMER.get_or_init(|| {
+        let language = &probe_code::config::get_config().search.stemmer_language;
+        let algorithm = stemmer_algorithm(language).unwrap_or_else(|| {
+            eprintln!("Warning: Unsupported stemmer language '{language}', using english");
+            Algorithm::English
+        });
+        Stemmer::create(algorithm)
+    })
 }
 
 /// Tokenizes text into lowercase words by splitting on whitespace and non-alphanumeric characters,
@@ -113,6 +347,53 @@ pub fn extract_query_terms(expr: &Expr) -> HashSet<String> {
     terms
 }
 
+/// Extracts the excluded (`-term`) keywords of a query expression as groups of tokens.
+///
+/// Each keyword yields its stemmed parts, split and stemmed the same way as positive terms,
+/// plus the keyword as written: the parser registers excluded keywords as special terms, which
+/// document tokenization keeps unstemmed. Exact (quoted) excluded terms are matched as written
+/// by the boolean scoring instead, so they are not included.
+pub fn extract_excluded_token_groups(expr: &Expr) -> Vec<Vec<String>> {
+    use Expr::*;
+
+    match expr {
+        Term {
+            lowercase_keywords,
+            excluded: true,
+            exact: false,
+            ..
+        } => {
+            let mut groups = Vec::new();
+            for keyword in lowercase_keywords {
+                let stemmed = tokenization::tokenize_and_stem(keyword);
+                if !stemmed.is_empty() && stemmed != [keyword.as_str()] {
+                    groups.push(stemmed);
+                }
+                groups.push(vec![keyword.clone()]);
+            }
+            groups
+        }
+        Term { .. } => Vec::new(),
+        And(left, right) | Or(left, right) => {
+            let mut groups = extract_excluded_token_groups(left);
+            groups.extend(extract_excluded_token_groups(right));
+            groups
+        }
+    }
+}
+
+/// Whether a tokenized document contains an excluded term, i.e. every token of one of the
+/// groups from [`extract_excluded_token_groups`]
+pub fn contains_excluded_term(tokens: &[String], groups: &[Vec<String>]) -> bool {
+    if groups.is_empty() {
+        return false;
+    }
+    let tokens: HashSet<&str> = tokens.iter().map(String::as_str).collect();
+    groups
+        .iter()
+        .any(|group| group.iter().all(|token| tokens.contains(token.as_str())))
+}
+
 /// Precomputes IDF values for a set of terms
 pub fn precompute_idfs(
     terms: &HashSet<String>,
@@ -355,13 +636,14 @@ pub fn rank_documents(params: &RankingParams) -> Vec<(usize, f64)> {
         );
     }
 
-    // 4) BM25 parameters
-    // These values are standard defaults for BM25 as established in academic literature:
-    // k1=1.2 controls term frequency saturation (higher values give more weight to term frequency)
-    // b=0.75 controls document length normalization (higher values give more penalty to longer documents)
+    // 4) BM25 parameters, from the ranking profile
+    // The standard defaults established in academic literature are k1=1.2 and b=0.75:
+    // k1 controls term frequency saturation (higher values give more weight to term frequency)
+    // b controls document length normalization (higher values give more penalty to longer documents)
     // See: Robertson, S. E., & Zaragoza, H. (2009). The Probabilistic Relevance Framework: BM25 and Beyond
-    let k1 = 1.5; // EXPERIMENT: Slightly increased from 1.2 for balanced term frequency weight
-    let b = 0.5; // EXPERIMENT: Moderately reduced from 0.75 for balanced length normalization
+    // The default profile uses k1=1.5 and b=0.5 for balanced term frequency weight and length
+    // normalization
+    let RankingProfile { k1, b, .. } = *params.profile;
 
     if debug_mode {
         println!("DEBUG: Starting parallel document scoring for {n_docs} documents");
@@ -554,8 +836,8 @@ pub fn rank_documents_simd(params: &RankingParams) -> Vec<(usize, f64)> {
                 avgdl,
                 idfs: &precomputed_idfs,
                 query_token_map: &query_token_map,
-                k1: 1.5, // EXPERIMENT: Slightly increased from 1.2
-                b: 0.5,  // EXPERIMENT: Moderately reduced from 0.75
+                k1: params.profile.k1,
+                b: params.profile.b,
             };
 
             // Apply boolean query logic - this filters out documents that don't match requirements
@@ -685,6 +967,185 @@ pub fn rank_documents_simd_simple(params: &RankingParams) -> Vec<(usize, f64)> {
     filtered_docs
 }
 
+/// Drops the results whose final score is below `min_score` and returns how many were
+/// dropped. Results without a score are kept, as exact, regex and inverted searches are
+/// not ranked.
+pub fn retain_min_score(results: &mut Vec<SearchResult>, min_score: f64) -> usize {
+    let before = results.len();
+    results.retain(|result| result.score.is_none_or(|score| score >= min_score));
+    before - results.len()
+}
+
+/// Collapses ranked blocks into one result per file, for file-level ranking.
+///
+/// A file's score is the sum of the BM25 scores of its matching blocks, so files with many
+/// relevant blocks outrank files with a single strong one. Each file is represented by its
+/// highest-scoring block, carrying the aggregate in `score` and `bm25_score`. Blocks the
+/// ranker left unmatched (`rank == usize::MAX`) are ignored. Files are returned by descending
+/// aggregate score, then by path, with 1-based ranks.
+pub fn rank_files(blocks: Vec<SearchResult>) -> Vec<SearchResult> {
+    let mut files: HashMap<String, (SearchResult, f64)> = HashMap::new();
+    for block in blocks {
+        if block.rank == Some(usize::MAX) {
+            continue;
+        }
+        let bm25 = block.bm25_score.or(block.score).unwrap_or(0.0);
+        match files.get_mut(&block.file) {
+            Some((best, total)) => {
+                *total += bm25;
+                if block.score.unwrap_or(0.0) > best.score.unwrap_or(0.0) {
+                    *best = block;
+                }
+            }
+            None => {
+                files.insert(block.file.clone(), (block, bm25));
+            }
+        }
+    }
+
+    let mut ranked: Vec<SearchResult> = files
+        .into_values()
+        .map(|(mut best, total)| {
+            best.score = Some(total);
+            best.bm25_score = Some(total);
+            best
+        })
+        .collect();
+    ranked.sort_by(|a, b| {
+        b.score
+            .unwrap_or(0.0)
+            .partial_cmp(&a.score.unwrap_or(0.0))
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| a.file.cmp(&b.file))
+    });
+    for (rank, result) in ranked.iter_mut().enumerate() {
+        result.rank = Some(rank + 1);
+        result.bm25_rank = Some(rank + 1);
+    }
+    ranked
+}
+
+/// The `k` highest-scoring results offered so far, holding no more than `k` at a time.
+///
+/// Results without a score (exact searches are not ranked) count as scoring zero. Among equal
+/// scores, the result offered first wins, so unranked results keep the order they came in.
+pub struct TopK {
+    k: usize,
+    offered: usize,
+    heap: BinaryHeap<Reverse<Scored>>,
+}
+
+/// A result ordered by score, then by how early it was offered
+struct Scored {
+    score: f64,
+    order: usize,
+    result: SearchResult,
+}
+
+impl Ord for Scored {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.score
+            .total_cmp(&other.score)
+            .then_with(|| other.order.cmp(&self.order))
+    }
+}
+
+impl PartialOrd for Scored {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Scored {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == std::cmp::Ordering::Equal
+    }
+}
+
+impl Eq for Scored {}
+
+impl TopK {
+    pub fn new(k: usize) -> Self {
+        Self {
+            k,
+            offered: 0,
+            heap: BinaryHeap::with_capacity(k.min(1024) + 1),
+        }
+    }
+
+    /// Keep `result` if it is among the `k` best so far, dropping the worst one it displaces
+    pub fn offer(&mut self, result: SearchResult) {
+        let scored = Scored {
+            score: result.score.unwrap_or(0.0),
+            order: self.offered,
+            result,
+        };
+        self.offered += 1;
+        if self.heap.len() < self.k {
+            self.heap.push(Reverse(scored));
+        } else if self.heap.peek().is_some_and(|worst| scored > worst.0) {
+            self.heap.pop();
+            self.heap.push(Reverse(scored));
+        }
+    }
+
+    /// The results kept, best first
+    pub fn into_sorted_vec(self) -> Vec<SearchResult> {
+        self.heap
+            .into_sorted_vec()
+            .into_iter()
+            .map(|Reverse(scored)| scored.result)
+            .collect()
+    }
+}
+
+/// Computes the per-term BM25 inputs (tf and idf) of every document, for `--explain`.
+///
+/// Documents are tokenized and IDF is computed exactly as in [`rank_documents`], so the values
+/// line up with the scores it produces. Query terms are sorted; terms absent from a document
+/// are reported with `tf = 0`.
+pub fn explain_term_scores(params: &RankingParams) -> Vec<Vec<TermScore>> {
+    let Ok(parsed_expr) = crate::search::elastic_query::parse_query(params.query, false) else {
+        return vec![Vec::new(); params.documents.len()];
+    };
+    let query_terms = extract_query_terms(&parsed_expr);
+    let Ok(query_token_map) = generate_query_token_map(&query_terms) else {
+        return vec![Vec::new(); params.documents.len()];
+    };
+
+    let tf_df_result = match params.pre_tokenized {
+        Some(pre_tokenized) => compute_tf_df_from_tokenized(pre_tokenized, &query_token_map),
+        None => {
+            let tokenized_docs: Vec<Vec<String>> =
+                params.documents.iter().map(|doc| tokenize(doc)).collect();
+            compute_tf_df_from_tokenized(&tokenized_docs, &query_token_map)
+        }
+    };
+    let idfs = precompute_idfs(
+        &query_terms,
+        &tf_df_result.document_frequencies,
+        params.documents.len(),
+    );
+
+    let mut sorted_terms: Vec<(&String, &u8)> = query_token_map.iter().collect();
+    sorted_terms.sort();
+
+    tf_df_result
+        .term_frequencies
+        .iter()
+        .map(|doc_tf| {
+            sorted_terms
+                .iter()
+                .map(|(term, token_index)| TermScore {
+                    term: term.to_string(),
+                    tf: doc_tf.get(*token_index).copied().unwrap_or(0),
+                    idf: idfs.get(*term).copied().unwrap_or(0.0),
+                })
+                .collect()
+        })
+        .collect()
+}
+
 /// Computes term frequencies (TF) for each document, document frequencies (DF) for each term,
 /// and document lengths from pre-tokenized content.
 ///
@@ -809,6 +1270,7 @@ mod tests {
             documents: &docs,
             query,
             pre_tokenized: None,
+            profile: &RankingProfile::default(),
         };
 
         let results = rank_documents(&params);
@@ -834,6 +1296,7 @@ mod tests {
             documents: &docs,
             query,
             pre_tokenized: None,
+            profile: &RankingProfile::default(),
         };
 
         let results = rank_documents(&params);
@@ -845,6 +1308,56 @@ mod tests {
         assert_eq!(simd_results[0].0, 0);
     }
 
+    #[test]
+    fn test_excluded_terms_are_stemmed_like_positive_terms() {
+        let expr =
+            crate::search::elastic_query::parse_query("validate -tokens -\"Cookies\"", false)
+                .unwrap();
+        // Quoted excluded terms are matched as written, so only "-tokens" contributes groups
+        let groups = extract_excluded_token_groups(&expr);
+        assert_eq!(
+            groups,
+            vec![vec!["token".to_string()], vec!["tokens".to_string()]]
+        );
+
+        for excluded_doc in ["validate the session token", "validate the session tokens"] {
+            assert!(contains_excluded_term(&tokenize(excluded_doc), &groups));
+        }
+        assert!(!contains_excluded_term(
+            &tokenize("validate the session"),
+            &groups
+        ));
+        assert!(!contains_excluded_term(
+            &tokenize("validate the session token"),
+            &[]
+        ));
+    }
+
+    #[test]
+    fn test_explain_term_scores_reports_tf_and_idf() {
+        let docs = vec!["token token order", "order list", "unrelated text"];
+        let params = RankingParams {
+            documents: &docs,
+            query: "token order",
+            pre_tokenized: None,
+            profile: &RankingProfile::default(),
+        };
+
+        let scores = explain_term_scores(&params);
+        assert_eq!(scores.len(), 3);
+
+        let terms: Vec<(&str, usize)> = scores[0]
+            .iter()
+            .map(|score| (score.term.as_str(), score.tf))
+            .collect();
+        assert_eq!(terms, vec![("order", 1), ("token", 2)]);
+        assert!(scores[2].iter().all(|score| score.tf == 0));
+
+        // The rarer term carries the higher idf
+        assert!(scores[0][1].idf > scores[0][0].idf);
+        assert_eq!(scores[0][1].idf, scores[1][1].idf);
+    }
+
     #[test]
     fn test_bm25_scoring_with_pre_tokenized() {
         // A trivial test: 2 docs, 1 query, with pre-tokenized content
@@ -867,6 +1380,7 @@ mod tests {
             documents: &docs,
             query,
             pre_tokenized: Some(&pre_tokenized),
+            profile: &RankingProfile::default(),
         };
 
         let results = rank_documents(&params);
@@ -894,6 +1408,7 @@ mod tests {
             documents: &docs,
             query,
             pre_tokenized: None,
+            profile: &RankingProfile::default(),
         };
 
         let results = rank_documents(&params);
@@ -1106,4 +1621,46 @@ mod tests {
         // The score should be the sum of individual scores
         assert_eq!(term_score, apple_score + banana_score);
     }
+
+    #[test]
+    fn test_profile_boosts() {
+        let file = "src/invoice_store.rs";
+        let code = "fn save(invoice: Invoice) {\n    write(invoice);\n}";
+        let queries = vec!["Invoice".to_string()];
+
+        let default = RankingProfile::default();
+        assert_eq!(
+            ProfileBoosts::new(&default, &queries).factor(file, code),
+            1.0
+        );
+
+        let profile = RankingProfile {
+            filename_boost: 2.0,
+            exact_match_boost: 3.0,
+            ..Default::default()
+        };
+        let boosts = ProfileBoosts::new(&profile, &queries);
+        assert_eq!(boosts.factor(file, code), 6.0);
+        assert_eq!(boosts.factor("src/ledger.rs", code), 3.0);
+        // Exact matches are case-sensitive
+        assert_eq!(
+            boosts.factor("src/ledger.rs", "fn save(invoice: u64) {}"),
+            1.0
+        );
+    }
+
+    #[test]
+    fn test_term_proximity() {
+        let terms: HashSet<String> = ["invoic", "store"].iter().map(|t| t.to_string()).collect();
+        let tokens = |text: &str| -> Vec<String> { text.split(' ').map(str::to_string).collect() };
+
+        assert_eq!(term_proximity(&tokens("invoic store"), &terms), 1.0);
+        assert_eq!(term_proximity(&tokens("invoic a b store"), &terms), 0.5);
+        // The shortest window counts, wherever it is
+        assert_eq!(
+            term_proximity(&tokens("invoic a b c store invoic"), &terms),
+            1.0
+        );
+        assert_eq!(term_proximity(&tokens("invoic a invoic"), &terms), 0.0);
+    }
 }
@@ -0,0 +1,208 @@
+//! Searching inside zip and jar archives.
+//!
+//! Text entries of an archive are searched like regular files under a virtual
+//! `archive.jar!path/in/archive.java` path. Entries are read into memory on demand; the
+//! archive is never unpacked on disk. Extraction is bounded per entry, per archive and by
+//! the number of entries, so a zip bomb cannot exhaust memory.
+
+use anyhow::{Context, Result};
+use probe_code::file_guard;
+use std::fs::File;
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+
+/// Separates the archive path from the entry path in a virtual path
+pub const ARCHIVE_ENTRY_SEPARATOR: char = '!';
+
+/// Maximum total uncompressed size of the entries searched in one archive
+pub const MAX_ARCHIVE_UNCOMPRESSED_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Maximum number of entries searched in one archive
+const MAX_ARCHIVE_ENTRIES: usize = 10_000;
+
+/// Extensions of the archives that can be searched
+const ARCHIVE_EXTENSIONS: &[&str] = &["zip", "jar"];
+
+/// Whether `path` is a zip or jar archive, judging by its extension
+pub fn is_archive(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ARCHIVE_EXTENSIONS.contains(&ext.to_lowercase().as_str()))
+}
+
+/// Virtual path of `entry` inside `archive`
+pub fn entry_path(archive: &Path, entry: &str) -> PathBuf {
+    PathBuf::from(format!(
+        "{}{ARCHIVE_ENTRY_SEPARATOR}{entry}",
+        archive.display()
+    ))
+}
+
+/// Split a virtual `archive!entry` path into the archive file and the entry name.
+/// Returns `None` for paths of regular files.
+pub fn split_entry_path(path: &Path) -> Option<(PathBuf, String)> {
+    let path_str = path.to_str()?;
+    path_str
+        .match_indices(ARCHIVE_ENTRY_SEPARATOR)
+        .map(|(index, _)| (Path::new(&path_str[..index]), &path_str[index + 1..]))
+        .find(|(archive, entry)| !entry.is_empty() && is_archive(archive) && archive.is_file())
+        .filter(|_| !path.exists())
+        .map(|(archive, entry)| (archive.to_path_buf(), entry.to_string()))
+}
+
+/// Virtual paths of the entries of `archives` worth searching as text: regular files that
+/// are not hard-denied (e.g. `.class`), fit the size limits and are accepted by `keep`,
+/// which is given the entry's path inside the archive. Unreadable archives are skipped.
+pub fn text_entries(archives: &[PathBuf], keep: impl Fn(&Path) -> bool) -> Vec<PathBuf> {
+    let debug_mode = std::env::var("DEBUG").unwrap_or_default() == "1";
+    let mut entries = Vec::new();
+    for archive in archives {
+        match archive_text_entries(archive, &keep) {
+            Ok(names) => entries.extend(names.iter().map(|name| entry_path(archive, name))),
+            Err(e) => {
+                if debug_mode {
+                    println!("DEBUG: Skipping unreadable archive {archive:?}: {e:?}");
+                }
+            }
+        }
+    }
+    entries
+}
+
+fn archive_text_entries(archive: &Path, keep: &impl Fn(&Path) -> bool) -> Result<Vec<String>> {
+    let mut zip = open_archive(archive)?;
+    let mut names = Vec::new();
+    let mut total_size = 0u64;
+    for index in 0..zip.len() {
+        // Encrypted or otherwise unsupported entries are skipped
+        let Ok(entry) = zip.by_index(index) else {
+            continue;
+        };
+        let Some(entry_name) = entry.enclosed_name() else {
+            continue;
+        };
+        if !entry.is_file()
+            || entry.size() > file_guard::MAX_SEARCHABLE_TEXT_FILE_SIZE_BYTES
+            || file_guard::is_hard_denied_path(&entry_name)
+            || !keep(&entry_name)
+        {
+            continue;
+        }
+
+        total_size += entry.size();
+        if total_size > MAX_ARCHIVE_UNCOMPRESSED_BYTES || names.len() == MAX_ARCHIVE_ENTRIES {
+            break;
+        }
+        names.push(entry.name().to_string());
+    }
+    Ok(names)
+}
+
+/// Read one entry of `archive` into memory. Fails if the entry is hard-denied or inflates
+/// beyond the searchable file size limit, whatever size the archive declares for it.
+pub fn read_entry(archive: &Path, entry: &str) -> Result<Vec<u8>> {
+    if file_guard::is_hard_denied_path(Path::new(entry)) {
+        anyhow::bail!(
+            "File extension is hard-denied for text search: {}",
+            entry_path(archive, entry).display()
+        );
+    }
+
+    let mut zip = open_archive(archive)?;
+    let file = zip
+        .by_name(entry)
+        .with_context(|| format!("Failed to read archive entry: {entry}"))?;
+    let limit = file_guard::MAX_SEARCHABLE_TEXT_FILE_SIZE_BYTES;
+    let mut bytes = Vec::new();
+    file.take(limit + 1)
+        .read_to_end(&mut bytes)
+        .with_context(|| format!("Failed to read archive entry: {entry}"))?;
+    if bytes.len() as u64 > limit {
+        anyhow::bail!("Archive entry too large: {entry} (limit: {limit} bytes)");
+    }
+    Ok(bytes)
+}
+
+/// Read a regular file, or the archive entry named by a virtual `archive!entry` path
+pub fn read_file(path: &Path) -> Result<Vec<u8>> {
+    match split_entry_path(path) {
+        Some((archive, entry)) => read_entry(&archive, &entry),
+        None => {
+            std::fs::read(path).with_context(|| format!("Failed to read file: {}", path.display()))
+        }
+    }
+}
+
+fn open_archive(archive: &Path) -> Result<zip::ZipArchive<BufReader<File>>> {
+    let resolved = file_guard::resolve_searchable_path(archive)?;
+    let file = File::open(&resolved)
+        .with_context(|| format!("Failed to open archive: {}", resolved.display()))?;
+    zip::ZipArchive::new(BufReader::new(file))
+        .with_context(|| format!("Failed to read archive: {}", resolved.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::TempDir;
+    use zip::write::SimpleFileOptions;
+
+    fn create_jar(path: &Path, entries: &[(&str, &[u8])]) {
+        let mut writer = zip::ZipWriter::new(File::create(path).unwrap());
+        let options =
+            SimpleFileOptions::default().compression_method(zip::CompressionMethod::Stored);
+        for (name, content) in entries {
+            writer.start_file(*name, options).unwrap();
+            writer.write_all(content).unwrap();
+        }
+        writer.finish().unwrap();
+    }
+
+    #[test]
+    fn test_text_entries_use_virtual_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let jar = temp_dir.path().join("lib-sources.jar");
+        create_jar(
+            &jar,
+            &[
+                ("com/acme/Ledger.java", b"class Ledger {}\n"),
+                ("com/acme/Ledger.class", b"\xca\xfe\xba\xbe"),
+                ("META-INF/MANIFEST.MF", b"Manifest-Version: 1.0\n"),
+            ],
+        );
+
+        let entries = text_entries(std::slice::from_ref(&jar), |entry| {
+            entry.extension().is_some_and(|ext| ext == "java")
+        });
+        assert_eq!(entries, vec![entry_path(&jar, "com/acme/Ledger.java")]);
+
+        let (archive, entry) = split_entry_path(&entries[0]).unwrap();
+        assert_eq!(archive, jar);
+        assert_eq!(entry, "com/acme/Ledger.java");
+        assert_eq!(read_file(&entries[0]).unwrap(), b"class Ledger {}\n");
+        assert!(read_entry(&jar, "com/acme/Ledger.class").is_err());
+    }
+
+    #[test]
+    fn test_regular_paths_are_not_archive_entries() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("notes!draft.txt");
+        std::fs::write(&file, "draft").unwrap();
+
+        assert!(split_entry_path(&file).is_none());
+        assert_eq!(read_file(&file).unwrap(), b"draft");
+    }
+
+    #[test]
+    fn test_oversized_entries_are_not_extracted() {
+        let temp_dir = TempDir::new().unwrap();
+        let zip = temp_dir.path().join("bomb.zip");
+        let huge = vec![b'a'; file_guard::MAX_SEARCHABLE_TEXT_FILE_SIZE_BYTES as usize + 1];
+        create_jar(&zip, &[("huge.txt", &huge), ("small.txt", b"small")]);
+
+        let entries = text_entries(std::slice::from_ref(&zip), |_| true);
+        assert_eq!(entries, vec![entry_path(&zip, "small.txt")]);
+        assert!(read_entry(&zip, "huge.txt").is_err());
+    }
+}
@@ -1,4 +1,8 @@
+use probe_code::language::factory::get_language_impl;
+use probe_code::language::get_or_parse_tree_pooled;
 use probe_code::models::SearchResult;
+use probe_code::search::archive;
+use probe_code::search::MergeThreshold;
 use std::collections::BTreeMap;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
@@ -131,9 +135,33 @@ pub fn deduplicate_contained_blocks(results: Vec<SearchResult>) -> Vec<SearchRes
 pub fn merge_ranked_blocks(
     results: Vec<SearchResult>,
     threshold: Option<usize>,
+) -> Vec<SearchResult> {
+    merge_ranked_blocks_with(results, threshold, true)
+}
+
+/// Like [`merge_ranked_blocks`], choosing whether merging respects symbol boundaries.
+///
+/// With `respect_boundaries`, blocks are only merged when they belong to the same top-level
+/// declaration of the file (as parsed by tree-sitter), however close they are; blank lines and
+/// comments between declarations are not boundaries. Files without a supported grammar fall
+/// back to merging by line distance alone.
+pub fn merge_ranked_blocks_with(
+    results: Vec<SearchResult>,
+    threshold: Option<usize>,
+    respect_boundaries: bool,
+) -> Vec<SearchResult> {
+    let threshold = threshold.map_or_else(MergeThreshold::default, MergeThreshold::Lines);
+    merge_ranked_blocks_with_threshold(results, threshold, respect_boundaries)
+}
+
+/// Like [`merge_ranked_blocks_with`], also accepting [`MergeThreshold::Auto`], which picks the
+/// threshold of each file from the density of its matched lines.
+pub fn merge_ranked_blocks_with_threshold(
+    results: Vec<SearchResult>,
+    merge_threshold: MergeThreshold,
+    respect_boundaries: bool,
 ) -> Vec<SearchResult> {
     let debug_mode = std::env::var("DEBUG").unwrap_or_default() == "1";
-    let threshold = threshold.unwrap_or(5); // Default to 5 lines if not specified
 
     if results.is_empty() {
         return results;
@@ -143,7 +171,7 @@ pub fn merge_ranked_blocks(
         println!(
             "DEBUG: Starting post-rank merging of {} results with threshold {}",
             results.len(),
-            threshold
+            merge_threshold
         );
     }
 
@@ -181,6 +209,23 @@ pub fn merge_ranked_blocks(
         // Sort blocks by start line for merging
         blocks.sort_by_key(|block| block.lines.0);
 
+        let threshold = match merge_threshold {
+            MergeThreshold::Lines(lines) => lines,
+            MergeThreshold::Auto => {
+                let threshold = auto_merge_threshold(&blocks);
+                if debug_mode {
+                    println!("DEBUG: Auto merge threshold for {file_path}: {threshold} lines");
+                }
+                threshold
+            }
+        };
+
+        let symbol_ranges = if respect_boundaries {
+            top_level_symbol_ranges(&file_path)
+        } else {
+            None
+        };
+
         // Sort blocks by start line
         blocks.sort_by_key(|block| block.lines.0);
 
@@ -212,7 +257,13 @@ pub fn merge_ranked_blocks(
                         continue;
                     }
 
-                    if should_merge_blocks(&current_block, next_block, threshold) {
+                    if should_merge_blocks(&current_block, next_block, threshold)
+                        && share_top_level_symbol(
+                            symbol_ranges.as_deref(),
+                            &current_block,
+                            next_block,
+                        )
+                    {
                         if debug_mode {
                             println!(
                                 "DEBUG: Merging blocks - current: {}-{}, next: {}-{}",
@@ -237,6 +288,17 @@ pub fn merge_ranked_blocks(
                             next_block.node_type.clone()
                         };
 
+                        // Keep the score breakdown of the higher-scoring block, whose score
+                        // the merged block takes over
+                        if next_block.score.unwrap_or(0.0) > current_block.score.unwrap_or(0.0) {
+                            current_block.score_breakdown = next_block.score_breakdown.clone();
+                        }
+
+                        // The merged block stands in for the collapsed duplicates of both
+                        if let Some(next_duplicates) = next_block.duplicates {
+                            *current_block.duplicates.get_or_insert(0) += next_duplicates;
+                        }
+
                         // Combine scores and term statistics
                         let merged_score = merge_scores(&current_block, next_block);
                         let merged_term_stats = merge_term_statistics(&current_block, next_block);
@@ -359,6 +421,87 @@ pub fn should_merge_blocks(block1: &SearchResult, block2: &SearchResult, thresho
     should_merge
 }
 
+/// Average gap between matched lines at which [`MergeThreshold::Auto`] picks the default
+/// threshold; denser files get a proportionally larger threshold and sparser ones a smaller one
+const AUTO_MERGE_REFERENCE_GAP: f64 = 20.0;
+
+/// Largest threshold [`MergeThreshold::Auto`] picks, however dense the matches
+const AUTO_MERGE_MAX_THRESHOLD: usize = 20;
+
+/// Merge threshold for the blocks of one file, inversely proportional to the average gap
+/// between their matched lines. Blocks without matched lines count with their first line.
+fn auto_merge_threshold(blocks: &[SearchResult]) -> usize {
+    let mut lines: Vec<usize> = blocks
+        .iter()
+        .flat_map(|block| match &block.matched_lines {
+            Some(matched) if !matched.is_empty() => {
+                matched.iter().map(|line| block.lines.0 + line).collect()
+            }
+            _ => vec![block.lines.0],
+        })
+        .collect();
+    lines.sort_unstable();
+    lines.dedup();
+
+    if lines.len() < 2 {
+        return MergeThreshold::DEFAULT_LINES;
+    }
+
+    let average_gap = (lines[lines.len() - 1] - lines[0]) as f64 / (lines.len() - 1) as f64;
+    let threshold = MergeThreshold::DEFAULT_LINES as f64 * AUTO_MERGE_REFERENCE_GAP / average_gap;
+    (threshold.round() as usize).clamp(1, AUTO_MERGE_MAX_THRESHOLD)
+}
+
+/// Line ranges (1-based, inclusive) of the top-level declarations of a file. Comments are
+/// skipped so they never separate a declaration from its doc comment.
+///
+/// Returns `None` when the file cannot be read or has no supported grammar.
+fn top_level_symbol_ranges(file: &str) -> Option<Vec<(usize, usize)>> {
+    let path = Path::new(file);
+    let extension = path.extension()?.to_str()?;
+    get_language_impl(extension)?;
+    let content = String::from_utf8_lossy(&archive::read_file(path).ok()?).into_owned();
+    // Same cache key as search file processing, so the tree is usually cached already
+    let tree =
+        get_or_parse_tree_pooled(&format!("{file}_{extension}"), &content, extension).ok()?;
+
+    let root = tree.root_node();
+    let mut cursor = root.walk();
+    let ranges = root
+        .named_children(&mut cursor)
+        .filter(|node| !node.kind().contains("comment"))
+        .map(|node| (node.start_position().row + 1, node.end_position().row + 1))
+        .collect();
+    Some(ranges)
+}
+
+/// Whether two blocks overlap a common top-level declaration. Blocks covering no declaration
+/// (only comments or blank lines) share one with any block, as do all blocks when the
+/// declarations are unknown.
+fn share_top_level_symbol(
+    symbol_ranges: Option<&[(usize, usize)]>,
+    block1: &SearchResult,
+    block2: &SearchResult,
+) -> bool {
+    let Some(symbol_ranges) = symbol_ranges else {
+        return true;
+    };
+    let overlapping = |block: &SearchResult| -> Vec<usize> {
+        symbol_ranges
+            .iter()
+            .enumerate()
+            .filter(|(_, &(start, end))| start <= block.lines.1 && block.lines.0 <= end)
+            .map(|(index, _)| index)
+            .collect()
+    };
+
+    let symbols1 = overlapping(block1);
+    let symbols2 = overlapping(block2);
+    symbols1.is_empty()
+        || symbols2.is_empty()
+        || symbols1.iter().any(|index| symbols2.contains(index))
+}
+
 /// Helper function to check if a node type represents a function-like construct
 fn is_function_like(node_type: &str) -> bool {
     node_type.contains("function")
@@ -706,6 +849,10 @@ mod tests {
             tokenized_content: None,
             lsp_info: None,
             parent_context: None,
+            score_breakdown: None,
+            term_matches: None,
+            duplicates: None,
+            root: None,
         }
     }
 
@@ -811,4 +958,70 @@ mod tests {
         let deduped = deduplicate_contained_blocks(results);
         assert_eq!(deduped.len(), 1);
     }
+
+    #[test]
+    fn test_auto_merge_threshold_follows_match_density() {
+        let with_matches = |start: usize, end: usize, matched: &[usize]| {
+            let mut block = make_result("example.rs", start, end, "function_item");
+            block.matched_lines = Some(matched.to_vec());
+            block
+        };
+
+        // Matches 2 lines apart on average merge aggressively
+        let dense = vec![with_matches(1, 6, &[0, 2, 4]), with_matches(8, 12, &[0, 2])];
+        assert_eq!(auto_merge_threshold(&dense), 20);
+
+        // Matches 20 lines apart get the default threshold
+        let regular = vec![with_matches(1, 5, &[0]), with_matches(21, 25, &[0])];
+        assert_eq!(
+            auto_merge_threshold(&regular),
+            MergeThreshold::DEFAULT_LINES
+        );
+
+        // Matches 100 lines apart stay granular
+        let sparse = vec![with_matches(1, 5, &[0]), with_matches(101, 105, &[0])];
+        assert_eq!(auto_merge_threshold(&sparse), 1);
+    }
+
+    #[test]
+    fn test_auto_merge_threshold_is_chosen_per_file() {
+        let results = vec![
+            make_result("dense.rs", 1, 3, "function_item"),
+            make_result("dense.rs", 5, 7, "function_item"),
+            make_result("dense.rs", 15, 17, "function_item"),
+            make_result("sparse.rs", 1, 3, "function_item"),
+            make_result("sparse.rs", 7, 9, "function_item"),
+            make_result("sparse.rs", 200, 202, "function_item"),
+        ];
+
+        let mut merged: Vec<(String, (usize, usize))> =
+            merge_ranked_blocks_with_threshold(results, MergeThreshold::Auto, false)
+                .into_iter()
+                .map(|block| (block.file, block.lines))
+                .collect();
+        merged.sort();
+
+        // dense.rs matches every 7 lines (threshold 14), sparse.rs every 99.5 (threshold 1)
+        assert_eq!(
+            merged,
+            vec![
+                ("dense.rs".to_string(), (1, 17)),
+                ("sparse.rs".to_string(), (1, 3)),
+                ("sparse.rs".to_string(), (7, 9)),
+                ("sparse.rs".to_string(), (200, 202)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_threshold_parsing() {
+        assert_eq!("auto".parse::<MergeThreshold>(), Ok(MergeThreshold::Auto));
+        assert_eq!("AUTO".parse::<MergeThreshold>(), Ok(MergeThreshold::Auto));
+        assert_eq!(
+            "12".parse::<MergeThreshold>(),
+            Ok(MergeThreshold::Lines(12))
+        );
+        assert!("dense".parse::<MergeThreshold>().is_err());
+        assert_eq!(MergeThreshold::Auto.to_string(), "auto");
+    }
 }
@@ -5,8 +5,9 @@ use std::collections::{HashMap, HashSet};
 use std::fs::{create_dir_all, File};
 use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
 
-use probe_code::models::SearchResult;
+use probe_code::models::{BlockRef, SearchResult};
 
 /// Generate a deterministic hash for a query string
 /// This is used to create a unique identifier for each query
@@ -122,6 +123,9 @@ impl SessionCache {
         // Validate and invalidate cache entries based on file MD5 changes
         cache.validate_and_invalidate_cache(debug_mode)?;
 
+        // Paging through a session only reads its cache; mark it as used so it does not expire
+        touch(&cache_path);
+
         Ok(cache)
     }
 
@@ -286,13 +290,96 @@ impl SessionCache {
 
     /// Get the path to the cache file
     pub fn get_cache_path(session_id: &str, query_hash: &str) -> PathBuf {
-        let home_dir = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
-        home_dir
-            .join(".cache")
-            .join("probe")
-            .join("sessions")
-            .join(format!("{session_id}_{query_hash}.json"))
+        sessions_dir().join(format!("{session_id}_{query_hash}.json"))
+    }
+}
+
+/// Directory holding the cache files of all sessions
+fn sessions_dir() -> PathBuf {
+    let home_dir = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+    home_dir.join(".cache").join("probe").join("sessions")
+}
+
+/// Set the modification time of `path` to now, ignoring failures
+fn touch(path: &Path) {
+    let _ = File::options()
+        .write(true)
+        .open(path)
+        .and_then(|file| file.set_modified(SystemTime::now()));
+}
+
+/// Reranker scores of the blocks reranked in a session, stored next to its cache files so that
+/// later searches in the same session reuse them across `probe` invocations. They expire with
+/// the session's other files.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SessionScores {
+    /// Score of each query and block pair, keyed by `"{query_hash:x}:{block_hash:x}"`
+    pub scores: HashMap<String, f32>,
+}
+
+impl SessionScores {
+    /// Path of the scores `model` gave in `session_id`
+    pub fn get_path(session_id: &str, model: &str) -> PathBuf {
+        sessions_dir().join(format!("{session_id}_rerank_{}.json", hash_query(model)))
+    }
+
+    /// Load the stored scores, or none if there are no readable ones
+    pub fn load(session_id: &str, model: &str) -> Self {
+        let path = Self::get_path(session_id, model);
+        let Ok(contents) = std::fs::read_to_string(&path) else {
+            return Self::default();
+        };
+        touch(&path);
+        serde_json::from_str(&contents).unwrap_or_default()
+    }
+
+    /// Store the scores for later searches in the session
+    pub fn save(&self, session_id: &str, model: &str) -> Result<()> {
+        let path = Self::get_path(session_id, model);
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string(self)?)?;
+        Ok(())
+    }
+}
+
+/// Remove session cache files that have not been loaded or written for longer than `ttl`
+/// Returns the number of removed files
+pub fn expire_sessions(ttl: Duration) -> Result<usize> {
+    expire_sessions_in(&sessions_dir(), ttl)
+}
+
+fn expire_sessions_in(dir: &Path, ttl: Duration) -> Result<usize> {
+    let debug_mode = std::env::var("PROBE_DEBUG").unwrap_or_default() == "1";
+    let entries = match std::fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(0),
+        Err(e) => return Err(e.into()),
+    };
+
+    let now = SystemTime::now();
+    let mut removed = 0;
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        let Ok(modified) = entry.metadata().and_then(|metadata| metadata.modified()) else {
+            continue;
+        };
+        // Files modified "in the future" (clock skew) are kept
+        if now.duration_since(modified).is_ok_and(|age| age > ttl)
+            && std::fs::remove_file(&path).is_ok()
+        {
+            if debug_mode {
+                println!("DEBUG: Removed expired session cache {path:?}");
+            }
+            removed += 1;
+        }
     }
+
+    Ok(removed)
 }
 /// Normalize a file path for consistent cache keys
 /// Removes leading "./" and ensures consistent format
@@ -314,12 +401,23 @@ pub fn generate_cache_key(result: &SearchResult) -> String {
     format!("{normalized_path}:{}-{}", result.lines.0, result.lines.1)
 }
 
-/// Filter search results using the cache without adding to the cache
+/// Parse a cache key of the form `file:start-end` back into a block reference
+fn parse_block_id(block_id: &str) -> Option<BlockRef> {
+    let (file, range) = block_id.split_once(':')?;
+    let (start, end) = range.split_once('-')?;
+    Some(BlockRef {
+        file: file.to_string(),
+        lines: (start.parse().ok()?, end.parse().ok()?),
+    })
+}
+
+/// Filter search results using the cache without adding to the cache. Returns the results
+/// that are not cached yet and the blocks of those that are.
 pub fn filter_results_with_cache(
     results: &[SearchResult],
     session_id: &str,
     query: &str,
-) -> Result<(Vec<SearchResult>, usize)> {
+) -> Result<(Vec<SearchResult>, Vec<BlockRef>)> {
     let query_hash = hash_query(query);
     let debug_mode = std::env::var("PROBE_DEBUG").unwrap_or_default() == "1";
 
@@ -333,7 +431,7 @@ pub fn filter_results_with_cache(
             println!("DEBUG: New session, not filtering results");
         }
         // Return all results with no skipped blocks
-        return Ok((results.to_vec(), 0));
+        return Ok((results.to_vec(), Vec::new()));
     }
 
     // Load the cache
@@ -344,7 +442,7 @@ pub fn filter_results_with_cache(
         if debug_mode {
             println!("DEBUG: Cache is empty, not filtering results");
         }
-        return Ok((results.to_vec(), 0));
+        return Ok((results.to_vec(), Vec::new()));
     }
 
     if debug_mode {
@@ -355,8 +453,8 @@ pub fn filter_results_with_cache(
         );
     }
 
-    // Count of skipped blocks
-    let mut skipped_count = 0;
+    // Blocks of the skipped results
+    let mut skipped_blocks = Vec::new();
 
     // For existing sessions, filter the results
     let filtered_results: Vec<SearchResult> = results
@@ -366,10 +464,13 @@ pub fn filter_results_with_cache(
             let is_cached = cache.is_cached(&cache_key);
 
             if is_cached {
-                if debug_mode && skipped_count < 5 {
+                if debug_mode && skipped_blocks.len() < 5 {
                     println!("DEBUG: Skipping cached block: {cache_key}");
                 }
-                skipped_count += 1;
+                skipped_blocks.push(BlockRef {
+                    file: result.file.clone(),
+                    lines: result.lines,
+                });
                 false
             } else {
                 true
@@ -381,21 +482,23 @@ pub fn filter_results_with_cache(
     if debug_mode {
         println!(
             "DEBUG: Filtered out {} cached blocks, returning {} results",
-            skipped_count,
+            skipped_blocks.len(),
             filtered_results.len()
         );
     }
 
-    Ok((filtered_results, skipped_count))
+    Ok((filtered_results, skipped_blocks))
 }
 
 /// Filter matched lines using the cache to skip already cached blocks
 /// This is applied early in the search process, right after ripgrep results
+///
+/// Returns the number of skipped lines and the cached blocks that contained them.
 pub fn filter_matched_lines_with_cache(
     file_term_map: &mut HashMap<PathBuf, HashMap<usize, HashSet<usize>>>,
     session_id: &str,
     query: &str,
-) -> Result<usize> {
+) -> Result<(usize, Vec<BlockRef>)> {
     let query_hash = hash_query(query);
     let debug_mode = std::env::var("PROBE_DEBUG").unwrap_or_default() == "1";
 
@@ -408,7 +511,7 @@ pub fn filter_matched_lines_with_cache(
         if debug_mode {
             println!("DEBUG: New session, not filtering matched lines");
         }
-        return Ok(0);
+        return Ok((0, Vec::new()));
     }
 
     // Load the cache
@@ -419,7 +522,7 @@ pub fn filter_matched_lines_with_cache(
         if debug_mode {
             println!("DEBUG: Cache is empty, not filtering matched lines");
         }
-        return Ok(0);
+        return Ok((0, Vec::new()));
     }
 
     if debug_mode {
@@ -432,6 +535,12 @@ pub fn filter_matched_lines_with_cache(
     // Count of skipped lines
     let mut skipped_count = 0;
     let mut files_to_remove = Vec::new();
+    let cached_blocks: Vec<(&String, BlockRef)> = cache
+        .block_identifiers
+        .iter()
+        .filter_map(|block_id| parse_block_id(block_id).map(|block| (block_id, block)))
+        .collect();
+    let mut suppressing_ids: HashSet<&String> = HashSet::new();
 
     // For each file in the map
     for (file_path, term_map) in file_term_map.iter_mut() {
@@ -462,34 +571,15 @@ pub fn filter_matched_lines_with_cache(
             let normalized_path = normalize_path(&path_str);
             let line_cache_key = format!("{normalized_path}:{line_num}");
 
-            // Check if this line is part of a cached block
-            let is_cached = cache.block_identifiers.iter().any(|block_id| {
-                // Parse the block ID to get file and line range
-                if let Some(colon_pos) = block_id.find(':') {
-                    if let Some(dash_pos) = block_id[colon_pos + 1..].find('-') {
-                        let file_part = &block_id[..colon_pos];
-                        let start_line_str = &block_id[colon_pos + 1..colon_pos + 1 + dash_pos];
-                        let end_line_str = &block_id[colon_pos + 1 + dash_pos + 1..];
-
-                        if let (Ok(start_line), Ok(end_line)) = (
-                            start_line_str.parse::<usize>(),
-                            end_line_str.parse::<usize>(),
-                        ) {
-                            // Check if this line is within a cached block from the same file
-                            let path_str = file_path.to_string_lossy();
-                            let normalized_path = normalize_path(&path_str);
-                            let normalized_file_part = normalize_path(file_part);
-
-                            return normalized_file_part == normalized_path
-                                && line_num >= start_line
-                                && line_num <= end_line;
-                        }
-                    }
-                }
-                false
+            // Check if this line is within a cached block from the same file
+            let containing_block = cached_blocks.iter().find(|(_, block)| {
+                normalize_path(&block.file) == normalized_path
+                    && line_num >= block.lines.0
+                    && line_num <= block.lines.1
             });
 
-            if is_cached {
+            if let Some((block_id, _)) = containing_block {
+                suppressing_ids.insert(*block_id);
                 if debug_mode && skipped_count < 5 {
                     println!("DEBUG: Skipping cached line: {line_cache_key}");
                 }
@@ -537,7 +627,14 @@ pub fn filter_matched_lines_with_cache(
         );
     }
 
-    Ok(skipped_count)
+    let mut suppressing_blocks: Vec<BlockRef> = cached_blocks
+        .into_iter()
+        .filter(|(block_id, _)| suppressing_ids.contains(*block_id))
+        .map(|(_, block)| block)
+        .collect();
+    suppressing_blocks.sort();
+
+    Ok((skipped_count, suppressing_blocks))
 }
 
 /// Add search results to the cache
@@ -735,6 +832,10 @@ mod tests {
             tokenized_content: None,
             lsp_info: None,
             parent_context: None,
+            score_breakdown: None,
+            term_matches: None,
+            duplicates: None,
+            root: None,
         };
 
         let result2 = SearchResult {
@@ -765,6 +866,10 @@ mod tests {
             tokenized_content: None,
             lsp_info: None,
             parent_context: None,
+            score_breakdown: None,
+            term_matches: None,
+            duplicates: None,
+            root: None,
         };
 
         // Generate cache keys for both results
@@ -776,6 +881,64 @@ mod tests {
         assert_eq!(key1, "path/to/file.rs:10-20");
     }
 
+    #[test]
+    fn test_expire_sessions_removes_stale_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let stale = dir.path().join("abcd_1.json");
+        let fresh = dir.path().join("abcd_2.json");
+        let other = dir.path().join("notes.txt");
+        for path in [&stale, &fresh, &other] {
+            File::create(path).unwrap();
+        }
+        let an_hour_ago = SystemTime::now() - Duration::from_secs(3600);
+        for path in [&stale, &other] {
+            File::options()
+                .write(true)
+                .open(path)
+                .unwrap()
+                .set_modified(an_hour_ago)
+                .unwrap();
+        }
+
+        let removed = expire_sessions_in(dir.path(), Duration::from_secs(60)).unwrap();
+
+        assert_eq!(removed, 1);
+        assert!(!stale.exists());
+        assert!(fresh.exists());
+        assert!(other.exists());
+    }
+
+    #[test]
+    fn test_touched_sessions_do_not_expire() {
+        let dir = tempfile::tempdir().unwrap();
+        let session = dir.path().join("abcd_1.json");
+        File::create(&session)
+            .unwrap()
+            .set_modified(SystemTime::now() - Duration::from_secs(3600))
+            .unwrap();
+
+        touch(&session);
+
+        assert_eq!(
+            expire_sessions_in(dir.path(), Duration::from_secs(60)).unwrap(),
+            0
+        );
+        assert!(session.exists());
+    }
+
+    #[test]
+    fn test_parse_block_id() {
+        assert_eq!(
+            parse_block_id("path/to/file.rs:10-20"),
+            Some(BlockRef {
+                file: "path/to/file.rs".to_string(),
+                lines: (10, 20),
+            })
+        );
+        assert_eq!(parse_block_id("path/to/file.rs:10"), None);
+        assert_eq!(parse_block_id("path/to/file.rs"), None);
+    }
+
     #[test]
     fn test_session_cache_with_query_hash() {
         // Test that different queries for the same session have different cache paths
@@ -0,0 +1,113 @@
+//! Restricting a search to the files changed in git since a ref, used with `changed_since`.
+//!
+//! The changed files are computed once with the `git` CLI and intersected with every file
+//! walk, so unchanged files are never read or tokenized. Changes are taken relative to the
+//! merge base of the ref and `HEAD`, the way a pull request diff is, and include staged,
+//! unstaged and untracked files. Deleted files have nothing left to search and are skipped.
+
+use anyhow::{bail, Context, Result};
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// The files changed since a git ref, matched against paths found by walking a search root
+#[derive(Debug)]
+pub struct ChangedFiles {
+    /// Search root as given, the prefix of every walked path
+    root: PathBuf,
+    /// Canonical form of `root`
+    canonical_root: PathBuf,
+    /// Absolute paths of the changed files
+    files: HashSet<PathBuf>,
+}
+
+impl ChangedFiles {
+    /// Collect the files changed since `git_ref` in the repository containing `root`
+    pub fn since(root: &Path, git_ref: &str) -> Result<Self> {
+        let canonical_root = root
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve search path: {}", root.display()))?;
+        let dir = if canonical_root.is_dir() {
+            canonical_root.as_path()
+        } else {
+            canonical_root.parent().unwrap_or(&canonical_root)
+        };
+
+        let Ok(toplevel) = git(dir, &["rev-parse", "--show-toplevel"]) else {
+            bail!(
+                "Cannot find files changed since '{git_ref}': {} is not in a git repository",
+                root.display()
+            );
+        };
+        let toplevel = PathBuf::from(toplevel.trim_end())
+            .canonicalize()
+            .context("Failed to resolve git repository root")?;
+
+        let merge_base = git(dir, &["merge-base", git_ref, "HEAD"])
+            .with_context(|| format!("Cannot find files changed since '{git_ref}'"))?;
+        // Both commands print paths relative to the repository root when run from it
+        let changed = git(
+            &toplevel,
+            &[
+                "diff",
+                "--name-only",
+                "-z",
+                "--diff-filter=d",
+                merge_base.trim_end(),
+            ],
+        )?;
+        let untracked = git(
+            &toplevel,
+            &["ls-files", "--others", "--exclude-standard", "-z"],
+        )?;
+
+        let files = changed
+            .split('\0')
+            .chain(untracked.split('\0'))
+            .filter(|path| !path.is_empty())
+            .map(|path| toplevel.join(path))
+            .collect();
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            canonical_root,
+            files,
+        })
+    }
+
+    /// Number of changed files in the repository, including those outside the search root
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Returns true if no file has changed
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Returns true if `file`, a path found by walking the search root, has changed
+    pub fn contains(&self, file: &Path) -> bool {
+        match file.strip_prefix(&self.root) {
+            Ok(relative) => self.files.contains(&self.canonical_root.join(relative)),
+            Err(_) => self.files.contains(file),
+        }
+    }
+}
+
+/// Run git in `dir` and return its standard output
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
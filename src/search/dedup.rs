@@ -0,0 +1,172 @@
+//! Collapsing duplicate blocks in ranked search results.
+//!
+//! Vendored, generated and copy-pasted code repeats the same block across many files, which
+//! crowds distinct matches out of the results. [`DedupMode::Exact`] collapses byte-identical
+//! blocks; [`DedupMode::Near`] also collapses blocks whose token shingles are mostly shared,
+//! estimated with MinHash signatures over the tokens from [`tokenize`]. Results are expected
+//! in ranked order, so the block kept for each group is its best-ranked instance.
+
+use probe_code::models::SearchResult;
+use probe_code::ranking::tokenize;
+use probe_code::search::search_options::DedupMode;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+
+/// Number of consecutive tokens in a shingle
+const SHINGLE_SIZE: usize = 3;
+
+/// Number of locality-sensitive hashing bands a signature is split into
+const BANDS: usize = 16;
+
+/// Number of MinHash values in each band
+const ROWS_PER_BAND: usize = 4;
+
+/// Number of MinHash values in a signature
+const SIGNATURE_LEN: usize = BANDS * ROWS_PER_BAND;
+
+/// Estimated shingle similarity at or above which two blocks are near-duplicates
+const NEAR_DUPLICATE_SIMILARITY: f64 = 0.8;
+
+/// Remove duplicate blocks from ranked `results`, keeping the first block of each group and
+/// recording how many were collapsed into it in `duplicates` (`Some(0)` for unique blocks).
+/// Returns the number of blocks removed; `DedupMode::None` leaves the results untouched.
+pub fn dedup_results(results: &mut Vec<SearchResult>, mode: DedupMode) -> usize {
+    if mode == DedupMode::None {
+        return 0;
+    }
+
+    let before = results.len();
+    let seeds: Vec<u64> = (0..SIGNATURE_LEN as u64).map(splitmix64).collect();
+    let mut kept: Vec<SearchResult> = Vec::with_capacity(before);
+    let mut signatures: Vec<Option<Vec<u64>>> = Vec::new();
+    let mut by_code: HashMap<u64, Vec<usize>> = HashMap::new();
+    let mut by_band: HashMap<(usize, u64), Vec<usize>> = HashMap::new();
+
+    for mut result in std::mem::take(results) {
+        let code_hash = hash_of(&result.code);
+        let identical = by_code.get(&code_hash).and_then(|candidates| {
+            candidates
+                .iter()
+                .copied()
+                .find(|&index| kept[index].code == result.code)
+        });
+        let signature = match (identical, mode) {
+            (None, DedupMode::Near) => minhash_signature(&result.code, &seeds),
+            _ => None,
+        };
+        let representative = identical.or_else(|| {
+            let signature = signature.as_ref()?;
+            let mut candidates: Vec<usize> = band_keys(signature)
+                .filter_map(|key| by_band.get(&key))
+                .flatten()
+                .copied()
+                .collect();
+            // Prefer the best-ranked matching group
+            candidates.sort_unstable();
+            candidates.dedup();
+            candidates.into_iter().find(|&index| {
+                signatures[index].as_ref().is_some_and(|kept_signature| {
+                    similarity(signature, kept_signature) >= NEAR_DUPLICATE_SIMILARITY
+                })
+            })
+        });
+
+        match representative {
+            Some(index) => *kept[index].duplicates.get_or_insert(0) += 1,
+            None => {
+                let index = kept.len();
+                by_code.entry(code_hash).or_default().push(index);
+                if let Some(signature) = &signature {
+                    for key in band_keys(signature) {
+                        by_band.entry(key).or_default().push(index);
+                    }
+                }
+                signatures.push(signature);
+                result.duplicates = Some(0);
+                kept.push(result);
+            }
+        }
+    }
+
+    *results = kept;
+    before - results.len()
+}
+
+/// MinHash signature of the token shingles of `code`, or `None` if it has no tokens
+fn minhash_signature(code: &str, seeds: &[u64]) -> Option<Vec<u64>> {
+    let tokens = tokenize(code);
+    if tokens.is_empty() {
+        return None;
+    }
+
+    let mut signature = vec![u64::MAX; seeds.len()];
+    for shingle in tokens.windows(SHINGLE_SIZE.min(tokens.len())) {
+        let shingle_hash = hash_of(shingle);
+        for (slot, seed) in signature.iter_mut().zip(seeds) {
+            *slot = (*slot).min(splitmix64(shingle_hash ^ seed));
+        }
+    }
+    Some(signature)
+}
+
+/// Bucket keys of a signature: one per band, so similar signatures share at least one
+fn band_keys(signature: &[u64]) -> impl Iterator<Item = (usize, u64)> + '_ {
+    signature
+        .chunks(ROWS_PER_BAND)
+        .enumerate()
+        .map(|(band, rows)| (band, hash_of(rows)))
+}
+
+/// Estimated Jaccard similarity of the shingle sets behind two signatures
+fn similarity(a: &[u64], b: &[u64]) -> f64 {
+    let equal = a.iter().zip(b).filter(|(x, y)| x == y).count();
+    equal as f64 / a.len() as f64
+}
+
+fn hash_of<T: Hash + ?Sized>(value: &T) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// SplitMix64 finalizer, used to derive independent hash functions from one base hash
+fn splitmix64(value: u64) -> u64 {
+    let mut z = value.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn signature(code: &str) -> Vec<u64> {
+        let seeds: Vec<u64> = (0..SIGNATURE_LEN as u64).map(splitmix64).collect();
+        minhash_signature(code, &seeds).expect("code has tokens")
+    }
+
+    fn ledger_block(name: &str) -> String {
+        let entries: String = (100..160)
+            .map(|i| format!("    let entry_{i} = {i};\n"))
+            .collect();
+        format!("fn settle_{name}() {{\n{entries}}}\n")
+    }
+
+    #[test]
+    fn test_similarity_separates_near_duplicates() {
+        let original = ledger_block("invoices");
+        let renamed = ledger_block("payments");
+        let unrelated = "fn render_chart(canvas: &mut Canvas, series: &[Point]) {\n    let scale = axis_scale(series);\n    canvas.draw_polyline(series, scale);\n}";
+
+        assert_eq!(
+            similarity(&signature(&original), &signature(&original)),
+            1.0
+        );
+        assert!(
+            similarity(&signature(&original), &signature(&renamed)) >= NEAR_DUPLICATE_SIMILARITY
+        );
+        assert!(similarity(&signature(&original), &signature(unrelated)) < 0.2);
+    }
+}
@@ -1,7 +1,8 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use ignore::WalkBuilder;
 use lazy_static::lazy_static;
 use probe_code::file_guard;
+use probe_code::search::archive;
 use probe_code::search::tokenization;
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
@@ -13,6 +14,8 @@ use std::time::Instant;
 pub struct FileList {
     /// The list of files in the directory (respecting ignore patterns)
     pub files: Vec<PathBuf>,
+    /// Zip and jar archives found by the walk; their entries are only searched on request
+    pub archives: Vec<PathBuf>,
     /// When this cache was created
     #[allow(dead_code)]
     pub created_at: Instant,
@@ -35,12 +38,56 @@ fn format_duration(duration: std::time::Duration) -> String {
     }
 }
 
+/// Read ignore patterns from a file in gitignore syntax: one glob per line, blank lines and
+/// `#` comments skipped. Negated `!` patterns cannot re-include files excluded by another
+/// source, so they are rejected.
+pub fn read_ignore_file(path: &Path) -> Result<Vec<String>> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("Failed to read ignore file: {}", path.display()))?;
+    let mut patterns = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let pattern = line.trim();
+        if pattern.is_empty() || pattern.starts_with('#') {
+            continue;
+        }
+        if pattern.starts_with('!') {
+            anyhow::bail!(
+                "Negated pattern {pattern:?} is not supported in ignore file {}:{}",
+                path.display(),
+                index + 1
+            );
+        }
+        patterns.push(pattern.to_string());
+    }
+    Ok(patterns)
+}
+
+/// All ignore patterns of a search: the `search.ignore` config list, then the patterns of each
+/// `--ignore-file`, then `--ignore` patterns. The sources are additive, a file excluded by any
+/// of them is skipped; repeated patterns are kept once.
+pub fn combine_ignore_patterns(
+    config_ignores: &[String],
+    ignore_files: &[PathBuf],
+    cli_ignores: &[String],
+) -> Result<Vec<String>> {
+    let mut patterns = config_ignores.to_vec();
+    for ignore_file in ignore_files {
+        patterns.extend(read_ignore_file(ignore_file)?);
+    }
+    patterns.extend(cli_ignores.iter().cloned());
+
+    let mut seen = HashSet::new();
+    patterns.retain(|pattern| seen.insert(pattern.clone()));
+    Ok(patterns)
+}
+
 /// Generate a cache key for a specific directory and options
 fn generate_cache_key(
     path: &Path,
     allow_tests: bool,
     custom_ignores: &[String],
     no_gitignore: bool,
+    follow_symlinks: bool,
 ) -> String {
     // Create a unique identifier for this cache based on the path and options
     let path_str = path.to_string_lossy();
@@ -70,7 +117,13 @@ fn generate_cache_key(
         format!("ignores_{hash:x}")
     };
 
-    format!("{path_str}_{allow_tests_str}_{ignores_hash}_{gitignore_str}")
+    let symlinks_str = if follow_symlinks {
+        "follow_symlinks"
+    } else {
+        "no_symlinks"
+    };
+
+    format!("{path_str}_{allow_tests_str}_{ignores_hash}_{gitignore_str}_{symlinks_str}")
 }
 
 /// Get a list of files in a directory, respecting ignore patterns and test file exclusions.
@@ -80,6 +133,7 @@ pub fn get_file_list(
     allow_tests: bool,
     custom_ignores: &[String],
     no_gitignore: bool,
+    follow_symlinks: bool,
 ) -> Result<Arc<FileList>> {
     let debug_mode = std::env::var("PROBE_DEBUG").unwrap_or_default() == "1";
     let start_time = Instant::now();
@@ -89,10 +143,17 @@ pub fn get_file_list(
         println!("DEBUG: allow_tests: {allow_tests}");
         println!("DEBUG: custom_ignores: {custom_ignores:?}");
         println!("DEBUG: no_gitignore: {no_gitignore}");
+        println!("DEBUG: follow_symlinks: {follow_symlinks}");
     }
 
     // Create a cache key for this request
-    let cache_key = generate_cache_key(path, allow_tests, custom_ignores, no_gitignore);
+    let cache_key = generate_cache_key(
+        path,
+        allow_tests,
+        custom_ignores,
+        no_gitignore,
+        follow_symlinks,
+    );
 
     // Check if we have this file list in the cache
     {
@@ -115,7 +176,13 @@ pub fn get_file_list(
         println!("DEBUG: File list not found in cache, building new list");
     }
 
-    let file_list = build_file_list(path, allow_tests, custom_ignores, no_gitignore)?;
+    let file_list = build_file_list(
+        path,
+        allow_tests,
+        custom_ignores,
+        no_gitignore,
+        follow_symlinks,
+    )?;
     let file_count = file_list.files.len();
 
     // Cache the file list
@@ -137,12 +204,43 @@ pub fn get_file_list(
     Ok(file_list)
 }
 
+/// Whether a walk error is a symlink loop, which the walker reports instead of descending
+pub fn is_symlink_loop(err: &ignore::Error) -> bool {
+    match err {
+        ignore::Error::Loop { .. } => true,
+        ignore::Error::WithPath { err, .. }
+        | ignore::Error::WithDepth { err, .. }
+        | ignore::Error::WithLineNumber { err, .. } => is_symlink_loop(err),
+        _ => false,
+    }
+}
+
+/// The path to list for a walked file. Reading refuses symlinks, so with `follow_symlinks`
+/// a symlinked file is listed under its real path; `None` if that is dangling.
+pub fn walked_file_path(entry: &ignore::DirEntry, follow_symlinks: bool) -> Option<PathBuf> {
+    if follow_symlinks && entry.path_is_symlink() {
+        std::fs::canonicalize(entry.path()).ok()
+    } else {
+        Some(entry.path().to_path_buf())
+    }
+}
+
+/// Keep only the first of several paths leading to the same real file, e.g. through two
+/// symlinked directories, so that each file is searched once.
+pub fn dedup_real_paths(files: &mut Vec<PathBuf>) {
+    let mut real_paths = HashSet::new();
+    files.retain(|file| {
+        real_paths.insert(std::fs::canonicalize(file).unwrap_or_else(|_| file.clone()))
+    });
+}
+
 /// Build a list of files in a directory, respecting ignore patterns and test file exclusions.
 fn build_file_list(
     path: &Path,
     allow_tests: bool,
     custom_ignores: &[String],
     no_gitignore: bool,
+    follow_symlinks: bool,
 ) -> Result<FileList> {
     let debug_mode = std::env::var("PROBE_DEBUG").unwrap_or_default() == "1";
     let start_time = Instant::now();
@@ -156,8 +254,9 @@ fn build_file_list(
     let mut builder = WalkBuilder::new(path);
 
     // Follow symlinks so that symlinked subdirectories are searched (#532).
-    // The `walkdir` crate (used by `ignore`) has built-in loop detection,
+    // The walker detects symlink loops and reports them instead of descending,
     // and `same_file_system(true)` below prevents crossing mount points.
+    // `follow_symlinks` only decides whether symlinked files are read under their real path.
     builder.follow_links(true);
 
     // Stay on the same file system to avoid traversing mount points
@@ -217,10 +316,8 @@ fn build_file_list(
         "*.exe",
         "*.out",
         "*.app",
-        "*.jar",
         "*.war",
         "*.ear",
-        "*.zip",
         "*.tar.gz",
         "*.rar",
         "*.log",
@@ -237,7 +334,12 @@ fn build_file_list(
     .into_iter()
     .map(String::from)
     .collect();
-    common_ignores.extend(file_guard::hard_deny_globs());
+    // Archives are walked but listed apart from the searchable files, see below
+    common_ignores.extend(
+        file_guard::hard_deny_globs()
+            .into_iter()
+            .filter(|glob| !archive::is_archive(Path::new(glob))),
+    );
 
     // Add test file patterns if allow_tests is false
     if !allow_tests {
@@ -318,12 +420,19 @@ fn build_file_list(
     // Collect files
     let walk_start = Instant::now();
     let mut files = Vec::new();
+    let mut archives = Vec::new();
     let mut total_files = 0;
 
     for result in builder.build() {
         total_files += 1;
         let entry = match result {
             Ok(entry) => entry,
+            Err(err) if is_symlink_loop(&err) => {
+                if debug_mode {
+                    println!("DEBUG: Skipping symlink loop: {err}");
+                }
+                continue;
+            }
             Err(err) => {
                 eprintln!("Error walking directory: {err}");
                 continue;
@@ -335,7 +444,7 @@ fn build_file_list(
             continue;
         }
 
-        // Extra defensive check: skip symlinks even though we configured the walker not to follow them
+        // Extra defensive check: skip symlinks the walker did not resolve
         if entry.file_type().is_some_and(|ft| ft.is_symlink()) {
             if debug_mode {
                 println!("DEBUG: Skipping symlink file: {:?}", entry.path());
@@ -350,6 +459,11 @@ fn build_file_list(
             continue;
         }
 
+        if archive::is_archive(entry.path()) {
+            archives.push(entry.path().to_path_buf());
+            continue;
+        }
+
         if file_guard::is_hard_denied_path(entry.path()) {
             if debug_mode {
                 println!("DEBUG: Skipping hard-denied file: {:?}", entry.path());
@@ -357,12 +471,18 @@ fn build_file_list(
             continue;
         }
 
-        files.push(entry.path().to_path_buf());
+        if let Some(file) = walked_file_path(&entry, follow_symlinks) {
+            files.push(file);
+        }
     }
 
     // Sort files for deterministic ordering to fix non-deterministic behavior
     // This ensures that file discovery is consistent across runs
     files.sort();
+    if follow_symlinks {
+        dedup_real_paths(&mut files);
+    }
+    archives.sort();
 
     let walk_duration = walk_start.elapsed();
 
@@ -386,6 +506,7 @@ fn build_file_list(
 
     Ok(FileList {
         files,
+        archives,
         created_at: Instant::now(),
     })
 }
@@ -428,6 +549,29 @@ fn is_test_path(search_root: &Path, file_path: &Path) -> bool {
         || file_name.ends_with("Test.php")
 }
 
+/// Whether an entry of an archive passes the test and language filters applied to the
+/// walked files. `entry` is the entry's path inside the archive.
+pub fn keeps_archive_entry(entry: &Path, allow_tests: bool, language: Option<&str>) -> bool {
+    if !allow_tests && is_test_path(Path::new(""), entry) {
+        return false;
+    }
+    language.is_none_or(|language| {
+        let extensions = get_language_extensions(language);
+        extensions.is_empty() || has_language_extension(entry, &extensions)
+    })
+}
+
+/// Whether `file` has one of `extensions` (given with their leading dot)
+fn has_language_extension(file: &Path, extensions: &[String]) -> bool {
+    if let Some(ext) = file.extension() {
+        let ext_lossy = ext.to_string_lossy();
+        let ext_str = format!(".{ext_lossy}");
+        extensions.iter().any(|e| e == &ext_str)
+    } else {
+        false
+    }
+}
+
 fn is_test_dir_name(name: &str) -> bool {
     matches!(
         name,
@@ -447,6 +591,7 @@ pub fn find_matching_filenames(
     term_indices: &HashMap<String, usize>,
     language: Option<&str>,
     no_gitignore: bool,
+    follow_symlinks: bool,
 ) -> Result<HashMap<PathBuf, HashSet<usize>>> {
     let debug_mode = std::env::var("PROBE_DEBUG").unwrap_or_default() == "1";
     let start_time = Instant::now();
@@ -462,8 +607,14 @@ pub fn find_matching_filenames(
     }
 
     // Get the cached file list, with language filtering if specified
-    let file_list =
-        get_file_list_by_language(path, allow_tests, custom_ignores, language, no_gitignore)?;
+    let file_list = get_file_list_by_language(
+        path,
+        allow_tests,
+        custom_ignores,
+        language,
+        no_gitignore,
+        follow_symlinks,
+    )?;
 
     if debug_mode {
         println!(
@@ -567,6 +718,7 @@ fn get_language_extensions(language: &str) -> Vec<String> {
             ".h".to_string(),
         ],
         "java" => vec![".java".to_string()],
+        "kotlin" => vec![".kt".to_string(), ".kts".to_string()],
         "ruby" => vec![".rb".to_string(), ".rake".to_string()],
         "php" => vec![".php".to_string()],
         "swift" => vec![".swift".to_string()],
@@ -587,10 +739,17 @@ pub fn get_file_list_by_language(
     custom_ignores: &[String],
     language: Option<&str>,
     no_gitignore: bool,
+    follow_symlinks: bool,
 ) -> Result<Arc<FileList>> {
     // If no language is specified, use the regular get_file_list function
     if language.is_none() {
-        return get_file_list(path, allow_tests, custom_ignores, no_gitignore);
+        return get_file_list(
+            path,
+            allow_tests,
+            custom_ignores,
+            no_gitignore,
+            follow_symlinks,
+        );
     }
 
     let debug_mode = std::env::var("PROBE_DEBUG").unwrap_or_default() == "1";
@@ -601,7 +760,13 @@ pub fn get_file_list_by_language(
     }
 
     // Get the full file list first
-    let full_file_list = get_file_list(path, allow_tests, custom_ignores, no_gitignore)?;
+    let full_file_list = get_file_list(
+        path,
+        allow_tests,
+        custom_ignores,
+        no_gitignore,
+        follow_symlinks,
+    )?;
 
     // Get the extensions for the specified language
     let extensions = get_language_extensions(language.unwrap());
@@ -618,15 +783,7 @@ pub fn get_file_list_by_language(
         full_file_list
             .files
             .iter()
-            .filter(|file| {
-                if let Some(ext) = file.extension() {
-                    let ext_lossy = ext.to_string_lossy();
-                    let ext_str = format!(".{ext_lossy}");
-                    extensions.iter().any(|e| e == &ext_str)
-                } else {
-                    false
-                }
-            })
+            .filter(|file| has_language_extension(file, &extensions))
             .cloned()
             .collect()
     };
@@ -644,6 +801,7 @@ pub fn get_file_list_by_language(
     // Create a new FileList with the filtered files
     Ok(Arc::new(FileList {
         files: filtered_files,
+        archives: full_file_list.archives.clone(),
         created_at: Instant::now(),
     }))
 }
@@ -677,6 +835,7 @@ mod tests {
             &term_indices,
             None,
             false,
+            false,
         )
         .unwrap();
 
@@ -696,7 +855,7 @@ mod tests {
         fs::write(&source_file, "fn main() {}").unwrap();
         fs::write(&binary_file, "not really an image").unwrap();
 
-        let file_list = build_file_list(root, true, &[], false).unwrap();
+        let file_list = build_file_list(root, true, &[], false, false).unwrap();
 
         assert!(file_list.files.iter().any(|f| f == &source_file));
         assert!(
@@ -705,6 +864,42 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_file_list_lists_archives_separately() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        let source_file = root.join("main.rs");
+        let jar_file = root.join("lib-sources.jar");
+        let zip_file = root.join("bundle.zip");
+        fs::write(&source_file, "fn main() {}").unwrap();
+        fs::write(&jar_file, "jar").unwrap();
+        fs::write(&zip_file, "zip").unwrap();
+
+        let file_list = build_file_list(root, true, &[], false, false).unwrap();
+
+        assert_eq!(file_list.files, vec![source_file]);
+        assert_eq!(file_list.archives, vec![zip_file, jar_file]);
+    }
+
+    #[test]
+    fn test_keeps_archive_entry_applies_test_and_language_filters() {
+        let entry = Path::new("com/acme/Ledger.java");
+        assert!(keeps_archive_entry(entry, false, None));
+        assert!(keeps_archive_entry(entry, false, Some("java")));
+        assert!(!keeps_archive_entry(entry, false, Some("rust")));
+        assert!(!keeps_archive_entry(
+            Path::new("src/test/java/LedgerTest.java"),
+            false,
+            Some("java")
+        ));
+        assert!(keeps_archive_entry(
+            Path::new("src/test/java/LedgerTest.java"),
+            true,
+            Some("java")
+        ));
+    }
+
     #[test]
     fn test_file_list_keeps_standard_text_config_extensions() {
         let temp_dir = TempDir::new().unwrap();
@@ -717,7 +912,7 @@ mod tests {
         fs::write(&conf_file, "needle=true").unwrap();
         fs::write(&yaml_file, "needle: true").unwrap();
 
-        let file_list = build_file_list(root, true, &[], false).unwrap();
+        let file_list = build_file_list(root, true, &[], false, false).unwrap();
 
         assert!(file_list.files.iter().any(|f| f == &json_file));
         assert!(file_list.files.iter().any(|f| f == &conf_file));
@@ -739,7 +934,7 @@ mod tests {
         let parent_file = temp_dir.path().join("docs_packages").join("parent.txt");
         fs::write(&parent_file, "parent content").unwrap();
 
-        let file_list = get_file_list(temp_dir.path(), true, &[], false).unwrap();
+        let file_list = get_file_list(temp_dir.path(), true, &[], false, false).unwrap();
 
         assert!(
             file_list.files.iter().any(|f| f == &test_file),
@@ -787,7 +982,7 @@ mod tests {
         fs::create_dir_all(docs_packages_file.parent().unwrap()).unwrap();
         fs::write(&docs_packages_file, "documentation packages").unwrap();
 
-        let file_list = get_file_list(temp_dir.path(), true, &[], false).unwrap();
+        let file_list = get_file_list(temp_dir.path(), true, &[], false, false).unwrap();
 
         assert!(
             file_list.files.iter().any(|f| f == &test_file),
@@ -803,6 +998,57 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_read_ignore_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let ignore_file = temp_dir.path().join(".probeignore");
+        fs::write(
+            &ignore_file,
+            "# generated code\nvendor/**\n\n  gen/**  \n*.pb.go\n",
+        )
+        .unwrap();
+        assert_eq!(
+            read_ignore_file(&ignore_file).unwrap(),
+            vec!["vendor/**", "gen/**", "*.pb.go"]
+        );
+
+        fs::write(&ignore_file, "vendor/**\n!vendor/keep.rs\n").unwrap();
+        let err = read_ignore_file(&ignore_file).unwrap_err();
+        assert!(err.to_string().contains(":2"), "{err}");
+        assert!(read_ignore_file(&temp_dir.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn test_ignore_sources_are_combined() {
+        let temp_dir = TempDir::new().unwrap();
+        for dir in ["vendor", "gen", "tmp", "src"] {
+            fs::create_dir_all(temp_dir.path().join(dir)).unwrap();
+            fs::write(temp_dir.path().join(dir).join("lib.rs"), "fn lib() {}").unwrap();
+        }
+        let ignore_file = temp_dir.path().join("extra.ignore");
+        fs::write(&ignore_file, "gen/**\nvendor/**\n").unwrap();
+
+        let patterns = combine_ignore_patterns(
+            &["vendor/**".to_string()],
+            std::slice::from_ref(&ignore_file),
+            &["tmp/**".to_string(), "gen/**".to_string()],
+        )
+        .unwrap();
+        assert_eq!(patterns, vec!["vendor/**", "gen/**", "tmp/**"]);
+
+        // A file excluded by any source is skipped, even with .gitignore disabled
+        let file_list = get_file_list(temp_dir.path(), true, &patterns, true, false).unwrap();
+        let dirs: Vec<_> = file_list
+            .files
+            .iter()
+            .filter_map(|f| f.strip_prefix(temp_dir.path()).ok())
+            .filter_map(|f| f.components().next())
+            .map(|c| c.as_os_str().to_string_lossy().to_string())
+            .filter(|dir| dir != "extra.ignore")
+            .collect();
+        assert_eq!(dirs, vec!["src"]);
+    }
+
     #[test]
     fn test_underscore_directory_with_custom_ignores() {
         let temp_dir = TempDir::new().unwrap();
@@ -819,7 +1065,8 @@ mod tests {
 
         // Test with custom ignore patterns
         let custom_ignores = vec!["*.tmp".to_string()];
-        let file_list = get_file_list(temp_dir.path(), true, &custom_ignores, false).unwrap();
+        let file_list =
+            get_file_list(temp_dir.path(), true, &custom_ignores, false, false).unwrap();
 
         assert!(
             file_list.files.iter().any(|f| f == &test_file),
@@ -856,7 +1103,7 @@ mod tests {
             expected_files.push(file);
         }
 
-        let file_list = get_file_list(temp_dir.path(), true, &[], false).unwrap();
+        let file_list = get_file_list(temp_dir.path(), true, &[], false, false).unwrap();
 
         for expected_file in &expected_files {
             assert!(
@@ -887,7 +1134,7 @@ mod tests {
         let valid_file = valid_dir.join("main.rs");
         fs::write(&valid_file, "fn main() {}").unwrap();
 
-        let file_list = get_file_list(temp_dir.path(), true, &[], false).unwrap();
+        let file_list = get_file_list(temp_dir.path(), true, &[], false, false).unwrap();
 
         assert!(
             !file_list.files.iter().any(|f| f == &node_file),
@@ -929,7 +1176,8 @@ mod tests {
         fs::write(&regular_file, "regular content").unwrap();
 
         // Test with gitignore enabled (default behavior)
-        let file_list_with_gitignore = get_file_list(temp_dir.path(), true, &[], false).unwrap();
+        let file_list_with_gitignore =
+            get_file_list(temp_dir.path(), true, &[], false, false).unwrap();
 
         assert!(
             file_list_with_gitignore
@@ -951,7 +1199,8 @@ mod tests {
         );
 
         // Test with gitignore disabled (no_gitignore = true)
-        let file_list_no_gitignore = get_file_list(temp_dir.path(), true, &[], true).unwrap();
+        let file_list_no_gitignore =
+            get_file_list(temp_dir.path(), true, &[], true, false).unwrap();
 
         assert!(
             file_list_no_gitignore
@@ -1012,7 +1261,7 @@ mod tests {
         std::fs::write(&test_file, "class UserServiceTest; end").unwrap();
         std::fs::write(&spec_file, "RSpec.describe UserService; end").unwrap();
 
-        let without_tests = build_file_list(root, false, &[], false).unwrap();
+        let without_tests = build_file_list(root, false, &[], false, false).unwrap();
         assert!(
             without_tests.files.iter().any(|f| f == &app_file),
             "non-test Ruby file should be included"
@@ -1026,7 +1275,7 @@ mod tests {
             "_spec.rb Ruby file should be excluded without allow_tests"
         );
 
-        let with_tests = build_file_list(root, true, &[], false).unwrap();
+        let with_tests = build_file_list(root, true, &[], false, false).unwrap();
         assert!(
             with_tests.files.iter().any(|f| f == &app_file),
             "non-test Ruby file should still be included"
@@ -1065,8 +1314,9 @@ mod tests {
         let regular = workspace.join("main.rs");
         std::fs::write(&regular, "fn main() {}").unwrap();
 
-        // Build file list from workspace — should find files in the symlinked dir
-        let file_list = build_file_list(&workspace, true, &[], false).unwrap();
+        // Build file list from workspace — should find files in the symlinked dir,
+        // whether or not symlinked files are followed as well
+        let file_list = build_file_list(&workspace, true, &[], false, false).unwrap();
 
         assert!(
             file_list.files.iter().any(|f| f.ends_with("main.rs")),
@@ -1081,4 +1331,34 @@ mod tests {
             file_list.files
         );
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_followed_symlinks_skip_loops_and_duplicates() {
+        use std::os::unix::fs::symlink;
+
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+
+        let shared = root.join("shared");
+        std::fs::create_dir_all(&shared).unwrap();
+        std::fs::write(shared.join("lib.rs"), "fn shared() {}").unwrap();
+
+        // Two packages link the same directory, one links its parent (a loop) and one
+        // links a single file
+        symlink(&shared, root.join("package_a")).unwrap();
+        symlink(&shared, root.join("package_b")).unwrap();
+        symlink(root, shared.join("back_to_root")).unwrap();
+        symlink(shared.join("lib.rs"), root.join("lib_link.rs")).unwrap();
+
+        let file_list = build_file_list(root, true, &[], false, true).unwrap();
+        let real_files: HashSet<PathBuf> = file_list
+            .files
+            .iter()
+            .map(|file| std::fs::canonicalize(file).unwrap())
+            .collect();
+
+        assert_eq!(file_list.files.len(), 1, "got: {:?}", file_list.files);
+        assert!(real_files.contains(&std::fs::canonicalize(shared.join("lib.rs")).unwrap()));
+    }
 }
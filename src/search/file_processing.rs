@@ -1,18 +1,22 @@
-use anyhow::{Context, Result};
+use anyhow::Result;
 use lazy_static::lazy_static;
 use lru::LruCache;
 use rayon::prelude::*;
 use std::cell::RefCell;
 use std::collections::{HashMap, HashSet};
-use std::fs;
 use std::path::Path;
 use std::sync::{Arc, Mutex};
 use std::time::{Duration, Instant};
 
+use probe_code::encoding::{self, SourceEncoding};
+use probe_code::language::context_symbols::attach_context_symbols;
 use probe_code::language::{is_test_file, parse_file_for_code_blocks_with_tree};
 use probe_code::models::SearchResult;
 use probe_code::ranking;
+use probe_code::search::node_scope::ScopedText;
+use probe_code::search::search_index::{FileStamp, SearchIndex};
 use probe_code::search::tokenization;
+use probe_code::search::NodeKind;
 
 // PHASE 3B OPTIMIZATION: Global tokenization cache for term matching
 // This cache stores tokenized results to avoid redundant tokenization of the same content
@@ -75,6 +79,14 @@ pub struct FileProcessingParams<'a> {
     #[allow(dead_code)]
     pub no_merge: bool,
     pub lsp: bool,
+    /// Maximum number of enclosing signatures to attach to each result; `None` disables it
+    pub context_symbols_depth: Option<usize>,
+    /// Persistent index to read block tokens from and record new ones in
+    pub index: Option<&'a SearchIndex>,
+    /// Tokenize only the text inside these kinds of syntax nodes
+    pub within: Option<&'a [NodeKind]>,
+    /// Decode the file with this encoding; `None` reads it as UTF-8
+    pub encoding: Option<SourceEncoding>,
 }
 
 /// Evaluate whether a block of lines satisfies a complex AST query
@@ -1007,6 +1019,10 @@ fn process_uncovered_lines_batch(ctx: &mut BatchProcessingContext) {
                 tokenized_content: Some(context_terms),
                 lsp_info: None,
                 parent_context: None,
+                score_breakdown: None,
+                term_matches: None,
+                duplicates: None,
+                root: None,
             };
 
             // Add to result creation time
@@ -1097,18 +1113,19 @@ pub fn process_file_with_results(
         result_building_uncovered_lines: None,
     };
 
+    // Stamp the file before reading it, so a concurrent edit can only make index entries stale
+    let index_stamp = params.index.and_then(|_| FileStamp::of(params.path));
+
     // Measure file I/O time
     let file_io_start = Instant::now();
-    let content = fs::read_to_string(params.path)
-        .context(format!("Failed to read file: {:?}", params.path))?;
+    // Binary detection already ran during the search; without an encoding, files it let
+    // through may still hold invalid UTF-8, which is replaced rather than failing the file
+    let content = encoding::read_text_file(params.path, params.encoding)?;
     let file_io_duration = file_io_start.elapsed();
     timings.file_io = Some(file_io_duration);
 
-    let extension = params
-        .path
-        .extension()
-        .and_then(|ext| ext.to_str())
-        .unwrap_or("");
+    // Extension to parse the file as, after the user's language overrides
+    let extension = crate::language::overrides::effective_extension(params.path, &content);
 
     // Get debug mode setting
     let debug_mode = std::env::var("PROBE_DEBUG").unwrap_or_default() == "1";
@@ -1206,8 +1223,20 @@ pub fn process_file_with_results(
     // Measure line map building time (this is an approximation since we can't directly measure it)
     let line_map_building_start = Instant::now();
 
+    // Keep a handle on the tree for the enclosing-symbol walk (trees are cheap to clone)
+    let context_tree = params
+        .context_symbols_depth
+        .and_then(|depth| parsed_tree.clone().map(|tree| (tree, depth)));
+
+    // With `within`, blocks are tokenized from the text inside the requested nodes only
+    let scoped_text = params.within.and_then(|kinds| {
+        parsed_tree
+            .as_ref()
+            .map(|tree| ScopedText::new(&content, tree.root_node(), kinds))
+    });
+
     // Call parse_file_for_code_blocks with the pre-parsed tree to avoid double parsing
-    let code_blocks_result = parse_file_for_code_blocks_with_tree(
+    let mut code_blocks_result = parse_file_for_code_blocks_with_tree(
         &content,
         extension,
         params.line_numbers,
@@ -1215,6 +1244,9 @@ pub fn process_file_with_results(
         Some(params.term_matches),
         parsed_tree,
     );
+    if let (Ok(code_blocks), Some((tree, depth))) = (code_blocks_result.as_mut(), context_tree) {
+        attach_context_symbols(code_blocks, &tree, &content, extension, depth);
+    }
 
     let line_map_building_duration = line_map_building_start.elapsed();
     timings.ast_parsing_line_map_building = Some(line_map_building_duration);
@@ -1323,34 +1355,57 @@ pub fn process_file_with_results(
                 // Start measuring term matching time
                 let term_matching_start = Instant::now();
 
-                // PHASE 3B OPTIMIZATION: Use global tokenization cache
-                let cache_key = compute_content_hash(&full_code, &params.path.to_string_lossy());
-                let mut block_terms = {
-                    let mut cache = TOKENIZATION_CACHE.lock().unwrap();
-                    if let Some(cached_terms) = cache.get(&cache_key) {
-                        if debug_mode {
-                            println!("DEBUG: PHASE 3B - Using cached tokenization for block");
-                        }
-                        cached_terms.clone()
-                    } else {
-                        drop(cache); // Release lock before tokenization
-                        let terms = ranking::preprocess_text_with_filename(
-                            &full_code,
-                            &params.path.to_string_lossy(),
-                        );
+                let block_lines = (final_start_line, final_end_line);
+                let scoped_code = scoped_text
+                    .as_ref()
+                    .map(|scoped| scoped.lines_text(block_lines));
+                let tokenized_code = scoped_code.as_deref().unwrap_or(&full_code);
+
+                // Blocks of files unchanged since they were indexed skip tokenization entirely.
+                // The index holds whole-block tokens, so it is bypassed for scoped blocks.
+                let index_entry = params
+                    .index
+                    .zip(index_stamp)
+                    .filter(|_| scoped_code.is_none());
+                let indexed_terms = index_entry
+                    .and_then(|(index, stamp)| index.block_tokens(&file_id, stamp, block_lines));
+                let mut block_terms = if let Some(terms) = indexed_terms {
+                    terms
+                } else {
+                    // PHASE 3B OPTIMIZATION: Use global tokenization cache
+                    let cache_key =
+                        compute_content_hash(tokenized_code, &params.path.to_string_lossy());
+                    let terms = {
                         let mut cache = TOKENIZATION_CACHE.lock().unwrap();
-                        cache.put(cache_key, terms.clone());
-                        if debug_mode {
-                            println!("DEBUG: PHASE 3B - Cached new tokenization for block");
+                        if let Some(cached_terms) = cache.get(&cache_key) {
+                            if debug_mode {
+                                println!("DEBUG: PHASE 3B - Using cached tokenization for block");
+                            }
+                            cached_terms.clone()
+                        } else {
+                            drop(cache); // Release lock before tokenization
+                            let terms = ranking::preprocess_text_with_filename(
+                                tokenized_code,
+                                &params.path.to_string_lossy(),
+                            );
+                            let mut cache = TOKENIZATION_CACHE.lock().unwrap();
+                            cache.put(cache_key, terms.clone());
+                            if debug_mode {
+                                println!("DEBUG: PHASE 3B - Cached new tokenization for block");
+                            }
+                            terms
                         }
-                        terms
+                    };
+                    if let Some((index, stamp)) = index_entry {
+                        index.insert_block_tokens(&file_id, stamp, block_lines, terms.clone());
                     }
+                    terms
                 };
 
                 // The shared tokenization cache is content-based, but exact query matching is
                 // query-specific. Ensure ranking sees the same literal terms that block filtering
                 // matched, including mixed-case exact terms normalized through term_indices.
-                let full_code_lower = full_code.to_lowercase();
+                let full_code_lower = tokenized_code.to_lowercase();
                 for term in params.query_plan.term_indices.keys() {
                     if full_code_lower.contains(term) && !block_terms.iter().any(|t| t == term) {
                         block_terms.push(term.clone());
@@ -1562,7 +1617,11 @@ pub fn process_file_with_results(
                         },
                         tokenized_content: Some(block_terms),
                         lsp_info: None,
-                        parent_context: None,
+                        parent_context: block.parent_context.clone(),
+                        score_breakdown: None,
+                        term_matches: None,
+                        duplicates: None,
+                        root: None,
                     };
 
                     let result_creation_duration_value = result_creation_start.elapsed();
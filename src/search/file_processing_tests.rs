@@ -104,6 +104,10 @@ mod tests {
             query_plan: &query_plan,
             no_merge: false,
             lsp: false,
+            context_symbols_depth: None,
+            index: None,
+            within: None,
+            encoding: None,
         };
 
         let (results, _) =
@@ -156,6 +160,10 @@ mod tests {
             query_plan: &query_plan,
             no_merge: false,
             lsp: false,
+            context_symbols_depth: None,
+            index: None,
+            within: None,
+            encoding: None,
         };
 
         // Capture the results to check them
@@ -207,6 +215,10 @@ mod tests {
             query_plan: &query_plan,
             no_merge: false,
             lsp: false,
+            context_symbols_depth: None,
+            index: None,
+            within: None,
+            encoding: None,
         };
 
         let (results, _) =
@@ -285,6 +297,10 @@ function test3() {
             query_plan: &query_plan,
             no_merge: false,
             lsp: false,
+            context_symbols_depth: None,
+            index: None,
+            within: None,
+            encoding: None,
         };
 
         let (results, _) =
@@ -385,6 +401,10 @@ function processResults(results) {
             query_plan: &query_plan,
             no_merge: false,
             lsp: false,
+            context_symbols_depth: None,
+            index: None,
+            within: None,
+            encoding: None,
         };
 
         let (results, _) =
@@ -450,6 +470,10 @@ fn test_long_lines_are_ignored() {
         query_plan: &query_plan,
         no_merge: false,
         lsp: false,
+        context_symbols_depth: None,
+        index: None,
+        within: None,
+        encoding: None,
     };
 
     let (results, _) =
@@ -473,6 +473,7 @@ fn normalize_language_name(lang: &str) -> String {
         "py" => "python".to_string(),
         "rb" => "ruby".to_string(),
         "cs" => "csharp".to_string(),
+        "kt" | "kts" => "kotlin".to_string(),
         "sol" => "solidity".to_string(),
         "cr" => "crystal".to_string(),
         "hs" | "lhs" => "haskell".to_string(),
@@ -542,7 +543,7 @@ fn get_extensions_for_type(file_type: &str) -> Option<HashSet<String>> {
             extensions.insert("hs".to_string());
             extensions.insert("lhs".to_string());
         }
-        "kotlin" => {
+        "kotlin" | "kt" | "kts" => {
             extensions.insert("kt".to_string());
             extensions.insert("kts".to_string());
         }
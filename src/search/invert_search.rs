@@ -0,0 +1,312 @@
+use anyhow::{Context, Result};
+use probe_code::encoding::SourceEncoding;
+use probe_code::extract::kind_filter::declared_kind;
+use probe_code::extract::SymbolKind;
+use probe_code::file_guard;
+use probe_code::language::factory::get_language_impl;
+use probe_code::language::language_trait::LanguageImpl;
+use probe_code::language::tree_cache;
+use probe_code::models::{SearchResult, SkipReason};
+use probe_code::search::archive;
+use probe_code::search::changed_files::ChangedFiles;
+use probe_code::search::file_list_cache;
+use probe_code::search::query::{create_query_plan, create_structured_patterns, QueryPlan};
+use probe_code::search::regex_search::{combine_patterns, make_result};
+use probe_code::search::timeout::SearchDeadline;
+use probe_code::search::tracked_files::TrackedFiles;
+use rayon::prelude::*;
+use regex::Regex;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Configuration for an inverted search
+pub struct InvertSearchConfig<'a> {
+    pub custom_ignores: &'a [String],
+    pub allow_tests: bool,
+    pub language: Option<&'a str>,
+    pub no_gitignore: bool,
+    /// Follow symbolic links to files; symlinked directories are always walked
+    pub follow_symlinks: bool,
+    pub exact: bool,
+    /// Treat the queries as raw regular expressions instead of elastic search syntax
+    pub regex: bool,
+    /// Only return blocks declaring these kinds of symbols
+    pub kinds: Option<&'a [SymbolKind]>,
+    /// Only search these files
+    pub changed_files: Option<&'a ChangedFiles>,
+    /// Only search files tracked by git
+    pub tracked_files: Option<&'a TrackedFiles>,
+    /// How files are recognised as binary and skipped
+    pub binary_detection: file_guard::BinaryDetection,
+    /// Extensions always searched as text
+    pub force_text_extensions: &'a [String],
+    /// Also search the text entries of zip and jar archives
+    pub search_archives: bool,
+    /// Decode files with this encoding instead of UTF-8
+    pub encoding: Option<SourceEncoding>,
+    /// Files not yet searched when this passes are skipped
+    pub deadline: SearchDeadline,
+}
+
+/// Decides whether the text of a code block matches the search queries
+pub enum BlockMatcher {
+    /// Raw regular expression (`--regex`)
+    Regex(Regex),
+    /// Elastic query: every pattern marks the term indices it finds, and the query
+    /// AST is evaluated against the terms present in the block, as in regular search
+    Query {
+        plan: QueryPlan,
+        patterns: Vec<(Regex, HashSet<usize>)>,
+    },
+}
+
+impl BlockMatcher {
+    pub fn new(queries: &[String], exact: bool, regex: bool) -> Result<Self> {
+        if regex {
+            let combined = combine_patterns(queries);
+            let regex =
+                Regex::new(&combined).with_context(|| format!("Invalid regex: {combined}"))?;
+            return Ok(BlockMatcher::Regex(regex));
+        }
+
+        let combined_query = queries.join(" AND ");
+        let plan = create_query_plan(&combined_query, exact)?;
+        let patterns = create_structured_patterns(&plan)
+            .into_iter()
+            // The combined pre-filter pattern carries no term indices
+            .filter(|(_, indices)| !indices.is_empty())
+            .map(|(pattern, indices)| {
+                Regex::new(&format!("(?i){pattern}"))
+                    .with_context(|| format!("Invalid search pattern: {pattern}"))
+                    .map(|regex| (regex, indices))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        Ok(BlockMatcher::Query { plan, patterns })
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        match self {
+            BlockMatcher::Regex(regex) => regex.is_match(text),
+            BlockMatcher::Query { plan, patterns } => {
+                let matched_terms: HashSet<usize> = patterns
+                    .iter()
+                    .filter(|(regex, _)| regex.is_match(text))
+                    .flat_map(|(_, indices)| indices.iter().copied())
+                    .collect();
+                plan.ast.evaluate(&matched_terms, &plan.term_indices, false)
+            }
+        }
+    }
+}
+
+/// Collect the outermost code blocks under `node` that do not match.
+///
+/// A block that matches is not reported itself, but its nested blocks are still
+/// checked, so a class with one logging method yields its other methods. With `kinds`,
+/// blocks of other kinds are never reported, only searched for nested blocks, so a
+/// non-matching class still yields the methods inside it.
+fn collect_non_matching_blocks<'a>(
+    node: tree_sitter::Node<'a>,
+    content: &str,
+    language_impl: &dyn LanguageImpl,
+    matcher: &BlockMatcher,
+    kinds: Option<&[SymbolKind]>,
+    allow_tests: bool,
+    blocks: &mut Vec<tree_sitter::Node<'a>>,
+) {
+    if language_impl.is_acceptable_parent(&node) {
+        let source = content.as_bytes();
+        if !allow_tests && language_impl.is_test_node(&node, source) {
+            return;
+        }
+        let wanted = kinds.is_none_or(|kinds| {
+            let kind = declared_kind(&node, language_impl, source);
+            kinds.iter().any(|wanted| wanted.matches(kind))
+        });
+        if wanted && !matcher.is_match(&content[node.start_byte()..node.end_byte()]) {
+            blocks.push(node);
+            return;
+        }
+    }
+
+    let mut cursor = node.walk();
+    for child in node.children(&mut cursor) {
+        collect_non_matching_blocks(
+            child,
+            content,
+            language_impl,
+            matcher,
+            kinds,
+            allow_tests,
+            blocks,
+        );
+    }
+}
+
+/// Return the code blocks in a single file's content that do not match, restricted to
+/// blocks declaring `kinds` if given.
+///
+/// Files without a supported grammar have no blocks and yield no results.
+pub fn search_content_inverted(
+    file_path: &Path,
+    content: &str,
+    matcher: &BlockMatcher,
+    kinds: Option<&[SymbolKind]>,
+    allow_tests: bool,
+) -> Result<Vec<SearchResult>> {
+    let extension = file_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("");
+    let Some(language_impl) = get_language_impl(extension) else {
+        return Ok(Vec::new());
+    };
+
+    let tree =
+        tree_cache::get_or_parse_tree_pooled(&file_path.to_string_lossy(), content, extension)
+            .with_context(|| format!("Failed to parse {}", file_path.display()))?;
+
+    let mut blocks = Vec::new();
+    collect_non_matching_blocks(
+        tree.root_node(),
+        content,
+        language_impl.as_ref(),
+        matcher,
+        kinds,
+        allow_tests,
+        &mut blocks,
+    );
+
+    let lines: Vec<&str> = content.lines().collect();
+    Ok(blocks
+        .into_iter()
+        .map(|node| {
+            let start_line = node.start_position().row + 1;
+            let end_line = node.end_position().row + 1;
+            let code = lines
+                .get(start_line - 1..end_line.min(lines.len()))
+                .map(|slice| slice.join("\n"))
+                .unwrap_or_default();
+            make_result(
+                file_path,
+                (start_line, end_line),
+                node.kind(),
+                code,
+                Vec::new(),
+            )
+        })
+        .collect())
+}
+
+/// Run an inverted search over all files under `root`, returning code blocks
+/// that contain no match for the queries.
+///
+/// Results are returned in a deterministic order (by file, then line), together with the
+/// files skipped as binary or undecodable and the number of files left unsearched because
+/// the deadline passed.
+pub fn search_inverted(
+    root: &Path,
+    queries: &[String],
+    config: &InvertSearchConfig,
+) -> Result<(Vec<SearchResult>, Vec<(PathBuf, SkipReason)>, usize)> {
+    let debug_mode = std::env::var("DEBUG").unwrap_or_default() == "1";
+    let matcher = BlockMatcher::new(queries, config.exact, config.regex)?;
+
+    let file_list = file_list_cache::get_file_list_by_language(
+        root,
+        config.allow_tests,
+        config.custom_ignores,
+        config.language,
+        config.no_gitignore,
+        config.follow_symlinks,
+    )?;
+
+    let mut files: Vec<PathBuf> = file_list.files.clone();
+    let mut archives = file_list.archives.clone();
+    if let Some(changed) = config.changed_files {
+        files.retain(|file| changed.contains(file));
+        archives.retain(|archive| changed.contains(archive));
+    }
+    if let Some(tracked) = config.tracked_files {
+        files.retain(|file| tracked.contains(file));
+        archives.retain(|archive| tracked.contains(archive));
+    }
+    if config.search_archives {
+        files.extend(archive::text_entries(&archives, |entry| {
+            file_list_cache::keeps_archive_entry(entry, config.allow_tests, config.language)
+        }));
+    }
+    files.sort();
+
+    let unsearched_files = Mutex::new(Vec::new());
+    let unprocessed_files = AtomicUsize::new(0);
+    let per_file: Vec<Vec<SearchResult>> = files
+        .par_iter()
+        .filter_map(|file_path| {
+            if config.deadline.expired() {
+                unprocessed_files.fetch_add(1, Ordering::Relaxed);
+                return None;
+            }
+            let detection = file_guard::binary_detection_for(
+                file_path,
+                config.binary_detection,
+                config.force_text_extensions,
+            );
+            let content = match file_guard::read_searchable_text_file_decoded(
+                file_path,
+                detection,
+                config.encoding,
+            ) {
+                Ok(content) => content,
+                Err(e) => {
+                    if debug_mode {
+                        println!("DEBUG: Skipping unreadable file {file_path:?}: {e:?}");
+                    }
+                    if let Some(reason) = file_guard::skip_reason(&e) {
+                        unsearched_files
+                            .lock()
+                            .unwrap()
+                            .push((file_path.clone(), reason));
+                    }
+                    return None;
+                }
+            };
+            match search_content_inverted(
+                file_path,
+                &content,
+                &matcher,
+                config.kinds,
+                config.allow_tests,
+            ) {
+                Ok(results) if !results.is_empty() => Some(results),
+                Ok(_) => None,
+                Err(e) => {
+                    if debug_mode {
+                        println!("DEBUG: Inverted search failed for {file_path:?}: {e:?}");
+                    }
+                    None
+                }
+            }
+        })
+        .collect();
+
+    let mut results: Vec<SearchResult> = per_file.into_iter().flatten().collect();
+    for (i, result) in results.iter_mut().enumerate() {
+        result.rank = Some(i + 1);
+    }
+
+    if debug_mode {
+        println!(
+            "DEBUG: Inverted search for {queries:?} found {} non-matching blocks",
+            results.len()
+        );
+    }
+
+    let mut unsearched_files = unsearched_files.into_inner().unwrap();
+    unsearched_files.sort_by(|a, b| a.0.cmp(&b.0));
+
+    Ok((results, unsearched_files, unprocessed_files.into_inner()))
+}
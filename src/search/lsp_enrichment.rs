@@ -1192,6 +1192,10 @@ mod tests {
             tokenized_content: None,
             lsp_info: None,
             parent_context: None,
+            score_breakdown: None,
+            term_matches: None,
+            duplicates: None,
+            root: None,
         }
     }
 
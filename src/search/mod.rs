@@ -2,14 +2,23 @@ pub mod file_processing;
 pub mod query;
 mod result_ranking;
 // Replace the old search_execution with new modules
+pub mod archive; // Searching inside zip/jar archives
 pub mod block_merging;
 pub mod cache; // New module for caching search results
+pub mod changed_files;
+pub mod dedup; // Collapsing duplicate blocks after ranking
 pub mod early_ranker; // New module for early BM25 ranking
 pub mod elastic_query;
 pub mod file_list_cache; // New module for caching file lists
 pub mod filters;
+pub mod invert_search;
 pub mod lsp_enrichment; // New module for LSP integration in search
+pub mod multi_root; // Merging the results of several search roots
+pub mod node_scope;
+pub mod recency_boost;
+pub mod regex_search;
 pub mod ripgrep_searcher;
+pub mod search_index; // Persistent index of tokenized blocks
 mod search_limiter;
 mod search_options;
 pub mod search_output;
@@ -19,6 +28,7 @@ pub mod simd_pattern_matching;
 pub mod simd_tokenization; // SIMD-accelerated tokenization
 pub mod term_exceptions; // New module for term exceptions
 pub mod timeout; // New module for timeout functionality
+pub mod tracked_files; // Restricting a search to files tracked by git
 pub mod tokenization; // New elastic search query parser
                       // Temporarily commented out due to compilation issues
                       // mod temp_frequency_search;
@@ -30,6 +40,10 @@ mod file_processing_tests;
 mod test_token_limiter_failures;
 
 // Public exports
-pub use search_options::SearchOptions;
-pub use search_output::format_and_print_search_results;
-pub use search_runner::perform_probe;
+pub use crate::file_guard::BinaryDetection;
+pub use search_options::{
+    DedupMode, MatchScope, MergeThreshold, NodeKind, RankMode, ResultSort, SearchOptions,
+};
+pub use search_output::{format_and_print_search_results, search_json_schema};
+pub use search_runner::{perform_probe, perform_probe_top_k, perform_probe_unranked};
+pub use search_tokens::TokenizerKind;
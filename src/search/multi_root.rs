@@ -0,0 +1,239 @@
+//! Combining the results of several search roots.
+//!
+//! A multi-root search walks each root on its own, so every root keeps its own ignore files,
+//! then ranks the results of all roots as one list. BM25 scores depend on the statistics of the
+//! results they were computed over, so scores from different roots are normalized before they
+//! are compared. Roots may overlap (the same root given twice, or one nested inside another);
+//! a block found under several roots is kept once.
+
+use probe_code::models::SearchResult;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// The roots to walk, in order, without repeats. `path` comes first.
+pub fn distinct_roots(path: &Path, extra_roots: &[PathBuf]) -> Vec<PathBuf> {
+    let mut roots: Vec<PathBuf> = Vec::new();
+    for root in std::iter::once(path).chain(extra_roots.iter().map(PathBuf::as_path)) {
+        if !roots.iter().any(|seen| same_path(seen, root)) {
+            roots.push(root.to_path_buf());
+        }
+    }
+    roots
+}
+
+/// Merge the results of each root into one list ordered by score, best first.
+///
+/// Scores are divided by the best score of their root, so the best result of every root scores
+/// 1.0. Each result records its root in `root`. A block found under several overlapping roots
+/// is kept once, attributed to the most specific (deepest) of them, so the outcome does not
+/// depend on the order the roots were given in. Ranks are renumbered over the merged list.
+pub fn merge_root_results(per_root: Vec<(PathBuf, Vec<SearchResult>)>) -> Vec<SearchResult> {
+    let mut kept: Vec<SearchResult> = Vec::new();
+    let mut depths: Vec<usize> = Vec::new();
+    let mut index_by_block: HashMap<(PathBuf, (usize, usize)), usize> = HashMap::new();
+
+    for (root, mut results) in per_root {
+        normalize_scores(&mut results);
+        let canonical_root = canonical(&root);
+        let depth = canonical_root.components().count();
+        for mut result in results {
+            result.root = Some(root.to_string_lossy().to_string());
+            // Results are found by walking the root, so their paths start with it
+            let file = Path::new(&result.file);
+            let file = match file.strip_prefix(&root) {
+                Ok(relative) => canonical_root.join(relative),
+                Err(_) => canonical(file),
+            };
+            let key = (file, result.lines);
+            match index_by_block.get(&key) {
+                Some(&index) => {
+                    if depth > depths[index] {
+                        kept[index] = result;
+                        depths[index] = depth;
+                    }
+                }
+                None => {
+                    index_by_block.insert(key, kept.len());
+                    kept.push(result);
+                    depths.push(depth);
+                }
+            }
+        }
+    }
+
+    // A stable sort keeps ties in root order
+    kept.sort_by(|a, b| {
+        b.score
+            .unwrap_or(0.0)
+            .partial_cmp(&a.score.unwrap_or(0.0))
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    for (index, result) in kept.iter_mut().enumerate() {
+        result.rank = Some(index + 1);
+    }
+    kept
+}
+
+/// Scale the scores of one root's results so the best is 1.0; unscored results are left as is
+fn normalize_scores(results: &mut [SearchResult]) {
+    let best = results
+        .iter()
+        .filter_map(|result| result.score)
+        .fold(0.0, f64::max);
+    if best > 0.0 {
+        for result in results.iter_mut() {
+            if let Some(score) = result.score.as_mut() {
+                *score /= best;
+            }
+        }
+    }
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn same_path(a: &Path, b: &Path) -> bool {
+    a == b || canonical(a) == canonical(b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn result(file: &Path, lines: (usize, usize), score: f64) -> SearchResult {
+        SearchResult {
+            file: file.to_string_lossy().to_string(),
+            lines,
+            node_type: "function_item".to_string(),
+            code: String::new(),
+            symbol_signature: None,
+            matched_by_filename: None,
+            rank: None,
+            score: Some(score),
+            tfidf_score: None,
+            bm25_score: None,
+            tfidf_rank: None,
+            bm25_rank: None,
+            new_score: None,
+            hybrid2_rank: None,
+            combined_score_rank: None,
+            file_unique_terms: None,
+            file_total_matches: None,
+            file_match_rank: None,
+            block_unique_terms: None,
+            block_total_matches: None,
+            parent_file_id: None,
+            block_id: None,
+            matched_keywords: None,
+            matched_lines: None,
+            tokenized_content: None,
+            lsp_info: None,
+            parent_context: None,
+            score_breakdown: None,
+            term_matches: None,
+            duplicates: None,
+            root: None,
+        }
+    }
+
+    #[test]
+    fn test_distinct_roots_drops_repeats() {
+        let dir = TempDir::new().unwrap();
+        let api = dir.path().join("api");
+        let web = dir.path().join("web");
+        fs::create_dir_all(&api).unwrap();
+        fs::create_dir_all(&web).unwrap();
+
+        let roots = distinct_roots(&api, &[web.clone(), api.join("."), web.clone()]);
+        assert_eq!(roots, vec![api, web]);
+    }
+
+    #[test]
+    fn test_merge_orders_by_score_and_tags_roots() {
+        let api = PathBuf::from("/work/api");
+        let web = PathBuf::from("/work/web");
+        let merged = merge_root_results(vec![
+            (
+                api.clone(),
+                vec![
+                    result(&api.join("a.rs"), (1, 5), 3.0),
+                    result(&api.join("b.rs"), (1, 5), 1.0),
+                ],
+            ),
+            (web.clone(), vec![result(&web.join("c.rs"), (1, 5), 2.0)]),
+        ]);
+
+        let order: Vec<(&str, Option<&str>, Option<usize>)> = merged
+            .iter()
+            .map(|r| (r.file.as_str(), r.root.as_deref(), r.rank))
+            .collect();
+        assert_eq!(
+            order,
+            vec![
+                ("/work/api/a.rs", Some("/work/api"), Some(1)),
+                ("/work/web/c.rs", Some("/work/web"), Some(2)),
+                ("/work/api/b.rs", Some("/work/api"), Some(3)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_compares_scores_relative_to_each_root() {
+        let api = PathBuf::from("/work/api");
+        let web = PathBuf::from("/work/web");
+        // A large root scores everything higher; its second best still ranks below the
+        // small root's best
+        let merged = merge_root_results(vec![
+            (
+                api.clone(),
+                vec![
+                    result(&api.join("a.rs"), (1, 5), 20.0),
+                    result(&api.join("b.rs"), (1, 5), 10.0),
+                ],
+            ),
+            (web.clone(), vec![result(&web.join("c.rs"), (1, 5), 2.0)]),
+        ]);
+
+        let order: Vec<(&str, Option<f64>)> =
+            merged.iter().map(|r| (r.file.as_str(), r.score)).collect();
+        assert_eq!(
+            order,
+            vec![
+                ("/work/api/a.rs", Some(1.0)),
+                ("/work/web/c.rs", Some(1.0)),
+                ("/work/api/b.rs", Some(0.5)),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_merge_keeps_overlapping_blocks_once_under_deepest_root() {
+        let dir = TempDir::new().unwrap();
+        let outer = dir.path().to_path_buf();
+        let inner = outer.join("service");
+        fs::create_dir_all(&inner).unwrap();
+        let file = inner.join("lib.rs");
+        fs::write(&file, "fn main() {}\n").unwrap();
+
+        for per_root in [
+            vec![
+                (outer.clone(), vec![result(&file, (1, 1), 2.0)]),
+                (inner.clone(), vec![result(&file, (1, 1), 1.5)]),
+            ],
+            vec![
+                (inner.clone(), vec![result(&file, (1, 1), 1.5)]),
+                (outer.clone(), vec![result(&file, (1, 1), 2.0)]),
+            ],
+        ] {
+            let merged = merge_root_results(per_root);
+            assert_eq!(merged.len(), 1);
+            assert_eq!(
+                merged[0].root.as_deref(),
+                Some(inner.to_string_lossy().as_ref())
+            );
+        }
+    }
+}
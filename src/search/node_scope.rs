@@ -0,0 +1,183 @@
+//! Restricting a search to the text inside chosen kinds of syntax nodes, used with `within`.
+//!
+//! Content matches outside the requested nodes are dropped right after file searching, so
+//! blocks that only match elsewhere are never built. The blocks that remain are tokenized
+//! from the text inside the requested nodes only, so ranking ignores the surrounding code
+//! as well. Files without a tree-sitter grammar have no nodes and never match.
+
+use anyhow::{Context, Result};
+use probe_code::file_guard;
+use probe_code::language::factory::get_language_impl;
+use probe_code::language::get_or_parse_tree_pooled;
+use probe_code::language::overrides::effective_extension;
+use probe_code::search::NodeKind;
+use rayon::prelude::*;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// The parts of a file that lie inside the requested kinds of nodes
+pub struct ScopedText<'a> {
+    content: &'a str,
+    /// Byte ranges of the outermost matching nodes, in source order
+    ranges: Vec<Range<usize>>,
+    /// Byte offset of the start of every line
+    line_starts: Vec<usize>,
+}
+
+impl<'a> ScopedText<'a> {
+    /// Collect the nodes of `kinds` in the tree parsed from `content`
+    pub fn new(content: &'a str, root: tree_sitter::Node<'_>, kinds: &[NodeKind]) -> Self {
+        let mut ranges = Vec::new();
+        let mut cursor = root.walk();
+        collect_ranges(&mut cursor, kinds, &mut ranges);
+
+        let line_starts = std::iter::once(0)
+            .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self {
+            content,
+            ranges,
+            line_starts,
+        }
+    }
+
+    /// Returns true if `bytes` lies entirely inside one of the nodes
+    pub fn contains(&self, bytes: Range<usize>) -> bool {
+        let index = self
+            .ranges
+            .partition_point(|range| range.start <= bytes.start);
+        index > 0 && bytes.end <= self.ranges[index - 1].end
+    }
+
+    /// 1-based line number of a byte offset
+    pub fn line_of(&self, byte: usize) -> usize {
+        self.line_starts.partition_point(|&start| start <= byte)
+    }
+
+    /// Text of the nodes inside `lines` (1-based, inclusive), one node per line
+    pub fn lines_text(&self, lines: (usize, usize)) -> String {
+        let start = self
+            .line_starts
+            .get(lines.0.saturating_sub(1))
+            .copied()
+            .unwrap_or(self.content.len());
+        let end = self
+            .line_starts
+            .get(lines.1)
+            .copied()
+            .unwrap_or(self.content.len());
+
+        self.ranges
+            .iter()
+            .filter(|range| range.start < end && range.end > start)
+            .filter_map(|range| self.content.get(range.start.max(start)..range.end.min(end)))
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// Push the byte ranges of the outermost nodes of `kinds` under the cursor and its siblings
+fn collect_ranges(
+    cursor: &mut tree_sitter::TreeCursor<'_>,
+    kinds: &[NodeKind],
+    ranges: &mut Vec<Range<usize>>,
+) {
+    loop {
+        let node = cursor.node();
+        if kinds.iter().any(|kind| kind.matches(node.kind())) {
+            ranges.push(node.byte_range());
+        } else if cursor.goto_first_child() {
+            collect_ranges(cursor, kinds, ranges);
+            cursor.goto_parent();
+        }
+        if !cursor.goto_next_sibling() {
+            break;
+        }
+    }
+}
+
+/// Parse `content` and collect the parts of it inside `kinds`; `None` when the file has no
+/// tree-sitter grammar or fails to parse
+pub fn scoped_text<'a>(
+    path: &Path,
+    content: &'a str,
+    kinds: &[NodeKind],
+) -> Option<ScopedText<'a>> {
+    let extension = effective_extension(path, content);
+    get_language_impl(extension)?;
+    // Same cache key as file processing, so the tree is parsed once per file
+    let cache_key = format!("{}_{extension}", path.to_string_lossy());
+    let tree = get_or_parse_tree_pooled(&cache_key, content, extension).ok()?;
+    Some(ScopedText::new(content, tree.root_node(), kinds))
+}
+
+/// Drop the content matches that fall outside `kinds` from the term map of every file, and the
+/// files left without any match. `patterns` are the structured patterns the files were
+/// searched with, each with the term indices it stands for.
+pub fn restrict_term_matches(
+    file_term_map: HashMap<PathBuf, HashMap<usize, HashSet<usize>>>,
+    patterns: &[(String, HashSet<usize>)],
+    kinds: &[NodeKind],
+) -> Result<HashMap<PathBuf, HashMap<usize, HashSet<usize>>>> {
+    let regexes = patterns
+        .iter()
+        .map(|(pattern, terms)| {
+            Regex::new(&format!("(?i){pattern}"))
+                .with_context(|| format!("Invalid pattern: {pattern}"))
+                .map(|regex| (regex, terms))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(file_term_map
+        .into_par_iter()
+        .filter_map(|(path, _)| {
+            let content = file_guard::read_searchable_text_file(&path).ok()?;
+            let scoped = scoped_text(&path, &content, kinds)?;
+
+            let mut term_map: HashMap<usize, HashSet<usize>> = HashMap::new();
+            for (regex, terms) in &regexes {
+                for m in regex.find_iter(&content) {
+                    if !scoped.contains(m.range()) {
+                        continue;
+                    }
+                    let line = scoped.line_of(m.start());
+                    for &term_idx in *terms {
+                        term_map.entry(term_idx).or_default().insert(line);
+                    }
+                }
+            }
+            (!term_map.is_empty()).then_some((path, term_map))
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scoped_text_keeps_only_requested_nodes() {
+        let content = "// Fetch the TODO list\nfn fetch() {\n    let url = \"https://example.com/todo\";\n    todo_list(url);\n}\n";
+        let path = Path::new("fetch.rs");
+
+        let comments = scoped_text(path, content, &[NodeKind::Comment]).unwrap();
+        assert_eq!(
+            comments.lines_text((1, 5)).trim_end(),
+            "// Fetch the TODO list"
+        );
+        let todo = content.find("TODO").unwrap();
+        assert!(comments.contains(todo..todo + 4));
+        let call = content.find("todo_list").unwrap();
+        assert!(!comments.contains(call..call + 4));
+
+        let strings = scoped_text(path, content, &[NodeKind::String]).unwrap();
+        assert_eq!(strings.lines_text((2, 5)), "\"https://example.com/todo\"");
+        assert_eq!(strings.lines_text((1, 1)), "");
+        assert_eq!(strings.line_of(content.find("https").unwrap()), 3);
+
+        // Files without a grammar have no nodes to search in
+        assert!(scoped_text(Path::new("notes.txt"), content, &[NodeKind::Comment]).is_none());
+    }
+}
@@ -2,6 +2,8 @@ use probe_code::search::elastic_query;
 use probe_code::search::tokenization;
 // No term_exceptions import needed
 use lru::LruCache;
+use probe_code::models::TermMatch;
+use regex::{Regex, RegexBuilder};
 use std::collections::{HashMap, HashSet};
 use std::num::NonZeroUsize;
 use std::sync::{Arc, Mutex};
@@ -763,6 +765,97 @@ pub fn create_structured_patterns(plan: &QueryPlan) -> Vec<(String, HashSet<usiz
     limited_results
 } // Re-added function closing brace
 
+/// The structured patterns of a query, compiled to locate term matches inside result blocks
+pub struct TermMatcher {
+    /// Each pattern with the (non-excluded) terms it stands for
+    patterns: Vec<(Regex, Vec<String>)>,
+}
+
+impl TermMatcher {
+    /// Compile the patterns from `create_structured_patterns`. Patterns that are not tied to
+    /// a term (the combined pre-filter pattern) or only to excluded terms are dropped.
+    pub fn new(plan: &QueryPlan, patterns: &[(String, HashSet<usize>)]) -> Self {
+        let terms_by_index: HashMap<usize, &String> = plan
+            .term_indices
+            .iter()
+            .filter(|(term, _)| !plan.excluded_terms.contains(*term))
+            .map(|(term, &idx)| (idx, term))
+            .collect();
+
+        let patterns = patterns
+            .iter()
+            .filter_map(|(pattern, indices)| {
+                let mut terms: Vec<String> = indices
+                    .iter()
+                    .filter_map(|idx| terms_by_index.get(idx))
+                    .map(|term| term.to_string())
+                    .collect();
+                if terms.is_empty() {
+                    return None;
+                }
+                terms.sort();
+                let regex = RegexBuilder::new(pattern)
+                    .case_insensitive(true)
+                    .build()
+                    .ok()?;
+                Some((regex, terms))
+            })
+            .collect();
+
+        Self { patterns }
+    }
+
+    /// Find the term matches in `code`, whose first line is line `start_line` of the file.
+    ///
+    /// Where several patterns of one term match overlapping text (the whole keyword and one
+    /// of its tokens), only the earliest, longest match is kept.
+    pub fn find(&self, code: &str, start_line: usize) -> Vec<TermMatch> {
+        let mut spans: Vec<(usize, usize, &str)> = Vec::new();
+        for (regex, terms) in &self.patterns {
+            for found in regex.find_iter(code).filter(|found| !found.is_empty()) {
+                for term in terms {
+                    spans.push((found.start(), found.end(), term));
+                }
+            }
+        }
+        spans.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)).then(a.2.cmp(b.2)));
+
+        let mut term_ends: HashMap<&str, usize> = HashMap::new();
+        spans.retain(|&(start, end, term)| {
+            let overlaps = term_ends
+                .get(term)
+                .is_some_and(|&term_end| start < term_end);
+            if !overlaps {
+                term_ends.insert(term, end);
+            }
+            !overlaps
+        });
+
+        // Spans are in offset order, so lines are counted incrementally
+        let mut line = start_line;
+        let mut line_start = 0;
+        let mut scanned = 0;
+        spans
+            .into_iter()
+            .map(|(start, end, term)| {
+                let skipped = &code[scanned..start];
+                line += skipped.matches('\n').count();
+                if let Some(newline) = skipped.rfind('\n') {
+                    line_start = scanned + newline + 1;
+                }
+                scanned = start;
+                TermMatch {
+                    term: term.to_string(),
+                    start_byte: start,
+                    end_byte: end,
+                    line,
+                    column: code[line_start..start].chars().count() + 1,
+                }
+            })
+            .collect()
+    }
+}
+
 /// Create a query plan from an already parsed AST
 pub fn create_query_plan_from_ast(
     ast: elastic_query::Expr,
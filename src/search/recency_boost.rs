@@ -0,0 +1,139 @@
+//! Recency boosting for search results.
+//!
+//! Files with recent commits are usually the code being worked on, so their results are
+//! upweighted by a factor that decays with the age of the file's last commit. Commit times
+//! come from [`GitService`]; outside a git repository the boost is a no-op.
+
+use lsp_daemon::GitService;
+use probe_code::models::SearchResult;
+use std::collections::HashMap;
+use std::path::{Component, Path};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Default number of days after which the recency boost is halved
+pub const DEFAULT_RECENCY_HALF_LIFE_DAYS: f64 = 14.0;
+
+/// Score multiplier for a file committed just now (decays towards 1.0)
+const MAX_RECENCY_BOOST: f64 = 2.0;
+
+/// Number of commits inspected when looking up when a file last changed
+const MAX_COMMITS: usize = 2000;
+
+/// Score multiplier for a file whose last commit is `age_secs` old. The boost above 1.0
+/// halves every `half_life_days`.
+pub fn recency_multiplier(age_secs: i64, half_life_days: f64) -> f64 {
+    if half_life_days <= 0.0 {
+        return 1.0;
+    }
+    let age_days = age_secs.max(0) as f64 / 86_400.0;
+    1.0 + (MAX_RECENCY_BOOST - 1.0) * 0.5f64.powf(age_days / half_life_days)
+}
+
+/// Multiply the score of each ranked result by its file's recency multiplier, then re-sort
+/// by score and reassign ranks. Files without commits (e.g. untracked) keep their score.
+///
+/// Returns `false`, leaving the results untouched, when `search_root` is not inside a git
+/// repository or its history cannot be read.
+pub fn apply_recency_boost(
+    results: &mut [SearchResult],
+    search_root: &Path,
+    half_life_days: f64,
+) -> bool {
+    let start_at = if search_root.is_file() {
+        search_root.parent().unwrap_or(search_root)
+    } else {
+        search_root
+    };
+    let git = match GitService::discover_repo(start_at, start_at) {
+        Ok(git) => git,
+        Err(_) => return false,
+    };
+    let Some(workdir) = git.workdir().and_then(|dir| dir.canonicalize().ok()) else {
+        return false;
+    };
+
+    // Git paths are relative to the repository root and use forward slashes
+    let mut repo_paths: HashMap<String, String> = HashMap::new();
+    for result in results.iter() {
+        if repo_paths.contains_key(&result.file) {
+            continue;
+        }
+        let repo_path = Path::new(&result.file)
+            .canonicalize()
+            .or_else(|_| start_at.join(&result.file).canonicalize())
+            .ok()
+            .and_then(|path| path.strip_prefix(&workdir).ok().map(to_git_path));
+        if let Some(repo_path) = repo_path {
+            repo_paths.insert(result.file.clone(), repo_path);
+        }
+    }
+
+    let mut paths: Vec<String> = repo_paths.values().cloned().collect();
+    paths.sort();
+    paths.dedup();
+    let commit_times = match git.last_commit_times(&paths, MAX_COMMITS) {
+        Ok(times) => times,
+        Err(_) => return false,
+    };
+
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs() as i64)
+        .unwrap_or_default();
+
+    for result in results.iter_mut() {
+        let Some(score) = result.score else {
+            continue;
+        };
+        let multiplier = repo_paths
+            .get(&result.file)
+            .and_then(|path| commit_times.get(path))
+            .map(|&committed_at| recency_multiplier(now - committed_at, half_life_days))
+            .unwrap_or(1.0);
+        result.score = Some(score * multiplier);
+    }
+
+    results.sort_by(|a, b| {
+        b.score
+            .unwrap_or(0.0)
+            .partial_cmp(&a.score.unwrap_or(0.0))
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then_with(|| (&a.file, a.lines.0).cmp(&(&b.file, b.lines.0)))
+    });
+
+    // Results the reranker dropped (rank usize::MAX) stay dropped
+    for (index, result) in results.iter_mut().enumerate() {
+        if matches!(result.rank, Some(rank) if rank != usize::MAX) {
+            result.rank = Some(index + 1);
+        }
+    }
+
+    true
+}
+
+fn to_git_path(path: &Path) -> String {
+    path.components()
+        .filter_map(|component| match component {
+            Component::Normal(part) => Some(part.to_string_lossy()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_recency_multiplier_decays_by_half_life() {
+        let day = 86_400;
+        assert_eq!(recency_multiplier(0, 14.0), 2.0);
+        assert_eq!(recency_multiplier(14 * day, 14.0), 1.5);
+        assert_eq!(recency_multiplier(28 * day, 14.0), 1.25);
+        assert!(recency_multiplier(365 * day, 14.0) < 1.001);
+        // Clock skew never pushes the boost above the maximum
+        assert_eq!(recency_multiplier(-day, 14.0), 2.0);
+        assert_eq!(recency_multiplier(0, 0.0), 1.0);
+    }
+}
@@ -0,0 +1,402 @@
+use anyhow::{Context, Result};
+use probe_code::encoding::SourceEncoding;
+use probe_code::file_guard;
+use probe_code::language::factory::get_language_impl;
+use probe_code::language::{parse_file_for_code_blocks, tree_cache};
+use probe_code::models::{SearchResult, SkipReason};
+use probe_code::search::archive;
+use probe_code::search::changed_files::ChangedFiles;
+use probe_code::search::file_list_cache;
+use probe_code::search::timeout::SearchDeadline;
+use probe_code::search::tracked_files::TrackedFiles;
+use probe_code::search::{MatchScope, NodeKind};
+use rayon::prelude::*;
+use regex::Regex;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+/// Configuration for a raw regex search
+pub struct RegexSearchConfig<'a> {
+    pub custom_ignores: &'a [String],
+    pub allow_tests: bool,
+    pub language: Option<&'a str>,
+    pub no_gitignore: bool,
+    /// Follow symbolic links to files; symlinked directories are always walked
+    pub follow_symlinks: bool,
+    pub match_scope: MatchScope,
+    /// Only keep matches inside these kinds of syntax nodes
+    pub within: Option<&'a [NodeKind]>,
+    /// Only search these files
+    pub changed_files: Option<&'a ChangedFiles>,
+    /// Only search files tracked by git
+    pub tracked_files: Option<&'a TrackedFiles>,
+    /// How files are recognised as binary and skipped
+    pub binary_detection: file_guard::BinaryDetection,
+    /// Extensions always searched as text
+    pub force_text_extensions: &'a [String],
+    /// Also search the text entries of zip and jar archives
+    pub search_archives: bool,
+    /// Decode files with this encoding instead of UTF-8
+    pub encoding: Option<SourceEncoding>,
+    /// Files not yet searched when this passes are skipped
+    pub deadline: SearchDeadline,
+}
+
+/// Returns true if a tree-sitter node kind represents a string literal or a comment.
+///
+/// Grammars name these nodes differently (`string_literal`, `interpreted_string_literal`,
+/// `template_string`, `line_comment`, `block_comment`, ...), but all of them contain
+/// either "string" or "comment" in their kind.
+pub fn is_string_or_comment_kind(kind: &str) -> bool {
+    kind.contains("comment") || (kind.contains("string") && !kind.contains("type"))
+}
+
+/// Find the outermost node accepted by `in_scope` enclosing the given byte range, if any
+fn enclosing_scoped_node<'t>(
+    root: tree_sitter::Node<'t>,
+    start: usize,
+    end: usize,
+    in_scope: impl Fn(&str) -> bool,
+) -> Option<tree_sitter::Node<'t>> {
+    let mut current = root.descendant_for_byte_range(start, end);
+    let mut found = None;
+    while let Some(node) = current {
+        if in_scope(node.kind()) {
+            found = Some(node);
+        }
+        current = node.parent();
+    }
+    found
+}
+
+pub(crate) fn make_result(
+    file_path: &Path,
+    lines: (usize, usize),
+    node_type: &str,
+    code: String,
+    matched: Vec<String>,
+) -> SearchResult {
+    SearchResult {
+        file: file_path.to_string_lossy().to_string(),
+        lines,
+        node_type: node_type.to_string(),
+        code,
+        symbol_signature: None,
+        matched_by_filename: None,
+        rank: None,
+        score: None,
+        tfidf_score: None,
+        bm25_score: None,
+        tfidf_rank: None,
+        bm25_rank: None,
+        new_score: None,
+        hybrid2_rank: None,
+        combined_score_rank: None,
+        file_unique_terms: None,
+        file_total_matches: None,
+        file_match_rank: None,
+        block_unique_terms: None,
+        block_total_matches: Some(matched.len()),
+        parent_file_id: None,
+        block_id: None,
+        matched_keywords: Some(matched),
+        matched_lines: None,
+        tokenized_content: None,
+        lsp_info: None,
+        parent_context: None,
+        score_breakdown: None,
+        term_matches: None,
+        duplicates: None,
+        root: None,
+    }
+}
+
+/// Expand line-level matches into the code blocks (functions, classes, ...) that contain
+/// them, as regular search does. Matched lines outside any block, and all lines of files
+/// without a grammar, are kept as single-line results.
+fn expand_to_code_blocks(
+    file_path: &Path,
+    content: &str,
+    line_results: Vec<SearchResult>,
+    snippet: impl Fn(usize, usize) -> String,
+) -> Vec<SearchResult> {
+    let extension = file_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("");
+    let line_numbers: HashSet<usize> = line_results.iter().map(|r| r.lines.0).collect();
+    // Test files are already filtered by the file list; like line results, blocks inside
+    // non-test files are not filtered for test code
+    let blocks = match parse_file_for_code_blocks(content, extension, &line_numbers, true, None) {
+        Ok(blocks) if !blocks.is_empty() => blocks,
+        _ => return line_results,
+    };
+
+    let mut block_results: Vec<Option<SearchResult>> = vec![None; blocks.len()];
+    let mut results = Vec::new();
+    for line_result in line_results {
+        let line = line_result.lines.0;
+        let Some(index) = blocks
+            .iter()
+            .position(|block| block.start_row < line && line <= block.end_row + 1)
+        else {
+            results.push(line_result);
+            continue;
+        };
+        let block = &blocks[index];
+        let (start_line, end_line) = (block.start_row + 1, block.end_row + 1);
+        let result = block_results[index].get_or_insert_with(|| {
+            let mut result = make_result(
+                file_path,
+                (start_line, end_line),
+                &block.node_type,
+                snippet(start_line, end_line),
+                Vec::new(),
+            );
+            result.matched_lines = Some(Vec::new());
+            result
+        });
+        let matched = line_result.matched_keywords.unwrap_or_default();
+        if let Some(keywords) = result.matched_keywords.as_mut() {
+            keywords.extend(matched);
+            result.block_total_matches = Some(keywords.len());
+        }
+        if let Some(lines) = result.matched_lines.as_mut() {
+            lines.push(line - start_line);
+        }
+    }
+
+    results.extend(block_results.into_iter().flatten());
+    results.sort_by_key(|result| result.lines.0);
+    results
+}
+
+/// Search a single file's content with a regex, honouring the match scope.
+///
+/// With `MatchScope::All` and no `within` kinds, matches are found line by line and then
+/// expanded into their enclosing code blocks. Otherwise the file is parsed and only matches
+/// that fall inside a string literal or comment (`MatchScope::StringsAndComments`) and inside
+/// one of the `within` kinds are kept; each enclosing node is reported once.
+pub fn search_content_with_regex(
+    file_path: &Path,
+    content: &str,
+    regex: &Regex,
+    match_scope: MatchScope,
+    within: Option<&[NodeKind]>,
+) -> Result<Vec<SearchResult>> {
+    let line_of = |byte: usize| content[..byte].matches('\n').count() + 1;
+    let lines: Vec<&str> = content.lines().collect();
+    let snippet = |start: usize, end: usize| {
+        lines
+            .get(start - 1..end.min(lines.len()))
+            .map(|slice| slice.join("\n"))
+            .unwrap_or_default()
+    };
+
+    match (match_scope, within) {
+        (MatchScope::All, None) => {
+            let mut results: Vec<SearchResult> = Vec::new();
+            for m in regex.find_iter(content) {
+                let line = line_of(m.start());
+                match results.last_mut() {
+                    Some(last) if last.lines.0 == line => {
+                        if let Some(keywords) = last.matched_keywords.as_mut() {
+                            keywords.push(m.as_str().to_string());
+                            last.block_total_matches = Some(keywords.len());
+                        }
+                    }
+                    _ => results.push(make_result(
+                        file_path,
+                        (line, line),
+                        "line",
+                        snippet(line, line),
+                        vec![m.as_str().to_string()],
+                    )),
+                }
+            }
+            Ok(expand_to_code_blocks(file_path, content, results, snippet))
+        }
+        _ => {
+            let in_scope = |kind: &str| {
+                (match_scope == MatchScope::All || is_string_or_comment_kind(kind))
+                    && within.is_none_or(|kinds| kinds.iter().any(|k| k.matches(kind)))
+            };
+            let extension = file_path
+                .extension()
+                .and_then(|ext| ext.to_str())
+                .unwrap_or("");
+            // Without a grammar we cannot tell syntax nodes apart from code
+            if get_language_impl(extension).is_none() {
+                return Ok(Vec::new());
+            }
+
+            let tree = tree_cache::get_or_parse_tree_pooled(
+                &file_path.to_string_lossy(),
+                content,
+                extension,
+            )
+            .with_context(|| format!("Failed to parse {}", file_path.display()))?;
+            let root = tree.root_node();
+
+            let mut results: Vec<SearchResult> = Vec::new();
+            let mut last_node: Option<(usize, usize)> = None;
+            for m in regex.find_iter(content) {
+                let Some(node) = enclosing_scoped_node(root, m.start(), m.end(), &in_scope) else {
+                    continue;
+                };
+                let node_range = (node.start_byte(), node.end_byte());
+                if last_node == Some(node_range) {
+                    if let Some(last) = results.last_mut() {
+                        if let Some(keywords) = last.matched_keywords.as_mut() {
+                            keywords.push(m.as_str().to_string());
+                            last.block_total_matches = Some(keywords.len());
+                        }
+                    }
+                    continue;
+                }
+                last_node = Some(node_range);
+
+                let start_line = node.start_position().row + 1;
+                let end_line = node.end_position().row + 1;
+                results.push(make_result(
+                    file_path,
+                    (start_line, end_line),
+                    node.kind(),
+                    snippet(start_line, end_line),
+                    vec![m.as_str().to_string()],
+                ));
+            }
+            Ok(results)
+        }
+    }
+}
+
+/// Combine several regex patterns into one, treating them as alternatives
+pub(crate) fn combine_patterns(patterns: &[String]) -> String {
+    if patterns.len() == 1 {
+        patterns[0].clone()
+    } else {
+        patterns
+            .iter()
+            .map(|p| format!("(?:{p})"))
+            .collect::<Vec<_>>()
+            .join("|")
+    }
+}
+
+/// Run a raw regex search over all files under `root`.
+///
+/// Results are returned in a deterministic order (by file, then line), together with the
+/// files skipped as binary or undecodable and the number of files left unsearched because
+/// the deadline passed.
+pub fn search_with_regex(
+    root: &Path,
+    patterns: &[String],
+    config: &RegexSearchConfig,
+) -> Result<(Vec<SearchResult>, Vec<(PathBuf, SkipReason)>, usize)> {
+    let debug_mode = std::env::var("DEBUG").unwrap_or_default() == "1";
+
+    let combined = combine_patterns(patterns);
+    let regex = Regex::new(&combined).with_context(|| format!("Invalid regex: {combined}"))?;
+
+    let file_list = file_list_cache::get_file_list_by_language(
+        root,
+        config.allow_tests,
+        config.custom_ignores,
+        config.language,
+        config.no_gitignore,
+        config.follow_symlinks,
+    )?;
+
+    let mut files: Vec<PathBuf> = file_list.files.clone();
+    let mut archives = file_list.archives.clone();
+    if let Some(changed) = config.changed_files {
+        files.retain(|file| changed.contains(file));
+        archives.retain(|archive| changed.contains(archive));
+    }
+    if let Some(tracked) = config.tracked_files {
+        files.retain(|file| tracked.contains(file));
+        archives.retain(|archive| tracked.contains(archive));
+    }
+    if config.search_archives {
+        files.extend(archive::text_entries(&archives, |entry| {
+            file_list_cache::keeps_archive_entry(entry, config.allow_tests, config.language)
+        }));
+    }
+    files.sort();
+
+    let unsearched_files = Mutex::new(Vec::new());
+    let unprocessed_files = AtomicUsize::new(0);
+    let per_file: Vec<Vec<SearchResult>> = files
+        .par_iter()
+        .filter_map(|file_path| {
+            if config.deadline.expired() {
+                unprocessed_files.fetch_add(1, Ordering::Relaxed);
+                return None;
+            }
+            let detection = file_guard::binary_detection_for(
+                file_path,
+                config.binary_detection,
+                config.force_text_extensions,
+            );
+            let content = match file_guard::read_searchable_text_file_decoded(
+                file_path,
+                detection,
+                config.encoding,
+            ) {
+                Ok(content) => content,
+                Err(e) => {
+                    if debug_mode {
+                        println!("DEBUG: Skipping unreadable file {file_path:?}: {e:?}");
+                    }
+                    if let Some(reason) = file_guard::skip_reason(&e) {
+                        unsearched_files
+                            .lock()
+                            .unwrap()
+                            .push((file_path.clone(), reason));
+                    }
+                    return None;
+                }
+            };
+            if !regex.is_match(&content) {
+                return None;
+            }
+            match search_content_with_regex(
+                file_path,
+                &content,
+                &regex,
+                config.match_scope,
+                config.within,
+            ) {
+                Ok(results) if !results.is_empty() => Some(results),
+                Ok(_) => None,
+                Err(e) => {
+                    if debug_mode {
+                        println!("DEBUG: Regex search failed for {file_path:?}: {e:?}");
+                    }
+                    None
+                }
+            }
+        })
+        .collect();
+
+    let mut results: Vec<SearchResult> = per_file.into_iter().flatten().collect();
+    for (i, result) in results.iter_mut().enumerate() {
+        result.rank = Some(i + 1);
+    }
+
+    if debug_mode {
+        println!(
+            "DEBUG: Regex search for '{combined}' ({:?}) found {} results",
+            config.match_scope,
+            results.len()
+        );
+    }
+
+    let mut unsearched_files = unsearched_files.into_inner().unwrap();
+    unsearched_files.sort_by(|a, b| a.0.cmp(&b.0));
+
+    Ok((results, unsearched_files, unprocessed_files.into_inner()))
+}
@@ -1,7 +1,8 @@
-#[cfg(feature = "bert-reranker")]
 use probe_code::bert_reranker;
-use probe_code::models::SearchResult;
-use probe_code::ranking;
+use probe_code::models::{ScoreBreakdown, SearchResult};
+use probe_code::ranking::{self, ProfileBoosts, RankingProfile};
+use probe_code::simd_ranking;
+use std::collections::HashMap;
 use std::time::Instant;
 
 /// Calculate coverage boost based on unique terms matched in the block
@@ -114,12 +115,18 @@ fn format_duration(duration: std::time::Duration) -> String {
     }
 }
 
-/// Function to rank search results based on query relevance using various algorithms
+/// Function to rank search results based on query relevance using various algorithms.
+/// BERT rerankers only rescore the top `rerank_top_k` results of a BM25 pass, and reuse
+/// block encodings from earlier searches in the same `session`. `profile` supplies the BM25
+/// parameters and the boosts applied on top of the BM25 score.
 pub fn rank_search_results(
     results: &mut [SearchResult],
     queries: &[String],
     reranker: &str,
     question: Option<&str>,
+    rerank_top_k: usize,
+    session: Option<&str>,
+    profile: &RankingProfile,
 ) {
     let start_time = Instant::now();
 
@@ -136,11 +143,17 @@ pub fn rank_search_results(
     }
 
     // Handle BERT-based reranking for MS-MARCO models
-    if reranker == "ms-marco-tinybert"
-        || reranker == "ms-marco-minilm-l6"
-        || reranker == "ms-marco-minilm-l12"
-    {
-        handle_bert_reranking(results, queries, reranker, question, debug_mode, start_time);
+    if bert_reranker::model_for_reranker(reranker).is_some() {
+        handle_bert_reranking(
+            results,
+            queries,
+            reranker,
+            question,
+            rerank_top_k,
+            session,
+            profile,
+            debug_mode,
+        );
         return;
     }
 
@@ -242,6 +255,7 @@ pub fn rank_search_results(
         } else {
             None
         },
+        profile,
     };
 
     let document_ranking_start = Instant::now();
@@ -254,7 +268,10 @@ pub fn rank_search_results(
     let use_simd = std::env::var("DISABLE_SIMD_RANKING").unwrap_or_default() != "1";
     let ranked_indices = if use_simd {
         if debug_mode {
-            println!("DEBUG: Using SIMD-optimized ranking (default)");
+            println!(
+                "DEBUG: Using SIMD-optimized ranking (default, {} kernels)",
+                simd_ranking::backend_in_use()
+            );
         }
         ranking::rank_documents_simd(&ranking_params)
     } else {
@@ -279,6 +296,7 @@ pub fn rank_search_results(
     // based on the query, and we want to preserve OR query behavior
     let filtering_start = Instant::now();
     let mut updated_results = Vec::new();
+    let profile_boosts = ProfileBoosts::new(profile, queries);
 
     // Update scores for all results
     for (rank_index, (original_index, bm25_score)) in ranked_indices.iter().enumerate() {
@@ -294,7 +312,10 @@ pub fn rank_search_results(
             let node_type_boost =
                 calculate_node_type_boost(&result_clone.node_type, result_clone.lines);
 
-            let boosted_score = bm25_score * coverage_boost * node_type_boost;
+            // Filename, exact match and proximity boosts of the ranking profile
+            let profile_boost = profile_boosts.factor(&result_clone.file, &result_clone.code);
+
+            let boosted_score = bm25_score * coverage_boost * node_type_boost * profile_boost;
             result_clone.score = Some(boosted_score);
             result_clone.bm25_score = Some(*bm25_score); // Keep original BM25 score
             updated_results.push(result_clone);
@@ -384,15 +405,99 @@ pub fn rank_search_results(
     }
 }
 
-/// Handle BERT-based reranking using the ms-marco-tinybert model
+/// Attach a [`ScoreBreakdown`] to each ranked result for `--explain`: the raw BM25 score, the
+/// adjustment the reranker made on top of it, and the per-term tf/idf values behind the BM25
+/// score. Runs right after [`rank_search_results`] with the same `profile`, so IDF and BM25
+/// are computed over the same results with the same parameters.
+pub fn attach_score_breakdowns(
+    results: &mut [SearchResult],
+    queries: &[String],
+    reranker: &str,
+    profile: &RankingProfile,
+) {
+    let combined_query = queries.join(" ");
+    let documents: Vec<String> = results
+        .iter()
+        .map(|r| format!("// Filename: {}\n{}", r.file, r.code))
+        .collect();
+    let documents_refs: Vec<&str> = documents.iter().map(|s| s.as_str()).collect();
+    let pre_tokenized: Vec<Vec<String>> = results
+        .iter()
+        .filter_map(|r| r.tokenized_content.clone())
+        .collect();
+    let has_tokenized = !pre_tokenized.is_empty() && pre_tokenized.len() == results.len();
+
+    let ranking_params = ranking::RankingParams {
+        documents: &documents_refs,
+        query: &combined_query,
+        pre_tokenized: if has_tokenized {
+            Some(&pre_tokenized)
+        } else {
+            None
+        },
+        profile,
+    };
+    // BERT rerankers overwrite `bm25_score`, so the raw BM25 scores are recomputed here
+    let bm25_scores: HashMap<usize, f64> = ranking::rank_documents(&ranking_params)
+        .into_iter()
+        .collect();
+    let term_scores = ranking::explain_term_scores(&ranking_params);
+
+    for (index, (result, terms)) in results.iter_mut().zip(term_scores).enumerate() {
+        let bm25 = bm25_scores.get(&index).copied().unwrap_or(0.0);
+        result.score_breakdown = Some(ScoreBreakdown {
+            reranker: reranker.to_string(),
+            bm25,
+            rerank: result.score.unwrap_or(bm25) - bm25,
+            terms,
+        });
+    }
+}
+
+/// Remove results containing an excluded (`-term`) query term, compared on stemmed tokens so
+/// `-tokens` also drops blocks that only mention `token`.
+///
+/// Runs after ranking, so negative terms win: a term that is both searched for and excluded
+/// removes every block containing it. Returns the number of removed results.
+pub fn drop_excluded_results(results: &mut Vec<SearchResult>, queries: &[String]) -> usize {
+    let Ok(expr) = probe_code::search::elastic_query::parse_query(&queries.join(" "), false) else {
+        return 0;
+    };
+    let groups = ranking::extract_excluded_token_groups(&expr);
+    if groups.is_empty() {
+        return 0;
+    }
+
+    let original_len = results.len();
+    results.retain(|result| {
+        let contains_excluded = match &result.tokenized_content {
+            Some(tokens) => ranking::contains_excluded_term(tokens, &groups),
+            None => ranking::contains_excluded_term(
+                &ranking::preprocess_text_with_filename(&result.code, &result.file),
+                &groups,
+            ),
+        };
+        !contains_excluded
+    });
+    original_len - results.len()
+}
+
+/// Handle BERT-based reranking: rank everything with hybrid BM25 first, then rescore the top
+/// `rerank_top_k` candidates with the cross-encoder. The remaining results keep their hybrid
+/// order below the rescored ones, and the hybrid ranking is kept if the model cannot be used.
+#[allow(clippy::too_many_arguments)]
 fn handle_bert_reranking(
     results: &mut [SearchResult],
     queries: &[String],
     reranker: &str,
     question: Option<&str>,
+    rerank_top_k: usize,
+    session: Option<&str>,
+    profile: &RankingProfile,
     debug_mode: bool,
-    start_time: Instant,
 ) {
+    let start_time = Instant::now();
+
     if debug_mode {
         println!("DEBUG: Using BERT reranking with {reranker}");
         if let Some(q) = question {
@@ -407,35 +512,52 @@ fn handle_bert_reranking(
         }
     }
 
+    // The BM25 pass picks the candidates, and is the fallback if BERT reranking fails
+    rank_search_results(
+        results,
+        queries,
+        "hybrid",
+        question,
+        rerank_top_k,
+        None,
+        profile,
+    );
+
     #[cfg(feature = "bert-reranker")]
     {
         use tokio::runtime::Runtime;
 
+        let model_name = bert_reranker::model_for_reranker(reranker)
+            .unwrap_or("cross-encoder/ms-marco-TinyBERT-L-2-v2");
+
+        // Results the BM25 pass did not rank are never candidates
+        let ranked = results
+            .iter()
+            .take_while(|r| r.rank != Some(usize::MAX))
+            .count();
+        let candidates = ranked.min(rerank_top_k);
+        if debug_mode {
+            println!("DEBUG: Rescoring the top {candidates} of {ranked} BM25 candidates with BERT");
+        }
+
         // Use thread-based approach to avoid nested runtime issues
         let bert_result = std::thread::spawn({
-            let results_clone = results.to_vec();
+            let results_clone = results[..candidates].to_vec();
             let queries_clone = queries.to_vec();
             let question_clone = question.map(|s| s.to_string());
-            let reranker_clone = reranker.to_string();
+            let session_clone = session.map(|s| s.to_string());
             move || {
                 let rt = Runtime::new().expect("Failed to create runtime for BERT reranking");
                 rt.block_on(async {
                     // Create a mutable copy to work with
                     let mut results_copy = results_clone;
 
-                    // Map reranker name to model name
-                    let model_name = match reranker_clone.as_str() {
-                        "ms-marco-tinybert" => "cross-encoder/ms-marco-TinyBERT-L-2-v2",
-                        "ms-marco-minilm-l6" => "cross-encoder/ms-marco-MiniLM-L-6-v2",
-                        "ms-marco-minilm-l12" => "cross-encoder/ms-marco-MiniLM-L-12-v2",
-                        _ => "cross-encoder/ms-marco-TinyBERT-L-2-v2", // default fallback
-                    };
-
                     bert_reranker::rerank_with_bert(
                         &mut results_copy,
                         &queries_clone,
                         model_name,
                         question_clone.as_deref(),
+                        session_clone.as_deref(),
                     )
                     .await
                     .map(|_| results_copy)
@@ -448,19 +570,16 @@ fn handle_bert_reranking(
             Ok(inner_result) => inner_result,
             Err(_) => {
                 eprintln!("BERT reranking thread panicked");
-                eprintln!("Falling back to BM25 ranking...");
-                fallback_to_bm25_ranking(results, queries, debug_mode, start_time);
+                eprintln!("Falling back to hybrid ranking...");
                 return;
             }
         };
 
         match bert_result {
             Ok(reranked_results) => {
-                // Copy the reranked results back to the original slice
+                // Copy the rescored candidates back over the top of the BM25 order
                 for (i, reranked_result) in reranked_results.into_iter().enumerate() {
-                    if i < results.len() {
-                        results[i] = reranked_result;
-                    }
+                    results[i] = reranked_result;
                 }
 
                 let total_duration = start_time.elapsed();
@@ -473,138 +592,16 @@ fn handle_bert_reranking(
             }
             Err(e) => {
                 eprintln!("BERT reranking failed: {e}");
-                eprintln!("Falling back to BM25 ranking...");
-                fallback_to_bm25_ranking(results, queries, debug_mode, start_time);
+                eprintln!("Falling back to hybrid ranking...");
             }
         }
     }
 
     #[cfg(not(feature = "bert-reranker"))]
     {
+        let _ = (start_time, session);
         eprintln!("BERT reranker '{reranker}' is not available.");
         eprintln!("To enable BERT reranking, build with: cargo build --features bert-reranker");
-        eprintln!("Falling back to BM25 ranking...");
-        fallback_to_bm25_ranking(results, queries, debug_mode, start_time);
-    }
-}
-
-/// Fallback to BM25 ranking when BERT reranking fails or is unavailable
-fn fallback_to_bm25_ranking(
-    results: &mut [SearchResult],
-    queries: &[String],
-    debug_mode: bool,
-    start_time: Instant,
-) {
-    // Reimplement the BM25 ranking logic that was in the main function
-    let combined_query = queries.join(" ");
-
-    // Extract document texts for ranking, including filename in each document
-    let documents: Vec<String> = results
-        .iter()
-        .map(|r| {
-            let mut doc = String::with_capacity(r.file.len() + r.code.len() + 15);
-            doc.push_str("// Filename: ");
-            doc.push_str(&r.file);
-            doc.push('\n');
-            doc.push_str(&r.code);
-            doc
-        })
-        .collect();
-    let documents_refs: Vec<&str> = documents.iter().map(|s| s.as_str()).collect();
-
-    // Extract pre-tokenized content if available
-    let pre_tokenized: Vec<Vec<String>> = results
-        .iter()
-        .filter_map(|r| r.tokenized_content.clone())
-        .collect();
-
-    let has_tokenized = !pre_tokenized.is_empty() && pre_tokenized.len() == results.len();
-
-    let ranking_params = ranking::RankingParams {
-        documents: &documents_refs,
-        query: &combined_query,
-        pre_tokenized: if has_tokenized {
-            Some(&pre_tokenized)
-        } else {
-            None
-        },
-    };
-
-    // Use SIMD-optimized ranking by default, can be disabled with DISABLE_SIMD_RANKING=1
-    let use_simd = std::env::var("DISABLE_SIMD_RANKING").unwrap_or_default() != "1";
-    let ranked_indices = if use_simd {
-        if debug_mode {
-            eprintln!("DEBUG: Using SIMD-optimized BM25 ranking (fallback)");
-        }
-        ranking::rank_documents_simd(&ranking_params)
-    } else {
-        if debug_mode {
-            eprintln!("DEBUG: Using traditional BM25 ranking (fallback)");
-        }
-        ranking::rank_documents(&ranking_params)
-    };
-
-    // Update scores for all results
-    let mut updated_results = Vec::new();
-
-    for (rank_index, (original_index, bm25_score)) in ranked_indices.iter().enumerate() {
-        if let Some(result) = results.get(*original_index) {
-            let mut result_clone = result.clone();
-            result_clone.rank = Some(rank_index + 1); // 1-based rank
-
-            // Calculate coverage boost based on unique terms matched in the block
-            let coverage_boost =
-                calculate_coverage_boost(result_clone.block_unique_terms, queries.len());
-
-            // Apply node type boosting (same logic as in the main function)
-            let node_type_boost =
-                calculate_node_type_boost(&result_clone.node_type, result_clone.lines);
-
-            let boosted_score = bm25_score * coverage_boost * node_type_boost;
-            result_clone.score = Some(boosted_score);
-            result_clone.bm25_score = Some(*bm25_score);
-            updated_results.push(result_clone);
-        }
-    }
-
-    // Sort by boosted score in descending order
-    updated_results.sort_by(|a, b| {
-        let score_a = a.score.unwrap_or(0.0);
-        let score_b = b.score.unwrap_or(0.0);
-        score_b
-            .partial_cmp(&score_a)
-            .unwrap_or(std::cmp::Ordering::Equal)
-    });
-
-    // Reassign ranks based on the sorted order
-    for (rank, result) in updated_results.iter_mut().enumerate() {
-        result.bm25_rank = Some(rank + 1);
-        result.rank = Some(rank + 1);
-    }
-
-    // Replace original results with updated results
-    let updated_len = updated_results.len();
-    if updated_len < results.len() {
-        for (i, result) in updated_results.into_iter().enumerate() {
-            results[i] = result;
-        }
-        // Mark remaining results as low priority
-        for result in results.iter_mut().skip(updated_len) {
-            result.matched_by_filename = Some(false);
-            result.score = Some(0.0);
-            result.rank = Some(usize::MAX);
-        }
-    } else {
-        for (i, result) in updated_results.into_iter().enumerate() {
-            results[i] = result;
-        }
-    }
-
-    let total_duration = start_time.elapsed();
-    if debug_mode {
-        println!(
-            "DEBUG: BM25 fallback ranking completed in {}",
-            format_duration(total_duration)
-        );
+        eprintln!("Falling back to hybrid ranking...");
     }
 }
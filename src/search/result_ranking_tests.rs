@@ -34,6 +34,10 @@ mod tests {
                 matched_lines: None,
                 tokenized_content: None,
                 parent_context: None,
+                score_breakdown: None,
+                term_matches: None,
+                duplicates: None,
+                root: None,
             },
             SearchResult {
                 file: "file2.rs".to_string(),
@@ -62,6 +66,10 @@ mod tests {
                 matched_lines: None,
                 tokenized_content: None,
                 parent_context: None,
+                score_breakdown: None,
+                term_matches: None,
+                duplicates: None,
+                root: None,
             },
             SearchResult {
                 file: "file3.rs".to_string(),
@@ -90,6 +98,10 @@ mod tests {
                 matched_lines: None,
                 tokenized_content: None,
                 parent_context: None,
+                score_breakdown: None,
+                term_matches: None,
+                duplicates: None,
+                root: None,
             },
         ]
     }
@@ -101,7 +113,7 @@ mod tests {
 
         // Enable debug mode for this test to verify logging
         std::env::set_var("DEBUG", "1");
-        rank_search_results(&mut results, &queries, "hybrid", None);
+        rank_search_results(&mut results, &queries, "hybrid", None, 50, None);
         std::env::remove_var("DEBUG");
 
         // Check that all results have been assigned ranks and scores
@@ -130,7 +142,7 @@ mod tests {
         let mut results = create_test_results();
         let queries = vec!["search".to_string()];
 
-        rank_search_results(&mut results, &queries, "tfidf", None);
+        rank_search_results(&mut results, &queries, "tfidf", None, 50, None);
 
         // Check that all results have been assigned ranks and scores
         for result in &results {
@@ -154,7 +166,7 @@ mod tests {
         let mut results = create_test_results();
         let queries = vec!["search".to_string()];
 
-        rank_search_results(&mut results, &queries, "bm25", None);
+        rank_search_results(&mut results, &queries, "bm25", None, 50, None);
 
         // Check that all results have been assigned ranks and scores
         for result in &results {
@@ -178,7 +190,7 @@ mod tests {
         let mut results = create_test_results();
         let queries = vec!["search".to_string(), "function".to_string()];
 
-        rank_search_results(&mut results, &queries, "hybrid", None);
+        rank_search_results(&mut results, &queries, "hybrid", None, 50, None);
 
         // Check that all results have been assigned ranks and scores
         for result in &results {
@@ -202,7 +214,7 @@ mod tests {
         let queries = vec!["search".to_string()];
 
         // Should not panic with empty results
-        rank_search_results(&mut results, &queries, "hybrid", None);
+        rank_search_results(&mut results, &queries, "hybrid", None, 50, None);
 
         assert_eq!(results.len(), 0);
     }
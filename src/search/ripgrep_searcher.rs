@@ -1,8 +1,11 @@
-use crate::file_guard;
+use crate::encoding::SourceEncoding;
+use crate::file_guard::{self, BinaryDetection};
+use crate::models::SkipReason;
 use anyhow::{Context, Result};
 use regex::{RegexSet, RegexSetBuilder};
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 use std::time::Instant;
 
 /// High-performance RegexSet-based searcher for fast file pattern matching
@@ -15,6 +18,9 @@ use std::time::Instant;
 pub struct RipgrepSearcher {
     debug_mode: bool,
     regex_set: RegexSet,
+    binary_detection: BinaryDetection,
+    force_text_extensions: Vec<String>,
+    encoding: Option<SourceEncoding>,
 }
 
 impl RipgrepSearcher {
@@ -74,9 +80,30 @@ impl RipgrepSearcher {
         Ok(RipgrepSearcher {
             debug_mode,
             regex_set,
+            binary_detection: BinaryDetection::default(),
+            force_text_extensions: Vec::new(),
+            encoding: None,
         })
     }
 
+    /// Skip files that `detection` finds binary, except those with one of
+    /// `force_text_extensions`
+    pub fn with_binary_detection(
+        mut self,
+        detection: BinaryDetection,
+        force_text_extensions: &[String],
+    ) -> Self {
+        self.binary_detection = detection;
+        self.force_text_extensions = force_text_extensions.to_vec();
+        self
+    }
+
+    /// Decode files with `encoding` instead of UTF-8
+    pub fn with_encoding(mut self, encoding: Option<SourceEncoding>) -> Self {
+        self.encoding = encoding;
+        self
+    }
+
     /// Search a single file and return term matches with line numbers
     /// This uses a fast RegexSet-based approach for maximum performance
     pub fn search_file(
@@ -92,7 +119,16 @@ impl RipgrepSearcher {
         }
 
         // Read the file content with shared text-search safety checks.
-        let content = match file_guard::read_searchable_text_file(file_path) {
+        let detection = file_guard::binary_detection_for(
+            file_path,
+            self.binary_detection,
+            &self.force_text_extensions,
+        );
+        let content = match file_guard::read_searchable_text_file_decoded(
+            file_path,
+            detection,
+            self.encoding,
+        ) {
             Ok(content) => content,
             Err(e) => {
                 if self.debug_mode {
@@ -147,12 +183,17 @@ impl RipgrepSearcher {
         Ok(term_map)
     }
 
-    /// Search multiple files in parallel using fast RegexSet-based approach
+    /// Search multiple files in parallel using fast RegexSet-based approach. Returns the term
+    /// matches of each matching file and the files skipped as binary or undecodable.
+    #[allow(clippy::type_complexity)]
     pub fn search_files_parallel(
         &self,
         file_paths: &[PathBuf],
         pattern_to_terms: &[HashSet<usize>],
-    ) -> Result<HashMap<PathBuf, HashMap<usize, HashSet<usize>>>> {
+    ) -> Result<(
+        HashMap<PathBuf, HashMap<usize, HashSet<usize>>>,
+        Vec<(PathBuf, SkipReason)>,
+    )> {
         use rayon::prelude::*;
 
         let start_time = Instant::now();
@@ -171,6 +212,7 @@ impl RipgrepSearcher {
 
         // Use par_iter().filter_map() for parallel processing
         // The searcher instance is thread-safe, so we can reuse it
+        let unsearched_files = Mutex::new(Vec::new());
         let results: Vec<(PathBuf, HashMap<usize, HashSet<usize>>)> = sorted_file_paths
             .par_iter()
             .filter_map(|file_path| {
@@ -188,6 +230,12 @@ impl RipgrepSearcher {
                         if self.debug_mode {
                             println!("DEBUG: Error searching file {file_path:?}: {e}");
                         }
+                        if let Some(reason) = file_guard::skip_reason(&e) {
+                            unsearched_files
+                                .lock()
+                                .unwrap()
+                                .push((file_path.clone(), reason));
+                        }
                         None
                     }
                 }
@@ -209,7 +257,10 @@ impl RipgrepSearcher {
             );
         }
 
-        Ok(final_results)
+        let mut unsearched_files = unsearched_files.into_inner().unwrap();
+        unsearched_files.sort_by(|a, b| a.0.cmp(&b.0));
+
+        Ok((final_results, unsearched_files))
     }
 }
 
@@ -260,13 +311,46 @@ mod tests {
             set
         }];
 
-        let results = searcher
+        let (results, unsearched_files) = searcher
             .search_files_parallel(&files, &pattern_to_terms)
             .unwrap();
 
         assert_eq!(results.len(), 2);
         assert!(results.contains_key(&file1));
         assert!(results.contains_key(&file2));
+        assert!(unsearched_files.is_empty());
+    }
+
+    #[test]
+    fn test_ripgrep_searcher_reports_binary_files() {
+        let dir = tempdir().unwrap();
+        let text = dir.path().join("text.rs");
+        let generated = dir.path().join("generated.pb");
+        fs::write(&text, "fn test() {}").unwrap();
+        fs::write(&generated, b"fn test() {}\0\x01").unwrap();
+
+        let patterns = vec!["fn test".to_string()];
+        let files = vec![text.clone(), generated.clone()];
+        let pattern_to_terms = vec![HashSet::from([0])];
+
+        let searcher = RipgrepSearcher::new(&patterns, true).unwrap();
+        let (results, unsearched_files) = searcher
+            .search_files_parallel(&files, &pattern_to_terms)
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(
+            unsearched_files,
+            vec![(generated.clone(), SkipReason::Binary)]
+        );
+
+        let searcher = RipgrepSearcher::new(&patterns, true)
+            .unwrap()
+            .with_binary_detection(BinaryDetection::NullByte, &["pb".to_string()]);
+        let (results, unsearched_files) = searcher
+            .search_files_parallel(&files, &pattern_to_terms)
+            .unwrap();
+        assert!(results.contains_key(&generated));
+        assert!(unsearched_files.is_empty());
     }
 
     #[test]
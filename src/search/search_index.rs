@@ -0,0 +1,207 @@
+//! Persistent index of tokenized code blocks, used with `--use-index`.
+//!
+//! Tokenizing code blocks is the bulk of the work repeated between searches over the same
+//! tree. The index keeps the tokens of every block processed so far, keyed by file path and
+//! block lines, and drops a file's blocks as soon as its modification time or size changes.
+//! Indexes live in `~/.cache/probe/index/`, one JSON file per search root, next to the
+//! session caches of [`super::cache`].
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::RwLock;
+use std::time::UNIX_EPOCH;
+
+use probe_code::search::cache::hash_query;
+
+/// Modification time and size of a file, used to detect stale index entries
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FileStamp {
+    pub mtime_secs: u64,
+    pub mtime_nanos: u32,
+    pub size: u64,
+}
+
+impl FileStamp {
+    /// Read the stamp of `path`; `None` when its metadata is unavailable
+    pub fn of(path: &Path) -> Option<Self> {
+        let metadata = fs::metadata(path).ok()?;
+        let mtime = metadata.modified().ok()?.duration_since(UNIX_EPOCH).ok()?;
+        Some(Self {
+            mtime_secs: mtime.as_secs(),
+            mtime_nanos: mtime.subsec_nanos(),
+            size: metadata.len(),
+        })
+    }
+}
+
+/// Tokenized blocks of one file, valid while the file still has `stamp`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct IndexedFile {
+    stamp: FileStamp,
+    /// Block tokens keyed by "start-end" (1-based, inclusive line numbers)
+    blocks: HashMap<String, Vec<String>>,
+}
+
+/// On-disk layout of an index
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct IndexData {
+    /// Probe version and tokenizer settings that wrote the index; tokens change with either
+    version: String,
+    files: HashMap<String, IndexedFile>,
+}
+
+/// Tokenized-block index of one search root, shared by the threads processing files
+pub struct SearchIndex {
+    index_path: PathBuf,
+    files: RwLock<HashMap<String, IndexedFile>>,
+    dirty: AtomicBool,
+}
+
+/// Version stamp of indexes: the probe version plus the stop-word and stemmer settings
+fn index_version() -> String {
+    let search = &probe_code::config::get_config().search;
+    let settings = format!(
+        "{}|{}|{}",
+        search.stemmer_language,
+        search.stop_words_add.join(","),
+        search.stop_words_remove.join(",")
+    );
+    format!("{}-{}", env!("CARGO_PKG_VERSION"), hash_query(&settings))
+}
+
+fn block_key(lines: (usize, usize)) -> String {
+    format!("{}-{}", lines.0, lines.1)
+}
+
+impl SearchIndex {
+    /// Load the index of `root`, or start an empty one if none exists yet
+    pub fn load(root: &Path) -> Self {
+        Self::load_from(Self::get_index_path(root))
+    }
+
+    /// Load the index stored at `index_path`. Unreadable indexes, indexes written by another
+    /// version or with other tokenizer settings, and entries of deleted files are discarded.
+    pub fn load_from(index_path: PathBuf) -> Self {
+        let mut files = fs::read_to_string(&index_path)
+            .ok()
+            .and_then(|contents| serde_json::from_str::<IndexData>(&contents).ok())
+            .filter(|data| data.version == index_version())
+            .map(|data| data.files)
+            .unwrap_or_default();
+        files.retain(|file, _| Path::new(file).exists());
+
+        Self {
+            index_path,
+            files: RwLock::new(files),
+            dirty: AtomicBool::new(false),
+        }
+    }
+
+    /// Path of the index file for a search root
+    pub fn get_index_path(root: &Path) -> PathBuf {
+        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+        let home_dir = dirs::home_dir().unwrap_or_else(|| PathBuf::from("."));
+        home_dir
+            .join(".cache")
+            .join("probe")
+            .join("index")
+            .join(format!("{}.json", hash_query(&root.to_string_lossy())))
+    }
+
+    /// Tokens of the block at `lines` of `file`, if it was indexed while the file had `stamp`
+    pub fn block_tokens(
+        &self,
+        file: &str,
+        stamp: FileStamp,
+        lines: (usize, usize),
+    ) -> Option<Vec<String>> {
+        let files = self.files.read().unwrap();
+        let indexed = files.get(file).filter(|indexed| indexed.stamp == stamp)?;
+        indexed.blocks.get(&block_key(lines)).cloned()
+    }
+
+    /// Record the tokens of a block; blocks indexed under an older stamp of the file are dropped
+    pub fn insert_block_tokens(
+        &self,
+        file: &str,
+        stamp: FileStamp,
+        lines: (usize, usize),
+        tokens: Vec<String>,
+    ) {
+        let mut files = self.files.write().unwrap();
+        let indexed = files
+            .entry(file.to_string())
+            .or_insert_with(|| IndexedFile {
+                stamp,
+                blocks: HashMap::new(),
+            });
+        if indexed.stamp != stamp {
+            indexed.stamp = stamp;
+            indexed.blocks.clear();
+        }
+        indexed.blocks.insert(block_key(lines), tokens);
+        self.dirty.store(true, Ordering::Relaxed);
+    }
+
+    /// Write the index back to disk if blocks were added since it was loaded
+    pub fn save(&self) -> Result<()> {
+        if !self.dirty.load(Ordering::Relaxed) {
+            return Ok(());
+        }
+        if let Some(parent) = self.index_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let files = self.files.read().unwrap();
+        let data = IndexData {
+            version: index_version(),
+            files: files.clone(),
+        };
+        // Write to a temporary file first so concurrent searches never read a partial index
+        let tmp_path = self.index_path.with_extension("json.tmp");
+        fs::write(&tmp_path, serde_json::to_vec(&data)?)?;
+        fs::rename(&tmp_path, &self.index_path)?;
+        self.dirty.store(false, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_index_round_trip_and_invalidation() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("billing.rs");
+        fs::write(&source, "fn settle() {}\n").unwrap();
+        let file = source.to_string_lossy().to_string();
+        let index_path = temp_dir.path().join("index").join("billing.json");
+
+        let stamp = FileStamp::of(&source).unwrap();
+        let index = SearchIndex::load_from(index_path.clone());
+        assert_eq!(index.block_tokens(&file, stamp, (1, 1)), None);
+        index.insert_block_tokens(&file, stamp, (1, 1), vec!["settl".to_string()]);
+        index.save().unwrap();
+
+        let reloaded = SearchIndex::load_from(index_path);
+        assert_eq!(
+            reloaded.block_tokens(&file, stamp, (1, 1)),
+            Some(vec!["settl".to_string()])
+        );
+        assert_eq!(reloaded.block_tokens(&file, stamp, (1, 2)), None);
+
+        // A changed file no longer matches its indexed stamp
+        fs::write(&source, "fn settle_invoice() {}\n").unwrap();
+        let changed = FileStamp::of(&source).unwrap();
+        assert_eq!(reloaded.block_tokens(&file, changed, (1, 1)), None);
+
+        reloaded.insert_block_tokens(&file, changed, (1, 1), vec!["invoic".to_string()]);
+        assert_eq!(reloaded.block_tokens(&file, stamp, (1, 1)), None);
+    }
+}
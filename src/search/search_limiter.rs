@@ -1,5 +1,7 @@
-use probe_code::models::{LimitedSearchResults, SearchLimits, SearchResult};
-use probe_code::search::search_tokens::count_block_tokens;
+use probe_code::models::{
+    LimitedSearchResults, SearchLimits, SearchResult, SizeEstimate, SkipReason, SkippedFile,
+};
+use probe_code::search::search_tokens::{count_block_tokens, count_tokens_with, TokenizerKind};
 
 /// Helper function to apply limits (max results, max bytes, max tokens) to search results
 ///
@@ -19,11 +21,19 @@ use probe_code::search::search_tokens::count_block_tokens;
 /// - Progressive evaluation: only count tokens when we estimate we're approaching the limit
 /// - Byte-based early estimation (1 token ≈ 4 bytes is rough approximation)
 /// - Result quality: Process ranked results first to ensure best results within limits
+///
+/// When a `tokenizer` is given, every result is counted exactly with that encoding instead of
+/// estimated, so `max_tokens` matches the target model. A result that does not fit is dropped
+/// whole, never split.
+///
+/// Every matching result that is left out is returned in `skipped_files` with the limit that
+/// excluded it. Once a limit is reached, all lower ranked results are skipped for that limit.
 pub fn apply_limits(
     results: Vec<SearchResult>,
     max_results: Option<usize>,
     max_bytes: Option<usize>,
     max_tokens: Option<usize>,
+    tokenizer: Option<TokenizerKind>,
 ) -> LimitedSearchResults {
     // Early return if no limits are specified - avoids all token counting and processing
     if max_results.is_none() && max_bytes.is_none() && max_tokens.is_none() {
@@ -33,6 +43,9 @@ pub fn apply_limits(
             limits_applied: None,
             cached_blocks_skipped: None,
             files_skipped_early_termination: None,
+            estimated_full_size: None,
+            cached_blocks: Vec::new(),
+            timed_out: false,
         };
     }
 
@@ -91,8 +104,8 @@ pub fn apply_limits(
     let max_token_limit = max_tokens.unwrap_or(usize::MAX);
     let mut token_counting_started = false;
 
-    // EARLY TERMINATION: Track if any limit has been reached to exit the loop completely
-    let mut limit_reached = false;
+    // EARLY TERMINATION: Track which limit has been reached, if any, to exit the loop completely
+    let mut limit_reached: Option<SkipReason> = None;
 
     // Ultra-lazy token counting approach:
     // - Skip token counting entirely if max_tokens is None (saves 31ms-11.72s)
@@ -106,13 +119,11 @@ pub fn apply_limits(
 
     for (index, r) in results.into_iter().enumerate() {
         // EARLY TERMINATION: Stop processing if any limit has been reached
-        if limit_reached || index >= max_iterations {
-            // Add remaining results to skipped if they have valid ranking
-            if r.rank.is_some()
-                && (r.tfidf_score.unwrap_or(0.0) > 0.0 || r.bm25_score.unwrap_or(0.0) > 0.0)
-            {
-                skipped.push(r);
-            }
+        if limit_reached.is_some() || index >= max_iterations {
+            // Add remaining results to skipped if they have valid ranking. Results past the
+            // iteration window count against max_results, which sizes that window
+            let reason = limit_reached.unwrap_or(SkipReason::MaxResults);
+            push_skipped(&mut skipped, r, reason);
             continue;
         }
         let r_bytes = r.code.len();
@@ -121,12 +132,8 @@ pub fn apply_limits(
         if let Some(max_res) = max_results {
             if running_count >= max_res {
                 // Early termination: we've reached max results, collect remaining as skipped
-                limit_reached = true;
-                if r.rank.is_some()
-                    && (r.tfidf_score.unwrap_or(0.0) > 0.0 || r.bm25_score.unwrap_or(0.0) > 0.0)
-                {
-                    skipped.push(r);
-                }
+                limit_reached = Some(SkipReason::MaxResults);
+                push_skipped(&mut skipped, r, SkipReason::MaxResults);
                 continue;
             }
         }
@@ -135,18 +142,17 @@ pub fn apply_limits(
         if let Some(max_bytes_limit) = max_bytes {
             if running_bytes + r_bytes > max_bytes_limit {
                 // Early termination: adding this result would exceed byte limit
-                limit_reached = true;
-                if r.rank.is_some()
-                    && (r.tfidf_score.unwrap_or(0.0) > 0.0 || r.bm25_score.unwrap_or(0.0) > 0.0)
-                {
-                    skipped.push(r);
-                }
+                limit_reached = Some(SkipReason::MaxBytes);
+                push_skipped(&mut skipped, r, SkipReason::MaxBytes);
                 continue;
             }
         }
 
         // PRE-COMPUTED LIMITS: Ultra-lazy token counting with running totals
-        let r_tokens = if max_tokens.is_some() {
+        let r_tokens = if let (Some(_), Some(kind)) = (max_tokens, tokenizer) {
+            // An explicit tokenizer always counts exactly
+            count_tokens_with(&r.code, kind)
+        } else if max_tokens.is_some() {
             // Use rough estimation and only start precise counting if we're very close to the limit
             let estimated_tokens = (r_bytes / 4).max(1);
             let estimated_total_after = running_tokens + estimated_tokens;
@@ -179,12 +185,8 @@ pub fn apply_limits(
         if let Some(max_tokens_limit) = max_tokens {
             if running_tokens + r_tokens > max_tokens_limit {
                 // Early termination: adding this result would exceed token limit
-                limit_reached = true;
-                if r.rank.is_some()
-                    && (r.tfidf_score.unwrap_or(0.0) > 0.0 || r.bm25_score.unwrap_or(0.0) > 0.0)
-                {
-                    skipped.push(r);
-                }
+                limit_reached = Some(SkipReason::MaxTokens);
+                push_skipped(&mut skipped, r, SkipReason::MaxTokens);
                 continue;
             }
         }
@@ -197,18 +199,21 @@ pub fn apply_limits(
     }
 
     // Final token count calculation: only do expensive precise counting if needed
-    let final_total_tokens =
-        if max_tokens.is_some() && !token_counting_started && !limited.is_empty() {
-            // We only used estimations, but we need to provide accurate final count for the user
-            // This is still more efficient than counting every result during the loop
-            // Use block-level caching for final token count calculation
-            limited
-                .iter()
-                .map(|result: &SearchResult| count_block_tokens(&result.code))
-                .sum()
-        } else {
-            running_tokens
-        };
+    let final_total_tokens = if max_tokens.is_some()
+        && tokenizer.is_none()
+        && !token_counting_started
+        && !limited.is_empty()
+    {
+        // We only used estimations, but we need to provide accurate final count for the user
+        // This is still more efficient than counting every result during the loop
+        // Use block-level caching for final token count calculation
+        limited
+            .iter()
+            .map(|result: &SearchResult| count_block_tokens(&result.code))
+            .sum()
+    } else {
+        running_tokens
+    };
 
     LimitedSearchResults {
         results: limited,
@@ -217,10 +222,44 @@ pub fn apply_limits(
             max_results,
             max_bytes,
             max_tokens,
+            min_score: None,
+            below_min_score: 0,
             total_bytes: running_bytes,
             total_tokens: final_total_tokens,
         }),
         cached_blocks_skipped: None,
         files_skipped_early_termination: None,
+        estimated_full_size: None,
+        cached_blocks: Vec::new(),
+        timed_out: false,
+    }
+}
+
+/// Record a result left out by `reason`, unless it has no valid ranking
+fn push_skipped(skipped: &mut Vec<SkippedFile>, result: SearchResult, reason: SkipReason) {
+    if result.rank.is_some()
+        && (result.tfidf_score.unwrap_or(0.0) > 0.0 || result.bm25_score.unwrap_or(0.0) > 0.0)
+    {
+        skipped.push(SkippedFile { result, reason });
+    }
+}
+
+/// Estimate the size of `results` if they were returned without limits.
+///
+/// Tokens are counted the way `apply_limits` counts them once it counts precisely: with the
+/// given `tokenizer` if any, otherwise with the default block-level counting. The estimate is
+/// therefore directly comparable to `max_tokens`.
+pub fn estimate_full_size(
+    results: &[SearchResult],
+    tokenizer: Option<TokenizerKind>,
+) -> SizeEstimate {
+    let count = |code: &str| match tokenizer {
+        Some(kind) => count_tokens_with(code, kind),
+        None => count_block_tokens(code),
+    };
+    SizeEstimate {
+        results: results.len(),
+        total_bytes: results.iter().map(|r| r.code.len()).sum(),
+        total_tokens: results.iter().map(|r| count(&r.code)).sum(),
     }
 }
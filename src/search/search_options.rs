@@ -1,6 +1,215 @@
-use std::path::Path;
+use probe_code::extract::SymbolKind;
+use probe_code::file_guard::BinaryDetection;
+use probe_code::models::SearchResult;
+use probe_code::search::search_tokens::TokenizerKind;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+/// Ordering applied to search results after limits have been applied
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ResultSort {
+    /// Keep the relevance order produced by the reranker
+    #[default]
+    Score,
+    /// Order by file path, then by start line
+    Path,
+    /// Order by start line, then by file path
+    Line,
+}
+
+impl ResultSort {
+    /// Reorder results in place. Limits are expected to have been applied already,
+    /// so the selected top-K results are the same regardless of the sort order.
+    pub fn apply(self, results: &mut [SearchResult]) {
+        match self {
+            ResultSort::Score => {}
+            ResultSort::Path => {
+                results.sort_by(|a, b| a.file.cmp(&b.file).then(a.lines.0.cmp(&b.lines.0)))
+            }
+            ResultSort::Line => {
+                results.sort_by(|a, b| a.lines.0.cmp(&b.lines.0).then(a.file.cmp(&b.file)))
+            }
+        }
+    }
+}
+
+impl FromStr for ResultSort {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "score" => Ok(ResultSort::Score),
+            "path" => Ok(ResultSort::Path),
+            "line" => Ok(ResultSort::Line),
+            _ => Err(format!(
+                "Unknown sort order: {s}. Expected one of: score, path, line"
+            )),
+        }
+    }
+}
+
+/// Which parts of the source a regex search is allowed to match in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MatchScope {
+    /// Match anywhere in the file
+    #[default]
+    All,
+    /// Only match inside string literals and comments (e.g. for secret auditing)
+    StringsAndComments,
+}
+
+impl FromStr for MatchScope {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().replace('_', "-").as_str() {
+            "all" => Ok(MatchScope::All),
+            "strings-and-comments" => Ok(MatchScope::StringsAndComments),
+            _ => Err(format!(
+                "Unknown match scope: {s}. Expected one of: all, strings-and-comments"
+            )),
+        }
+    }
+}
+
+/// Category of syntax node that a search can be restricted to with `within`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NodeKind {
+    /// Line, block and doc comments
+    Comment,
+    /// String literals, including template and raw strings
+    String,
+    /// Identifiers of variables, functions, types, fields, ...
+    Identifier,
+}
+
+impl NodeKind {
+    /// Returns true if a tree-sitter node kind belongs to this category. Grammars name their
+    /// nodes differently (`line_comment`, `interpreted_string_literal`, `type_identifier`, ...),
+    /// so kinds are matched by the word they contain.
+    pub fn matches(self, kind: &str) -> bool {
+        match self {
+            NodeKind::Comment => kind.contains("comment"),
+            NodeKind::String => kind.contains("string") && !kind.contains("type"),
+            NodeKind::Identifier => kind.contains("identifier"),
+        }
+    }
+}
+
+impl FromStr for NodeKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "comment" | "comments" => Ok(NodeKind::Comment),
+            "string" | "strings" => Ok(NodeKind::String),
+            "identifier" | "identifiers" => Ok(NodeKind::Identifier),
+            _ => Err(format!(
+                "Unknown node kind: {s}. Expected one of: comment, string, identifier"
+            )),
+        }
+    }
+}
+
+/// Unit that search results are ranked and limited in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RankMode {
+    /// Rank individual code blocks
+    #[default]
+    Block,
+    /// Rank whole files by the summed BM25 score of their matching blocks; each file is
+    /// returned once, represented by its best block, and `max_results` counts files
+    File,
+}
+
+impl FromStr for RankMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "block" => Ok(RankMode::Block),
+            "file" => Ok(RankMode::File),
+            _ => Err(format!(
+                "Unknown rank mode: {s}. Expected one of: block, file"
+            )),
+        }
+    }
+}
+
+/// How duplicate blocks are collapsed in the ranked results. The best-ranked block of each
+/// group of duplicates is kept, with the number of collapsed blocks in `duplicates`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DedupMode {
+    /// Keep every block
+    #[default]
+    None,
+    /// Collapse blocks whose code is byte-identical
+    Exact,
+    /// Also collapse near-duplicates: blocks whose token shingles are mostly the same, such as
+    /// boilerplate differing in a name or literal
+    Near,
+}
+
+impl FromStr for DedupMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "none" => Ok(DedupMode::None),
+            "exact" => Ok(DedupMode::Exact),
+            "near" => Ok(DedupMode::Near),
+            _ => Err(format!(
+                "Unknown dedup mode: {s}. Expected one of: none, exact, near"
+            )),
+        }
+    }
+}
+
+/// Maximum gap in lines between two blocks of a file for them to be merged
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MergeThreshold {
+    /// The same gap for every file
+    Lines(usize),
+    /// A gap chosen per file from the average distance between its matched lines, so dense
+    /// files merge aggressively and sparse files stay granular
+    Auto,
+}
+
+impl MergeThreshold {
+    /// Gap used when no threshold is configured
+    pub const DEFAULT_LINES: usize = 5;
+}
+
+impl Default for MergeThreshold {
+    fn default() -> Self {
+        MergeThreshold::Lines(Self::DEFAULT_LINES)
+    }
+}
+
+impl std::fmt::Display for MergeThreshold {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MergeThreshold::Lines(lines) => write!(f, "{lines}"),
+            MergeThreshold::Auto => write!(f, "auto"),
+        }
+    }
+}
+
+impl FromStr for MergeThreshold {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.eq_ignore_ascii_case("auto") {
+            return Ok(MergeThreshold::Auto);
+        }
+        s.parse().map(MergeThreshold::Lines).map_err(|_| {
+            format!("Invalid merge threshold: {s}. Expected a number of lines or 'auto'")
+        })
+    }
+}
 
 /// Options for performing a search
+#[derive(Clone)]
 pub struct SearchOptions<'a> {
     pub path: &'a Path,
     pub queries: &'a [String],
@@ -15,13 +224,81 @@ pub struct SearchOptions<'a> {
     pub max_results: Option<usize>,
     pub max_bytes: Option<usize>,
     pub max_tokens: Option<usize>,
+    /// Count `max_tokens` exactly with this encoding instead of estimating from byte counts
+    pub tokenizer: Option<TokenizerKind>,
     pub allow_tests: bool,
     pub no_merge: bool,
-    pub merge_threshold: Option<usize>,
+    /// Maximum gap between merged blocks, fixed or tuned per file (default: 5 lines)
+    pub merge_threshold: Option<MergeThreshold>,
+    /// Only merge blocks that belong to the same top-level declaration, whatever their distance
+    pub merge_respect_boundaries: bool,
     pub dry_run: bool,
     pub session: Option<&'a str>,
     pub timeout: u64,
     pub question: Option<&'a str>,
     pub no_gitignore: bool,
     pub lsp: bool,
+    /// Final ordering of results; limits are always selected by score first
+    pub sort: ResultSort,
+    /// Treat queries as raw regular expressions instead of elastic search syntax
+    pub regex: bool,
+    /// Restrict regex matches to parts of the source; only used when `regex` is set
+    pub match_scope: MatchScope,
+    /// Return code blocks that contain no match for the query instead of those that do
+    pub invert: bool,
+    /// Only return blocks declaring these kinds of symbols, e.g. functions; only used when
+    /// `invert` is set
+    pub kinds: Option<Vec<SymbolKind>>,
+    /// Upweight results from files with recent git commits
+    pub recency_boost: bool,
+    /// Days after which the recency boost is halved; only used when `recency_boost` is set
+    pub recency_half_life_days: f64,
+    /// Attach the one-line signatures of enclosing named scopes (impl, class, module) to results
+    pub context_symbols: bool,
+    /// Maximum number of enclosing signatures per result; only used when `context_symbols` is set
+    pub context_symbols_depth: usize,
+    /// Attach a score breakdown (BM25, reranker delta, per-term tf/idf) to each result
+    pub explain: bool,
+    /// Reuse block tokenization from the persistent index for files unchanged since the last run
+    pub use_index: bool,
+    /// Rank code blocks or whole files
+    pub rank_mode: RankMode,
+    /// Only match and tokenize text inside these kinds of syntax nodes; files without a
+    /// tree-sitter grammar never match
+    pub within: Option<Vec<NodeKind>>,
+    /// Only search files changed in git since this ref (branch, tag or commit), compared with
+    /// its merge base with `HEAD` and including uncommitted and untracked files
+    pub changed_since: Option<String>,
+    /// Only search files tracked by git, leaving out untracked files even when no ignore rule
+    /// matches them; fails outside a git repository
+    pub tracked_only: bool,
+    /// Number of top BM25 candidates a BERT reranker rescores; the rest keep their BM25 order
+    pub rerank_top_k: usize,
+    /// How files are recognised as binary; binary files are not searched and are reported in
+    /// `skipped_files`
+    pub binary_detection: BinaryDetection,
+    /// Extensions (with or without the leading dot) always searched as text, whatever
+    /// `binary_detection` says
+    pub force_text_extensions: &'a [String],
+    /// Also search the text entries of zip and jar archives, reported under virtual
+    /// `archive.jar!path/in/archive` paths
+    pub search_archives: bool,
+    /// Encoding of the searched files: an encoding label such as `shift_jis` or `latin1`,
+    /// or `auto` to detect it per file. `None` reads files as UTF-8.
+    pub encoding: Option<String>,
+    /// Drop ranked results whose final reranker score is below this threshold, before the
+    /// result limits are applied
+    pub min_score: Option<f32>,
+    /// Follow symbolic links to files while walking `path`, and search a file reached through
+    /// several links once. Symlinked directories are walked either way
+    pub follow_symlinks: bool,
+    /// Name of the ranking profile (from `search.ranking_profiles` in the config) whose
+    /// weights rank the results. `None` uses the configured `search.ranking_profile`.
+    pub ranking_profile: Option<String>,
+    /// Collapse duplicate blocks after ranking, keeping the best-ranked of each group
+    pub dedup: DedupMode,
+    /// Further roots searched together with `path`, e.g. sibling repositories. Each root is
+    /// walked with its own ignore files; results are ranked, de-duplicated and limited as one
+    /// set and carry the root they came from in `root`
+    pub extra_roots: &'a [PathBuf],
 }
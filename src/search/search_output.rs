@@ -5,7 +5,8 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use probe_code::language::is_test_file;
-use probe_code::models::SearchResult;
+use probe_code::models::{ScoreBreakdown, SearchResult, SkipReason, SkippedFile, TermMatch};
+use probe_code::search::archive;
 use probe_code::search::query::QueryPlan;
 use probe_code::search::search_tokens::sum_tokens_with_deduplication;
 use probe_code::semantic_context::{
@@ -28,7 +29,8 @@ pub fn create_file_content_cache(results: &[&SearchResult]) -> HashMap<PathBuf,
 
     // Read each file once and cache the content
     for file_path in unique_files {
-        if let Ok(content) = std::fs::read_to_string(&file_path) {
+        if let Ok(bytes) = archive::read_file(&file_path) {
+            let content = String::from_utf8_lossy(&bytes).into_owned();
             cache.insert(file_path, Arc::new(content));
         }
     }
@@ -42,8 +44,10 @@ pub fn format_and_print_search_results(
     dry_run: bool,
     format: &str,
     query_plan: Option<&QueryPlan>,
-    skipped_files: Option<&[SearchResult]>,
+    skipped_files: Option<&[SkippedFile]>,
     limits: Option<&probe_code::models::SearchLimits>,
+    plain_snippet: bool,
+    unprocessed_on_timeout: Option<usize>,
 ) {
     let debug_mode = std::env::var("DEBUG").unwrap_or_default() == "1";
 
@@ -62,17 +66,37 @@ pub fn format_and_print_search_results(
             format_and_print_color_results(&valid_results, dry_run, query_plan, debug_mode);
         }
         "json" => {
-            if let Err(e) = format_and_print_json_results(&valid_results, skipped_files, limits) {
+            if let Err(e) = format_and_print_json_results(
+                &valid_results,
+                skipped_files,
+                limits,
+                unprocessed_on_timeout,
+            ) {
                 eprintln!("Error formatting JSON: {e}");
             }
             return; // Skip the summary output at the end
         }
+        "jsonl" => {
+            if let Err(e) = format_and_print_jsonl_results(
+                &valid_results,
+                skipped_files,
+                limits,
+                unprocessed_on_timeout,
+            ) {
+                eprintln!("Error formatting JSONL: {e}");
+            }
+            return; // Skip the summary output at the end
+        }
         "xml" => {
             if let Err(e) = format_and_print_xml_results(&valid_results, skipped_files, limits) {
                 eprintln!("Error formatting XML: {e}");
             }
             return; // Skip the summary output at the end
         }
+        "plain" => {
+            format_and_print_plain_results(&valid_results, plain_snippet);
+            return; // Nothing but the result lines
+        }
         "outline" => {
             let file_cache = create_file_content_cache(&valid_results);
             format_and_print_outline_results(&valid_results, dry_run, &file_cache);
@@ -101,31 +125,38 @@ pub fn format_and_print_search_results(
                     .unwrap_or("");
                 let is_full_file = result.node_type == "file";
 
+                let explain = score_breakdown_suffix(result)
+                    + &duplicates_suffix(result)
+                    + &root_suffix(result);
+
                 if dry_run {
                     // In dry-run mode, only print file names and line numbers
                     if is_full_file {
-                        println!("File: {}", result.file);
+                        println!("File: {}{explain}", result.file);
                     } else {
                         println!(
-                            "File: {}, Lines: {}-{}",
+                            "File: {}, Lines: {}-{}{explain}",
                             result.file, result.lines.0, result.lines.1
                         );
                     }
                 } else {
                     // Normal mode with full content or symbol display
                     if is_full_file {
-                        println!("File: {}", result.file);
+                        println!("File: {}{explain}", result.file);
                         println!("```{extension}");
                         println!("{}", result.code);
                         println!("```");
                     } else {
-                        println!("File: {}", result.file);
+                        println!("File: {}{explain}", result.file);
                         println!(
                             "Lines: {start}-{end}",
                             start = result.lines.0,
                             end = result.lines.1
                         );
                         println!("```{extension}");
+                        for line in context_symbol_lines(result) {
+                            println!("{line}");
+                        }
                         println!("{code}", code = result.code);
                         println!("```");
                     }
@@ -279,15 +310,17 @@ fn format_and_print_color_results(
         );
 
         // Print the file path and node info with color
+        let explain =
+            score_breakdown_suffix(result) + &duplicates_suffix(result) + &root_suffix(result);
         if is_full_file {
             println!(
-                "{label} {file}",
+                "{label} {file}{explain}",
                 label = "File:".bold().green().yellow(),
                 file = result.file
             );
         } else {
             println!(
-                "{} {} ({})",
+                "{} {} ({}){explain}",
                 "File:".bold().green(),
                 result.file.yellow(),
                 result.node_type.cyan()
@@ -396,6 +429,10 @@ fn format_and_print_color_results(
             patterns.push(RESULT_REGEX.clone());
         }
 
+        for line in context_symbol_lines(result) {
+            println!("{}", line.dimmed());
+        }
+
         // Process the code line by line with inline highlighting
         for line in result.code.lines() {
             let mut output_line = String::new();
@@ -557,6 +594,53 @@ fn format_and_print_color_results(
     println!("Total tokens returned: {total_tokens}");
 }
 
+/// Lines printed above a result's code for `--context-symbols`: each enclosing signature,
+/// with `...` standing in for the source skipped before the next one
+fn context_symbol_lines(result: &SearchResult) -> Vec<String> {
+    let Some(contexts) = &result.parent_context else {
+        return Vec::new();
+    };
+    let mut lines = Vec::new();
+    for (index, context) in contexts.iter().enumerate() {
+        lines.push(context.context_line.clone());
+        let next_start = contexts
+            .get(index + 1)
+            .map_or(result.lines.0, |next| next.start_line);
+        if next_start > context.start_line + 1 {
+            lines.push("...".to_string());
+        }
+    }
+    lines
+}
+
+/// Compact ` [bm25=3.20 rerank=+0.40]` suffix for results scored with `--explain`
+fn score_breakdown_suffix(result: &SearchResult) -> String {
+    match &result.score_breakdown {
+        Some(breakdown) => format!(
+            " [bm25={:.2} rerank={:+.2}]",
+            breakdown.bm25, breakdown.rerank
+        ),
+        None => String::new(),
+    }
+}
+
+/// ` (+3 duplicates)` suffix for results that other blocks were collapsed into by `--dedup`
+fn duplicates_suffix(result: &SearchResult) -> String {
+    match result.duplicates {
+        Some(1) => " (+1 duplicate)".to_string(),
+        Some(count) if count > 1 => format!(" (+{count} duplicates)"),
+        _ => String::new(),
+    }
+}
+
+/// ` [root: /work/api]` suffix naming the search root of results from multi-root searches
+fn root_suffix(result: &SearchResult) -> String {
+    match &result.root {
+        Some(root) => format!(" [root: {root}]"),
+        None => String::new(),
+    }
+}
+
 /// Helper function to escape XML special characters
 fn escape_xml(s: &str) -> String {
     s.replace("&", "&amp;")
@@ -569,66 +653,206 @@ fn escape_xml(s: &str) -> String {
 /// Format and print search results in JSON format
 fn format_and_print_json_results(
     results: &[&SearchResult],
-    skipped_files: Option<&[SearchResult]>,
+    skipped_files: Option<&[SkippedFile]>,
     limits: Option<&probe_code::models::SearchLimits>,
+    unprocessed_on_timeout: Option<usize>,
 ) -> Result<()> {
-    // Create a simplified version of the results for JSON output
-    #[derive(serde::Serialize)]
-    struct JsonResult<'a> {
-        file: &'a str,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        language: Option<&'static str>,
-        lines: [usize; 2],
-        node_type: &'a str,
-        code: &'a str,
-        // Structural classification of the block
-        scope: &'a str,
-        // Whether this result comes from a test file or contains test code
-        #[serde(skip_serializing_if = "Option::is_none")]
-        is_test: Option<bool>,
-        // Whether this result comes from a documentation file
-        #[serde(skip_serializing_if = "Option::is_none")]
-        is_doc: Option<bool>,
-        // Whether this result is a fenced code example in documentation
-        #[serde(skip_serializing_if = "Option::is_none")]
-        is_example: Option<bool>,
-        // The owning symbol name (function, class, method) for this block
-        #[serde(skip_serializing_if = "Option::is_none")]
-        owner_symbol: Option<String>,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        owner_qualified_symbol: Option<String>,
-        #[serde(skip_serializing_if = "Vec::is_empty")]
-        enclosing_symbols: Vec<EnclosingSymbol>,
-        #[serde(skip_serializing_if = "Option::is_none")]
-        enclosing_call: Option<EnclosingCall>,
-        #[serde(skip_serializing_if = "Vec::is_empty")]
-        enclosing_calls: Vec<EnclosingCall>,
-        // Raw source comments attached at the start of this block.
-        #[serde(skip_serializing_if = "Vec::is_empty")]
-        leading_comments: Vec<SourceComment>,
-        // Classified textual match locations within this returned block.
-        #[serde(skip_serializing_if = "Vec::is_empty")]
-        matches: Vec<SourceMatch>,
-        // Symbol signature (when symbols flag is used)
-        symbol_signature: Option<&'a String>,
-        // Include other relevant fields
-        matched_keywords: Option<&'a Vec<String>>,
-        score: Option<f64>,
-        tfidf_score: Option<f64>,
-        bm25_score: Option<f64>,
-        file_unique_terms: Option<usize>,
-        file_total_matches: Option<usize>,
-        block_unique_terms: Option<usize>,
-        block_total_matches: Option<usize>,
-    }
-
-    #[derive(serde::Serialize)]
-    struct SkippedFileInfo {
-        file: String,
-        uniq: usize,
-        all: usize,
+    let wrapper = build_json_output(results, skipped_files, limits, unprocessed_on_timeout);
+    println!("{json}", json = serde_json::to_string_pretty(&wrapper)?);
+    Ok(())
+}
+
+/// Print one `path:line` line per result, or `path:line:snippet` with the first line of
+/// the result's code, without colors or any other output
+fn format_and_print_plain_results(results: &[&SearchResult], snippet: bool) {
+    use std::io::Write;
+
+    let stdout = std::io::stdout();
+    let mut out = std::io::BufWriter::new(stdout.lock());
+    for result in results {
+        let written = if snippet {
+            let first_line = result.code.lines().next().unwrap_or("").trim();
+            writeln!(out, "{}:{}:{first_line}", result.file, result.lines.0)
+        } else {
+            writeln!(out, "{}:{}", result.file, result.lines.0)
+        };
+        // Stop quietly when the reader goes away, e.g. `| head`
+        if written.is_err() {
+            return;
+        }
+    }
+    let _ = out.flush();
+}
+
+/// Print one compact JSON line per result, then a final line with the remaining fields of
+/// the JSON output (`summary`, `limits`, `skipped_files`, `version`)
+fn format_and_print_jsonl_results(
+    results: &[&SearchResult],
+    skipped_files: Option<&[SkippedFile]>,
+    limits: Option<&probe_code::models::SearchLimits>,
+    unprocessed_on_timeout: Option<usize>,
+) -> Result<()> {
+    use std::io::Write;
+
+    let mut wrapper = build_json_output(results, skipped_files, limits, unprocessed_on_timeout);
+    let json_results = wrapper
+        .as_object_mut()
+        .and_then(|object| object.remove("results"));
+
+    let stdout = std::io::stdout();
+    let mut out = std::io::BufWriter::new(stdout.lock());
+    if let Some(serde_json::Value::Array(json_results)) = json_results {
+        for result in json_results {
+            writeln!(out, "{}", serde_json::to_string(&result)?)?;
+        }
     }
+    writeln!(out, "{}", serde_json::to_string(&wrapper)?)?;
+    out.flush()?;
+    Ok(())
+}
+
+/// JSON output of `probe search`
+#[derive(serde::Serialize, schemars::JsonSchema)]
+struct SearchJsonOutput<'a> {
+    /// Matching code blocks, best first
+    results: Vec<JsonResult<'a>>,
+    summary: JsonSummary,
+    /// Version of probe that produced the output
+    version: &'static str,
+    /// Limits the search ran with
+    #[serde(skip_serializing_if = "Option::is_none")]
+    limits: Option<JsonLimits>,
+    /// Files whose matches were left out because of a limit
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    skipped_files: Vec<SkippedFileInfo>,
+}
+
+/// A matching code block
+#[derive(serde::Serialize, schemars::JsonSchema)]
+struct JsonResult<'a> {
+    file: &'a str,
+    /// Search root the file was found under (when several roots were searched)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    root: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    language: Option<&'static str>,
+    /// First and last line of the block
+    lines: [usize; 2],
+    node_type: &'a str,
+    code: &'a str,
+    /// Structural classification of the block
+    scope: &'a str,
+    /// Whether this result comes from a test file or contains test code
+    #[serde(skip_serializing_if = "Option::is_none")]
+    is_test: Option<bool>,
+    /// Whether this result comes from a documentation file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    is_doc: Option<bool>,
+    /// Whether this result is a fenced code example in documentation
+    #[serde(skip_serializing_if = "Option::is_none")]
+    is_example: Option<bool>,
+    /// The owning symbol name (function, class, method) for this block
+    #[serde(skip_serializing_if = "Option::is_none")]
+    owner_symbol: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    owner_qualified_symbol: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    enclosing_symbols: Vec<EnclosingSymbol>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    enclosing_call: Option<EnclosingCall>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    enclosing_calls: Vec<EnclosingCall>,
+    /// Raw source comments attached at the start of this block.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    leading_comments: Vec<SourceComment>,
+    /// Classified textual match locations within this returned block.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    matches: Vec<SourceMatch>,
+    /// Symbol signature (when symbols flag is used)
+    symbol_signature: Option<&'a String>,
+    /// Enclosing scope signatures, outermost first (when --context-symbols is used)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    context_symbols: Option<Vec<ContextSymbol<'a>>>,
+    // Include other relevant fields
+    matched_keywords: Option<&'a Vec<String>>,
+    /// Byte range, line and column of each query term match within `code`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    term_matches: Option<&'a Vec<TermMatch>>,
+    score: Option<f64>,
+    tfidf_score: Option<f64>,
+    bm25_score: Option<f64>,
+    file_unique_terms: Option<usize>,
+    file_total_matches: Option<usize>,
+    block_unique_terms: Option<usize>,
+    block_total_matches: Option<usize>,
+    /// BM25, reranker adjustment and per-term tf/idf (when --explain is used)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    score_breakdown: Option<&'a ScoreBreakdown>,
+    /// Number of duplicate blocks collapsed into this one (when --dedup is used)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    duplicates: Option<usize>,
+}
+
+/// Signature of a scope enclosing a result
+#[derive(serde::Serialize, schemars::JsonSchema)]
+struct ContextSymbol<'a> {
+    node_type: &'a str,
+    lines: [usize; 2],
+    signature: &'a str,
+}
+
+/// Totals over the returned results
+#[derive(serde::Serialize, schemars::JsonSchema)]
+struct JsonSummary {
+    count: usize,
+    total_bytes: usize,
+    total_tokens: usize,
+    /// Whether the timeout passed before every file was processed, leaving partial results
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    timed_out: bool,
+    /// Number of files left unprocessed because of the timeout
+    #[serde(skip_serializing_if = "Option::is_none")]
+    unprocessed_files: Option<usize>,
+}
+
+/// Configured limits and the size of the returned results
+#[derive(serde::Serialize, schemars::JsonSchema)]
+struct JsonLimits {
+    max_results: Option<usize>,
+    max_bytes: Option<usize>,
+    max_tokens: Option<usize>,
+    /// Minimum final score of returned results
+    min_score: Option<f32>,
+    /// Number of ranked results dropped for scoring below `min_score`
+    below_min_score: usize,
+    total_bytes: usize,
+    total_tokens: usize,
+}
+
+/// A file with matching blocks that were left out of the results
+#[derive(serde::Serialize, schemars::JsonSchema)]
+struct SkippedFileInfo {
+    file: String,
+    /// Distinct query terms matched by the skipped blocks
+    uniq: usize,
+    /// Number of skipped blocks
+    all: usize,
+    /// Limit that left out the file's best block
+    reason: SkipReason,
+}
 
+/// JSON Schema of the `--format json` output of `probe search`
+pub fn search_json_schema() -> schemars::Schema {
+    probe_code::utils::json_schema::output_schema::<SearchJsonOutput<'static>>()
+}
+
+/// Build the JSON search output: the results plus summary, limits and skipped files
+fn build_json_output(
+    results: &[&SearchResult],
+    skipped_files: Option<&[SkippedFile]>,
+    limits: Option<&probe_code::models::SearchLimits>,
+    unprocessed_on_timeout: Option<usize>,
+) -> serde_json::Value {
     let mut parsed_files: std::collections::HashMap<
         std::path::PathBuf,
         Option<ParsedSourceContext>,
@@ -676,6 +900,7 @@ fn format_and_print_json_results(
 
             JsonResult {
                 file: &r.file,
+                root: r.root.as_deref(),
                 language: language_name_for_path(file_path),
                 lines: [r.lines.0, r.lines.1],
                 node_type: &r.node_type,
@@ -705,7 +930,18 @@ fn format_and_print_json_results(
                 leading_comments,
                 matches,
                 symbol_signature: r.symbol_signature.as_ref(),
+                context_symbols: r.parent_context.as_ref().map(|contexts| {
+                    contexts
+                        .iter()
+                        .map(|context| ContextSymbol {
+                            node_type: &context.node_type,
+                            lines: [context.start_line, context.end_line],
+                            signature: context.context_line.trim(),
+                        })
+                        .collect()
+                }),
                 matched_keywords: r.matched_keywords.as_ref(),
+                term_matches: r.term_matches.as_ref(),
                 score: r.score,
                 tfidf_score: r.tfidf_score,
                 bm25_score: r.bm25_score,
@@ -713,6 +949,8 @@ fn format_and_print_json_results(
                 file_total_matches: r.file_total_matches,
                 block_unique_terms: r.block_unique_terms,
                 block_total_matches: r.block_total_matches,
+                score_breakdown: r.score_breakdown.as_ref(),
+                duplicates: r.duplicates,
             }
         })
         .collect();
@@ -723,16 +961,21 @@ fn format_and_print_json_results(
     let total_tokens = sum_tokens_with_deduplication(&code_blocks);
 
     // Process skipped files if provided
-    let skipped_file_list: Option<Vec<SkippedFileInfo>> = skipped_files.map(|skipped| {
+    let skipped_file_list: Vec<SkippedFileInfo> = skipped_files.map_or_else(Vec::new, |skipped| {
         use std::collections::HashMap;
         use std::collections::HashSet;
 
-        let mut file_matches: HashMap<String, (HashSet<String>, usize)> = HashMap::new();
+        // Skipped results are in rank order, so a file keeps the reason of its best block
+        let mut file_matches: HashMap<String, (HashSet<String>, usize, SkipReason)> =
+            HashMap::new();
 
-        for result in skipped {
-            let entry = file_matches
-                .entry(result.file.clone())
-                .or_insert((HashSet::new(), 0));
+        for skipped_file in skipped {
+            let result = &skipped_file.result;
+            let entry = file_matches.entry(result.file.clone()).or_insert((
+                HashSet::new(),
+                0,
+                skipped_file.reason,
+            ));
 
             if let Some(keywords) = &result.matched_keywords {
                 for keyword in keywords {
@@ -744,10 +987,11 @@ fn format_and_print_json_results(
 
         let mut list: Vec<SkippedFileInfo> = file_matches
             .into_iter()
-            .map(|(file, (unique, total))| SkippedFileInfo {
+            .map(|(file, (unique, total, reason))| SkippedFileInfo {
                 file,
                 uniq: unique.len(),
                 all: total,
+                reason,
             })
             .collect();
 
@@ -756,37 +1000,30 @@ fn format_and_print_json_results(
         list
     });
 
-    // Create a wrapper object with results and summary
-    let mut wrapper = serde_json::json!({
-        "results": json_results,
-        "summary": {
-            "count": results.len(),
-            "total_bytes": results.iter().map(|r| r.code.len()).sum::<usize>(),
-            "total_tokens": total_tokens,
+    // Create a wrapper object with results, summary, limits and skipped files
+    let output = SearchJsonOutput {
+        results: json_results,
+        summary: JsonSummary {
+            count: results.len(),
+            total_bytes: results.iter().map(|r| r.code.len()).sum::<usize>(),
+            total_tokens,
+            timed_out: unprocessed_on_timeout.is_some(),
+            unprocessed_files: unprocessed_on_timeout,
         },
-        "version": probe_code::version::get_version()
-    });
-
-    // Add limits if provided
-    if let Some(limits_data) = limits {
-        wrapper["limits"] = serde_json::json!({
-            "max_results": limits_data.max_results,
-            "max_bytes": limits_data.max_bytes,
-            "max_tokens": limits_data.max_tokens,
-            "total_bytes": limits_data.total_bytes,
-            "total_tokens": limits_data.total_tokens,
-        });
-    }
-
-    // Add skipped files if provided
-    if let Some(skipped_list) = skipped_file_list {
-        if !skipped_list.is_empty() {
-            wrapper["skipped_files"] = serde_json::json!(skipped_list);
-        }
-    }
+        version: probe_code::version::get_version(),
+        limits: limits.map(|limits_data| JsonLimits {
+            max_results: limits_data.max_results,
+            max_bytes: limits_data.max_bytes,
+            max_tokens: limits_data.max_tokens,
+            min_score: limits_data.min_score,
+            below_min_score: limits_data.below_min_score,
+            total_bytes: limits_data.total_bytes,
+            total_tokens: limits_data.total_tokens,
+        }),
+        skipped_files: skipped_file_list,
+    };
 
-    println!("{json}", json = serde_json::to_string_pretty(&wrapper)?);
-    Ok(())
+    serde_json::to_value(output).expect("search output is always valid JSON")
 }
 
 /// Check if a file is a documentation/help file based on path and extension.
@@ -1076,7 +1313,7 @@ fn extract_owner_symbol(code: &str, node_type: &str) -> Option<String> {
 /// Format and print search results in XML format
 fn format_and_print_xml_results(
     results: &[&SearchResult],
-    skipped_files: Option<&[SearchResult]>,
+    skipped_files: Option<&[SkippedFile]>,
     limits: Option<&probe_code::models::SearchLimits>,
 ) -> Result<()> {
     println!("<?xml version=\"1.0\" encoding=\"UTF-8\"?>");
@@ -1085,6 +1322,9 @@ fn format_and_print_xml_results(
     for result in results {
         println!("  <result>");
         println!("    <file>{}</file>", result.file);
+        if let Some(root) = &result.root {
+            println!("    <root>{root}</root>");
+        }
         println!(
             "    <lines>{start}-{end}</lines>",
             start = result.lines.0,
@@ -1096,6 +1336,20 @@ fn format_and_print_xml_results(
             println!("    <symbol_signature>{symbol_signature}</symbol_signature>");
         }
 
+        if let Some(contexts) = &result.parent_context {
+            println!("    <context_symbols>");
+            for context in contexts {
+                println!(
+                    "      <symbol node_type=\"{}\" lines=\"{}-{}\">{}</symbol>",
+                    escape_xml(&context.node_type),
+                    context.start_line,
+                    context.end_line,
+                    escape_xml(context.context_line.trim())
+                );
+            }
+            println!("    </context_symbols>");
+        }
+
         if let Some(keywords) = &result.matched_keywords {
             println!("    <matched_keywords>");
             for keyword in keywords {
@@ -1166,6 +1420,13 @@ fn format_and_print_xml_results(
         if let Some(max_tokens) = limits_data.max_tokens {
             println!("    <max_tokens>{max_tokens}</max_tokens>");
         }
+        if let Some(min_score) = limits_data.min_score {
+            println!("    <min_score>{min_score}</min_score>");
+            println!(
+                "    <below_min_score>{}</below_min_score>",
+                limits_data.below_min_score
+            );
+        }
         println!("    <total_bytes>{}</total_bytes>", limits_data.total_bytes);
         println!(
             "    <total_tokens>{}</total_tokens>",
@@ -1180,12 +1441,16 @@ fn format_and_print_xml_results(
             use std::collections::HashMap;
             use std::collections::HashSet;
 
-            let mut file_matches: HashMap<String, (HashSet<String>, usize)> = HashMap::new();
+            let mut file_matches: HashMap<String, (HashSet<String>, usize, SkipReason)> =
+                HashMap::new();
 
-            for result in skipped {
-                let entry = file_matches
-                    .entry(result.file.clone())
-                    .or_insert((HashSet::new(), 0));
+            for skipped_file in skipped {
+                let result = &skipped_file.result;
+                let entry = file_matches.entry(result.file.clone()).or_insert((
+                    HashSet::new(),
+                    0,
+                    skipped_file.reason,
+                ));
 
                 if let Some(keywords) = &result.matched_keywords {
                     for keyword in keywords {
@@ -1195,19 +1460,20 @@ fn format_and_print_xml_results(
                 entry.1 += 1;
             }
 
-            let mut list: Vec<(String, usize, usize)> = file_matches
+            let mut list: Vec<(String, usize, usize, SkipReason)> = file_matches
                 .into_iter()
-                .map(|(file, (unique, total))| (file, unique.len(), total))
+                .map(|(file, (unique, total, reason))| (file, unique.len(), total, reason))
                 .collect();
 
             list.sort_by(|a, b| b.1.cmp(&a.1).then(b.2.cmp(&a.2)));
 
             println!("  <skipped_files count=\"{}\">", list.len());
-            for (file, unique_matches, total_matches) in list {
+            for (file, unique_matches, total_matches, reason) in list {
                 println!("    <file>");
                 println!("      <path>{}</path>", escape_xml(&file));
                 println!("      <uniq>{unique_matches}</uniq>");
                 println!("      <all>{total_matches}</all>");
+                println!("      <reason>{}</reason>", reason.as_str());
                 println!("    </file>");
             }
             println!("  </skipped_files>");
@@ -2349,7 +2615,7 @@ fn get_comment_prefix(extension: &str) -> &'static str {
     match extension {
         // C-style comments
         "rs" | "c" | "h" | "cpp" | "cc" | "cxx" | "hpp" | "hxx" | "java" | "js" | "jsx" | "ts"
-        | "tsx" | "cs" | "swift" | "go" | "php" | "sol" => "//",
+        | "tsx" | "cs" | "kt" | "kts" | "swift" | "go" | "php" | "sol" => "//",
 
         // Python-style comments
         "py" | "rb" | "cr" | "sh" | "bash" | "pl" | "r" | "yaml" | "yml" => "#",
@@ -2836,7 +3102,7 @@ fn format_and_print_outline_xml_results(
     results: &[&SearchResult],
     dry_run: bool,
     file_cache: &HashMap<PathBuf, Arc<String>>,
-    skipped_files: Option<&[SearchResult]>,
+    skipped_files: Option<&[SkippedFile]>,
     limits: Option<&probe_code::models::SearchLimits>,
 ) -> Result<()> {
     // Track content for accounting
@@ -2979,6 +3245,13 @@ fn format_and_print_outline_xml_results(
         if let Some(max_tokens) = limits_data.max_tokens {
             println!("  <max_tokens>{max_tokens}</max_tokens>");
         }
+        if let Some(min_score) = limits_data.min_score {
+            println!("  <min_score>{min_score}</min_score>");
+            println!(
+                "  <below_min_score>{}</below_min_score>",
+                limits_data.below_min_score
+            );
+        }
         println!("  <total_bytes>{}</total_bytes>", limits_data.total_bytes);
         println!(
             "  <total_tokens>{}</total_tokens>",
@@ -2993,12 +3266,16 @@ fn format_and_print_outline_xml_results(
             use std::collections::HashMap;
             use std::collections::HashSet;
 
-            let mut file_matches: HashMap<String, (HashSet<String>, usize)> = HashMap::new();
+            let mut file_matches: HashMap<String, (HashSet<String>, usize, SkipReason)> =
+                HashMap::new();
 
-            for result in skipped {
-                let entry = file_matches
-                    .entry(result.file.clone())
-                    .or_insert((HashSet::new(), 0));
+            for skipped_file in skipped {
+                let result = &skipped_file.result;
+                let entry = file_matches.entry(result.file.clone()).or_insert((
+                    HashSet::new(),
+                    0,
+                    skipped_file.reason,
+                ));
 
                 if let Some(keywords) = &result.matched_keywords {
                     for keyword in keywords {
@@ -3008,19 +3285,20 @@ fn format_and_print_outline_xml_results(
                 entry.1 += 1;
             }
 
-            let mut list: Vec<(String, usize, usize)> = file_matches
+            let mut list: Vec<(String, usize, usize, SkipReason)> = file_matches
                 .into_iter()
-                .map(|(file, (unique, total))| (file, unique.len(), total))
+                .map(|(file, (unique, total, reason))| (file, unique.len(), total, reason))
                 .collect();
 
             list.sort_by(|a, b| b.1.cmp(&a.1).then(b.2.cmp(&a.2)));
 
             println!("<skipped_files count=\"{}\">", list.len());
-            for (file, unique_matches, total_matches) in list {
+            for (file, unique_matches, total_matches, reason) in list {
                 println!("  <file>");
                 println!("    <path>{}</path>", escape_xml(&file));
                 println!("    <uniq>{unique_matches}</uniq>");
                 println!("    <all>{total_matches}</all>");
+                println!("    <reason>{}</reason>", reason.as_str());
                 println!("  </file>");
             }
             println!("</skipped_files>");
@@ -3365,6 +3643,10 @@ mod tests {
             tokenized_content: None,
             lsp_info: None,
             parent_context: None,
+            score_breakdown: None,
+            term_matches: None,
+            duplicates: None,
+            root: None,
         };
 
         let result2 = SearchResult {
@@ -3395,6 +3677,10 @@ mod tests {
             tokenized_content: None,
             lsp_info: None,
             parent_context: None,
+            score_breakdown: None,
+            term_matches: None,
+            duplicates: None,
+            root: None,
         };
 
         let results = vec![&result1, &result2];
@@ -3416,6 +3702,61 @@ mod tests {
         assert_eq!(cache.get(&path2).unwrap().as_ref(), content2);
     }
 
+    #[test]
+    fn test_context_symbol_lines_mark_skipped_source() {
+        use probe_code::models::ParentContext;
+
+        let context = |node_type: &str, start_line, end_line, line: &str| ParentContext {
+            node_type: node_type.to_string(),
+            start_line,
+            end_line,
+            context_line: line.to_string(),
+            preceding_comments: Vec::new(),
+        };
+        let result = SearchResult {
+            file: "src/billing.rs".to_string(),
+            lines: (8, 10),
+            node_type: "function_item".to_string(),
+            code: "        pub fn settle(&self) {\n        }".to_string(),
+            symbol_signature: None,
+            matched_by_filename: None,
+            rank: None,
+            score: None,
+            tfidf_score: None,
+            tfidf_rank: None,
+            bm25_score: None,
+            bm25_rank: None,
+            combined_score_rank: None,
+            new_score: None,
+            hybrid2_rank: None,
+            file_unique_terms: None,
+            file_total_matches: None,
+            file_match_rank: None,
+            block_unique_terms: None,
+            block_total_matches: None,
+            parent_file_id: None,
+            block_id: None,
+            matched_lines: None,
+            matched_keywords: None,
+            tokenized_content: None,
+            lsp_info: None,
+            parent_context: Some(vec![
+                context("mod_item", 1, 20, "mod billing {"),
+                context("impl_item", 2, 12, "    impl Invoice {"),
+            ]),
+            score_breakdown: None,
+            term_matches: None,
+            duplicates: None,
+            root: None,
+        };
+
+        // The impl directly follows the module line; the fn starts further down
+        assert_eq!(
+            context_symbol_lines(&result),
+            vec!["mod billing {", "    impl Invoice {", "..."]
+        );
+    }
+
     #[test]
     fn test_is_test_code_block_rust() {
         assert!(is_test_code_block(
@@ -1,14 +1,18 @@
 use anyhow::Result;
+use probe_code::encoding::SourceEncoding;
 use probe_code::file_guard;
 use probe_code::search::file_list_cache;
 use rayon::prelude::*;
 use std::collections::{HashMap, HashSet};
+use std::ops::ControlFlow;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 // No need for term_exceptions import
 
-use probe_code::models::{LimitedSearchResults, SearchResult};
+use probe_code::models::{
+    LimitedSearchResults, SearchLimits, SearchResult, SkipReason, SkippedFile,
+};
 
 /// Configuration for search with structured patterns
 #[derive(Debug, Clone)]
@@ -17,20 +21,49 @@ pub struct SearchConfig<'a> {
     pub allow_tests: bool,
     pub language: Option<&'a str>,
     pub no_gitignore: bool,
+    /// Follow symbolic links to files; symlinked directories are always walked
+    pub follow_symlinks: bool,
+    /// Only search these files
+    pub changed_files: Option<&'a ChangedFiles>,
+    /// Only search files tracked by git
+    pub tracked_files: Option<&'a TrackedFiles>,
+    /// How files are recognised as binary and skipped
+    pub binary_detection: file_guard::BinaryDetection,
+    /// Extensions always searched as text
+    pub force_text_extensions: &'a [String],
+    /// Also search the text entries of zip and jar archives
+    pub search_archives: bool,
+    /// Decode files with this encoding instead of UTF-8
+    pub encoding: Option<SourceEncoding>,
+    /// Files not yet scanned when this passes are skipped, once some file has matched
+    pub deadline: SearchDeadline,
 }
 use probe_code::path_resolver::resolve_path;
+use probe_code::ranking::{
+    rank_files, resolve_ranking_profile, retain_min_score, RankingProfile, TopK,
+};
 use probe_code::search::{
+    archive,
     cache,
+    changed_files::ChangedFiles,
+    dedup::dedup_results,
     early_ranker,
     // file_list_cache, // Add the new file_list_cache module (unused)
     file_processing::{process_file_with_results, FileProcessingParams},
     filters::SearchFilters,
-    query::{create_structured_patterns, QueryPlan},
-    result_ranking::rank_search_results,
-    search_limiter::apply_limits,
-    search_options::SearchOptions,
+    invert_search::{search_inverted, InvertSearchConfig},
+    multi_root,
+    node_scope::restrict_term_matches,
+    query::{create_structured_patterns, QueryPlan, TermMatcher},
+    recency_boost::apply_recency_boost,
+    regex_search::{search_with_regex, RegexSearchConfig},
+    result_ranking::{attach_score_breakdowns, drop_excluded_results, rank_search_results},
+    search_index::SearchIndex,
+    search_limiter::{apply_limits, estimate_full_size},
+    search_options::{DedupMode, RankMode, ResultSort, SearchOptions},
     simd_pattern_matching::{SimdPatternConfig, SimdPatternMatcher},
-    timeout,
+    timeout::SearchDeadline,
+    tracked_files::TrackedFiles,
 };
 
 /// Struct to hold timing information for different stages of the search process
@@ -219,47 +252,365 @@ pub fn print_timings(timings: &SearchTimings) {
 
 // Removed evaluate_ignoring_negatives helper function in favor of direct usage
 
+/// Callback receiving streamed search results; returning `ControlFlow::Break` stops the search
+type ResultSink<'s> = &'s mut dyn FnMut(SearchResult) -> ControlFlow<()>;
+
 /// Our main "perform_probe" function remains largely the same. Below we show how you might
 /// incorporate "search_with_structured_patterns" to handle the AST logic in a specialized path.
 /// For simplicity, we won't fully replace the existing logic. Instead, we'll demonstrate
 /// how you'd do it if you wanted to leverage the new approach.
 pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
-    // Start timing the entire search process
-    let total_start = Instant::now();
+    if options.extra_roots.is_empty() {
+        run_probe(
+            options,
+            None,
+            true,
+            SearchDeadline::after_seconds(options.timeout),
+        )
+    } else {
+        run_probe_roots(options)
+    }
+}
 
-    let SearchOptions {
-        path,
-        queries,
-        files_only,
-        custom_ignores,
-        exclude_filenames,
-        reranker,
-        frequency_search: _,
-        exact,
-        language,
-        max_results,
-        max_bytes,
-        max_tokens,
-        allow_tests,
-        no_merge,
-        merge_threshold,
-        dry_run: _, // We don't need this in perform_probe, but need to include it in the pattern
-        session,
-        timeout,
-        question,
-        no_gitignore,
-        lsp,
-    } = options;
-    // Start the timeout thread
-    let timeout_handle = timeout::start_timeout_thread(*timeout);
+/// Stream search results to `on_result` as files are processed, without ranking them globally.
+///
+/// Files are visited in early-rank order and processed in batches; each batch is ranked on its
+/// own and its results are emitted before the next batch is read, so memory stays bounded by
+/// one batch. Results therefore come in batch order, not best first: a strong match in a late
+/// batch is emitted after weaker matches from earlier ones, and scores are only comparable
+/// within a batch. Use `perform_probe` or `perform_probe_top_k` when the best results must come
+/// first.
+///
+/// Returning `ControlFlow::Break` from the callback skips all remaining files. Limits, session
+/// caching and block merging are not applied: the caller decides how many results it needs.
+/// `rank` is the emission order.
+///
+/// Inverted, regex and files-only searches have no incremental pipeline; their results are
+/// collected first and then emitted. Returns the number of results emitted.
+pub fn perform_probe_unranked<F>(options: &SearchOptions, mut on_result: F) -> Result<usize>
+where
+    F: FnMut(SearchResult) -> ControlFlow<()>,
+{
+    let mut emitted = 0;
+    let mut counting_sink = |mut result: SearchResult| {
+        emitted += 1;
+        result.rank = Some(emitted);
+        on_result(result)
+    };
 
-    let include_filenames = !exclude_filenames;
+    if options.invert || options.regex || options.files_only || !options.extra_roots.is_empty() {
+        for result in perform_probe(options)?.results {
+            if counting_sink(result).is_break() {
+                break;
+            }
+        }
+    } else {
+        run_probe(
+            options,
+            Some(&mut counting_sink),
+            true,
+            SearchDeadline::after_seconds(options.timeout),
+        )?;
+    }
+    Ok(emitted)
+}
+
+/// Search like `perform_probe_unranked`, but emit only the `k` best-scoring results, best first,
+/// with `rank` their position.
+///
+/// Each batch is ranked as it is processed and offered to a bounded top-k heap, so memory stays
+/// bounded by one batch plus `k` results however large the tree is. Every file is processed
+/// before the first result is emitted; returning `ControlFlow::Break` from the callback stops
+/// the emission. Returns the number of results emitted.
+pub fn perform_probe_top_k<F>(options: &SearchOptions, k: usize, mut on_result: F) -> Result<usize>
+where
+    F: FnMut(SearchResult) -> ControlFlow<()>,
+{
+    let mut top = TopK::new(k);
+    if options.invert || options.regex || options.files_only || !options.extra_roots.is_empty() {
+        for result in perform_probe(options)?.results {
+            top.offer(result);
+        }
+    } else {
+        let mut offer = |result: SearchResult| {
+            top.offer(result);
+            ControlFlow::Continue(())
+        };
+        run_probe(
+            options,
+            Some(&mut offer),
+            true,
+            SearchDeadline::after_seconds(options.timeout),
+        )?;
+    }
+
+    let mut emitted = 0;
+    for mut result in top.into_sorted_vec() {
+        emitted += 1;
+        result.rank = Some(emitted);
+        if on_result(result).is_break() {
+            break;
+        }
+    }
+    Ok(emitted)
+}
+
+/// Search each root of a multi-root search on its own, then rank, de-duplicate and limit the
+/// results of all roots together. The session cache is applied to the merged results only, so
+/// blocks cut by the limits are not remembered as seen.
+fn run_probe_roots(options: &SearchOptions) -> Result<LimitedSearchResults> {
     let debug_mode = std::env::var("DEBUG").unwrap_or_default() == "1";
+    // Inverted and regex searches do not use sessions, as with a single root
+    let (effective_session, session_was_generated) = if options.invert || options.regex {
+        (None, false)
+    } else {
+        resolve_session(options.session, debug_mode)
+    };
+    // The roots share one deadline, so the timeout covers the whole search
+    let deadline = SearchDeadline::after_seconds(options.timeout);
+
+    let mut per_root = Vec::new();
+    let mut skipped_files = Vec::new();
+    let mut below_min_score = 0;
+    let mut unprocessed_files = 0;
+    let mut timed_out = false;
+    for root in multi_root::distinct_roots(options.path, options.extra_roots) {
+        // Limits, de-duplication and the final order apply to the merged results
+        let root_options = SearchOptions {
+            path: &root,
+            extra_roots: &[],
+            max_results: None,
+            max_bytes: None,
+            max_tokens: None,
+            sort: ResultSort::Score,
+            dedup: DedupMode::None,
+            ..options.clone()
+        };
+        let limited = run_probe(&root_options, None, false, deadline)?;
+        if debug_mode {
+            println!(
+                "DEBUG: Root {} returned {} results",
+                root.display(),
+                limited.results.len()
+            );
+        }
 
-    // Handle session ID generation if session is provided but empty
-    // For test runs, force session to None to disable caching
-    let (effective_session, session_was_generated) = if let Some(s) = session {
-        if s.is_empty() || *s == "new" {
+        skipped_files.extend(limited.skipped_files);
+        below_min_score += limited
+            .limits_applied
+            .map_or(0, |limits| limits.below_min_score);
+        unprocessed_files += limited.files_skipped_early_termination.unwrap_or(0);
+        timed_out |= limited.timed_out;
+        per_root.push((root, limited.results));
+    }
+    let mut results = multi_root::merge_root_results(per_root);
+    dedup_results(&mut results, options.dedup);
+
+    let raw_query = options.queries.join(" AND ");
+    let mut cached_blocks = Vec::new();
+    if let Some(session_id) = effective_session {
+        match cache::filter_results_with_cache(&results, session_id, &raw_query) {
+            Ok((unseen, seen)) => {
+                results = unseen;
+                cached_blocks = seen;
+            }
+            Err(e) => eprintln!("Error checking cache: {e}"),
+        }
+    }
+
+    let mut limited = limit_results(results, options, below_min_score);
+    skipped_files.append(&mut limited.skipped_files);
+    limited.skipped_files = skipped_files;
+    limited.cached_blocks_skipped = (!cached_blocks.is_empty()).then_some(cached_blocks.len());
+    // Report which blocks were suppressed when the caller asked for an explanation
+    if options.explain {
+        cached_blocks.sort();
+        cached_blocks.dedup();
+        limited.cached_blocks = cached_blocks;
+    }
+    flag_timeout(&mut limited, unprocessed_files);
+    limited.timed_out |= timed_out;
+    options.sort.apply(&mut limited.results);
+
+    if let Some(session_id) = effective_session {
+        if let Err(e) = cache::add_results_to_cache(&limited.results, session_id, &raw_query) {
+            eprintln!("Error adding results to cache: {e}");
+        }
+        if session_was_generated {
+            println!("Session ID: {session_id} (generated - ALWAYS USE IT in future sessions for caching)");
+        } else {
+            println!("Session ID: {session_id}");
+        }
+    }
+
+    Ok(limited)
+}
+
+/// Rank results with the configured reranker, or skip ranking for exact searches, then apply
+/// a deterministic order so results with equal scores do not shuffle between runs. Returns
+/// the number of results dropped for scoring below `min_score`.
+fn rank_and_order_results(
+    results: &mut Vec<SearchResult>,
+    options: &SearchOptions,
+    profile: &RankingProfile,
+    skip_ranking: bool,
+    debug_mode: bool,
+) -> usize {
+    if !skip_ranking {
+        // Only perform ranking if exact flag is not set
+        rank_search_results(
+            results,
+            options.queries,
+            options.reranker,
+            options.question,
+            options.rerank_top_k,
+            options.session,
+            profile,
+        );
+        if options.explain {
+            attach_score_breakdowns(results, options.queries, options.reranker, profile);
+        }
+
+        // Excluded terms are matched on stemmed tokens after ranking, so negatives always win
+        let excluded = drop_excluded_results(results, options.queries);
+        if debug_mode && excluded > 0 {
+            println!("DEBUG: Removed {excluded} results containing excluded terms");
+        }
+
+        // Blend git recency into the reranker scores (no-op outside git repositories)
+        if options.recency_boost {
+            let boosted =
+                apply_recency_boost(results, options.path, options.recency_half_life_days);
+            if debug_mode {
+                println!("DEBUG: Recency boost applied: {boosted}");
+            }
+        }
+
+        // Apply deterministic secondary sort to ensure consistent ordering for results with equal scores
+        // This prevents non-deterministic behavior when results have the same ranking score
+        results.sort_by(|a, b| {
+            // First sort by score (if available), then by file path, then by line number
+            match (a.rank, b.rank) {
+                (Some(rank_a), Some(rank_b)) => {
+                    // If ranks are different, use rank ordering
+                    match rank_a.cmp(&rank_b) {
+                        std::cmp::Ordering::Equal => {
+                            // If ranks are equal, use deterministic secondary sort
+                            (&a.file, a.lines.0).cmp(&(&b.file, b.lines.0))
+                        }
+                        other => other,
+                    }
+                }
+                (Some(_), None) => std::cmp::Ordering::Less,
+                (None, Some(_)) => std::cmp::Ordering::Greater,
+                (None, None) => {
+                    // If no ranks, sort by file path and line number
+                    (&a.file, a.lines.0).cmp(&(&b.file, b.lines.0))
+                }
+            }
+        });
+    } else {
+        // For exact searches, always apply deterministic sort
+        results.sort_by(|a, b| (&a.file, a.lines.0).cmp(&(&b.file, b.lines.0)));
+    }
+
+    // Duplicates collapse into their first, best-ranked instance
+    let collapsed = dedup_results(results, options.dedup);
+    if debug_mode && collapsed > 0 {
+        println!("DEBUG: Collapsed {collapsed} duplicate blocks");
+    }
+
+    // File-level ranking collapses the ordered blocks into one result per file, before limits
+    // are applied so that `max_results` counts files
+    if options.rank_mode == RankMode::File {
+        *results = rank_files(std::mem::take(results));
+    }
+
+    // The score threshold applies to the final scores, after recency and file aggregation
+    match options.min_score {
+        Some(min_score) if !skip_ranking => {
+            let dropped = retain_min_score(results, f64::from(min_score));
+            if debug_mode && dropped > 0 {
+                println!("DEBUG: Dropped {dropped} results scoring below {min_score}");
+            }
+            dropped
+        }
+        _ => 0,
+    }
+}
+
+/// Apply the result limits; dry runs first estimate the size of the results without them.
+/// `below_min_score` results were already dropped by the score threshold.
+fn limit_results(
+    results: Vec<SearchResult>,
+    options: &SearchOptions,
+    below_min_score: usize,
+) -> LimitedSearchResults {
+    let estimated_full_size = options
+        .dry_run
+        .then(|| estimate_full_size(&results, options.tokenizer));
+    let mut limited = apply_limits(
+        results,
+        options.max_results,
+        options.max_bytes,
+        options.max_tokens,
+        options.tokenizer,
+    );
+    limited.estimated_full_size = estimated_full_size;
+
+    if let Some(min_score) = options.min_score {
+        let limits = limited.limits_applied.get_or_insert_with(|| {
+            // Count the returned results the same way the limits would have
+            let size = estimate_full_size(&limited.results, options.tokenizer);
+            SearchLimits {
+                max_results: None,
+                max_bytes: None,
+                max_tokens: None,
+                min_score: None,
+                below_min_score: 0,
+                total_bytes: size.total_bytes,
+                total_tokens: size.total_tokens,
+            }
+        });
+        limits.min_score = Some(min_score);
+        limits.below_min_score = below_min_score;
+    }
+    limited
+}
+
+/// Record where the query terms match in each result's final (merged) code
+fn attach_term_matches(results: &mut [SearchResult], term_matcher: &TermMatcher) {
+    for result in results.iter_mut() {
+        let matches = term_matcher.find(&result.code, result.lines.0);
+        result.term_matches = (!matches.is_empty()).then_some(matches);
+    }
+}
+
+/// Flag results cut short by the timeout, with the number of files left unprocessed
+fn flag_timeout(limited: &mut LimitedSearchResults, unprocessed_files: usize) {
+    if unprocessed_files > 0 {
+        limited.timed_out = true;
+        limited.files_skipped_early_termination = Some(unprocessed_files);
+    }
+}
+
+/// Report each file skipped as binary or undecodable, in path order
+fn unsearched_skips(files: &[(PathBuf, SkipReason)]) -> Vec<SkippedFile> {
+    let mut files = files.to_vec();
+    files.sort_by(|a, b| a.0.cmp(&b.0));
+    files.dedup_by(|a, b| a.0 == b.0);
+    files
+        .into_iter()
+        .map(|(file, reason)| SkippedFile::unsearched(file.to_string_lossy().to_string(), reason))
+        .collect()
+}
+
+/// Resolve the session to cache results under: `""` or `"new"` reuse `PROBE_SESSION_ID` or
+/// generate a fresh ID, and no session falls back to `PROBE_SESSION_ID`. Returns the session
+/// and whether its ID was generated.
+fn resolve_session(session: Option<&str>, debug_mode: bool) -> (Option<&str>, bool) {
+    if let Some(s) = session {
+        if s.is_empty() || s == "new" {
             // Check if we have a session ID in the environment variable
             if let Ok(env_session_id) = std::env::var("PROBE_SESSION_ID") {
                 if !env_session_id.is_empty() {
@@ -300,7 +651,7 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
                 }
             }
         } else {
-            (Some(*s), false)
+            (Some(s), false)
         }
     } else {
         // Check if we have a session ID in the environment variable
@@ -318,6 +669,153 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
         } else {
             (None, false)
         }
+    }
+}
+
+/// Run a search over `options.path`, leaving files not yet processed at `deadline` out of the
+/// results. Without `use_session`, the session cache is neither read nor written, whatever
+/// `options.session` and `PROBE_SESSION_ID` say.
+fn run_probe(
+    options: &SearchOptions,
+    mut sink: Option<ResultSink>,
+    use_session: bool,
+    deadline: SearchDeadline,
+) -> Result<LimitedSearchResults> {
+    // Start timing the entire search process
+    let total_start = Instant::now();
+
+    let SearchOptions {
+        path,
+        queries,
+        files_only,
+        custom_ignores,
+        exclude_filenames,
+        reranker: _, // Ranking options are read by rank_and_order_results
+        frequency_search: _,
+        exact,
+        language,
+        max_results,
+        max_bytes: _, // Limits are applied by limit_results
+        max_tokens,
+        tokenizer: _,
+        allow_tests,
+        no_merge,
+        merge_threshold,
+        merge_respect_boundaries,
+        dry_run,
+        session,
+        timeout: _, // The deadline is set by the caller
+        question: _,
+        no_gitignore,
+        lsp,
+        sort,
+        regex,
+        match_scope,
+        invert,
+        kinds,
+        recency_boost: _,
+        recency_half_life_days: _,
+        context_symbols,
+        context_symbols_depth,
+        explain,
+        use_index,
+        rank_mode: _,
+        within,
+        changed_since,
+        tracked_only,
+        rerank_top_k: _,
+        binary_detection,
+        force_text_extensions,
+        search_archives,
+        encoding,
+        min_score: _,
+        follow_symlinks,
+        ranking_profile,
+        dedup,
+        extra_roots: _, // Each root is searched on its own by run_probe_roots
+    } = options;
+    let encoding = SourceEncoding::parse_option(encoding.as_deref())?;
+    let ranking_profile = resolve_ranking_profile(ranking_profile.as_deref())?;
+
+    // Resolve the files changed since the ref, and the tracked files, once; every file walk
+    // is intersected with them
+    let changed_files = changed_since
+        .as_deref()
+        .map(|git_ref| ChangedFiles::since(path, git_ref))
+        .transpose()?;
+    let tracked_files = tracked_only
+        .then(|| TrackedFiles::in_repo(path))
+        .transpose()?;
+
+    let include_filenames = !exclude_filenames;
+    let debug_mode = std::env::var("DEBUG").unwrap_or_default() == "1";
+
+    // Inverted mode reports whole blocks without any match, so it has its own pipeline
+    if *invert {
+        let config = InvertSearchConfig {
+            custom_ignores,
+            allow_tests: *allow_tests,
+            language: language.as_ref().map(|lang| normalize_language_alias(lang)),
+            no_gitignore: *no_gitignore,
+            follow_symlinks: *follow_symlinks,
+            exact: *exact,
+            regex: *regex,
+            kinds: kinds.as_deref(),
+            changed_files: changed_files.as_ref(),
+            tracked_files: tracked_files.as_ref(),
+            binary_detection: *binary_detection,
+            force_text_extensions,
+            search_archives: *search_archives,
+            encoding,
+            deadline,
+        };
+        let (mut results, unsearched_files, unprocessed_files) =
+            search_inverted(path, queries, &config)?;
+        dedup_results(&mut results, *dedup);
+        let mut limited = limit_results(results, options, 0);
+        limited
+            .skipped_files
+            .extend(unsearched_skips(&unsearched_files));
+        flag_timeout(&mut limited, unprocessed_files);
+        sort.apply(&mut limited.results);
+        return Ok(limited);
+    }
+
+    // Raw regex mode bypasses the elastic query pipeline entirely
+    if *regex {
+        let config = RegexSearchConfig {
+            custom_ignores,
+            allow_tests: *allow_tests,
+            language: language.as_ref().map(|lang| normalize_language_alias(lang)),
+            no_gitignore: *no_gitignore,
+            follow_symlinks: *follow_symlinks,
+            match_scope: *match_scope,
+            within: within.as_deref(),
+            changed_files: changed_files.as_ref(),
+            tracked_files: tracked_files.as_ref(),
+            binary_detection: *binary_detection,
+            force_text_extensions,
+            search_archives: *search_archives,
+            encoding,
+            deadline,
+        };
+        let (mut results, unsearched_files, unprocessed_files) =
+            search_with_regex(path, queries, &config)?;
+        dedup_results(&mut results, *dedup);
+        let mut limited = limit_results(results, options, 0);
+        limited
+            .skipped_files
+            .extend(unsearched_skips(&unsearched_files));
+        flag_timeout(&mut limited, unprocessed_files);
+        sort.apply(&mut limited.results);
+        return Ok(limited);
+    }
+
+    // Handle session ID generation if session is provided but empty
+    let (effective_session, session_was_generated) = if use_session {
+        resolve_session(*session, debug_mode)
+    } else {
+        (None, false)
     };
 
     let mut timings = SearchTimings {
@@ -384,6 +882,9 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
             limits_applied: None,
             cached_blocks_skipped: None,
             files_skipped_early_termination: None,
+            estimated_full_size: None,
+            cached_blocks: Vec::new(),
+            timed_out: false,
         });
     }
 
@@ -429,6 +930,7 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
 
     // Use combined pattern approach for more efficient searching
     let structured_patterns = create_structured_patterns(&plan);
+    let term_matcher = TermMatcher::new(&plan, &structured_patterns);
 
     let pg_duration = pg_start.elapsed();
     timings.pattern_generation = Some(pg_duration);
@@ -473,15 +975,29 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
         allow_tests: *allow_tests,
         language: lang_param,
         no_gitignore: *no_gitignore,
+        follow_symlinks: *follow_symlinks,
+        changed_files: changed_files.as_ref(),
+        tracked_files: tracked_files.as_ref(),
+        binary_detection: *binary_detection,
+        force_text_extensions,
+        search_archives: *search_archives,
+        encoding,
+        deadline,
     };
 
-    let mut file_term_map = search_with_structured_patterns(
-        path,
-        &plan,
-        &structured_patterns,
-        &search_config,
-        &search_filters,
-    )?;
+    let (mut file_term_map, mut unsearched_files, unscanned_files) =
+        search_with_structured_patterns(
+            path,
+            &plan,
+            &structured_patterns,
+            &search_config,
+            &search_filters,
+        )?;
+
+    // Keep only the matches inside the requested node kinds; files left without any are dropped
+    if let Some(kinds) = within {
+        file_term_map = restrict_term_matches(file_term_map, &structured_patterns, kinds)?;
+    }
 
     let fs_duration = fs_start.elapsed();
     timings.file_searching = Some(fs_duration);
@@ -512,9 +1028,10 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
     // Skip filename matching for exact searches (--exact flag) and when all AST terms
     // are exact (e.g., quoted queries like "cleanupScopeMappings"). Filename matching
     // tokenizes terms into subwords which creates false positives for exact queries. (#527)
+    // File names are not part of any syntax node, so `within` searches skip it as well.
     let fm_start = Instant::now();
     let ast_all_exact = crate::search::query::is_exact_search(&plan.ast);
-    if include_filenames && !exact && !ast_all_exact {
+    if include_filenames && !exact && !ast_all_exact && within.is_none() {
         if debug_mode {
             println!("DEBUG: Starting filename matching...");
         }
@@ -545,7 +1062,7 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
             path.to_path_buf()
         };
 
-        let filename_matches: HashMap<PathBuf, HashSet<usize>> =
+        let mut filename_matches: HashMap<PathBuf, HashSet<usize>> =
             file_list_cache::find_matching_filenames(
                 &resolved_path,
                 queries,
@@ -555,8 +1072,16 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
                 &plan.term_indices,
                 lang_param,
                 *no_gitignore,
+                *follow_symlinks,
             )?;
 
+        if let Some(changed) = &changed_files {
+            filename_matches.retain(|file, _| changed.contains(file));
+        }
+        if let Some(tracked) = &tracked_files {
+            filename_matches.retain(|file, _| tracked.contains(file));
+        }
+
         if debug_mode {
             println!(
                 "DEBUG: Found {} files matching by filename",
@@ -569,12 +1094,21 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
             // Read the file content to get the total number of lines. Use the
             // same guard as content search so filename matches cannot pull in
             // binary or oversized files.
-            let file_content = match file_guard::read_searchable_text_file(pathbuf.as_path()) {
+            let detection =
+                file_guard::binary_detection_for(pathbuf, *binary_detection, force_text_extensions);
+            let file_content = match file_guard::read_searchable_text_file_decoded(
+                pathbuf.as_path(),
+                detection,
+                encoding,
+            ) {
                 Ok(content) => content,
                 Err(e) => {
                     if debug_mode {
                         println!("DEBUG: Skipping filename-matched file {pathbuf:?}: {e:?}");
                     }
+                    if let Some(reason) = file_guard::skip_reason(&e) {
+                        unsearched_files.push((pathbuf.clone(), reason));
+                    }
                     continue;
                 }
             };
@@ -727,12 +1261,22 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
                 tokenized_content: None,
                 lsp_info: None,
                 parent_context: None,
+                score_breakdown: None,
+                term_matches: None,
+                duplicates: None,
+                root: None,
             });
         }
-        let mut limited = apply_limits(res, *max_results, *max_bytes, *max_tokens);
+        let mut limited = limit_results(res, options, 0);
+        limited
+            .skipped_files
+            .extend(unsearched_skips(&unsearched_files));
 
         // No caching for files-only mode
         limited.cached_blocks_skipped = None;
+        limited.cached_blocks.clear();
+
+        sort.apply(&mut limited.results);
 
         // Set total search time
         timings.total_search_time = Some(total_start.elapsed());
@@ -746,6 +1290,7 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
     // Apply early caching if session is provided - AFTER getting ripgrep results but BEFORE processing
     let ec_start = Instant::now();
     let mut early_skipped_count = 0;
+    let mut cached_blocks = Vec::new();
     if let Some(session_id) = effective_session {
         // Get the raw query string for caching
         let raw_query = if queries.len() > 1 {
@@ -766,11 +1311,12 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
 
         // Filter matched lines using the cache
         match cache::filter_matched_lines_with_cache(&mut file_term_map, session_id, &raw_query) {
-            Ok(skipped) => {
+            Ok((skipped, blocks)) => {
                 if debug_mode {
                     println!("DEBUG: Early caching skipped {skipped} matched lines");
                 }
                 early_skipped_count = skipped;
+                cached_blocks.extend(blocks);
             }
             Err(e) => {
                 // Log the error but continue without early caching
@@ -888,24 +1434,38 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
     let mut total_synchronization_time = Duration::new(0, 0);
     let mut total_uncovered_lines_time = Duration::new(0, 0);
 
+    // Exact searches (or all-exact ASTs like quoted queries) keep file order instead of ranking
+    let skip_ranking = *exact || ast_all_exact;
+
     // Batch processing parameters
     const BATCH_SIZE: usize = 100;
-    let estimated_files_needed = early_ranker::estimate_files_needed(
-        *max_results,
-        *max_tokens,
-        250, // Average tokens per result estimate
-    );
+    // Dry runs estimate the size of the full result set, so they process every file
+    let estimated_files_needed = if *dry_run {
+        usize::MAX
+    } else {
+        early_ranker::estimate_files_needed(
+            *max_results,
+            *max_tokens,
+            250, // Average tokens per result estimate
+        )
+    };
 
     let mut files_processed = 0;
     let mut batch_number = 0;
     let mut should_continue = true;
+    let mut timed_out = unscanned_files > 0;
 
     // Track total files available for accurate skipped file count
     let total_ranked_files = ranked_files.len();
 
     // Use dynamic batch size: min of BATCH_SIZE and estimated_files_needed
     // This prevents processing way more files than needed when limits are strict
-    let effective_batch_size = BATCH_SIZE.min(estimated_files_needed);
+    // Streaming callers stop on their own, so the estimate from limits does not apply
+    let effective_batch_size = if sink.is_some() {
+        BATCH_SIZE
+    } else {
+        BATCH_SIZE.min(estimated_files_needed)
+    };
     if debug_mode {
         println!(
             "DEBUG: Using batch size {} (BATCH_SIZE={}, estimated_files_needed={})",
@@ -913,6 +1473,9 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
         );
     }
 
+    // Tokenized blocks of files unchanged since earlier runs are reused from the persistent index
+    let search_index = use_index.then(|| SearchIndex::load(path));
+
     // Process files in batches
     for batch in ranked_files.chunks(effective_batch_size) {
         if !should_continue {
@@ -932,6 +1495,19 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
         let mut batch_results = Vec::new();
 
         for early_rank_result in batch {
+            // At the timeout, keep what the files processed so far produced. The best-ranked
+            // file is always processed, so a tight timeout still returns something
+            if files_processed > 0 && deadline.expired() {
+                if debug_mode {
+                    println!(
+                        "DEBUG: Timed out after processing {files_processed} of {total_ranked_files} files"
+                    );
+                }
+                timed_out = true;
+                should_continue = false;
+                break;
+            }
+
             let pathbuf = &early_rank_result.path;
             if debug_mode {
                 println!(
@@ -985,6 +1561,10 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
                     no_merge: *no_merge,
                     query_plan: &plan,
                     lsp: *lsp,
+                    context_symbols_depth: context_symbols.then_some(*context_symbols_depth),
+                    index: search_index.as_ref(),
+                    within: within.as_deref(),
+                    encoding,
                 };
 
                 if debug_mode {
@@ -1177,6 +1757,26 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
             );
         }
 
+        // When streaming, rank and emit this batch instead of collecting it
+        if let Some(sink) = sink.as_mut() {
+            // Streamed results have no limits section to report the dropped count in
+            rank_and_order_results(
+                &mut batch_results,
+                options,
+                &ranking_profile,
+                skip_ranking,
+                debug_mode,
+            );
+            attach_term_matches(&mut batch_results, &term_matcher);
+            for result in batch_results.drain(..) {
+                if sink(result).is_break() {
+                    should_continue = false;
+                    break;
+                }
+            }
+            continue;
+        }
+
         // Add batch results to final results
         final_results.append(&mut batch_results);
 
@@ -1192,7 +1792,7 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
 
         // Also check if we already have way more results than needed
         // Use a more conservative multiplier aligned with our 1.5x buffer strategy
-        if let Some(max_res) = max_results {
+        if let Some(max_res) = max_results.as_ref().filter(|_| !*dry_run) {
             let buffered_max_results = (*max_res as f64 * 2.0).ceil() as usize; // 2x buffer for safety
             if final_results.len() > buffered_max_results {
                 if debug_mode {
@@ -1207,6 +1807,35 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
         }
     }
 
+    if let Some(search_index) = &search_index {
+        if let Err(e) = search_index.save() {
+            if debug_mode {
+                println!("DEBUG: Failed to save search index: {e}");
+            }
+        }
+    }
+
+    if sink.is_some() {
+        if debug_mode {
+            println!(
+                "DEBUG: Streaming search processed {files_processed} of {total_ranked_files} files in {}",
+                format_duration(rp_start.elapsed())
+            );
+        }
+        return Ok(LimitedSearchResults {
+            results: Vec::new(),
+            skipped_files: unsearched_skips(&unsearched_files),
+            limits_applied: None,
+            cached_blocks_skipped: None,
+            files_skipped_early_termination: Some(
+                total_ranked_files.saturating_sub(files_processed) + unscanned_files,
+            ),
+            estimated_full_size: None,
+            cached_blocks: Vec::new(),
+            timed_out,
+        });
+    }
+
     let rp_duration = rp_start.elapsed();
     // Calculate the total time spent on detailed result building operations
     let detailed_result_building_time = total_term_matching_time
@@ -1318,7 +1947,6 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
     }
     // Rank results (skip if exact flag is set or all AST terms are exact like quoted queries)
     let rr_start = Instant::now();
-    let skip_ranking = *exact || ast_all_exact;
     if debug_mode {
         if skip_ranking {
             println!("DEBUG: Skipping result ranking due to exact flag being set");
@@ -1327,38 +1955,23 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
         }
     }
 
-    if !skip_ranking {
-        // Only perform ranking if exact flag is not set
-        rank_search_results(&mut final_results, queries, reranker, *question);
-
-        // Apply deterministic secondary sort to ensure consistent ordering for results with equal scores
-        // This prevents non-deterministic behavior when results have the same ranking score
-        final_results.sort_by(|a, b| {
-            // First sort by score (if available), then by file path, then by line number
-            match (a.rank, b.rank) {
-                (Some(rank_a), Some(rank_b)) => {
-                    // If ranks are different, use rank ordering
-                    match rank_a.cmp(&rank_b) {
-                        std::cmp::Ordering::Equal => {
-                            // If ranks are equal, use deterministic secondary sort
-                            (&a.file, a.lines.0).cmp(&(&b.file, b.lines.0))
-                        }
-                        other => other,
-                    }
-                }
-                (Some(_), None) => std::cmp::Ordering::Less,
-                (None, Some(_)) => std::cmp::Ordering::Greater,
-                (None, None) => {
-                    // If no ranks, sort by file path and line number
-                    (&a.file, a.lines.0).cmp(&(&b.file, b.lines.0))
-                }
-            }
-        });
-    } else {
-        // For exact searches, always apply deterministic sort
-        final_results.sort_by(|a, b| (&a.file, a.lines.0).cmp(&(&b.file, b.lines.0)));
+    // Past the timeout, return the results found so far unranked
+    let out_of_time = !skip_ranking && deadline.expired();
+    if out_of_time {
+        timed_out = true;
+        if debug_mode {
+            println!("DEBUG: Search timed out before ranking, returning results unranked");
+        }
     }
 
+    let below_min_score = rank_and_order_results(
+        &mut final_results,
+        options,
+        &ranking_profile,
+        skip_ranking || out_of_time,
+        debug_mode,
+    );
+
     let rr_duration = rr_start.elapsed();
     timings.result_ranking = Some(rr_duration);
 
@@ -1387,10 +2000,12 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
     }
 
     // First apply limits to the results
-    let mut limited = apply_limits(filtered_results, *max_results, *max_bytes, *max_tokens);
+    let mut limited = limit_results(filtered_results, options, below_min_score);
 
     // Calculate files skipped due to early termination
-    let files_skipped_early_termination = total_ranked_files.saturating_sub(files_processed);
+    // Files never scanned count as skipped too
+    let files_skipped_early_termination =
+        total_ranked_files.saturating_sub(files_processed) + unscanned_files;
 
     // Set the files skipped due to early termination
     limited.files_skipped_early_termination = if files_skipped_early_termination > 0 {
@@ -1398,6 +2013,7 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
     } else {
         None
     };
+    limited.timed_out = timed_out;
 
     // Measure limit application timing immediately after limits are applied
     let la_duration = la_start.elapsed();
@@ -1439,7 +2055,8 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
 
         // Filter results using the cache - but only to count skipped blocks, not to filter
         match cache::filter_results_with_cache(&limited.results, session_id, &raw_query) {
-            Ok((_, cached_skipped)) => {
+            Ok((_, blocks)) => {
+                let cached_skipped = blocks.len();
                 if debug_mode {
                     println!("DEBUG: Final caching found {cached_skipped} cached blocks");
                     println!(
@@ -1449,6 +2066,7 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
                 }
 
                 skipped_count += cached_skipped;
+                cached_blocks.extend(blocks);
             }
             Err(e) => {
                 // Log the error but continue without caching
@@ -1477,6 +2095,17 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
         None
     };
 
+    // Report which blocks were suppressed when the caller asked for an explanation
+    if *explain {
+        cached_blocks.sort();
+        cached_blocks.dedup();
+        limited.cached_blocks = cached_blocks;
+    }
+
+    limited
+        .skipped_files
+        .extend(unsearched_skips(&unsearched_files));
+
     let fc_duration = fc_start.elapsed();
     timings.final_caching = Some(fc_duration);
 
@@ -1499,6 +2128,9 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
             limits_applied: limited.limits_applied,
             cached_blocks_skipped: limited.cached_blocks_skipped,
             files_skipped_early_termination: limited.files_skipped_early_termination,
+            estimated_full_size: limited.estimated_full_size,
+            cached_blocks: limited.cached_blocks,
+            timed_out: limited.timed_out,
         }
     } else {
         limited
@@ -1511,8 +2143,12 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
     }
 
     let final_results = if !limited.results.is_empty() && !*no_merge {
-        use probe_code::search::block_merging::merge_ranked_blocks;
-        let merged = merge_ranked_blocks(limited.results.clone(), *merge_threshold);
+        use probe_code::search::block_merging::merge_ranked_blocks_with_threshold;
+        let merged = merge_ranked_blocks_with_threshold(
+            limited.results.clone(),
+            merge_threshold.unwrap_or_default(),
+            *merge_respect_boundaries,
+        );
 
         let bm_duration = bm_start.elapsed();
         timings.block_merging = Some(bm_duration);
@@ -1532,6 +2168,9 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
             limits_applied: limited.limits_applied,
             cached_blocks_skipped: limited.cached_blocks_skipped,
             files_skipped_early_termination: limited.files_skipped_early_termination,
+            estimated_full_size: limited.estimated_full_size,
+            cached_blocks: limited.cached_blocks,
+            timed_out: limited.timed_out,
         };
 
         // Update the cache with the merged results (after merging)
@@ -1571,6 +2210,13 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
         limited
     };
 
+    // Reorder the selected results if a non-relevance order was requested.
+    // Limits were applied on the score-ranked list above, so this only changes
+    // presentation order, not which results are returned.
+    let mut final_results = final_results;
+    sort.apply(&mut final_results.results);
+    attach_term_matches(&mut final_results.results, &term_matcher);
+
     // Print the session ID to the console if it was generated or provided
     if let Some(session_id) = effective_session {
         if session_was_generated {
@@ -1586,12 +2232,12 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
     // Print timing information
     print_timings(&timings);
 
-    // Stop the timeout thread
-    timeout_handle.store(true, std::sync::atomic::Ordering::SeqCst);
-
     Ok(final_results)
 }
 
+/// Number of files scanned between checks of the search deadline
+const SCAN_CHUNK_SIZE: usize = 512;
+
 /// Helper function to search files using structured patterns from a QueryPlan.
 /// This function uses ripgrep's optimized search engine for maximum performance
 /// and collects matches by term indices. It uses the file_list_cache to get a filtered
@@ -1603,13 +2249,21 @@ pub fn perform_probe(options: &SearchOptions) -> Result<LimitedSearchResults> {
 /// * `patterns` - The generated regex patterns with their term indices
 /// * `config` - Search configuration options
 /// * `search_filters` - File filtering options
+///
+/// Returns the matched term lines of each file, the files skipped as binary or
+/// undecodable, and the number of files left unscanned at the deadline.
+#[allow(clippy::type_complexity)]
 pub fn search_with_structured_patterns(
     root_path_str: &Path,
     _plan: &QueryPlan,
     patterns: &[(String, HashSet<usize>)],
     config: &SearchConfig,
     search_filters: &SearchFilters,
-) -> Result<HashMap<PathBuf, HashMap<usize, HashSet<usize>>>> {
+) -> Result<(
+    HashMap<PathBuf, HashMap<usize, HashSet<usize>>>,
+    Vec<(PathBuf, SkipReason)>,
+    usize,
+)> {
     // Resolve the path if it's a special format (e.g., "go:github.com/user/repo")
     let root_path = if let Some(path_str) = root_path_str.to_str() {
         match resolve_path(path_str) {
@@ -1684,7 +2338,11 @@ pub fn search_with_structured_patterns(
         // Format patterns for case-insensitive ripgrep search
         let formatted_patterns: Vec<String> =
             pattern_strings.iter().map(|p| format!("(?i){p}")).collect();
-        Some(RipgrepSearcher::new(&formatted_patterns, true)?)
+        Some(
+            RipgrepSearcher::new(&formatted_patterns, true)?
+                .with_binary_detection(config.binary_detection, config.force_text_extensions)
+                .with_encoding(config.encoding),
+        )
     } else {
         None
     };
@@ -1714,19 +2372,93 @@ pub fn search_with_structured_patterns(
         config.custom_ignores,
         config.language,
         config.no_gitignore,
+        config.follow_symlinks,
     )?;
 
+    // Keep only the changed files, if requested
+    let initial_file_list = match config.changed_files {
+        Some(changed) => {
+            if debug_mode {
+                println!(
+                    "DEBUG: Restricting {} files to the {} changed in git",
+                    initial_file_list.files.len(),
+                    changed.len()
+                );
+            }
+            Arc::new(probe_code::search::file_list_cache::FileList {
+                files: initial_file_list
+                    .files
+                    .iter()
+                    .filter(|file| changed.contains(file))
+                    .cloned()
+                    .collect(),
+                archives: initial_file_list
+                    .archives
+                    .iter()
+                    .filter(|archive| changed.contains(archive))
+                    .cloned()
+                    .collect(),
+                created_at: initial_file_list.created_at,
+            })
+        }
+        None => initial_file_list,
+    };
+
+    // Keep only the files tracked by git, if requested
+    let initial_file_list = match config.tracked_files {
+        Some(tracked) => {
+            if debug_mode {
+                println!(
+                    "DEBUG: Restricting {} files to the {} tracked by git",
+                    initial_file_list.files.len(),
+                    tracked.len()
+                );
+            }
+            Arc::new(probe_code::search::file_list_cache::FileList {
+                files: initial_file_list
+                    .files
+                    .iter()
+                    .filter(|file| tracked.contains(file))
+                    .cloned()
+                    .collect(),
+                archives: initial_file_list
+                    .archives
+                    .iter()
+                    .filter(|archive| tracked.contains(archive))
+                    .cloned()
+                    .collect(),
+                created_at: initial_file_list.created_at,
+            })
+        }
+        None => initial_file_list,
+    };
+
+    // Search the text entries of archives like regular files, if requested
+    let mut candidate_files = initial_file_list.files.clone();
+    if config.search_archives {
+        let entries = archive::text_entries(&initial_file_list.archives, |entry| {
+            file_list_cache::keeps_archive_entry(entry, config.allow_tests, config.language)
+        });
+        if debug_mode {
+            println!(
+                "DEBUG: Found {} text entries in {} archives",
+                entries.len(),
+                initial_file_list.archives.len()
+            );
+        }
+        candidate_files.extend(entries);
+    }
+
     // Apply search filters to further filter the file list
     let filtered_files = if !search_filters.is_empty() {
         if debug_mode {
             println!(
                 "DEBUG: Applying search filters to {} files",
-                initial_file_list.files.len()
+                candidate_files.len()
             );
         }
 
-        let filtered: Vec<PathBuf> = initial_file_list
-            .files
+        let filtered: Vec<PathBuf> = candidate_files
             .iter()
             .filter(|file_path| {
                 let matches = search_filters.matches_file(file_path);
@@ -1742,7 +2474,7 @@ pub fn search_with_structured_patterns(
             println!(
                 "DEBUG: Search filters kept {} out of {} files",
                 filtered.len(),
-                initial_file_list.files.len()
+                candidate_files.len()
             );
         }
 
@@ -1751,15 +2483,16 @@ pub fn search_with_structured_patterns(
         if debug_mode {
             println!(
                 "DEBUG: No search filters active, using all {} files",
-                initial_file_list.files.len()
+                candidate_files.len()
             );
         }
-        initial_file_list.files.clone()
+        candidate_files
     };
 
     // Create a new file list structure with the filtered files
     let file_list = probe_code::search::file_list_cache::FileList {
         files: filtered_files,
+        archives: Vec::new(),
         created_at: initial_file_list.created_at,
     };
 
@@ -1772,64 +2505,89 @@ pub fn search_with_structured_patterns(
         }
     }
 
-    // Step 3: Process files in parallel using either SIMD or ripgrep
-    let result = if use_simd {
-        // Use SIMD-based search with deterministic collection
-        let simd_matcher = Arc::new(simd_matcher);
-        let pattern_to_terms = Arc::new(pattern_to_terms);
-
-        // Sort files for deterministic processing order to fix non-deterministic behavior
-        let mut sorted_files = file_list.files.clone();
-        sorted_files.sort();
-
-        // Collect results in parallel first, then sort for deterministic order
-        let results_vec: Vec<_> = sorted_files
-            .par_iter()
-            .filter_map(|file_path| {
-                let simd_matcher = Arc::clone(&simd_matcher);
-                let pattern_to_terms = Arc::clone(&pattern_to_terms);
+    // Step 3: Process files in parallel using either SIMD or ripgrep. Files are scanned in
+    // sorted chunks for a deterministic order; past the deadline, the remaining chunks are left
+    // unscanned once something has matched
+    let simd_matcher = Arc::new(simd_matcher);
+    let pattern_to_terms = Arc::new(pattern_to_terms);
+    let mut sorted_files = file_list.files.clone();
+    sorted_files.sort();
+
+    let mut file_term_maps = std::collections::BTreeMap::new();
+    let mut unsearched_files = Vec::new();
+    let mut unscanned_files = 0;
+    for (chunk_index, chunk) in sorted_files.chunks(SCAN_CHUNK_SIZE).enumerate() {
+        if !file_term_maps.is_empty() && config.deadline.expired() {
+            unscanned_files = sorted_files.len() - chunk_index * SCAN_CHUNK_SIZE;
+            if debug_mode {
+                println!("DEBUG: Timed out with {unscanned_files} files left unscanned");
+            }
+            break;
+        }
 
-                // Search file with SIMD pattern matching
-                match search_file_with_simd(file_path, &simd_matcher, &pattern_to_terms) {
-                    Ok(term_map) => {
-                        if !term_map.is_empty() {
+        if use_simd {
+            let chunk_unsearched = std::sync::Mutex::new(Vec::new());
+            let results_vec: Vec<_> = chunk
+                .par_iter()
+                .filter_map(|file_path| {
+                    let simd_matcher = Arc::clone(&simd_matcher);
+                    let pattern_to_terms = Arc::clone(&pattern_to_terms);
+
+                    // Search file with SIMD pattern matching
+                    let detection = file_guard::binary_detection_for(
+                        file_path,
+                        config.binary_detection,
+                        config.force_text_extensions,
+                    );
+                    match search_file_with_simd(
+                        file_path,
+                        &simd_matcher,
+                        &pattern_to_terms,
+                        detection,
+                        config.encoding,
+                    ) {
+                        Ok(term_map) => {
+                            if !term_map.is_empty() {
+                                if debug_mode {
+                                    println!(
+                                        "DEBUG: File {:?} matched patterns with {} term indices",
+                                        file_path,
+                                        term_map.len()
+                                    );
+                                }
+                                Some((file_path.clone(), term_map))
+                            } else {
+                                None
+                            }
+                        }
+                        Err(e) => {
                             if debug_mode {
-                                println!(
-                                    "DEBUG: File {:?} matched patterns with {} term indices",
-                                    file_path,
-                                    term_map.len()
-                                );
+                                println!("DEBUG: Error searching file {file_path:?}: {e:?}");
+                            }
+                            if let Some(reason) = file_guard::skip_reason(&e) {
+                                chunk_unsearched
+                                    .lock()
+                                    .unwrap()
+                                    .push((file_path.clone(), reason));
                             }
-                            Some((file_path.clone(), term_map))
-                        } else {
                             None
                         }
                     }
-                    Err(e) => {
-                        if debug_mode {
-                            println!("DEBUG: Error searching file {file_path:?}: {e:?}");
-                        }
-                        None
-                    }
-                }
-            })
-            .collect();
-
-        // Convert to BTreeMap for deterministic ordering by file path
-        let mut file_term_maps = std::collections::BTreeMap::new();
-        for (path, term_map) in results_vec {
-            file_term_maps.insert(path, term_map);
+                })
+                .collect();
+            file_term_maps.extend(results_vec);
+            unsearched_files.extend(chunk_unsearched.into_inner().unwrap());
+        } else {
+            let (chunk_results, chunk_unsearched) = searcher
+                .as_ref()
+                .unwrap()
+                .search_files_parallel(chunk, &pattern_to_terms)?;
+            file_term_maps.extend(chunk_results);
+            unsearched_files.extend(chunk_unsearched);
         }
-
-        // Convert BTreeMap back to HashMap for compatibility with existing code
-        file_term_maps.into_iter().collect::<HashMap<_, _>>()
-    } else {
-        // Use ripgrep-based search
-        searcher
-            .as_ref()
-            .unwrap()
-            .search_files_parallel(&file_list.files, &pattern_to_terms)?
-    };
+    }
+    unsearched_files.sort_by(|a, b| a.0.cmp(&b.0));
+    let result: HashMap<_, _> = file_term_maps.into_iter().collect();
 
     let total_duration = search_start.elapsed();
 
@@ -1841,7 +2599,7 @@ pub fn search_with_structured_patterns(
         );
     }
 
-    Ok(result)
+    Ok((result, unsearched_files, unscanned_files))
 }
 
 /// Helper function to search a file with SIMD pattern matching
@@ -1850,20 +2608,23 @@ fn search_file_with_simd(
     file_path: &Path,
     simd_matcher: &Option<SimdPatternMatcher>,
     pattern_to_terms: &[HashSet<usize>],
+    detection: file_guard::BinaryDetection,
+    encoding: Option<SourceEncoding>,
 ) -> Result<HashMap<usize, HashSet<usize>>> {
     let mut term_map = HashMap::new();
     let debug_mode = std::env::var("DEBUG").unwrap_or_default() == "1";
 
     // Read the file content with shared text-search safety checks.
-    let content = match file_guard::read_searchable_text_file(file_path) {
-        Ok(content) => content,
-        Err(e) => {
-            if debug_mode {
-                println!("DEBUG: Skipping unreadable/search-denied file {file_path:?}: {e:?}");
+    let content =
+        match file_guard::read_searchable_text_file_decoded(file_path, detection, encoding) {
+            Ok(content) => content,
+            Err(e) => {
+                if debug_mode {
+                    println!("DEBUG: Skipping unreadable/search-denied file {file_path:?}: {e:?}");
+                }
+                return Err(e);
             }
-            return Err(e);
-        }
-    };
+        };
 
     // Process each line
     for (line_number, line) in content.lines().enumerate() {
@@ -1913,6 +2674,7 @@ fn normalize_language_alias(lang: &str) -> &str {
         "cc" | "cxx" | "hpp" | "hxx" => "cpp",
         "rb" => "ruby",
         "cs" => "csharp",
+        "kt" | "kts" => "kotlin",
         "sol" => "solidity",
         "cr" => "crystal",
         "hs" | "lhs" => "haskell",
@@ -1,7 +1,8 @@
 use dashmap::DashMap;
+use std::str::FromStr;
 use std::sync::OnceLock;
 use std::time::{SystemTime, UNIX_EPOCH};
-use tiktoken_rs::{p50k_base, CoreBPE};
+use tiktoken_rs::{cl100k_base, o200k_base, p50k_base, CoreBPE};
 
 /// Cache configuration for token counting
 #[derive(Debug, Clone)]
@@ -335,6 +336,59 @@ pub fn get_tokenizer() -> &'static CoreBPE {
     TOKENIZER.get_or_init(|| p50k_base().expect("Failed to initialize tiktoken tokenizer"))
 }
 
+/// BPE encoding used to count tokens exactly against a model family's limit
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TokenizerKind {
+    /// `p50k_base` (Codex, text-davinci); the encoding used by [`count_tokens`]
+    P50k,
+    /// `cl100k_base` (GPT-3.5, GPT-4)
+    Cl100k,
+    /// `o200k_base` (GPT-4o and later)
+    O200k,
+}
+
+impl TokenizerKind {
+    /// Returns a reference to the tiktoken tokenizer for this encoding
+    pub fn tokenizer(self) -> &'static CoreBPE {
+        static CL100K: OnceLock<CoreBPE> = OnceLock::new();
+        static O200K: OnceLock<CoreBPE> = OnceLock::new();
+        match self {
+            TokenizerKind::P50k => get_tokenizer(),
+            TokenizerKind::Cl100k => CL100K
+                .get_or_init(|| cl100k_base().expect("Failed to initialize tiktoken tokenizer")),
+            TokenizerKind::O200k => {
+                O200K.get_or_init(|| o200k_base().expect("Failed to initialize tiktoken tokenizer"))
+            }
+        }
+    }
+}
+
+impl FromStr for TokenizerKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().trim_end_matches("_base") {
+            "p50k" => Ok(TokenizerKind::P50k),
+            "cl100k" => Ok(TokenizerKind::Cl100k),
+            "o200k" => Ok(TokenizerKind::O200k),
+            _ => Err(format!(
+                "Unknown tokenizer: {s}. Expected one of: p50k, cl100k, o200k"
+            )),
+        }
+    }
+}
+
+/// Count the tokens of a code block with the given encoding.
+///
+/// `p50k` goes through the block cache of [`count_block_tokens`]; the other encodings are
+/// counted directly, since the caches are keyed by content only.
+pub fn count_tokens_with(text: &str, kind: TokenizerKind) -> usize {
+    match kind {
+        TokenizerKind::P50k => count_block_tokens(text),
+        _ => kind.tokenizer().encode_with_special_tokens(text).len(),
+    }
+}
+
 /// Helper function to count tokens in a string using tiktoken (same tokenizer as GPT models)
 ///
 /// This function implements both content-level and block-level token count caching to improve
@@ -674,6 +728,26 @@ mod tests {
         assert_eq!(cache_size, 0);
     }
 
+    #[test]
+    fn test_count_tokens_with_model_encodings() {
+        // Reference counts from OpenAI's tiktoken
+        assert_eq!(count_tokens_with("2 + 2 = 4", TokenizerKind::P50k), 5);
+        assert_eq!(count_tokens_with("2 + 2 = 4", TokenizerKind::Cl100k), 7);
+        assert_eq!(
+            count_tokens_with("antidisestablishmentarianism", TokenizerKind::P50k),
+            5
+        );
+        assert_eq!(
+            count_tokens_with("antidisestablishmentarianism", TokenizerKind::Cl100k),
+            6
+        );
+        assert_eq!(count_tokens_with("Hello, world!", TokenizerKind::O200k), 4);
+
+        assert_eq!("cl100k".parse(), Ok(TokenizerKind::Cl100k));
+        assert_eq!("O200K_BASE".parse(), Ok(TokenizerKind::O200k));
+        assert!("gpt2".parse::<TokenizerKind>().is_err());
+    }
+
     #[test]
     fn test_count_tokens_consistency() {
         // Test that our cached count_tokens function returns same results as direct tiktoken
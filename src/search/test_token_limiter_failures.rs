@@ -1,8 +1,8 @@
 #[cfg(test)]
 mod token_limiter_failure_tests {
     use super::super::search_limiter::apply_limits;
-    use crate::models::SearchResult;
-    use crate::search::search_tokens::count_block_tokens;
+    use crate::models::{SearchResult, SkipReason};
+    use crate::search::search_tokens::{count_block_tokens, count_tokens_with, TokenizerKind};
 
     /// Helper function to create a SearchResult with specific code content
     fn create_test_result(code: &str, rank: Option<usize>) -> SearchResult {
@@ -34,6 +34,10 @@ mod token_limiter_failure_tests {
             tokenized_content: None,
             lsp_info: None,
             parent_context: None,
+            score_breakdown: None,
+            term_matches: None,
+            duplicates: None,
+            root: None,
         }
     }
 
@@ -54,7 +58,7 @@ mod token_limiter_failure_tests {
             .collect();
 
         let token_limit = 100;
-        let limited = apply_limits(results, None, None, Some(token_limit));
+        let limited = apply_limits(results, None, None, Some(token_limit), None);
 
         // Calculate actual token count to verify overrun
         let actual_total_tokens: usize = limited
@@ -149,7 +153,7 @@ mod token_limiter_failure_tests {
             .collect();
 
         let token_limit = 150;
-        let limited = apply_limits(results.clone(), None, None, Some(token_limit));
+        let limited = apply_limits(results.clone(), None, None, Some(token_limit), None);
 
         let reported_tokens = limited
             .limits_applied
@@ -263,4 +267,60 @@ mod token_limiter_failure_tests {
             println!("SUCCESS: Created scenario where estimation ({estimated_tokens}) < 90% threshold ({threshold_90_percent}) but actual ({actual_tokens}) > limit ({token_limit})");
         }
     }
+
+    #[test]
+    fn test_explicit_tokenizer_counts_exactly_and_drops_whole_blocks() {
+        let blocks: Vec<String> = (0..10)
+            .map(|i| format!("const{{x{i},y{i}}}={{x{i}:1,y{i}:2}};if(x{i}>y{i}){{z=x{i}+y{i}}}"))
+            .collect();
+        let results: Vec<SearchResult> = blocks
+            .iter()
+            .enumerate()
+            .map(|(i, code)| create_test_result(code, Some(i)))
+            .collect();
+
+        let block_tokens: Vec<usize> = blocks
+            .iter()
+            .map(|code| count_tokens_with(code, TokenizerKind::Cl100k))
+            .collect();
+        // A limit that ends in the middle of the fourth block
+        let token_limit = block_tokens[..3].iter().sum::<usize>() + block_tokens[3] / 2;
+
+        let limited = apply_limits(
+            results,
+            None,
+            None,
+            Some(token_limit),
+            Some(TokenizerKind::Cl100k),
+        );
+
+        assert_eq!(limited.results.len(), 3);
+        assert_eq!(limited.results[2].code, blocks[2]);
+        let reported_tokens = limited.limits_applied.unwrap().total_tokens;
+        assert_eq!(reported_tokens, block_tokens[..3].iter().sum::<usize>());
+        assert!(reported_tokens <= token_limit);
+        assert!(limited
+            .skipped_files
+            .iter()
+            .all(|skipped| skipped.reason == SkipReason::MaxTokens));
+    }
+
+    #[test]
+    fn test_skipped_results_record_the_limit_reached_first() {
+        let results: Vec<SearchResult> = (0..5)
+            .map(|i| create_test_result(&format!("fn block_{i}() {{}}"), Some(i)))
+            .collect();
+        let block_bytes = results[0].code.len();
+
+        // The byte limit is reached before the result limit
+        let limited = apply_limits(results, Some(4), Some(block_bytes * 2), None, None);
+
+        assert_eq!(limited.results.len(), 2);
+        let reasons: Vec<SkipReason> = limited
+            .skipped_files
+            .iter()
+            .map(|skipped| skipped.reason)
+            .collect();
+        assert_eq!(reasons, vec![SkipReason::MaxBytes; 3]);
+    }
 }
@@ -2097,9 +2097,60 @@ pub fn is_programming_stop_word(word: &str) -> bool {
     PROGRAMMING_STOP_WORDS.contains(word)
 }
 
-/// Checks if a word is either an English or programming stop word
+/// Stop words filtered out by the tokenizer: the built-in English and programming stop words,
+/// adjusted by the `stop_words_add` and `stop_words_remove` search settings
+#[derive(Debug, Clone)]
+pub struct StopWords {
+    words: HashSet<String>,
+}
+
+impl StopWords {
+    /// The built-in stop words plus `add` minus `remove`. `"*"` in `remove` removes every
+    /// built-in stop word; with nothing added, stop-word filtering is then disabled.
+    pub fn new(add: &[String], remove: &[String]) -> Self {
+        let mut words: HashSet<String> = if remove.iter().any(|word| word == "*") {
+            HashSet::new()
+        } else {
+            // Small numbers (0-10) count as English stop words
+            ENGLISH_STOP_WORDS
+                .iter()
+                .chain(PROGRAMMING_STOP_WORDS.iter())
+                .cloned()
+                .chain((0..=10).map(|n| n.to_string()))
+                .collect()
+        };
+        for word in remove {
+            words.remove(&word.to_lowercase());
+        }
+        words.extend(add.iter().map(|word| word.to_lowercase()));
+        Self { words }
+    }
+
+    /// Returns true if `word` (lowercase) is a stop word
+    pub fn contains(&self, word: &str) -> bool {
+        if self.words.is_empty() {
+            return false;
+        }
+        // Numbers match in any notation, e.g. "07"
+        self.words.contains(word)
+            || word
+                .parse::<u32>()
+                .is_ok_and(|n| self.words.contains(&n.to_string()))
+    }
+}
+
+/// Returns the stop words in effect, loaded from the search settings on first use
+pub fn stop_words() -> &'static StopWords {
+    static STOP_WORDS: Lazy<StopWords> = Lazy::new(|| {
+        let search = &probe_code::config::get_config().search;
+        StopWords::new(&search.stop_words_add, &search.stop_words_remove)
+    });
+    &STOP_WORDS
+}
+
+/// Checks if a word is a stop word, after the configured additions and removals
 pub fn is_stop_word(word: &str) -> bool {
-    is_english_stop_word(word) || is_programming_stop_word(word)
+    stop_words().contains(word)
 }
 
 /// Attempts to split a compound word into its constituent parts using a vocabulary
@@ -2903,6 +2954,27 @@ mod tests {
         assert!(!is_programming_stop_word("endpoint"));
     }
 
+    #[test]
+    fn test_configured_stop_words() {
+        let defaults = StopWords::new(&[], &[]);
+        assert!(defaults.contains("type"));
+        assert!(defaults.contains("the"));
+        assert!(defaults.contains("07"));
+        assert!(!defaults.contains("widget"));
+
+        // Domain jargon can be kept, and noise words added
+        let custom = StopWords::new(&["Widget".to_string()], &["type".to_string()]);
+        assert!(!custom.contains("type"));
+        assert!(custom.contains("widget"));
+        assert!(custom.contains("the"));
+
+        // An empty effective list disables stop-word filtering entirely
+        let none = StopWords::new(&[], &["*".to_string()]);
+        assert!(!none.contains("the"));
+        assert!(!none.contains("type"));
+        assert!(!none.contains("3"));
+    }
+
     #[test]
     fn test_tokenize() {
         // Test method with API acronym
@@ -0,0 +1,82 @@
+//! Restricting a search to the files tracked by git, used with `tracked_only`.
+//!
+//! The tracked files are read once from the git index and intersected with every file walk.
+//! This is stricter than honouring `.gitignore`: untracked files are left out even when no
+//! ignore rule matches them, so results only ever come from code that is committed or staged.
+
+use anyhow::{bail, Context, Result};
+use lsp_daemon::GitService;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+
+/// The files tracked by git, matched against paths found by walking a search root
+#[derive(Debug)]
+pub struct TrackedFiles {
+    /// Search root as given, the prefix of every walked path
+    root: PathBuf,
+    /// Canonical form of `root`
+    canonical_root: PathBuf,
+    /// Absolute paths of the tracked files
+    files: HashSet<PathBuf>,
+}
+
+impl TrackedFiles {
+    /// Collect the files tracked in the repository containing `root`
+    pub fn in_repo(root: &Path) -> Result<Self> {
+        let canonical_root = root
+            .canonicalize()
+            .with_context(|| format!("Failed to resolve search path: {}", root.display()))?;
+        let dir = if canonical_root.is_dir() {
+            canonical_root.as_path()
+        } else {
+            canonical_root.parent().unwrap_or(&canonical_root)
+        };
+
+        let Ok(git) = GitService::discover_repo(dir, dir) else {
+            bail!(
+                "Cannot search only tracked files: {} is not in a git repository",
+                root.display()
+            );
+        };
+        let Some(workdir) = git.workdir() else {
+            bail!(
+                "Cannot search only tracked files: {} is in a bare git repository",
+                root.display()
+            );
+        };
+        let workdir = workdir
+            .canonicalize()
+            .context("Failed to resolve git repository root")?;
+
+        let files = git
+            .tracked_files()
+            .context("Failed to list the files tracked by git")?
+            .iter()
+            .map(|path| workdir.join(path))
+            .collect();
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            canonical_root,
+            files,
+        })
+    }
+
+    /// Number of tracked files in the repository, including those outside the search root
+    pub fn len(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Returns true if the repository tracks no files
+    pub fn is_empty(&self) -> bool {
+        self.files.is_empty()
+    }
+
+    /// Returns true if `file`, a path found by walking the search root, is tracked
+    pub fn contains(&self, file: &Path) -> bool {
+        match file.strip_prefix(&self.root) {
+            Ok(relative) => self.files.contains(&self.canonical_root.join(relative)),
+            Err(_) => self.files.contains(file),
+        }
+    }
+}
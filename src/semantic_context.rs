@@ -1,3 +1,4 @@
+use schemars::JsonSchema;
 use serde::Serialize;
 use std::path::Path;
 use tree_sitter::{Node, Tree};
@@ -10,7 +11,8 @@ pub struct SourceRange {
     pub columns: [usize; 2],
 }
 
-#[derive(Debug, Clone, Serialize)]
+/// Source comment attached to the start of a code block
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct SourceComment {
     pub kind: String,
     pub start_line: usize,
@@ -18,7 +20,8 @@ pub struct SourceComment {
     pub text: String,
 }
 
-#[derive(Debug, Clone, Serialize)]
+/// Occurrence of a query term in a code block; `kind` is `code`, `comment` or `string`
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct SourceMatch {
     pub text: String,
     pub start_line: usize,
@@ -30,14 +33,16 @@ pub struct SourceMatch {
     pub comment_role: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+/// Named definition (module, class, function, ...) that encloses a code block
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct EnclosingSymbol {
     pub kind: String,
     pub name: String,
     pub line: usize,
 }
 
-#[derive(Debug, Clone, Serialize)]
+/// Call expression that encloses a code block
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct EnclosingCall {
     pub callee: String,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -45,7 +50,8 @@ pub struct EnclosingCall {
     pub line: usize,
 }
 
-#[derive(Debug, Clone, Serialize)]
+/// Syntax node matched by a query
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct MatchContext {
     pub node_type: String,
     pub content: String,
@@ -53,7 +59,8 @@ pub struct MatchContext {
     pub columns: [usize; 2],
 }
 
-#[derive(Debug, Clone, Serialize)]
+/// Definition that owns a match or code block
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct OwnerContext {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub symbol: Option<String>,
@@ -75,7 +82,8 @@ pub struct OwnerContext {
     pub content: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize)]
+/// Source context of a query match, reported with `--with-context`
+#[derive(Debug, Clone, Serialize, JsonSchema)]
 pub struct QuerySourceContext {
     pub language: String,
     pub r#match: MatchContext,
@@ -93,6 +101,7 @@ pub fn language_name_for_path(path: &Path) -> Option<&'static str> {
         "c" | "h" => Some("c"),
         "cpp" | "cc" | "cxx" | "hpp" | "hxx" => Some("cpp"),
         "java" => Some("java"),
+        "kt" | "kts" => Some("kotlin"),
         "rb" => Some("ruby"),
         "php" => Some("php"),
         "swift" => Some("swift"),
@@ -1,6 +1,73 @@
 use crate::ranking::QueryTokenMap;
 use ahash::AHashMap as HashMap;
 use simsimd::SpatialSimilarity;
+use std::sync::OnceLock;
+
+/// Kernels the dense vector operations run on, chosen once per process
+static BACKEND: OnceLock<&'static str> = OnceLock::new();
+
+#[cfg(test)]
+thread_local! {
+    /// Set by `with_scalar_kernels` to force the scalar path on the current test thread
+    static FORCE_SCALAR: std::cell::Cell<bool> = const { std::cell::Cell::new(false) };
+}
+
+/// Name of the kernels the dense vector operations in this module run on: `"avx512"` or
+/// `"avx2"` on x86-64 and `"neon"` on Arm when the CPU supports them at runtime, otherwise
+/// `"scalar"`. The SIMD paths are only taken when supported, so binaries built on a newer
+/// machine still run on older CPUs. Setting DISABLE_SIMD_KERNELS=1 forces the scalar path;
+/// it is read once, on first use.
+pub fn backend_in_use() -> &'static str {
+    BACKEND.get_or_init(|| {
+        if std::env::var("DISABLE_SIMD_KERNELS").unwrap_or_default() == "1" {
+            "scalar"
+        } else {
+            detect_cpu_backend()
+        }
+    })
+}
+
+/// Run `f` with the scalar kernels on the current thread, whatever the CPU supports
+#[cfg(test)]
+pub(crate) fn with_scalar_kernels<R>(f: impl FnOnce() -> R) -> R {
+    FORCE_SCALAR.with(|forced| forced.set(true));
+    let result = f();
+    FORCE_SCALAR.with(|forced| forced.set(false));
+    result
+}
+
+fn detect_cpu_backend() -> &'static str {
+    #[cfg(target_arch = "x86_64")]
+    {
+        if is_x86_feature_detected!("avx512f") {
+            return "avx512";
+        }
+        if is_x86_feature_detected!("avx2") && is_x86_feature_detected!("fma") {
+            return "avx2";
+        }
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        if std::arch::is_aarch64_feature_detected!("neon") {
+            return "neon";
+        }
+    }
+    "scalar"
+}
+
+/// Whether the SimSIMD kernels may be used on this CPU
+fn simd_available() -> bool {
+    #[cfg(test)]
+    if FORCE_SCALAR.with(|forced| forced.get()) {
+        return false;
+    }
+    backend_in_use() != "scalar"
+}
+
+/// Dot product without SIMD
+fn scalar_dot(a: &[f32], b: &[f32]) -> f32 {
+    a.iter().zip(b.iter()).map(|(&x, &y)| x * y).sum::<f32>()
+}
 
 /// Sparse vector representation optimized for SIMD operations
 /// Maintains sorted indices for optimal SimSIMD performance
@@ -77,16 +144,16 @@ impl SparseVector {
             return 0.0;
         }
 
+        if !simd_available() {
+            return scalar_dot(&self_values, &other_values);
+        }
+
         // Use SimSIMD's direct dot product function - this is the real SIMD acceleration!
         f32::dot(&self_values, &other_values)
             .map(|x| x as f32)
             .unwrap_or_else(|| {
                 // Fallback to manual computation if SimSIMD fails
-                self_values
-                    .iter()
-                    .zip(other_values.iter())
-                    .map(|(&a, &b)| a * b)
-                    .sum::<f32>()
+                scalar_dot(&self_values, &other_values)
             })
     }
 
@@ -96,10 +163,14 @@ impl SparseVector {
             return 0.0;
         }
 
+        if !simd_available() {
+            return scalar_dot(a, b);
+        }
+
         // Use SimSIMD's direct dot product function - this is the real SIMD acceleration!
         f32::dot(a, b).map(|x| x as f32).unwrap_or_else(|| {
             // Manual fallback
-            a.iter().zip(b.iter()).map(|(&x, &y)| x * y).sum::<f32>()
+            scalar_dot(a, b)
         })
     }
 
@@ -340,10 +411,98 @@ impl<'a> SimdBm25Params<'a> {
     }
 }
 
+/// Cosine similarity of `query` against each document embedding in `docs`.
+///
+/// Scores are computed with the SimSIMD kernels when the CPU supports them at runtime (see
+/// [`backend_in_use`]) and with scalar code otherwise, so the same binary runs on any
+/// machine. Returns one score in `[-1.0, 1.0]` per document, in the
+/// order of `docs`. Documents whose length differs from the query's, and zero vectors on
+/// either side, score `0.0`.
+///
+/// No alignment is required: the kernels use unaligned loads, so rows can be passed straight
+/// from `Vec<f32>`. Rows aligned to 32 bytes (AVX) or 16 bytes (NEON) avoid split loads and
+/// can be slightly faster for long embeddings.
+pub fn cosine_similarity_batch(query: &[f32], docs: &[Vec<f32>]) -> Vec<f32> {
+    if query.iter().all(|&x| x == 0.0) {
+        return vec![0.0; docs.len()];
+    }
+
+    let use_simd = simd_available();
+    docs.iter()
+        .map(|doc| {
+            if doc.len() != query.len() {
+                return 0.0;
+            }
+            if !use_simd {
+                return scalar_cosine_similarity(query, doc);
+            }
+            // SimSIMD returns the cosine distance, 1 - similarity
+            f32::cosine(query, doc)
+                .map(|distance| (1.0 - distance) as f32)
+                .unwrap_or_else(|| scalar_cosine_similarity(query, doc))
+        })
+        .collect()
+}
+
+/// Cosine similarity without SIMD, used as a fallback; zero vectors score 0.0
+fn scalar_cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    let (mut dot, mut norm_a, mut norm_b) = (0.0f64, 0.0f64, 0.0f64);
+    for (&x, &y) in a.iter().zip(b) {
+        dot += x as f64 * y as f64;
+        norm_a += x as f64 * x as f64;
+        norm_b += y as f64 * y as f64;
+    }
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    (dot / (norm_a.sqrt() * norm_b.sqrt())) as f32
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_cosine_similarity_batch_matches_scalar_reference() {
+        // Deterministic pseudo-random embeddings, long enough to exercise the vector kernels
+        let mut seed = 42u32;
+        let mut next = || {
+            seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+            (seed >> 8) as f32 / (1u32 << 24) as f32 - 0.5
+        };
+        let query: Vec<f32> = (0..384).map(|_| next()).collect();
+        let mut docs: Vec<Vec<f32>> = (0..16)
+            .map(|_| (0..384).map(|_| next()).collect())
+            .collect();
+        docs.push(query.clone());
+        docs.push(query.iter().map(|x| -x).collect());
+        docs.push(vec![0.0; 384]);
+        docs.push(vec![1.0; 3]);
+
+        let scores = cosine_similarity_batch(&query, &docs);
+        assert_eq!(scores.len(), docs.len());
+        for (doc, score) in docs.iter().zip(&scores) {
+            let expected = if doc.len() == query.len() {
+                scalar_cosine_similarity(&query, doc)
+            } else {
+                0.0
+            };
+            assert!(
+                (score - expected).abs() < 1e-3,
+                "SIMD score {score} differs from scalar {expected}"
+            );
+        }
+        assert!((scores[16] - 1.0).abs() < 1e-3);
+        assert!((scores[17] + 1.0).abs() < 1e-3);
+        assert_eq!(scores[18], 0.0);
+        assert_eq!(scores[19], 0.0);
+
+        assert_eq!(
+            cosine_similarity_batch(&[0.0; 4], &[vec![1.0; 4]]),
+            vec![0.0]
+        );
+    }
+
     #[test]
     fn test_sparse_vector_creation() {
         let mut tf_map = HashMap::new();
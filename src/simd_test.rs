@@ -77,3 +77,46 @@ pub fn test_simd_implementation() {
 
     println!("SIMD test completed successfully!");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simd_ranking::{cosine_similarity_batch, with_scalar_kernels};
+
+    #[test]
+    fn test_forced_scalar_path_matches_simd() {
+        let a: Vec<f32> = (0..257).map(|i| (i as f32 * 0.37).sin()).collect();
+        let b: Vec<f32> = (0..257).map(|i| (i as f32 * 0.11).cos()).collect();
+        let docs = vec![b.clone(), a.clone()];
+
+        let mut tf_map1 = HashMap::new();
+        let mut tf_map2 = HashMap::new();
+        for i in 0..40u8 {
+            tf_map1.insert(i, i as usize + 1);
+            tf_map2.insert(i * 2, 3);
+        }
+        let sparse1 = SparseVector::from_tf_map(&tf_map1);
+        let sparse2 = SparseVector::from_tf_map(&tf_map2);
+
+        // Default behavior: whatever kernels this CPU supports
+        let simd_dense = SparseVector::simd_dot_product_dense(&a, &b);
+        let simd_sparse = sparse1.dot_product(&sparse2);
+        let simd_cosine = cosine_similarity_batch(&a, &docs);
+
+        // Forcing the scalar path on this thread leaves other tests untouched
+        let (scalar_dense, scalar_sparse, scalar_cosine) = with_scalar_kernels(|| {
+            (
+                SparseVector::simd_dot_product_dense(&a, &b),
+                sparse1.dot_product(&sparse2),
+                cosine_similarity_batch(&a, &docs),
+            )
+        });
+
+        assert!((simd_dense - scalar_dense).abs() < 1e-3);
+        assert!((simd_sparse - scalar_sparse).abs() < 1e-3);
+        assert!((scalar_sparse - sparse1.manual_dot_product(&sparse2)).abs() < 1e-3);
+        for (simd, scalar) in simd_cosine.iter().zip(&scalar_cosine) {
+            assert!((simd - scalar).abs() < 1e-3, "{simd} != {scalar}");
+        }
+    }
+}
@@ -0,0 +1,11 @@
+use schemars::generate::SchemaSettings;
+use schemars::{JsonSchema, Schema};
+
+/// JSON Schema of a type as it is serialized: fields skipped when empty are optional.
+/// Descriptions come from the doc comments of the type and its fields.
+pub fn output_schema<T: JsonSchema>() -> Schema {
+    SchemaSettings::default()
+        .for_serialize()
+        .into_generator()
+        .into_root_schema_for::<T>()
+}
@@ -0,0 +1,138 @@
+use probe_code::models::LimitedSearchResults;
+use probe_code::search::{
+    perform_probe, BinaryDetection, DedupMode, MatchScope, RankMode, ResultSort, SearchOptions,
+};
+use std::fs::{self, File};
+use std::io::Write;
+use std::path::Path;
+use tempfile::TempDir;
+use zip::write::SimpleFileOptions;
+
+const JAVA_SOURCE: &[u8] = b"package com.acme;
+
+public class LedgerService {
+    public void settle(Ledger ledger) {
+        ledger.settle();
+    }
+}
+";
+
+fn create_test_files(root: &Path) {
+    fs::write(
+        root.join("ledger.rs"),
+        "fn settle(ledger: &Ledger) {\n    ledger.settle();\n}\n",
+    )
+    .unwrap();
+
+    let mut jar = zip::ZipWriter::new(File::create(root.join("ledger-sources.jar")).unwrap());
+    let options = SimpleFileOptions::default();
+    jar.start_file("com/acme/LedgerService.java", options)
+        .unwrap();
+    jar.write_all(JAVA_SOURCE).unwrap();
+    jar.start_file("com/acme/LedgerService.class", options)
+        .unwrap();
+    jar.write_all(b"\xca\xfe\xba\xbe ledger").unwrap();
+    jar.finish().unwrap();
+}
+
+fn search(root: &Path, search_archives: bool) -> LimitedSearchResults {
+    let queries = vec!["ledger".to_string()];
+    let custom_ignores: Vec<String> = vec![];
+    let options = SearchOptions {
+        path: root,
+        queries: &queries,
+        files_only: false,
+        custom_ignores: &custom_ignores,
+        exclude_filenames: true,
+        language: None,
+        reranker: "bm25",
+        frequency_search: true,
+        max_results: None,
+        max_bytes: None,
+        max_tokens: None,
+        tokenizer: None,
+        allow_tests: true,
+        no_merge: true,
+        merge_threshold: None,
+        merge_respect_boundaries: true,
+        dry_run: false,
+        session: None,
+        timeout: 30,
+        question: None,
+        exact: false,
+        no_gitignore: true,
+        lsp: false,
+        sort: ResultSort::Path,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
+    };
+
+    perform_probe(&options).expect("Failed to perform search")
+}
+
+fn result_files(results: &LimitedSearchResults) -> Vec<String> {
+    let mut files: Vec<String> = results
+        .results
+        .iter()
+        .filter_map(|r| Path::new(&r.file).file_name())
+        .map(|name| name.to_string_lossy().to_string())
+        .collect();
+    files.dedup();
+    files
+}
+
+#[test]
+fn test_archives_are_not_searched_by_default() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    create_test_files(temp_dir.path());
+
+    let results = search(temp_dir.path(), false);
+    assert_eq!(result_files(&results), vec!["ledger.rs"]);
+}
+
+#[test]
+fn test_search_archives_reports_virtual_entry_paths() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    create_test_files(temp_dir.path());
+
+    let results = search(temp_dir.path(), true);
+    let entry = results
+        .results
+        .iter()
+        .find(|r| {
+            r.file
+                .ends_with("ledger-sources.jar!com/acme/LedgerService.java")
+        })
+        .expect("the jar entry should be searched");
+    assert!(entry.code.contains("ledger.settle()"));
+    assert!(
+        entry.score.is_some(),
+        "archive entries are ranked like files"
+    );
+
+    // Compiled classes inside the archive are never searched
+    assert!(!results.results.iter().any(|r| r.file.ends_with(".class")));
+    assert!(result_files(&results).contains(&"ledger.rs".to_string()));
+}
@@ -0,0 +1,137 @@
+use probe_code::models::{LimitedSearchResults, SkipReason};
+use probe_code::search::{
+    perform_probe, BinaryDetection, DedupMode, MatchScope, RankMode, ResultSort, SearchOptions,
+};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn create_test_files(root: &Path) {
+    fs::write(
+        root.join("ledger.rs"),
+        "fn settle(ledger: &Ledger) {\n    ledger.settle();\n}\n",
+    )
+    .unwrap();
+    // Minified bundle with an embedded NUL byte in a string literal
+    fs::write(
+        root.join("bundle.js"),
+        "function settleLedger(ledger) { return ledger.settle(\"\0\"); }\n",
+    )
+    .unwrap();
+}
+
+fn search(
+    root: &Path,
+    binary_detection: BinaryDetection,
+    force_text_extensions: &[String],
+) -> LimitedSearchResults {
+    let queries = vec!["ledger".to_string()];
+    let custom_ignores: Vec<String> = vec![];
+    let options = SearchOptions {
+        path: root,
+        queries: &queries,
+        files_only: false,
+        custom_ignores: &custom_ignores,
+        exclude_filenames: true,
+        language: None,
+        reranker: "bm25",
+        frequency_search: true,
+        max_results: None,
+        max_bytes: None,
+        max_tokens: None,
+        tokenizer: None,
+        allow_tests: true,
+        no_merge: true,
+        merge_threshold: None,
+        merge_respect_boundaries: true,
+        dry_run: false,
+        session: None,
+        timeout: 30,
+        question: None,
+        exact: false,
+        no_gitignore: true,
+        lsp: false,
+        sort: ResultSort::Path,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection,
+        force_text_extensions,
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
+    };
+
+    perform_probe(&options).expect("Failed to perform search")
+}
+
+fn result_files(results: &LimitedSearchResults) -> Vec<String> {
+    let mut files: Vec<String> = results
+        .results
+        .iter()
+        .filter_map(|r| Path::new(&r.file).file_name())
+        .map(|name| name.to_string_lossy().to_string())
+        .collect();
+    files.dedup();
+    files
+}
+
+fn binary_skips(results: &LimitedSearchResults) -> Vec<String> {
+    results
+        .skipped_files
+        .iter()
+        .filter(|skipped| skipped.reason == SkipReason::Binary)
+        .filter_map(|skipped| Path::new(&skipped.result.file).file_name())
+        .map(|name| name.to_string_lossy().to_string())
+        .collect()
+}
+
+#[test]
+fn test_null_byte_detection_reports_binary_skips() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    create_test_files(temp_dir.path());
+
+    let results = search(temp_dir.path(), BinaryDetection::NullByte, &[]);
+    assert_eq!(result_files(&results), vec!["ledger.rs"]);
+    assert_eq!(binary_skips(&results), vec!["bundle.js"]);
+}
+
+#[test]
+fn test_force_text_extension_searches_binary_looking_files() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    create_test_files(temp_dir.path());
+
+    let results = search(
+        temp_dir.path(),
+        BinaryDetection::NullByte,
+        &["js".to_string()],
+    );
+    assert_eq!(result_files(&results), vec!["bundle.js", "ledger.rs"]);
+    assert!(binary_skips(&results).is_empty());
+}
+
+#[test]
+fn test_heuristic_detection_allows_sparse_control_bytes() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    create_test_files(temp_dir.path());
+
+    let results = search(temp_dir.path(), BinaryDetection::Heuristic, &[]);
+    assert_eq!(result_files(&results), vec!["bundle.js", "ledger.rs"]);
+    assert!(binary_skips(&results).is_empty());
+}
@@ -3,8 +3,11 @@ use std::path::Path;
 use tempfile::TempDir;
 
 use probe_code::models::SearchResult;
-use probe_code::search::block_merging::merge_ranked_blocks;
-use probe_code::search::{perform_probe, SearchOptions};
+use probe_code::search::block_merging::{merge_ranked_blocks, merge_ranked_blocks_with};
+use probe_code::search::{
+    perform_probe, BinaryDetection, DedupMode, MatchScope, MergeThreshold, RankMode, ResultSort,
+    SearchOptions,
+};
 
 #[test]
 fn test_merge_ranked_blocks() {
@@ -39,6 +42,10 @@ fn test_merge_ranked_blocks() {
         matched_lines: None,
         lsp_info: None,
         parent_context: None,
+        score_breakdown: None,
+        term_matches: None,
+        duplicates: None,
+        root: None,
     };
     let block2 = SearchResult {
     file: "test_file.rs".to_string(),
@@ -68,6 +75,10 @@ fn test_merge_ranked_blocks() {
         matched_lines: None,
         lsp_info: None,
         parent_context: None,
+        score_breakdown: None,
+        term_matches: None,
+        duplicates: None,
+        root: None,
 };
 
     // Create block from a different file that should not be merged
@@ -99,6 +110,10 @@ fn test_merge_ranked_blocks() {
         matched_lines: None,
         lsp_info: None,
         parent_context: None,
+        score_breakdown: None,
+        term_matches: None,
+        duplicates: None,
+        root: None,
     };
 
     // Create a vector with all blocks
@@ -197,9 +212,11 @@ fn test_integration_with_search_flow() {
         max_results: None,
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: true,
         no_merge: false,
-        merge_threshold: Some(20), // Increase threshold to ensure non-adjacent blocks are merged
+        merge_threshold: Some(MergeThreshold::Lines(20)), // Increase threshold to ensure non-adjacent blocks are merged
+        merge_respect_boundaries: false, // Merge adjacent functions by line distance alone
         dry_run: false,
         session: None,
         timeout: 30,
@@ -207,6 +224,31 @@ fn test_integration_with_search_flow() {
         exact: false,
         no_gitignore: false,
         lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     // Run a search that should produce multiple overlapping blocks
@@ -313,9 +355,11 @@ fn test_no_merge_flag() {
         max_results: None,
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: true,
         no_merge: false,
-        merge_threshold: Some(20), // Increase threshold to ensure non-adjacent blocks are merged
+        merge_threshold: Some(MergeThreshold::Lines(20)), // Increase threshold to ensure non-adjacent blocks are merged
+        merge_respect_boundaries: false, // Merge adjacent functions by line distance alone
         dry_run: false,
         session: None,
         timeout: 30,
@@ -323,6 +367,31 @@ fn test_no_merge_flag() {
         exact: false,
         no_gitignore: false,
         lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     // Run a search that should produce merged blocks
@@ -341,9 +410,11 @@ fn test_no_merge_flag() {
         max_results: None,
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: true,
         no_merge: true,
-        merge_threshold: Some(20), // Increase threshold to ensure non-adjacent blocks are merged
+        merge_threshold: Some(MergeThreshold::Lines(20)), // Increase threshold to ensure non-adjacent blocks are merged
+        merge_respect_boundaries: true,
         dry_run: false,
         session: None,
         timeout: 30,
@@ -351,6 +422,31 @@ fn test_no_merge_flag() {
         exact: false,
         no_gitignore: false,
         lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     // Run a search that should not merge blocks
@@ -400,3 +496,85 @@ fn test_no_merge_flag() {
         );
     }
 }
+
+/// A function-level result for `lines` of `file`
+fn function_block(file: &Path, lines: (usize, usize), rank: usize) -> SearchResult {
+    let content = fs::read_to_string(file).unwrap();
+    SearchResult {
+        file: file.to_string_lossy().to_string(),
+        lines,
+        node_type: "function_item".to_string(),
+        code: content
+            .lines()
+            .skip(lines.0 - 1)
+            .take(lines.1 - lines.0 + 1)
+            .collect::<Vec<_>>()
+            .join("\n"),
+        symbol_signature: None,
+        matched_by_filename: None,
+        rank: Some(rank),
+        score: Some(1.0 / rank as f64),
+        tfidf_score: None,
+        bm25_score: None,
+        tfidf_rank: None,
+        bm25_rank: None,
+        new_score: None,
+        hybrid2_rank: None,
+        combined_score_rank: None,
+        file_unique_terms: None,
+        file_total_matches: None,
+        file_match_rank: None,
+        block_unique_terms: None,
+        block_total_matches: None,
+        parent_file_id: None,
+        block_id: None,
+        matched_keywords: None,
+        tokenized_content: None,
+        matched_lines: None,
+        lsp_info: None,
+        parent_context: None,
+        score_breakdown: None,
+        term_matches: None,
+        duplicates: None,
+        root: None,
+    }
+}
+
+#[test]
+fn test_merge_respects_symbol_boundaries() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("orders.rs");
+    fs::write(
+        &file,
+        "fn open_order() {\n    audit(1);\n}\n\n/// Closes the order\nfn close_order() {\n    audit(2);\n}\n",
+    )
+    .unwrap();
+
+    let blocks = || {
+        vec![
+            function_block(&file, (1, 3), 1),
+            function_block(&file, (6, 8), 2),
+        ]
+    };
+
+    // Two adjacent top-level functions stay separate, whatever the threshold
+    let merged = merge_ranked_blocks_with(blocks(), Some(20), true);
+    let lines: Vec<_> = merged.iter().map(|r| r.lines).collect();
+    assert_eq!(lines, vec![(1, 3), (6, 8)]);
+
+    // Without boundaries they are merged by line distance alone
+    let merged = merge_ranked_blocks_with(blocks(), Some(20), false);
+    assert_eq!(merged.len(), 1);
+    assert_eq!(merged[0].lines, (1, 8));
+
+    // A doc comment is not a boundary for the declaration it documents
+    let mut comment = function_block(&file, (5, 5), 3);
+    comment.node_type = "line_comment".to_string();
+    let merged = merge_ranked_blocks_with(
+        vec![function_block(&file, (6, 8), 1), comment],
+        Some(5),
+        true,
+    );
+    assert_eq!(merged.len(), 1);
+    assert_eq!(merged[0].lines, (5, 8));
+}
@@ -344,3 +344,64 @@ async fn test_cache_stats() {
         "No computations should be in flight"
     );
 }
+
+#[tokio::test]
+async fn test_warm_reports_computed_and_cached() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    let cache = Arc::new(CallGraphCache::new(CallGraphCacheConfig::default()));
+    let key = |name: &str| {
+        NodeKey::new(
+            name,
+            PathBuf::from("/test/hot.rs"),
+            format!("hash_{}", name),
+        )
+    };
+    let empty = || CallHierarchyInfo {
+        incoming_calls: vec![],
+        outgoing_calls: vec![],
+    };
+
+    // One symbol is cached before warming
+    cache
+        .get_or_compute(key("cached"), move || async move { Ok(empty()) })
+        .await
+        .unwrap();
+
+    let running = Arc::new(AtomicUsize::new(0));
+    let peak = Arc::new(AtomicUsize::new(0));
+    let keys = vec![
+        key("cached"),
+        key("alpha"),
+        key("beta"),
+        key("gamma"),
+        key("alpha"),
+        key("broken"),
+    ];
+    let report = cache
+        .warm(keys, 2, |key: NodeKey| {
+            let running = running.clone();
+            let peak = peak.clone();
+            async move {
+                let now = running.fetch_add(1, Ordering::SeqCst) + 1;
+                peak.fetch_max(now, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(20)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+                match key.symbol.as_str() {
+                    "broken" => anyhow::bail!("no symbol at position"),
+                    // Answered from the provider's own cache
+                    "gamma" => Ok((empty(), true)),
+                    _ => Ok((empty(), false)),
+                }
+            }
+        })
+        .await;
+
+    assert_eq!(report.computed, 2);
+    // The pre-cached symbol, "gamma" and the second "alpha"
+    assert_eq!(report.cached, 3);
+    assert_eq!(report.failed.len(), 1);
+    assert_eq!(report.failed[0].0.symbol, "broken");
+    assert!(peak.load(Ordering::SeqCst) <= 2);
+    assert!(cache.get(&key("gamma")).is_some());
+}
@@ -0,0 +1,58 @@
+use probe_code::capabilities::{language_capabilities, LanguageCapabilities};
+
+fn capabilities_for(language: &str) -> LanguageCapabilities {
+    language_capabilities()
+        .into_iter()
+        .find(|caps| caps.language == language)
+        .unwrap_or_else(|| panic!("Missing capabilities for {language}"))
+}
+
+#[test]
+fn test_rust_reports_full_support() {
+    let rust = capabilities_for("rust");
+    assert_eq!(rust.extensions, vec!["rs"]);
+    assert!(rust.search);
+    assert!(rust.extract);
+    assert!(rust.ast_query);
+    assert_eq!(rust.analyzer.as_deref(), Some("specialized"));
+    assert_eq!(rust.lsp_server.as_deref(), Some("rust-analyzer"));
+}
+
+#[test]
+fn test_go_uses_generic_tree_sitter_analyzer() {
+    let go = capabilities_for("go");
+    assert!(go.search && go.extract && go.ast_query);
+    assert_eq!(go.analyzer.as_deref(), Some("tree-sitter"));
+    assert_eq!(go.lsp_server.as_deref(), Some("gopls"));
+}
+
+#[test]
+fn test_markup_languages_have_no_ast_query_or_lsp() {
+    for language in ["markdown", "yaml", "html"] {
+        let caps = capabilities_for(language);
+        assert!(caps.search, "{language} should be searchable");
+        assert!(!caps.ast_query, "{language} should not support AST queries");
+        assert_eq!(caps.analyzer, None);
+        assert_eq!(caps.lsp_server, None);
+        assert!(!caps.lsp_available);
+    }
+}
+
+#[test]
+fn test_manifest_serializes_to_json() {
+    let json = serde_json::to_value(language_capabilities()).unwrap();
+    let entries = json.as_array().unwrap();
+    assert!(entries.len() >= 18);
+    for key in [
+        "language",
+        "extensions",
+        "search",
+        "extract",
+        "ast_query",
+        "analyzer",
+        "lsp_server",
+        "lsp_available",
+    ] {
+        assert!(entries[0].get(key).is_some(), "Missing key {key}");
+    }
+}
@@ -0,0 +1,147 @@
+use probe_code::search::{
+    perform_probe, BinaryDetection, DedupMode, MatchScope, RankMode, ResultSort, SearchOptions,
+};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+/// Run a git command in `dir`
+fn git(dir: &Path, args: &[&str]) {
+    let status = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .env("GIT_AUTHOR_NAME", "Test User")
+        .env("GIT_AUTHOR_EMAIL", "test@example.com")
+        .env("GIT_COMMITTER_NAME", "Test User")
+        .env("GIT_COMMITTER_EMAIL", "test@example.com")
+        .status()
+        .expect("Failed to run git");
+    assert!(status.success(), "git {args:?} failed");
+}
+
+/// A repository where `base` is tagged before one file is changed and another is added
+fn create_fixture_repo() -> TempDir {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let root = temp_dir.path();
+    git(root, &["init", "-q"]);
+
+    fs::write(
+        root.join("billing.rs"),
+        "fn settle(order: &Order) {\n    charge_invoice(order);\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("refunds.rs"),
+        "fn refund(order: &Order) {\n    reverse_invoice(order);\n}\n",
+    )
+    .unwrap();
+    git(root, &["add", "."]);
+    git(root, &["commit", "-q", "-m", "base"]);
+    git(root, &["tag", "base"]);
+
+    fs::write(
+        root.join("refunds.rs"),
+        "fn refund(order: &Order) {\n    reverse_invoice(order);\n    notify(order);\n}\n",
+    )
+    .unwrap();
+    git(root, &["commit", "-q", "-am", "notify on refund"]);
+    // Untracked files count as changed
+    fs::write(
+        root.join("credits.rs"),
+        "fn credit(order: &Order) {\n    issue_invoice(order);\n}\n",
+    )
+    .unwrap();
+
+    temp_dir
+}
+
+fn search_files(root: &Path, changed_since: Option<&str>) -> anyhow::Result<Vec<String>> {
+    let queries = vec!["invoice".to_string()];
+    let custom_ignores: Vec<String> = vec![];
+    let options = SearchOptions {
+        path: root,
+        queries: &queries,
+        files_only: false,
+        custom_ignores: &custom_ignores,
+        exclude_filenames: true,
+        language: None,
+        reranker: "bm25",
+        frequency_search: true,
+        max_results: None,
+        max_bytes: None,
+        max_tokens: None,
+        tokenizer: None,
+        allow_tests: true,
+        no_merge: true,
+        merge_threshold: None,
+        merge_respect_boundaries: true,
+        dry_run: false,
+        session: None,
+        timeout: 30,
+        question: None,
+        exact: false,
+        no_gitignore: true,
+        lsp: false,
+        sort: ResultSort::Path,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: changed_since.map(str::to_string),
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
+    };
+
+    let mut files: Vec<String> = perform_probe(&options)?
+        .results
+        .into_iter()
+        .filter_map(|r| {
+            Path::new(&r.file)
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+        })
+        .collect();
+    files.dedup();
+    Ok(files)
+}
+
+#[test]
+fn test_changed_since_only_searches_changed_files() {
+    let repo = create_fixture_repo();
+
+    let all = search_files(repo.path(), None).unwrap();
+    assert_eq!(all, vec!["billing.rs", "credits.rs", "refunds.rs"]);
+
+    let changed = search_files(repo.path(), Some("base")).unwrap();
+    assert_eq!(changed, vec!["credits.rs", "refunds.rs"]);
+}
+
+#[test]
+fn test_changed_since_requires_git_repository() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    fs::write(temp_dir.path().join("billing.rs"), "fn invoice() {}\n").unwrap();
+
+    let error = search_files(temp_dir.path(), Some("main")).unwrap_err();
+    assert!(
+        error.to_string().contains("not in a git repository"),
+        "{error}"
+    );
+}
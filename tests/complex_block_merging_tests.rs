@@ -3,7 +3,10 @@ use tempfile::TempDir;
 
 use probe_code::models::SearchResult;
 use probe_code::search::block_merging::merge_ranked_blocks;
-use probe_code::search::{perform_probe, SearchOptions};
+use probe_code::search::{
+    perform_probe, BinaryDetection, DedupMode, MatchScope, MergeThreshold, RankMode, ResultSort,
+    SearchOptions,
+};
 
 /// Test merging of blocks with different node types
 #[test]
@@ -39,6 +42,10 @@ fn test_merge_different_node_types() {
         matched_lines: None,
         lsp_info: None,
         parent_context: None,
+        score_breakdown: None,
+        term_matches: None,
+        duplicates: None,
+        root: None,
     };
     let block2 = SearchResult {
     file: "mixed_types.rs".to_string(),
@@ -68,6 +75,10 @@ fn test_merge_different_node_types() {
         matched_lines: None,
         lsp_info: None,
         parent_context: None,
+        score_breakdown: None,
+        term_matches: None,
+        duplicates: None,
+        root: None,
 };
 
     let block3 = SearchResult {
@@ -98,6 +109,10 @@ fn test_merge_different_node_types() {
         matched_lines: None,
         lsp_info: None,
         parent_context: None,
+        score_breakdown: None,
+        term_matches: None,
+        duplicates: None,
+        root: None,
     };
 
     // Create a vector with all blocks
@@ -169,6 +184,10 @@ fn test_merge_with_gaps() {
         matched_lines: None,
         lsp_info: None,
         parent_context: None,
+        score_breakdown: None,
+        term_matches: None,
+        duplicates: None,
+        root: None,
     };
 
     // Gap of 3 lines between block1 and block2
@@ -200,6 +219,10 @@ fn test_merge_with_gaps() {
         matched_lines: None,
         lsp_info: None,
         parent_context: None,
+        score_breakdown: None,
+        term_matches: None,
+        duplicates: None,
+        root: None,
     };
 
     // Gap of 2 lines between block2 and block3
@@ -233,6 +256,10 @@ fn test_merge_with_gaps() {
         matched_lines: None,
         lsp_info: None,
         parent_context: None,
+        score_breakdown: None,
+        term_matches: None,
+        duplicates: None,
+        root: None,
     };
 
     // Test with default threshold (5)
@@ -332,6 +359,10 @@ fn test_merge_overlapping_blocks() {
         matched_lines: None,
         lsp_info: None,
         parent_context: None,
+        score_breakdown: None,
+        term_matches: None,
+        duplicates: None,
+        root: None,
     };
 
     // Overlaps with block1 (lines 5-7 are shared)
@@ -363,6 +394,10 @@ fn test_merge_overlapping_blocks() {
         matched_lines: None,
         lsp_info: None,
         parent_context: None,
+        score_breakdown: None,
+        term_matches: None,
+        duplicates: None,
+        root: None,
     };
 
     // Create a vector with both blocks
@@ -450,9 +485,11 @@ fn third_function() {
             max_results: None,
             max_bytes: None,
             max_tokens: None,
+            tokenizer: None,
             allow_tests: true,
             no_merge: false,
-            merge_threshold: Some(threshold),
+            merge_threshold: Some(MergeThreshold::Lines(threshold)),
+            merge_respect_boundaries: false, // Merge adjacent functions by line distance alone
             dry_run: false,
             session: None,
             timeout: 30,
@@ -460,6 +497,31 @@ fn third_function() {
             exact: false,
             no_gitignore: false,
             lsp: false,
+            sort: ResultSort::Score,
+            regex: false,
+            match_scope: MatchScope::All,
+            invert: false,
+            kinds: None,
+            recency_boost: false,
+            recency_half_life_days: 14.0,
+            context_symbols: false,
+            context_symbols_depth: 5,
+            explain: false,
+            use_index: false,
+            rank_mode: RankMode::Block,
+            within: None,
+            changed_since: None,
+            tracked_only: false,
+            rerank_top_k: 50,
+            binary_detection: BinaryDetection::NullByte,
+            force_text_extensions: &[],
+            search_archives: false,
+            encoding: None,
+            min_score: None,
+            follow_symlinks: false,
+            ranking_profile: None,
+            dedup: DedupMode::None,
+            extra_roots: &[],
         };
 
         // Run the search
@@ -541,6 +603,10 @@ fn test_merge_parent_child_blocks() {
         matched_lines: None,
         lsp_info: None,
         parent_context: None,
+        score_breakdown: None,
+        term_matches: None,
+        duplicates: None,
+        root: None,
     };
 
     // Child block (method inside the struct)
@@ -572,6 +638,10 @@ fn test_merge_parent_child_blocks() {
         matched_lines: None,
         lsp_info: None,
         parent_context: None,
+        score_breakdown: None,
+        term_matches: None,
+        duplicates: None,
+        root: None,
     };
 
     // Create a vector with both blocks
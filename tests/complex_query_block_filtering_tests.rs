@@ -6,7 +6,9 @@ use tempfile::TempDir;
 use probe_code::search::elastic_query::parse_query_test as parse_query;
 use probe_code::search::file_processing::filter_code_block_with_ast;
 use probe_code::search::query::create_query_plan;
-use probe_code::search::{perform_probe, SearchOptions};
+use probe_code::search::{
+    perform_probe, BinaryDetection, DedupMode, MatchScope, RankMode, ResultSort, SearchOptions,
+};
 
 /// Test complex boolean expressions for block filtering
 #[test]
@@ -303,9 +305,11 @@ func (i *IPWhiteListMiddleware) Process() {
         max_results: None,
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: true,
         no_merge: true,
         merge_threshold: None,
+        merge_respect_boundaries: true,
         dry_run: false,
         session: None,
         timeout: 30,
@@ -313,6 +317,31 @@ func (i *IPWhiteListMiddleware) Process() {
         exact: false,
         no_gitignore: false,
         lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     // Run the search
@@ -385,9 +414,11 @@ func Process() {
         max_results: None,
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: true,
         no_merge: true,
         merge_threshold: None,
+        merge_respect_boundaries: true,
         dry_run: false,
         session: None,
         timeout: 30,
@@ -395,6 +426,31 @@ func Process() {
         exact: false,
         no_gitignore: false,
         lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     // Run the search
@@ -108,6 +108,12 @@ fn search_config_strategy() -> impl Strategy<Value = Option<SearchConfig>> {
                         merge_threshold,
                         allow_tests,
                         no_gitignore,
+                        stop_words_add: None,
+                        stop_words_remove: None,
+                        stemmer_language: None,
+                        ignore: None,
+                        ranking_profile: None,
+                        ranking_profiles: None,
                     })
                 }
             )
@@ -156,6 +162,7 @@ fn probe_config_strategy() -> impl Strategy<Value = ProbeConfig> {
             lsp: None,
             performance: None,
             indexing: None,
+            languages: None,
         }
     })
 }
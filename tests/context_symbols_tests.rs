@@ -0,0 +1,107 @@
+use probe_code::search::{
+    perform_probe, BinaryDetection, DedupMode, MatchScope, RankMode, ResultSort, SearchOptions,
+};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+const SOURCE: &str = r#"mod billing {
+    pub struct Invoice;
+
+    impl Invoice {
+        pub fn settle(&self) {
+            settle_invoice_now(self);
+        }
+    }
+}
+"#;
+
+fn search(root: &Path, context_symbols: bool, context_symbols_depth: usize) -> Vec<Vec<String>> {
+    let queries = vec!["settle_invoice_now".to_string()];
+    let custom_ignores: Vec<String> = vec![];
+    let options = SearchOptions {
+        path: root,
+        queries: &queries,
+        files_only: false,
+        custom_ignores: &custom_ignores,
+        exclude_filenames: true,
+        language: None,
+        reranker: "bm25",
+        frequency_search: true,
+        max_results: None,
+        max_bytes: None,
+        max_tokens: None,
+        tokenizer: None,
+        allow_tests: true,
+        no_merge: true,
+        merge_threshold: None,
+        merge_respect_boundaries: true,
+        dry_run: false,
+        session: None,
+        timeout: 30,
+        question: None,
+        exact: false,
+        no_gitignore: true,
+        lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols,
+        context_symbols_depth,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
+    };
+
+    perform_probe(&options)
+        .expect("Failed to perform search")
+        .results
+        .into_iter()
+        .map(|r| {
+            r.parent_context
+                .unwrap_or_default()
+                .into_iter()
+                .map(|context| context.context_line)
+                .collect()
+        })
+        .collect()
+}
+
+#[test]
+fn test_context_symbols_attach_enclosing_signatures() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    fs::write(temp_dir.path().join("billing.rs"), SOURCE).unwrap();
+
+    let contexts = search(temp_dir.path(), true, 5);
+    assert_eq!(contexts.len(), 1);
+    assert_eq!(contexts[0], vec!["mod billing {", "    impl Invoice {"]);
+
+    // The depth cap keeps the nearest enclosing scope
+    assert_eq!(
+        search(temp_dir.path(), true, 1),
+        vec![vec!["    impl Invoice {".to_string()]]
+    );
+
+    // Without the flag no context is attached
+    assert_eq!(
+        search(temp_dir.path(), false, 5),
+        vec![Vec::<String>::new()]
+    );
+}
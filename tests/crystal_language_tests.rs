@@ -1,8 +1,10 @@
 use probe_code::extract::process_file_for_extraction;
 use probe_code::extract::symbols::extract_symbols;
-use probe_code::query::{perform_query, QueryOptions};
+use probe_code::query::{perform_query, GroupBy, QueryOptions};
 use probe_code::search::filters::SearchFilters;
-use probe_code::search::{perform_probe, SearchOptions};
+use probe_code::search::{
+    perform_probe, BinaryDetection, DedupMode, MatchScope, RankMode, ResultSort, SearchOptions,
+};
 use probe_code::semantic_context::build_query_source_context;
 use std::path::{Path, PathBuf};
 
@@ -144,6 +146,7 @@ fn test_crystal_query_support() {
     let options = QueryOptions {
         path: &fixture_root(),
         pattern: "def active? : Bool",
+        or_patterns: &[],
         language: Some("crystal"),
         ignore: &[],
         allow_tests: true,
@@ -153,6 +156,10 @@ fn test_crystal_query_support() {
         no_gitignore: true,
         strict: false,
         text_extensions: &[],
+        parent_kinds: &[],
+        sexp: false,
+        group_by: GroupBy::None,
+        follow_symlinks: false,
     };
 
     let matches = perform_query(&options).expect("Crystal query should run");
@@ -174,6 +181,7 @@ fn test_crystal_query_auto_detect_support() {
     let options = QueryOptions {
         path: &fixture_root(),
         pattern: "class User < Serializable",
+        or_patterns: &[],
         language: None,
         ignore: &[],
         allow_tests: true,
@@ -183,6 +191,10 @@ fn test_crystal_query_auto_detect_support() {
         no_gitignore: true,
         strict: false,
         text_extensions: &[],
+        parent_kinds: &[],
+        sexp: false,
+        group_by: GroupBy::None,
+        follow_symlinks: false,
     };
 
     let matches = perform_query(&options).expect("Crystal query should auto-detect .cr files");
@@ -216,15 +228,42 @@ fn test_crystal_search_language_filter_and_test_exclusion() {
         max_results: Some(20),
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: false,
         no_merge: false,
         merge_threshold: None,
+        merge_respect_boundaries: true,
         lsp: false,
         dry_run: false,
         session: None,
         timeout: 30,
         question: None,
         no_gitignore: true,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     let results = perform_probe(&options).expect("search should support Crystal language filter");
@@ -256,15 +295,42 @@ fn test_crystal_search_excludes_direct_spec_paths() {
         max_results: Some(20),
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: false,
         no_merge: false,
         merge_threshold: None,
+        merge_respect_boundaries: true,
         lsp: false,
         dry_run: false,
         session: None,
         timeout: 30,
         question: None,
         no_gitignore: true,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     let results = perform_probe(&options).expect("search should handle direct Crystal spec paths");
@@ -296,15 +362,42 @@ fn test_crystal_search_language_hint_filter() {
         max_results: Some(20),
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: false,
         no_merge: false,
         merge_threshold: None,
+        merge_respect_boundaries: true,
         lsp: false,
         dry_run: false,
         session: None,
         timeout: 30,
         question: None,
         no_gitignore: true,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     let results = perform_probe(&options).expect("search should support Crystal lang: hint");
@@ -0,0 +1,130 @@
+use probe_code::extract::process_file_for_extraction;
+use probe_code::query::{perform_query, AstMatch, GroupBy, QueryOptions};
+use std::path::PathBuf;
+
+fn fixture_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/csharp/project1")
+}
+
+fn cart_file() -> PathBuf {
+    fixture_root().join("src/Cart.cs")
+}
+
+fn extract_symbol(symbol: &str) -> Vec<(usize, usize)> {
+    process_file_for_extraction(
+        &cart_file(),
+        None,
+        None,
+        Some(symbol),
+        true,
+        0,
+        None,
+        false,
+        false,
+    )
+    .expect("extract should find C# symbol")
+    .into_iter()
+    .map(|result| result.lines)
+    .collect()
+}
+
+fn query(pattern: &str, language: Option<&str>) -> Vec<AstMatch> {
+    let options = QueryOptions {
+        path: &fixture_root(),
+        pattern,
+        or_patterns: &[],
+        language,
+        ignore: &[],
+        allow_tests: true,
+        max_results: None,
+        with_context: false,
+        format: "terminal",
+        no_gitignore: true,
+        strict: false,
+        text_extensions: &[],
+        parent_kinds: &[],
+        sexp: false,
+        group_by: GroupBy::None,
+        follow_symlinks: false,
+    };
+
+    perform_query(&options).expect("C# query should run")
+}
+
+fn line_ranges(matches: &[AstMatch]) -> Vec<(usize, usize)> {
+    matches.iter().map(|m| (m.line_start, m.line_end)).collect()
+}
+
+#[test]
+fn test_csharp_symbol_extraction_block_boundaries() {
+    assert_eq!(extract_symbol("Total"), vec![(32, 35)]);
+    assert_eq!(extract_symbol("Cart.Add"), vec![(22, 25)]);
+    assert_eq!(extract_symbol("Owner"), vec![(10, 10)]);
+    assert_eq!(extract_symbol("Count"), vec![(12, 15)]);
+    assert_eq!(extract_symbol("Cart"), vec![(6, 36)]);
+}
+
+#[test]
+fn test_csharp_symbol_extraction_ignores_return_types() {
+    // `Find` returns a `LineItem`, which must not make it a match for the class name
+    assert_eq!(extract_symbol("LineItem"), vec![(38, 45)]);
+}
+
+#[test]
+fn test_csharp_extraction_by_line_target() {
+    let results = process_file_for_extraction(
+        &cart_file(),
+        Some(14),
+        Some(14),
+        None,
+        true,
+        0,
+        None,
+        false,
+        false,
+    )
+    .expect("extract should find enclosing C# property from line target");
+
+    assert_eq!(results.lines, (12, 15));
+    assert!(results.code.contains("public int Count"));
+}
+
+#[test]
+fn test_csharp_query_matches_methods() {
+    let matches = query("public $RET $NAME($$$) { $$$ }", Some("csharp"));
+
+    // The constructor has no return type, so only the three methods match
+    assert_eq!(
+        line_ranges(&matches),
+        vec![(22, 25), (27, 30), (32, 35)],
+        "matches: {:?}",
+        matches.iter().map(|m| &m.matched_text).collect::<Vec<_>>()
+    );
+    let names: Vec<&str> = matches
+        .iter()
+        .map(|m| {
+            let name = &m.captures["NAME"][0];
+            &m.matched_text[name.byte_start - m.byte_start..name.byte_end - m.byte_start]
+        })
+        .collect();
+    assert_eq!(names, vec!["Add", "Find", "Total"]);
+}
+
+#[test]
+fn test_csharp_query_matches_properties_with_language_alias() {
+    let matches = query("public $TYPE $NAME { get; set; }", Some("cs"));
+
+    assert_eq!(
+        line_ranges(&matches),
+        vec![(10, 10), (40, 40), (42, 42), (44, 44)],
+        "matches: {:?}",
+        matches.iter().map(|m| &m.matched_text).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_csharp_query_auto_detect_statements() {
+    let matches = query("_items.Add($ITEM)", None);
+
+    assert_eq!(line_ranges(&matches), vec![(24, 24)]);
+}
@@ -0,0 +1,155 @@
+use probe_code::models::SearchResult;
+use probe_code::search::{
+    perform_probe, BinaryDetection, DedupMode, MatchScope, RankMode, ResultSort, SearchOptions,
+};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+/// A long boilerplate function; blocks with different names share all but a few shingles
+fn ledger_block(name: &str) -> String {
+    let entries: String = (100..160)
+        .map(|i| format!("    let entry_{i} = load({i});\n"))
+        .collect();
+    format!("fn settle_{name}() {{\n{entries}}}\n")
+}
+
+fn create_test_files(root: &Path) {
+    // Two vendored copies of the same block, a renamed copy and an unrelated function
+    for vendor in ["vendor_a", "vendor_b"] {
+        fs::create_dir(root.join(vendor)).unwrap();
+        fs::write(
+            root.join(vendor).join("ledger.rs"),
+            ledger_block("invoices"),
+        )
+        .unwrap();
+    }
+    fs::write(root.join("payments.rs"), ledger_block("payments")).unwrap();
+    fs::write(
+        root.join("audit.rs"),
+        "fn settle_audit(auditor: &Auditor) {\n    auditor.sign_off();\n}\n",
+    )
+    .unwrap();
+}
+
+fn search(root: &Path, dedup: DedupMode) -> Vec<SearchResult> {
+    let queries = vec!["settle".to_string()];
+    let custom_ignores: Vec<String> = vec![];
+    let options = SearchOptions {
+        path: root,
+        queries: &queries,
+        files_only: false,
+        custom_ignores: &custom_ignores,
+        exclude_filenames: true,
+        language: None,
+        reranker: "bm25",
+        frequency_search: true,
+        max_results: None,
+        max_bytes: None,
+        max_tokens: None,
+        tokenizer: None,
+        allow_tests: true,
+        no_merge: true,
+        merge_threshold: None,
+        merge_respect_boundaries: true,
+        dry_run: false,
+        session: None,
+        timeout: 30,
+        question: None,
+        exact: false,
+        no_gitignore: true,
+        lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup,
+        extra_roots: &[],
+    };
+
+    perform_probe(&options)
+        .expect("Failed to perform search")
+        .results
+}
+
+fn result_for<'a>(results: &'a [SearchResult], file_name: &str) -> Option<&'a SearchResult> {
+    results.iter().find(|r| r.file.ends_with(file_name))
+}
+
+#[test]
+fn test_no_dedup_keeps_every_block() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    create_test_files(temp_dir.path());
+
+    let results = search(temp_dir.path(), DedupMode::None);
+    assert_eq!(results.len(), 4);
+    assert!(results.iter().all(|r| r.duplicates.is_none()));
+}
+
+#[test]
+fn test_exact_dedup_collapses_identical_blocks() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    create_test_files(temp_dir.path());
+
+    let results = search(temp_dir.path(), DedupMode::Exact);
+    assert_eq!(results.len(), 3);
+    let vendored: Vec<&SearchResult> = results
+        .iter()
+        .filter(|r| r.file.ends_with("ledger.rs"))
+        .collect();
+    assert_eq!(vendored.len(), 1);
+    assert_eq!(vendored[0].duplicates, Some(1));
+    assert_eq!(
+        result_for(&results, "payments.rs").and_then(|r| r.duplicates),
+        Some(0)
+    );
+    assert_eq!(
+        result_for(&results, "audit.rs").and_then(|r| r.duplicates),
+        Some(0)
+    );
+}
+
+#[test]
+fn test_near_dedup_keeps_the_best_ranked_instance() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    create_test_files(temp_dir.path());
+
+    let all = search(temp_dir.path(), DedupMode::None);
+    let best_ledger_score = all
+        .iter()
+        .filter(|r| !r.file.ends_with("audit.rs"))
+        .filter_map(|r| r.score)
+        .fold(f64::MIN, f64::max);
+
+    let results = search(temp_dir.path(), DedupMode::Near);
+    assert_eq!(results.len(), 2);
+    let kept = results
+        .iter()
+        .find(|r| !r.file.ends_with("audit.rs"))
+        .expect("one ledger block is kept");
+    assert_eq!(kept.duplicates, Some(2));
+    assert_eq!(kept.score, Some(best_ledger_score));
+    assert_eq!(
+        result_for(&results, "audit.rs").and_then(|r| r.duplicates),
+        Some(0)
+    );
+}
@@ -0,0 +1,103 @@
+use probe_code::models::LimitedSearchResults;
+use probe_code::search::{
+    perform_probe, BinaryDetection, DedupMode, MatchScope, RankMode, ResultSort, SearchOptions,
+};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn create_test_files(root: &Path) {
+    for name in ["orders", "refunds", "invoices", "payouts"] {
+        fs::write(
+            root.join(format!("{name}.rs")),
+            format!("fn settle_{name}(ledger: &Ledger) {{\n    ledger.settle(\"{name}\");\n}}\n"),
+        )
+        .unwrap();
+    }
+}
+
+fn search(root: &Path, dry_run: bool, max_results: Option<usize>) -> LimitedSearchResults {
+    let queries = vec!["ledger".to_string()];
+    let custom_ignores: Vec<String> = vec![];
+    let options = SearchOptions {
+        path: root,
+        queries: &queries,
+        files_only: false,
+        custom_ignores: &custom_ignores,
+        exclude_filenames: true,
+        language: None,
+        reranker: "bm25",
+        frequency_search: true,
+        max_results,
+        max_bytes: None,
+        max_tokens: None,
+        tokenizer: None,
+        allow_tests: true,
+        no_merge: true,
+        merge_threshold: None,
+        merge_respect_boundaries: true,
+        dry_run,
+        session: None,
+        timeout: 30,
+        question: None,
+        exact: false,
+        no_gitignore: true,
+        lsp: false,
+        sort: ResultSort::Path,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
+    };
+
+    perform_probe(&options).expect("Failed to perform search")
+}
+
+#[test]
+fn test_dry_run_estimates_size_without_limits() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    create_test_files(temp_dir.path());
+
+    // Regular searches do not estimate
+    assert!(search(temp_dir.path(), false, Some(1))
+        .estimated_full_size
+        .is_none());
+
+    let unlimited = search(temp_dir.path(), false, None);
+    let limited = search(temp_dir.path(), true, Some(1));
+    assert_eq!(limited.results.len(), 1);
+
+    let estimate = limited
+        .estimated_full_size
+        .expect("dry runs estimate the full size");
+    assert_eq!(estimate.results, unlimited.results.len());
+    assert_eq!(
+        estimate.total_bytes,
+        unlimited
+            .results
+            .iter()
+            .map(|r| r.code.len())
+            .sum::<usize>()
+    );
+    assert!(estimate.total_tokens > 0);
+}
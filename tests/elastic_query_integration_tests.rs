@@ -8,7 +8,10 @@ use tempfile::TempDir;
 
 use probe_code::search::elastic_query::Expr;
 use probe_code::search::query::QueryPlan;
-use probe_code::search::{perform_probe, SearchOptions};
+use probe_code::search::{
+    perform_probe, BinaryDetection, DedupMode, MatchScope, MergeThreshold, RankMode, ResultSort,
+    SearchOptions,
+};
 
 /// Create test files with different content for testing queries
 fn create_test_files(temp_dir: &Path) {
@@ -118,9 +121,11 @@ fn test_required_term_query() {
         max_results: None,
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: true,
         no_merge: false,
-        merge_threshold: Some(5),
+        merge_threshold: Some(MergeThreshold::Lines(5)),
+        merge_respect_boundaries: true,
         dry_run: false,
         session: None,
         timeout: 30,
@@ -128,6 +133,31 @@ fn test_required_term_query() {
         exact: false,
         no_gitignore: false,
         lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     // Print the temp_path for debugging
@@ -229,9 +259,11 @@ fn test_excluded_term_query() {
         max_results: None,
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: true,
         no_merge: false,
-        merge_threshold: Some(5),
+        merge_threshold: Some(MergeThreshold::Lines(5)),
+        merge_respect_boundaries: true,
         dry_run: false,
         session: None,
         timeout: 30,
@@ -239,6 +271,31 @@ fn test_excluded_term_query() {
         exact: false,
         no_gitignore: false,
         lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     // Print the query for debugging
@@ -296,6 +353,96 @@ fn test_excluded_term_query() {
     );
 }
 
+/// Excluded terms are stemmed like positive terms, and win over them
+#[test]
+fn test_excluded_term_matches_stemmed_forms() {
+    let temp_dir = TempDir::new().unwrap();
+    let temp_path = temp_dir.path();
+    fs::write(
+        temp_path.join("rotate_a.rs"),
+        "fn refresh_session() {\n    rotate_token();\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        temp_path.join("rotate_b.rs"),
+        "fn refresh_session() {\n    rotate_cookie();\n}\n",
+    )
+    .unwrap();
+
+    let search_files = |query: &str| -> Vec<String> {
+        let queries = vec![query.to_string()];
+        let custom_ignores: Vec<String> = vec![];
+        let options = SearchOptions {
+            path: temp_path,
+            queries: &queries,
+            files_only: false,
+            custom_ignores: &custom_ignores,
+            exclude_filenames: true,
+            language: None,
+            reranker: "bm25",
+            frequency_search: true,
+            max_results: None,
+            max_bytes: None,
+            max_tokens: None,
+            tokenizer: None,
+            allow_tests: true,
+            no_merge: true,
+            merge_threshold: None,
+            merge_respect_boundaries: true,
+            dry_run: false,
+            session: None,
+            timeout: 30,
+            question: None,
+            exact: false,
+            no_gitignore: true,
+            lsp: false,
+            sort: ResultSort::Score,
+            regex: false,
+            match_scope: MatchScope::All,
+            invert: false,
+            kinds: None,
+            recency_boost: false,
+            recency_half_life_days: 14.0,
+            context_symbols: false,
+            context_symbols_depth: 5,
+            explain: false,
+            use_index: false,
+            rank_mode: RankMode::Block,
+            within: None,
+            changed_since: None,
+            tracked_only: false,
+            rerank_top_k: 50,
+            binary_detection: BinaryDetection::NullByte,
+            force_text_extensions: &[],
+            search_archives: false,
+            encoding: None,
+            min_score: None,
+            follow_symlinks: false,
+            ranking_profile: None,
+            dedup: DedupMode::None,
+            extra_roots: &[],
+        };
+        perform_probe(&options)
+            .unwrap()
+            .results
+            .into_iter()
+            .map(|r| {
+                Path::new(&r.file)
+                    .file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect()
+    };
+
+    // "-tokens" also excludes the block that only mentions "token"
+    assert_eq!(search_files("refresh -tokens"), vec!["rotate_b.rs"]);
+
+    // A term that is both searched for and excluded is excluded
+    assert!(search_files("cookie -cookies").is_empty());
+}
+
 /// Test a query with OR: keywordAlpha OR keywordBeta
 #[test]
 fn test_or_query() {
@@ -324,9 +471,11 @@ fn test_or_query() {
         max_results: None,
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: true,
         no_merge: false,
-        merge_threshold: Some(5),
+        merge_threshold: Some(MergeThreshold::Lines(5)),
+        merge_respect_boundaries: true,
         dry_run: false,
         session: None,
         timeout: 30,
@@ -334,6 +483,31 @@ fn test_or_query() {
         exact: false,
         no_gitignore: false,
         lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     // Print the test files for debugging
@@ -427,9 +601,11 @@ fn test_complex_query_or() {
         max_results: None,
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: true,
         no_merge: false,
-        merge_threshold: Some(5),
+        merge_threshold: Some(MergeThreshold::Lines(5)),
+        merge_respect_boundaries: true,
         dry_run: false,
         session: None,
         timeout: 30,
@@ -437,6 +613,31 @@ fn test_complex_query_or() {
         exact: false,
         no_gitignore: false,
         lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     // Print the test files for debugging
@@ -528,9 +729,11 @@ fn test_complex_query_exclusion() {
         max_results: None,
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: true,
         no_merge: false,
-        merge_threshold: Some(5),
+        merge_threshold: Some(MergeThreshold::Lines(5)),
+        merge_respect_boundaries: true,
         dry_run: false,
         session: None,
         timeout: 30,
@@ -538,6 +741,31 @@ fn test_complex_query_exclusion() {
         exact: false,
         no_gitignore: false,
         lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     // Print the query for debugging
@@ -629,9 +857,11 @@ fn test_function() {
         max_results: None,
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: true,
         no_merge: false,
-        merge_threshold: Some(5),
+        merge_threshold: Some(MergeThreshold::Lines(5)),
+        merge_respect_boundaries: true,
         dry_run: false,
         session: None,
         timeout: 30,
@@ -639,6 +869,31 @@ fn test_function() {
         exact: false,
         no_gitignore: false,
         lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     // Run the search
@@ -0,0 +1,128 @@
+use probe_code::models::{LimitedSearchResults, SkipReason};
+use probe_code::search::{
+    perform_probe, BinaryDetection, DedupMode, MatchScope, RankMode, ResultSort, SearchOptions,
+};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn create_test_files(root: &Path) {
+    let (legacy, _, _) = encoding_rs::SHIFT_JIS
+        .encode("fn settle_ledger() {\n    println!(\"台帳を精算する\");\n}\n");
+    fs::write(root.join("legacy.rs"), legacy).unwrap();
+    // A lead byte followed by a line break is malformed in Shift_JIS
+    fs::write(root.join("broken.rs"), b"fn ledger_total() {}\n// \x82\n").unwrap();
+}
+
+fn search(root: &Path, encoding: Option<&str>) -> anyhow::Result<LimitedSearchResults> {
+    let queries = vec!["ledger".to_string()];
+    let custom_ignores: Vec<String> = vec![];
+    let options = SearchOptions {
+        path: root,
+        queries: &queries,
+        files_only: false,
+        custom_ignores: &custom_ignores,
+        exclude_filenames: true,
+        language: None,
+        reranker: "bm25",
+        frequency_search: true,
+        max_results: None,
+        max_bytes: None,
+        max_tokens: None,
+        tokenizer: None,
+        allow_tests: true,
+        no_merge: true,
+        merge_threshold: None,
+        merge_respect_boundaries: true,
+        dry_run: false,
+        session: None,
+        timeout: 30,
+        question: None,
+        exact: false,
+        no_gitignore: true,
+        lsp: false,
+        sort: ResultSort::Path,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: encoding.map(str::to_string),
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
+    };
+
+    perform_probe(&options)
+}
+
+fn result_code(results: &LimitedSearchResults, file_name: &str) -> String {
+    results
+        .results
+        .iter()
+        .filter(|r| {
+            Path::new(&r.file)
+                .file_name()
+                .is_some_and(|f| f == file_name)
+        })
+        .map(|r| r.code.clone())
+        .collect()
+}
+
+fn undecodable_files(results: &LimitedSearchResults) -> Vec<String> {
+    results
+        .skipped_files
+        .iter()
+        .filter(|skipped| skipped.reason == SkipReason::Decoding)
+        .filter_map(|skipped| Path::new(&skipped.result.file).file_name())
+        .map(|name| name.to_string_lossy().to_string())
+        .collect()
+}
+
+#[test]
+fn test_encoding_decodes_legacy_files() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    create_test_files(temp_dir.path());
+
+    let results = search(temp_dir.path(), Some("shift_jis")).unwrap();
+    assert!(result_code(&results, "legacy.rs").contains("台帳を精算する"));
+    assert!(result_code(&results, "broken.rs").is_empty());
+    assert_eq!(undecodable_files(&results), vec!["broken.rs"]);
+}
+
+#[test]
+fn test_default_reads_files_as_utf8() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    create_test_files(temp_dir.path());
+
+    let results = search(temp_dir.path(), None).unwrap();
+    let legacy = result_code(&results, "legacy.rs");
+    assert!(legacy.contains("settle_ledger"));
+    assert!(!legacy.contains("台帳"));
+    assert!(!result_code(&results, "broken.rs").is_empty());
+    assert!(undecodable_files(&results).is_empty());
+}
+
+#[test]
+fn test_unknown_encoding_is_an_error() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    create_test_files(temp_dir.path());
+
+    let err = search(temp_dir.path(), Some("klingon")).unwrap_err();
+    assert!(err.to_string().contains("Unknown encoding: klingon"));
+}
@@ -0,0 +1,80 @@
+use probe_code::extract::extraction_cache::{clear_extraction_cache, parse_count};
+use probe_code::extract::{handle_extract, ExtractOptions};
+use serial_test::serial;
+use std::fs;
+use tempfile::tempdir;
+
+fn cached_extract_options(file: String) -> ExtractOptions {
+    ExtractOptions {
+        files: vec![file],
+        custom_ignores: Vec::new(),
+        context_lines: 0,
+        format: "plain".to_string(),
+        from_clipboard: false,
+        input_file: None,
+        to_clipboard: false,
+        clipboard_max_bytes: None,
+        dry_run: false,
+        plain_paths: false,
+        diff: false,
+        allow_tests: true,
+        keep_input: false,
+        prompt: None,
+        instructions: None,
+        no_gitignore: false,
+        lsp: false,
+        cache: true,
+        strip_comments: false,
+        redact_strings: false,
+        signatures_only: false,
+        only_kinds: None,
+        public_only: false,
+        line_numbers: false,
+        blame: false,
+        diff_blocks: false,
+        encoding: None,
+    }
+}
+
+#[test]
+#[serial]
+fn test_unchanged_file_is_served_from_cache() {
+    clear_extraction_cache();
+    let temp_dir = tempdir().unwrap();
+    let file_path = temp_dir.path().join("cached.rs");
+    fs::write(&file_path, "fn cached() {\n    println!(\"cached\");\n}\n").unwrap();
+    let file = format!("{}:2", file_path.to_string_lossy());
+
+    let before = parse_count();
+    handle_extract(cached_extract_options(file.clone())).unwrap();
+    assert_eq!(parse_count(), before + 1, "First extract should parse");
+
+    handle_extract(cached_extract_options(file)).unwrap();
+    assert_eq!(
+        parse_count(),
+        before + 1,
+        "Second extract of an unchanged file should skip reparsing"
+    );
+}
+
+#[test]
+#[serial]
+fn test_changed_file_is_reparsed() {
+    clear_extraction_cache();
+    let temp_dir = tempdir().unwrap();
+    let file_path = temp_dir.path().join("changed.rs");
+    fs::write(&file_path, "fn before() {\n    println!(\"before\");\n}\n").unwrap();
+    let file = format!("{}:2", file_path.to_string_lossy());
+
+    let before = parse_count();
+    handle_extract(cached_extract_options(file.clone())).unwrap();
+    assert_eq!(parse_count(), before + 1);
+
+    fs::write(&file_path, "fn after() {\n    println!(\"after\");\n}\n").unwrap();
+    handle_extract(cached_extract_options(file)).unwrap();
+    assert_eq!(
+        parse_count(),
+        before + 2,
+        "Changed file content should invalidate the cached extraction"
+    );
+}
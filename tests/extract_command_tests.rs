@@ -254,6 +254,10 @@ fn test_format_and_print_extraction_results() {
         tokenized_content: None,
         lsp_info: None,
         parent_context: None,
+        score_breakdown: None,
+        term_matches: None,
+        duplicates: None,
+        root: None,
     };
 
     // Test different formats
@@ -1309,6 +1313,114 @@ index cb2cb64..3717769 100644
     );
 }
 
+#[test]
+fn test_integration_extract_command_diff_blocks_marks_changed_lines() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let content = "fn first() {\n    let a = 1;\n}\n\nfn second() {\n    let b = 2;\n}\n\nfn third() {\n    let c = 3;\n}\n";
+    fs::write(temp_dir.path().join("lib.rs"), content).unwrap();
+
+    // One hunk touching both `first` and `second`
+    let diff_content = r#"diff --git a/lib.rs b/lib.rs
+index cb2cb64..3717769 100644
+--- a/lib.rs
++++ b/lib.rs
+@@ -2,5 +2,5 @@ fn first() {
+-    let a = 0;
++    let a = 1;
+ }
+ 
+ fn second() {
+-    let b = 0;
++    let b = 2;
+"#;
+    fs::write(temp_dir.path().join("change.diff"), diff_content).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_probe"))
+        .current_dir(temp_dir.path())
+        .args([
+            "extract",
+            "--input-file",
+            "change.diff",
+            "--diff-blocks",
+            "--format",
+            "json",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success(), "Command failed to execute");
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let parsed: serde_json::Value = serde_json::from_str(&stdout).unwrap();
+    let results = parsed["results"].as_array().unwrap();
+
+    // One block per function, each with its own changed line; `third` is untouched
+    assert_eq!(results.len(), 2, "{stdout}");
+    assert_eq!(results[0]["lines"], serde_json::json!([1, 3]));
+    assert_eq!(results[0]["changed_lines"], serde_json::json!([2]));
+    assert_eq!(results[1]["lines"], serde_json::json!([5, 7]));
+    assert_eq!(results[1]["changed_lines"], serde_json::json!([6]));
+
+    let output = Command::new(env!("CARGO_BIN_EXE_probe"))
+        .current_dir(temp_dir.path())
+        .args([
+            "extract",
+            "--input-file",
+            "change.diff",
+            "--diff-blocks",
+            "--format",
+            "plain",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(
+        stdout.contains("  fn first() {\n+     let a = 1;\n  }"),
+        "{stdout}"
+    );
+}
+
+#[test]
+fn test_integration_extract_command_plain_paths_lists_diff_ranges() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let content = "fn first() {\n    let a = 1;\n}\n\nfn second() {\n    let b = 2;\n}\n";
+    fs::write(temp_dir.path().join("lib.rs"), content).unwrap();
+
+    let diff_content = r#"diff --git a/lib.rs b/lib.rs
+index cb2cb64..3717769 100644
+--- a/lib.rs
++++ b/lib.rs
+@@ -2,5 +2,5 @@ fn first() {
+-    let a = 0;
++    let a = 1;
+ }
+ 
+ fn second() {
+-    let b = 0;
++    let b = 2;
+"#;
+    fs::write(temp_dir.path().join("change.diff"), diff_content).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_probe"))
+        .current_dir(temp_dir.path())
+        .args([
+            "extract",
+            "--input-file",
+            "change.diff",
+            "--diff-blocks",
+            "--plain-paths",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success(), "Command failed to execute");
+
+    // Nothing but one `file:start-end` line per block
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let lines: Vec<&str> = stdout.lines().collect();
+    assert_eq!(lines.len(), 2, "{stdout}");
+    assert!(lines[0].ends_with("lib.rs:1-3"), "{stdout}");
+    assert!(lines[1].ends_with("lib.rs:5-7"), "{stdout}");
+}
+
 #[test]
 fn test_integration_extract_command_xml_format() {
     use roxmltree::Document;
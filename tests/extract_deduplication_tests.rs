@@ -45,7 +45,9 @@ fn standalone_function() {
         from_clipboard: false,
         input_file: None,
         to_clipboard: false,
+        clipboard_max_bytes: None,
         dry_run: true, // Use dry run to avoid actual output
+        plain_paths: false,
         diff: false,
         allow_tests: true,
         keep_input: false,
@@ -53,6 +55,16 @@ fn standalone_function() {
         instructions: None,
         no_gitignore: false,
         lsp: false,
+        cache: false,
+        strip_comments: false,
+        redact_strings: false,
+        signatures_only: false,
+        only_kinds: None,
+        public_only: false,
+        line_numbers: false,
+        blame: false,
+        diff_blocks: false,
+        encoding: None,
     };
 
     // Call handle_extract
@@ -140,3 +152,97 @@ fn standalone_function() {
 
     // Success! The deduplication is working correctly
 }
+
+#[test]
+fn test_multiple_target_lines_extract_merged_blocks_in_file_order() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let file_path = temp_dir.path().join("nested_test.rs");
+    let content = r#"
+fn outer_function() {
+    let x = 10;
+
+    // This is a nested function that should be deduplicated
+    fn inner_function() {
+        let y = 20;
+        println!("Inner function: {}", y);
+    }
+
+    // Call the inner function
+    inner_function();
+    println!("Outer function: {}", x);
+}
+
+fn standalone_function() {
+    println!("This is standalone");
+}
+"#;
+    fs::write(&file_path, content).unwrap();
+
+    // Lines are given out of order, and line 6 lies inside the block of line 2
+    let output = Command::new(env!("CARGO_BIN_EXE_probe"))
+        .args([
+            "extract",
+            &format!("{}:17,2,6", file_path.to_string_lossy()),
+            "--allow-tests",
+            "--format",
+            "plain",
+        ])
+        .env("NO_COLOR", "1")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success(), "Command failed to execute");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // Each block appears once, in source order, with the gap between them marked
+    assert_eq!(stdout.matches("fn outer_function()").count(), 1, "{stdout}");
+    assert_eq!(stdout.matches("fn inner_function()").count(), 1, "{stdout}");
+    let outer = stdout.find("fn outer_function()").unwrap();
+    let gap = stdout.find("... line 15 omitted ...").expect(&stdout);
+    let standalone = stdout.find("fn standalone_function()").unwrap();
+    assert!(outer < gap && gap < standalone, "{stdout}");
+}
+
+#[test]
+fn test_target_in_context_padding_still_extracts_its_own_block() {
+    let temp_dir = tempfile::tempdir().unwrap();
+    let file_path = temp_dir.path().join("padding_test.rs");
+    let content = r#"
+fn first_function() {
+    println!("first");
+}
+fn second_function() {
+    let a = 1;
+    let b = 2;
+    let c = 3;
+    println!("second ends: {}", a + b + c);
+}
+"#;
+    fs::write(&file_path, content).unwrap();
+
+    // Line 6 lies in the context padding of line 3's block, but belongs to second_function
+    let output = Command::new(env!("CARGO_BIN_EXE_probe"))
+        .args([
+            "extract",
+            &format!("{}:3,6", file_path.to_string_lossy()),
+            "--context",
+            "3",
+            "--allow-tests",
+            "--format",
+            "plain",
+        ])
+        .env("NO_COLOR", "1")
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success(), "Command failed to execute");
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    assert_eq!(stdout.matches("fn first_function()").count(), 1, "{stdout}");
+    assert_eq!(
+        stdout.matches("fn second_function()").count(),
+        1,
+        "{stdout}"
+    );
+    assert!(stdout.contains("second ends"), "{stdout}");
+}
@@ -0,0 +1,167 @@
+use probe_code::extract::kind_filter::{extract_declarations, SymbolKind};
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+const RUST_SOURCE: &str = r#"/// Default port
+pub const PORT: u16 = 8080;
+
+/// A server
+#[derive(Debug)]
+pub struct Server {
+    port: u16,
+}
+
+impl Server {
+    /// Create a server
+    pub fn new() -> Self {
+        Server { port: PORT }
+    }
+
+    fn reset(&mut self) {
+        self.port = PORT;
+    }
+}
+
+pub enum Mode {
+    Fast,
+    Safe,
+}
+
+/// Start serving
+pub fn serve(server: &Server) -> bool {
+    let local = 1;
+    server.port > local
+}
+"#;
+
+fn write_source(dir: &TempDir, name: &str, content: &str) -> std::path::PathBuf {
+    let file = dir.path().join(name);
+    std::fs::write(&file, content).unwrap();
+    file
+}
+
+fn declaration_lines(file: &Path, kinds: &[SymbolKind], public_only: bool) -> Vec<(usize, usize)> {
+    extract_declarations(file, kinds, public_only, true, None)
+        .unwrap()
+        .expect("file should parse")
+        .results
+        .into_iter()
+        .map(|result| result.lines)
+        .collect()
+}
+
+#[test]
+fn test_symbol_kind_parsing() {
+    assert_eq!("fn".parse::<SymbolKind>(), Ok(SymbolKind::Function));
+    assert_eq!("Types".parse::<SymbolKind>(), Ok(SymbolKind::Type));
+    assert!("widget".parse::<SymbolKind>().is_err());
+}
+
+#[test]
+fn test_only_kinds_selects_declarations_with_their_doc_comments() {
+    let dir = TempDir::new().unwrap();
+    let file = write_source(&dir, "server.rs", RUST_SOURCE);
+
+    // Methods are found inside the impl, but nothing inside function bodies
+    assert_eq!(
+        declaration_lines(&file, &[SymbolKind::Function], false),
+        vec![(11, 14), (16, 18), (26, 30)]
+    );
+    assert_eq!(
+        declaration_lines(&file, &[SymbolKind::Type], false),
+        vec![(4, 8), (21, 24)]
+    );
+    assert_eq!(
+        declaration_lines(&file, &[SymbolKind::Const, SymbolKind::Enum], false),
+        vec![(1, 2), (21, 24)]
+    );
+}
+
+#[test]
+fn test_only_kinds_with_signatures_only() {
+    let dir = TempDir::new().unwrap();
+    let file = write_source(&dir, "server.rs", RUST_SOURCE);
+
+    let output = Command::new(env!("CARGO_BIN_EXE_probe"))
+        .args(["extract", file.to_str().unwrap()])
+        .args(["--only-kinds", "function", "--signatures-only"])
+        .args(["--format", "plain"])
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("/// Start serving"));
+    assert!(stdout.contains("pub fn serve(server: &Server) -> bool { ... }"));
+    assert!(stdout.contains("fn reset(&mut self) { ... }"));
+    assert!(!stdout.contains("pub struct Server"));
+    assert!(!stdout.contains("let local = 1;"));
+}
+
+#[test]
+fn test_public_only_skips_private_declarations() {
+    let dir = TempDir::new().unwrap();
+    let file = write_source(&dir, "server.rs", RUST_SOURCE);
+    assert_eq!(
+        declaration_lines(&file, &[SymbolKind::Function], true),
+        vec![(11, 14), (26, 30)]
+    );
+
+    let source = "def api():\n    pass\n\ndef _helper():\n    pass\n\nclass Client:\n    def send(self):\n        pass\n\n    def _retry(self):\n        pass\n\n    def __init__(self):\n        pass\n";
+    let file = write_source(&dir, "client.py", source);
+    assert_eq!(
+        declaration_lines(&file, &[SymbolKind::Function], true),
+        vec![(1, 2), (8, 9), (14, 15)]
+    );
+}
+
+#[test]
+fn test_only_kinds_extracts_whole_file_with_syntax_errors() {
+    let dir = TempDir::new().unwrap();
+    let source = "fn good(a: i32) -> i32 {\n    a + 1\n}\n\nfn broken(a: i32 {\n    a + 2\n}\n";
+    let file = write_source(&dir, "broken.rs", source);
+
+    let declarations = extract_declarations(&file, &[SymbolKind::Function], false, true, None)
+        .unwrap()
+        .expect("Rust has a grammar");
+    assert!(declarations.results.is_empty());
+    assert!(!declarations.error_lines.is_empty());
+
+    let output = Command::new(env!("CARGO_BIN_EXE_probe"))
+        .args(["extract", file.to_str().unwrap()])
+        .args(["--only-kinds", "function", "--format", "plain"])
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stdout.contains("a + 1"));
+    assert!(stdout.contains("a + 2"));
+    assert!(stderr.contains("syntax errors"), "{stderr}");
+}
+
+#[test]
+fn test_only_kinds_falls_back_to_whole_file_without_grammar() {
+    let dir = TempDir::new().unwrap();
+    let file = write_source(&dir, "notes.txt", "fn not_code() {}\n");
+
+    assert!(
+        extract_declarations(&file, &[SymbolKind::Function], false, true, None)
+            .unwrap()
+            .is_none()
+    );
+
+    let output = Command::new(env!("CARGO_BIN_EXE_probe"))
+        .args(["extract", file.to_str().unwrap()])
+        .args(["--only-kinds", "function", "--format", "plain"])
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stdout.contains("fn not_code() {}"));
+    assert!(stderr.contains("could not parse"), "{stderr}");
+}
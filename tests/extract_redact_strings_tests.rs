@@ -0,0 +1,61 @@
+use probe_code::extract::string_redactor::redact_strings;
+use probe_code::language::get_pooled_parser;
+
+/// The redacted output must still parse without errors
+fn assert_parses_cleanly(source: &str, extension: &str) {
+    let mut parser = get_pooled_parser(extension).unwrap();
+    let tree = parser.parse(source, None).unwrap();
+    assert!(
+        !tree.root_node().has_error(),
+        "Redacted {extension} source should still parse:\n{source}"
+    );
+}
+
+#[test]
+fn test_redact_rust_strings_keeps_code_and_quotes() {
+    let source = "fn connect() {\n    let key = \"sk-live-12345\";\n    let raw = r#\"token=\"abc\"\"#;\n    client.auth(key, raw);\n}\n";
+    let redacted = redact_strings(source, "rs").unwrap();
+
+    assert!(!redacted.contains("sk-live-12345"));
+    assert!(!redacted.contains("token="));
+    assert!(redacted.contains("let key = \"<redacted:13>\";"));
+    assert!(redacted.contains("let raw = r#\"<redacted:11>\"#;"));
+    assert!(redacted.contains("client.auth(key, raw);"));
+    assert_eq!(redacted.lines().count(), source.lines().count());
+    assert_parses_cleanly(&redacted, "rs");
+}
+
+#[test]
+fn test_redact_python_multiline_and_fstrings() {
+    let source =
+        "PASSWORD = \"\"\"line one\nline two\"\"\"\nuser = f\"admin-{name}-secret\"\nprint(user)\n";
+    let redacted = redact_strings(source, "py").unwrap();
+
+    assert!(!redacted.contains("line one"));
+    assert!(!redacted.contains("secret"));
+    // Multi-line strings keep their line count so positions stay valid
+    assert_eq!(redacted.lines().count(), source.lines().count());
+    assert!(redacted.starts_with("PASSWORD = \"\"\"<redacted:17>\n\"\"\"\n"));
+    // Interpolated code is not redacted
+    assert!(redacted.contains("{name}"));
+    assert!(redacted.contains("print(user)"));
+    assert_parses_cleanly(&redacted, "py");
+}
+
+#[test]
+fn test_redact_javascript_template_literal() {
+    let source = "const url = `https://api.example.com/${path}?token=xyz`;\nconst empty = '';\n";
+    let redacted = redact_strings(source, "js").unwrap();
+
+    assert!(!redacted.contains("api.example.com"));
+    assert!(!redacted.contains("token=xyz"));
+    assert!(redacted.contains("${path}"));
+    assert!(redacted.contains("const empty = '';"));
+    assert_parses_cleanly(&redacted, "js");
+}
+
+#[test]
+fn test_code_without_strings_is_untouched() {
+    let source = "func add(a, b int) int {\n\treturn a + b\n}\n";
+    assert_eq!(redact_strings(source, "go").unwrap(), source);
+}
@@ -0,0 +1,83 @@
+use probe_code::extract::signature_extractor::elide_bodies;
+use std::process::Command;
+use tempfile::TempDir;
+
+const RUST_SOURCE: &str = r#"/// Adds two numbers
+#[inline]
+pub fn add(a: i32, b: i32) -> i32 {
+    a + b
+}
+
+pub trait Shape {
+    /// Area of the shape
+    fn area(&self) -> f64;
+    fn name(&self) -> String {
+        "shape".to_string()
+    }
+}
+"#;
+
+#[test]
+fn test_rust_bodies_are_elided() {
+    assert_eq!(
+        elide_bodies(RUST_SOURCE, "rs").unwrap(),
+        r#"/// Adds two numbers
+#[inline]
+pub fn add(a: i32, b: i32) -> i32 { ... }
+
+pub trait Shape {
+    /// Area of the shape
+    fn area(&self) -> f64;
+    fn name(&self) -> String { ... }
+}
+"#
+    );
+}
+
+#[test]
+fn test_python_bodies_keep_docstrings() {
+    let source = "def greet(name):\n    \"\"\"Say hello.\"\"\"\n    message = \"hi \" + name\n    return message\n\ndef shout(name):\n    return name.upper()\n";
+    assert_eq!(
+        elide_bodies(source, "py").unwrap(),
+        "def greet(name):\n    \"\"\"Say hello.\"\"\"\n    ...\n\ndef shout(name):\n    ...\n"
+    );
+}
+
+#[test]
+fn test_javascript_methods_are_elided_but_expression_lambdas_kept() {
+    let source = "class Cart {\n  total(items) {\n    return items.reduce((a, b) => a + b, 0);\n  }\n}\nconst double = (x) => x * 2;\n";
+    assert_eq!(
+        elide_bodies(source, "js").unwrap(),
+        "class Cart {\n  total(items) { ... }\n}\nconst double = (x) => x * 2;\n"
+    );
+}
+
+#[test]
+fn test_unparsable_code_is_kept_in_full() {
+    let source = "fn broken(a: i32 {\n    a + 1\n}\n";
+    assert_eq!(elide_bodies(source, "rs").unwrap(), source);
+    assert_eq!(
+        elide_bodies("some text\n", "unknown-ext").unwrap(),
+        "some text\n"
+    );
+}
+
+#[test]
+fn test_extract_signatures_only_flag() {
+    let temp_dir = TempDir::new().unwrap();
+    let file = temp_dir.path().join("shapes.rs");
+    std::fs::write(&file, RUST_SOURCE).unwrap();
+
+    let output = Command::new(env!("CARGO_BIN_EXE_probe"))
+        .args(["extract", file.to_str().unwrap(), "--signatures-only"])
+        .args(["--format", "plain"])
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("pub fn add(a: i32, b: i32) -> i32 { ... }"));
+    assert!(stdout.contains("fn name(&self) -> String { ... }"));
+    assert!(stdout.contains("/// Area of the shape"));
+    assert!(!stdout.contains("\"shape\".to_string()"));
+}
@@ -0,0 +1,56 @@
+use probe_code::extract::comment_stripper::strip_comments;
+
+#[test]
+fn test_strip_rust_comments_preserves_lines() {
+    let source = r#"/// Adds two numbers
+fn add(a: i32, b: i32) -> i32 {
+    // sum them up
+    let url = "http://example.com"; // trailing note
+    /* block
+       comment */
+    a + b
+}
+"#;
+    let stripped = strip_comments(source, "rs").unwrap();
+
+    assert_eq!(
+        stripped.lines().count(),
+        source.lines().count(),
+        "Line count must be preserved:\n{stripped}"
+    );
+    assert!(!stripped.contains("Adds two numbers"));
+    assert!(!stripped.contains("sum them up"));
+    assert!(!stripped.contains("trailing note"));
+    assert!(!stripped.contains("block"));
+
+    let lines: Vec<&str> = stripped.lines().collect();
+    assert_eq!(lines[0], "");
+    assert_eq!(lines[1], "fn add(a: i32, b: i32) -> i32 {");
+    assert_eq!(lines[2], "");
+    // Comment markers inside string literals are code, not comments
+    assert_eq!(lines[3], "    let url = \"http://example.com\";");
+    assert_eq!(lines[6], "    a + b");
+}
+
+#[test]
+fn test_strip_python_comments_keeps_docstrings_and_code() {
+    let source = "def greet(name):\n    # say hello\n    message = \"# not a comment\"\n    return message  # done\n";
+    let stripped = strip_comments(source, "py").unwrap();
+
+    assert_eq!(
+        stripped,
+        "def greet(name):\n\n    message = \"# not a comment\"\n    return message\n"
+    );
+}
+
+#[test]
+fn test_code_without_comments_is_untouched() {
+    let source = "function total(items) {\n  return items.reduce((a, b) => a + b, 0);\n}\n";
+    assert_eq!(strip_comments(source, "js").unwrap(), source);
+}
+
+#[test]
+fn test_unknown_language_is_returned_unchanged() {
+    let source = "# heading\nsome text\n";
+    assert_eq!(strip_comments(source, "unknown-ext").unwrap(), source);
+}
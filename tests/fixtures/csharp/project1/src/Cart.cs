@@ -0,0 +1,46 @@
+using System.Collections.Generic;
+using System.Linq;
+
+namespace ProbeFixture.Shop
+{
+    public class Cart
+    {
+        private readonly List<LineItem> _items = new List<LineItem>();
+
+        public string Owner { get; set; }
+
+        public int Count
+        {
+            get { return _items.Count; }
+        }
+
+        public Cart(string owner)
+        {
+            Owner = owner;
+        }
+
+        public void Add(LineItem item)
+        {
+            _items.Add(item);
+        }
+
+        public LineItem Find(string sku)
+        {
+            return _items.FirstOrDefault(item => item.Sku == sku);
+        }
+
+        public decimal Total()
+        {
+            return _items.Sum(item => item.Price * item.Quantity);
+        }
+    }
+
+    public class LineItem
+    {
+        public string Sku { get; set; }
+
+        public decimal Price { get; set; }
+
+        public int Quantity { get; set; }
+    }
+}
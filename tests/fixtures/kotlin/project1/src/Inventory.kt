@@ -0,0 +1,34 @@
+package probe.fixture
+
+fun formatPrice(cents: Long): String {
+    val units = cents / 100
+    return "$units.${cents % 100}"
+}
+
+fun String.shout(): String = uppercase() + "!"
+
+class Inventory(val owner: String) {
+    val items = mutableMapOf<String, Int>()
+
+    fun add(sku: String, quantity: Int): Int {
+        val total = (items[sku] ?: 0) + quantity
+        items[sku] = total
+        return total
+    }
+
+    fun count(sku: String): Int {
+        return items[sku] ?: 0
+    }
+
+    companion object {
+        fun empty(): Inventory {
+            return Inventory("nobody")
+        }
+    }
+}
+
+object Registry {
+    fun contains(name: String): Boolean {
+        return name.isNotEmpty()
+    }
+}
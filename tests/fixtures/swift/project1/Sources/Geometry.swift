@@ -0,0 +1,41 @@
+import Foundation
+
+struct Point {
+    var x: Double
+    var y: Double
+
+    init(x: Double, y: Double) {
+        self.x = x
+        self.y = y
+    }
+}
+
+extension Point {
+    func distance(to other: Point) -> Double {
+        let dx = x - other.x
+        let dy = y - other.y
+        return (dx * dx + dy * dy).squareRoot()
+    }
+
+    mutating func translate(by offset: Point) {
+        x += offset.x
+        y += offset.y
+    }
+
+    struct Segment {
+        let start: Point
+        let end: Point
+
+        var length: Double {
+            return start.distance(to: end)
+        }
+    }
+}
+
+class Canvas {
+    private var points: [Point] = []
+
+    func add(_ point: Point) {
+        points.append(point)
+    }
+}
@@ -0,0 +1,90 @@
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+const SOURCE: &str = "fn process_data() {
+    let value = compute();
+    value
+}
+
+fn unrelated() {}
+";
+
+fn create_fixture() -> TempDir {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    fs::write(temp_dir.path().join("lib.rs"), SOURCE).unwrap();
+    fs::write(temp_dir.path().join("notes.txt"), "nothing to see\n").unwrap();
+    temp_dir
+}
+
+fn run_grep(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_probe"))
+        .arg("grep")
+        .args(args)
+        .arg(dir)
+        .output()
+        .expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+fn run_grep_json(dir: &Path, args: &[&str]) -> Vec<Value> {
+    let stdout = run_grep(dir, &[args, &["--format", "json"]].concat());
+    let json: Value = serde_json::from_str(&stdout).expect("grep should print valid JSON");
+    json["results"].as_array().cloned().unwrap_or_default()
+}
+
+#[test]
+fn test_grep_json_matches() {
+    let dir = create_fixture();
+
+    let results = run_grep_json(dir.path(), &["compute", "-C", "1"]);
+    assert_eq!(results.len(), 1, "{results:?}");
+    assert!(results[0]["file"].as_str().unwrap().ends_with("lib.rs"));
+    assert_eq!(results[0]["line"], 2);
+    assert_eq!(results[0]["column"], 17);
+    assert!(results[0]["text"].as_str().unwrap().contains("compute()"));
+    assert_eq!(results[0]["before"][0], "fn process_data() {");
+    assert_eq!(results[0]["after"][0], "    value");
+    assert!(results[0].get("symbol").is_none());
+}
+
+#[test]
+fn test_grep_json_count_and_file_modes() {
+    let dir = create_fixture();
+
+    let counts = run_grep_json(dir.path(), &["value", "--count"]);
+    assert_eq!(counts.len(), 1, "{counts:?}");
+    assert_eq!(counts[0]["count"], 2);
+
+    let files = run_grep_json(dir.path(), &["fn", "--files-with-matches"]);
+    assert_eq!(files.len(), 1, "{files:?}");
+    assert_eq!(files[0].as_object().unwrap().len(), 1);
+    assert!(files[0]["file"].as_str().unwrap().ends_with("lib.rs"));
+}
+
+#[test]
+fn test_grep_show_symbol_labels_matches() {
+    let dir = create_fixture();
+
+    let results = run_grep_json(dir.path(), &["compute", "--show-symbol"]);
+    assert_eq!(results.len(), 1, "{results:?}");
+    assert!(results[0]["symbol"]
+        .as_str()
+        .unwrap()
+        .contains("process_data"));
+
+    let stdout = run_grep(
+        dir.path(),
+        &["compute", "-n", "--show-symbol", "--color", "never"],
+    );
+    assert!(stdout.contains("let value = compute();"), "{stdout}");
+    assert!(stdout.contains("process_data"), "{stdout}");
+    assert!(!stdout.contains("unrelated"), "{stdout}");
+}
@@ -4,7 +4,11 @@ use std::path::PathBuf;
 use tempfile::TempDir;
 
 // The integration test needs access to the library crate
-use probe_code::search::{perform_probe, SearchOptions};
+use probe_code::models::SkipReason;
+use probe_code::search::{
+    perform_probe, BinaryDetection, DedupMode, MatchScope, MergeThreshold, RankMode, ResultSort,
+    SearchOptions,
+};
 
 // Helper function to create test files
 fn create_test_file(dir: &TempDir, filename: &str, content: &str) -> PathBuf {
@@ -145,9 +149,11 @@ fn test_search_single_term() {
         max_results: None,
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: false,
         no_merge: true,
         merge_threshold: None,
+        merge_respect_boundaries: true,
         dry_run: false,
         session: None,
         timeout: 30,
@@ -155,6 +161,31 @@ fn test_search_single_term() {
         exact: false,
         no_gitignore: false,
         lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     // Search for a single term
@@ -210,9 +241,11 @@ fn test_search_multiple_terms() {
         max_results: None,
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: true, // Allow test files
         no_merge: true,
         merge_threshold: None,
+        merge_respect_boundaries: true,
         dry_run: false,
         session: None,
         timeout: 30,
@@ -220,6 +253,31 @@ fn test_search_multiple_terms() {
         exact: false,
         no_gitignore: false,
         lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     let search_results = perform_probe(&options).expect("Failed to perform search");
@@ -261,9 +319,11 @@ fn test_search_files_only() {
         max_results: None,
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: false,
         no_merge: true,
         merge_threshold: None,
+        merge_respect_boundaries: true,
         dry_run: false,
         session: None,
         timeout: 30,
@@ -271,6 +331,31 @@ fn test_search_files_only() {
         exact: false,
         no_gitignore: false,
         lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     // Search for files only
@@ -337,9 +422,11 @@ fn test_search_include_filenames() {
         max_results: None,
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: false,
         no_merge: true,
         merge_threshold: None,
+        merge_respect_boundaries: true,
         dry_run: false,
         session: None,
         timeout: 30,
@@ -347,6 +434,31 @@ fn test_search_include_filenames() {
         exact: false,
         no_gitignore: false,
         lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     // Search with filename matching enabled
@@ -406,9 +518,11 @@ fn test_search_with_limits() {
         max_results: Some(2), // limit to 2 results
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: false,
         no_merge: true,
         merge_threshold: None,
+        merge_respect_boundaries: true,
         dry_run: false,
         session: None,
         timeout: 30,
@@ -416,6 +530,31 @@ fn test_search_with_limits() {
         exact: false,
         no_gitignore: false,
         lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     // Search with limits
@@ -459,9 +598,11 @@ fn test_frequency_search() {
         max_results: None,
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: false,
         no_merge: true,
         merge_threshold: None,
+        merge_respect_boundaries: true,
         dry_run: false,
         session: None,
         timeout: 30,
@@ -469,6 +610,31 @@ fn test_frequency_search() {
         exact: false,
         no_gitignore: false,
         lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     // Search using frequency-based search
@@ -519,10 +685,12 @@ func main() {
         max_results: None,
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: false,
         // using "all terms" mode
         no_merge: true,
         merge_threshold: None,
+        merge_respect_boundaries: true,
         dry_run: false,
         session: None,
         timeout: 30,
@@ -530,6 +698,31 @@ func main() {
         exact: false,
         no_gitignore: false,
         lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     // Search for both terms in "all terms" mode
@@ -564,9 +757,11 @@ fn test_search_with_custom_ignores() {
         max_results: None,
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: false,
         no_merge: true,
         merge_threshold: None,
+        merge_respect_boundaries: true,
         dry_run: false,
         session: None,
         timeout: 30,
@@ -574,6 +769,31 @@ fn test_search_with_custom_ignores() {
         exact: false,
         no_gitignore: false,
         lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     // Search with custom ignore patterns
@@ -684,9 +904,11 @@ fn calculate_product(a: i32, b: i32) -> i32 {
         max_results: None,
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: true,
         no_merge: false,
-        merge_threshold: Some(5),
+        merge_threshold: Some(MergeThreshold::Lines(5)),
+        merge_respect_boundaries: false, // Merge adjacent functions by line distance alone
         dry_run: false,
         session: None,
         timeout: 30,
@@ -694,6 +916,31 @@ fn calculate_product(a: i32, b: i32) -> i32 {
         exact: false,
         no_gitignore: false,
         lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     // Perform search
@@ -795,9 +1042,11 @@ fn test_skipped_files_with_match_counts() {
         max_results: Some(2), // Very low limit to force skipping
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: false,
         no_merge: true,
         merge_threshold: None,
+        merge_respect_boundaries: true,
         dry_run: false,
         session: None,
         timeout: 30,
@@ -805,6 +1054,31 @@ fn test_skipped_files_with_match_counts() {
         exact: false,
         no_gitignore: false,
         lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     // Perform search
@@ -838,12 +1112,18 @@ fn test_skipped_files_with_match_counts() {
     for skipped in &search_result.skipped_files {
         // Each skipped file should have a file path
         assert!(
-            !skipped.file.is_empty(),
+            !skipped.result.file.is_empty(),
             "Skipped file should have a file path"
         );
 
         // Should have a rank (since we're ranking before limiting)
-        assert!(skipped.rank.is_some(), "Skipped file should have a rank");
+        assert!(
+            skipped.result.rank.is_some(),
+            "Skipped file should have a rank"
+        );
+
+        // Only the result cap was set, so it is the reason for every skip
+        assert_eq!(skipped.reason, SkipReason::MaxResults);
     }
 
     // Verify the total number of results + skipped equals roughly what we expect
@@ -920,9 +1200,11 @@ fn test_quoted_search_excludes_unrelated_files() {
         max_results: None,
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: true,
         no_merge: false,
         merge_threshold: None,
+        merge_respect_boundaries: true,
         dry_run: false,
         session: None,
         timeout: 30,
@@ -930,6 +1212,31 @@ fn test_quoted_search_excludes_unrelated_files() {
         exact: false, // NOT using --exact flag, just quoted query
         no_gitignore: true,
         lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     let search_result = perform_probe(&options).expect("Search should succeed");
@@ -0,0 +1,162 @@
+use probe_code::extract::SymbolKind;
+use probe_code::search::{
+    perform_probe, BinaryDetection, DedupMode, MatchScope, RankMode, ResultSort, SearchOptions,
+};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+const HANDLERS: &str = r#"fn handle_create(req: &Request) -> Response {
+    if let Err(e) = validate(req) {
+        log::error!("create failed: {e}");
+    }
+    Response::ok()
+}
+
+fn handle_delete(req: &Request) -> Response {
+    remove(req);
+    Response::ok()
+}
+
+fn handle_update(req: &Request) -> Response {
+    save(req);
+    Response::ok()
+}
+"#;
+
+const STORE: &str = r#"struct Store {
+    items: Vec<u32>,
+}
+
+impl Store {
+    fn add(&mut self, item: u32) {
+        log::info!("adding {item}");
+        self.items.push(item);
+    }
+
+    fn clear(&mut self) {
+        self.items.clear();
+    }
+}
+"#;
+
+fn inverted_search(
+    root: &Path,
+    query: &str,
+    regex: bool,
+    kinds: Option<Vec<SymbolKind>>,
+) -> Vec<(usize, String)> {
+    let queries = vec![query.to_string()];
+    let custom_ignores: Vec<String> = vec![];
+    let options = SearchOptions {
+        path: root,
+        queries: &queries,
+        files_only: false,
+        custom_ignores: &custom_ignores,
+        exclude_filenames: true,
+        language: None,
+        reranker: "bm25",
+        frequency_search: true,
+        max_results: None,
+        max_bytes: None,
+        max_tokens: None,
+        tokenizer: None,
+        allow_tests: true,
+        no_merge: true,
+        merge_threshold: None,
+        merge_respect_boundaries: true,
+        dry_run: false,
+        session: None,
+        timeout: 30,
+        question: None,
+        exact: false,
+        no_gitignore: true,
+        lsp: false,
+        sort: ResultSort::Line,
+        regex,
+        match_scope: MatchScope::All,
+        invert: true,
+        kinds,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
+    };
+
+    perform_probe(&options)
+        .expect("Failed to perform inverted search")
+        .results
+        .into_iter()
+        .map(|r| (r.lines.0, r.code))
+        .collect()
+}
+
+#[test]
+fn test_invert_returns_only_non_matching_blocks() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    fs::write(temp_dir.path().join("handlers.rs"), HANDLERS).unwrap();
+
+    let results = inverted_search(temp_dir.path(), "error", false, None);
+
+    let start_lines: Vec<usize> = results.iter().map(|(line, _)| *line).collect();
+    assert_eq!(start_lines, vec![8, 13], "unexpected blocks: {results:?}");
+    assert!(results[0].1.contains("fn handle_delete"));
+    assert!(results[1].1.contains("fn handle_update"));
+    assert!(
+        results
+            .iter()
+            .all(|(_, code)| !code.contains("handle_create")),
+        "the block mentioning the query must be excluded"
+    );
+}
+
+#[test]
+fn test_invert_with_regex() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    fs::write(temp_dir.path().join("handlers.rs"), HANDLERS).unwrap();
+
+    let results = inverted_search(temp_dir.path(), r"save\(|remove\(", true, None);
+
+    assert_eq!(results.len(), 1, "unexpected blocks: {results:?}");
+    assert!(results[0].1.contains("fn handle_create"));
+}
+
+#[test]
+fn test_invert_with_kinds_only_returns_blocks_of_those_kinds() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    fs::write(temp_dir.path().join("store.rs"), STORE).unwrap();
+
+    // The struct does not mention logging either, but only functions are audited
+    let all_blocks = inverted_search(temp_dir.path(), "log", false, None);
+    let start_lines: Vec<usize> = all_blocks.iter().map(|(line, _)| *line).collect();
+    assert_eq!(
+        start_lines,
+        vec![1, 11],
+        "unexpected blocks: {all_blocks:?}"
+    );
+
+    let functions = inverted_search(
+        temp_dir.path(),
+        "log",
+        false,
+        Some(vec![SymbolKind::Function]),
+    );
+    assert_eq!(functions.len(), 1, "unexpected blocks: {functions:?}");
+    assert!(functions[0].1.contains("fn clear"));
+}
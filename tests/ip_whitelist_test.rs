@@ -1,4 +1,6 @@
-use probe_code::search::{perform_probe, SearchOptions};
+use probe_code::search::{
+    perform_probe, BinaryDetection, DedupMode, MatchScope, RankMode, ResultSort, SearchOptions,
+};
 use std::path::PathBuf;
 
 #[test]
@@ -24,15 +26,42 @@ fn test_ip_whitelist_stemming() {
         max_results: None,
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: true,
         no_merge: true,
         merge_threshold: None,
+        merge_respect_boundaries: true,
         dry_run: false,
         session: None,
         timeout: 30,
         question: None,
         no_gitignore: false,
         lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     // Enable debug mode to see the actual terms
@@ -114,15 +143,42 @@ fn test_negative_terms_exclude_files() {
         max_results: None,
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: true,
         no_merge: true,
         merge_threshold: None,
+        merge_respect_boundaries: true,
         dry_run: false,
         session: None,
         timeout: 30,
         question: None,
         no_gitignore: false,
         lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     // Enable debug mode to see the actual terms
@@ -787,3 +787,211 @@ fn test_json_output_with_files_only() {
         );
     }
 }
+
+#[test]
+fn test_jsonl_output_matches_json_results() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    create_test_directory_structure(&temp_dir);
+
+    let run = |format: &str| {
+        let output = Command::new(env!("CARGO_BIN_EXE_probe"))
+            .args([
+                "search",
+                "search", // Pattern to search for
+                temp_dir.path().to_str().unwrap(),
+                "--format",
+                format,
+                "--exclude-filenames",
+                "--max-results",
+                "2",
+            ])
+            .output()
+            .expect("Failed to execute command");
+        assert!(output.status.success());
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    let json_result: Value =
+        serde_json::from_str(extract_json_from_output(&run("json"))).expect("Failed to parse JSON");
+
+    // Every line is a standalone JSON document: the results, then the summary line
+    let jsonl_output = run("jsonl");
+    let mut lines: Vec<Value> = jsonl_output
+        .lines()
+        .map(|line| serde_json::from_str(line).expect("Each line should be valid JSON"))
+        .collect();
+    let summary_line = lines
+        .pop()
+        .expect("JSONL output should end with a summary line");
+
+    assert_eq!(&Value::Array(lines), json_result.get("results").unwrap());
+    assert!(summary_line.get("results").is_none());
+    assert_eq!(summary_line.get("summary"), json_result.get("summary"));
+    assert_eq!(summary_line.get("limits"), json_result.get("limits"));
+    assert_eq!(
+        summary_line.get("limits").unwrap().get("max_results"),
+        Some(&Value::from(2))
+    );
+}
+
+#[test]
+fn test_plain_output_lists_result_locations() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    create_test_directory_structure(&temp_dir);
+
+    let run = |format: &str, extra: &[&str]| {
+        let output = Command::new(env!("CARGO_BIN_EXE_probe"))
+            .args([
+                "search",
+                "search", // Pattern to search for
+                temp_dir.path().to_str().unwrap(),
+                "--format",
+                format,
+                "--exclude-filenames",
+                "--max-results",
+                "2",
+            ])
+            .args(extra)
+            .output()
+            .expect("Failed to execute command");
+        assert!(output.status.success());
+        String::from_utf8_lossy(&output.stdout).to_string()
+    };
+
+    let json_result: Value = serde_json::from_str(extract_json_from_output(&run("json", &[])))
+        .expect("Failed to parse JSON");
+    let results = json_result.get("results").unwrap().as_array().unwrap();
+    let expected: Vec<String> = results
+        .iter()
+        .map(|r| {
+            format!(
+                "{}:{}",
+                r.get("file").unwrap().as_str().unwrap(),
+                r.get("lines").unwrap()[0]
+            )
+        })
+        .collect();
+
+    // Nothing but one location per result: no banner, limits, hints or tips
+    let plain_output = run("plain", &[]);
+    assert_eq!(plain_output.lines().collect::<Vec<_>>(), expected);
+
+    let snippet_output = run("plain", &["--snippet"]);
+    let snippet_lines: Vec<&str> = snippet_output.lines().collect();
+    assert_eq!(snippet_lines.len(), expected.len());
+    for ((line, location), result) in snippet_lines.iter().zip(&expected).zip(results) {
+        let code = result.get("code").unwrap().as_str().unwrap();
+        let first_line = code.lines().next().unwrap_or("").trim();
+        assert_eq!(*line, format!("{location}:{first_line}"));
+    }
+}
+
+#[test]
+fn test_explain_adds_score_breakdown_to_json_results() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    create_test_directory_structure(&temp_dir);
+
+    let run = |explain: bool| {
+        let mut args = vec![
+            "search",
+            "search", // Pattern to search for
+            temp_dir.path().to_str().unwrap(),
+            "--format",
+            "json",
+            "--exclude-filenames",
+        ];
+        if explain {
+            args.push("--explain");
+        }
+        let output = Command::new(env!("CARGO_BIN_EXE_probe"))
+            .args(args)
+            .output()
+            .expect("Failed to execute command");
+        assert!(output.status.success());
+        let json: Value = serde_json::from_str(extract_json_from_output(&String::from_utf8_lossy(
+            &output.stdout,
+        )))
+        .expect("Failed to parse JSON");
+        json.get("results").unwrap().as_array().unwrap().clone()
+    };
+
+    assert!(run(false)
+        .iter()
+        .all(|result| result.get("score_breakdown").is_none()));
+
+    let results = run(true);
+    assert!(!results.is_empty());
+    for result in &results {
+        let breakdown = result
+            .get("score_breakdown")
+            .expect("Each result should have a score breakdown");
+        assert_eq!(breakdown.get("reranker"), Some(&Value::from("bm25")));
+
+        let bm25 = breakdown.get("bm25").unwrap().as_f64().unwrap();
+        let rerank = breakdown.get("rerank").unwrap().as_f64().unwrap();
+        let score = result.get("score").unwrap().as_f64().unwrap();
+        assert!(bm25 > 0.0);
+        assert!((bm25 + rerank - score).abs() < 1e-9);
+
+        let terms = breakdown.get("terms").unwrap().as_array().unwrap();
+        assert_eq!(terms.len(), 1);
+        assert!(terms[0].get("tf").unwrap().as_u64().unwrap() > 0);
+        assert!(terms[0].get("idf").unwrap().as_f64().unwrap() > 0.0);
+    }
+}
+
+#[test]
+fn test_term_matches_report_char_boundary_offsets() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    fs::write(
+        temp_dir.path().join("billing.rs"),
+        "fn send_invoice() {\n    // Ünïcödé → Invoice\n    let invoice = \"Größe\";\n}\n",
+    )
+    .expect("Failed to write test file");
+
+    let output = Command::new(env!("CARGO_BIN_EXE_probe"))
+        .args([
+            "search",
+            "invoice",
+            temp_dir.path().to_str().unwrap(),
+            "--format",
+            "json",
+            "--exclude-filenames",
+        ])
+        .output()
+        .expect("Failed to execute command");
+    assert!(output.status.success());
+    let json: Value = serde_json::from_str(extract_json_from_output(&String::from_utf8_lossy(
+        &output.stdout,
+    )))
+    .expect("Failed to parse JSON");
+
+    let result = &json["results"][0];
+    let code = result["code"].as_str().unwrap();
+    let start_line = result["lines"][0].as_u64().unwrap() as usize;
+    let matches = result["term_matches"]
+        .as_array()
+        .expect("Result should list its term matches");
+    assert_eq!(matches.len(), 3);
+
+    for term_match in matches {
+        assert_eq!(term_match["term"], "invoice");
+        let start = term_match["start_byte"].as_u64().unwrap() as usize;
+        let end = term_match["end_byte"].as_u64().unwrap() as usize;
+        let text = code
+            .get(start..end)
+            .expect("Offsets should be on character boundaries");
+        assert_eq!(text.to_lowercase(), "invoice");
+
+        // Line and column point at the same text as the byte range
+        let line = term_match["line"].as_u64().unwrap() as usize;
+        let column = term_match["column"].as_u64().unwrap() as usize;
+        let line_text = code.lines().nth(line - start_line).unwrap();
+        let column_text: String = line_text.chars().skip(column - 1).collect();
+        assert!(column_text.starts_with(text));
+    }
+
+    // Columns count characters, not bytes
+    assert_eq!(matches[1]["line"].as_u64(), Some(start_line as u64 + 1));
+    assert_eq!(matches[1]["column"].as_u64(), Some(18));
+}
@@ -0,0 +1,66 @@
+use probe_code::{extract_json_schema, query_json_schema, search_json_schema};
+use serde_json::Value;
+
+/// Follow a `{"$ref": "#/$defs/Name"}` schema to its definition
+fn resolve<'a>(root: &'a Value, schema: &'a Value) -> &'a Value {
+    match schema["$ref"].as_str() {
+        Some(reference) => {
+            let name = reference.trim_start_matches("#/$defs/");
+            &root["$defs"][name]
+        }
+        None => schema,
+    }
+}
+
+fn required(schema: &Value) -> Vec<&str> {
+    schema["required"]
+        .as_array()
+        .map(|keys| keys.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default()
+}
+
+#[test]
+fn test_search_schema_describes_results() {
+    let schema = serde_json::to_value(search_json_schema()).unwrap();
+
+    assert_eq!(required(&schema), vec!["results", "summary", "version"]);
+    assert!(schema["properties"]["limits"].is_object());
+    assert!(schema["properties"]["skipped_files"].is_object());
+
+    let result = resolve(&schema, &schema["properties"]["results"]["items"]);
+    assert_eq!(
+        result["properties"]["scope"]["description"],
+        "Structural classification of the block"
+    );
+    assert!(required(result).contains(&"code"));
+    assert!(!required(result).contains(&"score_breakdown"));
+}
+
+#[test]
+fn test_extract_schema_describes_results() {
+    let schema = serde_json::to_value(extract_json_schema()).unwrap();
+
+    assert_eq!(required(&schema), vec!["results", "summary", "version"]);
+    let result = resolve(&schema, &schema["properties"]["results"]["items"]);
+    for key in ["file", "lines", "node_type", "code"] {
+        assert!(required(result).contains(&key), "{key} should be required");
+    }
+    for key in ["line_numbers", "blame", "changed_lines"] {
+        assert!(result["properties"][key].is_object(), "Missing {key}");
+        assert!(!required(result).contains(&key), "{key} should be optional");
+    }
+}
+
+#[test]
+fn test_query_schema_describes_matches_and_groups() {
+    let schema = serde_json::to_value(query_json_schema()).unwrap();
+
+    assert_eq!(required(&schema), vec!["results", "summary", "version"]);
+    assert!(schema["properties"]["groups"].is_object());
+    assert!(schema["properties"]["schema_version"].is_object());
+
+    let query_match = resolve(&schema, &schema["properties"]["results"]["items"]);
+    for key in ["captures", "definition", "owner", "match"] {
+        assert!(query_match["properties"][key].is_object(), "Missing {key}");
+    }
+}
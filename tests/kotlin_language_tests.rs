@@ -0,0 +1,132 @@
+use probe_code::extract::process_file_for_extraction;
+use probe_code::query::{perform_query, AstMatch, GroupBy, QueryOptions};
+use std::path::PathBuf;
+
+fn fixture_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/kotlin/project1")
+}
+
+fn inventory_file() -> PathBuf {
+    fixture_root().join("src/Inventory.kt")
+}
+
+fn extract_symbol(symbol: &str) -> Vec<(usize, usize)> {
+    process_file_for_extraction(
+        &inventory_file(),
+        None,
+        None,
+        Some(symbol),
+        true,
+        0,
+        None,
+        false,
+        false,
+    )
+    .expect("extract should find Kotlin symbol")
+    .into_iter()
+    .map(|result| result.lines)
+    .collect()
+}
+
+fn query(pattern: &str, language: Option<&str>) -> Vec<AstMatch> {
+    let options = QueryOptions {
+        path: &fixture_root(),
+        pattern,
+        or_patterns: &[],
+        language,
+        ignore: &[],
+        allow_tests: true,
+        max_results: None,
+        with_context: false,
+        format: "terminal",
+        no_gitignore: true,
+        strict: false,
+        text_extensions: &[],
+        parent_kinds: &[],
+        sexp: false,
+        group_by: GroupBy::None,
+        follow_symlinks: false,
+    };
+
+    perform_query(&options).expect("Kotlin query should run")
+}
+
+fn capture<'a>(m: &'a AstMatch, name: &str) -> &'a str {
+    let span = &m.captures[name][0];
+    &m.matched_text[span.byte_start - m.byte_start..span.byte_end - m.byte_start]
+}
+
+#[test]
+fn test_kotlin_symbol_extraction_block_boundaries() {
+    assert_eq!(extract_symbol("formatPrice"), vec![(3, 6)]);
+    assert_eq!(extract_symbol("shout"), vec![(8, 8)]);
+    assert_eq!(extract_symbol("Inventory"), vec![(10, 28)]);
+    assert_eq!(extract_symbol("Inventory.count"), vec![(19, 21)]);
+    assert_eq!(extract_symbol("Registry"), vec![(30, 34)]);
+}
+
+#[test]
+fn test_kotlin_companion_object_members() {
+    assert_eq!(extract_symbol("empty"), vec![(24, 26)]);
+    assert_eq!(extract_symbol("Inventory.empty"), vec![(24, 26)]);
+}
+
+#[test]
+fn test_kotlin_extraction_by_line_target() {
+    let results = process_file_for_extraction(
+        &inventory_file(),
+        Some(15),
+        Some(15),
+        None,
+        true,
+        0,
+        None,
+        false,
+        false,
+    )
+    .expect("extract should find enclosing Kotlin function from line target");
+
+    assert_eq!(results.lines, (13, 17));
+    assert!(results
+        .code
+        .starts_with("fun add(sku: String, quantity: Int): Int {"));
+}
+
+#[test]
+fn test_kotlin_query_matches_functions() {
+    let matches = query("fun $NAME($$$): $RET { $$$ }", Some("kotlin"));
+
+    // `shout` has an expression body, so it does not match the block pattern
+    let found: Vec<(&str, &str, usize, usize)> = matches
+        .iter()
+        .map(|m| {
+            (
+                capture(m, "NAME"),
+                capture(m, "RET"),
+                m.line_start,
+                m.line_end,
+            )
+        })
+        .collect();
+    assert_eq!(
+        found,
+        vec![
+            ("formatPrice", "String", 3, 6),
+            ("add", "Int", 13, 17),
+            ("count", "Int", 19, 21),
+            ("empty", "Inventory", 24, 26),
+            ("contains", "Boolean", 31, 33),
+        ]
+    );
+}
+
+#[test]
+fn test_kotlin_query_language_alias_and_auto_detect() {
+    let matches = query("Inventory($OWNER)", Some("kt"));
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].line_start, 25);
+    assert_eq!(capture(&matches[0], "OWNER"), "\"nobody\"");
+
+    let auto_detected = query("Inventory($OWNER)", None);
+    assert_eq!(auto_detected.len(), 1);
+}
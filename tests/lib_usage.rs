@@ -1,6 +1,8 @@
 #[cfg(test)]
 mod tests {
-    use probe_code::search::{perform_probe, SearchOptions};
+    use probe_code::search::{
+        perform_probe, BinaryDetection, DedupMode, MatchScope, RankMode, ResultSort, SearchOptions,
+    };
     use std::fs;
     use tempfile::TempDir;
 
@@ -32,15 +34,42 @@ fn lib_usage_search_sample() {
             max_results: Some(5),
             max_bytes: None,
             max_tokens: None,
+            tokenizer: None,
             allow_tests: true,
             no_merge: false,
             merge_threshold: None,
+            merge_respect_boundaries: true,
             dry_run: false,
             session: None,
             timeout: 30,
             question: None,
             no_gitignore: false,
             lsp: false,
+            sort: ResultSort::Score,
+            regex: false,
+            match_scope: MatchScope::All,
+            invert: false,
+            kinds: None,
+            recency_boost: false,
+            recency_half_life_days: 14.0,
+            context_symbols: false,
+            context_symbols_depth: 5,
+            explain: false,
+            use_index: false,
+            rank_mode: RankMode::Block,
+            within: None,
+            changed_since: None,
+            tracked_only: false,
+            rerank_top_k: 50,
+            binary_detection: BinaryDetection::NullByte,
+            force_text_extensions: &[],
+            search_archives: false,
+            encoding: None,
+            min_score: None,
+            follow_symlinks: false,
+            ranking_profile: None,
+            dedup: DedupMode::None,
+            extra_roots: &[],
         };
 
         let results = perform_probe(&options).unwrap();
@@ -52,7 +81,7 @@ fn lib_usage_search_sample() {
 
     #[test]
     fn test_query_functionality() {
-        use probe_code::query::{perform_query, QueryOptions};
+        use probe_code::query::{perform_query, GroupBy, QueryOptions};
 
         let temp_dir = TempDir::new().unwrap();
         let file_path = temp_dir.path().join("sample.rs");
@@ -69,6 +98,7 @@ fn lib_usage_query_sample(value: i32) -> i32 {
         let options = QueryOptions {
             path: temp_dir.path(),
             pattern: "fn $NAME($$$PARAMS) $$$BODY",
+            or_patterns: &[],
             language: Some("rust"),
             ignore: &[],
             allow_tests: true,
@@ -78,6 +108,10 @@ fn lib_usage_query_sample(value: i32) -> i32 {
             no_gitignore: false,
             strict: false,
             text_extensions: &[],
+            parent_kinds: &[],
+            sexp: false,
+            group_by: GroupBy::None,
+            follow_symlinks: false,
         };
 
         let matches = perform_query(&options).unwrap();
@@ -61,6 +61,10 @@ func Subtract(x, y int) int {
         matched_lines: None,
         tokenized_content: None,
         parent_context: None,
+        score_breakdown: None,
+        term_matches: None,
+        duplicates: None,
+        root: None,
     }
 }
 
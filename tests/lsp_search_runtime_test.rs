@@ -132,6 +132,10 @@ fn create_test_search_result() -> SearchResult {
         symbol_signature: None,
         parent_context: None,
         matched_lines: None,
+        score_breakdown: None,
+        term_matches: None,
+        duplicates: None,
+        root: None,
     }
 }
 
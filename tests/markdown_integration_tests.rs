@@ -1,7 +1,9 @@
 use std::fs;
 use tempfile::tempdir;
 
-use probe_code::search::{perform_probe, SearchOptions};
+use probe_code::search::{
+    perform_probe, BinaryDetection, DedupMode, MatchScope, RankMode, ResultSort, SearchOptions,
+};
 
 #[test]
 fn test_markdown_basic_search() {
@@ -48,15 +50,42 @@ def hello_world():
         max_results: None,
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: true,
         no_merge: true,
         merge_threshold: None,
+        merge_respect_boundaries: true,
         lsp: false,
         dry_run: false,
         session: None,
         timeout: 30,
         question: None,
         no_gitignore: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
     let results = perform_probe(&options).unwrap();
     assert!(!results.results.is_empty());
@@ -101,15 +130,42 @@ console.log("Hello from JS");
         max_results: None,
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: true,
         no_merge: true,
         merge_threshold: None,
+        merge_respect_boundaries: true,
         lsp: false,
         dry_run: false,
         session: None,
         timeout: 30,
         question: None,
         no_gitignore: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
     let results = perform_probe(&options).unwrap();
     assert!(!results.results.is_empty());
@@ -172,15 +228,42 @@ fn main() {
             max_results: None,
             max_bytes: None,
             max_tokens: None,
+            tokenizer: None,
             allow_tests: true,
             no_merge: true,
             merge_threshold: None,
+            merge_respect_boundaries: true,
             lsp: false,
             dry_run: false,
             session: None,
             timeout: 30,
             question: None,
             no_gitignore: false,
+            sort: ResultSort::Score,
+            regex: false,
+            match_scope: MatchScope::All,
+            invert: false,
+            kinds: None,
+            recency_boost: false,
+            recency_half_life_days: 14.0,
+            context_symbols: false,
+            context_symbols_depth: 5,
+            explain: false,
+            use_index: false,
+            rank_mode: RankMode::Block,
+            within: None,
+            changed_since: None,
+            tracked_only: false,
+            rerank_top_k: 50,
+            binary_detection: BinaryDetection::NullByte,
+            force_text_extensions: &[],
+            search_archives: false,
+            encoding: None,
+            min_score: None,
+            follow_symlinks: false,
+            ranking_profile: None,
+            dedup: DedupMode::None,
+            extra_roots: &[],
         };
         let results = perform_probe(&options).unwrap();
         assert!(
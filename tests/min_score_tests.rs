@@ -0,0 +1,136 @@
+use probe_code::models::LimitedSearchResults;
+use probe_code::search::{
+    perform_probe, BinaryDetection, DedupMode, MatchScope, RankMode, ResultSort, SearchOptions,
+};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn create_test_files(root: &Path) {
+    // ledger.rs is all about invoices; the other files mention one in passing
+    fs::write(
+        root.join("ledger.rs"),
+        "fn reconcile_invoice(invoice: Invoice) {\n    let invoice_total = invoice.total();\n    post(invoice, invoice_total);\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("billing.rs"),
+        "fn charge(customer: Customer, amount: u64, currency: Currency, gateway: Gateway) {\n    gateway.send(customer, amount, currency);\n    notify(customer, \"invoice\");\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("audit.rs"),
+        "fn audit(entries: &[Entry], auditor: &Auditor, period: Period, region: Region) {\n    auditor.check(entries, period, region);\n    log(\"invoice\");\n}\n",
+    )
+    .unwrap();
+}
+
+fn search(root: &Path, min_score: Option<f32>) -> LimitedSearchResults {
+    let queries = vec!["invoice".to_string()];
+    let custom_ignores: Vec<String> = vec![];
+    let options = SearchOptions {
+        path: root,
+        queries: &queries,
+        files_only: false,
+        custom_ignores: &custom_ignores,
+        exclude_filenames: true,
+        language: None,
+        reranker: "bm25",
+        frequency_search: true,
+        max_results: None,
+        max_bytes: None,
+        max_tokens: None,
+        tokenizer: None,
+        allow_tests: true,
+        no_merge: true,
+        merge_threshold: None,
+        merge_respect_boundaries: true,
+        dry_run: false,
+        session: None,
+        timeout: 30,
+        question: None,
+        exact: false,
+        no_gitignore: true,
+        lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
+    };
+
+    perform_probe(&options).expect("Failed to perform search")
+}
+
+fn scores(results: &LimitedSearchResults) -> Vec<f64> {
+    results
+        .results
+        .iter()
+        .map(|r| r.score.expect("ranked results have a score"))
+        .collect()
+}
+
+#[test]
+fn test_min_score_drops_weak_matches() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    create_test_files(temp_dir.path());
+
+    let all = search(temp_dir.path(), None);
+    let all_scores = scores(&all);
+    let best = all_scores.iter().copied().fold(f64::MIN, f64::max);
+    let worst = all_scores.iter().copied().fold(f64::MAX, f64::min);
+    assert!(best > worst, "expected distinct scores, got {all_scores:?}");
+    assert!(all.limits_applied.is_none());
+
+    let threshold = ((best + worst) / 2.0) as f32;
+    let gated = search(temp_dir.path(), Some(threshold));
+    let gated_scores = scores(&gated);
+    assert!(!gated_scores.is_empty());
+    assert!(gated_scores
+        .iter()
+        .all(|&score| score >= f64::from(threshold)));
+    assert!(gated.results[0].file.ends_with("ledger.rs"));
+
+    let limits = gated
+        .limits_applied
+        .expect("min score is reported with the limits");
+    assert_eq!(limits.min_score, Some(threshold));
+    assert_eq!(
+        limits.below_min_score,
+        all_scores.len() - gated_scores.len()
+    );
+    assert!(limits.below_min_score > 0);
+}
+
+#[test]
+fn test_min_score_above_every_result_returns_nothing() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    create_test_files(temp_dir.path());
+
+    let results = search(temp_dir.path(), Some(f32::MAX));
+    assert!(results.results.is_empty());
+    assert_eq!(
+        results.limits_applied.map(|limits| limits.below_min_score),
+        Some(scores(&search(temp_dir.path(), None)).len())
+    );
+}
@@ -0,0 +1,186 @@
+use probe_code::models::LimitedSearchResults;
+use probe_code::search::{
+    perform_probe, BinaryDetection, DedupMode, MatchScope, RankMode, ResultSort, SearchOptions,
+};
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+/// Two sibling repositories, each with its own .gitignore
+fn create_workspace(root: &Path) -> (PathBuf, PathBuf) {
+    let api = root.join("api");
+    let web = root.join("web");
+    fs::create_dir_all(&api).unwrap();
+    fs::create_dir_all(&web).unwrap();
+
+    fs::write(api.join(".gitignore"), "generated.rs\n").unwrap();
+    fs::write(
+        api.join("billing.rs"),
+        "fn charge_invoice() {\n    send(invoice);\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        api.join("generated.rs"),
+        "fn generated_invoice() {\n    emit(invoice);\n}\n",
+    )
+    .unwrap();
+
+    fs::write(
+        web.join("checkout.rs"),
+        "fn render_invoice() {\n    draw(invoice);\n}\n",
+    )
+    .unwrap();
+    // Only api ignores generated.rs
+    fs::write(
+        web.join("generated.rs"),
+        "fn generated_invoice() {\n    emit(invoice);\n}\n",
+    )
+    .unwrap();
+
+    (api, web)
+}
+
+fn search(
+    path: &Path,
+    extra_roots: &[PathBuf],
+    max_results: Option<usize>,
+) -> LimitedSearchResults {
+    let queries = vec!["invoice".to_string()];
+    let custom_ignores: Vec<String> = vec![];
+    let options = SearchOptions {
+        path,
+        queries: &queries,
+        files_only: false,
+        custom_ignores: &custom_ignores,
+        exclude_filenames: true,
+        language: None,
+        reranker: "bm25",
+        frequency_search: true,
+        max_results,
+        max_bytes: None,
+        max_tokens: None,
+        tokenizer: None,
+        allow_tests: true,
+        no_merge: true,
+        merge_threshold: None,
+        merge_respect_boundaries: true,
+        dry_run: false,
+        session: None,
+        timeout: 30,
+        question: None,
+        exact: false,
+        no_gitignore: false,
+        lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots,
+    };
+
+    perform_probe(&options).expect("Failed to perform search")
+}
+
+/// (file name, root directory name) of each result
+fn files_and_roots(results: &LimitedSearchResults) -> HashSet<(String, String)> {
+    let name = |path: &str| {
+        Path::new(path)
+            .file_name()
+            .unwrap()
+            .to_string_lossy()
+            .to_string()
+    };
+    results
+        .results
+        .iter()
+        .map(|r| (name(&r.file), name(r.root.as_deref().expect("root is set"))))
+        .collect()
+}
+
+#[test]
+fn test_multi_root_search_tags_results_with_their_root() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let (api, web) = create_workspace(temp_dir.path());
+
+    let results = search(&api, &[web.clone()], None);
+
+    let expected: HashSet<(String, String)> = [
+        ("billing.rs", "api"),
+        ("checkout.rs", "web"),
+        ("generated.rs", "web"),
+    ]
+    .iter()
+    .map(|(file, root)| (file.to_string(), root.to_string()))
+    .collect();
+    // api/generated.rs is ignored by api's .gitignore, which does not apply to web
+    assert_eq!(files_and_roots(&results), expected);
+
+    let ranks: Vec<_> = results.results.iter().map(|r| r.rank).collect();
+    assert_eq!(ranks, vec![Some(1), Some(2), Some(3)]);
+}
+
+#[test]
+fn test_single_root_results_have_no_root() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let (api, _) = create_workspace(temp_dir.path());
+
+    let results = search(&api, &[], None);
+    assert_eq!(results.results.len(), 1);
+    assert!(results.results[0].root.is_none());
+}
+
+#[test]
+fn test_multi_root_max_results_applies_to_merged_results() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let (api, web) = create_workspace(temp_dir.path());
+
+    let results = search(&api, &[web], Some(2));
+
+    assert_eq!(results.results.len(), 2);
+    let limits = results.limits_applied.expect("limits are reported");
+    assert_eq!(limits.max_results, Some(2));
+    assert_eq!(results.skipped_files.len(), 1);
+}
+
+#[test]
+fn test_overlapping_roots_return_each_block_once() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let (api, web) = create_workspace(temp_dir.path());
+
+    // The workspace root contains api, which is also given on its own
+    let results = search(temp_dir.path(), &[api.clone(), api, web], None);
+
+    let blocks: Vec<_> = results
+        .results
+        .iter()
+        .map(|r| (fs::canonicalize(&r.file).unwrap(), r.lines))
+        .collect();
+    let unique: HashSet<_> = blocks.iter().cloned().collect();
+    assert_eq!(blocks.len(), unique.len(), "duplicate blocks: {blocks:?}");
+
+    // Blocks found under several roots belong to the deepest one
+    let roots = files_and_roots(&results);
+    assert!(roots.contains(&("billing.rs".to_string(), "api".to_string())));
+    assert!(roots.contains(&("checkout.rs".to_string(), "web".to_string())));
+}
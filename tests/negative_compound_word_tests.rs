@@ -3,7 +3,9 @@ use std::path::Path;
 use tempfile::TempDir;
 
 use probe_code::search::query::create_query_plan;
-use probe_code::search::{perform_probe, SearchOptions};
+use probe_code::search::{
+    perform_probe, BinaryDetection, DedupMode, MatchScope, RankMode, ResultSort, SearchOptions,
+};
 
 /// Test negative compound word handling
 #[test]
@@ -98,9 +100,11 @@ fn test_basic_negative_compound_word(temp_path: &Path) {
         max_results: None,
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: true,
         no_merge: true,
         merge_threshold: None,
+        merge_respect_boundaries: true,
         dry_run: false,
         session: None,
         timeout: 30,
@@ -108,6 +112,31 @@ fn test_basic_negative_compound_word(temp_path: &Path) {
         exact: false,
         no_gitignore: false,
         lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     // Run the search
@@ -168,10 +197,12 @@ fn test_complex_negative_compound_word(temp_path: &Path) {
         max_results: None,
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: true,
         // Use all terms mode
         no_merge: true,
         merge_threshold: None,
+        merge_respect_boundaries: true,
         dry_run: false,
         session: None,
         timeout: 30,
@@ -179,6 +210,31 @@ fn test_complex_negative_compound_word(temp_path: &Path) {
         exact: false,
         no_gitignore: false,
         lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     // Run the search
@@ -30,7 +30,9 @@ fn test_parallel_file_extraction() {
         from_clipboard: false,
         input_file: None,
         to_clipboard: false,
+        clipboard_max_bytes: None,
         dry_run: false,
+        plain_paths: false,
         diff: false,
         allow_tests: true,
         instructions: None,
@@ -38,6 +40,16 @@ fn test_parallel_file_extraction() {
         prompt: None,
         no_gitignore: false,
         lsp: false,
+        cache: false,
+        strip_comments: false,
+        redact_strings: false,
+        signatures_only: false,
+        only_kinds: None,
+        public_only: false,
+        line_numbers: false,
+        blame: false,
+        diff_blocks: false,
+        encoding: None,
     };
 
     // Run the extraction
@@ -94,7 +106,9 @@ fn function_four() {
         from_clipboard: false,
         input_file: None,
         to_clipboard: false,
+        clipboard_max_bytes: None,
         dry_run: false,
+        plain_paths: false,
         diff: false,
         allow_tests: true,
         instructions: None,
@@ -102,6 +116,16 @@ fn function_four() {
         prompt: None,
         no_gitignore: false,
         lsp: false,
+        cache: false,
+        strip_comments: false,
+        redact_strings: false,
+        signatures_only: false,
+        only_kinds: None,
+        public_only: false,
+        line_numbers: false,
+        blame: false,
+        diff_blocks: false,
+        encoding: None,
     };
 
     // Run the extraction
@@ -162,7 +186,9 @@ fn test_parallel_extraction_performance() {
         from_clipboard: false,
         input_file: None,
         to_clipboard: false,
+        clipboard_max_bytes: None,
         dry_run: true, // Use dry run to avoid large output
+        plain_paths: false,
         diff: false,
         allow_tests: true,
         instructions: None,
@@ -170,6 +196,16 @@ fn test_parallel_extraction_performance() {
         prompt: None,
         no_gitignore: false,
         lsp: false,
+        cache: false,
+        strip_comments: false,
+        redact_strings: false,
+        signatures_only: false,
+        only_kinds: None,
+        public_only: false,
+        line_numbers: false,
+        blame: false,
+        diff_blocks: false,
+        encoding: None,
     };
 
     // Run the extraction
@@ -1,7 +1,10 @@
 use probe_code::search::search_runner::{
     perform_probe, search_with_structured_patterns, SearchConfig,
 };
-use probe_code::search::SearchOptions;
+use probe_code::search::timeout::SearchDeadline;
+use probe_code::search::{
+    BinaryDetection, DedupMode, MatchScope, MergeThreshold, RankMode, ResultSort, SearchOptions,
+};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::time::Instant;
@@ -79,9 +82,11 @@ fn test_parallel_file_search() {
         max_results: Some(100),
         max_bytes: Some(1_000_000),
         max_tokens: Some(100_000),
+        tokenizer: None,
         allow_tests: true,
         no_merge: false,
-        merge_threshold: Some(5),
+        merge_threshold: Some(MergeThreshold::Lines(5)),
+        merge_respect_boundaries: true,
         dry_run: false,
         session: None,
         timeout: 30,
@@ -89,6 +94,31 @@ fn test_parallel_file_search() {
         exact: false,
         no_gitignore: false,
         lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     // Measure search time
@@ -143,6 +173,14 @@ fn test_structured_patterns_search() {
         allow_tests: true,
         language: None,
         no_gitignore: false,
+        follow_symlinks: false,
+        changed_files: None,
+        tracked_files: None,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        deadline: SearchDeadline::after_seconds(30),
     };
     let result = search_with_structured_patterns(
         base_path,
@@ -155,7 +193,11 @@ fn test_structured_patterns_search() {
 
     // Verify search results
     assert!(result.is_ok(), "Structured pattern search should succeed");
-    let file_term_maps = result.unwrap();
+    let (file_term_maps, unsearched_files, _) = result.unwrap();
+    assert!(
+        unsearched_files.is_empty(),
+        "Text files should not be skipped"
+    );
 
     // Ensure we found matches
     assert!(!file_term_maps.is_empty(), "Search should find matches");
@@ -216,9 +258,11 @@ fn function_{}() {{
         max_results: Some(100),
         max_bytes: Some(1_000_000),
         max_tokens: Some(100_000),
+        tokenizer: None,
         allow_tests: true,
         no_merge: false,
-        merge_threshold: Some(5),
+        merge_threshold: Some(MergeThreshold::Lines(5)),
+        merge_respect_boundaries: true,
         dry_run: false,
         session: None,
         timeout: 30,
@@ -226,6 +270,31 @@ fn function_{}() {{
         exact: false,
         no_gitignore: false,
         lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     // Measure search time
@@ -322,9 +391,11 @@ fn function_with_blocks_{}() {{
         max_results: Some(100),
         max_bytes: Some(1_000_000),
         max_tokens: Some(100_000),
+        tokenizer: None,
         allow_tests: true,
         no_merge: false,
-        merge_threshold: Some(5),
+        merge_threshold: Some(MergeThreshold::Lines(5)),
+        merge_respect_boundaries: true,
         dry_run: false,
         session: None,
         timeout: 30,
@@ -332,6 +403,31 @@ fn function_with_blocks_{}() {{
         exact: false,
         no_gitignore: false,
         lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     // Measure search time
@@ -1,4 +1,4 @@
-use probe_code::ranking::{compute_avgdl, rank_documents, tokenize, RankingParams};
+use probe_code::ranking::{compute_avgdl, rank_documents, tokenize, RankingParams, RankingProfile};
 use probe_code::search::query::{create_query_plan, create_structured_patterns, regex_escape};
 use proptest::prelude::*;
 
@@ -93,6 +93,7 @@ proptest! {
             documents: &docs_refs,
             query: &query,
             pre_tokenized: None,
+            profile: &RankingProfile::default(),
         };
 
         // This should never panic
@@ -1,5 +1,8 @@
 use anyhow::Result;
-use probe_code::query::{perform_query, QueryOptions};
+use probe_code::query::{
+    count_query_matches, group_matches, perform_query, perform_query_at_position, GroupBy,
+    QueryOptions,
+};
 use std::fs;
 use tempfile::tempdir;
 
@@ -26,6 +29,7 @@ fn add(a: i32, b: i32) -> i32 {
     let options = QueryOptions {
         path: temp_path,
         pattern: "fn $NAME($$$PARAMS) $$$BODY",
+        or_patterns: &[],
         language: Some("rust"),
         ignore: &[],
         allow_tests: true,
@@ -35,6 +39,10 @@ fn add(a: i32, b: i32) -> i32 {
         no_gitignore: false,
         strict: false,
         text_extensions: &[],
+        parent_kinds: &[],
+        sexp: false,
+        group_by: GroupBy::None,
+        follow_symlinks: false,
     };
 
     // Perform the query
@@ -81,6 +89,7 @@ const multiply = (a, b) => a * b;
     let options = QueryOptions {
         path: temp_path,
         pattern: "function $NAME($$$PARAMS) $$$BODY",
+        or_patterns: &[],
         language: Some("javascript"),
         ignore: &[],
         allow_tests: true,
@@ -90,6 +99,10 @@ const multiply = (a, b) => a * b;
         no_gitignore: false,
         strict: false,
         text_extensions: &[],
+        parent_kinds: &[],
+        sexp: false,
+        group_by: GroupBy::None,
+        follow_symlinks: false,
     };
 
     // Perform the query
@@ -104,6 +117,7 @@ const multiply = (a, b) => a * b;
     let arrow_options = QueryOptions {
         path: temp_path,
         pattern: "const $NAME = ($$$PARAMS) => $$$BODY",
+        or_patterns: &[],
         language: Some("javascript"),
         ignore: &[],
         allow_tests: true,
@@ -113,6 +127,10 @@ const multiply = (a, b) => a * b;
         no_gitignore: false,
         strict: false,
         text_extensions: &[],
+        parent_kinds: &[],
+        sexp: false,
+        group_by: GroupBy::None,
+        follow_symlinks: false,
     };
 
     // Perform the query
@@ -163,6 +181,7 @@ static mode_t change_sacl_perms(SMB_ACL_T sacl, rsync_acl *racl, mode_t old_mode
     let options = QueryOptions {
         path: temp_path,
         pattern: "$RET $NAME($$$PARAMS) { $$$BODY }",
+        or_patterns: &[],
         language: Some("c"),
         ignore: &[],
         allow_tests: true,
@@ -172,6 +191,10 @@ static mode_t change_sacl_perms(SMB_ACL_T sacl, rsync_acl *racl, mode_t old_mode
         no_gitignore: false,
         strict: false,
         text_extensions: &[],
+        parent_kinds: &[],
+        sexp: false,
+        group_by: GroupBy::None,
+        follow_symlinks: false,
     };
 
     let matches = perform_query(&options)?;
@@ -212,6 +235,7 @@ int exact_return(void)
     let options = QueryOptions {
         path: temp_path,
         pattern: "int $NAME($$$PARAMS) { return 7; }",
+        or_patterns: &[],
         language: Some("c"),
         ignore: &[],
         allow_tests: true,
@@ -221,6 +245,10 @@ int exact_return(void)
         no_gitignore: false,
         strict: false,
         text_extensions: &[],
+        parent_kinds: &[],
+        sexp: false,
+        group_by: GroupBy::None,
+        follow_symlinks: false,
     };
 
     let matches = perform_query(&options)?;
@@ -256,6 +284,7 @@ fn func5() {}
     let options = QueryOptions {
         path: temp_path,
         pattern: "fn $NAME() {}",
+        or_patterns: &[],
         language: Some("rust"),
         ignore: &[],
         allow_tests: true,
@@ -265,6 +294,10 @@ fn func5() {}
         no_gitignore: false,
         strict: false,
         text_extensions: &[],
+        parent_kinds: &[],
+        sexp: false,
+        group_by: GroupBy::None,
+        follow_symlinks: false,
     };
 
     // Perform the query
@@ -296,6 +329,7 @@ fn test_query_ignore_patterns() -> Result<()> {
     let options = QueryOptions {
         path: temp_path,
         pattern: "fn $NAME() {}",
+        or_patterns: &[],
         language: Some("rust"),
         ignore: &["test".to_string()],
         allow_tests: false,
@@ -305,6 +339,10 @@ fn test_query_ignore_patterns() -> Result<()> {
         no_gitignore: false,
         strict: false,
         text_extensions: &[],
+        parent_kinds: &[],
+        sexp: false,
+        group_by: GroupBy::None,
+        follow_symlinks: false,
     };
 
     // Perform the query
@@ -337,6 +375,7 @@ fn auto_detected_function() {
     let options = QueryOptions {
         path: temp_path,
         pattern: "fn $NAME($$$PARAMS) $$$BODY",
+        or_patterns: &[],
         language: None, // No language specified, should auto-detect
         ignore: &[],
         allow_tests: true,
@@ -346,6 +385,10 @@ fn auto_detected_function() {
         no_gitignore: false,
         strict: false,
         text_extensions: &[],
+        parent_kinds: &[],
+        sexp: false,
+        group_by: GroupBy::None,
+        follow_symlinks: false,
     };
 
     // Perform the query
@@ -372,6 +415,7 @@ fn test_query_plain_text_fallback_for_unsupported_extension() -> Result<()> {
     let options = QueryOptions {
         path: temp_path,
         pattern: "reqproof:documents",
+        or_patterns: &[],
         language: None,
         ignore: &[],
         allow_tests: true,
@@ -381,6 +425,10 @@ fn test_query_plain_text_fallback_for_unsupported_extension() -> Result<()> {
         no_gitignore: false,
         strict: false,
         text_extensions: &[],
+        parent_kinds: &[],
+        sexp: false,
+        group_by: GroupBy::None,
+        follow_symlinks: false,
     };
 
     let matches = perform_query(&options)?;
@@ -404,6 +452,7 @@ fn test_query_custom_text_extension_cannot_override_hard_deny() -> Result<()> {
     let options = QueryOptions {
         path: temp_path,
         pattern: "needle",
+        or_patterns: &[],
         language: None,
         ignore: &[],
         allow_tests: true,
@@ -413,6 +462,10 @@ fn test_query_custom_text_extension_cannot_override_hard_deny() -> Result<()> {
         no_gitignore: false,
         strict: false,
         text_extensions: &text_extensions,
+        parent_kinds: &[],
+        sexp: false,
+        group_by: GroupBy::None,
+        follow_symlinks: false,
     };
 
     let matches = perform_query(&options)?;
@@ -438,6 +491,7 @@ fn test_query_skips_oversized_text_files() -> Result<()> {
     let options = QueryOptions {
         path: temp_path,
         pattern: "needle",
+        or_patterns: &[],
         language: None,
         ignore: &[],
         allow_tests: true,
@@ -447,6 +501,10 @@ fn test_query_skips_oversized_text_files() -> Result<()> {
         no_gitignore: false,
         strict: false,
         text_extensions: &[],
+        parent_kinds: &[],
+        sexp: false,
+        group_by: GroupBy::None,
+        follow_symlinks: false,
     };
 
     let matches = perform_query(&options)?;
@@ -466,6 +524,7 @@ fn test_query_strict_skips_unsupported_extension() -> Result<()> {
     let options = QueryOptions {
         path: temp_path,
         pattern: "reqproof:documents",
+        or_patterns: &[],
         language: None,
         ignore: &[],
         allow_tests: true,
@@ -475,6 +534,10 @@ fn test_query_strict_skips_unsupported_extension() -> Result<()> {
         no_gitignore: false,
         strict: true,
         text_extensions: &[],
+        parent_kinds: &[],
+        sexp: false,
+        group_by: GroupBy::None,
+        follow_symlinks: false,
     };
 
     let matches = perform_query(&options)?;
@@ -495,6 +558,7 @@ fn test_query_strict_skips_standard_text_extension_without_override() -> Result<
     let options = QueryOptions {
         path: temp_path,
         pattern: "reqproof:documents",
+        or_patterns: &[],
         language: None,
         ignore: &[],
         allow_tests: true,
@@ -504,6 +568,10 @@ fn test_query_strict_skips_standard_text_extension_without_override() -> Result<
         no_gitignore: false,
         strict: true,
         text_extensions: &[],
+        parent_kinds: &[],
+        sexp: false,
+        group_by: GroupBy::None,
+        follow_symlinks: false,
     };
 
     let matches = perform_query(&options)?;
@@ -528,6 +596,7 @@ fn test_query_custom_text_extension_forces_text_mode() -> Result<()> {
     let options = QueryOptions {
         path: temp_path,
         pattern: "reqproof:documents",
+        or_patterns: &[],
         language: None,
         ignore: &[],
         allow_tests: true,
@@ -537,6 +606,10 @@ fn test_query_custom_text_extension_forces_text_mode() -> Result<()> {
         no_gitignore: false,
         strict: false,
         text_extensions: &text_extensions,
+        parent_kinds: &[],
+        sexp: false,
+        group_by: GroupBy::None,
+        follow_symlinks: false,
     };
 
     let matches = perform_query(&options)?;
@@ -562,6 +635,7 @@ fn test_query_explicit_language_skips_standard_text_extension() -> Result<()> {
     let options = QueryOptions {
         path: temp_path,
         pattern: "reqproof:documents",
+        or_patterns: &[],
         language: Some("rust"),
         ignore: &[],
         allow_tests: true,
@@ -571,6 +645,10 @@ fn test_query_explicit_language_skips_standard_text_extension() -> Result<()> {
         no_gitignore: false,
         strict: false,
         text_extensions: &[],
+        parent_kinds: &[],
+        sexp: false,
+        group_by: GroupBy::None,
+        follow_symlinks: false,
     };
 
     let matches = perform_query(&options)?;
@@ -579,3 +657,404 @@ fn test_query_explicit_language_skips_standard_text_extension() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_query_sexp_for_rust_function() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let temp_path = temp_dir.path();
+
+    let rust_file_path = temp_path.join("lib.rs");
+    fs::write(
+        &rust_file_path,
+        "fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n",
+    )?;
+
+    let mut options = QueryOptions {
+        path: temp_path,
+        pattern: "fn $NAME($$$PARAMS) -> i32 { $$$BODY }",
+        or_patterns: &[],
+        language: Some("rust"),
+        ignore: &[],
+        allow_tests: true,
+        max_results: None,
+        with_context: false,
+        format: "json",
+        no_gitignore: false,
+        strict: false,
+        text_extensions: &[],
+        parent_kinds: &[],
+        sexp: true,
+        group_by: GroupBy::None,
+        follow_symlinks: false,
+    };
+
+    let matches = perform_query(&options)?;
+
+    assert_eq!(matches.len(), 1);
+    let sexp = matches[0].sexp.as_deref().expect("S-expression requested");
+    assert!(
+        sexp.starts_with("(function_item"),
+        "unexpected sexp: {sexp}"
+    );
+    assert!(
+        sexp.contains("name: (identifier)"),
+        "unexpected sexp: {sexp}"
+    );
+
+    // S-expressions are only computed on request
+    options.sexp = false;
+    let matches = perform_query(&options)?;
+    assert!(matches[0].sexp.is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_query_sexp_format_indents_nested_nodes() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let temp_path = temp_dir.path();
+
+    fs::write(
+        temp_path.join("lib.rs"),
+        "fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n",
+    )?;
+
+    let options = QueryOptions {
+        path: temp_path,
+        pattern: "fn $NAME($$$PARAMS) -> i32 { $$$BODY }",
+        or_patterns: &[],
+        language: Some("rust"),
+        ignore: &[],
+        allow_tests: true,
+        max_results: None,
+        with_context: false,
+        format: "sexp",
+        no_gitignore: false,
+        strict: false,
+        text_extensions: &[],
+        parent_kinds: &[],
+        sexp: false,
+        group_by: GroupBy::None,
+        follow_symlinks: false,
+    };
+
+    let matches = perform_query(&options)?;
+    assert_eq!(matches.len(), 1);
+    let sexp = matches[0]
+        .sexp
+        .as_deref()
+        .expect("sexp format attaches S-expressions");
+    let lines: Vec<&str> = sexp.lines().collect();
+    assert_eq!(lines[0], "(function_item");
+    assert_eq!(lines[1], "  name: (identifier)");
+    assert!(
+        lines.contains(&"    (binary_expression"),
+        "unexpected sexp: {sexp}"
+    );
+
+    // Same tree as the single-line form, only with whitespace differences
+    let flatten = |s: &str| s.split_whitespace().collect::<Vec<_>>().join(" ");
+    let single_line = perform_query(&QueryOptions {
+        format: "json",
+        sexp: true,
+        ..options
+    })?;
+    assert_eq!(
+        flatten(sexp),
+        flatten(single_line[0].sexp.as_deref().unwrap())
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_count_query_matches_per_file() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let temp_path = temp_dir.path();
+
+    fs::write(temp_path.join("a.rs"), "fn one() {}\nfn two() {}\n")?;
+    fs::write(temp_path.join("b.rs"), "fn three() {}\n")?;
+    fs::write(temp_path.join("c.rs"), "struct Empty;\n")?;
+
+    let options = QueryOptions {
+        path: temp_path,
+        pattern: "fn $NAME() {}",
+        or_patterns: &[],
+        language: Some("rust"),
+        ignore: &[],
+        allow_tests: true,
+        max_results: None,
+        with_context: false,
+        format: "color",
+        no_gitignore: false,
+        strict: false,
+        text_extensions: &[],
+        parent_kinds: &[],
+        sexp: false,
+        group_by: GroupBy::None,
+        follow_symlinks: false,
+    };
+
+    let counts = count_query_matches(&options)?;
+    assert_eq!(counts.total, 3);
+    assert_eq!(
+        counts.files,
+        vec![(temp_path.join("a.rs"), 2), (temp_path.join("b.rs"), 1)]
+    );
+
+    // The limit caps the total, however the files were scheduled
+    let counts = count_query_matches(&QueryOptions {
+        max_results: Some(2),
+        ..options
+    })?;
+    assert_eq!(counts.total, 2);
+    assert_eq!(counts.files.iter().map(|(_, n)| n).sum::<usize>(), 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_query_parent_kind_anchors_matches() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let temp_path = temp_dir.path();
+
+    fs::write(
+        temp_path.join("lib.rs"),
+        "fn run(x: Option<String>) -> usize {\n    x.clone().unwrap();\n    x.unwrap().len()\n}\n",
+    )?;
+
+    let parent_kinds = vec!["expression_statement".to_string()];
+    let options = QueryOptions {
+        path: temp_path,
+        pattern: "$A.unwrap()",
+        or_patterns: &[],
+        language: Some("rust"),
+        ignore: &[],
+        allow_tests: true,
+        max_results: None,
+        with_context: false,
+        format: "json",
+        no_gitignore: false,
+        strict: false,
+        text_extensions: &[],
+        parent_kinds: &parent_kinds,
+        sexp: false,
+        group_by: GroupBy::None,
+        follow_symlinks: false,
+    };
+
+    // Only the standalone statement, not the call inside the `.len()` chain
+    let matches = perform_query(&options)?;
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].line_start, 2);
+
+    // Anchor the capture: `$A` is the receiver of `.unwrap` in both calls,
+    // so its parent is always the field access
+    let capture_kinds = vec!["$A=field_expression".to_string()];
+    let matches = perform_query(&QueryOptions {
+        parent_kinds: &capture_kinds,
+        ..options
+    })?;
+    assert_eq!(matches.len(), 2);
+
+    let capture_kinds = vec!["A=call_expression".to_string()];
+    let matches = perform_query(&QueryOptions {
+        parent_kinds: &capture_kinds,
+        ..options
+    })?;
+    assert!(matches.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn test_query_captures_metavariable_spans() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let temp_path = temp_dir.path();
+
+    let content = "fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n";
+    fs::write(temp_path.join("lib.rs"), content)?;
+
+    let options = QueryOptions {
+        path: temp_path,
+        pattern: "fn $NAME($$$PARAMS) -> i32 { $$$BODY }",
+        or_patterns: &[],
+        language: Some("rust"),
+        ignore: &[],
+        allow_tests: true,
+        max_results: None,
+        with_context: false,
+        format: "json",
+        no_gitignore: false,
+        strict: false,
+        text_extensions: &[],
+        parent_kinds: &[],
+        sexp: false,
+        group_by: GroupBy::None,
+        follow_symlinks: false,
+    };
+
+    let matches = perform_query(&options)?;
+    assert_eq!(matches.len(), 1);
+    let captures = &matches[0].captures;
+
+    let name = &captures["NAME"];
+    assert_eq!(name.len(), 1);
+    assert_eq!(name[0].kind, "identifier");
+    assert_eq!(&content[name[0].byte_start..name[0].byte_end], "add");
+    assert_eq!((name[0].line_start, name[0].column_start), (1, 4));
+    assert_eq!((name[0].line_end, name[0].column_end), (1, 7));
+
+    // Multi-node captures keep every node, in source order
+    let params = &captures["PARAMS"];
+    assert!(params
+        .windows(2)
+        .all(|pair| pair[0].byte_end <= pair[1].byte_start));
+    let parameters: Vec<&str> = params
+        .iter()
+        .filter(|span| span.kind == "parameter")
+        .map(|span| &content[span.byte_start..span.byte_end])
+        .collect();
+    assert_eq!(parameters, vec!["a: i32", "b: i32"]);
+
+    let body = &captures["BODY"];
+    assert!(body
+        .iter()
+        .any(|span| &content[span.byte_start..span.byte_end] == "a + b"));
+    assert_eq!(body[0].line_start, 2);
+
+    Ok(())
+}
+
+#[test]
+fn test_query_at_position_returns_ancestor_chain() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let rust_file_path = temp_dir.path().join("lib.rs");
+    fs::write(
+        &rust_file_path,
+        "fn add(a: i32, b: i32) -> i32 {\n    a + b\n}\n",
+    )?;
+
+    // Line 2, column 5 is the `a` in `a + b`
+    let result = perform_query_at_position(&rust_file_path, 2, 5)?.expect("position in file");
+
+    assert_eq!(result.node.kind, "identifier");
+    assert_eq!((result.node.line_start, result.node.column_start), (2, 5));
+    let kinds: Vec<&str> = result.ancestors.iter().map(|a| a.kind.as_str()).collect();
+    assert_eq!(
+        kinds,
+        vec!["binary_expression", "block", "function_item", "source_file"]
+    );
+    assert_eq!(result.ancestors[2].line_start, 1);
+    assert_eq!(result.ancestors[2].line_end, 3);
+
+    Ok(())
+}
+
+#[test]
+fn test_query_at_position_out_of_range() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let rust_file_path = temp_dir.path().join("lib.rs");
+    fs::write(&rust_file_path, "fn main() {}\n")?;
+
+    assert!(perform_query_at_position(&rust_file_path, 0, 1)?.is_none());
+    assert!(perform_query_at_position(&rust_file_path, 1, 0)?.is_none());
+    assert!(perform_query_at_position(&rust_file_path, 1, 80)?.is_none());
+    assert!(perform_query_at_position(&rust_file_path, 42, 1)?.is_none());
+
+    let text_file_path = temp_dir.path().join("notes.txt");
+    fs::write(&text_file_path, "not code\n")?;
+    assert!(perform_query_at_position(&text_file_path, 1, 1).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_query_group_by_symbol_collapses_nested_matches() -> Result<()> {
+    let temp_dir = tempdir()?;
+    fs::write(
+        temp_dir.path().join("lib.rs"),
+        "fn total(a: i32, b: i32, c: i32) -> i32 {\n    a + b + c\n}\n\nfn offset(x: i32) -> i32 {\n    x + 1\n}\n",
+    )?;
+
+    let options = QueryOptions {
+        path: temp_dir.path(),
+        pattern: "$A + $B",
+        or_patterns: &[],
+        language: Some("rust"),
+        ignore: &[],
+        allow_tests: true,
+        max_results: None,
+        with_context: false,
+        format: "plain",
+        no_gitignore: true,
+        strict: false,
+        text_extensions: &[],
+        parent_kinds: &[],
+        sexp: false,
+        group_by: GroupBy::Symbol,
+        follow_symlinks: false,
+    };
+
+    // `a + b` lies inside `a + b + c` in the same function and is collapsed into it
+    let matches = perform_query(&options)?;
+    let texts: Vec<&str> = matches.iter().map(|m| m.matched_text.as_str()).collect();
+    assert_eq!(texts, vec!["a + b + c", "x + 1"]);
+
+    let groups = group_matches(&matches, GroupBy::Symbol);
+    let names: Vec<Option<&str>> = groups
+        .iter()
+        .map(|group| group.definition.and_then(|d| d.name.as_deref()))
+        .collect();
+    assert_eq!(names, vec![Some("total"), Some("offset")]);
+    let definition = groups[1].definition.unwrap();
+    assert_eq!(definition.node_type, "function_item");
+    assert_eq!((definition.line_start, definition.line_end), (5, 7));
+
+    // Grouping by file puts both matches under one heading
+    let groups = group_matches(&matches, GroupBy::File);
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].indices, vec![0, 1]);
+    assert!(groups[0].definition.is_none());
+
+    Ok(())
+}
+
+#[test]
+fn test_query_or_patterns_union_matches() -> Result<()> {
+    let temp_dir = tempdir()?;
+    fs::write(
+        temp_dir.path().join("lib.rs"),
+        "fn a(x: i32) -> i32 {\n    x + 1\n}\n\nfn b(y: i32) -> i32 {\n    y * 2\n}\n",
+    )?;
+
+    let or_patterns = vec!["$A * $B".to_string(), "x + 1".to_string()];
+    let options = QueryOptions {
+        path: temp_dir.path(),
+        pattern: "$A + $B",
+        or_patterns: &or_patterns,
+        language: Some("rust"),
+        ignore: &[],
+        allow_tests: true,
+        max_results: None,
+        with_context: false,
+        format: "plain",
+        no_gitignore: true,
+        strict: false,
+        text_extensions: &[],
+        parent_kinds: &[],
+        sexp: false,
+        group_by: GroupBy::None,
+        follow_symlinks: false,
+    };
+
+    // `x + 1` matches the first and third pattern but is reported once, for the first
+    let matches = perform_query(&options)?;
+    let found: Vec<(&str, usize)> = matches
+        .iter()
+        .map(|m| (m.matched_text.as_str(), m.pattern_index))
+        .collect();
+    assert_eq!(found, vec![("x + 1", 0), ("y * 2", 1)]);
+
+    Ok(())
+}
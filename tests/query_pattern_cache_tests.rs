@@ -0,0 +1,84 @@
+use anyhow::Result;
+use probe_code::query::{
+    clear_pattern_cache, pattern_compile_count, perform_query, GroupBy, QueryOptions,
+};
+use serial_test::serial;
+use std::fs;
+use std::path::Path;
+use tempfile::tempdir;
+
+fn query_options<'a>(path: &'a Path, pattern: &'a str, language: &'a str) -> QueryOptions<'a> {
+    QueryOptions {
+        path,
+        pattern,
+        or_patterns: &[],
+        language: Some(language),
+        ignore: &[],
+        allow_tests: true,
+        max_results: None,
+        with_context: false,
+        format: "plain",
+        no_gitignore: true,
+        strict: false,
+        text_extensions: &[],
+        parent_kinds: &[],
+        sexp: false,
+        group_by: GroupBy::None,
+        follow_symlinks: false,
+    }
+}
+
+#[test]
+#[serial]
+fn test_pattern_compiled_once_across_files() -> Result<()> {
+    clear_pattern_cache();
+    let temp_dir = tempdir()?;
+    for i in 0..25 {
+        fs::write(
+            temp_dir.path().join(format!("module_{i}.rs")),
+            format!("fn handler_{i}(value: i32) -> i32 {{\n    value + {i}\n}}\n"),
+        )?;
+    }
+
+    let pattern = "fn $NAME($$$PARAMS) -> i32 { $$$BODY }";
+    let before = pattern_compile_count();
+    let matches = perform_query(&query_options(temp_dir.path(), pattern, "rust"))?;
+    assert_eq!(matches.len(), 25);
+    assert_eq!(
+        pattern_compile_count(),
+        before + 1,
+        "Pattern should be compiled once for all files"
+    );
+
+    // A second run in the same process reuses the compiled pattern
+    perform_query(&query_options(temp_dir.path(), pattern, "rust"))?;
+    assert_eq!(pattern_compile_count(), before + 1);
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_pattern_cache_keyed_by_pattern_and_language() -> Result<()> {
+    clear_pattern_cache();
+    let temp_dir = tempdir()?;
+    fs::write(temp_dir.path().join("lib.rs"), "fn run() {}\n")?;
+    fs::write(temp_dir.path().join("app.js"), "function run() {}\n")?;
+
+    let before = pattern_compile_count();
+    let rust_matches = perform_query(&query_options(temp_dir.path(), "run()", "rust"))?;
+    let js_matches = perform_query(&query_options(temp_dir.path(), "run()", "javascript"))?;
+    assert_eq!(
+        pattern_compile_count(),
+        before + 2,
+        "Same pattern in another language needs its own compilation"
+    );
+    assert!(rust_matches.iter().all(|m| m.file_path.ends_with("lib.rs")));
+    assert!(js_matches.iter().all(|m| m.file_path.ends_with("app.js")));
+
+    let fn_matches = perform_query(&query_options(temp_dir.path(), "fn $NAME() {}", "rust"))?;
+    assert_eq!(pattern_compile_count(), before + 3);
+    assert_eq!(fn_matches.len(), 1);
+
+    Ok(())
+}
@@ -0,0 +1,117 @@
+use probe_code::models::SearchResult;
+use probe_code::search::{
+    perform_probe, BinaryDetection, DedupMode, MatchScope, RankMode, ResultSort, SearchOptions,
+};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn create_test_files(root: &Path) {
+    // ledger.rs mentions invoices throughout; the other files only once
+    fs::write(
+        root.join("ledger.rs"),
+        "fn open_invoice() {\n    record(invoice);\n}\n\nfn close_invoice() {\n    archive(invoice);\n}\n\nfn total_invoice() {\n    sum(invoice);\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("billing.rs"),
+        "fn charge() {\n    send(invoice);\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("audit.rs"),
+        "fn audit() {\n    check(invoice);\n}\n",
+    )
+    .unwrap();
+}
+
+fn search(root: &Path, rank_mode: RankMode, max_results: Option<usize>) -> Vec<SearchResult> {
+    let queries = vec!["invoice".to_string()];
+    let custom_ignores: Vec<String> = vec![];
+    let options = SearchOptions {
+        path: root,
+        queries: &queries,
+        files_only: false,
+        custom_ignores: &custom_ignores,
+        exclude_filenames: true,
+        language: None,
+        reranker: "bm25",
+        frequency_search: true,
+        max_results,
+        max_bytes: None,
+        max_tokens: None,
+        tokenizer: None,
+        allow_tests: true,
+        no_merge: true,
+        merge_threshold: None,
+        merge_respect_boundaries: true,
+        dry_run: false,
+        session: None,
+        timeout: 30,
+        question: None,
+        exact: false,
+        no_gitignore: true,
+        lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
+    };
+
+    perform_probe(&options)
+        .expect("Failed to perform search")
+        .results
+}
+
+#[test]
+fn test_file_rank_mode_returns_one_result_per_file() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    create_test_files(temp_dir.path());
+
+    let blocks = search(temp_dir.path(), RankMode::Block, None);
+    assert!(blocks.len() > 3, "expected several blocks, got {blocks:?}");
+
+    let files = search(temp_dir.path(), RankMode::File, None);
+    let names: HashSet<_> = files.iter().map(|r| r.file.clone()).collect();
+    assert_eq!(files.len(), 3);
+    assert_eq!(names.len(), 3);
+
+    // The file matching in every block outranks files with a single matching block
+    assert!(files[0].file.ends_with("ledger.rs"), "{files:?}");
+    assert!(files[0].score > files[1].score);
+    let ranks: Vec<_> = files.iter().map(|r| r.rank).collect();
+    assert_eq!(ranks, vec![Some(1), Some(2), Some(3)]);
+}
+
+#[test]
+fn test_file_rank_mode_counts_max_results_in_files() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    create_test_files(temp_dir.path());
+
+    let files = search(temp_dir.path(), RankMode::File, Some(2));
+    let names: HashSet<_> = files.iter().map(|r| r.file.clone()).collect();
+    assert_eq!(files.len(), 2);
+    assert_eq!(names.len(), 2);
+}
@@ -0,0 +1,100 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+fn create_test_files(root: &Path) {
+    // notes.rs mentions invoices far more often; invoice.rs only matches by name
+    fs::write(
+        root.join("notes.rs"),
+        "fn summary() {\n    let invoice = load();\n    let invoice_total = invoice.total();\n    audit(invoice, invoice_total);\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("invoice.rs"),
+        "fn total(amount: u64) {\n    send(invoice(amount));\n}\n",
+    )
+    .unwrap();
+}
+
+/// Search `root` for "invoice" with a config defining a profile that favors filename matches,
+/// and return the file names in result order
+fn ranked_files(root: &Path, config_dir: &Path, profile: Option<&str>) -> Vec<String> {
+    let config = config_dir.join("settings.json");
+    fs::write(
+        &config,
+        r#"{"search": {"ranking_profiles": {"filenames": {"filename_boost": 10.0}}}}"#,
+    )
+    .unwrap();
+
+    let mut command = Command::new(env!("CARGO_BIN_EXE_probe"));
+    command
+        .args(["search", "invoice", root.to_str().unwrap()])
+        .args([
+            "--format",
+            "json",
+            "--reranker",
+            "bm25",
+            "--exclude-filenames",
+        ])
+        .env("PROBE_CONFIG_PATH", &config);
+    if let Some(profile) = profile {
+        command.args(["--ranking-profile", profile]);
+    }
+    let output = command.output().expect("Failed to execute command");
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    json["results"]
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|result| {
+            Path::new(result["file"].as_str().unwrap())
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .to_string()
+        })
+        .collect()
+}
+
+#[test]
+fn test_filename_boost_reorders_results() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let config_dir = TempDir::new().expect("Failed to create temp dir");
+    create_test_files(temp_dir.path());
+
+    assert_eq!(
+        ranked_files(temp_dir.path(), config_dir.path(), None),
+        vec!["notes.rs", "invoice.rs"]
+    );
+    assert_eq!(
+        ranked_files(temp_dir.path(), config_dir.path(), Some("default")),
+        vec!["notes.rs", "invoice.rs"]
+    );
+    assert_eq!(
+        ranked_files(temp_dir.path(), config_dir.path(), Some("filenames")),
+        vec!["invoice.rs", "notes.rs"]
+    );
+}
+
+#[test]
+fn test_unknown_ranking_profile_is_an_error() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    create_test_files(temp_dir.path());
+
+    let output = Command::new(env!("CARGO_BIN_EXE_probe"))
+        .args(["search", "invoice", temp_dir.path().to_str().unwrap()])
+        .args(["--ranking-profile", "nonexistent"])
+        .output()
+        .expect("Failed to execute command");
+    assert!(!output.status.success());
+    assert!(
+        String::from_utf8_lossy(&output.stderr).contains("Unknown ranking profile: nonexistent")
+    );
+}
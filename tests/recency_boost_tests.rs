@@ -0,0 +1,156 @@
+use probe_code::search::{
+    perform_probe, BinaryDetection, DedupMode, MatchScope, RankMode, ResultSort, SearchOptions,
+};
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+use tempfile::TempDir;
+
+/// Run a git command in `dir`, committing with the given author/committer date
+fn git(dir: &Path, args: &[&str], date: Option<&str>) {
+    let mut command = Command::new("git");
+    command
+        .args(args)
+        .current_dir(dir)
+        .env("GIT_AUTHOR_NAME", "Test User")
+        .env("GIT_AUTHOR_EMAIL", "test@example.com")
+        .env("GIT_COMMITTER_NAME", "Test User")
+        .env("GIT_COMMITTER_EMAIL", "test@example.com");
+    if let Some(date) = date {
+        command
+            .env("GIT_AUTHOR_DATE", date)
+            .env("GIT_COMMITTER_DATE", date);
+    }
+    let status = command.status().expect("Failed to run git");
+    assert!(status.success(), "git {args:?} failed");
+}
+
+/// Two files with equally relevant matches: one committed long ago, one just now
+fn create_fixture_repo() -> TempDir {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let root = temp_dir.path();
+    git(root, &["init", "-q"], None);
+
+    fs::write(
+        root.join("a_stale.rs"),
+        "fn charge_alpha(order: &Order) {\n    settle_invoice(order);\n}\n",
+    )
+    .unwrap();
+    git(root, &["add", "a_stale.rs"], None);
+    git(
+        root,
+        &["commit", "-q", "-m", "old"],
+        Some("2020-01-01T00:00:00Z"),
+    );
+
+    fs::write(
+        root.join("b_fresh.rs"),
+        "fn charge_bravo(order: &Order) {\n    settle_invoice(order);\n}\n",
+    )
+    .unwrap();
+    git(root, &["add", "b_fresh.rs"], None);
+    git(root, &["commit", "-q", "-m", "new"], None);
+
+    temp_dir
+}
+
+fn search_files(root: &Path, recency_boost: bool) -> Vec<String> {
+    let queries = vec!["settle_invoice".to_string()];
+    let custom_ignores: Vec<String> = vec![];
+    let options = SearchOptions {
+        path: root,
+        queries: &queries,
+        files_only: false,
+        custom_ignores: &custom_ignores,
+        exclude_filenames: true,
+        language: None,
+        reranker: "bm25",
+        frequency_search: true,
+        max_results: None,
+        max_bytes: None,
+        max_tokens: None,
+        tokenizer: None,
+        allow_tests: true,
+        no_merge: true,
+        merge_threshold: None,
+        merge_respect_boundaries: true,
+        dry_run: false,
+        session: None,
+        timeout: 30,
+        question: None,
+        exact: false,
+        no_gitignore: true,
+        lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
+    };
+
+    perform_probe(&options)
+        .expect("Failed to perform search")
+        .results
+        .into_iter()
+        .map(|r| {
+            Path::new(&r.file)
+                .file_name()
+                .unwrap()
+                .to_string_lossy()
+                .to_string()
+        })
+        .collect()
+}
+
+#[test]
+fn test_recency_boost_ranks_recently_committed_file_first() {
+    let repo = create_fixture_repo();
+
+    // Equal relevance: ties fall back to path order
+    assert_eq!(
+        search_files(repo.path(), false),
+        vec!["a_stale.rs", "b_fresh.rs"]
+    );
+
+    assert_eq!(
+        search_files(repo.path(), true),
+        vec!["b_fresh.rs", "a_stale.rs"]
+    );
+}
+
+#[test]
+fn test_recency_boost_is_noop_outside_git() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    for name in ["a_stale.rs", "b_fresh.rs"] {
+        fs::write(
+            temp_dir.path().join(name),
+            "fn charge(order: &Order) {\n    settle_invoice(order);\n}\n",
+        )
+        .unwrap();
+    }
+
+    assert_eq!(
+        search_files(temp_dir.path(), true),
+        search_files(temp_dir.path(), false)
+    );
+}
@@ -0,0 +1,247 @@
+use probe_code::search::{
+    perform_probe, BinaryDetection, DedupMode, MatchScope, RankMode, ResultSort, SearchOptions,
+};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn regex_search(root: &Path, pattern: &str, match_scope: MatchScope) -> Vec<(usize, String)> {
+    let queries = vec![pattern.to_string()];
+    let custom_ignores: Vec<String> = vec![];
+    let options = SearchOptions {
+        path: root,
+        queries: &queries,
+        files_only: false,
+        custom_ignores: &custom_ignores,
+        exclude_filenames: true,
+        language: None,
+        reranker: "bm25",
+        frequency_search: true,
+        max_results: None,
+        max_bytes: None,
+        max_tokens: None,
+        tokenizer: None,
+        allow_tests: true,
+        no_merge: true,
+        merge_threshold: None,
+        merge_respect_boundaries: true,
+        dry_run: false,
+        session: None,
+        timeout: 30,
+        question: None,
+        exact: false,
+        no_gitignore: true,
+        lsp: false,
+        sort: ResultSort::Line,
+        regex: true,
+        match_scope,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
+    };
+
+    perform_probe(&options)
+        .expect("Failed to perform regex search")
+        .results
+        .into_iter()
+        .map(|r| (r.lines.0, r.node_type))
+        .collect()
+}
+
+/// Writes `source` to `file_name` and checks that the `api_secret` regex only hits the
+/// string literal on `string_line` when scoped, while the variable of the same name on the
+/// line before is also reported (within its enclosing block) when unscoped.
+fn assert_matches_only_string(file_name: &str, source: &str, string_line: usize) {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    fs::write(temp_dir.path().join(file_name), source).unwrap();
+
+    let scoped = regex_search(
+        temp_dir.path(),
+        r"api_secret",
+        MatchScope::StringsAndComments,
+    );
+    assert_eq!(
+        scoped.len(),
+        1,
+        "{file_name}: expected only the string literal to match, got {scoped:?}"
+    );
+    assert_eq!(scoped[0].0, string_line, "{file_name}: wrong line");
+    assert!(
+        scoped[0].1.contains("string"),
+        "{file_name}: expected a string node, got {}",
+        scoped[0].1
+    );
+
+    let unscoped = regex_search(temp_dir.path(), r"api_secret", MatchScope::All);
+    assert!(
+        unscoped.iter().any(|(start, _)| *start < string_line),
+        "{file_name}: unscoped regex should also match the variable, got {unscoped:?}"
+    );
+}
+
+#[test]
+fn test_rust_regex_matches_string_not_variable() {
+    assert_matches_only_string(
+        "config.rs",
+        "fn load() {\n    let api_secret = read();\n    let key = \"api_secret=abc123\";\n}\n",
+        3,
+    );
+}
+
+#[test]
+fn test_python_regex_matches_string_not_variable() {
+    assert_matches_only_string(
+        "config.py",
+        "def load():\n    api_secret = read()\n    key = \"api_secret=abc123\"\n",
+        3,
+    );
+}
+
+#[test]
+fn test_javascript_regex_matches_string_not_variable() {
+    assert_matches_only_string(
+        "config.js",
+        "function load() {\n  const api_secret = read();\n  const key = 'api_secret=abc123';\n}\n",
+        3,
+    );
+}
+
+#[test]
+fn test_go_regex_matches_string_not_variable() {
+    assert_matches_only_string(
+        "config.go",
+        "package main\n\nfunc load() {\n\tapi_secret := read()\n\tkey := \"api_secret=abc123\"\n\t_ = key\n}\n",
+        5,
+    );
+}
+
+#[test]
+fn test_regex_matches_inside_comments() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    fs::write(
+        temp_dir.path().join("notes.rs"),
+        "// TODO: rotate password=hunter2\nfn password() {}\n",
+    )
+    .unwrap();
+
+    let results = regex_search(
+        temp_dir.path(),
+        r"password=\w+",
+        MatchScope::StringsAndComments,
+    );
+    assert_eq!(results.len(), 1, "Expected one comment match: {results:?}");
+    assert_eq!(results[0].0, 1);
+    assert!(results[0].1.contains("comment"));
+}
+
+#[test]
+fn test_regex_matches_expand_to_enclosing_blocks() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    fs::write(
+        temp_dir.path().join("settings.rs"),
+        "fn get_db_config() -> Config {\n    load(\"db\")\n}\n\nfn get_cache_config() -> Config {\n    load(\"cache\")\n}\n\nfn get_config() -> Config {\n    load(\"all\")\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        temp_dir.path().join("notes.txt"),
+        "see get_db_config\nand get_cache_config\n",
+    )
+    .unwrap();
+
+    let results = regex_search(temp_dir.path(), r"get_.+_config", MatchScope::All);
+    // Whole functions for the Rust file, single lines for the file without a grammar
+    assert_eq!(
+        results,
+        vec![
+            (1, "line".to_string()),
+            (1, "function_item".to_string()),
+            (2, "line".to_string()),
+            (5, "function_item".to_string()),
+        ],
+        "{results:?}"
+    );
+}
+
+#[test]
+fn test_invalid_regex_is_an_error() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    fs::write(temp_dir.path().join("lib.rs"), "fn main() {}\n").unwrap();
+
+    let queries = vec!["get_(config".to_string()];
+    let custom_ignores: Vec<String> = vec![];
+    let options = SearchOptions {
+        path: temp_dir.path(),
+        queries: &queries,
+        files_only: false,
+        custom_ignores: &custom_ignores,
+        exclude_filenames: true,
+        language: None,
+        reranker: "bm25",
+        frequency_search: true,
+        max_results: None,
+        max_bytes: None,
+        max_tokens: None,
+        tokenizer: None,
+        allow_tests: true,
+        no_merge: true,
+        merge_threshold: None,
+        merge_respect_boundaries: true,
+        dry_run: false,
+        session: None,
+        timeout: 30,
+        question: None,
+        exact: false,
+        no_gitignore: true,
+        lsp: false,
+        sort: ResultSort::Line,
+        regex: true,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
+    };
+
+    let error = perform_probe(&options).expect_err("Invalid regex should not fall back");
+    assert!(
+        error.to_string().contains("Invalid regex"),
+        "Unexpected error: {error}"
+    );
+}
@@ -0,0 +1,133 @@
+use probe_code::search::{
+    perform_probe, BinaryDetection, DedupMode, MatchScope, RankMode, ResultSort, SearchOptions,
+};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn create_test_files(root: &Path) {
+    // Files named so that path order differs from relevance order:
+    // the file with the most matches sorts last by path.
+    fs::write(
+        root.join("a_sparse.rs"),
+        "fn a_sparse() {\n    let widget = 1;\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("m_medium.rs"),
+        "fn m_medium() {\n    let widget = widget_factory(widget);\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("z_dense.rs"),
+        "fn z_dense_widget() {\n    let widget = widget_builder(widget, widget, widget);\n    widget_render(widget);\n}\n",
+    )
+    .unwrap();
+}
+
+fn search_with_sort(
+    root: &Path,
+    sort: ResultSort,
+    max_results: Option<usize>,
+) -> Vec<(String, usize)> {
+    let queries = vec!["widget".to_string()];
+    let custom_ignores: Vec<String> = vec![];
+    let options = SearchOptions {
+        path: root,
+        queries: &queries,
+        files_only: false,
+        custom_ignores: &custom_ignores,
+        exclude_filenames: true,
+        language: None,
+        reranker: "bm25",
+        frequency_search: true,
+        max_results,
+        max_bytes: None,
+        max_tokens: None,
+        tokenizer: None,
+        allow_tests: true,
+        no_merge: true,
+        merge_threshold: None,
+        merge_respect_boundaries: true,
+        dry_run: false,
+        session: None,
+        timeout: 30,
+        question: None,
+        exact: false,
+        no_gitignore: true,
+        lsp: false,
+        sort,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
+    };
+
+    perform_probe(&options)
+        .expect("Failed to perform search")
+        .results
+        .into_iter()
+        .map(|r| (r.file, r.lines.0))
+        .collect()
+}
+
+#[test]
+fn test_path_sort_orders_results_by_path_then_line() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    create_test_files(temp_dir.path());
+
+    let results = search_with_sort(temp_dir.path(), ResultSort::Path, None);
+    assert!(
+        results.len() >= 3,
+        "Expected a result per file: {results:?}"
+    );
+
+    let mut expected = results.clone();
+    expected.sort();
+    assert_eq!(
+        results, expected,
+        "Results should be ordered by path, then line"
+    );
+}
+
+#[test]
+fn test_path_sort_keeps_score_based_top_k_selection() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    create_test_files(temp_dir.path());
+
+    let by_score = search_with_sort(temp_dir.path(), ResultSort::Score, Some(2));
+    let by_path = search_with_sort(temp_dir.path(), ResultSort::Path, Some(2));
+
+    assert_eq!(by_score.len(), 2);
+    assert_eq!(by_path.len(), 2);
+
+    // The same top-2 results must be selected regardless of the final ordering
+    let score_set: HashSet<_> = by_score.iter().cloned().collect();
+    let path_set: HashSet<_> = by_path.iter().cloned().collect();
+    assert_eq!(score_set, path_set, "Top-K selection should be score-based");
+
+    let mut expected = by_path.clone();
+    expected.sort();
+    assert_eq!(by_path, expected, "Selected results should be path-sorted");
+}
@@ -0,0 +1,113 @@
+use probe_code::search::search_index::SearchIndex;
+use probe_code::search::{
+    perform_probe, BinaryDetection, DedupMode, MatchScope, RankMode, ResultSort, SearchOptions,
+};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn search(root: &Path, query: &str, use_index: bool) -> Vec<(String, String)> {
+    let queries = vec![query.to_string()];
+    let custom_ignores: Vec<String> = vec![];
+    let options = SearchOptions {
+        path: root,
+        queries: &queries,
+        files_only: false,
+        custom_ignores: &custom_ignores,
+        exclude_filenames: true,
+        language: None,
+        reranker: "bm25",
+        frequency_search: true,
+        max_results: None,
+        max_bytes: None,
+        max_tokens: None,
+        tokenizer: None,
+        allow_tests: true,
+        no_merge: true,
+        merge_threshold: None,
+        merge_respect_boundaries: true,
+        dry_run: false,
+        session: None,
+        timeout: 30,
+        question: None,
+        exact: false,
+        no_gitignore: true,
+        lsp: false,
+        sort: ResultSort::Path,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
+    };
+
+    perform_probe(&options)
+        .expect("Failed to perform search")
+        .results
+        .into_iter()
+        .map(|r| {
+            let keywords = r.matched_keywords.unwrap_or_default().join(",");
+            (r.file, keywords)
+        })
+        .collect()
+}
+
+#[test]
+fn test_search_index_is_reused_and_invalidated_on_change() {
+    // Keep the index out of the real cache directory
+    let home = TempDir::new().expect("Failed to create temp dir");
+    std::env::set_var("HOME", home.path());
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let root = temp_dir.path();
+    fs::write(
+        root.join("billing.rs"),
+        "fn settle(order: &Order) {\n    charge_invoice(order);\n}\n",
+    )
+    .unwrap();
+    fs::write(
+        root.join("refunds.rs"),
+        "fn refund(order: &Order) {\n    reverse_charge(order);\n}\n",
+    )
+    .unwrap();
+
+    let index_path = SearchIndex::get_index_path(root);
+    assert!(!index_path.exists());
+
+    // The cold run builds the index; the warm run returns the same results from it
+    let cold = search(root, "order", true);
+    assert!(index_path.exists());
+    assert_eq!(cold, search(root, "order", false));
+    assert_eq!(search(root, "order", true), cold);
+
+    // Changing a file invalidates its indexed blocks
+    fs::write(
+        root.join("refunds.rs"),
+        "fn refund(order: &Order) {\n    reverse_charge(order);\n    notify_customer(order);\n}\n",
+    )
+    .unwrap();
+    let changed = search(root, "customer order", true);
+    assert_eq!(changed, search(root, "customer order", false));
+    assert!(changed
+        .iter()
+        .any(|(file, keywords)| file.ends_with("refunds.rs") && keywords.contains("custom")));
+}
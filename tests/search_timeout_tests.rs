@@ -0,0 +1,97 @@
+use probe_code::models::LimitedSearchResults;
+use probe_code::search::{
+    perform_probe, BinaryDetection, DedupMode, MatchScope, RankMode, ResultSort, SearchOptions,
+};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+const FILE_COUNT: usize = 400;
+
+fn create_test_files(root: &Path) {
+    for i in 0..FILE_COUNT {
+        fs::write(
+            root.join(format!("invoice_{i}.rs")),
+            format!("fn settle_invoice_{i}(invoice: Invoice) {{\n    post(invoice, {i});\n}}\n"),
+        )
+        .unwrap();
+    }
+}
+
+fn search(root: &Path, timeout: u64) -> LimitedSearchResults {
+    let queries = vec!["invoice".to_string()];
+    let custom_ignores: Vec<String> = vec![];
+    let options = SearchOptions {
+        path: root,
+        queries: &queries,
+        files_only: false,
+        custom_ignores: &custom_ignores,
+        exclude_filenames: true,
+        language: None,
+        reranker: "bm25",
+        frequency_search: true,
+        max_results: None,
+        max_bytes: None,
+        max_tokens: None,
+        tokenizer: None,
+        allow_tests: true,
+        no_merge: true,
+        merge_threshold: None,
+        merge_respect_boundaries: true,
+        dry_run: false,
+        session: None,
+        timeout,
+        question: None,
+        exact: false,
+        no_gitignore: true,
+        lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
+    };
+
+    perform_probe(&options).expect("a timeout returns partial results, not an error")
+}
+
+#[test]
+fn test_timeout_returns_partial_flagged_results() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    create_test_files(temp_dir.path());
+
+    let complete = search(temp_dir.path(), 30);
+    assert!(!complete.timed_out);
+    assert!(complete.files_skipped_early_termination.is_none());
+
+    let partial = search(temp_dir.path(), 0);
+    assert!(partial.timed_out);
+    assert!(!partial.results.is_empty());
+    assert!(partial.results.len() < complete.results.len());
+
+    let unprocessed = partial
+        .files_skipped_early_termination
+        .expect("the unprocessed files are counted");
+    assert!(unprocessed > 0);
+    assert!(unprocessed < FILE_COUNT);
+}
@@ -1,6 +1,8 @@
 use probe_code::extract::process_file_for_extraction;
 use probe_code::extract::symbols::extract_symbols;
-use probe_code::search::{perform_probe, SearchOptions};
+use probe_code::search::{
+    perform_probe, BinaryDetection, DedupMode, MatchScope, RankMode, ResultSort, SearchOptions,
+};
 use std::path::PathBuf;
 
 fn fixture_root() -> PathBuf {
@@ -117,15 +119,42 @@ fn test_solidity_search_language_filter_and_test_exclusion() {
         max_results: Some(20),
         max_bytes: None,
         max_tokens: None,
+        tokenizer: None,
         allow_tests: false,
         no_merge: false,
         merge_threshold: None,
+        merge_respect_boundaries: true,
         lsp: false,
         dry_run: false,
         session: None,
         timeout: 30,
         question: None,
         no_gitignore: true,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
     };
 
     let results = perform_probe(&options).expect("search should support Solidity language filter");
@@ -0,0 +1,155 @@
+use probe_code::search::{
+    perform_probe, perform_probe_top_k, perform_probe_unranked, BinaryDetection, DedupMode,
+    MatchScope, RankMode, ResultSort, SearchOptions,
+};
+use std::collections::BTreeSet;
+use std::fs;
+use std::ops::ControlFlow;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn create_fixture() -> TempDir {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    for index in 0..5 {
+        fs::write(
+            temp_dir.path().join(format!("billing_{index}.rs")),
+            format!("fn charge_{index}(order: &Order) {{\n    settle_invoice(order);\n}}\n"),
+        )
+        .unwrap();
+    }
+    temp_dir
+}
+
+fn options<'a>(
+    root: &'a Path,
+    queries: &'a [String],
+    custom_ignores: &'a [String],
+) -> SearchOptions<'a> {
+    SearchOptions {
+        path: root,
+        queries,
+        files_only: false,
+        custom_ignores,
+        exclude_filenames: true,
+        language: None,
+        reranker: "bm25",
+        frequency_search: true,
+        max_results: None,
+        max_bytes: None,
+        max_tokens: None,
+        tokenizer: None,
+        allow_tests: true,
+        no_merge: true,
+        merge_threshold: None,
+        merge_respect_boundaries: true,
+        dry_run: false,
+        session: None,
+        timeout: 30,
+        question: None,
+        exact: false,
+        no_gitignore: true,
+        lsp: false,
+        sort: ResultSort::Score,
+        regex: false,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within: None,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
+    }
+}
+
+#[test]
+fn test_streaming_emits_same_results_as_collected_search() {
+    let temp_dir = create_fixture();
+    let queries = vec!["settle_invoice".to_string()];
+    let custom_ignores: Vec<String> = vec![];
+    let options = options(temp_dir.path(), &queries, &custom_ignores);
+
+    let mut streamed = Vec::new();
+    let emitted = perform_probe_unranked(&options, |result| {
+        streamed.push(result);
+        ControlFlow::Continue(())
+    })
+    .expect("Failed to stream search");
+
+    assert_eq!(emitted, 5);
+    let ranks: Vec<_> = streamed.iter().map(|r| r.rank).collect();
+    assert_eq!(ranks, (1..=5).map(Some).collect::<Vec<_>>());
+
+    let streamed_files: BTreeSet<_> = streamed.into_iter().map(|r| r.file).collect();
+    let collected_files: BTreeSet<_> = perform_probe(&options)
+        .expect("Failed to perform search")
+        .results
+        .into_iter()
+        .map(|r| r.file)
+        .collect();
+    assert_eq!(streamed_files, collected_files);
+}
+
+#[test]
+fn test_streaming_stops_when_callback_breaks() {
+    let temp_dir = create_fixture();
+    let queries = vec!["settle_invoice".to_string()];
+    let custom_ignores: Vec<String> = vec![];
+    let options = options(temp_dir.path(), &queries, &custom_ignores);
+
+    let mut seen = 0;
+    let emitted = perform_probe_unranked(&options, |_| {
+        seen += 1;
+        ControlFlow::Break(())
+    })
+    .expect("Failed to stream search");
+
+    assert_eq!(emitted, 1);
+    assert_eq!(seen, 1);
+}
+
+#[test]
+fn test_top_k_emits_best_results_first() {
+    let temp_dir = create_fixture();
+    // One file mentions the term far more often than the others
+    fs::write(
+        temp_dir.path().join("settlement.rs"),
+        "fn settle_all(order: &Order) {\n    settle_invoice(order);\n    settle_invoice(order);\n    settle_invoice(order);\n}\n",
+    )
+    .unwrap();
+    let queries = vec!["settle_invoice".to_string()];
+    let custom_ignores: Vec<String> = vec![];
+    let options = options(temp_dir.path(), &queries, &custom_ignores);
+
+    let mut top = Vec::new();
+    let emitted = perform_probe_top_k(&options, 2, |result| {
+        top.push(result);
+        ControlFlow::Continue(())
+    })
+    .expect("Failed to stream search");
+
+    assert_eq!(emitted, 2);
+    let ranks: Vec<_> = top.iter().map(|r| r.rank).collect();
+    assert_eq!(ranks, vec![Some(1), Some(2)]);
+    assert!(top[0].score >= top[1].score);
+
+    let best = perform_probe(&options)
+        .expect("Failed to perform search")
+        .results;
+    assert_eq!(top[0].file, best[0].file);
+}
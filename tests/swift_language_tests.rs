@@ -0,0 +1,107 @@
+use probe_code::extract::process_file_for_extraction;
+use probe_code::query::{perform_query, AstMatch, GroupBy, QueryOptions};
+use std::path::PathBuf;
+
+fn fixture_root() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/swift/project1")
+}
+
+fn geometry_file() -> PathBuf {
+    fixture_root().join("Sources/Geometry.swift")
+}
+
+fn extract_symbol(symbol: &str) -> Vec<(usize, usize)> {
+    process_file_for_extraction(
+        &geometry_file(),
+        None,
+        None,
+        Some(symbol),
+        true,
+        0,
+        None,
+        false,
+        false,
+    )
+    .expect("extract should find Swift symbol")
+    .into_iter()
+    .map(|result| result.lines)
+    .collect()
+}
+
+fn extract_line(line: usize) -> (usize, usize, String) {
+    let result = process_file_for_extraction(
+        &geometry_file(),
+        Some(line),
+        Some(line),
+        None,
+        true,
+        0,
+        None,
+        false,
+        false,
+    )
+    .expect("extract should find enclosing Swift declaration from line target");
+    (result.lines.0, result.lines.1, result.code.clone())
+}
+
+fn query(pattern: &str, language: Option<&str>) -> Vec<AstMatch> {
+    let options = QueryOptions {
+        path: &fixture_root(),
+        pattern,
+        or_patterns: &[],
+        language,
+        ignore: &[],
+        allow_tests: true,
+        max_results: None,
+        with_context: false,
+        format: "terminal",
+        no_gitignore: true,
+        strict: false,
+        text_extensions: &[],
+        parent_kinds: &[],
+        sexp: false,
+        group_by: GroupBy::None,
+        follow_symlinks: false,
+    };
+
+    perform_query(&options).expect("Swift query should run")
+}
+
+#[test]
+fn test_swift_symbol_extraction_block_boundaries() {
+    assert_eq!(extract_symbol("distance"), vec![(14, 18)]);
+    assert_eq!(extract_symbol("Segment"), vec![(25, 32)]);
+    assert_eq!(extract_symbol("length"), vec![(29, 31)]);
+    assert_eq!(extract_symbol("Canvas.add"), vec![(38, 40)]);
+}
+
+#[test]
+fn test_swift_extension_members() {
+    // The struct and the extension that adds methods to it are both `Point`
+    assert_eq!(extract_symbol("Point"), vec![(3, 11), (13, 33)]);
+    assert_eq!(extract_symbol("Point.translate"), vec![(20, 23)]);
+    assert_eq!(extract_symbol("Point.Segment"), vec![(25, 32)]);
+}
+
+#[test]
+fn test_swift_extraction_by_line_target_returns_whole_function() {
+    let (start, end, code) = extract_line(16);
+    assert_eq!((start, end), (14, 18));
+    assert!(code.starts_with("func distance(to other: Point) -> Double {"));
+    assert!(code.trim_end().ends_with('}'));
+
+    let (start, end, code) = extract_line(8);
+    assert_eq!((start, end), (7, 10));
+    assert!(code.starts_with("init(x: Double, y: Double) {"));
+}
+
+#[test]
+fn test_swift_query_matches_calls() {
+    let matches = query("$OBJ.distance(to: $OTHER)", Some("swift"));
+    assert_eq!(matches.len(), 1);
+    assert_eq!((matches[0].line_start, matches[0].line_end), (30, 30));
+
+    let auto_detected = query("points.append($POINT)", None);
+    assert_eq!(auto_detected.len(), 1);
+    assert_eq!(auto_detected[0].line_start, 39);
+}
@@ -69,6 +69,10 @@ async fn test_fix_using_handle_try_current() {
         symbol_signature: None,
         parent_context: None,
         matched_lines: None,
+        score_breakdown: None,
+        term_matches: None,
+        duplicates: None,
+        root: None,
     }];
 
     let result = probe_code::search::lsp_enrichment::enrich_results_with_lsp(&mut results, false);
@@ -112,6 +116,10 @@ async fn test_realistic_search_scenario() {
         symbol_signature: None,
         parent_context: None,
         matched_lines: None,
+        score_breakdown: None,
+        term_matches: None,
+        duplicates: None,
+        root: None,
     };
 
     let mut results = vec![result];
@@ -0,0 +1,155 @@
+use probe_code::models::SearchResult;
+use probe_code::search::{
+    perform_probe, BinaryDetection, DedupMode, MatchScope, NodeKind, RankMode, ResultSort,
+    SearchOptions,
+};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+fn create_test_files(root: &Path) {
+    fs::write(
+        root.join("payments.rs"),
+        r#"// TODO: retry the payment when the gateway times out
+fn charge(order: &Order) {
+    submit_payment(order);
+}
+
+fn refund(order: &Order) {
+    let url = "https://payments.example.com/refund";
+    reverse(url, order);
+}
+"#,
+    )
+    .unwrap();
+    // Files without a grammar have no comments or strings to search in
+    fs::write(root.join("notes.txt"), "payment TODO list\n").unwrap();
+}
+
+fn search(
+    root: &Path,
+    query: &str,
+    within: Option<Vec<NodeKind>>,
+    regex: bool,
+) -> Vec<SearchResult> {
+    let queries = vec![query.to_string()];
+    let custom_ignores: Vec<String> = vec![];
+    let options = SearchOptions {
+        path: root,
+        queries: &queries,
+        files_only: false,
+        custom_ignores: &custom_ignores,
+        exclude_filenames: true,
+        language: None,
+        reranker: "bm25",
+        frequency_search: true,
+        max_results: None,
+        max_bytes: None,
+        max_tokens: None,
+        tokenizer: None,
+        allow_tests: true,
+        no_merge: true,
+        merge_threshold: None,
+        merge_respect_boundaries: true,
+        dry_run: false,
+        session: None,
+        timeout: 30,
+        question: None,
+        exact: false,
+        no_gitignore: true,
+        lsp: false,
+        sort: ResultSort::Path,
+        regex,
+        match_scope: MatchScope::All,
+        invert: false,
+        kinds: None,
+        recency_boost: false,
+        recency_half_life_days: 14.0,
+        context_symbols: false,
+        context_symbols_depth: 5,
+        explain: false,
+        use_index: false,
+        rank_mode: RankMode::Block,
+        within,
+        changed_since: None,
+        tracked_only: false,
+        rerank_top_k: 50,
+        binary_detection: BinaryDetection::NullByte,
+        force_text_extensions: &[],
+        search_archives: false,
+        encoding: None,
+        min_score: None,
+        follow_symlinks: false,
+        ranking_profile: None,
+        dedup: DedupMode::None,
+        extra_roots: &[],
+    };
+
+    perform_probe(&options)
+        .expect("Failed to perform search")
+        .results
+}
+
+#[test]
+fn test_within_excludes_blocks_matching_only_outside_requested_nodes() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    create_test_files(temp_dir.path());
+
+    // Unrestricted, both functions match
+    let all = search(temp_dir.path(), "payment", None, false);
+    assert!(all.iter().any(|r| r.code.contains("fn refund")), "{all:?}");
+
+    // Only the comment mentions payments in `charge`; `refund` only does in a string
+    let comments = search(
+        temp_dir.path(),
+        "payment",
+        Some(vec![NodeKind::Comment]),
+        false,
+    );
+    assert!(!comments.is_empty());
+    assert!(comments.iter().all(|r| r.file.ends_with("payments.rs")));
+    assert!(
+        comments.iter().any(|r| r.code.contains("TODO")),
+        "{comments:?}"
+    );
+    assert!(
+        !comments.iter().any(|r| r.code.contains("fn refund")),
+        "{comments:?}"
+    );
+
+    let strings = search(
+        temp_dir.path(),
+        "payment",
+        Some(vec![NodeKind::String]),
+        false,
+    );
+    assert!(!strings.is_empty());
+    assert!(
+        strings.iter().all(|r| r.code.contains("https://payments")),
+        "{strings:?}"
+    );
+    assert!(
+        !strings.iter().any(|r| r.code.contains("TODO")),
+        "{strings:?}"
+    );
+}
+
+#[test]
+fn test_within_composes_with_regex() {
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    create_test_files(temp_dir.path());
+
+    let results = search(
+        temp_dir.path(),
+        r#"https?://[^"\s]+"#,
+        Some(vec![NodeKind::String]),
+        true,
+    );
+    assert_eq!(results.len(), 1, "{results:?}");
+    assert_eq!(results[0].lines, (7, 7));
+    assert!(results[0].node_type.contains("string"));
+
+    // The only TODO in code is in a comment
+    let results = search(temp_dir.path(), "TODO", Some(vec![NodeKind::String]), true);
+    assert!(results.is_empty(), "{results:?}");
+}